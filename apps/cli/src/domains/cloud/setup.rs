@@ -56,6 +56,8 @@ async fn add_cloud_interactive(ctx: &Context) -> Result<()> {
 			"Dropbox".to_string(),
 			"Azure Blob Storage".to_string(),
 			"Google Cloud Storage".to_string(),
+			"SFTP server".to_string(),
+			"WebDAV (Nextcloud, ownCloud, etc.)".to_string(),
 		],
 	)?;
 
@@ -66,6 +68,8 @@ async fn add_cloud_interactive(ctx: &Context) -> Result<()> {
 		3 => add_dropbox_interactive(ctx).await,
 		4 => add_azure_blob_interactive(ctx).await,
 		5 => add_gcs_interactive(ctx).await,
+		6 => add_sftp_interactive(ctx).await,
+		7 => add_webdav_interactive(ctx).await,
 		_ => unreachable!(),
 	}
 }
@@ -120,6 +124,8 @@ async fn add_s3_interactive(ctx: &Context) -> Result<()> {
 	let access_key = password("Access Key ID", false)?.unwrap();
 	let secret_key = password("Secret Access Key", false)?.unwrap();
 
+	let (encrypt, encrypt_names) = select_encryption()?;
+
 	println!("\nSummary:");
 	println!("  Provider: {:?}", service_type);
 	println!("  Name:     {}", name);
@@ -128,6 +134,7 @@ async fn add_s3_interactive(ctx: &Context) -> Result<()> {
 	if let Some(ref e) = endpoint {
 		println!("  Endpoint: {}", e);
 	}
+	print_encryption(encrypt, encrypt_names);
 	println!();
 
 	confirm_or_abort("Add this cloud volume?", false)?;
@@ -142,6 +149,8 @@ async fn add_s3_interactive(ctx: &Context) -> Result<()> {
 			secret_access_key: secret_key,
 			endpoint,
 		},
+		encrypt,
+		encrypt_names,
 	};
 
 	execute_add_cloud(ctx, input).await
@@ -162,12 +171,15 @@ async fn add_google_drive_interactive(ctx: &Context) -> Result<()> {
 	let access_token = password("Access Token", false)?.unwrap();
 	let refresh_token = password("Refresh Token", false)?.unwrap();
 
+	let (encrypt, encrypt_names) = select_encryption()?;
+
 	println!("\nSummary:");
 	println!("  Provider: Google Drive");
 	println!("  Name:     {}", name);
 	if let Some(ref r) = root {
 		println!("  Root:     {}", r);
 	}
+	print_encryption(encrypt, encrypt_names);
 	println!();
 
 	confirm_or_abort("Add this cloud volume?", false)?;
@@ -182,6 +194,8 @@ async fn add_google_drive_interactive(ctx: &Context) -> Result<()> {
 			client_id,
 			client_secret,
 		},
+		encrypt,
+		encrypt_names,
 	};
 
 	execute_add_cloud(ctx, input).await
@@ -202,12 +216,15 @@ async fn add_onedrive_interactive(ctx: &Context) -> Result<()> {
 	let access_token = password("Access Token", false)?.unwrap();
 	let refresh_token = password("Refresh Token", false)?.unwrap();
 
+	let (encrypt, encrypt_names) = select_encryption()?;
+
 	println!("\nSummary:");
 	println!("  Provider: Microsoft OneDrive");
 	println!("  Name:     {}", name);
 	if let Some(ref r) = root {
 		println!("  Root:     {}", r);
 	}
+	print_encryption(encrypt, encrypt_names);
 	println!();
 
 	confirm_or_abort("Add this cloud volume?", false)?;
@@ -222,6 +239,8 @@ async fn add_onedrive_interactive(ctx: &Context) -> Result<()> {
 			client_id,
 			client_secret,
 		},
+		encrypt,
+		encrypt_names,
 	};
 
 	execute_add_cloud(ctx, input).await
@@ -241,12 +260,15 @@ async fn add_dropbox_interactive(ctx: &Context) -> Result<()> {
 	println!("\nAfter completing OAuth flow, you'll receive a refresh token:");
 	let refresh_token = password("Refresh Token", false)?.unwrap();
 
+	let (encrypt, encrypt_names) = select_encryption()?;
+
 	println!("\nSummary:");
 	println!("  Provider: Dropbox");
 	println!("  Name:     {}", name);
 	if let Some(ref r) = root {
 		println!("  Root:     {}", r);
 	}
+	print_encryption(encrypt, encrypt_names);
 	println!();
 
 	confirm_or_abort("Add this cloud volume?", false)?;
@@ -260,6 +282,8 @@ async fn add_dropbox_interactive(ctx: &Context) -> Result<()> {
 			client_id,
 			client_secret,
 		},
+		encrypt,
+		encrypt_names,
 	};
 
 	execute_add_cloud(ctx, input).await
@@ -274,6 +298,8 @@ async fn add_azure_blob_interactive(ctx: &Context) -> Result<()> {
 	println!("\nCredentials will be stored securely in your system keyring\n");
 	let account_key = password("Storage account key", false)?.unwrap();
 
+	let (encrypt, encrypt_names) = select_encryption()?;
+
 	println!("\nSummary:");
 	println!("  Provider: Azure Blob Storage");
 	println!("  Name:      {}", name);
@@ -282,6 +308,7 @@ async fn add_azure_blob_interactive(ctx: &Context) -> Result<()> {
 	if let Some(ref e) = endpoint {
 		println!("  Endpoint:  {}", e);
 	}
+	print_encryption(encrypt, encrypt_names);
 	println!();
 
 	confirm_or_abort("Add this cloud volume?", false)?;
@@ -295,6 +322,8 @@ async fn add_azure_blob_interactive(ctx: &Context) -> Result<()> {
 			account_name,
 			account_key,
 		},
+		encrypt,
+		encrypt_names,
 	};
 
 	execute_add_cloud(ctx, input).await
@@ -314,6 +343,8 @@ async fn add_gcs_interactive(ctx: &Context) -> Result<()> {
 	let credential = std::fs::read_to_string(&service_account_path)
 		.map_err(|e| anyhow::anyhow!("Failed to read service account file: {}", e))?;
 
+	let (encrypt, encrypt_names) = select_encryption()?;
+
 	println!("\nSummary:");
 	println!("  Provider: Google Cloud Storage");
 	println!("  Name:     {}", name);
@@ -324,6 +355,7 @@ async fn add_gcs_interactive(ctx: &Context) -> Result<()> {
 	if let Some(ref e) = endpoint {
 		println!("  Endpoint: {}", e);
 	}
+	print_encryption(encrypt, encrypt_names);
 	println!();
 
 	confirm_or_abort("Add this cloud volume?", false)?;
@@ -337,11 +369,131 @@ async fn add_gcs_interactive(ctx: &Context) -> Result<()> {
 			endpoint,
 			credential,
 		},
+		encrypt,
+		encrypt_names,
+	};
+
+	execute_add_cloud(ctx, input).await
+}
+
+async fn add_sftp_interactive(ctx: &Context) -> Result<()> {
+	let name = text("Volume name (e.g., 'Home NAS')", false)?.unwrap();
+	let host = text("Host name or address", false)?.unwrap();
+	let port = text("Port (default: 22)", true)?
+		.map(|p| p.parse::<u16>())
+		.transpose()
+		.map_err(|e| anyhow::anyhow!("Invalid port: {}", e))?;
+	let username = text("Username", false)?.unwrap();
+	let root = text("Root path (leave empty for the home directory)", true)?;
+
+	println!("\nThe server must already be in your known_hosts file.");
+	let key_path = text(
+		"Path to SSH private key (leave empty to use the SSH agent)",
+		true,
+	)?;
+
+	let (encrypt, encrypt_names) = select_encryption()?;
+
+	println!("\nSummary:");
+	println!("  Provider: SFTP");
+	println!("  Name:     {}", name);
+	println!("  Server:   {}@{}:{}", username, host, port.unwrap_or(22));
+	if let Some(ref r) = root {
+		println!("  Root:     {}", r);
+	}
+	print_encryption(encrypt, encrypt_names);
+	println!();
+
+	confirm_or_abort("Add this cloud volume?", false)?;
+
+	let input = VolumeAddCloudInput {
+		service: CloudServiceType::Sftp,
+		display_name: name.clone(),
+		config: CloudStorageConfig::Sftp {
+			host,
+			port,
+			username,
+			key_path,
+			root,
+		},
+		encrypt,
+		encrypt_names,
+	};
+
+	execute_add_cloud(ctx, input).await
+}
+
+async fn add_webdav_interactive(ctx: &Context) -> Result<()> {
+	let name = text("Volume name (e.g., 'Nextcloud')", false)?.unwrap();
+
+	println!("\nFor Nextcloud the WebDAV URL looks like:");
+	println!("  https://cloud.example.com/remote.php/dav/files/<username>\n");
+
+	let endpoint = text("WebDAV URL", false)?.unwrap();
+	let username = text("Username", false)?.unwrap();
+	let root = text("Root path (leave empty for the whole share)", true)?;
+
+	println!("\nCredentials will be stored securely in your system keyring\n");
+
+	let password = password("Password or app password", false)?.unwrap();
+
+	let (encrypt, encrypt_names) = select_encryption()?;
+
+	println!("\nSummary:");
+	println!("  Provider: WebDAV");
+	println!("  Name:     {}", name);
+	println!("  URL:      {}", endpoint);
+	println!("  Username: {}", username);
+	if let Some(ref r) = root {
+		println!("  Root:     {}", r);
+	}
+	print_encryption(encrypt, encrypt_names);
+	println!();
+
+	confirm_or_abort("Add this cloud volume?", false)?;
+
+	let input = VolumeAddCloudInput {
+		service: CloudServiceType::WebDav,
+		display_name: name.clone(),
+		config: CloudStorageConfig::WebDav {
+			endpoint,
+			username,
+			password,
+			root,
+		},
+		encrypt,
+		encrypt_names,
 	};
 
 	execute_add_cloud(ctx, input).await
 }
 
+/// Ask whether files are encrypted on this device before they're uploaded
+fn select_encryption() -> Result<(bool, bool)> {
+	let choice = select(
+		"Client-side encryption",
+		&[
+			"None".to_string(),
+			"Encrypt file contents".to_string(),
+			"Encrypt file contents and names".to_string(),
+		],
+	)?;
+
+	if choice > 0 {
+		println!("\nThe key is kept on this device only, the files can't be read anywhere else");
+	}
+
+	Ok((choice > 0, choice == 2))
+}
+
+fn print_encryption(encrypt: bool, encrypt_names: bool) {
+	match (encrypt, encrypt_names) {
+		(true, true) => println!("  Encrypted: contents and names"),
+		(true, false) => println!("  Encrypted: contents"),
+		_ => {}
+	}
+}
+
 async fn execute_add_cloud(ctx: &Context, input: VolumeAddCloudInput) -> Result<()> {
 	print!("Connecting to cloud storage... ");
 	std::io::Write::flush(&mut std::io::stdout())?;
@@ -0,0 +1,106 @@
+use clap::Args;
+use uuid::Uuid;
+
+use sd_core::ops::collections::{
+	add_files::input::CollectionAddFilesInput, create::input::CollectionCreateInput,
+	delete::input::CollectionDeleteInput, get::query::CollectionGetQueryInput,
+	remove_files::input::CollectionRemoveFilesInput, rename::input::CollectionRenameInput,
+	reorder::input::CollectionReorderInput,
+};
+
+#[derive(Args, Debug)]
+pub struct CollectionCreateArgs {
+	/// Name of the collection
+	pub name: String,
+	/// Optional description
+	#[arg(long)]
+	pub description: Option<String>,
+}
+
+impl From<CollectionCreateArgs> for CollectionCreateInput {
+	fn from(args: CollectionCreateArgs) -> Self {
+		Self {
+			name: args.name,
+			description: args.description,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct CollectionRenameArgs {
+	pub collection_id: Uuid,
+	/// New name
+	pub name: String,
+}
+
+impl From<CollectionRenameArgs> for CollectionRenameInput {
+	fn from(args: CollectionRenameArgs) -> Self {
+		Self {
+			collection_id: args.collection_id,
+			name: args.name,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct CollectionDeleteArgs {
+	pub collection_id: Uuid,
+	#[arg(long, short = 'y', default_value_t = false)]
+	pub yes: bool,
+}
+
+impl From<CollectionDeleteArgs> for CollectionDeleteInput {
+	fn from(args: CollectionDeleteArgs) -> Self {
+		Self {
+			collection_id: args.collection_id,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct CollectionShowArgs {
+	pub collection_id: Uuid,
+}
+
+impl From<CollectionShowArgs> for CollectionGetQueryInput {
+	fn from(args: CollectionShowArgs) -> Self {
+		Self {
+			collection_id: args.collection_id,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct CollectionFilesArgs {
+	pub collection_id: Uuid,
+	/// File IDs (space-separated UUIDs)
+	#[arg(required = true)]
+	pub files: Vec<Uuid>,
+}
+
+impl From<CollectionFilesArgs> for CollectionAddFilesInput {
+	fn from(args: CollectionFilesArgs) -> Self {
+		Self {
+			collection_id: args.collection_id,
+			file_ids: args.files,
+		}
+	}
+}
+
+impl From<CollectionFilesArgs> for CollectionRemoveFilesInput {
+	fn from(args: CollectionFilesArgs) -> Self {
+		Self {
+			collection_id: args.collection_id,
+			file_ids: args.files,
+		}
+	}
+}
+
+impl From<CollectionFilesArgs> for CollectionReorderInput {
+	fn from(args: CollectionFilesArgs) -> Self {
+		Self {
+			collection_id: args.collection_id,
+			file_ids: args.files,
+		}
+	}
+}
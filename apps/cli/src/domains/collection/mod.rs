@@ -0,0 +1,124 @@
+mod args;
+
+use anyhow::Result;
+use clap::Subcommand;
+
+use crate::context::Context;
+use crate::util::prelude::*;
+
+use sd_core::ops::collections::{
+	add_files::output::CollectionAddFilesOutput, create::output::CollectionCreateOutput,
+	delete::output::CollectionDeleteOutput, get::output::CollectionGetOutput,
+	list::output::CollectionsListOutput, list::query::CollectionsListQueryInput,
+	remove_files::output::CollectionRemoveFilesOutput, rename::output::CollectionRenameOutput,
+	reorder::output::CollectionReorderOutput,
+};
+
+use self::args::*;
+
+#[derive(Subcommand, Debug)]
+pub enum CollectionCmd {
+	/// Create a new collection
+	Create(CollectionCreateArgs),
+	/// Rename a collection
+	Rename(CollectionRenameArgs),
+	/// Delete a collection (files are not touched)
+	Delete(CollectionDeleteArgs),
+	/// List collections
+	List,
+	/// Show a collection's files in order
+	Show(CollectionShowArgs),
+	/// Add files to the end of a collection
+	Add(CollectionFilesArgs),
+	/// Remove files from a collection
+	Remove(CollectionFilesArgs),
+	/// Move files to the front of a collection, in the given order
+	Reorder(CollectionFilesArgs),
+}
+
+pub async fn run(ctx: &Context, cmd: CollectionCmd) -> Result<()> {
+	match cmd {
+		CollectionCmd::Create(args) => {
+			let input: sd_core::ops::collections::create::input::CollectionCreateInput =
+				args.into();
+			let out: CollectionCreateOutput = execute_action!(ctx, input);
+			print_output!(ctx, &out, |o: &CollectionCreateOutput| {
+				println!("{} (id: {})", o.collection.name, o.collection.id);
+			});
+		}
+		CollectionCmd::Rename(args) => {
+			let input: sd_core::ops::collections::rename::input::CollectionRenameInput =
+				args.into();
+			let out: CollectionRenameOutput = execute_action!(ctx, input);
+			print_output!(ctx, &out, |o: &CollectionRenameOutput| {
+				println!("Renamed collection to {}", o.collection.name);
+			});
+		}
+		CollectionCmd::Delete(args) => {
+			confirm_or_abort(
+				&format!(
+					"This will delete collection {}. Its files are not affected. Continue?",
+					args.collection_id
+				),
+				args.yes,
+			)?;
+			let collection_id = args.collection_id;
+			let input: sd_core::ops::collections::delete::input::CollectionDeleteInput =
+				args.into();
+			let out: CollectionDeleteOutput = execute_action!(ctx, input);
+			print_output!(ctx, &out, |_: &CollectionDeleteOutput| {
+				println!("Deleted collection {}", collection_id);
+			});
+		}
+		CollectionCmd::List => {
+			let out: CollectionsListOutput = execute_query!(ctx, CollectionsListQueryInput);
+			print_output!(ctx, &out, |o: &CollectionsListOutput| {
+				if o.collections.is_empty() {
+					println!("No collections found");
+					return;
+				}
+				for c in &o.collections {
+					println!("{} {} ({} files)", c.id, c.name, c.file_count);
+				}
+			});
+		}
+		CollectionCmd::Show(args) => {
+			let input: sd_core::ops::collections::get::query::CollectionGetQueryInput = args.into();
+			let out: CollectionGetOutput = execute_query!(ctx, input);
+			print_output!(ctx, &out, |o: &CollectionGetOutput| {
+				println!("{} ({} files)", o.collection.name, o.collection.file_count);
+				if let Some(description) = &o.collection.description {
+					println!("{}", description);
+				}
+				for f in &o.files {
+					println!("  {:>3}. {} {}", f.position + 1, f.file.id, f.file.name);
+				}
+			});
+		}
+		CollectionCmd::Add(args) => {
+			let input: sd_core::ops::collections::add_files::input::CollectionAddFilesInput =
+				args.into();
+			let out: CollectionAddFilesOutput = execute_action!(ctx, input);
+			print_output!(ctx, &out, |o: &CollectionAddFilesOutput| {
+				println!("Added {} file(s) to {}", o.added, o.collection.name);
+			});
+		}
+		CollectionCmd::Remove(args) => {
+			let input: sd_core::ops::collections::remove_files::input::CollectionRemoveFilesInput =
+				args.into();
+			let out: CollectionRemoveFilesOutput = execute_action!(ctx, input);
+			print_output!(ctx, &out, |o: &CollectionRemoveFilesOutput| {
+				println!("Removed {} file(s) from {}", o.removed, o.collection.name);
+			});
+		}
+		CollectionCmd::Reorder(args) => {
+			let input: sd_core::ops::collections::reorder::input::CollectionReorderInput =
+				args.into();
+			let out: CollectionReorderOutput = execute_action!(ctx, input);
+			print_output!(ctx, &out, |o: &CollectionReorderOutput| {
+				println!("Reordered {}", o.collection.name);
+			});
+		}
+	}
+	Ok(())
+}
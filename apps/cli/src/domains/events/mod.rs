@@ -439,6 +439,20 @@ fn summarize_event(event: &Event) -> String {
 			format!("Sync error: {}", message)
 		}
 
+		// File sync events
+		Event::FileSyncConflictsDetected {
+			conduit_id,
+			generation,
+			detected,
+			pending,
+			..
+		} => {
+			format!(
+				"File sync {} generation {} found {} conflict(s), {} waiting for a decision",
+				conduit_id, generation, detected, pending
+			)
+		}
+
 		// Proxy pairing events
 		Event::ProxyPairingConfirmationRequired {
 			vouchee_device_name,
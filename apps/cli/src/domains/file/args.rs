@@ -3,7 +3,10 @@ use std::path::PathBuf;
 
 use sd_core::{
 	domain::addressing::{SdPath, SdPathBatch},
-	ops::files::copy::input::{CopyMethod, FileCopyInput},
+	ops::files::{
+		copy::input::{CopyMethod, FileCopyInput},
+		encryption::{FileDecryptInput, FileEncryptInput},
+	},
 };
 
 #[derive(Args, Debug, Clone)]
@@ -80,3 +83,46 @@ pub struct FileListArgs {
 	#[arg(long, default_value = "name")]
 	pub sort_by: String,
 }
+
+#[derive(Args, Debug, Clone)]
+pub struct FileEncryptArgs {
+	/// Files to encrypt, each one is replaced by <name>.sdenc
+	#[arg(required = true)]
+	pub paths: Vec<PathBuf>,
+
+	/// Overwrite the originals with random data before removing them
+	#[arg(long, default_value_t = false)]
+	pub secure_erase: bool,
+}
+
+impl FileEncryptArgs {
+	pub fn to_input(&self, password: String) -> FileEncryptInput {
+		FileEncryptInput {
+			targets: local_batch(&self.paths),
+			password,
+			secure_erase: self.secure_erase,
+		}
+	}
+}
+
+#[derive(Args, Debug, Clone)]
+pub struct FileDecryptArgs {
+	/// .sdenc files to decrypt, each one is replaced by the original file
+	#[arg(required = true)]
+	pub paths: Vec<PathBuf>,
+}
+
+impl FileDecryptArgs {
+	pub fn to_input(&self, password: String) -> FileDecryptInput {
+		FileDecryptInput {
+			targets: local_batch(&self.paths),
+			password,
+		}
+	}
+}
+
+fn local_batch(paths: &[PathBuf]) -> SdPathBatch {
+	SdPathBatch {
+		paths: paths.iter().map(|p| SdPath::local(p.clone())).collect(),
+	}
+}
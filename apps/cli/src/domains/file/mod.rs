@@ -21,6 +21,10 @@ pub enum FileCmd {
 	Info(FileInfoArgs),
 	/// List directory contents
 	List(FileListArgs),
+	/// Encrypt files in place with a password
+	Encrypt(FileEncryptArgs),
+	/// Decrypt files encrypted with `file encrypt`
+	Decrypt(FileDecryptArgs),
 }
 
 pub async fn run(ctx: &Context, cmd: FileCmd) -> Result<()> {
@@ -102,6 +106,31 @@ pub async fn run(ctx: &Context, cmd: FileCmd) -> Result<()> {
 				}
 			);
 		}
+		FileCmd::Encrypt(args) => {
+			use crate::util::confirm::password;
+
+			let first = password("Password", false)?.unwrap_or_default();
+			let second = password("Repeat password", false)?.unwrap_or_default();
+			if first != second {
+				anyhow::bail!("Passwords don't match");
+			}
+
+			let input = args.to_input(first);
+			let job_id: JobId = execute_action!(ctx, input);
+			print_output!(ctx, &job_id, |id: &JobId| {
+				println!("Dispatched encryption job {}", id);
+				println!("The password can't be recovered, keep it somewhere safe");
+			});
+		}
+		FileCmd::Decrypt(args) => {
+			let password = crate::util::confirm::password("Password", false)?.unwrap_or_default();
+
+			let input = args.to_input(password);
+			let job_id: JobId = execute_action!(ctx, input);
+			print_output!(ctx, &job_id, |id: &JobId| {
+				println!("Dispatched decryption job {}", id);
+			});
+		}
 	}
 	Ok(())
 }
@@ -0,0 +1,290 @@
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use sd_core::{
+	domain::addressing::SdPath,
+	infra::db::entities::{sync_conduit::SyncMode, sync_conflict::Resolution},
+	ops::file_sync::{
+		FileSyncConflictsInput, FileSyncCreateInput, FileSyncDeleteInput, FileSyncGenerationsInput,
+		FileSyncNowInput, FileSyncResolveConflictInput, FileSyncSetEnabledInput,
+		FileSyncStatusInput, FileSyncUpdateInput,
+	},
+	service::file_sync::conflict::ConflictStrategy,
+};
+
+#[derive(Subcommand, Debug)]
+pub enum FileSyncCmd {
+	/// Keep a target directory in sync with a source directory
+	Create(FileSyncCreateArgs),
+	/// List sync conduits
+	List,
+	/// Show a conduit and its latest sync
+	Status(FileSyncConduitArgs),
+	/// Change the settings of a conduit
+	Update(FileSyncUpdateArgs),
+	/// Allow a conduit to sync again
+	Enable(FileSyncConduitArgs),
+	/// Stop a conduit from syncing, keeping its history
+	Disable(FileSyncConduitArgs),
+	/// Delete a conduit, files on both sides are kept
+	Delete(FileSyncDeleteArgs),
+	/// Sync a conduit now
+	Run(FileSyncConduitArgs),
+	/// Show past syncs of a conduit
+	History(FileSyncHistoryArgs),
+	/// Show files changed on both sides of a bidirectional conduit
+	Conflicts(FileSyncConflictsArgs),
+	/// Decide which side of a conflict to keep, applied by the next sync
+	Resolve(FileSyncResolveArgs),
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy)]
+pub enum SyncModeArg {
+	/// One-way, the target becomes an exact copy of the source
+	Mirror,
+	/// Two-way, changes on either side are synced to the other
+	Bidirectional,
+}
+
+impl From<SyncModeArg> for SyncMode {
+	fn from(mode: SyncModeArg) -> Self {
+		match mode {
+			SyncModeArg::Mirror => SyncMode::Mirror,
+			SyncModeArg::Bidirectional => SyncMode::Bidirectional,
+		}
+	}
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy)]
+pub enum ConflictStrategyArg {
+	/// Keep the most recently modified version
+	NewestWins,
+	/// The source side always wins
+	SourceWins,
+	/// The target side always wins
+	TargetWins,
+	/// Keep both, the source version is saved as a conflict copy
+	ConflictFile,
+	/// Ask, see `sd file-sync conflicts`
+	Prompt,
+}
+
+impl From<ConflictStrategyArg> for ConflictStrategy {
+	fn from(strategy: ConflictStrategyArg) -> Self {
+		match strategy {
+			ConflictStrategyArg::NewestWins => ConflictStrategy::NewestWins,
+			ConflictStrategyArg::SourceWins => ConflictStrategy::SourceWins,
+			ConflictStrategyArg::TargetWins => ConflictStrategy::TargetWins,
+			ConflictStrategyArg::ConflictFile => ConflictStrategy::CreateConflictFile,
+			ConflictStrategyArg::Prompt => ConflictStrategy::PromptUser,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct FileSyncCreateArgs {
+	/// Indexed directory to sync from
+	pub source: PathBuf,
+
+	/// Indexed directory to sync to
+	pub target: PathBuf,
+
+	#[arg(long, value_enum, default_value = "mirror")]
+	pub mode: SyncModeArg,
+
+	/// Only "manual" is supported, syncs are started with `run`
+	#[arg(long, default_value = "manual")]
+	pub schedule: String,
+
+	/// How files changed on both sides are settled
+	#[arg(long, value_enum, default_value = "newest-wins")]
+	pub conflict_strategy: ConflictStrategyArg,
+
+	/// Number of files transferred at once
+	#[arg(long)]
+	pub parallel: Option<i32>,
+
+	/// Bandwidth limit in MB/s
+	#[arg(long)]
+	pub bandwidth: Option<i32>,
+}
+
+impl From<FileSyncCreateArgs> for FileSyncCreateInput {
+	fn from(args: FileSyncCreateArgs) -> Self {
+		Self {
+			source: SdPath::local(args.source),
+			target: SdPath::local(args.target),
+			mode: args.mode.into(),
+			schedule: args.schedule,
+			conflict_strategy: args.conflict_strategy.into(),
+			parallel_transfers: args.parallel,
+			bandwidth_limit_mbps: args.bandwidth,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct FileSyncUpdateArgs {
+	/// Conduit ID
+	pub conduit_id: Uuid,
+
+	#[arg(long, value_enum)]
+	pub mode: Option<SyncModeArg>,
+
+	/// Only "manual" is supported
+	#[arg(long)]
+	pub schedule: Option<String>,
+
+	/// How files changed on both sides are settled
+	#[arg(long, value_enum)]
+	pub conflict_strategy: Option<ConflictStrategyArg>,
+
+	/// Apply the locations' indexer rules to synced files
+	#[arg(long)]
+	pub use_index_rules: Option<bool>,
+
+	/// Number of files transferred at once
+	#[arg(long)]
+	pub parallel: Option<i32>,
+
+	/// Bandwidth limit in MB/s, 0 removes the limit
+	#[arg(long)]
+	pub bandwidth: Option<i32>,
+}
+
+impl From<FileSyncUpdateArgs> for FileSyncUpdateInput {
+	fn from(args: FileSyncUpdateArgs) -> Self {
+		Self {
+			conduit_id: args.conduit_id,
+			mode: args.mode.map(Into::into),
+			schedule: args.schedule,
+			conflict_strategy: args.conflict_strategy.map(Into::into),
+			use_index_rules: args.use_index_rules,
+			parallel_transfers: args.parallel,
+			bandwidth_limit_mbps: args.bandwidth,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct FileSyncConduitArgs {
+	/// Conduit ID
+	pub conduit_id: Uuid,
+}
+
+impl FileSyncConduitArgs {
+	pub fn to_status_input(&self) -> FileSyncStatusInput {
+		FileSyncStatusInput {
+			conduit_id: self.conduit_id,
+		}
+	}
+
+	pub fn to_set_enabled_input(&self, enabled: bool) -> FileSyncSetEnabledInput {
+		FileSyncSetEnabledInput {
+			conduit_id: self.conduit_id,
+			enabled,
+		}
+	}
+
+	pub fn to_sync_now_input(&self) -> FileSyncNowInput {
+		FileSyncNowInput {
+			conduit_id: self.conduit_id,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct FileSyncDeleteArgs {
+	/// Conduit ID
+	pub conduit_id: Uuid,
+
+	/// Skip confirmation
+	#[arg(long, short = 'y', default_value_t = false)]
+	pub yes: bool,
+}
+
+impl From<&FileSyncDeleteArgs> for FileSyncDeleteInput {
+	fn from(args: &FileSyncDeleteArgs) -> Self {
+		Self {
+			conduit_id: args.conduit_id,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct FileSyncHistoryArgs {
+	/// Conduit ID
+	pub conduit_id: Uuid,
+
+	/// Number of syncs to show
+	#[arg(long, default_value_t = 20)]
+	pub limit: u64,
+}
+
+impl From<FileSyncHistoryArgs> for FileSyncGenerationsInput {
+	fn from(args: FileSyncHistoryArgs) -> Self {
+		Self {
+			conduit_id: args.conduit_id,
+			limit: Some(args.limit),
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct FileSyncConflictsArgs {
+	/// Conduit ID
+	pub conduit_id: Uuid,
+
+	/// Also show conflicts that already have a decision
+	#[arg(long, default_value_t = false)]
+	pub all: bool,
+}
+
+impl From<FileSyncConflictsArgs> for FileSyncConflictsInput {
+	fn from(args: FileSyncConflictsArgs) -> Self {
+		Self {
+			conduit_id: args.conduit_id,
+			include_resolved: args.all,
+		}
+	}
+}
+
+#[derive(clap::ValueEnum, Debug, Clone, Copy)]
+pub enum ResolutionArg {
+	/// Overwrite the target with the source version
+	Source,
+	/// Overwrite the source with the target version
+	Target,
+	/// Keep the target and save the source version as a conflict copy
+	Both,
+}
+
+impl From<ResolutionArg> for Resolution {
+	fn from(resolution: ResolutionArg) -> Self {
+		match resolution {
+			ResolutionArg::Source => Resolution::KeepSource,
+			ResolutionArg::Target => Resolution::KeepTarget,
+			ResolutionArg::Both => Resolution::KeepBoth,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct FileSyncResolveArgs {
+	/// Conflict ID, as shown by `sd file-sync conflicts`
+	pub conflict_id: Uuid,
+
+	/// Which version to keep
+	#[arg(long, value_enum)]
+	pub keep: ResolutionArg,
+}
+
+impl From<FileSyncResolveArgs> for FileSyncResolveConflictInput {
+	fn from(args: FileSyncResolveArgs) -> Self {
+		Self {
+			conflict_id: args.conflict_id,
+			resolution: args.keep.into(),
+		}
+	}
+}
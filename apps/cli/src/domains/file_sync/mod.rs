@@ -0,0 +1,209 @@
+mod args;
+
+use anyhow::Result;
+
+use crate::util::prelude::*;
+
+use crate::context::Context;
+use sd_core::ops::file_sync::{
+	FileSyncConduit, FileSyncConflict, FileSyncConflictSide, FileSyncConflictsOutput,
+	FileSyncDeleteOutput, FileSyncGeneration, FileSyncGenerationsOutput, FileSyncListInput,
+	FileSyncListOutput, FileSyncNowOutput, FileSyncStatusOutput,
+};
+
+pub use self::args::FileSyncCmd;
+use self::args::*;
+
+pub async fn run(ctx: &Context, cmd: FileSyncCmd) -> Result<()> {
+	match cmd {
+		FileSyncCmd::Create(args) => {
+			let input: sd_core::ops::file_sync::FileSyncCreateInput = args.into();
+			let out: FileSyncConduit = execute_action!(ctx, input);
+			print_output!(ctx, &out, |c: &FileSyncConduit| {
+				println!("Created conduit {}", c.id);
+				print_conduit(c);
+			});
+		}
+		FileSyncCmd::List => {
+			let out: FileSyncListOutput = execute_query!(ctx, FileSyncListInput {});
+			print_output!(ctx, &out, |o: &FileSyncListOutput| {
+				if o.conduits.is_empty() {
+					println!("No sync conduits");
+				}
+				for c in &o.conduits {
+					println!("- {}", c.id);
+					print_conduit(c);
+				}
+			});
+		}
+		FileSyncCmd::Status(args) => {
+			let out: FileSyncStatusOutput = execute_query!(ctx, args.to_status_input());
+			print_output!(ctx, &out, |o: &FileSyncStatusOutput| {
+				println!("Conduit {}", o.conduit.id);
+				print_conduit(&o.conduit);
+				println!(
+					"    syncs: {} ({} files, {} bytes)",
+					o.conduit.total_syncs, o.conduit.files_synced, o.conduit.bytes_transferred
+				);
+				match &o.latest_generation {
+					Some(generation) => print_generation(generation),
+					None => println!("    never synced"),
+				}
+			});
+		}
+		FileSyncCmd::Update(args) => {
+			let input: sd_core::ops::file_sync::FileSyncUpdateInput = args.into();
+			let out: FileSyncConduit = execute_action!(ctx, input);
+			print_output!(ctx, &out, |c: &FileSyncConduit| {
+				println!("Updated conduit {}", c.id);
+				print_conduit(c);
+			});
+		}
+		FileSyncCmd::Enable(args) => {
+			let out: FileSyncConduit = execute_action!(ctx, args.to_set_enabled_input(true));
+			print_output!(ctx, &out, |c: &FileSyncConduit| {
+				println!("Conduit {} enabled", c.id);
+			});
+		}
+		FileSyncCmd::Disable(args) => {
+			let out: FileSyncConduit = execute_action!(ctx, args.to_set_enabled_input(false));
+			print_output!(ctx, &out, |c: &FileSyncConduit| {
+				println!("Conduit {} disabled", c.id);
+			});
+		}
+		FileSyncCmd::Delete(args) => {
+			confirm_or_abort(
+				&format!(
+					"This will delete sync conduit {} and its history. Files are kept. Continue?",
+					args.conduit_id
+				),
+				args.yes,
+			)?;
+			let input: sd_core::ops::file_sync::FileSyncDeleteInput = (&args).into();
+			let out: FileSyncDeleteOutput = execute_action!(ctx, input);
+			print_output!(ctx, &out, |o: &FileSyncDeleteOutput| {
+				println!("Conduit {} deleted", o.conduit_id);
+			});
+		}
+		FileSyncCmd::Run(args) => {
+			let out: FileSyncNowOutput = execute_action!(ctx, args.to_sync_now_input());
+			print_output!(ctx, &out, |o: &FileSyncNowOutput| {
+				if o.job_ids.is_empty() {
+					println!("Conduit {} is already in sync", o.conduit_id);
+					return;
+				}
+				println!("Sync {} of conduit {} started", o.generation, o.conduit_id);
+				for job_id in &o.job_ids {
+					println!("    job {}", job_id);
+				}
+			});
+		}
+		FileSyncCmd::History(args) => {
+			let input: sd_core::ops::file_sync::FileSyncGenerationsInput = args.into();
+			let out: FileSyncGenerationsOutput = execute_query!(ctx, input);
+			print_output!(ctx, &out, |o: &FileSyncGenerationsOutput| {
+				if o.generations.is_empty() {
+					println!("No syncs yet");
+				}
+				for generation in &o.generations {
+					print_generation(generation);
+				}
+			});
+		}
+		FileSyncCmd::Conflicts(args) => {
+			let input: sd_core::ops::file_sync::FileSyncConflictsInput = args.into();
+			let out: FileSyncConflictsOutput = execute_query!(ctx, input);
+			print_output!(ctx, &out, |o: &FileSyncConflictsOutput| {
+				if o.conflicts.is_empty() {
+					println!("No conflicts");
+				}
+				for conflict in &o.conflicts {
+					print_conflict(conflict);
+				}
+			});
+		}
+		FileSyncCmd::Resolve(args) => {
+			let input: sd_core::ops::file_sync::FileSyncResolveConflictInput = args.into();
+			let out: FileSyncConflict = execute_action!(ctx, input);
+			print_output!(ctx, &out, |c: &FileSyncConflict| {
+				println!(
+					"Conflict on {} resolved, the next sync applies it",
+					c.path.display()
+				);
+			});
+		}
+	}
+	Ok(())
+}
+
+fn print_conduit(c: &FileSyncConduit) {
+	println!(
+		"    {} -> {} [{}, {}, {}]{}{}",
+		c.source_path.display(),
+		c.target_path.display(),
+		c.mode,
+		c.schedule,
+		c.conflict_strategy.as_str(),
+		if c.enabled { "" } else { " (disabled)" },
+		if c.syncing { " (syncing)" } else { "" }
+	);
+	if let Some(completed) = c.last_sync_completed_at {
+		println!("    last sync: {}", completed.with_timezone(&chrono::Local));
+	}
+	if let Some(error) = &c.last_sync_error {
+		println!("    last error: {}", error);
+	}
+}
+
+fn print_generation(g: &FileSyncGeneration) {
+	let state = match g.completed_at {
+		Some(completed) => format!("completed {}", completed.with_timezone(&chrono::Local)),
+		None => "running".to_string(),
+	};
+	println!(
+		"    #{} started {}, {}: {} copied, {} deleted, {} errors, {}",
+		g.generation,
+		g.started_at.with_timezone(&chrono::Local),
+		state,
+		g.files_copied,
+		g.files_deleted,
+		g.errors_encountered,
+		g.verification_status
+	);
+}
+
+fn print_conflict(c: &FileSyncConflict) {
+	let state = match (c.resolution, c.applied_at) {
+		(None, _) => "waiting for a decision".to_string(),
+		(Some(resolution), None) => format!("{}, applied by the next sync", resolution),
+		(Some(resolution), Some(applied)) => format!(
+			"{}, applied {}",
+			resolution,
+			applied.with_timezone(&chrono::Local)
+		),
+	};
+	println!(
+		"- {} {} ({:?}): {}",
+		c.id,
+		c.path.display(),
+		c.conflict_type,
+		state
+	);
+	print_conflict_side("source", &c.source);
+	print_conflict_side("target", &c.target);
+}
+
+fn print_conflict_side(side: &str, s: &FileSyncConflictSide) {
+	let path = s
+		.path
+		.as_ref()
+		.map(|path| path.display().to_string())
+		.unwrap_or_else(|| "no longer indexed".to_string());
+	println!(
+		"    {}: {}, {} bytes, modified {}",
+		side,
+		path,
+		s.size,
+		s.modified_at.with_timezone(&chrono::Local)
+	);
+}
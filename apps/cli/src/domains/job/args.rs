@@ -1,9 +1,17 @@
-use clap::Args;
+use anyhow::{anyhow, Result};
+use clap::{ArgGroup, Args, Subcommand};
 use uuid::Uuid;
 
 use sd_core::{
-	infra::job::types::JobStatus,
-	ops::jobs::{info::query::JobInfoQueryInput, list::query::JobListInput},
+	infra::job::{schedule::ScheduleTrigger, types::JobStatus},
+	ops::jobs::{
+		info::query::JobInfoQueryInput,
+		list::query::JobListInput,
+		schedule::{
+			create::JobScheduleCreateInput, delete::JobScheduleDeleteInput,
+			pause::JobSchedulePauseInput, resume::JobScheduleResumeInput,
+		},
+	},
 };
 
 #[derive(Args, Debug)]
@@ -60,3 +68,129 @@ pub struct JobControlArgs {
 	/// Job ID to control
 	pub job_id: Uuid,
 }
+
+#[derive(Subcommand, Debug)]
+pub enum ScheduleCmd {
+	/// Create a recurring schedule for a job
+	Create(ScheduleCreateArgs),
+	/// List schedules
+	List,
+	/// Pause a schedule
+	Pause(ScheduleControlArgs),
+	/// Resume a paused schedule
+	Resume(ScheduleControlArgs),
+	/// Delete a schedule
+	Delete(ScheduleControlArgs),
+}
+
+#[derive(Args, Debug)]
+#[command(group(ArgGroup::new("trigger").required(true).args(["cron", "every", "on_idle"])))]
+pub struct ScheduleCreateArgs {
+	/// Schedule name
+	pub name: String,
+
+	/// Job type to run (see the job schemas, e.g. "indexer")
+	#[arg(long)]
+	pub job: String,
+
+	/// Job arguments as JSON
+	#[arg(long, default_value = "{}")]
+	pub args: String,
+
+	/// Cron expression in local time, e.g. "0 2 * * *" or "@daily"
+	#[arg(long)]
+	pub cron: Option<String>,
+
+	/// Run at a fixed interval, e.g. "90s", "30m", "1h" or "7d"
+	#[arg(long, value_parser = parse_duration)]
+	pub every: Option<u64>,
+
+	/// Run once the device has been idle this long, e.g. "10m"
+	#[arg(long, value_parser = parse_duration)]
+	pub on_idle: Option<u64>,
+
+	/// Minimum time between idle runs
+	#[arg(long, value_parser = parse_duration, default_value = "1d", requires = "on_idle")]
+	pub min_interval: u64,
+
+	/// Skip runs missed while the daemon was down instead of running once on startup
+	#[arg(long)]
+	pub no_catch_up: bool,
+}
+
+impl ScheduleCreateArgs {
+	pub fn to_input(&self) -> Result<JobScheduleCreateInput> {
+		let trigger = match (&self.cron, self.every, self.on_idle) {
+			(Some(expression), _, _) => ScheduleTrigger::Cron {
+				expression: expression.clone(),
+			},
+			(_, Some(seconds), _) => ScheduleTrigger::Interval { seconds },
+			(_, _, Some(idle_seconds)) => ScheduleTrigger::OnIdle {
+				idle_seconds,
+				min_interval_seconds: self.min_interval,
+			},
+			_ => return Err(anyhow!("One of --cron, --every or --on-idle is required")),
+		};
+
+		Ok(JobScheduleCreateInput {
+			name: self.name.clone(),
+			job_name: self.job.clone(),
+			job_args: serde_json::from_str(&self.args)
+				.map_err(|e| anyhow!("Invalid --args JSON: {}", e))?,
+			trigger,
+			catch_up: !self.no_catch_up,
+		})
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct ScheduleControlArgs {
+	/// Schedule ID
+	pub schedule_id: Uuid,
+}
+
+impl ScheduleControlArgs {
+	pub fn to_pause_input(&self) -> JobSchedulePauseInput {
+		JobSchedulePauseInput {
+			schedule_id: self.schedule_id,
+		}
+	}
+
+	pub fn to_resume_input(&self) -> JobScheduleResumeInput {
+		JobScheduleResumeInput {
+			schedule_id: self.schedule_id,
+		}
+	}
+
+	pub fn to_delete_input(&self) -> JobScheduleDeleteInput {
+		JobScheduleDeleteInput {
+			schedule_id: self.schedule_id,
+		}
+	}
+}
+
+/// Parse "45", "90s", "30m", "12h" or "7d" into seconds
+fn parse_duration(s: &str) -> Result<u64, String> {
+	let s = s.trim();
+	let (number, unit) = s
+		.find(|c: char| !c.is_ascii_digit())
+		.map_or((s, ""), |index| s.split_at(index));
+
+	let multiplier = match unit {
+		"" | "s" => 1,
+		"m" => 60,
+		"h" => 60 * 60,
+		"d" => 24 * 60 * 60,
+		_ => {
+			return Err(format!(
+				"Unknown duration unit '{}', use s, m, h or d",
+				unit
+			))
+		}
+	};
+
+	number
+		.parse::<u64>()
+		.map(|value| value * multiplier)
+		.map_err(|_| format!("Invalid duration '{}'", s))
+}
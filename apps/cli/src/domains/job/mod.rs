@@ -7,6 +7,7 @@ use crate::ui::create_simple_progress;
 use crate::util::prelude::*;
 
 use crate::context::Context;
+use sd_core::infra::job::schedule::{JobSchedule, ScheduleTrigger};
 use sd_core::ops::{
 	jobs::{
 		control::{
@@ -16,6 +17,10 @@ use sd_core::ops::{
 		},
 		info::output::JobInfoOutput,
 		list::output::JobListOutput,
+		schedule::{
+			delete::JobScheduleDeleteOutput,
+			list::{JobScheduleListInput, JobScheduleListOutput},
+		},
 	},
 	libraries::list::query::ListLibrariesQuery,
 };
@@ -36,6 +41,9 @@ pub enum JobCmd {
 	Resume(JobControlArgs),
 	/// Cancel a job
 	Cancel(JobControlArgs),
+	/// Recurring job schedules
+	#[command(subcommand)]
+	Schedule(ScheduleCmd),
 }
 
 pub async fn run(ctx: &Context, cmd: JobCmd) -> Result<()> {
@@ -128,10 +136,92 @@ pub async fn run(ctx: &Context, cmd: JobCmd) -> Result<()> {
 				}
 			});
 		}
+		JobCmd::Schedule(cmd) => run_schedule(ctx, cmd).await?,
 	}
 	Ok(())
 }
 
+async fn run_schedule(ctx: &Context, cmd: ScheduleCmd) -> Result<()> {
+	match cmd {
+		ScheduleCmd::Create(args) => {
+			let input = args.to_input()?;
+			let out: JobSchedule = execute_action!(ctx, input);
+			print_output!(ctx, &out, |s: &JobSchedule| {
+				println!("Created schedule '{}' ({})", s.name, s.id);
+				print_next_run(s);
+			});
+		}
+		ScheduleCmd::List => {
+			let out: JobScheduleListOutput = execute_query!(ctx, JobScheduleListInput {});
+			print_output!(ctx, &out, |o: &JobScheduleListOutput| {
+				if o.schedules.is_empty() {
+					println!("No schedules");
+				}
+				for s in &o.schedules {
+					println!(
+						"- {} {} [{}] {} {}",
+						s.id,
+						s.name,
+						s.job_name,
+						describe_trigger(&s.trigger),
+						if s.paused { "(paused)" } else { "" }
+					);
+					if let Some(last_run) = s.last_run_at {
+						println!("    last run: {}", last_run.with_timezone(&chrono::Local));
+					}
+					if let Some(error) = &s.last_error {
+						println!("    last error: {}", error);
+					}
+					if !s.paused {
+						print_next_run(s);
+					}
+				}
+			});
+		}
+		ScheduleCmd::Pause(args) => {
+			let out: JobSchedule = execute_action!(ctx, args.to_pause_input());
+			print_output!(ctx, &out, |s: &JobSchedule| {
+				println!("Schedule '{}' paused", s.name);
+			});
+		}
+		ScheduleCmd::Resume(args) => {
+			let out: JobSchedule = execute_action!(ctx, args.to_resume_input());
+			print_output!(ctx, &out, |s: &JobSchedule| {
+				println!("Schedule '{}' resumed", s.name);
+				print_next_run(s);
+			});
+		}
+		ScheduleCmd::Delete(args) => {
+			let out: JobScheduleDeleteOutput = execute_action!(ctx, args.to_delete_input());
+			print_output!(ctx, &out, |o: &JobScheduleDeleteOutput| {
+				println!("Schedule {} deleted", o.schedule_id);
+			});
+		}
+	}
+	Ok(())
+}
+
+fn describe_trigger(trigger: &ScheduleTrigger) -> String {
+	match trigger {
+		ScheduleTrigger::Cron { expression } => format!("cron '{}'", expression),
+		ScheduleTrigger::Interval { seconds } => format!("every {}s", seconds),
+		ScheduleTrigger::OnIdle {
+			idle_seconds,
+			min_interval_seconds,
+		} => format!(
+			"after {}s idle, at most every {}s",
+			idle_seconds, min_interval_seconds
+		),
+	}
+}
+
+fn print_next_run(schedule: &JobSchedule) {
+	match schedule.next_run_at {
+		Some(next_run) => println!("    next run: {}", next_run.with_timezone(&chrono::Local)),
+		None => println!("    next run: never"),
+	}
+}
+
 /// Run the job monitor with either TUI or simple progress bars
 async fn run_job_monitor(ctx: &Context, args: JobMonitorArgs) -> Result<()> {
 	use std::time::Duration;
@@ -52,6 +52,7 @@ pub async fn run(ctx: &Context, cmd: LocationCmd) -> Result<()> {
 					name: args.name,
 					mode,
 					job_policies: None,
+					indexer_limits: None,
 				}
 			};
 
@@ -249,5 +250,6 @@ async fn run_interactive_add(ctx: &Context) -> Result<LocationAddInput> {
 		name,
 		mode,
 		job_policies: None,
+		indexer_limits: None,
 	})
 }
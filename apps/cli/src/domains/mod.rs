@@ -1,9 +1,11 @@
 pub mod cloud;
+pub mod collection;
 pub mod config;
 pub mod daemon;
 pub mod devices;
 pub mod events;
 pub mod file;
+pub mod file_sync;
 pub mod index;
 pub mod job;
 pub mod library;
@@ -7,10 +7,14 @@ use sd_core::ops::search::input::{
 	DateField, DateRangeFilter, FileSearchInput, PaginationOptions, SearchFilters, SearchMode,
 	SearchScope, SizeRangeFilter, SortDirection, SortField, SortOptions, TagFilter,
 };
+use sd_core::ops::search::parse::query::SearchParseQueryInput;
+use sd_core::ops::search::saved::{
+	delete::input::SavedSearchDeleteInput, run::query::SavedSearchRunQueryInput,
+};
 
 #[derive(Args, Debug)]
 pub struct FileSearchArgs {
-	/// Search query
+	/// Search query, may include filters such as kind:image or size:>20MB
 	pub query: String,
 
 	/// Search mode
@@ -91,6 +95,7 @@ pub enum SearchModeArg {
 	Fast,
 	Normal,
 	Full,
+	Semantic,
 }
 
 #[derive(clap::ValueEnum, Debug, Clone)]
@@ -142,6 +147,7 @@ impl From<FileSearchArgs> for FileSearchInput {
 			SearchModeArg::Fast => SearchMode::Fast,
 			SearchModeArg::Normal => SearchMode::Normal,
 			SearchModeArg::Full => SearchMode::Full,
+			SearchModeArg::Semantic => SearchMode::Semantic,
 		};
 
 		let scope = if let Some(sd_path_str) = args.sd_path {
@@ -251,3 +257,65 @@ impl From<FileSearchArgs> for FileSearchInput {
 		}
 	}
 }
+
+#[derive(Args, Debug)]
+pub struct SaveSearchArgs {
+	/// Name of the saved search
+	pub name: String,
+
+	/// Search to save, pagination options are ignored
+	#[command(flatten)]
+	pub search: FileSearchArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct RunSavedSearchArgs {
+	pub saved_search_id: Uuid,
+
+	/// Limit number of results
+	#[arg(long, default_value = "50")]
+	pub limit: u32,
+
+	/// Offset for pagination
+	#[arg(long, default_value = "0")]
+	pub offset: u32,
+}
+
+impl From<RunSavedSearchArgs> for SavedSearchRunQueryInput {
+	fn from(args: RunSavedSearchArgs) -> Self {
+		Self {
+			saved_search_id: args.saved_search_id,
+			pagination: Some(PaginationOptions {
+				limit: args.limit,
+				offset: args.offset,
+			}),
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct UnsaveSearchArgs {
+	pub saved_search_id: Uuid,
+	#[arg(long, short = 'y', default_value_t = false)]
+	pub yes: bool,
+}
+
+impl From<UnsaveSearchArgs> for SavedSearchDeleteInput {
+	fn from(args: UnsaveSearchArgs) -> Self {
+		Self {
+			saved_search_id: args.saved_search_id,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct ParseSearchArgs {
+	/// Search query to check
+	pub query: String,
+}
+
+impl From<ParseSearchArgs> for SearchParseQueryInput {
+	fn from(args: ParseSearchArgs) -> Self {
+		Self { query: args.query }
+	}
+}
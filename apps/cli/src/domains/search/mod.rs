@@ -6,7 +6,19 @@ use clap::Subcommand;
 use crate::context::Context;
 use crate::util::prelude::*;
 
-use sd_core::ops::search::{output::FileSearchOutput, query::FileSearchQuery};
+use sd_core::ops::search::{
+	input::FileSearchInput,
+	output::FileSearchOutput,
+	parse::{output::SearchParseOutput, query::SearchParseQueryInput},
+	query::FileSearchQuery,
+	saved::{
+		create::{input::SavedSearchCreateInput, output::SavedSearchCreateOutput},
+		delete::{input::SavedSearchDeleteInput, output::SavedSearchDeleteOutput},
+		list::{output::SavedSearchesListOutput, query::SavedSearchesListQueryInput},
+		run::{output::SavedSearchRunOutput, query::SavedSearchRunQueryInput},
+	},
+	syntax::SyntaxError,
+};
 
 use self::args::*;
 
@@ -14,70 +26,83 @@ use self::args::*;
 pub enum SearchCmd {
 	/// Search for files
 	Files(FileSearchArgs),
+	/// Save a search under a name
+	Save(SaveSearchArgs),
+	/// List saved searches with their result counts
+	Saved,
+	/// Run a saved search
+	Run(RunSavedSearchArgs),
+	/// Delete a saved search
+	Unsave(UnsaveSearchArgs),
+	/// Check a query and show it in canonical form
+	Parse(ParseSearchArgs),
 }
 
 pub async fn run(ctx: &Context, cmd: SearchCmd) -> Result<()> {
 	match cmd {
 		SearchCmd::Files(args) => {
-			let input: sd_core::ops::search::input::FileSearchInput = args.into();
+			let input = apply_syntax(ctx, args.into()).await?;
 			let out: FileSearchOutput = execute_query!(ctx, input);
-			print_output!(ctx, &out, |o: &FileSearchOutput| {
-				if o.results.is_empty() {
-					println!("No files found");
+			print_output!(ctx, &out, print_search_output);
+		}
+		SearchCmd::Save(args) => {
+			let input = SavedSearchCreateInput {
+				name: args.name,
+				definition: apply_syntax(ctx, args.search.into()).await?.into(),
+			};
+			let out: SavedSearchCreateOutput = execute_action!(ctx, input);
+			print_output!(ctx, &out, |o: &SavedSearchCreateOutput| {
+				println!("{} (id: {})", o.saved_search.name, o.saved_search.id);
+			});
+		}
+		SearchCmd::Saved => {
+			let out: SavedSearchesListOutput = execute_query!(ctx, SavedSearchesListQueryInput);
+			print_output!(ctx, &out, |o: &SavedSearchesListOutput| {
+				if o.saved_searches.is_empty() {
+					println!("No saved searches");
 					return;
 				}
-
-				println!("Found {} files ({} total)", o.results.len(), o.total_found);
-				println!("Search ID: {}", o.search_id);
-				println!("Execution time: {}ms", o.execution_time_ms);
-				println!();
-
-				for (i, result) in o.results.iter().enumerate() {
-					println!(
-						"{}. {} (score: {:.2})",
-						i + 1,
-						result.file.name,
-						result.score
-					);
-
-					if let Some(extension) = result.file.extension.as_deref() {
-						println!("   Extension: {}", extension);
-					}
-
-					println!("   Size: {} bytes", result.file.size);
-					println!(
-						"   Modified: {}",
-						result.file.modified_at.format("%Y-%m-%d %H:%M:%S")
-					);
-					println!("   Path: {}", result.file.sd_path.display());
-
-					if !result.highlights.is_empty() {
-						println!("   Highlights:");
-						for highlight in &result.highlights {
-							println!("     {}: {}", highlight.field, highlight.text);
-						}
-					}
-
-					if let Some(content) = &result.matched_content {
-						println!("   Matched content: {}", content);
-					}
-
-					println!();
-				}
-
-				// Show facets if available
-				if !o.facets.file_types.is_empty() {
-					println!("File types:");
-					for (file_type, count) in &o.facets.file_types {
-						println!("  {}: {}", file_type, count);
+				for s in &o.saved_searches {
+					match s.result_count {
+						Some(count) => println!("{} {} ({} files)", s.id, s.name, count),
+						None => println!("{} {}", s.id, s.name),
 					}
-					println!();
 				}
-
-				if !o.suggestions.is_empty() {
-					println!("Suggestions:");
-					for suggestion in &o.suggestions {
-						println!("  {}", suggestion);
+			});
+		}
+		SearchCmd::Run(args) => {
+			let input: SavedSearchRunQueryInput = args.into();
+			let out: SavedSearchRunOutput = execute_query!(ctx, input);
+			print_output!(ctx, &out, |o: &SavedSearchRunOutput| {
+				println!("{}", o.saved_search.name);
+				print_search_output(&o.results);
+			});
+		}
+		SearchCmd::Unsave(args) => {
+			confirm_or_abort(
+				&format!(
+					"This will delete saved search {}. Files are not affected. Continue?",
+					args.saved_search_id
+				),
+				args.yes,
+			)?;
+			let saved_search_id = args.saved_search_id;
+			let input: SavedSearchDeleteInput = args.into();
+			let out: SavedSearchDeleteOutput = execute_action!(ctx, input);
+			print_output!(ctx, &out, |_: &SavedSearchDeleteOutput| {
+				println!("Deleted saved search {}", saved_search_id);
+			});
+		}
+		SearchCmd::Parse(args) => {
+			let query = args.query.clone();
+			let input: SearchParseQueryInput = args.into();
+			let out: SearchParseOutput = execute_query!(ctx, input);
+			print_output!(ctx, &out, |o: &SearchParseOutput| {
+				match &o.error {
+					Some(error) => println!("{}", describe_syntax_error(&query, error)),
+					None => {
+						println!("Canonical: {}", o.canonical);
+						println!("Text: {}", o.query);
 					}
 				}
 			});
@@ -85,3 +110,97 @@ pub async fn run(ctx: &Context, cmd: SearchCmd) -> Result<()> {
 	}
 	Ok(())
 }
+
+/// Move filters written in the query, like `kind:image`, into the input's filters
+async fn apply_syntax(ctx: &Context, mut input: FileSearchInput) -> Result<FileSearchInput> {
+	let parsed: SearchParseOutput = execute_query!(
+		ctx,
+		SearchParseQueryInput {
+			query: input.query.clone(),
+		}
+	);
+	if let Some(error) = &parsed.error {
+		anyhow::bail!(describe_syntax_error(&input.query, error));
+	}
+
+	input.query = parsed.query;
+	input.filters.merge(parsed.filters);
+	Ok(input)
+}
+
+/// The error message with the query underlined where it went wrong
+fn describe_syntax_error(query: &str, error: &SyntaxError) -> String {
+	let column = query[..error.span.start].chars().count();
+	let width = query[error.span.start..error.span.end]
+		.chars()
+		.count()
+		.max(1);
+	format!(
+		"{}\n  {}\n  {}{}",
+		error.message,
+		query,
+		" ".repeat(column),
+		"^".repeat(width)
+	)
+}
+
+fn print_search_output(o: &FileSearchOutput) {
+	if o.results.is_empty() {
+		println!("No files found");
+		return;
+	}
+
+	println!("Found {} files ({} total)", o.results.len(), o.total_found);
+	println!("Search ID: {}", o.search_id);
+	println!("Execution time: {}ms", o.execution_time_ms);
+	println!();
+
+	for (i, result) in o.results.iter().enumerate() {
+		println!(
+			"{}. {} (score: {:.2})",
+			i + 1,
+			result.file.name,
+			result.score
+		);
+
+		if let Some(extension) = result.file.extension.as_deref() {
+			println!("   Extension: {}", extension);
+		}
+
+		println!("   Size: {} bytes", result.file.size);
+		println!(
+			"   Modified: {}",
+			result.file.modified_at.format("%Y-%m-%d %H:%M:%S")
+		);
+		println!("   Path: {}", result.file.sd_path.display());
+
+		if !result.highlights.is_empty() {
+			println!("   Highlights:");
+			for highlight in &result.highlights {
+				println!("     {}: {}", highlight.field, highlight.text);
+			}
+		}
+
+		if let Some(content) = &result.matched_content {
+			println!("   Matched content: {}", content);
+		}
+
+		println!();
+	}
+
+	// Show facets if available
+	if !o.facets.file_types.is_empty() {
+		println!("File types:");
+		for (file_type, count) in &o.facets.file_types {
+			println!("  {}: {}", file_type, count);
+		}
+		println!();
+	}
+
+	if !o.suggestions.is_empty() {
+		println!("Suggestions:");
+		for suggestion in &o.suggestions {
+			println!("  {}", suggestion);
+		}
+	}
+}
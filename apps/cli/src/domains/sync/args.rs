@@ -1,4 +1,11 @@
-use clap::Args;
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+use sd_core::ops::sync::{
+	export_bundle::SyncBundleExportInput, import_bundle::SyncBundleImportInput,
+	verify::SyncVerifyInput,
+};
 
 #[derive(Args, Debug)]
 pub struct SyncEventsArgs {
@@ -93,3 +100,68 @@ pub struct SyncMetricsArgs {
 	#[arg(long, help = "Show only error metrics")]
 	pub errors: bool,
 }
+
+#[derive(Args, Debug)]
+pub struct SyncVerifyArgs {
+	/// Peer whose records to verify (defaults to every connected sync partner)
+	#[arg(long)]
+	pub peer: Option<Uuid>,
+}
+
+impl From<SyncVerifyArgs> for SyncVerifyInput {
+	fn from(args: SyncVerifyArgs) -> Self {
+		Self { peer_id: args.peer }
+	}
+}
+
+#[derive(Subcommand, Debug)]
+pub enum BundleCmd {
+	/// Write the changes a device hasn't received to a bundle file
+	Export(BundleExportArgs),
+	/// Apply a bundle from another device and write the ack to take back
+	Import(BundleImportArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct BundleExportArgs {
+	/// Device the bundle is for
+	#[arg(long)]
+	pub peer: Uuid,
+
+	/// Path to save the bundle to
+	#[arg(long, short = 'o')]
+	pub output: PathBuf,
+}
+
+impl From<BundleExportArgs> for SyncBundleExportInput {
+	fn from(args: BundleExportArgs) -> Self {
+		Self {
+			target_device: args.peer,
+			export_path: args.output,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct BundleImportArgs {
+	/// Path to the bundle to import
+	pub import_path: PathBuf,
+
+	/// Path to save the ack bundle to (defaults to next to the imported bundle)
+	#[arg(long)]
+	pub ack_output: Option<PathBuf>,
+
+	/// Import bundles whose signer isn't a known key of the source device
+	#[arg(long)]
+	pub allow_unknown_signer: bool,
+}
+
+impl From<BundleImportArgs> for SyncBundleImportInput {
+	fn from(args: BundleImportArgs) -> Self {
+		Self {
+			import_path: args.import_path,
+			ack_path: args.ack_output,
+			allow_unknown_signer: args.allow_unknown_signer,
+		}
+	}
+}
@@ -12,7 +12,12 @@ use tokio::time::sleep;
 use crate::context::Context;
 use crate::util::prelude::*;
 use sd_core::infra::sync::{EventSeverity, SyncEventQuery, SyncEventType};
-use sd_core::ops::sync::get_metrics::GetSyncMetricsInput;
+use sd_core::ops::sync::{
+	export_bundle::{SyncBundleExportInput, SyncBundleExportOutput},
+	get_metrics::GetSyncMetricsInput,
+	import_bundle::{SyncBundleImportInput, SyncBundleImportOutput},
+	verify::{SyncVerifyInput, SyncVerifyOutput},
+};
 use sd_core::service::sync::state::DeviceSyncState;
 
 use self::args::*;
@@ -27,12 +32,21 @@ pub enum SyncCmd {
 
 	/// Show computed sync partners for this library
 	Partners,
+
+	/// Sync offline through bundle files
+	#[command(subcommand)]
+	Bundle(BundleCmd),
+
+	/// Verify synced data against peers and repair what differs
+	Verify(SyncVerifyArgs),
 }
 
 pub async fn run(ctx: &Context, cmd: SyncCmd) -> Result<()> {
 	match cmd {
 		SyncCmd::Partners => show_partners(ctx).await?,
 		SyncCmd::Events(args) => export_events(ctx, args).await?,
+		SyncCmd::Bundle(cmd) => run_bundle(ctx, cmd).await?,
+		SyncCmd::Verify(args) => verify(ctx, args).await?,
 		SyncCmd::Metrics(args) => {
 			// Parse time filters
 			let since = if let Some(since_str) = &args.since {
@@ -58,6 +72,77 @@ pub async fn run(ctx: &Context, cmd: SyncCmd) -> Result<()> {
 	Ok(())
 }
 
+async fn verify(ctx: &Context, args: SyncVerifyArgs) -> Result<()> {
+	let input: SyncVerifyInput = args.into();
+	let out: SyncVerifyOutput = execute_action!(ctx, input);
+	print_output!(ctx, &out, |o: &SyncVerifyOutput| {
+		if o.reports.is_empty() {
+			println!("No connected sync partners to verify against");
+			return;
+		}
+		for report in &o.reports {
+			println!(
+				"Verified against {} in {}ms: {} records differed, {} repaired",
+				report.peer,
+				report.duration_ms,
+				report.mismatches(),
+				report.repairs()
+			);
+			for model in &report.models {
+				if model.mismatched + model.extra == 0 {
+					continue;
+				}
+				println!(
+					"  {}: {} mismatched, {} extra, {} repaired, {} deleted",
+					model.model_type, model.mismatched, model.extra, model.repaired, model.deleted
+				);
+			}
+		}
+	});
+	Ok(())
+}
+
+async fn run_bundle(ctx: &Context, cmd: BundleCmd) -> Result<()> {
+	match cmd {
+		BundleCmd::Export(args) => {
+			let input: SyncBundleExportInput = args.into();
+			let out: SyncBundleExportOutput = execute_action!(ctx, input);
+			print_output!(ctx, &out, |o: &SyncBundleExportOutput| {
+				println!(
+					"Exported bundle for {} to {}",
+					o.summary.target_device,
+					o.export_path.display()
+				);
+				println!(
+					"  {} records, {} deletions, {} shared changes, {} bytes",
+					o.summary.state_records,
+					o.summary.deletions,
+					o.summary.shared_changes,
+					o.size_bytes
+				);
+			});
+		}
+		BundleCmd::Import(args) => {
+			let input: SyncBundleImportInput = args.into();
+			let out: SyncBundleImportOutput = execute_action!(ctx, input);
+			print_output!(ctx, &out, |o: &SyncBundleImportOutput| {
+				println!("Imported bundle from {}", o.summary.source_device);
+				println!(
+					"  {} records, {} deletions, {} shared changes",
+					o.summary.state_records, o.summary.deletions, o.summary.shared_changes
+				);
+				if let Some(ack_path) = &o.ack_path {
+					println!(
+						"Take the ack back to the source device: {}",
+						ack_path.display()
+					);
+				}
+			});
+		}
+	}
+	Ok(())
+}
+
 async fn run_single_query(
 	ctx: &Context,
 	since: Option<DateTime<Utc>>,
@@ -296,6 +381,21 @@ fn display_metrics(
 			"".to_string(),
 		]);
 
+		grid_table.add_row(vec![
+			format!(
+				"Verifications        {:>6}",
+				snapshot.operations.verification_runs
+			),
+			format!(
+				"Mismatches   {:>10}",
+				snapshot.operations.verification_mismatches
+			),
+			format!(
+				"Repairs     {:>8}",
+				snapshot.operations.verification_repairs
+			),
+		]);
+
 		println!("{}", grid_table);
 		println!();
 	}
@@ -32,11 +32,11 @@ pub struct VolumeAddCloudArgs {
 	#[arg(long)]
 	pub secret_access_key: Option<String>,
 
-	/// Custom endpoint (S3, Azure, GCS)
+	/// Custom endpoint (S3, Azure, GCS), DAV URL (WebDAV)
 	#[arg(long)]
 	pub endpoint: Option<String>,
 
-	/// Root folder path or ID (Google Drive, OneDrive, Dropbox, GCS)
+	/// Root folder path or ID (Google Drive, OneDrive, Dropbox, GCS, SFTP, WebDAV)
 	#[arg(long)]
 	pub root: Option<String>,
 
@@ -71,6 +71,34 @@ pub struct VolumeAddCloudArgs {
 	/// Path to service account JSON file (GCS)
 	#[arg(long)]
 	pub service_account: Option<String>,
+
+	/// Server host name or address (SFTP)
+	#[arg(long)]
+	pub host: Option<String>,
+
+	/// Server port (SFTP, defaults to 22)
+	#[arg(long)]
+	pub port: Option<u16>,
+
+	/// Login name (SFTP, WebDAV)
+	#[arg(long)]
+	pub username: Option<String>,
+
+	/// Password (WebDAV)
+	#[arg(long)]
+	pub password: Option<String>,
+
+	/// Path to the SSH private key, the SSH agent is used without it (SFTP)
+	#[arg(long)]
+	pub key_path: Option<String>,
+
+	/// Encrypt file contents on this device before uploading them
+	#[arg(long)]
+	pub encrypt: bool,
+
+	/// Encrypt file and directory names as well (requires --encrypt)
+	#[arg(long, requires = "encrypt")]
+	pub encrypt_names: bool,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug)]
@@ -84,6 +112,9 @@ pub enum CloudServiceArg {
 	BackblazeB2,
 	Wasabi,
 	DigitalOceanSpaces,
+	Sftp,
+	#[value(name = "webdav")]
+	WebDav,
 }
 
 impl From<CloudServiceArg> for CloudServiceType {
@@ -98,6 +129,8 @@ impl From<CloudServiceArg> for CloudServiceType {
 			CloudServiceArg::BackblazeB2 => CloudServiceType::BackblazeB2,
 			CloudServiceArg::Wasabi => CloudServiceType::Wasabi,
 			CloudServiceArg::DigitalOceanSpaces => CloudServiceType::DigitalOceanSpaces,
+			CloudServiceArg::Sftp => CloudServiceType::Sftp,
+			CloudServiceArg::WebDav => CloudServiceType::WebDav,
 		}
 	}
 }
@@ -234,12 +267,38 @@ impl VolumeAddCloudArgs {
 					credential,
 				}
 			}
+			CloudServiceArg::Sftp => {
+				let host = self.host.ok_or("--host is required for SFTP")?;
+				let username = self.username.ok_or("--username is required for SFTP")?;
+
+				CloudStorageConfig::Sftp {
+					host,
+					port: self.port,
+					username,
+					key_path: self.key_path,
+					root: self.root,
+				}
+			}
+			CloudServiceArg::WebDav => {
+				let endpoint = self.endpoint.ok_or("--endpoint is required for WebDAV")?;
+				let username = self.username.ok_or("--username is required for WebDAV")?;
+				let password = self.password.ok_or("--password is required for WebDAV")?;
+
+				CloudStorageConfig::WebDav {
+					endpoint,
+					username,
+					password,
+					root: self.root,
+				}
+			}
 		};
 
 		Ok(VolumeAddCloudInput {
 			service,
 			display_name: self.name,
 			config,
+			encrypt: self.encrypt,
+			encrypt_names: self.encrypt_names,
 		})
 	}
 }
@@ -47,11 +47,14 @@ mod util;
 
 use crate::context::{Context, OutputFormat};
 use crate::domains::{
-	cloud, config as config_cmd,
+	cloud,
+	collection::{self, CollectionCmd},
+	config as config_cmd,
 	daemon::{self, DaemonCmd},
 	devices::{self, DevicesCmd},
 	events::{self, EventsCmd},
 	file::{self, FileCmd},
+	file_sync::{self, FileSyncCmd},
 	index::{self, IndexCmd},
 	job::{self, JobCmd},
 	library::{self, LibraryCmd},
@@ -177,6 +180,9 @@ enum Commands {
 	/// Configuration management
 	#[command(subcommand)]
 	Config(ConfigCmd),
+	/// Hand-curated, ordered collections of files
+	#[command(subcommand)]
+	Collection(CollectionCmd),
 	/// Daemon management (auto-start, etc)
 	#[command(subcommand)]
 	Daemon(DaemonCmd),
@@ -192,6 +198,9 @@ enum Commands {
 	/// File operations
 	#[command(subcommand)]
 	File(FileCmd),
+	/// Keep directories in sync with each other
+	#[command(subcommand)]
+	FileSync(FileSyncCmd),
 	/// Indexing operations
 	#[command(subcommand)]
 	Index(IndexCmd),
@@ -697,10 +706,12 @@ async fn run_client_command(
 				OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&status)?),
 			}
 		}
+		Commands::Collection(cmd) => collection::run(&ctx, cmd).await?,
 		Commands::Devices(cmd) => devices::run(&ctx, cmd).await?,
 		Commands::Events(cmd) => events::run(&ctx, cmd).await?,
 		Commands::Library(cmd) => library::run(&ctx, cmd).await?,
 		Commands::File(cmd) => file::run(&ctx, cmd).await?,
+		Commands::FileSync(cmd) => file_sync::run(&ctx, cmd).await?,
 		Commands::Index(cmd) => index::run(&ctx, cmd).await?,
 		Commands::Location(cmd) => location::run(&ctx, cmd).await?,
 		Commands::Network(cmd) => network::run(&ctx, cmd).await?,
@@ -39,6 +39,7 @@ impl Scenario for CoreIndexingScenario {
 				name: Some(format!("bench:{}", recipe.name)),
 				mode: sd_core::ops::indexing::IndexMode::Shallow,
 				job_policies: None,
+				indexer_limits: None,
 			};
 			let action = sd_core::ops::locations::add::action::LocationAddAction::from_input(input)
 				.map_err(|e| anyhow::anyhow!(e))?;
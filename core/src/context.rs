@@ -38,6 +38,7 @@ pub struct CoreContext {
 	// Remote job cache for cross-device job visibility
 	pub remote_job_cache: Arc<RemoteJobCache>,
 	// File type registry (loaded once at startup, never changes)
+	// Built-in types and those defined in the app data directory, libraries add their own
 	pub file_type_registry: Arc<FileTypeRegistry>,
 	// Job logging configuration
 	pub job_logging_config: Option<JobLoggingConfig>,
@@ -72,7 +73,7 @@ impl CoreContext {
 				EphemeralIndexCache::new().expect("Failed to create ephemeral index cache"),
 			),
 			remote_job_cache: Arc::new(RemoteJobCache::new()),
-			file_type_registry: Arc::new(FileTypeRegistry::new()),
+			file_type_registry: Arc::new(FileTypeRegistry::with_app_definitions(&data_dir)),
 			job_logging_config: None,
 			job_logs_dir: None,
 			data_dir,
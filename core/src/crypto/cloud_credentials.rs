@@ -285,6 +285,16 @@ pub enum CredentialData {
 
 	/// Connection string (Azure, etc.)
 	ConnectionString(String),
+
+	/// Username and password (WebDAV)
+	Password { username: String, password: String },
+
+	/// SSH login (SFTP), the key file stays where it is and only its path is stored
+	SshKey {
+		username: String,
+		/// None to use the SSH agent or default identities
+		key_path: Option<String>,
+	},
 }
 
 impl CloudCredential {
@@ -339,6 +349,34 @@ impl CloudCredential {
 		}
 	}
 
+	/// Create a new username and password credential
+	pub fn new_password(
+		service: crate::volume::CloudServiceType,
+		username: String,
+		password: String,
+	) -> Self {
+		Self {
+			service,
+			data: CredentialData::Password { username, password },
+			created_at: chrono::Utc::now(),
+			expires_at: None,
+		}
+	}
+
+	/// Create a new SSH login credential
+	pub fn new_ssh_key(
+		service: crate::volume::CloudServiceType,
+		username: String,
+		key_path: Option<String>,
+	) -> Self {
+		Self {
+			service,
+			data: CredentialData::SshKey { username, key_path },
+			created_at: chrono::Utc::now(),
+			expires_at: None,
+		}
+	}
+
 	/// Check if this credential is expired
 	pub fn is_expired(&self) -> bool {
 		if let Some(expires_at) = self.expires_at {
@@ -449,4 +487,34 @@ mod tests {
 		assert!(!credential_not_expired.is_expired());
 		assert!(credential_expired.is_expired());
 	}
+
+	#[test]
+	fn test_self_hosted_credentials_round_trip() {
+		let webdav = CloudCredential::new_password(
+			crate::volume::CloudServiceType::WebDav,
+			"alice".to_string(),
+			"app-password".to_string(),
+		);
+		let sftp = CloudCredential::new_ssh_key(
+			crate::volume::CloudServiceType::Sftp,
+			"alice".to_string(),
+			Some("/home/alice/.ssh/id_ed25519".to_string()),
+		);
+
+		let webdav: CloudCredential =
+			serde_json::from_slice(&serde_json::to_vec(&webdav).unwrap()).unwrap();
+		let sftp: CloudCredential =
+			serde_json::from_slice(&serde_json::to_vec(&sftp).unwrap()).unwrap();
+
+		assert!(matches!(
+			webdav.data,
+			CredentialData::Password { ref username, ref password }
+				if username == "alice" && password == "app-password"
+		));
+		assert!(matches!(
+			sftp.data,
+			CredentialData::SshKey { ref key_path, .. }
+				if key_path.as_deref() == Some("/home/alice/.ssh/id_ed25519")
+		));
+	}
 }
@@ -233,6 +233,41 @@ impl KeyManager {
 		Ok(key)
 	}
 
+	/// Get the key of a client-side encrypted volume
+	///
+	/// Unlike library keys these are never generated on demand, a missing key means the
+	/// volume's contents can't be read on this device.
+	pub async fn get_volume_key(
+		&self,
+		fingerprint: &str,
+	) -> Result<[u8; KEY_LENGTH], KeyManagerError> {
+		let key = self.get_secret(&format!("volume_{}", fingerprint)).await?;
+
+		key.try_into()
+			.map_err(|_| KeyManagerError::InvalidKeyFormat)
+	}
+
+	/// Get the key of a client-side encrypted volume, generating it for a new volume
+	///
+	/// The key is kept when the volume is removed, so adding the same storage again can
+	/// still read what was uploaded before.
+	pub async fn get_or_create_volume_key(
+		&self,
+		fingerprint: &str,
+	) -> Result<[u8; KEY_LENGTH], KeyManagerError> {
+		match self.get_volume_key(fingerprint).await {
+			Err(KeyManagerError::KeyNotFound(_))
+			| Err(KeyManagerError::TableError(redb::TableError::TableDoesNotExist(_))) => {}
+			result => return result,
+		}
+
+		let key = self.generate_key()?;
+		self.set_secret(&format!("volume_{}", fingerprint), &key)
+			.await?;
+
+		Ok(key)
+	}
+
 	/// Store an encrypted secret in the KV store
 	pub async fn set_secret(&self, key: &str, value: &[u8]) -> Result<(), KeyManagerError> {
 		let device_key = self.get_device_key().await?;
@@ -390,6 +425,30 @@ mod tests {
 		assert_eq!(key1, key2);
 	}
 
+	#[tokio::test]
+	async fn test_volume_key_storage() {
+		let temp_dir = TempDir::new().unwrap();
+		let manager = KeyManager::new_with_fallback(
+			temp_dir.path().to_path_buf(),
+			Some(temp_dir.path().join("device_key.txt")),
+		)
+		.unwrap();
+
+		assert!(manager.get_volume_key("fingerprint").await.is_err());
+
+		let key1 = manager
+			.get_or_create_volume_key("fingerprint")
+			.await
+			.unwrap();
+		let key2 = manager
+			.get_or_create_volume_key("fingerprint")
+			.await
+			.unwrap();
+
+		assert_eq!(key1, key2);
+		assert_eq!(manager.get_volume_key("fingerprint").await.unwrap(), key1);
+	}
+
 	#[tokio::test]
 	async fn test_secret_storage() {
 		let temp_dir = TempDir::new().unwrap();
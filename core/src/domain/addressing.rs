@@ -985,6 +985,31 @@ mod tests {
 		assert!(path.is_local());
 	}
 
+	#[test]
+	fn test_sdpath_self_hosted_cloud_uri() {
+		use crate::volume::backend::CloudServiceType;
+
+		let path = SdPath::from_uri("sftp://alice@nas.local:2222/photos/2024/beach.jpg").unwrap();
+		assert_eq!(
+			path.as_cloud(),
+			Some((
+				CloudServiceType::Sftp,
+				"alice@nas.local:2222",
+				"photos/2024/beach.jpg"
+			))
+		);
+		assert_eq!(
+			path.display(),
+			"sftp://alice@nas.local:2222/photos/2024/beach.jpg"
+		);
+
+		let path = SdPath::from_uri("webdav://alice@cloud.example.com/Documents").unwrap();
+		assert_eq!(
+			path.as_cloud(),
+			Some((CloudServiceType::WebDav, "alice@cloud.example.com", "Documents"))
+		);
+	}
+
 	#[test]
 	fn test_sdpath_sidecar_creation() {
 		let content_id = Uuid::new_v4();
@@ -0,0 +1,154 @@
+//! Collections - hand-curated, ordered sets of files
+//!
+//! Unlike tags, which describe files, a collection is a list the user puts together
+//! and orders themselves. Its files can come from any location on any device, and
+//! collections sync across devices as shared resources.
+
+use crate::domain::{resource::Identifiable, File};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// A hand-curated collection of files
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct Collection {
+	/// Unique identifier
+	pub id: Uuid,
+
+	pub name: String,
+
+	pub description: Option<String>,
+
+	/// Number of files in the collection
+	pub file_count: u64,
+
+	/// Timestamps
+	pub created_at: DateTime<Utc>,
+	pub updated_at: DateTime<Utc>,
+}
+
+impl Collection {
+	/// Build collections from database models, counting their files
+	pub async fn from_db_models(
+		db: &sea_orm::DatabaseConnection,
+		models: Vec<crate::infra::db::entities::collection::Model>,
+	) -> crate::common::errors::Result<Vec<Self>> {
+		use crate::infra::db::entities::collection_entry;
+		use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+		let mut file_counts: HashMap<i32, u64> = HashMap::new();
+		if !models.is_empty() {
+			let entries = collection_entry::Entity::find()
+				.filter(collection_entry::Column::CollectionId.is_in(models.iter().map(|m| m.id)))
+				.all(db)
+				.await?;
+			for entry in entries {
+				*file_counts.entry(entry.collection_id).or_default() += 1;
+			}
+		}
+
+		Ok(models
+			.into_iter()
+			.map(|model| Self {
+				id: model.uuid,
+				file_count: file_counts.get(&model.id).copied().unwrap_or(0),
+				name: model.name,
+				description: model.description,
+				created_at: model.created_at,
+				updated_at: model.updated_at,
+			})
+			.collect())
+	}
+}
+
+impl Identifiable for Collection {
+	fn id(&self) -> Uuid {
+		self.id
+	}
+
+	fn resource_type() -> &'static str {
+		"collection"
+	}
+
+	async fn from_ids(
+		db: &sea_orm::DatabaseConnection,
+		ids: &[Uuid],
+	) -> crate::common::errors::Result<Vec<Self>>
+	where
+		Self: Sized,
+	{
+		use crate::infra::db::entities::collection;
+		use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+		let models = collection::Entity::find()
+			.filter(collection::Column::Uuid.is_in(ids.to_vec()))
+			.all(db)
+			.await?;
+
+		Self::from_db_models(db, models).await
+	}
+}
+
+/// A file in a collection
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionFile {
+	/// Position in the collection, files are listed in ascending order
+	pub position: i32,
+
+	pub added_at: DateTime<Utc>,
+
+	pub file: File,
+}
+
+impl CollectionFile {
+	/// Files of a collection in order, skipping entries not indexed on this device yet
+	pub async fn for_collection(
+		db: &sea_orm::DatabaseConnection,
+		collection_id: i32,
+	) -> crate::common::errors::Result<Vec<Self>> {
+		use crate::infra::db::entities::{collection_entry, entry};
+		use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, QueryOrder};
+
+		let members = collection_entry::Entity::find()
+			.filter(collection_entry::Column::CollectionId.eq(collection_id))
+			.order_by_asc(collection_entry::Column::Position)
+			.order_by_asc(collection_entry::Column::AddedAt)
+			.order_by_asc(collection_entry::Column::Uuid)
+			.all(db)
+			.await?;
+		if members.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		let entry_uuids: HashMap<i32, Uuid> = entry::Entity::find()
+			.filter(entry::Column::Id.is_in(members.iter().map(|m| m.entry_id)))
+			.all(db)
+			.await?
+			.into_iter()
+			.filter_map(|entry| Some((entry.id, entry.uuid?)))
+			.collect();
+		let uuids = entry_uuids.values().copied().collect::<Vec<_>>();
+		let mut files: HashMap<Uuid, File> = File::from_entry_uuids(db, &uuids)
+			.await?
+			.into_iter()
+			.map(|file| (file.id, file))
+			.collect();
+
+		Ok(members
+			.into_iter()
+			.filter_map(|member| {
+				let file = files.remove(entry_uuids.get(&member.entry_id)?)?;
+				Some(Self {
+					position: member.position,
+					added_at: member.added_at,
+					file,
+				})
+			})
+			.collect())
+	}
+}
+
+// Register Collection as a simple resource
+crate::register_resource!(Collection);
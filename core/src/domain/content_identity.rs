@@ -197,11 +197,18 @@ impl ContentHashGenerator {
 		let mut hasher = Hasher::new();
 		hasher.update(&size.to_le_bytes());
 
-		let content = backend
-			.read(path)
+		// Stream the file so backends never have to buffer it whole
+		let mut reader = backend
+			.open_reader(path, None)
 			.await
 			.map_err(|e| ContentHashError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?;
-		hasher.update(&content);
+		while let Some(chunk) = reader
+			.next_chunk()
+			.await
+			.map_err(|e| ContentHashError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?
+		{
+			hasher.update(&chunk);
+		}
 
 		Ok(hasher.finalize().to_hex()[..16].to_string())
 	}
@@ -253,6 +260,54 @@ impl ContentHashGenerator {
 		Ok(hasher.finalize().to_hex()[..16].to_string())
 	}
 
+	/// Generate a content hash from a reader that only goes forward (blocking)
+	///
+	/// Gives the same hash as the backend-based implementation, for sources such as
+	/// compressed archive members where seeking to a sample means decompressing
+	/// everything before it anyway. The skipped bytes are read and discarded.
+	pub fn generate_content_hash_from_reader(
+		reader: &mut dyn std::io::Read,
+		size: u64,
+	) -> Result<String, ContentHashError> {
+		use blake3::Hasher;
+		use std::io::Read;
+
+		if size == 0 {
+			return Err(ContentHashError::EmptyFile);
+		}
+
+		let mut hasher = Hasher::new();
+		hasher.update(&size.to_le_bytes());
+
+		if size <= MINIMUM_FILE_SIZE {
+			std::io::copy(&mut (&mut *reader).take(size), &mut hasher)?;
+			return Ok(hasher.finalize().to_hex()[..16].to_string());
+		}
+
+		// Header, 4 evenly spaced samples and footer, which never overlap above MINIMUM_FILE_SIZE
+		let seek_jump = (size - HEADER_OR_FOOTER_SIZE * 2) / SAMPLE_COUNT;
+		let mut ranges = vec![0..HEADER_OR_FOOTER_SIZE];
+		ranges.extend((0..SAMPLE_COUNT).map(|i| {
+			let start = HEADER_OR_FOOTER_SIZE + i * seek_jump;
+			start..start + SAMPLE_SIZE
+		}));
+		ranges.push(size - HEADER_OR_FOOTER_SIZE..size);
+
+		let mut position = 0;
+		let mut buffer = vec![0u8; SAMPLE_SIZE as usize];
+		for range in ranges {
+			let skip = range.start.saturating_sub(position);
+			std::io::copy(&mut (&mut *reader).take(skip), &mut std::io::sink())?;
+
+			let sample = &mut buffer[..(range.end - range.start) as usize];
+			reader.read_exact(sample)?;
+			hasher.update(sample);
+			position = range.end;
+		}
+
+		Ok(hasher.finalize().to_hex()[..16].to_string())
+	}
+
 	/// Verify a content hash matches the current content of a file
 	pub async fn verify_content_hash(
 		path: &std::path::Path,
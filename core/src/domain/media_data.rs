@@ -23,6 +23,10 @@ pub struct ImageMediaData {
 	pub focal_length: Option<String>,
 	pub aperture: Option<String>,
 	pub shutter_speed: Option<String>,
+	/// Exposure time in seconds
+	pub exposure_time: Option<String>,
+	/// Exposure compensation in EV
+	pub exposure_bias: Option<String>,
 	pub iso: Option<u32>,
 	pub orientation: Option<u8>,
 	pub color_space: Option<String>,
@@ -100,6 +104,8 @@ impl From<image_media_data::Model> for ImageMediaData {
 			focal_length: model.focal_length,
 			aperture: model.aperture,
 			shutter_speed: model.shutter_speed,
+			exposure_time: model.exposure_time,
+			exposure_bias: model.exposure_bias,
 			iso: model.iso.map(|i| i as u32),
 			orientation: model.orientation.map(|o| o as u8),
 			color_space: model.color_space,
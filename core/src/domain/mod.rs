@@ -6,6 +6,7 @@
 //! - ContentIdentity is optional (for deduplication)
 
 pub mod addressing;
+pub mod collection;
 pub mod content_identity;
 pub mod device;
 pub mod file;
@@ -16,6 +17,7 @@ pub mod memory;
 pub mod resource;
 pub mod resource_manager;
 pub mod resource_registry;
+pub mod saved_search;
 pub mod space;
 pub mod tag;
 pub mod user_metadata;
@@ -23,6 +25,7 @@ pub mod volume;
 
 // Re-export commonly used types
 pub use addressing::{PathResolutionError, SdPath, SdPathBatch, SdPathParseError};
+pub use collection::{Collection, CollectionFile};
 pub use content_identity::{ContentHashError, ContentHashGenerator, ContentIdentity, ContentKind};
 pub use device::{ConnectionMethod, Device, OperatingSystem};
 pub use file::{EntryKind, File, Sidecar};
@@ -32,6 +35,7 @@ pub use media_data::{AudioMediaData, ImageMediaData, VideoMediaData};
 pub use memory::{MemoryFile, MemoryMetadata, MemoryScope};
 pub use resource::{EventEmitter, Identifiable};
 pub use resource_manager::ResourceManager;
+pub use saved_search::{SavedSearch, SearchDefinition};
 pub use space::{
 	GroupType, ItemType, Space, SpaceGroup, SpaceGroupWithItems, SpaceItem, SpaceLayout,
 };
@@ -0,0 +1,137 @@
+//! Saved searches - named file searches that act as living folders
+//!
+//! A saved search stores what to search for, not its results. Every device runs it
+//! against its own index, so the result count is computed locally and kept up to date
+//! as files change, while the definition syncs across devices as a shared resource.
+
+use crate::domain::resource::Identifiable;
+use crate::ops::search::input::{
+	FileSearchInput, PaginationOptions, SearchFilters, SearchMode, SearchScope, SortOptions,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use uuid::Uuid;
+
+/// A named file search, run on demand or pinned to a space
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SavedSearch {
+	/// Unique identifier
+	pub id: Uuid,
+
+	pub name: String,
+
+	/// What the search matches
+	pub definition: SearchDefinition,
+
+	/// Number of files matching on this device, absent until counted
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub result_count: Option<u64>,
+
+	/// Timestamps
+	pub created_at: DateTime<Utc>,
+	pub updated_at: DateTime<Utc>,
+}
+
+/// A `FileSearchInput` without pagination
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SearchDefinition {
+	pub query: String,
+	#[serde(default)]
+	pub scope: SearchScope,
+	#[serde(default)]
+	pub mode: SearchMode,
+	#[serde(default)]
+	pub filters: SearchFilters,
+	#[serde(default)]
+	pub sort: SortOptions,
+}
+
+impl SearchDefinition {
+	/// Build the input for one page of results
+	pub fn to_input(&self, pagination: PaginationOptions) -> FileSearchInput {
+		FileSearchInput {
+			query: self.query.clone(),
+			scope: self.scope.clone(),
+			mode: self.mode.clone(),
+			filters: self.filters.clone(),
+			sort: self.sort.clone(),
+			pagination,
+		}
+	}
+
+	/// Check the definition with the same rules as a one-off search
+	pub fn validate(&self) -> Result<(), String> {
+		self.to_input(PaginationOptions::default()).validate()
+	}
+}
+
+impl From<FileSearchInput> for SearchDefinition {
+	fn from(input: FileSearchInput) -> Self {
+		Self {
+			query: input.query,
+			scope: input.scope,
+			mode: input.mode,
+			filters: input.filters,
+			sort: input.sort,
+		}
+	}
+}
+
+impl SavedSearch {
+	/// Build a saved search from its database model, without a result count
+	pub fn from_db_model(
+		model: crate::infra::db::entities::saved_search::Model,
+	) -> crate::common::errors::Result<Self> {
+		let definition = serde_json::from_value(model.definition).map_err(|e| {
+			crate::common::errors::CoreError::Other(anyhow::anyhow!(
+				"Invalid definition for saved search {}: {}",
+				model.uuid,
+				e
+			))
+		})?;
+
+		Ok(Self {
+			id: model.uuid,
+			name: model.name,
+			definition,
+			result_count: None,
+			created_at: model.created_at,
+			updated_at: model.updated_at,
+		})
+	}
+}
+
+impl Identifiable for SavedSearch {
+	fn id(&self) -> Uuid {
+		self.id
+	}
+
+	fn resource_type() -> &'static str {
+		"saved_search"
+	}
+
+	// Counts need the library's file types, so resources built here leave them out
+	// and clients keep the count they last received
+	async fn from_ids(
+		db: &sea_orm::DatabaseConnection,
+		ids: &[Uuid],
+	) -> crate::common::errors::Result<Vec<Self>>
+	where
+		Self: Sized,
+	{
+		use crate::infra::db::entities::saved_search;
+		use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+		saved_search::Entity::find()
+			.filter(saved_search::Column::Uuid.is_in(ids.to_vec()))
+			.all(db)
+			.await?
+			.into_iter()
+			.map(Self::from_db_model)
+			.collect()
+	}
+}
+
+// Register SavedSearch as a simple resource
+crate::register_resource!(SavedSearch);
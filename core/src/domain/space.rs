@@ -434,6 +434,15 @@ pub enum ItemType {
 	/// Tag filter
 	Tag { tag_id: Uuid },
 
+	/// Smart tag, listing the entries its composition rules match
+	SmartTag { tag_id: Uuid },
+
+	/// Hand-curated collection of files
+	Collection { collection_id: Uuid },
+
+	/// Saved search, listing the files it currently matches
+	SavedSearch { saved_search_id: Uuid },
+
 	/// Any arbitrary path (dragged from explorer)
 	Path { sd_path: SdPath },
 }
@@ -0,0 +1,338 @@
+//! Container-aware identification
+//!
+//! Many formats are ZIP or OLE (Compound File Binary) containers: DOCX, XLSX, EPUB, JAR
+//! and APK all start with the ZIP `50 4B 03 04` bytes, DOC, XLS and MSI with the same
+//! OLE header. Magic bytes can't tell them apart, what's inside the container can: the
+//! `mimetype` entry of OpenDocument and EPUB files, the main part listed in the
+//! `[Content_Types].xml` of Office Open XML packages, well-known entries of Java and
+//! Android archives, and the stream names of OLE files.
+
+use std::{
+	fs::File,
+	io::{self, Read, Seek, SeekFrom},
+	path::Path,
+};
+use uuid::Uuid;
+
+/// Header of a ZIP archive with at least one entry
+const ZIP_MAGIC: &[u8] = &[0x50, 0x4B, 0x03, 0x04];
+
+/// Header of an empty ZIP archive
+const ZIP_EMPTY_MAGIC: &[u8] = &[0x50, 0x4B, 0x05, 0x06];
+
+/// Header of an OLE compound file
+const OLE_MAGIC: &[u8] = &[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1];
+
+/// Largest `mimetype` or `[Content_Types].xml` entry read
+const MAX_ENTRY_BYTES: u64 = 64 * 1024;
+
+/// Root storage class of Windows Installer packages
+const MSI_CLSID: Uuid = Uuid::from_u128(0x000c1084_0000_0000_c000_000000000046);
+
+/// MIME type of plain ZIP archives
+const ZIP_MIME: &str = "application/zip";
+
+/// Container format a file's header belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerFormat {
+	Zip,
+	Ole,
+}
+
+impl ContainerFormat {
+	/// Detect the container format from the first bytes of a file
+	pub fn detect(header: &[u8]) -> Option<Self> {
+		if header.starts_with(ZIP_MAGIC) || header.starts_with(ZIP_EMPTY_MAGIC) {
+			Some(Self::Zip)
+		} else if header.starts_with(OLE_MAGIC) {
+			Some(Self::Ole)
+		} else {
+			None
+		}
+	}
+
+	/// Whether a magic byte pattern is the header of a container, which many formats share
+	pub fn is_container_pattern(pattern: &super::MagicBytePattern) -> bool {
+		pattern.offset == 0
+			&& [ZIP_MAGIC, ZIP_EMPTY_MAGIC, OLE_MAGIC]
+				.iter()
+				.any(|magic| pattern.matches(magic))
+	}
+}
+
+/// What the structure of a container says a file is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerIdentity {
+	pub format: ContainerFormat,
+
+	/// MIME types the structure points to, most specific first
+	pub mime_types: Vec<String>,
+
+	/// Whether a format inside the container was recognized, not only the container
+	pub is_specific: bool,
+}
+
+impl ContainerIdentity {
+	fn specific(format: ContainerFormat, mime_types: Vec<String>) -> Self {
+		Self {
+			format,
+			mime_types,
+			is_specific: true,
+		}
+	}
+}
+
+/// Inspect the structure of a file if it's a ZIP or OLE container
+///
+/// Blocking, call from `spawn_blocking`. Returns `None` for other files, for damaged
+/// containers, and for OLE files none of the known streams identify.
+pub fn inspect(path: &Path) -> io::Result<Option<ContainerIdentity>> {
+	let mut file = File::open(path)?;
+	let mut header = [0u8; 8];
+	let read = read_header(&mut file, &mut header)?;
+
+	let Some(format) = ContainerFormat::detect(&header[..read]) else {
+		return Ok(None);
+	};
+	file.seek(SeekFrom::Start(0))?;
+
+	Ok(match format {
+		ContainerFormat::Zip => inspect_zip(file),
+		ContainerFormat::Ole => inspect_ole(file),
+	})
+}
+
+fn read_header(file: &mut File, header: &mut [u8]) -> io::Result<usize> {
+	let mut read = 0;
+	while read < header.len() {
+		match file.read(&mut header[read..])? {
+			0 => break,
+			n => read += n,
+		}
+	}
+	Ok(read)
+}
+
+/// Identify a ZIP archive by its entries
+pub fn inspect_zip<R: Read + Seek>(reader: R) -> Option<ContainerIdentity> {
+	let mut archive = zip::ZipArchive::new(reader).ok()?;
+
+	// OpenDocument and EPUB store their MIME type as the first entry
+	if let Some(mime) = read_entry(&mut archive, "mimetype") {
+		let mime = mime.trim();
+		if mime.contains('/') && !mime.contains(char::is_whitespace) {
+			return Some(ContainerIdentity::specific(
+				ContainerFormat::Zip,
+				vec![mime.to_string()],
+			));
+		}
+	}
+
+	if let Some(content_types) = read_entry(&mut archive, "[Content_Types].xml") {
+		if let Some(mime) = ooxml_main_type(&content_types) {
+			// Macro-enabled formats are registered with a version suffix
+			let versioned = format!("{}.12", mime);
+			return Some(ContainerIdentity::specific(
+				ContainerFormat::Zip,
+				vec![mime, versioned],
+			));
+		}
+	}
+
+	// APKs are JARs too, so they're checked first
+	if archive.index_for_name("AndroidManifest.xml").is_some() {
+		return Some(ContainerIdentity::specific(
+			ContainerFormat::Zip,
+			vec!["application/vnd.android.package-archive".to_string()],
+		));
+	}
+	if archive.index_for_name("META-INF/MANIFEST.MF").is_some() {
+		return Some(ContainerIdentity::specific(
+			ContainerFormat::Zip,
+			vec!["application/java-archive".to_string()],
+		));
+	}
+
+	Some(ContainerIdentity {
+		format: ContainerFormat::Zip,
+		mime_types: vec![ZIP_MIME.to_string()],
+		is_specific: false,
+	})
+}
+
+fn read_entry<R: Read + Seek>(archive: &mut zip::ZipArchive<R>, name: &str) -> Option<String> {
+	let entry = archive.by_name(name).ok()?;
+	if entry.size() > MAX_ENTRY_BYTES {
+		return None;
+	}
+
+	let mut content = String::new();
+	entry
+		.take(MAX_ENTRY_BYTES)
+		.read_to_string(&mut content)
+		.ok()?;
+	Some(content)
+}
+
+/// MIME type of an Office Open XML package, from the content type of its main part
+///
+/// The main part's content type is the package's MIME type with a `.main+xml` suffix,
+/// e.g. `...wordprocessingml.document.main+xml` for DOCX, or `.main` for binary parts.
+fn ooxml_main_type(content_types: &str) -> Option<String> {
+	content_types
+		.split("ContentType=")
+		.skip(1)
+		.filter_map(|rest| {
+			let quote = rest.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+			rest[1..].split(quote).next()
+		})
+		.find_map(|content_type| {
+			content_type
+				.strip_suffix(".main+xml")
+				.or_else(|| content_type.strip_suffix(".main"))
+		})
+		.map(str::to_string)
+}
+
+/// Identify an OLE compound file by its root storage
+pub fn inspect_ole<R: Read + Seek>(reader: R) -> Option<ContainerIdentity> {
+	let compound = cfb::CompoundFile::open(reader).ok()?;
+
+	if *compound.root_entry().clsid() == MSI_CLSID {
+		return Some(ContainerIdentity::specific(
+			ContainerFormat::Ole,
+			vec!["application/x-msi".to_string()],
+		));
+	}
+
+	let mime = compound
+		.read_root_storage()
+		.find_map(|entry| match entry.name() {
+			"WordDocument" => Some("application/msword"),
+			"Workbook" | "Book" => Some("application/vnd.ms-excel"),
+			"PowerPoint Document" => Some("application/vnd.ms-powerpoint"),
+			"__properties_version1.0" => Some("application/vnd.ms-outlook"),
+			_ => None,
+		})?;
+
+	Some(ContainerIdentity::specific(
+		ContainerFormat::Ole,
+		vec![mime.to_string()],
+	))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::{Cursor, Write};
+
+	fn zip_with(entries: &[(&str, &str)]) -> Cursor<Vec<u8>> {
+		let mut zip = zip::ZipWriter::new(Cursor::new(Vec::new()));
+		for (name, data) in entries {
+			zip.start_file(*name, zip::write::SimpleFileOptions::default())
+				.unwrap();
+			zip.write_all(data.as_bytes()).unwrap();
+		}
+		let mut cursor = zip.finish().unwrap();
+		cursor.set_position(0);
+		cursor
+	}
+
+	fn ole_with(streams: &[&str]) -> Cursor<Vec<u8>> {
+		let mut compound = cfb::CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+		for stream in streams {
+			compound.create_stream(format!("/{}", stream)).unwrap();
+		}
+		compound.flush().unwrap();
+		let mut cursor = compound.into_inner();
+		cursor.set_position(0);
+		cursor
+	}
+
+	#[test]
+	fn detects_container_headers() {
+		assert_eq!(
+			ContainerFormat::detect(&[0x50, 0x4B, 0x03, 0x04, 0x14]),
+			Some(ContainerFormat::Zip)
+		);
+		assert_eq!(
+			ContainerFormat::detect(OLE_MAGIC),
+			Some(ContainerFormat::Ole)
+		);
+		assert_eq!(ContainerFormat::detect(&[0xFF, 0xD8, 0xFF]), None);
+		assert_eq!(ContainerFormat::detect(&[0x50]), None);
+	}
+
+	#[test]
+	fn identifies_zip_based_formats() {
+		let docx = zip_with(&[
+			(
+				"[Content_Types].xml",
+				r#"<Types><Default Extension="xml" ContentType="application/xml"/><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/></Types>"#,
+			),
+			("word/document.xml", "<w:document/>"),
+		]);
+		assert_eq!(
+			inspect_zip(docx).unwrap().mime_types[0],
+			"application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+		);
+
+		let xlsm = zip_with(&[(
+			"[Content_Types].xml",
+			r#"<Types><Override PartName="/xl/workbook.xml" ContentType='application/vnd.ms-excel.sheet.macroEnabled.main+xml'/></Types>"#,
+		)]);
+		assert_eq!(
+			inspect_zip(xlsm).unwrap().mime_types,
+			vec![
+				"application/vnd.ms-excel.sheet.macroEnabled",
+				"application/vnd.ms-excel.sheet.macroEnabled.12"
+			]
+		);
+
+		let epub = zip_with(&[("mimetype", "application/epub+zip"), ("OEBPS/a.xhtml", "")]);
+		assert_eq!(
+			inspect_zip(epub).unwrap().mime_types,
+			vec!["application/epub+zip"]
+		);
+
+		let apk = zip_with(&[("AndroidManifest.xml", ""), ("META-INF/MANIFEST.MF", "")]);
+		assert_eq!(
+			inspect_zip(apk).unwrap().mime_types,
+			vec!["application/vnd.android.package-archive"]
+		);
+
+		let jar = zip_with(&[("META-INF/MANIFEST.MF", "Manifest-Version: 1.0")]);
+		assert_eq!(
+			inspect_zip(jar).unwrap().mime_types,
+			vec!["application/java-archive"]
+		);
+
+		let plain = inspect_zip(zip_with(&[("notes.txt", "hello")])).unwrap();
+		assert!(!plain.is_specific);
+		assert_eq!(plain.mime_types, vec![ZIP_MIME]);
+	}
+
+	#[test]
+	fn identifies_ole_based_formats() {
+		let doc = inspect_ole(ole_with(&["WordDocument", "1Table"])).unwrap();
+		assert_eq!(doc.mime_types, vec!["application/msword"]);
+
+		let xls = inspect_ole(ole_with(&["Workbook"])).unwrap();
+		assert_eq!(xls.mime_types, vec!["application/vnd.ms-excel"]);
+
+		let ppt = inspect_ole(ole_with(&["PowerPoint Document"])).unwrap();
+		assert_eq!(ppt.mime_types, vec!["application/vnd.ms-powerpoint"]);
+
+		let mut msi = cfb::CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+		msi.set_storage_clsid("/", MSI_CLSID).unwrap();
+		msi.flush().unwrap();
+		let mut cursor = msi.into_inner();
+		cursor.set_position(0);
+		assert_eq!(
+			inspect_ole(cursor).unwrap().mime_types,
+			vec!["application/x-msi"]
+		);
+
+		assert_eq!(inspect_ole(ole_with(&["Unrelated"])), None);
+	}
+}
@@ -0,0 +1,179 @@
+//! User-defined file types
+//!
+//! Formats Spacedrive doesn't ship definitions for (instrument data, CAD, game assets)
+//! can be described by the user. They come from two places: TOML files in the
+//! `file_types` directory of the app data directory, in the same format as the built-in
+//! definitions, and the custom types in a library's settings. Both are matched by
+//! extension and magic bytes alongside the built-ins, priority deciding between them.
+
+use super::{FileType, FileTypeError, MagicBytePattern, Result};
+use crate::domain::ContentKind;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashSet;
+
+/// Directory of the app data directory holding custom TOML definitions
+pub const CUSTOM_DEFINITIONS_DIR: &str = "file_types";
+
+/// Priority given to custom types that don't set one, that of the most specific built-ins
+pub const DEFAULT_CUSTOM_PRIORITY: u8 = 100;
+
+fn default_priority() -> u8 {
+	DEFAULT_CUSTOM_PRIORITY
+}
+
+/// A file type defined by the user
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct CustomFileType {
+	/// Unique identifier in MIME form, e.g. "application/x-acme-scan"
+	pub id: String,
+
+	/// Human-readable name
+	pub name: String,
+
+	/// File extensions (without dots), may be empty if magic bytes identify the type
+	#[serde(default)]
+	pub extensions: Vec<String>,
+
+	/// MIME types, the id is used when none are given
+	#[serde(default)]
+	pub mime_types: Vec<String>,
+
+	/// Category for grouping
+	pub category: ContentKind,
+
+	/// Priority against other types sharing an extension (higher = preferred)
+	#[serde(default = "default_priority")]
+	pub priority: u8,
+
+	/// Magic byte patterns, any of which identifies the type
+	#[serde(default)]
+	pub magic_bytes: Vec<CustomMagicBytes>,
+}
+
+/// Magic bytes of a custom file type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct CustomMagicBytes {
+	/// Hex bytes separated by spaces, `??` matches any byte and `00-1F` a range
+	pub pattern: String,
+
+	/// Offset from start of file
+	#[serde(default)]
+	pub offset: usize,
+
+	/// Priority against other patterns matching the same file (higher = more specific)
+	#[serde(default = "default_priority")]
+	pub priority: u8,
+}
+
+/// Where a custom file type is defined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum CustomFileTypeSource {
+	/// A TOML file in the app data directory, shared by every library
+	AppData,
+	/// The library's settings
+	Library,
+}
+
+impl CustomFileType {
+	/// Normalize and check the definition
+	///
+	/// Extensions are lowercased and stripped of leading dots, like the indexer stores them.
+	pub fn normalized(mut self) -> std::result::Result<Self, String> {
+		self.id = self.id.trim().to_string();
+		self.name = self.name.trim().to_string();
+		for extension in &mut self.extensions {
+			*extension = extension.trim().trim_start_matches('.').to_lowercase();
+		}
+		let mut seen = HashSet::new();
+		self.extensions
+			.retain(|extension| !extension.is_empty() && seen.insert(extension.clone()));
+
+		let mut parts = self.id.split('/');
+		let is_mime_like = matches!(
+			(parts.next(), parts.next(), parts.next()),
+			(Some(kind), Some(subtype), None) if !kind.is_empty() && !subtype.is_empty()
+		);
+		if !is_mime_like || self.id.contains(char::is_whitespace) {
+			return Err(format!(
+				"File type id '{}' must look like a MIME type, e.g. application/x-acme-scan",
+				self.id
+			));
+		}
+		if self.name.is_empty() {
+			return Err("File type name cannot be empty".to_string());
+		}
+		if self.extensions.is_empty() && self.magic_bytes.is_empty() {
+			return Err(
+				"A file type needs at least one extension or magic byte pattern".to_string(),
+			);
+		}
+		if self
+			.extensions
+			.iter()
+			.any(|extension| extension.contains(|c: char| matches!(c, '/' | '\\' | '.')))
+		{
+			return Err("Extensions cannot contain dots or path separators".to_string());
+		}
+
+		self.to_file_type().map_err(|e| e.to_string())?;
+
+		Ok(self)
+	}
+
+	/// Convert to a registry file type
+	pub fn to_file_type(&self) -> Result<FileType> {
+		let magic_bytes = self
+			.magic_bytes
+			.iter()
+			.map(|magic| {
+				if magic.pattern.trim().is_empty() {
+					return Err("empty pattern".to_string());
+				}
+				MagicBytePattern::from_hex_string(&magic.pattern, magic.offset, magic.priority)
+			})
+			.collect::<std::result::Result<Vec<_>, _>>()
+			.map_err(|e| FileTypeError::InvalidConfig(format!("Invalid magic bytes: {}", e)))?;
+
+		let mime_types = if self.mime_types.is_empty() {
+			vec![self.id.clone()]
+		} else {
+			self.mime_types.clone()
+		};
+
+		Ok(FileType {
+			id: self.id.clone(),
+			name: self.name.clone(),
+			extensions: self.extensions.clone(),
+			mime_types,
+			uti: None,
+			magic_bytes,
+			category: self.category,
+			priority: self.priority,
+			metadata: serde_json::json!({ "custom": true }),
+		})
+	}
+}
+
+impl From<&FileType> for CustomFileType {
+	fn from(file_type: &FileType) -> Self {
+		Self {
+			id: file_type.id.clone(),
+			name: file_type.name.clone(),
+			extensions: file_type.extensions.clone(),
+			mime_types: file_type.mime_types.clone(),
+			category: file_type.category,
+			priority: file_type.priority,
+			magic_bytes: file_type
+				.magic_bytes
+				.iter()
+				.map(|pattern| CustomMagicBytes {
+					pattern: pattern.to_hex_string(),
+					offset: pattern.offset,
+					priority: pattern.priority,
+				})
+				.collect(),
+		}
+	}
+}
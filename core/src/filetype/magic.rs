@@ -83,6 +83,15 @@ impl MagicBytePattern {
 		true
 	}
 
+	/// Format the pattern back into the hex string it can be parsed from
+	pub fn to_hex_string(&self) -> String {
+		self.bytes
+			.iter()
+			.map(|byte| byte.to_string())
+			.collect::<Vec<_>>()
+			.join(" ")
+	}
+
 	/// Get the minimum buffer size needed to check this pattern
 	pub fn required_size(&self) -> usize {
 		self.offset + self.bytes.len()
@@ -146,6 +155,12 @@ mod tests {
 		assert!(matches!(pattern.bytes[3], MagicByte::Exact(0x47)));
 	}
 
+	#[test]
+	fn test_magic_byte_pattern_round_trip() {
+		let pattern = MagicBytePattern::from_hex_string("4d 5a ?? 00-1F", 2, 90).unwrap();
+		assert_eq!(pattern.to_hex_string(), "4D 5A ?? 00-1F");
+	}
+
 	#[test]
 	fn test_pattern_matching() {
 		let pattern = MagicBytePattern::from_hex_string("FF D8", 0, 100).unwrap();
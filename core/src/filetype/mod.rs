@@ -12,9 +12,13 @@ use thiserror::Error;
 use uuid::Uuid;
 
 pub mod builtin;
+pub mod container;
+pub mod custom;
 pub mod magic;
 pub mod registry;
 
+pub use container::{ContainerFormat, ContainerIdentity};
+pub use custom::{CustomFileType, CustomFileTypeSource, CustomMagicBytes};
 pub use magic::{MagicByte, MagicBytePattern};
 pub use registry::FileTypeRegistry;
 
@@ -74,6 +78,9 @@ pub enum IdentificationMethod {
 	/// Identified by content analysis
 	ContentAnalysis,
 
+	/// Identified by the structure of a ZIP or OLE container
+	Container,
+
 	/// Identified by multiple methods
 	Combined,
 }
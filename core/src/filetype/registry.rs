@@ -2,12 +2,17 @@
 
 use super::{FileType, FileTypeError, IdentificationMethod, IdentificationResult, Result};
 use crate::domain::ContentKind;
-use crate::filetype::magic::MagicBytePattern;
+use crate::filetype::{
+	container::{self, ContainerFormat},
+	custom::{CustomFileType, CustomFileTypeSource, CUSTOM_DEFINITIONS_DIR},
+	magic::MagicBytePattern,
+};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::path::Path;
 use tokio::fs::File;
 use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tracing::warn;
 
 /// Maximum bytes to read for magic byte identification
 const MAX_MAGIC_BYTES: usize = 8192;
@@ -47,6 +52,7 @@ struct MagicByteDefinition {
 }
 
 /// Registry of all known file types
+#[derive(Clone)]
 pub struct FileTypeRegistry {
 	/// All registered file types by ID
 	types: HashMap<String, FileType>,
@@ -56,6 +62,9 @@ pub struct FileTypeRegistry {
 
 	/// MIME type to type ID mapping
 	mime_map: HashMap<String, String>,
+
+	/// Where each user-defined type comes from, built-ins aren't listed
+	custom: HashMap<String, CustomFileTypeSource>,
 }
 
 impl FileTypeRegistry {
@@ -65,6 +74,7 @@ impl FileTypeRegistry {
 			types: HashMap::new(),
 			extension_map: HashMap::new(),
 			mime_map: HashMap::new(),
+			custom: HashMap::new(),
 		};
 
 		// Load built-in types
@@ -73,6 +83,43 @@ impl FileTypeRegistry {
 		registry
 	}
 
+	/// Create a registry with built-in types and those defined in the app data directory
+	pub fn with_app_definitions(data_dir: &Path) -> Self {
+		let mut registry = Self::new();
+
+		let dir = data_dir.join(CUSTOM_DEFINITIONS_DIR);
+		if dir.is_dir() {
+			if let Err(e) = registry.load_from_dir(&dir) {
+				warn!(
+					"Failed to load file type definitions from {}: {}",
+					dir.display(),
+					e
+				);
+			}
+		}
+
+		registry
+	}
+
+	/// A copy of this registry with a library's custom types added
+	///
+	/// Invalid definitions, or ones reusing the id of a type already registered, are
+	/// skipped with a warning rather than failing the whole library.
+	pub fn with_custom_types(&self, custom_types: &[CustomFileType]) -> Self {
+		let mut registry = self.clone();
+
+		for custom_type in custom_types {
+			let result = custom_type.to_file_type().and_then(|file_type| {
+				registry.register_custom(file_type, CustomFileTypeSource::Library)
+			});
+			if let Err(e) = result {
+				warn!("Skipping custom file type {}: {}", custom_type.id, e);
+			}
+		}
+
+		registry
+	}
+
 	/// Load built-in file type definitions
 	fn load_builtin_types(&mut self) {
 		// Load all TOML definitions from the builtin module
@@ -109,11 +156,46 @@ impl FileTypeRegistry {
 		Ok(())
 	}
 
+	/// Register a user-defined file type, which can't replace a type already registered
+	pub fn register_custom(
+		&mut self,
+		file_type: FileType,
+		source: CustomFileTypeSource,
+	) -> Result<()> {
+		if self.types.contains_key(&file_type.id) {
+			return Err(FileTypeError::InvalidConfig(format!(
+				"A file type with id {} already exists",
+				file_type.id
+			)));
+		}
+
+		let id = file_type.id.clone();
+		self.register(file_type)?;
+		self.custom.insert(id, source);
+		Ok(())
+	}
+
 	/// Get a file type by ID
 	pub fn get(&self, id: &str) -> Option<&FileType> {
 		self.types.get(id)
 	}
 
+	/// User-defined file types and where they're defined, sorted by ID
+	pub fn custom_types(&self) -> Vec<(&FileType, CustomFileTypeSource)> {
+		let mut custom_types: Vec<_> = self
+			.custom
+			.iter()
+			.filter_map(|(id, source)| self.types.get(id).map(|file_type| (file_type, *source)))
+			.collect();
+		custom_types.sort_by(|(a, _), (b, _)| a.id.cmp(&b.id));
+		custom_types
+	}
+
+	/// Whether a type ships with Spacedrive rather than being user-defined
+	pub fn is_builtin(&self, id: &str) -> bool {
+		self.types.contains_key(id) && !self.custom.contains_key(id)
+	}
+
 	/// Get file types by extension
 	pub fn get_by_extension(&self, ext: &str) -> Vec<&FileType> {
 		let ext = ext.trim_start_matches('.').to_lowercase();
@@ -176,6 +258,17 @@ impl FileTypeRegistry {
 
 	/// Identify a file type from a path
 	pub async fn identify(&self, path: &Path) -> Result<IdentificationResult> {
+		let result = self.identify_by_candidates(path).await;
+
+		// ZIP and OLE based formats share their magic bytes, their structure tells them apart
+		match self.identify_by_container(path, &result).await {
+			Some(found) => Ok(found),
+			None => result,
+		}
+	}
+
+	/// Identify by extension, then magic bytes and content analysis between candidates
+	async fn identify_by_candidates(&self, path: &Path) -> Result<IdentificationResult> {
 		// Get extension
 		let extension = path.extension().and_then(|s| s.to_str()).unwrap_or("");
 
@@ -215,11 +308,96 @@ impl FileTypeRegistry {
 			}
 			_ => {
 				// Multiple candidates, use magic bytes to resolve
-				self.identify_by_magic_bytes(path, &candidates).await
+				let result = self.identify_by_magic_bytes(path, &candidates).await;
+				if matches!(&result, Ok(found) if matches!(found.method, IdentificationMethod::MagicBytes))
+				{
+					return result;
+				}
+
+				// Otherwise a custom type outranking the built-ins wins over content analysis
+				match Self::identify_by_priority(&candidates) {
+					Ok(fallback)
+						if result.is_err() || self.custom.contains_key(&fallback.file_type.id) =>
+					{
+						Ok(fallback)
+					}
+					_ => result,
+				}
 			}
 		}
 	}
 
+	/// Identify a ZIP or OLE container by what's inside it
+	///
+	/// Only files that may be containers are opened: the ones identified as a type with a
+	/// container header, or not identified with confidence. A recognized format replaces
+	/// the current result, a plain ZIP only replaces guesses the file's bytes didn't
+	/// confirm. Custom types are left alone, the user knows what their files are.
+	async fn identify_by_container(
+		&self,
+		path: &Path,
+		current: &Result<IdentificationResult>,
+	) -> Option<IdentificationResult> {
+		if let Ok(found) = current {
+			if self.custom.contains_key(&found.file_type.id) {
+				return None;
+			}
+			let is_container_type = found
+				.file_type
+				.magic_bytes
+				.iter()
+				.any(ContainerFormat::is_container_pattern);
+			if found.confidence >= 90 && !is_container_type {
+				return None;
+			}
+		}
+
+		let owned_path = path.to_path_buf();
+		let identity = tokio::task::spawn_blocking(move || container::inspect(&owned_path))
+			.await
+			.ok()?
+			.ok()??;
+
+		if !identity.is_specific {
+			if let Ok(found) = current {
+				let confirmed = matches!(found.method, IdentificationMethod::Combined)
+					|| (matches!(found.method, IdentificationMethod::Extension)
+						&& found.confidence >= 90);
+				if confirmed {
+					return None;
+				}
+			}
+		}
+
+		let file_type = identity
+			.mime_types
+			.iter()
+			.find_map(|mime| self.get_by_mime(mime))?;
+
+		Some(IdentificationResult {
+			file_type: file_type.clone(),
+			confidence: if identity.is_specific { 98 } else { 95 },
+			method: IdentificationMethod::Container,
+		})
+	}
+
+	/// Pick the highest priority candidate that doesn't need magic bytes to match
+	///
+	/// Used when several types share an extension and none of their magic bytes match.
+	/// Ties go to the type registered last, so custom types win them over built-ins.
+	fn identify_by_priority(candidates: &[&FileType]) -> Result<IdentificationResult> {
+		candidates
+			.iter()
+			.filter(|file_type| file_type.magic_bytes.is_empty())
+			.max_by_key(|file_type| file_type.priority)
+			.map(|file_type| IdentificationResult {
+				file_type: (*file_type).clone(),
+				confidence: 60,
+				method: IdentificationMethod::Extension,
+			})
+			.ok_or(FileTypeError::UnknownType)
+	}
+
 	/// Identify by magic bytes from a set of candidates
 	async fn identify_by_magic_bytes(
 		&self,
@@ -244,8 +422,10 @@ impl FileTypeRegistry {
 			}
 		}
 
-		// Sort by priority (highest first)
-		matches.sort_by_key(|(_, priority)| std::cmp::Reverse(*priority));
+		// Sort by pattern priority, then type priority (highest first)
+		matches.sort_by_key(|(file_type, priority)| {
+			std::cmp::Reverse((*priority, file_type.priority))
+		});
 
 		if let Some((file_type, _)) = matches.first() {
 			Ok(IdentificationResult {
@@ -341,6 +521,50 @@ impl FileTypeRegistry {
 		Ok(())
 	}
 
+	/// Load user-defined types from the TOML files of a directory, returns how many
+	///
+	/// A file that fails to parse is skipped with a warning, so one mistake doesn't
+	/// hide every other definition.
+	pub fn load_from_dir(&mut self, dir: &Path) -> Result<usize> {
+		let mut paths = std::fs::read_dir(dir)?
+			.filter_map(|entry| entry.ok().map(|entry| entry.path()))
+			.filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+			.collect::<Vec<_>>();
+		paths.sort();
+
+		let mut loaded = 0;
+		for path in paths {
+			let result = std::fs::read_to_string(&path)
+				.map_err(FileTypeError::from)
+				.and_then(|content| {
+					toml::from_str::<FileTypeDefinitions>(&content).map_err(|e| {
+						FileTypeError::InvalidConfig(format!("TOML parse error: {}", e))
+					})
+				});
+
+			let defs = match result {
+				Ok(defs) => defs,
+				Err(e) => {
+					warn!("Skipping file type definitions {}: {}", path.display(), e);
+					continue;
+				}
+			};
+
+			for def in defs.file_types {
+				let id = def.id.clone();
+				let result = self.definition_to_file_type(def).and_then(|file_type| {
+					self.register_custom(file_type, CustomFileTypeSource::AppData)
+				});
+				match result {
+					Ok(()) => loaded += 1,
+					Err(e) => warn!("Skipping file type {} in {}: {}", id, path.display(), e),
+				}
+			}
+		}
+
+		Ok(loaded)
+	}
+
 	/// Convert a definition to a FileType
 	fn definition_to_file_type(&self, def: FileTypeDefinition) -> Result<FileType> {
 		// Parse category
@@ -424,4 +648,132 @@ mod tests {
 		let ts_types = registry.get_by_extension("ts");
 		assert_eq!(ts_types.len(), 2); // TypeScript and MPEG-TS
 	}
+
+	fn write_zip(path: &Path, entries: &[(&str, &str)]) {
+		use std::io::Write;
+
+		let mut zip = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+		for (name, data) in entries {
+			zip.start_file(*name, zip::write::SimpleFileOptions::default())
+				.unwrap();
+			zip.write_all(data.as_bytes()).unwrap();
+		}
+		zip.finish().unwrap();
+	}
+
+	#[tokio::test]
+	async fn test_container_identification() {
+		let temp_dir = tempfile::TempDir::new().unwrap();
+		let registry = FileTypeRegistry::new();
+		let docx = [(
+			"[Content_Types].xml",
+			r#"<Types><Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/></Types>"#,
+		)];
+
+		// Extension-less and mislabeled documents are identified by their structure
+		for name in ["report", "report.xlsx"] {
+			let path = temp_dir.path().join(name);
+			write_zip(&path, &docx);
+			let result = registry.identify(&path).await.unwrap();
+			assert_eq!(
+				result.file_type.id,
+				"application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+			);
+			assert_eq!(result.file_type.category, ContentKind::Document);
+			assert!(matches!(result.method, IdentificationMethod::Container));
+		}
+
+		let epub = temp_dir.path().join("book");
+		write_zip(&epub, &[("mimetype", "application/epub+zip")]);
+		let result = registry.identify(&epub).await.unwrap();
+		assert_eq!(result.file_type.category, ContentKind::Book);
+
+		// A plain ZIP doesn't override an extension its header confirms
+		let jar = temp_dir.path().join("plugin.jar");
+		write_zip(&jar, &[("plugin.class", "")]);
+		let result = registry.identify(&jar).await.unwrap();
+		assert_eq!(result.file_type.id, "application/java-archive");
+
+		// But does replace one it contradicts
+		let photo = temp_dir.path().join("photo.jpg");
+		write_zip(&photo, &[("photo.jpg", "")]);
+		let result = registry.identify(&photo).await.unwrap();
+		assert_eq!(result.file_type.id, "application/zip");
+	}
+
+	fn custom_type(id: &str, extensions: &[&str], magic: Option<&str>) -> CustomFileType {
+		CustomFileType {
+			id: id.to_string(),
+			name: id.to_string(),
+			extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+			mime_types: Vec::new(),
+			category: ContentKind::Document,
+			priority: 100,
+			magic_bytes: magic
+				.into_iter()
+				.map(|pattern| crate::filetype::custom::CustomMagicBytes {
+					pattern: pattern.to_string(),
+					offset: 0,
+					priority: 100,
+				})
+				.collect(),
+		}
+	}
+
+	#[tokio::test]
+	async fn test_custom_types() {
+		let temp_dir = tempfile::TempDir::new().unwrap();
+		let registry = FileTypeRegistry::new().with_custom_types(&[
+			custom_type("application/x-acme-scan", &["acme"], None),
+			custom_type("application/x-acme-raw", &[], Some("41 43 4D 45")),
+			// Built-in ids can't be redefined
+			custom_type("image/jpeg", &["jpg"], None),
+		]);
+
+		let scan = temp_dir.path().join("sample.acme");
+		tokio::fs::write(&scan, b"measurements").await.unwrap();
+		let result = registry.identify(&scan).await.unwrap();
+		assert_eq!(result.file_type.id, "application/x-acme-scan");
+		assert_eq!(
+			result.file_type.primary_mime_type(),
+			Some("application/x-acme-scan")
+		);
+
+		let raw = temp_dir.path().join("capture.001");
+		tokio::fs::write(&raw, b"ACME\x01\x02").await.unwrap();
+		let result = registry.identify(&raw).await.unwrap();
+		assert_eq!(result.file_type.id, "application/x-acme-raw");
+
+		assert_eq!(registry.get_by_extension("jpg").len(), 1);
+		assert_eq!(registry.custom_types().len(), 2);
+		assert!(registry.is_builtin("image/jpeg"));
+		assert!(!registry.is_builtin("application/x-acme-scan"));
+	}
+
+	#[tokio::test]
+	async fn test_custom_type_priority_against_builtins() {
+		let temp_dir = tempfile::TempDir::new().unwrap();
+		let script = temp_dir.path().join("script.ts");
+		tokio::fs::write(&script, b"export const a = 1;")
+			.await
+			.unwrap();
+		let stream = temp_dir.path().join("stream.ts");
+		tokio::fs::write(&stream, [0x47, 0x40, 0x00, 0x10])
+			.await
+			.unwrap();
+
+		let builtin = FileTypeRegistry::new();
+		let result = builtin.identify(&script).await.unwrap();
+		assert_eq!(result.file_type.id, "text/typescript");
+
+		// A custom type without magic bytes outranks content analysis...
+		let registry =
+			builtin.with_custom_types(&[custom_type("application/x-acme-table", &["ts"], None)]);
+		let result = registry.identify(&script).await.unwrap();
+		assert_eq!(result.file_type.id, "application/x-acme-table");
+
+		// ...but not a built-in whose magic bytes match
+		let result = registry.identify(&stream).await.unwrap();
+		assert_eq!(result.file_type.id, "video/mp2t");
+	}
 }
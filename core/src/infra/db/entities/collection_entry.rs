@@ -16,6 +16,9 @@ pub struct Model {
 
 	pub added_at: DateTime<Utc>,
 
+	/// Order within the collection, ties broken by `added_at`
+	pub position: i32,
+
 	// Sync fields
 	pub uuid: Uuid,
 	pub version: i64,
@@ -180,6 +183,15 @@ impl Syncable for Model {
 				)
 				.map_err(|e| sea_orm::DbErr::Custom(format!("Invalid added_at: {}", e)))?;
 
+				// Absent from devices that predate ordering
+				let position: i32 = data
+					.get("position")
+					.cloned()
+					.map(serde_json::from_value)
+					.transpose()
+					.map_err(|e| sea_orm::DbErr::Custom(format!("Invalid position: {}", e)))?
+					.unwrap_or(0);
+
 				let version: i64 = serde_json::from_value(
 					data.get("version")
 						.ok_or_else(|| sea_orm::DbErr::Custom("Missing version".to_string()))?
@@ -191,6 +203,7 @@ impl Syncable for Model {
 					collection_id: Set(collection_id),
 					entry_id: Set(entry_id),
 					added_at: Set(added_at),
+					position: Set(position),
 					uuid: Set(uuid),
 					version: Set(version),
 					updated_at: Set(Utc::now()),
@@ -203,6 +216,7 @@ impl Syncable for Model {
 								Column::CollectionId,
 								Column::EntryId,
 								Column::AddedAt,
+								Column::Position,
 								Column::Version,
 								Column::UpdatedAt,
 							])
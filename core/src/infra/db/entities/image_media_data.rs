@@ -23,6 +23,8 @@ pub struct Model {
 	pub focal_length: Option<String>,
 	pub aperture: Option<String>,
 	pub shutter_speed: Option<String>,
+	pub exposure_time: Option<String>,
+	pub exposure_bias: Option<String>,
 	pub iso: Option<i32>,
 	pub orientation: Option<i16>,
 	pub color_space: Option<String>,
@@ -217,6 +219,18 @@ impl Syncable for Model {
 							.unwrap_or(serde_json::Value::Null),
 					)
 					.unwrap()),
+					exposure_time: Set(serde_json::from_value(
+						data.get("exposure_time")
+							.cloned()
+							.unwrap_or(serde_json::Value::Null),
+					)
+					.unwrap()),
+					exposure_bias: Set(serde_json::from_value(
+						data.get("exposure_bias")
+							.cloned()
+							.unwrap_or(serde_json::Value::Null),
+					)
+					.unwrap()),
 					iso: Set(serde_json::from_value(
 						data.get("iso").cloned().unwrap_or(serde_json::Value::Null),
 					)
@@ -283,6 +297,8 @@ impl Syncable for Model {
 								Column::FocalLength,
 								Column::Aperture,
 								Column::ShutterSpeed,
+								Column::ExposureTime,
+								Column::ExposureBias,
 								Column::Iso,
 								Column::Orientation,
 								Column::ColorSpace,
@@ -0,0 +1,50 @@
+//! Location indexer rule entity
+//!
+//! Links a location to the indexer rules applied on top of the library toggles
+//! when indexing it.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "location_indexer_rules")]
+pub struct Model {
+	#[sea_orm(primary_key, auto_increment = false)]
+	pub location_id: i32,
+
+	#[sea_orm(primary_key, auto_increment = false)]
+	pub indexer_rule_id: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "super::location::Entity",
+		from = "Column::LocationId",
+		to = "super::location::Column::Id",
+		on_delete = "Cascade"
+	)]
+	Location,
+
+	#[sea_orm(
+		belongs_to = "super::indexer_rule::Entity",
+		from = "Column::IndexerRuleId",
+		to = "super::indexer_rule::Column::Id",
+		on_delete = "Cascade"
+	)]
+	IndexerRule,
+}
+
+impl Related<super::location::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Location.def()
+	}
+}
+
+impl Related<super::indexer_rule::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::IndexerRule.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
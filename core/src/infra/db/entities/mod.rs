@@ -13,10 +13,13 @@ pub mod entry;
 pub mod entry_closure;
 pub mod image_media_data;
 pub mod location;
+pub mod location_indexer_rule;
 pub mod mime_type;
+pub mod perceptual_hash;
 pub mod user_metadata;
 
 // Tagging system
+pub mod smart_tag_member;
 pub mod tag;
 pub mod tag_closure;
 pub mod tag_relationship;
@@ -27,12 +30,14 @@ pub mod audit_log;
 pub mod collection;
 pub mod collection_entry;
 pub mod indexer_rule;
+pub mod saved_search;
 pub mod sidecar;
 pub mod sidecar_availability;
 pub mod space;
 pub mod space_group;
 pub mod space_item;
 pub mod sync_conduit;
+pub mod sync_conflict;
 pub mod sync_generation;
 pub mod video_media_data;
 pub mod volume;
@@ -52,18 +57,23 @@ pub use entry_closure::Entity as EntryClosure;
 pub use image_media_data::Entity as ImageMediaData;
 pub use indexer_rule::Entity as IndexerRule;
 pub use location::Entity as Location;
+pub use location_indexer_rule::Entity as LocationIndexerRule;
+pub use perceptual_hash::Entity as PerceptualHash;
+pub use saved_search::Entity as SavedSearch;
 pub use sidecar::Entity as Sidecar;
 pub use sidecar_availability::Entity as SidecarAvailability;
 pub use space::Entity as Space;
 pub use space_group::Entity as SpaceGroup;
 pub use space_item::Entity as SpaceItem;
 pub use sync_conduit::Entity as SyncConduit;
+pub use sync_conflict::Entity as SyncConflict;
 pub use sync_generation::Entity as SyncGeneration;
 pub use user_metadata::Entity as UserMetadata;
 pub use video_media_data::Entity as VideoMediaData;
 pub use volume::Entity as Volume;
 
 // Tagging entities
+pub use smart_tag_member::Entity as SmartTagMember;
 pub use tag::Entity as Tag;
 pub use tag_closure::Entity as TagClosure;
 pub use tag_relationship::Entity as TagRelationship;
@@ -85,12 +95,17 @@ pub use entry_closure::ActiveModel as EntryClosureActive;
 pub use image_media_data::ActiveModel as ImageMediaDataActive;
 pub use indexer_rule::ActiveModel as IndexerRuleActive;
 pub use location::ActiveModel as LocationActive;
+pub use location_indexer_rule::ActiveModel as LocationIndexerRuleActive;
+pub use perceptual_hash::ActiveModel as PerceptualHashActive;
+pub use saved_search::ActiveModel as SavedSearchActive;
 pub use sidecar::ActiveModel as SidecarActive;
 pub use sidecar_availability::ActiveModel as SidecarAvailabilityActive;
+pub use smart_tag_member::ActiveModel as SmartTagMemberActive;
 pub use space::ActiveModel as SpaceActive;
 pub use space_group::ActiveModel as SpaceGroupActive;
 pub use space_item::ActiveModel as SpaceItemActive;
 pub use sync_conduit::ActiveModel as SyncConduitActive;
+pub use sync_conflict::ActiveModel as SyncConflictActive;
 pub use sync_generation::ActiveModel as SyncGenerationActive;
 pub use user_metadata::ActiveModel as UserMetadataActive;
 pub use video_media_data::ActiveModel as VideoMediaDataActive;
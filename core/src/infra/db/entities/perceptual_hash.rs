@@ -0,0 +1,33 @@
+//! Perceptual hash entity for near-duplicate image and video detection
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "perceptual_hashes")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: i32,
+
+	/// Content hash of the file the hashes were computed from
+	#[sea_orm(unique, indexed)]
+	pub content_hash: String,
+
+	/// "image" or "video"
+	pub media_kind: String,
+
+	// 64-bit hashes are stored bit-for-bit in signed columns
+	pub phash: i64,
+	pub dhash: Option<i64>,
+	pub keyframe_hashes: Option<Json>, // Vec<(f64, i64)> position and pHash of video keyframes as JSON
+
+	pub width: Option<i32>,
+	pub height: Option<i32>,
+
+	pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -0,0 +1,153 @@
+//! Saved search entity
+
+use crate::infra::sync::{ChangeType, SharedChangeEntry, Syncable};
+use sea_orm::entity::prelude::*;
+use sea_orm::{ActiveValue::NotSet, Set};
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "saved_searches")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: i32,
+	#[sea_orm(unique)]
+	pub uuid: Uuid,
+	pub name: String,
+	/// `SearchDefinition` as JSON
+	pub definition: Json,
+	pub created_at: DateTimeUtc,
+	pub updated_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+// Syncable Implementation
+//
+// Saved searches are SHARED resources, any device can create or change them.
+// Result counts are computed by each device against its own index and never synced.
+impl Syncable for Model {
+	const SYNC_MODEL: &'static str = "saved_search";
+
+	fn sync_id(&self) -> Uuid {
+		self.uuid
+	}
+
+	fn version(&self) -> i64 {
+		self.updated_at.timestamp()
+	}
+
+	fn exclude_fields() -> Option<&'static [&'static str]> {
+		Some(&["id"])
+	}
+
+	fn sync_depends_on() -> &'static [&'static str] {
+		&[]
+	}
+
+	async fn query_for_sync(
+		_device_id: Option<Uuid>,
+		since: Option<chrono::DateTime<chrono::Utc>>,
+		cursor: Option<(chrono::DateTime<chrono::Utc>, Uuid)>,
+		batch_size: usize,
+		db: &DatabaseConnection,
+	) -> Result<Vec<(Uuid, serde_json::Value, chrono::DateTime<chrono::Utc>)>, sea_orm::DbErr> {
+		use sea_orm::{Condition, QueryOrder, QuerySelect};
+
+		let mut query = Entity::find();
+
+		if let Some(since_time) = since {
+			query = query.filter(Column::UpdatedAt.gte(since_time));
+		}
+
+		if let Some((cursor_ts, cursor_uuid)) = cursor {
+			query = query.filter(
+				Condition::any().add(Column::UpdatedAt.gt(cursor_ts)).add(
+					Condition::all()
+						.add(Column::UpdatedAt.eq(cursor_ts))
+						.add(Column::Uuid.gt(cursor_uuid)),
+				),
+			);
+		}
+
+		let results = query
+			.order_by_asc(Column::UpdatedAt)
+			.order_by_asc(Column::Uuid)
+			.limit(batch_size as u64)
+			.all(db)
+			.await?;
+
+		let mut sync_results = Vec::new();
+		for saved_search in results {
+			let json = match saved_search.to_sync_json() {
+				Ok(j) => j,
+				Err(e) => {
+					tracing::warn!(error = %e, uuid = %saved_search.uuid, "Failed to serialize saved search for sync");
+					continue;
+				}
+			};
+
+			sync_results.push((saved_search.uuid, json, saved_search.updated_at));
+		}
+
+		Ok(sync_results)
+	}
+
+	async fn apply_shared_change(
+		entry: SharedChangeEntry,
+		db: &DatabaseConnection,
+	) -> Result<(), sea_orm::DbErr> {
+		match entry.change_type {
+			ChangeType::Insert | ChangeType::Update => {
+				let data = entry.data.as_object().ok_or_else(|| {
+					sea_orm::DbErr::Custom("Saved search data is not an object".to_string())
+				})?;
+
+				let field = |name: &str| {
+					data.get(name)
+						.cloned()
+						.ok_or_else(|| sea_orm::DbErr::Custom(format!("Missing {}", name)))
+				};
+				let invalid = |name: &str, e: serde_json::Error| {
+					sea_orm::DbErr::Custom(format!("Invalid {}: {}", name, e))
+				};
+
+				let active = ActiveModel {
+					id: NotSet,
+					uuid: Set(entry.record_uuid),
+					name: Set(
+						serde_json::from_value(field("name")?).map_err(|e| invalid("name", e))?
+					),
+					definition: Set(field("definition")?),
+					created_at: Set(serde_json::from_value(field("created_at")?)
+						.map_err(|e| invalid("created_at", e))?),
+					updated_at: Set(serde_json::from_value(field("updated_at")?)
+						.map_err(|e| invalid("updated_at", e))?),
+				};
+
+				Entity::insert(active)
+					.on_conflict(
+						sea_orm::sea_query::OnConflict::column(Column::Uuid)
+							.update_columns([Column::Name, Column::Definition, Column::UpdatedAt])
+							.to_owned(),
+					)
+					.exec(db)
+					.await?;
+			}
+
+			ChangeType::Delete => {
+				Entity::delete_many()
+					.filter(Column::Uuid.eq(entry.record_uuid))
+					.exec(db)
+					.await?;
+			}
+		}
+
+		Ok(())
+	}
+}
+
+// Register with sync system via inventory
+crate::register_syncable_shared!(Model, "saved_search", "saved_searches");
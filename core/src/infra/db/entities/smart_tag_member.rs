@@ -0,0 +1,51 @@
+//! Smart tag membership entity
+//!
+//! Entries matched by a tag's composition rules. Derived locally, never synced.
+
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "smart_tag_members")]
+pub struct Model {
+	#[sea_orm(primary_key, auto_increment = false)]
+	pub tag_id: i32,
+
+	#[sea_orm(primary_key, auto_increment = false)]
+	pub entry_id: i32,
+
+	pub matched_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "super::tag::Entity",
+		from = "Column::TagId",
+		to = "super::tag::Column::Id",
+		on_delete = "Cascade"
+	)]
+	Tag,
+
+	#[sea_orm(
+		belongs_to = "super::entry::Entity",
+		from = "Column::EntryId",
+		to = "super::entry::Column::Id",
+		on_delete = "Cascade"
+	)]
+	Entry,
+}
+
+impl Related<super::tag::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Tag.def()
+	}
+}
+
+impl Related<super::entry::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::Entry.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
@@ -1,6 +1,7 @@
 use chrono::{DateTime, Utc};
 use sea_orm::entity::prelude::*;
 use serde::{Deserialize, Serialize};
+use specta::Type;
 use uuid::Uuid;
 
 /// Represents a sync relationship between two directories
@@ -31,6 +32,9 @@ pub struct Model {
 	/// Sync schedule: "instant", "interval:5m", or "manual"
 	pub schedule: String,
 
+	/// Conflict strategy: "newest_wins", "source_wins", "target_wins", "create_conflict_file" or "prompt_user"
+	pub conflict_strategy: String,
+
 	/// Whether to use indexer rules for filtering
 	pub use_index_rules: bool,
 
@@ -86,6 +90,10 @@ pub enum Relation {
 	/// One-to-many relationship with sync generations
 	#[sea_orm(has_many = "super::sync_generation::Entity")]
 	SyncGenerations,
+
+	/// One-to-many relationship with recorded conflicts
+	#[sea_orm(has_many = "super::sync_conflict::Entity")]
+	SyncConflicts,
 }
 
 impl Related<super::sync_generation::Entity> for Entity {
@@ -94,10 +102,16 @@ impl Related<super::sync_generation::Entity> for Entity {
 	}
 }
 
+impl Related<super::sync_conflict::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::SyncConflicts.def()
+	}
+}
+
 impl ActiveModelBehavior for ActiveModel {}
 
 /// Sync mode variants
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
 pub enum SyncMode {
 	/// One-way sync from source to target with automatic cleanup
 	Mirror,
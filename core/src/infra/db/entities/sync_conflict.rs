@@ -0,0 +1,118 @@
+use chrono::{DateTime, Utc};
+use sea_orm::entity::prelude::*;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use uuid::Uuid;
+
+/// A file sync conflict waiting for, or resolved by, a user decision
+///
+/// Recorded when a conduit's conflict strategy asks the user. Both sides' metadata
+/// is captured at detection time. A resolution is applied by the next generation,
+/// which then sets `applied_at`.
+#[derive(Clone, Debug, PartialEq, Eq, DeriveEntityModel, Serialize, Deserialize)]
+#[sea_orm(table_name = "sync_conflict")]
+pub struct Model {
+	#[sea_orm(primary_key)]
+	pub id: i32,
+
+	#[sea_orm(unique)]
+	pub uuid: Uuid,
+
+	/// Foreign key to sync_conduit
+	pub conduit_id: i32,
+
+	/// Generation that first detected the conflict
+	pub generation_id: i32,
+
+	/// Path relative to the conduit's source and target directories
+	pub relative_path: String,
+
+	/// "both_modified", "deleted_vs_modified" or "type_mismatch"
+	pub conflict_type: String,
+
+	/// Source side entry, None once it is no longer indexed
+	pub source_entry_id: Option<i32>,
+	pub source_size: i64,
+	pub source_modified_at: DateTime<Utc>,
+
+	/// Target side entry, None once it is no longer indexed
+	pub target_entry_id: Option<i32>,
+	pub target_size: i64,
+	pub target_modified_at: DateTime<Utc>,
+
+	pub detected_at: DateTime<Utc>,
+
+	/// "keep_source", "keep_target" or "keep_both", None while unresolved
+	pub resolution: Option<String>,
+	pub resolved_at: Option<DateTime<Utc>>,
+
+	/// When a generation carried out the resolution
+	pub applied_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+	#[sea_orm(
+		belongs_to = "super::sync_conduit::Entity",
+		from = "Column::ConduitId",
+		to = "super::sync_conduit::Column::Id"
+	)]
+	SyncConduit,
+
+	#[sea_orm(
+		belongs_to = "super::sync_generation::Entity",
+		from = "Column::GenerationId",
+		to = "super::sync_generation::Column::Id"
+	)]
+	SyncGeneration,
+}
+
+impl Related<super::sync_conduit::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::SyncConduit.def()
+	}
+}
+
+impl Related<super::sync_generation::Entity> for Entity {
+	fn to() -> RelationDef {
+		Relation::SyncGeneration.def()
+	}
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+/// How the user decided a conflict
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub enum Resolution {
+	/// Overwrite the target with the source version
+	KeepSource,
+	/// Overwrite the source with the target version
+	KeepTarget,
+	/// Keep the target version and save the source version next to it as a conflict copy
+	KeepBoth,
+}
+
+impl Resolution {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::KeepSource => "keep_source",
+			Self::KeepTarget => "keep_target",
+			Self::KeepBoth => "keep_both",
+		}
+	}
+
+	pub fn from_str(s: &str) -> Option<Self> {
+		match s {
+			"keep_source" => Some(Self::KeepSource),
+			"keep_target" => Some(Self::KeepTarget),
+			"keep_both" => Some(Self::KeepBoth),
+			_ => None,
+		}
+	}
+}
+
+impl std::fmt::Display for Resolution {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.as_str())
+	}
+}
@@ -0,0 +1,96 @@
+//! FTS5 Content Search Index Migration
+//!
+//! Creates a second FTS5 virtual table over `content_identities.text_content`
+//! so that ranked search can match text extracted from files (OCR output,
+//! speech transcripts, document text) in addition to entry names.
+//!
+//! The index is keyed by content identity id, so every entry sharing the same
+//! content shares a single indexed document.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		let db = manager.get_connection();
+
+		db.execute_unprepared(
+			r#"
+            CREATE VIRTUAL TABLE IF NOT EXISTS content_search_index USING fts5(
+                text_content,
+                tokenize="unicode61 remove_diacritics 2",
+                prefix='2,3'
+            );
+            "#,
+		)
+		.await?;
+
+		db.execute_unprepared(
+			r#"
+            CREATE TRIGGER IF NOT EXISTS content_identities_search_insert
+            AFTER INSERT ON content_identities WHEN new.text_content IS NOT NULL
+            BEGIN
+                INSERT INTO content_search_index(rowid, text_content)
+                VALUES (new.id, new.text_content);
+            END;
+            "#,
+		)
+		.await?;
+
+		// Text content is usually written after the content identity is created
+		// (OCR, transcription), so updates must be able to insert as well
+		db.execute_unprepared(
+			r#"
+            CREATE TRIGGER IF NOT EXISTS content_identities_search_update
+            AFTER UPDATE OF text_content ON content_identities
+            BEGIN
+                DELETE FROM content_search_index WHERE rowid = old.id;
+                INSERT INTO content_search_index(rowid, text_content)
+                SELECT new.id, new.text_content WHERE new.text_content IS NOT NULL;
+            END;
+            "#,
+		)
+		.await?;
+
+		db.execute_unprepared(
+			r#"
+            CREATE TRIGGER IF NOT EXISTS content_identities_search_delete
+            AFTER DELETE ON content_identities
+            BEGIN
+                DELETE FROM content_search_index WHERE rowid = old.id;
+            END;
+            "#,
+		)
+		.await?;
+
+		// Populate with text that was extracted before this migration
+		db.execute_unprepared(
+			r#"
+            INSERT INTO content_search_index(rowid, text_content)
+            SELECT id, text_content FROM content_identities
+            WHERE text_content IS NOT NULL AND text_content != '';
+            "#,
+		)
+		.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		let db = manager.get_connection();
+
+		db.execute_unprepared("DROP TRIGGER IF EXISTS content_identities_search_insert;")
+			.await?;
+		db.execute_unprepared("DROP TRIGGER IF EXISTS content_identities_search_update;")
+			.await?;
+		db.execute_unprepared("DROP TRIGGER IF EXISTS content_identities_search_delete;")
+			.await?;
+		db.execute_unprepared("DROP TABLE IF EXISTS content_search_index;")
+			.await?;
+
+		Ok(())
+	}
+}
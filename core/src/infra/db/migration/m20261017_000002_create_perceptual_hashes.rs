@@ -0,0 +1,88 @@
+//! Create perceptual_hashes table for near-duplicate image and video detection
+//!
+//! Hashes are keyed by content hash so files with identical bytes share a
+//! single row, and unchanged files are never decoded twice.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(PerceptualHashes::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(PerceptualHashes::Id)
+							.integer()
+							.not_null()
+							.auto_increment()
+							.primary_key(),
+					)
+					.col(
+						ColumnDef::new(PerceptualHashes::ContentHash)
+							.string()
+							.not_null()
+							.unique_key(),
+					)
+					.col(
+						ColumnDef::new(PerceptualHashes::MediaKind)
+							.string()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(PerceptualHashes::Phash)
+							.big_integer()
+							.not_null(),
+					)
+					.col(ColumnDef::new(PerceptualHashes::Dhash).big_integer())
+					.col(ColumnDef::new(PerceptualHashes::KeyframeHashes).json())
+					.col(ColumnDef::new(PerceptualHashes::Width).integer())
+					.col(ColumnDef::new(PerceptualHashes::Height).integer())
+					.col(
+						ColumnDef::new(PerceptualHashes::CreatedAt)
+							.timestamp()
+							.not_null()
+							.default(Expr::current_timestamp()),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_perceptual_hashes_content_hash")
+					.table(PerceptualHashes::Table)
+					.col(PerceptualHashes::ContentHash)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(PerceptualHashes::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+enum PerceptualHashes {
+	Table,
+	Id,
+	ContentHash,
+	MediaKind,
+	Phash,
+	Dhash,
+	KeyframeHashes,
+	Width,
+	Height,
+	CreatedAt,
+}
@@ -0,0 +1,92 @@
+//! Create smart_tag_members table for tags evaluated from composition rules
+//!
+//! Membership is derived from synced tags and entries, so every device computes
+//! its own and the table is never synced.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(SmartTagMembers::Table)
+					.if_not_exists()
+					.col(ColumnDef::new(SmartTagMembers::TagId).integer().not_null())
+					.col(
+						ColumnDef::new(SmartTagMembers::EntryId)
+							.integer()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(SmartTagMembers::MatchedAt)
+							.timestamp()
+							.not_null()
+							.default(Expr::current_timestamp()),
+					)
+					.primary_key(
+						Index::create()
+							.col(SmartTagMembers::TagId)
+							.col(SmartTagMembers::EntryId),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_smart_tag_member_tag")
+							.from(SmartTagMembers::Table, SmartTagMembers::TagId)
+							.to(Tag::Table, Tag::Id)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_smart_tag_member_entry")
+							.from(SmartTagMembers::Table, SmartTagMembers::EntryId)
+							.to(Entries::Table, Entries::Id)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_smart_tag_members_entry_id")
+					.table(SmartTagMembers::Table)
+					.col(SmartTagMembers::EntryId)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(SmartTagMembers::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+enum SmartTagMembers {
+	Table,
+	TagId,
+	EntryId,
+	MatchedAt,
+}
+
+#[derive(DeriveIden)]
+enum Tag {
+	Table,
+	Id,
+}
+
+#[derive(DeriveIden)]
+enum Entries {
+	Table,
+	Id,
+}
@@ -0,0 +1,80 @@
+//! Create location_indexer_rules table linking locations to their own indexer rules
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(LocationIndexerRules::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(LocationIndexerRules::LocationId)
+							.integer()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(LocationIndexerRules::IndexerRuleId)
+							.integer()
+							.not_null(),
+					)
+					.primary_key(
+						Index::create()
+							.col(LocationIndexerRules::LocationId)
+							.col(LocationIndexerRules::IndexerRuleId),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_location_indexer_rule_location")
+							.from(
+								LocationIndexerRules::Table,
+								LocationIndexerRules::LocationId,
+							)
+							.to(Locations::Table, Locations::Id)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_location_indexer_rule_rule")
+							.from(
+								LocationIndexerRules::Table,
+								LocationIndexerRules::IndexerRuleId,
+							)
+							.to(IndexerRules::Table, IndexerRules::Id)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(LocationIndexerRules::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+enum LocationIndexerRules {
+	Table,
+	LocationId,
+	IndexerRuleId,
+}
+
+#[derive(DeriveIden)]
+enum Locations {
+	Table,
+	Id,
+}
+
+#[derive(DeriveIden)]
+enum IndexerRules {
+	Table,
+	Id,
+}
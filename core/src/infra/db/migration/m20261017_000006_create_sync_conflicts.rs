@@ -0,0 +1,188 @@
+//! Persist file sync conflicts and let conduits choose how conflicts are resolved
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(SyncConduit::Table)
+					.add_column(
+						ColumnDef::new(SyncConduit::ConflictStrategy)
+							.string()
+							.not_null()
+							.default("newest_wins"),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_table(
+				Table::create()
+					.table(SyncConflict::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(SyncConflict::Id)
+							.integer()
+							.not_null()
+							.auto_increment()
+							.primary_key(),
+					)
+					.col(
+						ColumnDef::new(SyncConflict::Uuid)
+							.binary()
+							.not_null()
+							.unique_key(),
+					)
+					.col(ColumnDef::new(SyncConflict::ConduitId).integer().not_null())
+					.col(
+						ColumnDef::new(SyncConflict::GenerationId)
+							.integer()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(SyncConflict::RelativePath)
+							.string()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(SyncConflict::ConflictType)
+							.string()
+							.not_null(),
+					)
+					.col(ColumnDef::new(SyncConflict::SourceEntryId).integer())
+					.col(
+						ColumnDef::new(SyncConflict::SourceSize)
+							.big_integer()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(SyncConflict::SourceModifiedAt)
+							.timestamp_with_time_zone()
+							.not_null(),
+					)
+					.col(ColumnDef::new(SyncConflict::TargetEntryId).integer())
+					.col(
+						ColumnDef::new(SyncConflict::TargetSize)
+							.big_integer()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(SyncConflict::TargetModifiedAt)
+							.timestamp_with_time_zone()
+							.not_null(),
+					)
+					.col(
+						ColumnDef::new(SyncConflict::DetectedAt)
+							.timestamp_with_time_zone()
+							.not_null(),
+					)
+					.col(ColumnDef::new(SyncConflict::Resolution).string())
+					.col(ColumnDef::new(SyncConflict::ResolvedAt).timestamp_with_time_zone())
+					.col(ColumnDef::new(SyncConflict::AppliedAt).timestamp_with_time_zone())
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_sync_conflict_conduit")
+							.from(SyncConflict::Table, SyncConflict::ConduitId)
+							.to(SyncConduit::Table, SyncConduit::Id)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_sync_conflict_generation")
+							.from(SyncConflict::Table, SyncConflict::GenerationId)
+							.to(SyncGeneration::Table, SyncGeneration::Id)
+							.on_delete(ForeignKeyAction::Cascade),
+					)
+					// Conflicts outlive reindexed entries, an unresolvable one is dropped when applied
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_sync_conflict_source_entry")
+							.from(SyncConflict::Table, SyncConflict::SourceEntryId)
+							.to(Entries::Table, Entries::Id)
+							.on_delete(ForeignKeyAction::SetNull),
+					)
+					.foreign_key(
+						ForeignKey::create()
+							.name("fk_sync_conflict_target_entry")
+							.from(SyncConflict::Table, SyncConflict::TargetEntryId)
+							.to(Entries::Table, Entries::Id)
+							.on_delete(ForeignKeyAction::SetNull),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_sync_conflict_conduit")
+					.table(SyncConflict::Table)
+					.col(SyncConflict::ConduitId)
+					.col(SyncConflict::AppliedAt)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(SyncConflict::Table).to_owned())
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(SyncConduit::Table)
+					.drop_column(SyncConduit::ConflictStrategy)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+enum SyncConflict {
+	Table,
+	Id,
+	Uuid,
+	ConduitId,
+	GenerationId,
+	RelativePath,
+	ConflictType,
+	SourceEntryId,
+	SourceSize,
+	SourceModifiedAt,
+	TargetEntryId,
+	TargetSize,
+	TargetModifiedAt,
+	DetectedAt,
+	Resolution,
+	ResolvedAt,
+	AppliedAt,
+}
+
+#[derive(DeriveIden)]
+enum SyncConduit {
+	Table,
+	Id,
+	ConflictStrategy,
+}
+
+#[derive(DeriveIden)]
+enum SyncGeneration {
+	Table,
+	Id,
+}
+
+#[derive(DeriveIden)]
+enum Entries {
+	Table,
+	Id,
+}
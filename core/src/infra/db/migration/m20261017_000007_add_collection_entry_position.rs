@@ -0,0 +1,80 @@
+//! Order the files of a collection
+//!
+//! Existing entries keep the order they were added in.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(CollectionEntry::Table)
+					.add_column(
+						ColumnDef::new(CollectionEntry::Position)
+							.integer()
+							.not_null()
+							.default(0),
+					)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.get_connection()
+			.execute_unprepared(
+				"UPDATE collection_entry SET position = (
+					SELECT COUNT(*) FROM collection_entry AS earlier
+					WHERE earlier.collection_id = collection_entry.collection_id
+					AND (earlier.added_at < collection_entry.added_at
+						OR (earlier.added_at = collection_entry.added_at
+							AND earlier.entry_id < collection_entry.entry_id))
+				)",
+			)
+			.await?;
+
+		manager
+			.create_index(
+				Index::create()
+					.name("idx_collection_entry_position")
+					.table(CollectionEntry::Table)
+					.col(CollectionEntry::CollectionId)
+					.col(CollectionEntry::Position)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_index(
+				Index::drop()
+					.name("idx_collection_entry_position")
+					.table(CollectionEntry::Table)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(CollectionEntry::Table)
+					.drop_column(CollectionEntry::Position)
+					.to_owned(),
+			)
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+enum CollectionEntry {
+	Table,
+	CollectionId,
+	Position,
+}
@@ -0,0 +1,64 @@
+//! Create saved_searches table for named, synced file searches
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.create_table(
+				Table::create()
+					.table(SavedSearches::Table)
+					.if_not_exists()
+					.col(
+						ColumnDef::new(SavedSearches::Id)
+							.integer()
+							.not_null()
+							.auto_increment()
+							.primary_key(),
+					)
+					.col(
+						ColumnDef::new(SavedSearches::Uuid)
+							.uuid()
+							.not_null()
+							.unique_key(),
+					)
+					.col(ColumnDef::new(SavedSearches::Name).string().not_null())
+					.col(ColumnDef::new(SavedSearches::Definition).json().not_null())
+					.col(
+						ColumnDef::new(SavedSearches::CreatedAt)
+							.timestamp()
+							.not_null()
+							.default(Expr::current_timestamp()),
+					)
+					.col(
+						ColumnDef::new(SavedSearches::UpdatedAt)
+							.timestamp()
+							.not_null()
+							.default(Expr::current_timestamp()),
+					)
+					.to_owned(),
+			)
+			.await
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.drop_table(Table::drop().table(SavedSearches::Table).to_owned())
+			.await
+	}
+}
+
+#[derive(DeriveIden)]
+enum SavedSearches {
+	Table,
+	Id,
+	Uuid,
+	Name,
+	Definition,
+	CreatedAt,
+	UpdatedAt,
+}
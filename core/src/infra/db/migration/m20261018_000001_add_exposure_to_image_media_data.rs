@@ -0,0 +1,63 @@
+//! Add exposure time and exposure bias to image media data
+//!
+//! Images indexed before this keep them empty until their metadata is extracted again.
+
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+	async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		// SQLite only adds one column per ALTER TABLE
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ImageMediaData::Table)
+					.add_column(ColumnDef::new(ImageMediaData::ExposureTime).string().null())
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ImageMediaData::Table)
+					.add_column(ColumnDef::new(ImageMediaData::ExposureBias).string().null())
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+
+	async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ImageMediaData::Table)
+					.drop_column(ImageMediaData::ExposureBias)
+					.to_owned(),
+			)
+			.await?;
+
+		manager
+			.alter_table(
+				Table::alter()
+					.table(ImageMediaData::Table)
+					.drop_column(ImageMediaData::ExposureTime)
+					.to_owned(),
+			)
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[derive(DeriveIden)]
+enum ImageMediaData {
+	Table,
+	ExposureTime,
+	ExposureBias,
+}
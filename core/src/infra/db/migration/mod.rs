@@ -38,6 +38,15 @@ mod m20260104_000001_replace_device_id_with_volume_id;
 mod m20260105_000001_add_volume_id_to_locations;
 mod m20260114_000001_fix_search_index_include_directories;
 mod m20260123_000001_remove_legacy_sync_columns;
+mod m20261017_000001_create_content_search_index;
+mod m20261017_000002_create_perceptual_hashes;
+mod m20261017_000003_create_smart_tag_members;
+mod m20261017_000004_create_location_indexer_rules;
+mod m20261017_000005_fix_sync_conduit_entry_keys;
+mod m20261017_000006_create_sync_conflicts;
+mod m20261017_000007_add_collection_entry_position;
+mod m20261017_000008_create_saved_searches;
+mod m20261018_000001_add_exposure_to_image_media_data;
 
 pub struct Migrator;
 
@@ -81,6 +90,15 @@ impl MigratorTrait for Migrator {
 			Box::new(m20260105_000001_add_volume_id_to_locations::Migration),
 			Box::new(m20260114_000001_fix_search_index_include_directories::Migration),
 			Box::new(m20260123_000001_remove_legacy_sync_columns::Migration),
+			Box::new(m20261017_000001_create_content_search_index::Migration),
+			Box::new(m20261017_000002_create_perceptual_hashes::Migration),
+			Box::new(m20261017_000003_create_smart_tag_members::Migration),
+			Box::new(m20261017_000004_create_location_indexer_rules::Migration),
+			Box::new(m20261017_000005_fix_sync_conduit_entry_keys::Migration),
+			Box::new(m20261017_000006_create_sync_conflicts::Migration),
+			Box::new(m20261017_000007_add_collection_entry_position::Migration),
+			Box::new(m20261017_000008_create_saved_searches::Migration),
+			Box::new(m20261018_000001_add_exposure_to_image_media_data::Migration),
 		]
 	}
 }
@@ -301,6 +301,18 @@ pub enum Event {
 		timestamp: String,
 	},
 
+	// File sync events
+	/// A file sync generation found conflicts that need a decision from the user
+	FileSyncConflictsDetected {
+		library_id: Uuid,
+		conduit_id: Uuid,
+		generation: i64,
+		/// Conflicts first seen in this generation
+		detected: usize,
+		/// All conflicts of the conduit still waiting for a decision
+		pending: usize,
+	},
+
 	// Generic resource events (normalized cache)
 	// Works for ALL resources: Location, Tag, Album, File, etc.
 	ResourceChanged {
@@ -991,6 +1003,9 @@ impl EventFilter for Event {
 			}
 			| Event::SyncError {
 				library_id: lid, ..
+			}
+			| Event::FileSyncConflictsDetected {
+				library_id: lid, ..
 			} => *lid == library_id,
 			_ => false,
 		}
@@ -0,0 +1,363 @@
+//! Cron expressions for recurring job schedules
+//!
+//! Standard five field syntax: minute, hour, day of month, month and day of week.
+//! Fields accept `*`, single values, ranges (`1-5`), lists (`1,15`) and steps
+//! (`*/15`, `0-30/10`). Month and weekday names (`jan`, `mon`) are accepted, as
+//! are the `@hourly`, `@daily`, `@weekly`, `@monthly` and `@yearly` shortcuts.
+//!
+//! As in classic cron, when both day fields are restricted a day matches if
+//! either of them does: `0 0 1 * mon` runs on the 1st and on every Monday.
+
+use chrono::{DateTime, Datelike, Duration, NaiveTime, TimeZone, Timelike};
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+/// Days searched for a match before giving up, covers `0 0 29 2 mon` style expressions
+const MAX_DAYS_SEARCHED: u32 = 366 * 28;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum CronError {
+	#[error("Expected 5 fields, found {0}")]
+	FieldCount(usize),
+
+	#[error("Invalid {field} value '{value}'")]
+	InvalidValue { field: &'static str, value: String },
+
+	#[error("{field} value {value} is outside {min}-{max}")]
+	OutOfRange {
+		field: &'static str,
+		value: u32,
+		min: u32,
+		max: u32,
+	},
+
+	#[error("Unknown shortcut '{0}'")]
+	UnknownShortcut(String),
+}
+
+struct Field {
+	name: &'static str,
+	min: u32,
+	max: u32,
+	names: &'static [&'static str],
+}
+
+const MINUTE: Field = Field {
+	name: "minute",
+	min: 0,
+	max: 59,
+	names: &[],
+};
+
+const HOUR: Field = Field {
+	name: "hour",
+	min: 0,
+	max: 23,
+	names: &[],
+};
+
+const DAY_OF_MONTH: Field = Field {
+	name: "day of month",
+	min: 1,
+	max: 31,
+	names: &[],
+};
+
+const MONTH: Field = Field {
+	name: "month",
+	min: 1,
+	max: 12,
+	names: &[
+		"jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+	],
+};
+
+// 7 is accepted as Sunday and folded into 0 after parsing
+const DAY_OF_WEEK: Field = Field {
+	name: "day of week",
+	min: 0,
+	max: 7,
+	names: &["sun", "mon", "tue", "wed", "thu", "fri", "sat"],
+};
+
+/// A parsed cron expression, each field stored as a bitmask of allowed values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronExpression {
+	source: String,
+	minutes: u64,
+	hours: u64,
+	days_of_month: u64,
+	months: u64,
+	days_of_week: u64,
+	day_of_month_restricted: bool,
+	day_of_week_restricted: bool,
+}
+
+impl CronExpression {
+	/// First time strictly after `after` matching the expression, in `after`'s timezone
+	///
+	/// Local times skipped by a DST transition never match, repeated ones match once.
+	pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+		let timezone = after.timezone();
+		let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+
+		let mut date = start.date();
+		let mut from = start.time();
+
+		for _ in 0..MAX_DAYS_SEARCHED {
+			if self.matches_day(date) {
+				for hour in from.hour()..24 {
+					if !bit(self.hours, hour) {
+						continue;
+					}
+
+					let first_minute = if hour == from.hour() {
+						from.minute()
+					} else {
+						0
+					};
+					for minute in first_minute..60 {
+						if !bit(self.minutes, minute) {
+							continue;
+						}
+
+						let naive = date.and_hms_opt(hour, minute, 0)?;
+						if let Some(time) = timezone.from_local_datetime(&naive).earliest() {
+							return Some(time);
+						}
+					}
+				}
+			}
+
+			date = date.succ_opt()?;
+			from = NaiveTime::MIN;
+		}
+
+		None
+	}
+
+	fn matches_day(&self, date: chrono::NaiveDate) -> bool {
+		if !bit(self.months, date.month()) {
+			return false;
+		}
+
+		let day_of_month = bit(self.days_of_month, date.day());
+		let day_of_week = bit(self.days_of_week, date.weekday().num_days_from_sunday());
+
+		if self.day_of_month_restricted && self.day_of_week_restricted {
+			day_of_month || day_of_week
+		} else {
+			day_of_month && day_of_week
+		}
+	}
+}
+
+impl FromStr for CronExpression {
+	type Err = CronError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let source = s.trim();
+		let expanded = match source.to_ascii_lowercase().as_str() {
+			"@yearly" | "@annually" => "0 0 1 1 *",
+			"@monthly" => "0 0 1 * *",
+			"@weekly" => "0 0 * * 0",
+			"@daily" | "@midnight" => "0 0 * * *",
+			"@hourly" => "0 * * * *",
+			shortcut if shortcut.starts_with('@') => {
+				return Err(CronError::UnknownShortcut(source.to_string()))
+			}
+			_ => source,
+		};
+
+		let fields: Vec<&str> = expanded.split_whitespace().collect();
+		let [minute, hour, day_of_month, month, day_of_week] = fields[..] else {
+			return Err(CronError::FieldCount(fields.len()));
+		};
+
+		let mut days_of_week = parse_field(&DAY_OF_WEEK, day_of_week)?;
+		if bit(days_of_week, 7) {
+			days_of_week = (days_of_week | 1) & !(1 << 7);
+		}
+
+		Ok(Self {
+			source: source.to_string(),
+			minutes: parse_field(&MINUTE, minute)?,
+			hours: parse_field(&HOUR, hour)?,
+			days_of_month: parse_field(&DAY_OF_MONTH, day_of_month)?,
+			months: parse_field(&MONTH, month)?,
+			days_of_week,
+			day_of_month_restricted: !day_of_month.starts_with('*'),
+			day_of_week_restricted: !day_of_week.starts_with('*'),
+		})
+	}
+}
+
+impl fmt::Display for CronExpression {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.write_str(&self.source)
+	}
+}
+
+fn bit(mask: u64, value: u32) -> bool {
+	mask & (1 << value) != 0
+}
+
+fn parse_field(field: &Field, text: &str) -> Result<u64, CronError> {
+	let invalid = || CronError::InvalidValue {
+		field: field.name,
+		value: text.to_string(),
+	};
+
+	let mut mask = 0;
+	for part in text.split(',') {
+		let (range, step) = match part.split_once('/') {
+			Some((range, step)) => {
+				let step = step.parse::<u32>().map_err(|_| invalid())?;
+				if step == 0 {
+					return Err(invalid());
+				}
+				(range, Some(step))
+			}
+			None => (part, None),
+		};
+
+		let (start, end) = if range == "*" {
+			(field.min, field.max)
+		} else if let Some((start, end)) = range.split_once('-') {
+			(parse_value(field, start)?, parse_value(field, end)?)
+		} else {
+			let value = parse_value(field, range)?;
+			// `5/15` means every 15 starting at 5
+			(value, if step.is_some() { field.max } else { value })
+		};
+
+		if start > end {
+			return Err(invalid());
+		}
+
+		for value in (start..=end).step_by(step.unwrap_or(1) as usize) {
+			mask |= 1 << value;
+		}
+	}
+
+	Ok(mask)
+}
+
+fn parse_value(field: &Field, text: &str) -> Result<u32, CronError> {
+	let lowercase = text.to_ascii_lowercase();
+	let value = match field.names.iter().position(|name| *name == lowercase) {
+		Some(index) => index as u32 + field.min,
+		None => text.parse::<u32>().map_err(|_| CronError::InvalidValue {
+			field: field.name,
+			value: text.to_string(),
+		})?,
+	};
+
+	if value < field.min || value > field.max {
+		return Err(CronError::OutOfRange {
+			field: field.name,
+			value,
+			min: field.min,
+			max: field.max,
+		});
+	}
+
+	Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::Utc;
+
+	fn at(s: &str) -> DateTime<Utc> {
+		DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+	}
+
+	fn next(expression: &str, after: &str) -> DateTime<Utc> {
+		expression
+			.parse::<CronExpression>()
+			.unwrap()
+			.next_after(&at(after))
+			.unwrap()
+	}
+
+	#[test]
+	fn nightly_run() {
+		assert_eq!(
+			next("0 2 * * *", "2026-10-17T03:00:00Z"),
+			at("2026-10-18T02:00:00Z")
+		);
+		// Strictly after, even when `after` matches exactly
+		assert_eq!(
+			next("0 2 * * *", "2026-10-17T02:00:00Z"),
+			at("2026-10-18T02:00:00Z")
+		);
+	}
+
+	#[test]
+	fn steps_and_ranges() {
+		assert_eq!(
+			next("*/15 * * * *", "2026-10-17T10:16:30Z"),
+			at("2026-10-17T10:30:00Z")
+		);
+		assert_eq!(
+			next("0-30/10 9 * * *", "2026-10-17T09:25:00Z"),
+			at("2026-10-17T09:30:00Z")
+		);
+	}
+
+	#[test]
+	fn weekday_names() {
+		// 2026-10-17 is a Saturday
+		assert_eq!(
+			next("0 9 * * mon-fri", "2026-10-17T10:00:00Z"),
+			at("2026-10-19T09:00:00Z")
+		);
+		assert_eq!(
+			next("@weekly", "2026-10-17T10:00:00Z"),
+			at("2026-10-18T00:00:00Z")
+		);
+	}
+
+	#[test]
+	fn day_fields_are_either_or() {
+		// The 1st of the month or any Monday, whichever comes first
+		assert_eq!(
+			next("0 0 1 * mon", "2026-10-17T00:00:00Z"),
+			at("2026-10-19T00:00:00Z")
+		);
+		assert_eq!(
+			next("0 0 29 feb *", "2026-10-17T00:00:00Z"),
+			at("2028-02-29T00:00:00Z")
+		);
+	}
+
+	#[test]
+	fn month_names_are_one_based() {
+		assert_eq!(
+			next("0 0 1 jan *", "2026-10-17T00:00:00Z"),
+			at("2027-01-01T00:00:00Z")
+		);
+	}
+
+	#[test]
+	fn invalid_expressions() {
+		assert_eq!(
+			"0 2 * *".parse::<CronExpression>(),
+			Err(CronError::FieldCount(4))
+		);
+		assert!(matches!(
+			"60 * * * *".parse::<CronExpression>(),
+			Err(CronError::OutOfRange { value: 60, .. })
+		));
+		assert!(matches!(
+			"*/0 * * * *".parse::<CronExpression>(),
+			Err(CronError::InvalidValue { .. })
+		));
+		assert!(matches!(
+			"@sometimes".parse::<CronExpression>(),
+			Err(CronError::UnknownShortcut(_))
+		));
+	}
+}
@@ -114,6 +114,44 @@ pub mod checkpoint {
 	impl ActiveModelBehavior for ActiveModel {}
 }
 
+pub mod schedules {
+	use super::*;
+
+	/// Recurring job schedule record
+	#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize, Deserialize)]
+	#[sea_orm(table_name = "job_schedules")]
+	pub struct Model {
+		#[sea_orm(primary_key, auto_increment = false)]
+		pub id: String,
+		pub name: String,
+
+		// What to run
+		pub job_name: String,
+		pub job_args: JsonValue,
+
+		/// Serialized ScheduleTrigger
+		pub trigger: JsonValue,
+
+		pub paused: bool,
+		/// Run once after a restart if runs were missed while the daemon was down
+		pub catch_up: bool,
+
+		// Timestamps
+		pub created_at: DateTime<Utc>,
+		pub next_run_at: Option<DateTime<Utc>>,
+		pub last_run_at: Option<DateTime<Utc>>,
+
+		// Outcome of the last run
+		pub last_job_id: Option<String>,
+		pub last_error: Option<String>,
+	}
+
+	#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+	pub enum Relation {}
+
+	impl ActiveModelBehavior for ActiveModel {}
+}
+
 /// Initialize job database
 pub async fn init_database(db_file_path: &Path) -> JobResult<DatabaseConnection> {
 	// Ensure the parent directory exists
@@ -153,6 +191,12 @@ async fn create_tables(db: &DatabaseConnection) -> JobResult<()> {
 	db.execute(db.get_database_backend().build(&checkpoint_statement))
 		.await?;
 
+	// Create schedules table if not exists
+	let mut schedules_statement = schema.create_table_from_entity(schedules::Entity);
+	schedules_statement.if_not_exists();
+	db.execute(db.get_database_backend().build(&schedules_statement))
+		.await?;
+
 	Ok(())
 }
 
@@ -10,6 +10,8 @@ use super::{
 	output::JobOutput,
 	progress::Progress,
 	registry::REGISTRY,
+	schedule::{JobSchedule, ScheduleRunner, ScheduleTrigger},
+	scheduler::{JobScheduler, ResourceBudget, ScheduledTask},
 	traits::{DynJob, Job, JobHandler},
 	types::{ActionContextInfo, ErasedJob, JobId, JobInfo, JobPriority, JobStatus},
 };
@@ -21,7 +23,7 @@ use crate::{
 };
 use async_trait::async_trait;
 use chrono::Utc;
-use sd_task_system::{TaskDispatcher, TaskHandle, TaskSystem};
+use sd_task_system::{TaskDispatcher, TaskSystem};
 use sea_orm::{ActiveModelTrait, ActiveValue::Set, DatabaseConnection, EntityTrait};
 use std::{collections::HashMap, path::PathBuf, sync::Arc};
 use tokio::sync::{broadcast, mpsc, watch, Mutex, RwLock};
@@ -31,6 +33,7 @@ use tracing::{debug, error, info, warn};
 pub struct JobManager {
 	db: Arc<JobDb>,
 	dispatcher: Arc<TaskSystem<JobError>>,
+	scheduler: Arc<JobScheduler>,
 	running_jobs: Arc<RwLock<HashMap<JobId, RunningJob>>>,
 	shutdown_tx: watch::Sender<bool>,
 	context: Arc<CoreContext>,
@@ -39,7 +42,7 @@ pub struct JobManager {
 
 struct RunningJob {
 	handle: JobHandle,
-	task_handle: TaskHandle<JobError>,
+	task_handle: ScheduledTask,
 	status_tx: watch::Sender<JobStatus>,
 	latest_progress: Arc<Mutex<Option<Progress>>>,
 	persistence_complete_rx: Option<tokio::sync::oneshot::Receiver<()>>,
@@ -60,13 +63,20 @@ impl JobManager {
 		let db = database::init_database(&job_db_path).await?;
 
 		// Create task system
-		let dispatcher = TaskSystem::new();
+		let dispatcher = Arc::new(TaskSystem::new());
+
+		// Jobs are admitted into the task system against this machine's resources
+		let scheduler = Arc::new(JobScheduler::new(
+			dispatcher.clone(),
+			ResourceBudget::detect(&data_dir),
+		));
 
 		let (shutdown_tx, _) = watch::channel(false);
 
 		let manager = Self {
 			db: Arc::new(JobDb::new(db)),
-			dispatcher: Arc::new(dispatcher),
+			dispatcher,
+			scheduler,
 			running_jobs: Arc::new(RwLock::new(HashMap::new())),
 			shutdown_tx,
 			context,
@@ -346,11 +356,17 @@ impl JobManager {
 			should_persist,
 		);
 
-		// Dispatch to task system
+		// Dispatch to task system, or queue until the job's requirements are met
 		let task_handle = self
-			.dispatcher
-			.get_dispatcher()
-			.dispatch_boxed(executor)
+			.scheduler
+			.submit(
+				job_id,
+				job_name,
+				priority,
+				REGISTRY.get_requirements(job_name),
+				executor,
+				status_rx_cleanup.clone(),
+			)
 			.await;
 
 		match task_handle {
@@ -539,7 +555,7 @@ impl JobManager {
 
 				Ok(handle)
 			}
-			Err(e) => Err(JobError::task_system(format!("{:?}", e))),
+			Err(e) => Err(e),
 		}
 	}
 
@@ -785,8 +801,18 @@ impl JobManager {
 			should_persist,
 		);
 
-		// Dispatch to task system
-		let task_handle = self.dispatcher.dispatch(executor).await;
+		// Dispatch to task system, or queue until the job's requirements are met
+		let task_handle = self
+			.scheduler
+			.submit(
+				job_id,
+				J::NAME,
+				priority,
+				J::requirements(),
+				Box::new(executor),
+				status_rx_cleanup.clone(),
+			)
+			.await;
 
 		match task_handle {
 			Ok(handle_result) => {
@@ -974,7 +1000,7 @@ impl JobManager {
 
 				Ok(handle)
 			}
-			Err(e) => Err(JobError::task_system(format!("{:?}", e))),
+			Err(e) => Err(e),
 		}
 	}
 
@@ -1002,7 +1028,97 @@ impl JobManager {
 		&self.db
 	}
 
-	/// List currently running jobs from memory (for live monitoring)
+	/// Start firing this library's job schedules, stops when the manager shuts down
+	pub fn start_schedule_runner(self: &Arc<Self>) {
+		ScheduleRunner::spawn(Arc::downgrade(self), self.shutdown_tx.subscribe());
+	}
+
+	/// Create a recurring schedule for a job, dispatched by name like `dispatch_by_name`
+	pub async fn create_schedule(
+		&self,
+		name: String,
+		job_name: String,
+		job_args: serde_json::Value,
+		trigger: ScheduleTrigger,
+		catch_up: bool,
+	) -> JobResult<JobSchedule> {
+		trigger.validate()?;
+
+		// Extension jobs are only known once their plugin is loaded, they are checked on dispatch
+		if REGISTRY.has_job(&job_name) {
+			REGISTRY.create_job(&job_name, job_args.clone())?;
+		} else if !job_name.contains(':') {
+			return Err(JobError::NotFound(format!(
+				"Job type '{}' not registered",
+				job_name
+			)));
+		}
+
+		let now = Utc::now();
+		let model = database::schedules::ActiveModel {
+			id: Set(Uuid::new_v4().to_string()),
+			name: Set(name),
+			job_name: Set(job_name),
+			job_args: Set(job_args),
+			trigger: Set(serde_json::to_value(&trigger).map_err(JobError::serialization)?),
+			paused: Set(false),
+			catch_up: Set(catch_up),
+			created_at: Set(now),
+			next_run_at: Set(trigger.next_run(now, true)),
+			last_run_at: Set(None),
+			last_job_id: Set(None),
+			last_error: Set(None),
+		}
+		.insert(self.db.conn())
+		.await?;
+
+		JobSchedule::try_from(model)
+	}
+
+	/// List all schedules of this library
+	pub async fn list_schedules(&self) -> JobResult<Vec<JobSchedule>> {
+		database::schedules::Entity::find()
+			.all(self.db.conn())
+			.await?
+			.into_iter()
+			.map(JobSchedule::try_from)
+			.collect()
+	}
+
+	/// Pause or resume a schedule
+	///
+	/// Resuming recomputes the next run from now, so runs skipped while paused
+	/// are not caught up.
+	pub async fn set_schedule_paused(&self, id: Uuid, paused: bool) -> JobResult<JobSchedule> {
+		let model = database::schedules::Entity::find_by_id(id.to_string())
+			.one(self.db.conn())
+			.await?
+			.ok_or_else(|| JobError::NotFound(format!("Schedule {} not found", id)))?;
+		let schedule = JobSchedule::try_from(model.clone())?;
+
+		let mut active: database::schedules::ActiveModel = model.into();
+		active.paused = Set(paused);
+		if !paused && schedule.paused {
+			active.next_run_at = Set(schedule.trigger.next_run(Utc::now(), true));
+		}
+
+		JobSchedule::try_from(active.update(self.db.conn()).await?)
+	}
+
+	/// Delete a schedule, jobs it already dispatched keep running
+	pub async fn delete_schedule(&self, id: Uuid) -> JobResult<()> {
+		let result = database::schedules::Entity::delete_by_id(id.to_string())
+			.exec(self.db.conn())
+			.await?;
+
+		if result.rows_affected == 0 {
+			return Err(JobError::NotFound(format!("Schedule {} not found", id)));
+		}
+
+		Ok(())
+	}
+
+	/// List running, paused and waiting jobs from memory (for live monitoring)
 	pub async fn list_running_jobs(&self) -> Vec<JobInfo> {
 		let device_id = self
 			.context
@@ -1016,9 +1132,10 @@ impl JobManager {
 			let handle = &running_job.handle;
 			let status = handle.status();
 
-			// Only include active jobs (running or paused) that should emit events
+			// Only include active jobs (running, paused or waiting for admission) that should emit events
 			// Background jobs (run_in_background=true) have should_emit_events=false
-			if status.is_active() && running_job.should_emit_events {
+			if (status.is_active() || status == JobStatus::Queued) && running_job.should_emit_events
+			{
 				// Get latest progress
 				let progress_percentage =
 					if let Some(progress) = running_job.latest_progress.lock().await.as_ref() {
@@ -1041,6 +1158,7 @@ impl JobManager {
 					parent_job_id: None,
 					action_type: None,
 					action_context: None,
+					waiting_reason: self.scheduler.waiting_reason(*job_id).await,
 				};
 
 				job_infos.push(job_info);
@@ -1148,6 +1266,7 @@ impl JobManager {
 				parent_job_id: None,
 				action_type,
 				action_context,
+				waiting_reason: self.scheduler.waiting_reason(*job_id).await,
 			});
 		}
 		drop(running_jobs_map);
@@ -1225,6 +1344,7 @@ impl JobManager {
 				parent_job_id: j.parent_job_id.and_then(|s| s.parse::<Uuid>().ok()),
 				action_type: j.action_type,
 				action_context,
+				waiting_reason: None,
 			});
 		}
 
@@ -1274,6 +1394,7 @@ impl JobManager {
 				parent_job_id: None,            // TODO: Get from DB if needed
 				action_type: None,
 				action_context: None,
+				waiting_reason: self.scheduler.waiting_reason(job_id).await,
 			}));
 		}
 
@@ -1315,6 +1436,7 @@ impl JobManager {
 				parent_job_id: j.parent_job_id.and_then(|s| s.parse::<Uuid>().ok()),
 				action_type: j.action_type,
 				action_context: None, // TODO: Parse action context from j.action_context
+				waiting_reason: None,
 			})
 		}))
 	}
@@ -1525,6 +1647,15 @@ impl JobManager {
 							.await
 						{
 							Ok(task_handle) => {
+								self.scheduler
+									.track_admitted(
+										job_id,
+										&job_record.name,
+										REGISTRY.get_requirements(&job_record.name),
+										status_tx.subscribe(),
+									)
+									.await;
+
 								// Track running job
 								// Clone latest_progress for monitoring task before moving into RunningJob
 								let latest_progress_for_monitor = latest_progress.clone();
@@ -1544,7 +1675,7 @@ impl JobManager {
 									job_id,
 									RunningJob {
 										handle: handle.clone(),
-										task_handle,
+										task_handle: task_handle.into(),
 										status_tx: status_tx.clone(),
 										latest_progress,
 										persistence_complete_rx: Some(persistence_complete_rx),
@@ -1807,8 +1938,10 @@ impl JobManager {
 					)));
 				}
 
-				// Cancel the task
-				if let Err(e) = running_job.task_handle.cancel().await {
+				// Jobs still queued for admission never reached the task system
+				if self.scheduler.cancel_waiting(job_id).await {
+					let _ = running_job.status_tx.send(JobStatus::Cancelled);
+				} else if let Err(e) = running_job.task_handle.cancel().await {
 					warn!("Failed to send cancel signal to job {}: {}", job_id, e);
 				}
 			}
@@ -2032,12 +2165,21 @@ impl JobManager {
 				.await
 				.map_err(|e| JobError::task_system(format!("Failed to dispatch: {:?}", e)))?;
 
+			self.scheduler
+				.track_admitted(
+					job_id,
+					&job_name,
+					REGISTRY.get_requirements(&job_name),
+					status_tx.subscribe(),
+				)
+				.await;
+
 			// Track running job
 			self.running_jobs.write().await.insert(
 				job_id,
 				RunningJob {
 					handle: handle.clone(),
-					task_handle,
+					task_handle: task_handle.into(),
 					status_tx: status_tx.clone(),
 					latest_progress,
 					persistence_complete_rx: Some(persistence_complete_rx),
@@ -3,6 +3,7 @@
 //! Provides a minimal-boilerplate job execution framework built on top of the task-system.
 
 pub mod context;
+pub mod cron;
 pub mod database;
 pub mod error;
 pub mod executor;
@@ -13,6 +14,8 @@ pub mod manager;
 pub mod output;
 pub mod progress;
 pub mod registry;
+pub mod schedule;
+pub mod scheduler;
 pub mod traits;
 pub mod types;
 
@@ -25,7 +28,7 @@ pub mod prelude {
 		handle::JobHandle,
 		output::JobOutput,
 		progress::{JobProgress, Progress},
-		traits::{Job, JobHandler},
+		traits::{Job, JobHandler, ResourceRequirement},
 		types::{JobId, JobInfo, JobStatus},
 	};
 
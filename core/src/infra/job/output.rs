@@ -54,6 +54,13 @@ pub enum JobOutput {
 		total_bytes: u64,
 	},
 
+	/// File encryption or decryption output
+	FileEncryption {
+		processed_count: usize,
+		failed_count: usize,
+		total_bytes: u64,
+	},
+
 	/// Duplicate detection output
 	DuplicateDetection {
 		duplicate_groups: usize,
@@ -82,6 +89,13 @@ pub enum JobOutput {
 		error_count: usize,
 	},
 
+	/// Embedding generation output
+	EmbeddingGeneration {
+		total_processed: usize,
+		success_count: usize,
+		error_count: usize,
+	},
+
 	/// Gaussian splat generation output
 	GaussianSplat {
 		total_processed: usize,
@@ -89,6 +103,23 @@ pub enum JobOutput {
 		error_count: usize,
 	},
 
+	/// File type re-identification output
+	FileTypeReidentify {
+		checked_count: usize,
+		updated_count: usize,
+		skipped_count: usize,
+	},
+
+	/// Sidecar garbage collection output
+	SidecarGc {
+		dry_run: bool,
+		orphaned_count: usize,
+		stale_count: usize,
+		evicted_count: usize,
+		freed_bytes: u64,
+		failed_count: usize,
+	},
+
 	/// Generic output with custom data
 	#[specta(skip)]
 	Custom(serde_json::Value),
@@ -232,6 +263,17 @@ impl fmt::Display for JobOutput {
 					deleted_count, failed_count, total_bytes
 				)
 			}
+			Self::FileEncryption {
+				processed_count,
+				failed_count,
+				total_bytes,
+			} => {
+				write!(
+					f,
+					"Processed {} files ({} failed, {} bytes)",
+					processed_count, failed_count, total_bytes
+				)
+			}
 			Self::DuplicateDetection {
 				duplicate_groups,
 				total_duplicates,
@@ -276,6 +318,17 @@ impl fmt::Display for JobOutput {
 					total_processed, success_count, error_count
 				)
 			}
+			Self::EmbeddingGeneration {
+				total_processed,
+				success_count,
+				error_count,
+			} => {
+				write!(
+					f,
+					"Embeddings: {} processed ({} success, {} errors)",
+					total_processed, success_count, error_count
+				)
+			}
 			Self::GaussianSplat {
 				total_processed,
 				success_count,
@@ -287,6 +340,36 @@ impl fmt::Display for JobOutput {
 					total_processed, success_count, error_count
 				)
 			}
+			Self::FileTypeReidentify {
+				checked_count,
+				updated_count,
+				skipped_count,
+			} => {
+				write!(
+					f,
+					"Identified {} files again ({} updated, {} skipped)",
+					checked_count, updated_count, skipped_count
+				)
+			}
+			Self::SidecarGc {
+				dry_run,
+				orphaned_count,
+				stale_count,
+				evicted_count,
+				freed_bytes,
+				failed_count,
+			} => {
+				write!(
+					f,
+					"{} {} orphaned, {} stale and {} evicted sidecars ({} bytes, {} failed)",
+					if *dry_run { "Would remove" } else { "Removed" },
+					orphaned_count,
+					stale_count,
+					evicted_count,
+					freed_bytes,
+					failed_count
+				)
+			}
 			Self::Custom(_) => write!(f, "Custom output"),
 		}
 	}
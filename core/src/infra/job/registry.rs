@@ -2,6 +2,7 @@
 
 use super::{
 	error::{JobError, JobResult},
+	scheduler::JobRequirements,
 	types::{ErasedJob, JobRegistration, JobSchema},
 };
 use inventory;
@@ -30,6 +31,7 @@ impl JobRegistry {
 				JobRegistration {
 					name: registration.name,
 					schema_fn: registration.schema_fn,
+					requirements_fn: registration.requirements_fn,
 					create_fn: registration.create_fn,
 					deserialize_fn: registration.deserialize_fn,
 					deserialize_dyn_fn: registration.deserialize_dyn_fn,
@@ -52,6 +54,14 @@ impl JobRegistry {
 		self.jobs.get(name).map(|reg| (reg.schema_fn)())
 	}
 
+	/// Get scheduling constraints for a job, defaults for unknown jobs
+	pub fn get_requirements(&self, name: &str) -> JobRequirements {
+		self.jobs
+			.get(name)
+			.map(|reg| (reg.requirements_fn)())
+			.unwrap_or_default()
+	}
+
 	/// Create a job instance from serialized data
 	pub fn create_job(&self, name: &str, data: serde_json::Value) -> JobResult<Box<dyn ErasedJob>> {
 		let registration = self
@@ -106,6 +116,7 @@ macro_rules! register_job {
 			$crate::infra::job::types::JobRegistration {
 				name: <$job_type as $crate::infra::job::traits::Job>::NAME,
 				schema_fn: <$job_type as $crate::infra::job::traits::Job>::schema,
+				requirements_fn: <$job_type as $crate::infra::job::traits::Job>::requirements,
 				create_fn: |data| {
 					// Note: This is a placeholder - actual executor creation happens
 					// in ErasedJob::create_executor which has all the parameters
@@ -0,0 +1,302 @@
+//! Recurring job schedules
+//!
+//! A schedule dispatches a job by name with JSON arguments, the same way
+//! [`JobManager::dispatch_by_name`] does for API callers, whenever its trigger
+//! fires. Schedules live in the library's job database and only run on this
+//! device. A runner task checks them every [`TICK`]. Runs missed while the daemon
+//! was down are caught up with a single run after restart, unless the schedule
+//! opted out, in which case they are skipped to the next occurrence.
+
+use super::{
+	cron::CronExpression,
+	database::schedules,
+	error::{JobError, JobResult},
+	manager::JobManager,
+	types::{JobId, JobStatus},
+};
+use chrono::{DateTime, Local, Utc};
+use sea_orm::{ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::{
+	sync::Weak,
+	time::{Duration, Instant},
+};
+use tokio::{sync::watch, time::MissedTickBehavior};
+use tracing::{debug, info, warn};
+use uuid::Uuid;
+
+/// How often the runner checks for due schedules
+pub const TICK: Duration = Duration::from_secs(30);
+
+/// Shortest interval accepted for interval and on-idle triggers
+pub const MIN_INTERVAL_SECS: u64 = 60;
+
+/// A run that came due this long ago was missed rather than just picked up late
+const MISSED_AFTER: Duration = Duration::from_secs(TICK.as_secs() * 2);
+
+/// Global CPU usage, in percent, below which the device counts as idle
+const IDLE_CPU_THRESHOLD: f32 = 25.0;
+
+/// What makes a schedule run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(tag = "type", content = "data")]
+pub enum ScheduleTrigger {
+	/// Five field cron expression, evaluated in the device's local time
+	Cron { expression: String },
+	/// Fixed delay between runs
+	Interval { seconds: u64 },
+	/// Run once no jobs are running and the CPU has been quiet for `idle_seconds`,
+	/// at most once every `min_interval_seconds`
+	OnIdle {
+		idle_seconds: u64,
+		min_interval_seconds: u64,
+	},
+}
+
+impl ScheduleTrigger {
+	pub fn validate(&self) -> JobResult<()> {
+		match self {
+			Self::Cron { expression } => {
+				expression
+					.parse::<CronExpression>()
+					.map(|_| ())
+					.map_err(|e| {
+						JobError::invalid_state(format!(
+							"Invalid cron expression '{}': {}",
+							expression, e
+						))
+					})
+			}
+			Self::Interval { seconds }
+			| Self::OnIdle {
+				min_interval_seconds: seconds,
+				..
+			} if *seconds < MIN_INTERVAL_SECS => Err(JobError::invalid_state(format!(
+				"Schedules can't run more often than every {} seconds",
+				MIN_INTERVAL_SECS
+			))),
+			_ => Ok(()),
+		}
+	}
+
+	/// First time the trigger is due when it was created or last fired at `after`
+	///
+	/// On-idle triggers are due right away on creation, the idle check gates them.
+	pub fn next_run(&self, after: DateTime<Utc>, created: bool) -> Option<DateTime<Utc>> {
+		match self {
+			Self::Cron { expression } => expression
+				.parse::<CronExpression>()
+				.ok()?
+				.next_after(&after.with_timezone(&Local))
+				.map(|time| time.with_timezone(&Utc)),
+			Self::Interval { seconds } => Some(after + chrono::Duration::seconds(*seconds as i64)),
+			Self::OnIdle { .. } if created => Some(after),
+			Self::OnIdle {
+				min_interval_seconds,
+				..
+			} => Some(after + chrono::Duration::seconds(*min_interval_seconds as i64)),
+		}
+	}
+}
+
+/// A recurring job schedule
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct JobSchedule {
+	pub id: Uuid,
+	pub name: String,
+	pub job_name: String,
+	pub job_args: serde_json::Value,
+	pub trigger: ScheduleTrigger,
+	pub paused: bool,
+	pub catch_up: bool,
+	pub created_at: DateTime<Utc>,
+	pub next_run_at: Option<DateTime<Utc>>,
+	pub last_run_at: Option<DateTime<Utc>>,
+	pub last_job_id: Option<Uuid>,
+	pub last_error: Option<String>,
+}
+
+impl TryFrom<schedules::Model> for JobSchedule {
+	type Error = JobError;
+
+	fn try_from(model: schedules::Model) -> JobResult<Self> {
+		Ok(Self {
+			id: model
+				.id
+				.parse()
+				.map_err(|e| JobError::serialization(format!("Invalid schedule id: {}", e)))?,
+			name: model.name,
+			job_name: model.job_name,
+			job_args: model.job_args,
+			trigger: serde_json::from_value(model.trigger).map_err(JobError::serialization)?,
+			paused: model.paused,
+			catch_up: model.catch_up,
+			created_at: model.created_at,
+			next_run_at: model.next_run_at,
+			last_run_at: model.last_run_at,
+			last_job_id: model.last_job_id.and_then(|id| id.parse().ok()),
+			last_error: model.last_error,
+		})
+	}
+}
+
+/// Background task firing due schedules of one library
+pub(crate) struct ScheduleRunner {
+	manager: Weak<JobManager>,
+	system: sysinfo::System,
+	idle_since: Option<Instant>,
+}
+
+impl ScheduleRunner {
+	/// Run until the job manager shuts down or is dropped
+	pub(crate) fn spawn(manager: Weak<JobManager>, mut shutdown_rx: watch::Receiver<bool>) {
+		tokio::spawn(async move {
+			let mut runner = Self {
+				manager,
+				system: sysinfo::System::new(),
+				idle_since: None,
+			};
+
+			let mut interval = tokio::time::interval(TICK);
+			interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+			loop {
+				tokio::select! {
+					_ = interval.tick() => {}
+					_ = shutdown_rx.changed() => {}
+				}
+
+				if *shutdown_rx.borrow() {
+					break;
+				}
+
+				let Some(manager) = runner.manager.upgrade() else {
+					break;
+				};
+
+				if let Err(e) = runner.tick(&manager).await {
+					warn!("Failed to run job schedules: {}", e);
+				}
+			}
+
+			debug!("Job schedule runner stopped");
+		});
+	}
+
+	async fn tick(&mut self, manager: &JobManager) -> JobResult<()> {
+		let now = Utc::now();
+		let idle_for = self.idle_for(manager).await;
+
+		let due = schedules::Entity::find()
+			.filter(schedules::Column::Paused.eq(false))
+			.filter(schedules::Column::NextRunAt.lte(now))
+			.all(manager.database().conn())
+			.await?;
+
+		for model in due {
+			let name = model.name.clone();
+			if let Err(e) = self.fire(manager, model, now, idle_for).await {
+				warn!("Failed to run schedule '{}': {}", name, e);
+			}
+		}
+
+		Ok(())
+	}
+
+	async fn fire(
+		&self,
+		manager: &JobManager,
+		model: schedules::Model,
+		now: DateTime<Utc>,
+		idle_for: Duration,
+	) -> JobResult<()> {
+		let schedule = JobSchedule::try_from(model.clone())?;
+
+		if let ScheduleTrigger::OnIdle { idle_seconds, .. } = schedule.trigger {
+			// Stays due until the device has been idle long enough
+			if idle_for < Duration::from_secs(idle_seconds) {
+				return Ok(());
+			}
+		}
+
+		let missed = schedule
+			.next_run_at
+			.is_some_and(|due| (now - due).to_std().is_ok_and(|late| late > MISSED_AFTER))
+			&& !matches!(schedule.trigger, ScheduleTrigger::OnIdle { .. });
+
+		let mut active: schedules::ActiveModel = model.into();
+		active.next_run_at = Set(schedule.trigger.next_run(now, false));
+
+		if missed && !schedule.catch_up {
+			info!(
+				"Skipping missed run of schedule '{}' ({})",
+				schedule.name, schedule.job_name
+			);
+		} else if self.previous_run_active(manager, &schedule).await {
+			info!(
+				"Skipping run of schedule '{}', its previous job is still running",
+				schedule.name
+			);
+		} else {
+			if missed {
+				info!("Catching up missed run of schedule '{}'", schedule.name);
+			}
+
+			active.last_run_at = Set(Some(now));
+			match manager
+				.dispatch_by_name(&schedule.job_name, schedule.job_args.clone())
+				.await
+			{
+				Ok(handle) => {
+					debug!(
+						"Schedule '{}' dispatched job {} ({})",
+						schedule.name,
+						handle.id(),
+						schedule.job_name
+					);
+					active.last_job_id = Set(Some(handle.id().to_string()));
+					active.last_error = Set(None);
+				}
+				Err(e) => {
+					warn!("Schedule '{}' failed to dispatch: {}", schedule.name, e);
+					active.last_error = Set(Some(e.to_string()));
+				}
+			}
+		}
+
+		active.update(manager.database().conn()).await?;
+
+		Ok(())
+	}
+
+	async fn previous_run_active(&self, manager: &JobManager, schedule: &JobSchedule) -> bool {
+		let Some(job_id) = schedule.last_job_id else {
+			return false;
+		};
+
+		matches!(
+			manager.get_job(JobId::from(job_id)).await,
+			Some(handle) if !handle.status().is_terminal()
+		)
+	}
+
+	/// How long the device has been idle, zero if it is busy
+	async fn idle_for(&mut self, manager: &JobManager) -> Duration {
+		self.system.refresh_cpu_usage();
+
+		let jobs_running = manager
+			.list_running_jobs()
+			.await
+			.iter()
+			.any(|job| job.status == JobStatus::Running);
+		let busy = jobs_running || self.system.global_cpu_usage() >= IDLE_CPU_THRESHOLD;
+
+		if busy {
+			self.idle_since = None;
+			return Duration::ZERO;
+		}
+
+		self.idle_since.get_or_insert_with(Instant::now).elapsed()
+	}
+}
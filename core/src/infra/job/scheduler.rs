@@ -0,0 +1,609 @@
+//! Admission control for jobs
+//!
+//! Jobs declare how many of their instances may run at once, the resources they
+//! hold while running and the job types they must wait for (see [`Job`]). The
+//! scheduler only hands a job to the task system once all of these are satisfied.
+//! Until then the job stays queued with a [`WaitReason`], and the queue is
+//! re-evaluated every time a tracked job reaches a terminal state.
+//!
+//! [`Job`]: super::traits::Job
+
+use super::{
+	error::{JobError, JobResult},
+	traits::ResourceRequirement,
+	types::{JobId, JobPriority, JobStatus},
+};
+use sd_task_system::{Task, TaskDispatcher, TaskHandle, TaskSystem, TaskSystemError};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::{
+	collections::HashMap,
+	fmt,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+use tokio::sync::{watch, Mutex};
+use tracing::{debug, info};
+
+/// Scheduling constraints of a job type, collected from its [`Job`] impl
+///
+/// [`Job`]: super::traits::Job
+#[derive(Debug, Clone, Default)]
+pub struct JobRequirements {
+	pub max_concurrent: Option<usize>,
+	pub resources: Vec<ResourceRequirement>,
+	pub dependencies: &'static [&'static str],
+	pub run_after: &'static [&'static str],
+}
+
+/// Why a queued job has not started yet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(tag = "type", content = "data")]
+pub enum WaitReason {
+	/// Another job this one depends on is still queued or running
+	Dependency { job_name: String },
+	/// The maximum number of instances of this job type are already running
+	ConcurrencyLimit { max_concurrent: usize },
+	/// A named resource is held by other jobs
+	Resource { name: String },
+	/// Not enough of the memory budget is left
+	Memory { required: u64, available: u64 },
+	/// Not enough disk space is left once other jobs' reservations are counted
+	DiskSpace { required: u64, available: u64 },
+}
+
+impl fmt::Display for WaitReason {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Self::Dependency { job_name } => write!(f, "Waiting for {} to finish", job_name),
+			Self::ConcurrencyLimit { max_concurrent } => write!(
+				f,
+				"Waiting for a free slot ({} running at most)",
+				max_concurrent
+			),
+			Self::Resource { name } => write!(f, "Waiting for resource '{}'", name),
+			Self::Memory {
+				required,
+				available,
+			} => write!(
+				f,
+				"Waiting for memory ({} bytes needed, {} available)",
+				required, available
+			),
+			Self::DiskSpace {
+				required,
+				available,
+			} => write!(
+				f,
+				"Waiting for disk space ({} bytes needed, {} available)",
+				required, available
+			),
+		}
+	}
+}
+
+/// Resources the scheduler hands out to jobs
+#[derive(Debug, Clone)]
+pub struct ResourceBudget {
+	/// Memory that admitted jobs may reserve in total
+	pub memory: u64,
+	/// Capacity of named resources, resources not listed have a capacity of one
+	pub named: HashMap<&'static str, usize>,
+	/// Path whose filesystem disk space reservations are checked against
+	pub disk_path: Option<PathBuf>,
+}
+
+impl ResourceBudget {
+	/// Budget for this machine: half the physical memory, disk space of `data_dir`
+	pub fn detect(data_dir: &Path) -> Self {
+		let mut system = sysinfo::System::new();
+		system.refresh_memory();
+
+		Self {
+			memory: system.total_memory() / 2,
+			named: HashMap::new(),
+			disk_path: Some(data_dir.to_path_buf()),
+		}
+	}
+
+	fn named_capacity(&self, name: &str) -> usize {
+		self.named.get(name).copied().unwrap_or(1)
+	}
+
+	/// Free space on the filesystem holding `disk_path`
+	fn available_disk_space(&self) -> Option<u64> {
+		let path = self.disk_path.as_ref()?;
+		let disks = sysinfo::Disks::new_with_refreshed_list();
+
+		disks
+			.iter()
+			.filter(|disk| path.starts_with(disk.mount_point()))
+			.max_by_key(|disk| disk.mount_point().as_os_str().len())
+			.map(|disk| disk.available_space())
+	}
+}
+
+/// Task handle of a job that may still be waiting for admission
+#[derive(Clone, Default)]
+pub struct ScheduledTask(Arc<Mutex<Option<TaskHandle<JobError>>>>);
+
+impl ScheduledTask {
+	/// Whether the job has been handed to the task system
+	pub async fn is_dispatched(&self) -> bool {
+		self.0.lock().await.is_some()
+	}
+
+	pub async fn pause(&self) -> Result<(), TaskSystemError> {
+		match &*self.0.lock().await {
+			Some(handle) => handle.pause().await,
+			None => Ok(()),
+		}
+	}
+
+	pub async fn cancel(&self) -> Result<(), TaskSystemError> {
+		match &*self.0.lock().await {
+			Some(handle) => handle.cancel().await,
+			None => Ok(()),
+		}
+	}
+}
+
+impl From<TaskHandle<JobError>> for ScheduledTask {
+	fn from(handle: TaskHandle<JobError>) -> Self {
+		Self(Arc::new(Mutex::new(Some(handle))))
+	}
+}
+
+/// A job known to the scheduler, admitted or not
+struct TrackedJob {
+	job_name: String,
+	requirements: JobRequirements,
+	admitted: bool,
+}
+
+/// A job waiting for admission, with everything needed to start it
+struct WaitingJob {
+	job_id: JobId,
+	priority: JobPriority,
+	sequence: u64,
+	executor: Box<dyn Task<JobError>>,
+	task: ScheduledTask,
+	reason: WaitReason,
+}
+
+#[derive(Default)]
+struct SchedulerState {
+	jobs: HashMap<JobId, TrackedJob>,
+	waiting: Vec<WaitingJob>,
+	next_sequence: u64,
+}
+
+/// Admits jobs into the task system against their requirements
+pub struct JobScheduler {
+	dispatcher: Arc<TaskSystem<JobError>>,
+	budget: ResourceBudget,
+	state: Mutex<SchedulerState>,
+}
+
+impl JobScheduler {
+	pub fn new(dispatcher: Arc<TaskSystem<JobError>>, budget: ResourceBudget) -> Self {
+		Self {
+			dispatcher,
+			budget,
+			state: Mutex::new(SchedulerState::default()),
+		}
+	}
+
+	/// Start a job now if its requirements allow it, queue it otherwise
+	///
+	/// The job is tracked until `status_rx` reports a terminal status, at which
+	/// point its reservation is released and waiting jobs are re-evaluated.
+	pub async fn submit(
+		self: &Arc<Self>,
+		job_id: JobId,
+		job_name: &str,
+		priority: JobPriority,
+		requirements: JobRequirements,
+		executor: Box<dyn Task<JobError>>,
+		status_rx: watch::Receiver<JobStatus>,
+	) -> JobResult<ScheduledTask> {
+		let mut state = self.state.lock().await;
+
+		let task = match self.check(&state, job_id, job_name, &requirements) {
+			Ok(()) => {
+				let handle = self
+					.dispatcher
+					.get_dispatcher()
+					.dispatch_boxed(executor)
+					.await
+					.map_err(|e| JobError::task_system(format!("{:?}", e)))?;
+				state
+					.jobs
+					.insert(job_id, TrackedJob::new(job_name, requirements, true));
+				ScheduledTask::from(handle)
+			}
+			Err(reason) => {
+				info!("Job {} ({}) queued: {}", job_id, job_name, reason);
+				let task = ScheduledTask::default();
+				let sequence = state.next_sequence;
+				state.next_sequence += 1;
+				state
+					.jobs
+					.insert(job_id, TrackedJob::new(job_name, requirements, false));
+				state.waiting.push(WaitingJob {
+					job_id,
+					priority,
+					sequence,
+					executor,
+					task: task.clone(),
+					reason,
+				});
+				task
+			}
+		};
+		drop(state);
+
+		self.watch_until_terminal(job_id, status_rx);
+
+		Ok(task)
+	}
+
+	/// Track a job that was dispatched without going through admission
+	///
+	/// Resumed jobs were admitted before they were interrupted, so they take
+	/// their reservation back without waiting, but still count against the
+	/// budget for jobs dispatched after them.
+	pub async fn track_admitted(
+		self: &Arc<Self>,
+		job_id: JobId,
+		job_name: &str,
+		requirements: JobRequirements,
+		status_rx: watch::Receiver<JobStatus>,
+	) {
+		self.state
+			.lock()
+			.await
+			.jobs
+			.insert(job_id, TrackedJob::new(job_name, requirements, true));
+
+		self.watch_until_terminal(job_id, status_rx);
+	}
+
+	/// Why a job is still queued, `None` if it is not waiting
+	pub async fn waiting_reason(&self, job_id: JobId) -> Option<WaitReason> {
+		self.state
+			.lock()
+			.await
+			.waiting
+			.iter()
+			.find(|waiting| waiting.job_id == job_id)
+			.map(|waiting| waiting.reason.clone())
+	}
+
+	/// Drop a job from the queue before it started, returns whether it was queued
+	pub async fn cancel_waiting(&self, job_id: JobId) -> bool {
+		let mut state = self.state.lock().await;
+		let before = state.waiting.len();
+		state.waiting.retain(|waiting| waiting.job_id != job_id);
+
+		if state.waiting.len() < before {
+			state.jobs.remove(&job_id);
+			true
+		} else {
+			false
+		}
+	}
+
+	fn watch_until_terminal(
+		self: &Arc<Self>,
+		job_id: JobId,
+		mut status_rx: watch::Receiver<JobStatus>,
+	) {
+		let scheduler = Arc::clone(self);
+
+		tokio::spawn(async move {
+			// A closed channel means the job is gone as well
+			while !status_rx.borrow().is_terminal() {
+				if status_rx.changed().await.is_err() {
+					break;
+				}
+			}
+
+			scheduler.release(job_id).await;
+		});
+	}
+
+	/// Forget a finished job and start whatever it was holding back
+	async fn release(&self, job_id: JobId) {
+		let mut state = self.state.lock().await;
+		state.jobs.remove(&job_id);
+		state.waiting.retain(|waiting| waiting.job_id != job_id);
+
+		// Highest priority first, then in submission order
+		state.waiting.sort_by(|a, b| {
+			b.priority
+				.cmp(&a.priority)
+				.then(a.sequence.cmp(&b.sequence))
+		});
+
+		let mut index = 0;
+		while index < state.waiting.len() {
+			let waiting = &state.waiting[index];
+			let candidate_id = waiting.job_id;
+			let Some(tracked) = state.jobs.get(&candidate_id) else {
+				state.waiting.remove(index);
+				continue;
+			};
+
+			match self.check(
+				&state,
+				candidate_id,
+				&tracked.job_name,
+				&tracked.requirements,
+			) {
+				Ok(()) => {
+					let waiting = state.waiting.remove(index);
+					match self
+						.dispatcher
+						.get_dispatcher()
+						.dispatch_boxed(waiting.executor)
+						.await
+					{
+						Ok(handle) => {
+							*waiting.task.0.lock().await = Some(handle);
+							if let Some(tracked) = state.jobs.get_mut(&candidate_id) {
+								tracked.admitted = true;
+							}
+							debug!("Admitted queued job {}", candidate_id);
+						}
+						Err(e) => {
+							// The task system is shutting down, nothing else will start
+							debug!("Failed to dispatch queued job {}: {:?}", candidate_id, e);
+							state.jobs.remove(&candidate_id);
+						}
+					}
+				}
+				Err(reason) => {
+					state.waiting[index].reason = reason;
+					index += 1;
+				}
+			}
+		}
+	}
+
+	/// Check whether a job may start given the jobs currently tracked
+	fn check(
+		&self,
+		state: &SchedulerState,
+		job_id: JobId,
+		job_name: &str,
+		requirements: &JobRequirements,
+	) -> Result<(), WaitReason> {
+		let others = || state.jobs.iter().filter(move |(id, _)| **id != job_id);
+		let admitted = || others().filter(|(_, job)| job.admitted).map(|(_, job)| job);
+
+		for (_, other) in others() {
+			if requirements
+				.dependencies
+				.iter()
+				.any(|name| *name == other.job_name)
+				|| other
+					.requirements
+					.run_after
+					.iter()
+					.any(|name| *name == job_name)
+			{
+				return Err(WaitReason::Dependency {
+					job_name: other.job_name.clone(),
+				});
+			}
+		}
+
+		if let Some(max_concurrent) = requirements.max_concurrent {
+			if admitted().filter(|job| job.job_name == job_name).count() >= max_concurrent {
+				return Err(WaitReason::ConcurrencyLimit { max_concurrent });
+			}
+		}
+
+		for resource in &requirements.resources {
+			match resource {
+				ResourceRequirement::Named(name) => {
+					let holders = admitted()
+						.filter(|job| {
+							job.requirements
+								.resources
+								.iter()
+								.any(|r| matches!(r, ResourceRequirement::Named(n) if n == name))
+						})
+						.count();
+					if holders >= self.budget.named_capacity(name) {
+						return Err(WaitReason::Resource {
+							name: name.to_string(),
+						});
+					}
+				}
+				ResourceRequirement::Memory(required) => {
+					let reserved: u64 = admitted().map(|job| job.requirements.memory()).sum();
+					let available = self.budget.memory.saturating_sub(reserved);
+
+					// A job larger than the whole budget still runs once nothing else
+					// holds memory, waiting would never help it
+					if reserved > 0 && *required > available {
+						return Err(WaitReason::Memory {
+							required: *required,
+							available,
+						});
+					}
+				}
+				ResourceRequirement::DiskSpace(required) => {
+					let reserved: u64 = admitted().map(|job| job.requirements.disk_space()).sum();
+					let Some(free) = self.budget.available_disk_space() else {
+						continue;
+					};
+					let available = free.saturating_sub(reserved);
+
+					if reserved > 0 && *required > available {
+						return Err(WaitReason::DiskSpace {
+							required: *required,
+							available,
+						});
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl TrackedJob {
+	fn new(job_name: &str, requirements: JobRequirements, admitted: bool) -> Self {
+		Self {
+			job_name: job_name.to_string(),
+			requirements,
+			admitted,
+		}
+	}
+}
+
+impl JobRequirements {
+	fn memory(&self) -> u64 {
+		self.resources
+			.iter()
+			.map(|resource| match resource {
+				ResourceRequirement::Memory(bytes) => *bytes,
+				_ => 0,
+			})
+			.sum()
+	}
+
+	fn disk_space(&self) -> u64 {
+		self.resources
+			.iter()
+			.map(|resource| match resource {
+				ResourceRequirement::DiskSpace(bytes) => *bytes,
+				_ => 0,
+			})
+			.sum()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use uuid::Uuid;
+
+	fn scheduler(memory: u64) -> JobScheduler {
+		JobScheduler::new(
+			Arc::new(TaskSystem::new()),
+			ResourceBudget {
+				memory,
+				named: HashMap::new(),
+				disk_path: None,
+			},
+		)
+	}
+
+	fn track(state: &mut SchedulerState, name: &str, requirements: JobRequirements) -> JobId {
+		let job_id = JobId(Uuid::new_v4());
+		state
+			.jobs
+			.insert(job_id, TrackedJob::new(name, requirements, true));
+		job_id
+	}
+
+	#[tokio::test]
+	async fn waits_for_dependencies() {
+		let scheduler = scheduler(0);
+		let mut state = SchedulerState::default();
+		track(&mut state, "ocr", JobRequirements::default());
+
+		let requirements = JobRequirements {
+			dependencies: &["ocr"],
+			..Default::default()
+		};
+		let job_id = JobId(Uuid::new_v4());
+
+		assert_eq!(
+			scheduler.check(&state, job_id, "embedding_generation", &requirements),
+			Err(WaitReason::Dependency {
+				job_name: "ocr".to_string()
+			})
+		);
+		assert_eq!(
+			scheduler.check(&state, job_id, "thumbnail_generation", &Default::default()),
+			Ok(())
+		);
+	}
+
+	#[tokio::test]
+	async fn limits_concurrency_and_named_resources() {
+		let scheduler = scheduler(0);
+		let mut state = SchedulerState::default();
+		let requirements = JobRequirements {
+			max_concurrent: Some(1),
+			..Default::default()
+		};
+		track(&mut state, "thumbnail_generation", requirements.clone());
+		track(
+			&mut state,
+			"speech_to_text",
+			JobRequirements {
+				resources: vec![ResourceRequirement::Named("ml_inference")],
+				..Default::default()
+			},
+		);
+
+		let job_id = JobId(Uuid::new_v4());
+		assert_eq!(
+			scheduler.check(&state, job_id, "thumbnail_generation", &requirements),
+			Err(WaitReason::ConcurrencyLimit { max_concurrent: 1 })
+		);
+		assert_eq!(
+			scheduler.check(
+				&state,
+				job_id,
+				"ocr",
+				&JobRequirements {
+					resources: vec![ResourceRequirement::Named("ml_inference")],
+					..Default::default()
+				}
+			),
+			Err(WaitReason::Resource {
+				name: "ml_inference".to_string()
+			})
+		);
+	}
+
+	#[tokio::test]
+	async fn oversized_jobs_run_alone() {
+		let scheduler = scheduler(100);
+		let mut state = SchedulerState::default();
+		let big = JobRequirements {
+			resources: vec![ResourceRequirement::Memory(150)],
+			..Default::default()
+		};
+
+		let job_id = JobId(Uuid::new_v4());
+		assert_eq!(
+			scheduler.check(&state, job_id, "proxy_generation", &big),
+			Ok(())
+		);
+
+		track(
+			&mut state,
+			"thumbnail_generation",
+			JobRequirements {
+				resources: vec![ResourceRequirement::Memory(40)],
+				..Default::default()
+			},
+		);
+		assert_eq!(
+			scheduler.check(&state, job_id, "proxy_generation", &big),
+			Err(WaitReason::Memory {
+				required: 150,
+				available: 60
+			})
+		);
+	}
+}
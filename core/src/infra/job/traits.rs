@@ -4,6 +4,7 @@ use super::{
 	context::JobContext,
 	error::JobResult,
 	output::JobOutput,
+	scheduler::JobRequirements,
 	types::{ErasedJob, JobSchema},
 };
 use async_trait::async_trait;
@@ -24,6 +25,20 @@ pub trait Job: Serialize + DeserializeOwned + Send + Sync + 'static {
 	/// Optional description
 	const DESCRIPTION: Option<&'static str> = None;
 
+	/// Maximum number of instances of this job running at once
+	const MAX_CONCURRENT: Option<usize> = None;
+
+	/// Jobs that must complete before this one starts
+	const DEPENDENCIES: &'static [&'static str] = &[];
+
+	/// Jobs that must wait for this one to complete before starting
+	const RUN_AFTER: &'static [&'static str] = &[];
+
+	/// Resources held while the job runs
+	fn required_resources() -> Vec<ResourceRequirement> {
+		vec![]
+	}
+
 	/// Get the job schema
 	fn schema() -> JobSchema {
 		JobSchema {
@@ -33,6 +48,16 @@ pub trait Job: Serialize + DeserializeOwned + Send + Sync + 'static {
 			description: Self::DESCRIPTION,
 		}
 	}
+
+	/// Get the scheduling constraints enforced by the job manager
+	fn requirements() -> JobRequirements {
+		JobRequirements {
+			max_concurrent: Self::MAX_CONCURRENT,
+			resources: Self::required_resources(),
+			dependencies: Self::DEPENDENCIES,
+			run_after: Self::RUN_AFTER,
+		}
+	}
 }
 
 /// Handler trait that defines job execution logic
@@ -90,19 +115,6 @@ pub trait ProgressReporter {
 	type Progress: super::progress::JobProgress;
 }
 
-/// Resource requirements for a job
-pub trait ResourceRequirements {
-	/// Maximum number of concurrent instances
-	fn max_concurrent() -> Option<usize> {
-		None
-	}
-
-	/// Required resources
-	fn required_resources() -> Vec<ResourceRequirement> {
-		vec![]
-	}
-}
-
 /// A required resource
 #[derive(Debug, Clone)]
 pub enum ResourceRequirement {
@@ -114,19 +126,6 @@ pub enum ResourceRequirement {
 	Memory(u64),
 }
 
-/// Job dependencies
-pub trait JobDependencies {
-	/// Jobs that must complete before this one
-	fn dependencies() -> &'static [&'static str] {
-		&[]
-	}
-
-	/// Jobs that should run after this one
-	fn run_after() -> &'static [&'static str] {
-		&[]
-	}
-}
-
 /// A dyn-compatible trait for dynamic job operations
 /// This is separate from Job to avoid serialization trait bounds
 pub trait DynJob: Send + Sync {
@@ -115,6 +115,7 @@ pub struct JobSchema {
 pub struct JobRegistration {
 	pub name: &'static str,
 	pub schema_fn: fn() -> JobSchema,
+	pub requirements_fn: fn() -> crate::infra::job::scheduler::JobRequirements,
 	pub create_fn: fn(serde_json::Value) -> Result<Box<dyn ErasedJob>, serde_json::Error>,
 	pub deserialize_fn: fn(&[u8]) -> Result<Box<dyn ErasedJob>, rmp_serde::decode::Error>,
 	pub deserialize_dyn_fn:
@@ -178,6 +179,8 @@ pub struct JobInfo {
 	pub parent_job_id: Option<Uuid>,
 	pub action_type: Option<String>,
 	pub action_context: Option<ActionContextInfo>,
+	/// Why a queued job hasn't started yet, if it is waiting on the scheduler
+	pub waiting_reason: Option<crate::infra::job::scheduler::WaitReason>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -67,6 +67,7 @@ impl SyncConfig {
 				pruning_interval_secs: 1800,
 				enable_metrics: true,
 				metrics_log_interval_secs: 60,
+				verification_interval_secs: 21600,
 			},
 		}
 	}
@@ -106,6 +107,7 @@ impl SyncConfig {
 				pruning_interval_secs: 7200,
 				enable_metrics: true,
 				metrics_log_interval_secs: 600,
+				verification_interval_secs: 86400,
 			},
 		}
 	}
@@ -143,6 +145,7 @@ impl SyncConfig {
 				pruning_interval_secs: 14400,
 				enable_metrics: false,
 				metrics_log_interval_secs: 1800,
+				verification_interval_secs: 0,
 			},
 		}
 	}
@@ -308,6 +311,13 @@ pub struct MonitoringConfig {
 	///
 	/// Default: 300 seconds (5 minutes)
 	pub metrics_log_interval_secs: u64,
+
+	/// Verify device-owned data against connected peers at this interval (seconds)
+	///
+	/// Compares digests of each peer's records with the peer and repairs what differs.
+	/// 0 disables periodic verification, it can still be run on demand.
+	/// Default: 86400 seconds (1 day)
+	pub verification_interval_secs: u64,
 }
 
 impl Default for MonitoringConfig {
@@ -316,6 +326,7 @@ impl Default for MonitoringConfig {
 			pruning_interval_secs: 3600,
 			enable_metrics: true,
 			metrics_log_interval_secs: 300,
+			verification_interval_secs: 86400,
 		}
 	}
 }
@@ -360,6 +371,7 @@ mod tests {
 		assert_eq!(config.retention.tombstone_max_retention_days, 14);
 		assert_eq!(config.network.sync_loop_interval_secs, 30);
 		assert!(!config.monitoring.enable_metrics); // Battery saving
+		assert_eq!(config.monitoring.verification_interval_secs, 0);
 	}
 
 	#[test]
@@ -142,6 +142,12 @@ pub enum SyncEventType {
 	/// Peer device disconnected
 	PeerDisconnected,
 
+	/// Verification against a peer finished
+	VerificationCompleted,
+
+	/// Verification found records that differ from the peer's
+	VerificationMismatch,
+
 	/// Sync error occurred
 	SyncError,
 }
@@ -162,6 +168,8 @@ impl SyncEventType {
 			Self::BackfillResponseSent => (EventCategory::Network, EventSeverity::Debug),
 			Self::PeerConnected => (EventCategory::Network, EventSeverity::Info),
 			Self::PeerDisconnected => (EventCategory::Network, EventSeverity::Info),
+			Self::VerificationCompleted => (EventCategory::Lifecycle, EventSeverity::Info),
+			Self::VerificationMismatch => (EventCategory::DataFlow, EventSeverity::Warning),
 			Self::SyncError => (EventCategory::Error, EventSeverity::Error),
 		}
 	}
@@ -181,6 +189,8 @@ impl SyncEventType {
 			Self::BackfillResponseSent => "backfill_response_sent",
 			Self::PeerConnected => "peer_connected",
 			Self::PeerDisconnected => "peer_disconnected",
+			Self::VerificationCompleted => "verification_completed",
+			Self::VerificationMismatch => "verification_mismatch",
 			Self::SyncError => "sync_error",
 		}
 	}
@@ -200,6 +210,8 @@ impl SyncEventType {
 			"backfill_response_sent" => Some(Self::BackfillResponseSent),
 			"peer_connected" => Some(Self::PeerConnected),
 			"peer_disconnected" => Some(Self::PeerDisconnected),
+			"verification_completed" => Some(Self::VerificationCompleted),
+			"verification_mismatch" => Some(Self::VerificationMismatch),
 			"sync_error" => Some(Self::SyncError),
 			_ => None,
 		}
@@ -328,6 +328,31 @@ impl PeerLog {
 		Ok(())
 	}
 
+	/// Get the HLC a peer last acknowledged, `None` if it never did
+	pub async fn get_ack(&self, peer_id: Uuid) -> Result<Option<HLC>, PeerLogError> {
+		let result = self
+			.conn
+			.query_one(Statement::from_sql_and_values(
+				DbBackend::Sqlite,
+				"SELECT last_acked_hlc FROM peer_acks WHERE peer_device_id = ?",
+				vec![peer_id.to_string().into()],
+			))
+			.await
+			.map_err(|e| PeerLogError::QueryError(e.to_string()))?;
+
+		match result {
+			Some(row) => {
+				let hlc_str: String = row
+					.try_get("", "last_acked_hlc")
+					.map_err(|e| PeerLogError::QueryError(e.to_string()))?;
+				let hlc = HLC::from_string(&hlc_str)
+					.map_err(|e| PeerLogError::ParseError(e.to_string()))?;
+				Ok(Some(hlc))
+			}
+			None => Ok(None),
+		}
+	}
+
 	/// Get the minimum HLC that all peers have acknowledged
 	///
 	/// Excludes self-ACKs (where peer_device_id == our device_id) from calculation.
@@ -681,6 +681,38 @@ pub async fn query_device_state(
 		.map_err(|e| ApplyError::DatabaseError(e.to_string()))
 }
 
+/// Query the current state of a shared model (for verification)
+///
+/// Routes to the appropriate model's query function via registry.
+pub async fn query_shared_state(
+	model_type: &str,
+	batch_size: usize,
+	db: Arc<DatabaseConnection>,
+) -> Result<Vec<(uuid::Uuid, serde_json::Value, chrono::DateTime<chrono::Utc>)>, ApplyError> {
+	let query_fn = {
+		let registry = SYNCABLE_REGISTRY.read().await;
+		let registration = registry
+			.get(model_type)
+			.ok_or_else(|| ApplyError::UnknownModel(model_type.to_string()))?;
+
+		if registration.is_device_owned {
+			return Err(ApplyError::WrongSyncType {
+				model: model_type.to_string(),
+				expected: "shared".to_string(),
+				got: "device-owned".to_string(),
+			});
+		}
+
+		registration
+			.state_query_fn
+			.ok_or_else(|| ApplyError::MissingQueryFunction(model_type.to_string()))?
+	}; // Lock is dropped here
+
+	query_fn(None, None, None, batch_size, db)
+		.await
+		.map_err(|e| ApplyError::DatabaseError(e.to_string()))
+}
+
 /// Query all shared models for backfill (generic registry-based approach)
 ///
 /// This discovers and queries ALL shared models registered in the system,
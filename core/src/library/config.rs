@@ -1,5 +1,6 @@
 //! Library configuration types
 
+use crate::{filetype::CustomFileType, ops::sidecar::SidecarQuota};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -69,6 +70,14 @@ pub struct LibrarySettings {
 	/// Indexer settings (rule toggles and related)
 	#[serde(default)]
 	pub indexer: IndexerSettings,
+
+	/// Disk space limits for regenerable sidecars on this device, none by default
+	#[serde(default)]
+	pub sidecar_quotas: Vec<SidecarQuota>,
+
+	/// File types defined by the user for this library
+	#[serde(default)]
+	pub custom_file_types: Vec<CustomFileType>,
 }
 
 impl LibraryConfig {
@@ -102,6 +111,8 @@ impl Default for LibrarySettings {
 			auto_track_system_volumes: true,               // Default to true for user convenience
 			auto_track_external_volumes: false,            // Default to false for privacy
 			indexer: IndexerSettings::default(),
+			sidecar_quotas: Vec::new(),
+			custom_file_types: Vec::new(),
 		}
 	}
 }
@@ -538,6 +538,12 @@ impl LibraryManager {
 			transaction_manager,
 			sync_service: OnceCell::new(),      // Initialized later
 			file_sync_service: OnceCell::new(), // Initialized later
+			vector_index: tokio::sync::OnceCell::new(),
+			file_types: std::sync::RwLock::new(Arc::new(
+				context
+					.file_type_registry()
+					.with_custom_types(&config.settings.custom_file_types),
+			)),
 			device_cache: Arc::new(std::sync::RwLock::new(device_cache)),
 			_lock: std::sync::Mutex::new(Some(lock)),
 		});
@@ -573,6 +579,16 @@ impl LibraryManager {
 			warn!("Sidecar manager not available during library open");
 		}
 
+		// Recurring job schedules fire from now on, catching up runs missed while closed
+		library.jobs.start_schedule_runner();
+
+		if let Err(e) = library.init_file_sync_service() {
+			warn!(
+				"Failed to initialize file sync service for library {}: {}",
+				config.id, e
+			);
+		}
+
 		// Now that the library is registered and sidecar manager is initialized, resume interrupted jobs
 		// DISABLED: Jobs will remain paused on startup instead of auto-resuming
 		// if let Err(e) = library.jobs.resume_interrupted_jobs_after_load().await {
@@ -1508,6 +1524,7 @@ impl LibraryManager {
 					IndexMode::None,
 					None, // No action context
 					None, // No job policies
+					None, // No indexer limits
 					&context.volume_manager,
 				)
 				.await
@@ -65,6 +65,12 @@ pub struct Library {
 	/// File sync service for cross-location file synchronization (initialized after library creation)
 	file_sync_service: OnceCell<Arc<crate::service::file_sync::FileSyncService>>,
 
+	/// Nearest neighbour index over content embeddings (loaded on first semantic search)
+	vector_index: tokio::sync::OnceCell<Arc<RwLock<crate::ops::search::vector_index::VectorIndex>>>,
+
+	/// File types known to this library, the core's plus the library's custom types
+	file_types: StdRwLock<Arc<crate::filetype::FileTypeRegistry>>,
+
 	/// Library-specific device cache (slug → UUID)
 	/// Loaded from this library's devices table for per-library device resolution
 	device_cache: Arc<StdRwLock<HashMap<String, Uuid>>>,
@@ -156,6 +162,26 @@ impl Library {
 		&self.core_context
 	}
 
+	/// Get the file type registry, including this library's custom types
+	pub fn file_types(&self) -> Arc<crate::filetype::FileTypeRegistry> {
+		match self.file_types.read() {
+			Ok(file_types) => file_types.clone(),
+			Err(_) => self.core_context.file_type_registry().clone(),
+		}
+	}
+
+	/// Rebuild the file type registry after the library's custom types changed
+	pub fn reload_file_types(&self, custom_types: &[crate::filetype::CustomFileType]) {
+		let registry = self
+			.core_context
+			.file_type_registry()
+			.with_custom_types(custom_types);
+
+		if let Ok(mut file_types) = self.file_types.write() {
+			*file_types = Arc::new(registry);
+		}
+	}
+
 	/// Initialize the sync service (called during library setup)
 	#[cfg_attr(test, allow(dead_code))] // Exposed for integration tests
 	pub async fn init_sync_service(
@@ -259,6 +285,7 @@ impl Library {
 		let config_path = self.path.join("library.json");
 		let json = tokio::fs::read_to_string(config_path).await?;
 		let config: LibraryConfig = serde_json::from_str(&json)?;
+		self.reload_file_types(&config.settings.custom_file_types);
 
 		let mut current_config = self.config.write().await;
 		*current_config = config;
@@ -468,6 +495,24 @@ impl Library {
 			.is_ok()
 	}
 
+	/// Get the semantic search index, loading it from disk on first use
+	pub async fn vector_index(
+		&self,
+	) -> Result<Arc<RwLock<crate::ops::search::vector_index::VectorIndex>>> {
+		use crate::ops::{models::EmbeddingModel, search::vector_index::VectorIndex};
+
+		self.vector_index
+			.get_or_try_init(|| async {
+				let model = EmbeddingModel::default();
+				VectorIndex::load(&self.path, model.id(), model.dimensions())
+					.await
+					.map(|index| Arc::new(RwLock::new(index)))
+					.map_err(|e| LibraryError::Other(format!("Failed to load vector index: {}", e)))
+			})
+			.await
+			.cloned()
+	}
+
 	/// Shutdown the library, gracefully stopping all jobs
 	pub async fn shutdown(&self) -> Result<()> {
 		debug!("Shutting down library {}", self.id());
@@ -483,6 +528,13 @@ impl Library {
 		// Shutdown the job manager, which will pause all running jobs
 		self.jobs.shutdown().await?;
 
+		// Persist vectors added since the index was last saved
+		if let Some(vector_index) = self.vector_index.get() {
+			if let Err(e) = vector_index.write().await.save().await {
+				warn!("Failed to save vector index: {}", e);
+			}
+		}
+
 		// Save config to ensure any updates are persisted
 		let config = self.config.read().await;
 		self.save_config(&*config).await?;
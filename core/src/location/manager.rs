@@ -44,6 +44,7 @@ impl LocationManager {
 		index_mode: IndexMode,
 		action_context: Option<crate::infra::action::context::ActionContext>,
 		job_policies: Option<String>,
+		indexer_limits: Option<crate::ops::indexing::IndexerLimits>,
 		volume_manager: &crate::volume::VolumeManager,
 	) -> LocationResult<(Uuid, String)> {
 		// Canonicalize local physical paths to absolute form before storing.
@@ -255,6 +256,18 @@ impl LocationManager {
 
 		let location_record = location_model.insert(&txn).await?;
 
+		// Store the limits with the location so its first index already applies them
+		if let Some(limits) = &indexer_limits {
+			crate::ops::indexing::rules::save_location_limits(
+				&txn,
+				location_record.id,
+				location_id,
+				limits,
+			)
+			.await
+			.map_err(|e| LocationError::Other(format!("Failed to save indexer limits: {}", e)))?;
+		}
+
 		// Commit transaction
 		txn.commit().await?;
 		info!("Created location record with ID: {}", location_record.id);
@@ -0,0 +1,129 @@
+use super::{input::CollectionAddFilesInput, output::CollectionAddFilesOutput};
+use crate::{
+	context::CoreContext,
+	infra::{
+		action::{error::ActionError, LibraryAction},
+		db::entities::{collection_entry, entry},
+	},
+};
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set, TransactionTrait};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionAddFilesAction {
+	input: CollectionAddFilesInput,
+}
+
+impl LibraryAction for CollectionAddFilesAction {
+	type Input = CollectionAddFilesInput;
+	type Output = CollectionAddFilesOutput;
+
+	fn from_input(input: CollectionAddFilesInput) -> Result<Self, String> {
+		if input.file_ids.is_empty() {
+			return Err("No files to add".to_string());
+		}
+
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		library: std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let db = library.db().conn();
+
+		let collection_model =
+			super::super::find_collection(&library, self.input.collection_id).await?;
+
+		let entries: HashMap<uuid::Uuid, i32> = entry::Entity::find()
+			.filter(entry::Column::Uuid.is_in(self.input.file_ids.clone()))
+			.all(db)
+			.await
+			.map_err(ActionError::SeaOrm)?
+			.into_iter()
+			.filter_map(|entry| Some((entry.uuid?, entry.id)))
+			.collect();
+		if let Some(missing) = self
+			.input
+			.file_ids
+			.iter()
+			.find(|file_id| !entries.contains_key(file_id))
+		{
+			return Err(ActionError::Validation {
+				field: "file_ids".to_string(),
+				message: format!("File {} not found", missing),
+			});
+		}
+
+		// Read the current members and append in one transaction, so concurrent adds
+		// can't hand out the same position
+		let txn = db.begin().await.map_err(ActionError::SeaOrm)?;
+
+		let members = collection_entry::Entity::find()
+			.filter(collection_entry::Column::CollectionId.eq(collection_model.id))
+			.all(&txn)
+			.await
+			.map_err(ActionError::SeaOrm)?;
+		let mut present: HashSet<i32> = members.iter().map(|m| m.entry_id).collect();
+		let mut position = members.iter().map(|m| m.position).max().unwrap_or(-1);
+
+		let now = Utc::now();
+		let mut added = Vec::new();
+		for file_id in &self.input.file_ids {
+			let entry_id = entries[file_id];
+			if !present.insert(entry_id) {
+				continue;
+			}
+			position += 1;
+
+			let active_model = collection_entry::ActiveModel {
+				collection_id: Set(collection_model.id),
+				entry_id: Set(entry_id),
+				added_at: Set(now),
+				position: Set(position),
+				uuid: Set(uuid::Uuid::new_v4()),
+				version: Set(1),
+				updated_at: Set(now),
+			};
+			added.push(
+				active_model
+					.insert(&txn)
+					.await
+					.map_err(ActionError::SeaOrm)?,
+			);
+		}
+
+		txn.commit().await.map_err(ActionError::SeaOrm)?;
+
+		library
+			.sync_models_batch(&added, crate::infra::sync::ChangeType::Insert, db)
+			.await
+			.map_err(|e| {
+				ActionError::Internal(format!("Failed to sync collection files: {}", e))
+			})?;
+
+		let collection = super::super::collection_changed(&library, collection_model).await?;
+
+		Ok(CollectionAddFilesOutput {
+			collection,
+			added: added.len() as u32,
+		})
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"collections.add_files"
+	}
+
+	async fn validate(
+		&self,
+		_library: &std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<crate::infra::action::ValidationResult, ActionError> {
+		Ok(crate::infra::action::ValidationResult::Success { metadata: None })
+	}
+}
+
+crate::register_library_action!(CollectionAddFilesAction, "collections.add_files");
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionAddFilesInput {
+	pub collection_id: Uuid,
+	/// Files to append, in order. Files already in the collection keep their place.
+	pub file_ids: Vec<Uuid>,
+}
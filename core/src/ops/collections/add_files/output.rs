@@ -0,0 +1,10 @@
+use crate::domain::Collection;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionAddFilesOutput {
+	pub collection: Collection,
+	/// Files that weren't in the collection yet
+	pub added: u32,
+}
@@ -0,0 +1,75 @@
+use super::{input::CollectionCreateInput, output::CollectionCreateOutput};
+use crate::{
+	context::CoreContext,
+	infra::action::{error::ActionError, LibraryAction},
+};
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, Set};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionCreateAction {
+	input: CollectionCreateInput,
+}
+
+impl CollectionCreateAction {
+	pub fn new(input: CollectionCreateInput) -> Self {
+		Self { input }
+	}
+}
+
+impl LibraryAction for CollectionCreateAction {
+	type Input = CollectionCreateInput;
+	type Output = CollectionCreateOutput;
+
+	fn from_input(input: CollectionCreateInput) -> Result<Self, String> {
+		if input.name.trim().is_empty() {
+			return Err("Collection name cannot be empty".to_string());
+		}
+
+		Ok(CollectionCreateAction::new(input))
+	}
+
+	async fn execute(
+		self,
+		library: std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let db = library.db().conn();
+		let now = Utc::now();
+
+		let active_model = crate::infra::db::entities::collection::ActiveModel {
+			id: sea_orm::NotSet,
+			uuid: Set(uuid::Uuid::new_v4()),
+			name: Set(self.input.name.trim().to_string()),
+			description: Set(self.input.description),
+			created_at: Set(now),
+			updated_at: Set(now),
+		};
+
+		let result = active_model.insert(db).await.map_err(ActionError::SeaOrm)?;
+
+		library
+			.sync_model(&result, crate::infra::sync::ChangeType::Insert)
+			.await
+			.map_err(|e| ActionError::Internal(format!("Failed to sync collection: {}", e)))?;
+
+		let collection = super::super::collection_changed(&library, result).await?;
+
+		Ok(CollectionCreateOutput { collection })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"collections.create"
+	}
+
+	async fn validate(
+		&self,
+		_library: &std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<crate::infra::action::ValidationResult, ActionError> {
+		Ok(crate::infra::action::ValidationResult::Success { metadata: None })
+	}
+}
+
+crate::register_library_action!(CollectionCreateAction, "collections.create");
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionCreateInput {
+	pub name: String,
+	pub description: Option<String>,
+}
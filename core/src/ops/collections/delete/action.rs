@@ -0,0 +1,64 @@
+use super::{input::CollectionDeleteInput, output::CollectionDeleteOutput};
+use crate::{
+	context::CoreContext,
+	infra::action::{error::ActionError, LibraryAction},
+};
+use sea_orm::ModelTrait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionDeleteAction {
+	input: CollectionDeleteInput,
+}
+
+impl LibraryAction for CollectionDeleteAction {
+	type Input = CollectionDeleteInput;
+	type Output = CollectionDeleteOutput;
+
+	fn from_input(input: CollectionDeleteInput) -> Result<Self, String> {
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		library: std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let db = library.db().conn();
+
+		let collection_model =
+			super::super::find_collection(&library, self.input.collection_id).await?;
+		let collection_id = collection_model.uuid;
+
+		// Delete cascades to the collection's entries, here and on peers applying it
+		collection_model
+			.clone()
+			.delete(db)
+			.await
+			.map_err(ActionError::SeaOrm)?;
+
+		library
+			.sync_model(&collection_model, crate::infra::sync::ChangeType::Delete)
+			.await
+			.map_err(|e| ActionError::Internal(format!("Failed to sync collection: {}", e)))?;
+
+		use crate::domain::{resource::EventEmitter, Collection};
+		Collection::emit_deleted(collection_id, library.event_bus());
+
+		Ok(CollectionDeleteOutput { success: true })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"collections.delete"
+	}
+
+	async fn validate(
+		&self,
+		_library: &std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<crate::infra::action::ValidationResult, ActionError> {
+		Ok(crate::infra::action::ValidationResult::Success { metadata: None })
+	}
+}
+
+crate::register_library_action!(CollectionDeleteAction, "collections.delete");
@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionDeleteInput {
+	pub collection_id: Uuid,
+}
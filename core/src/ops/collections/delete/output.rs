@@ -0,0 +1,7 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionDeleteOutput {
+	pub success: bool,
+}
@@ -0,0 +1,10 @@
+use crate::domain::{Collection, CollectionFile};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionGetOutput {
+	pub collection: Collection,
+	/// Files in collection order
+	pub files: Vec<CollectionFile>,
+}
@@ -0,0 +1,73 @@
+use super::output::CollectionGetOutput;
+use crate::domain::{Collection, CollectionFile};
+use crate::infra::query::{QueryError, QueryResult};
+use crate::{context::CoreContext, infra::query::LibraryQuery};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionGetQueryInput {
+	pub collection_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionGetQuery {
+	collection_id: Uuid,
+}
+
+impl LibraryQuery for CollectionGetQuery {
+	type Input = CollectionGetQueryInput;
+	type Output = CollectionGetOutput;
+
+	fn from_input(input: Self::Input) -> QueryResult<Self> {
+		Ok(Self {
+			collection_id: input.collection_id,
+		})
+	}
+
+	async fn execute(
+		self,
+		context: Arc<CoreContext>,
+		session: crate::infra::api::SessionContext,
+	) -> QueryResult<Self::Output> {
+		let library_id = session
+			.current_library_id
+			.ok_or_else(|| QueryError::Internal("No library selected".to_string()))?;
+
+		let library = context
+			.libraries()
+			.await
+			.get_library(library_id)
+			.await
+			.ok_or_else(|| QueryError::Internal("Library not found".to_string()))?;
+
+		let db = library.db().conn();
+
+		let collection_model = crate::infra::db::entities::collection::Entity::find()
+			.filter(crate::infra::db::entities::collection::Column::Uuid.eq(self.collection_id))
+			.one(db)
+			.await?
+			.ok_or_else(|| {
+				QueryError::Internal(format!("Collection {} not found", self.collection_id))
+			})?;
+
+		let files = CollectionFile::for_collection(db, collection_model.id)
+			.await
+			.map_err(|e| QueryError::Internal(e.to_string()))?;
+
+		let collection = Collection::from_db_models(db, vec![collection_model])
+			.await
+			.map_err(|e| QueryError::Internal(e.to_string()))?
+			.pop()
+			.ok_or_else(|| {
+				QueryError::Internal(format!("Collection {} not found", self.collection_id))
+			})?;
+
+		Ok(CollectionGetOutput { collection, files })
+	}
+}
+
+crate::register_library_query!(CollectionGetQuery, "collections.get");
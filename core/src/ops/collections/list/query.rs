@@ -0,0 +1,55 @@
+use super::output::CollectionsListOutput;
+use crate::domain::Collection;
+use crate::infra::query::{QueryError, QueryResult};
+use crate::{context::CoreContext, infra::query::LibraryQuery};
+use sea_orm::{EntityTrait, QueryOrder};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionsListQueryInput;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionsListQuery;
+
+impl LibraryQuery for CollectionsListQuery {
+	type Input = CollectionsListQueryInput;
+	type Output = CollectionsListOutput;
+
+	fn from_input(_input: Self::Input) -> QueryResult<Self> {
+		Ok(Self {})
+	}
+
+	async fn execute(
+		self,
+		context: Arc<CoreContext>,
+		session: crate::infra::api::SessionContext,
+	) -> QueryResult<Self::Output> {
+		let library_id = session
+			.current_library_id
+			.ok_or_else(|| QueryError::Internal("No library selected".to_string()))?;
+
+		let library = context
+			.libraries()
+			.await
+			.get_library(library_id)
+			.await
+			.ok_or_else(|| QueryError::Internal("Library not found".to_string()))?;
+
+		let db = library.db().conn();
+
+		let collection_models = crate::infra::db::entities::collection::Entity::find()
+			.order_by_asc(crate::infra::db::entities::collection::Column::Name)
+			.all(db)
+			.await?;
+
+		let collections = Collection::from_db_models(db, collection_models)
+			.await
+			.map_err(|e| QueryError::Internal(e.to_string()))?;
+
+		Ok(CollectionsListOutput { collections })
+	}
+}
+
+crate::register_library_query!(CollectionsListQuery, "collections.list");
@@ -0,0 +1,66 @@
+//! Collection operations
+//!
+//! Queries and actions for hand-curated, ordered collections of files. Collections
+//! and their membership are shared resources, every change is synced to peers.
+
+pub mod add_files;
+pub mod create;
+pub mod delete;
+pub mod get;
+pub mod list;
+pub mod remove_files;
+pub mod rename;
+pub mod reorder;
+
+pub use add_files::*;
+pub use create::*;
+pub use delete::*;
+pub use get::*;
+pub use list::*;
+pub use remove_files::*;
+pub use rename::*;
+pub use reorder::*;
+
+use crate::{
+	domain::Collection,
+	infra::{action::error::ActionError, db::entities::collection},
+	library::Library,
+};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use uuid::Uuid;
+
+/// Find a collection by its UUID
+pub(crate) async fn find_collection(
+	library: &Library,
+	collection_id: Uuid,
+) -> Result<collection::Model, ActionError> {
+	collection::Entity::find()
+		.filter(collection::Column::Uuid.eq(collection_id))
+		.one(library.db().conn())
+		.await
+		.map_err(ActionError::SeaOrm)?
+		.ok_or_else(|| ActionError::Internal(format!("Collection {} not found", collection_id)))
+}
+
+/// Emit a collection's resource event and return it with its file count
+pub(crate) async fn collection_changed(
+	library: &Library,
+	model: collection::Model,
+) -> Result<Collection, ActionError> {
+	let collection_id = model.uuid;
+
+	let resource_manager = crate::domain::ResourceManager::new(
+		std::sync::Arc::new(library.db().conn().clone()),
+		library.event_bus().clone(),
+	);
+	resource_manager
+		.emit_resource_events("collection", vec![collection_id])
+		.await
+		.map_err(|e| ActionError::Internal(format!("Failed to emit resource events: {}", e)))?;
+
+	Collection::from_db_models(library.db().conn(), vec![model])
+		.await
+		.map_err(|e| ActionError::Internal(e.to_string()))?
+		.pop()
+		.ok_or_else(|| ActionError::Internal(format!("Collection {} not found", collection_id)))
+}
@@ -0,0 +1,87 @@
+use super::{input::CollectionRemoveFilesInput, output::CollectionRemoveFilesOutput};
+use crate::{
+	context::CoreContext,
+	infra::{
+		action::{error::ActionError, LibraryAction},
+		db::entities::{collection_entry, entry},
+	},
+};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionRemoveFilesAction {
+	input: CollectionRemoveFilesInput,
+}
+
+impl LibraryAction for CollectionRemoveFilesAction {
+	type Input = CollectionRemoveFilesInput;
+	type Output = CollectionRemoveFilesOutput;
+
+	fn from_input(input: CollectionRemoveFilesInput) -> Result<Self, String> {
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		library: std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let db = library.db().conn();
+
+		let collection_model =
+			super::super::find_collection(&library, self.input.collection_id).await?;
+
+		let entry_ids: Vec<i32> = entry::Entity::find()
+			.filter(entry::Column::Uuid.is_in(self.input.file_ids.clone()))
+			.all(db)
+			.await
+			.map_err(ActionError::SeaOrm)?
+			.into_iter()
+			.map(|entry| entry.id)
+			.collect();
+
+		let removed = collection_entry::Entity::find()
+			.filter(collection_entry::Column::CollectionId.eq(collection_model.id))
+			.filter(collection_entry::Column::EntryId.is_in(entry_ids))
+			.all(db)
+			.await
+			.map_err(ActionError::SeaOrm)?;
+
+		if !removed.is_empty() {
+			collection_entry::Entity::delete_many()
+				.filter(collection_entry::Column::Uuid.is_in(removed.iter().map(|m| m.uuid)))
+				.exec(db)
+				.await
+				.map_err(ActionError::SeaOrm)?;
+
+			library
+				.sync_models_batch(&removed, crate::infra::sync::ChangeType::Delete, db)
+				.await
+				.map_err(|e| {
+					ActionError::Internal(format!("Failed to sync collection files: {}", e))
+				})?;
+		}
+
+		let collection = super::super::collection_changed(&library, collection_model).await?;
+
+		Ok(CollectionRemoveFilesOutput {
+			collection,
+			removed: removed.len() as u32,
+		})
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"collections.remove_files"
+	}
+
+	async fn validate(
+		&self,
+		_library: &std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<crate::infra::action::ValidationResult, ActionError> {
+		Ok(crate::infra::action::ValidationResult::Success { metadata: None })
+	}
+}
+
+crate::register_library_action!(CollectionRemoveFilesAction, "collections.remove_files");
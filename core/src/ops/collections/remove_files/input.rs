@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionRemoveFilesInput {
+	pub collection_id: Uuid,
+	pub file_ids: Vec<Uuid>,
+}
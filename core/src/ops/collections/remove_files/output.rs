@@ -0,0 +1,10 @@
+use crate::domain::Collection;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionRemoveFilesOutput {
+	pub collection: Collection,
+	/// Files that were in the collection
+	pub removed: u32,
+}
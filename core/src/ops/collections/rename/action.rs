@@ -0,0 +1,67 @@
+use super::{input::CollectionRenameInput, output::CollectionRenameOutput};
+use crate::{
+	context::CoreContext,
+	infra::action::{error::ActionError, LibraryAction},
+};
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, Set};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionRenameAction {
+	input: CollectionRenameInput,
+}
+
+impl LibraryAction for CollectionRenameAction {
+	type Input = CollectionRenameInput;
+	type Output = CollectionRenameOutput;
+
+	fn from_input(input: CollectionRenameInput) -> Result<Self, String> {
+		if input.name.trim().is_empty() {
+			return Err("Collection name cannot be empty".to_string());
+		}
+
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		library: std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let db = library.db().conn();
+
+		let collection_model =
+			super::super::find_collection(&library, self.input.collection_id).await?;
+
+		let mut active_model: crate::infra::db::entities::collection::ActiveModel =
+			collection_model.into();
+		active_model.name = Set(self.input.name.trim().to_string());
+		active_model.updated_at = Set(Utc::now());
+
+		let result = active_model.update(db).await.map_err(ActionError::SeaOrm)?;
+
+		library
+			.sync_model(&result, crate::infra::sync::ChangeType::Update)
+			.await
+			.map_err(|e| ActionError::Internal(format!("Failed to sync collection: {}", e)))?;
+
+		let collection = super::super::collection_changed(&library, result).await?;
+
+		Ok(CollectionRenameOutput { collection })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"collections.rename"
+	}
+
+	async fn validate(
+		&self,
+		_library: &std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<crate::infra::action::ValidationResult, ActionError> {
+		Ok(crate::infra::action::ValidationResult::Success { metadata: None })
+	}
+}
+
+crate::register_library_action!(CollectionRenameAction, "collections.rename");
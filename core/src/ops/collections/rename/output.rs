@@ -0,0 +1,8 @@
+use crate::domain::Collection;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionRenameOutput {
+	pub collection: Collection,
+}
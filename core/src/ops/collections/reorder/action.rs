@@ -0,0 +1,131 @@
+use super::{input::CollectionReorderInput, output::CollectionReorderOutput};
+use crate::{
+	context::CoreContext,
+	infra::{
+		action::{error::ActionError, LibraryAction},
+		db::entities::{collection_entry, entry},
+	},
+};
+use chrono::Utc;
+use sea_orm::{
+	ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, QueryOrder, Set, TransactionTrait,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CollectionReorderAction {
+	input: CollectionReorderInput,
+}
+
+impl LibraryAction for CollectionReorderAction {
+	type Input = CollectionReorderInput;
+	type Output = CollectionReorderOutput;
+
+	fn from_input(input: CollectionReorderInput) -> Result<Self, String> {
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		library: std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let db = library.db().conn();
+
+		let collection_model =
+			super::super::find_collection(&library, self.input.collection_id).await?;
+
+		// Read and rewrite positions in one transaction, so a concurrent add or
+		// reorder can't interleave with this one
+		let txn = db.begin().await.map_err(ActionError::SeaOrm)?;
+
+		let mut members = collection_entry::Entity::find()
+			.filter(collection_entry::Column::CollectionId.eq(collection_model.id))
+			.order_by_asc(collection_entry::Column::Position)
+			.order_by_asc(collection_entry::Column::AddedAt)
+			.order_by_asc(collection_entry::Column::Uuid)
+			.all(&txn)
+			.await
+			.map_err(ActionError::SeaOrm)?;
+
+		let entry_ids: HashMap<uuid::Uuid, i32> = entry::Entity::find()
+			.filter(entry::Column::Uuid.is_in(self.input.file_ids.clone()))
+			.all(&txn)
+			.await
+			.map_err(ActionError::SeaOrm)?
+			.into_iter()
+			.filter_map(|entry| Some((entry.uuid?, entry.id)))
+			.collect();
+
+		// Rank listed files by their place in the input, the rest after them.
+		// The sort is stable, so unlisted files keep their current order.
+		let mut rank: HashMap<i32, usize> = HashMap::new();
+		for file_id in &self.input.file_ids {
+			let entry_id = entry_ids
+				.get(file_id)
+				.ok_or_else(|| ActionError::Validation {
+					field: "file_ids".to_string(),
+					message: format!("File {} not found", file_id),
+				})?;
+			if !members.iter().any(|m| m.entry_id == *entry_id) {
+				return Err(ActionError::Validation {
+					field: "file_ids".to_string(),
+					message: format!("File {} is not in this collection", file_id),
+				});
+			}
+			let next = rank.len();
+			rank.entry(*entry_id).or_insert(next);
+		}
+		members.sort_by_key(|m| rank.get(&m.entry_id).copied().unwrap_or(usize::MAX));
+
+		// Only rewrite entries whose position changed, keeping sync traffic small
+		let now = Utc::now();
+		let mut updated = Vec::new();
+		for (position, member) in members.into_iter().enumerate() {
+			let position = position as i32;
+			if member.position == position {
+				continue;
+			}
+
+			let version = member.version;
+			let mut active_model: collection_entry::ActiveModel = member.into();
+			active_model.position = Set(position);
+			active_model.version = Set(version + 1);
+			active_model.updated_at = Set(now);
+			updated.push(
+				active_model
+					.update(&txn)
+					.await
+					.map_err(ActionError::SeaOrm)?,
+			);
+		}
+
+		txn.commit().await.map_err(ActionError::SeaOrm)?;
+
+		library
+			.sync_models_batch(&updated, crate::infra::sync::ChangeType::Update, db)
+			.await
+			.map_err(|e| {
+				ActionError::Internal(format!("Failed to sync collection files: {}", e))
+			})?;
+
+		let collection = super::super::collection_changed(&library, collection_model).await?;
+
+		Ok(CollectionReorderOutput { collection })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"collections.reorder"
+	}
+
+	async fn validate(
+		&self,
+		_library: &std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<crate::infra::action::ValidationResult, ActionError> {
+		Ok(crate::infra::action::ValidationResult::Success { metadata: None })
+	}
+}
+
+crate::register_library_action!(CollectionReorderAction, "collections.reorder");
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionReorderInput {
+	pub collection_id: Uuid,
+	/// Files in their new order. Files left out keep their relative order after these.
+	pub file_ids: Vec<Uuid>,
+}
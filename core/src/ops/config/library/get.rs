@@ -4,6 +4,7 @@ use crate::{
 	context::CoreContext,
 	infra::query::{LibraryQuery, QueryError, QueryResult},
 	library::config::{IndexerSettings, LibrarySettings},
+	ops::sidecar::SidecarQuota,
 };
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -39,6 +40,9 @@ pub struct LibrarySettingsOutput {
 
 	/// Indexer settings
 	pub indexer: IndexerSettingsOutput,
+
+	/// Disk space limits for regenerable sidecars
+	pub sidecar_quotas: Vec<SidecarQuota>,
 }
 
 /// Indexer settings output
@@ -74,6 +78,7 @@ impl From<&LibrarySettings> for LibrarySettingsOutput {
 			auto_track_system_volumes: settings.auto_track_system_volumes,
 			auto_track_external_volumes: settings.auto_track_external_volumes,
 			indexer: IndexerSettingsOutput::from(&settings.indexer),
+			sidecar_quotas: settings.sidecar_quotas.clone(),
 		}
 	}
 }
@@ -3,6 +3,7 @@
 use crate::{
 	context::CoreContext,
 	infra::action::{error::ActionError, LibraryAction, ValidationResult},
+	ops::sidecar::SidecarQuota,
 };
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -68,6 +69,11 @@ pub struct UpdateLibraryConfigInput {
 	/// Only index images
 	#[serde(skip_serializing_if = "Option::is_none")]
 	pub only_images: Option<bool>,
+
+	// Sidecars
+	/// Replace the sidecar quotas, an empty list removes them all
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub sidecar_quotas: Option<Vec<SidecarQuota>>,
 }
 
 /// Output for update library configuration action
@@ -108,6 +114,13 @@ impl LibraryAction for UpdateLibraryConfigAction {
 			}
 		}
 
+		if let Some(quotas) = &self.input.sidecar_quotas {
+			SidecarQuota::validate_all(quotas).map_err(|message| ActionError::Validation {
+				field: "sidecar_quotas".to_string(),
+				message,
+			})?;
+		}
+
 		Ok(ValidationResult::Success { metadata: None })
 	}
 
@@ -213,6 +226,13 @@ impl LibraryAction for UpdateLibraryConfigAction {
 						changes.push("only_images");
 					}
 				}
+
+				if let Some(sidecar_quotas) = &self.input.sidecar_quotas {
+					if &settings.sidecar_quotas != sidecar_quotas {
+						settings.sidecar_quotas = sidecar_quotas.clone();
+						changes.push("sidecar_quotas");
+					}
+				}
 			})
 			.await
 			.map_err(|e| ActionError::Internal(format!("Failed to update config: {}", e)))?;
@@ -0,0 +1,79 @@
+//! Conflicts recorded for a bidirectional conduit
+
+use super::{file_sync_service, find_conduit, FileSyncConflict};
+use crate::{
+	context::CoreContext,
+	infra::query::{LibraryQuery, QueryError, QueryResult},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncConflictsInput {
+	pub conduit_id: Uuid,
+	/// Also list conflicts that already have a decision
+	#[serde(default)]
+	pub include_resolved: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncConflictsOutput {
+	pub conflicts: Vec<FileSyncConflict>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncConflictsQuery {
+	pub input: FileSyncConflictsInput,
+}
+
+impl LibraryQuery for FileSyncConflictsQuery {
+	type Input = FileSyncConflictsInput;
+	type Output = FileSyncConflictsOutput;
+
+	fn from_input(input: Self::Input) -> QueryResult<Self> {
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		context: Arc<CoreContext>,
+		session: crate::infra::api::SessionContext,
+	) -> QueryResult<Self::Output> {
+		let library_id = session
+			.current_library_id
+			.ok_or_else(|| QueryError::Internal("No library selected".to_string()))?;
+		let library = context
+			.libraries()
+			.await
+			.get_library(library_id)
+			.await
+			.ok_or_else(|| QueryError::LibraryNotFound(library_id))?;
+		let service = file_sync_service(&library).map_err(QueryError::Internal)?;
+
+		let conduit = find_conduit(service, self.input.conduit_id)
+			.await
+			.map_err(QueryError::InvalidInput)?;
+
+		let records = service
+			.conduit_manager()
+			.list_conflicts(conduit.id, self.input.include_resolved)
+			.await
+			.map_err(|e| QueryError::Internal(e.to_string()))?;
+
+		let db = library.db().conn();
+		let mut conflicts = Vec::with_capacity(records.len());
+		for (conflict, generation) in records {
+			conflicts.push(
+				FileSyncConflict::from_model(db, conflict, generation)
+					.await
+					.map_err(QueryError::Internal)?,
+			);
+		}
+
+		Ok(FileSyncConflictsOutput { conflicts })
+	}
+}
+
+crate::register_library_query!(FileSyncConflictsQuery, "file_sync.conflicts");
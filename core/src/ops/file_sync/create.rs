@@ -0,0 +1,158 @@
+//! Create a sync conduit
+
+use super::{file_sync_service, validate_parallel_transfers, validate_schedule, FileSyncConduit};
+use crate::{
+	context::CoreContext,
+	domain::addressing::SdPath,
+	infra::{
+		action::{
+			error::{ActionError, ActionResult},
+			LibraryAction,
+		},
+		db::entities::{entry::EntryKind, sync_conduit::SyncMode},
+	},
+	ops::indexing::PathResolver,
+	service::file_sync::{conduit::ConduitUpdate, conflict::ConflictStrategy},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncCreateInput {
+	/// Indexed directory to sync from
+	pub source: SdPath,
+	/// Indexed directory to sync to
+	pub target: SdPath,
+	#[serde(default = "default_mode")]
+	pub mode: SyncMode,
+	/// Only "manual" is supported, syncs are started with `file_sync.sync_now`
+	#[serde(default = "default_schedule")]
+	pub schedule: String,
+	/// How conflicts of bidirectional conduits are settled
+	#[serde(default)]
+	pub conflict_strategy: ConflictStrategy,
+	#[serde(default)]
+	pub parallel_transfers: Option<i32>,
+	#[serde(default)]
+	pub bandwidth_limit_mbps: Option<i32>,
+}
+
+fn default_mode() -> SyncMode {
+	SyncMode::Mirror
+}
+
+fn default_schedule() -> String {
+	"manual".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSyncCreateAction {
+	input: FileSyncCreateInput,
+}
+
+impl FileSyncCreateAction {
+	async fn resolve_directory(
+		library: &crate::library::Library,
+		path: &SdPath,
+		field: &str,
+	) -> ActionResult<i32> {
+		let entry = PathResolver::resolve_to_entry(library.db().conn(), path)
+			.await?
+			.ok_or_else(|| ActionError::Validation {
+				field: field.to_string(),
+				message: format!("{} is not inside an indexed location", path),
+			})?;
+
+		if entry.entry_kind() != EntryKind::Directory {
+			return Err(ActionError::Validation {
+				field: field.to_string(),
+				message: format!("{} is not a directory", path),
+			});
+		}
+
+		Ok(entry.id)
+	}
+}
+
+impl LibraryAction for FileSyncCreateAction {
+	type Input = FileSyncCreateInput;
+	type Output = FileSyncConduit;
+
+	fn from_input(input: FileSyncCreateInput) -> Result<Self, String> {
+		validate_schedule(&input.schedule)?;
+		if let Some(parallel_transfers) = input.parallel_transfers {
+			validate_parallel_transfers(parallel_transfers)?;
+		}
+		if input.bandwidth_limit_mbps.is_some_and(|limit| limit <= 0) {
+			return Err("Bandwidth limit must be positive".to_string());
+		}
+		if input.mode == SyncMode::Selective {
+			return Err("Selective sync is not supported yet".to_string());
+		}
+		if input.source == input.target {
+			return Err("Source and target must be different directories".to_string());
+		}
+
+		Ok(Self { input })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"file_sync.create"
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> ActionResult<Self::Output> {
+		let service = file_sync_service(&library).map_err(ActionError::Internal)?;
+		let input = self.input;
+
+		let source_id = Self::resolve_directory(&library, &input.source, "source").await?;
+		let target_id = Self::resolve_directory(&library, &input.target, "target").await?;
+
+		// A conduit between nested directories would copy into itself
+		let db = library.db().conn();
+		let source_path = PathResolver::get_full_path(db, source_id).await?;
+		let target_path = PathResolver::get_full_path(db, target_id).await?;
+		if source_path.starts_with(&target_path) || target_path.starts_with(&source_path) {
+			return Err(ActionError::Validation {
+				field: "target".to_string(),
+				message: "Source and target can't be inside each other".to_string(),
+			});
+		}
+
+		let manager = service.conduit_manager();
+		let mut conduit = manager
+			.create_conduit(
+				source_id,
+				target_id,
+				input.mode,
+				input.schedule,
+				input.conflict_strategy,
+			)
+			.await
+			.map_err(|e| ActionError::Internal(e.to_string()))?;
+
+		if input.parallel_transfers.is_some() || input.bandwidth_limit_mbps.is_some() {
+			conduit = manager
+				.update_conduit(
+					conduit.id,
+					ConduitUpdate {
+						parallel_transfers: input.parallel_transfers,
+						bandwidth_limit_mbps: input.bandwidth_limit_mbps.map(Some),
+						..Default::default()
+					},
+				)
+				.await
+				.map_err(|e| ActionError::Internal(e.to_string()))?;
+		}
+
+		FileSyncConduit::from_model(db, service, conduit)
+			.await
+			.map_err(ActionError::Internal)
+	}
+}
+
+crate::register_library_action!(FileSyncCreateAction, "file_sync.create");
@@ -0,0 +1,72 @@
+//! Delete a sync conduit
+
+use super::{file_sync_service, find_conduit};
+use crate::{
+	context::CoreContext,
+	infra::action::{
+		error::{ActionError, ActionResult},
+		LibraryAction,
+	},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncDeleteInput {
+	pub conduit_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncDeleteOutput {
+	pub conduit_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSyncDeleteAction {
+	input: FileSyncDeleteInput,
+}
+
+impl LibraryAction for FileSyncDeleteAction {
+	type Input = FileSyncDeleteInput;
+	type Output = FileSyncDeleteOutput;
+
+	fn from_input(input: FileSyncDeleteInput) -> Result<Self, String> {
+		Ok(Self { input })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"file_sync.delete"
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> ActionResult<Self::Output> {
+		let service = file_sync_service(&library).map_err(ActionError::Internal)?;
+		let conduit = find_conduit(service, self.input.conduit_id)
+			.await
+			.map_err(ActionError::Internal)?;
+
+		if service.is_syncing(conduit.id).await {
+			return Err(ActionError::Internal(
+				"Can't delete a conduit while it is syncing".to_string(),
+			));
+		}
+
+		// Files on either side are left alone, generations go with the conduit
+		service
+			.conduit_manager()
+			.delete_conduit(conduit.id)
+			.await
+			.map_err(|e| ActionError::Internal(e.to_string()))?;
+
+		Ok(FileSyncDeleteOutput {
+			conduit_id: self.input.conduit_id,
+		})
+	}
+}
+
+crate::register_library_action!(FileSyncDeleteAction, "file_sync.delete");
@@ -0,0 +1,70 @@
+//! Sync history of a conduit
+
+use super::{file_sync_service, find_conduit, FileSyncGeneration};
+use crate::{
+	context::CoreContext,
+	infra::query::{LibraryQuery, QueryError, QueryResult},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncGenerationsInput {
+	pub conduit_id: Uuid,
+	/// Return at most this many generations, newest first
+	#[serde(default)]
+	pub limit: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncGenerationsOutput {
+	pub generations: Vec<FileSyncGeneration>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncGenerationsQuery {
+	pub input: FileSyncGenerationsInput,
+}
+
+impl LibraryQuery for FileSyncGenerationsQuery {
+	type Input = FileSyncGenerationsInput;
+	type Output = FileSyncGenerationsOutput;
+
+	fn from_input(input: Self::Input) -> QueryResult<Self> {
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		context: Arc<CoreContext>,
+		session: crate::infra::api::SessionContext,
+	) -> QueryResult<Self::Output> {
+		let library_id = session
+			.current_library_id
+			.ok_or_else(|| QueryError::Internal("No library selected".to_string()))?;
+		let library = context
+			.libraries()
+			.await
+			.get_library(library_id)
+			.await
+			.ok_or_else(|| QueryError::LibraryNotFound(library_id))?;
+		let service = file_sync_service(&library).map_err(QueryError::Internal)?;
+
+		let conduit = find_conduit(service, self.input.conduit_id)
+			.await
+			.map_err(QueryError::InvalidInput)?;
+		let generations = service
+			.conduit_manager()
+			.list_generations(conduit.id, self.input.limit)
+			.await
+			.map_err(|e| QueryError::Internal(e.to_string()))?;
+
+		Ok(FileSyncGenerationsOutput {
+			generations: generations.into_iter().map(Into::into).collect(),
+		})
+	}
+}
+
+crate::register_library_query!(FileSyncGenerationsQuery, "file_sync.generations");
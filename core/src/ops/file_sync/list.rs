@@ -0,0 +1,69 @@
+//! List sync conduits
+
+use super::{file_sync_service, FileSyncConduit};
+use crate::{
+	context::CoreContext,
+	infra::query::{LibraryQuery, QueryError, QueryResult},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncListInput {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncListOutput {
+	pub conduits: Vec<FileSyncConduit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncListQuery {
+	pub input: FileSyncListInput,
+}
+
+impl LibraryQuery for FileSyncListQuery {
+	type Input = FileSyncListInput;
+	type Output = FileSyncListOutput;
+
+	fn from_input(input: Self::Input) -> QueryResult<Self> {
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		context: Arc<CoreContext>,
+		session: crate::infra::api::SessionContext,
+	) -> QueryResult<Self::Output> {
+		let library_id = session
+			.current_library_id
+			.ok_or_else(|| QueryError::Internal("No library selected".to_string()))?;
+		let library = context
+			.libraries()
+			.await
+			.get_library(library_id)
+			.await
+			.ok_or_else(|| QueryError::LibraryNotFound(library_id))?;
+		let service = file_sync_service(&library).map_err(QueryError::Internal)?;
+
+		let mut models = service
+			.conduit_manager()
+			.list_all()
+			.await
+			.map_err(|e| QueryError::Internal(e.to_string()))?;
+		models.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+		let mut conduits = Vec::with_capacity(models.len());
+		for model in models {
+			conduits.push(
+				FileSyncConduit::from_model(library.db().conn(), service, model)
+					.await
+					.map_err(QueryError::Internal)?,
+			);
+		}
+
+		Ok(FileSyncListOutput { conduits })
+	}
+}
+
+crate::register_library_query!(FileSyncListQuery, "file_sync.list");
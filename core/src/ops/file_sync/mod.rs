@@ -1,4 +1,95 @@
-// File sync operations module
+//! File sync operations
+//!
+//! Actions and queries for sync conduits, persistent sync relationships between
+//! two indexed directories. The work itself is done by
+//! [`FileSyncService`](crate::service::file_sync::FileSyncService), these only
+//! address conduits by UUID and translate its results for the API.
 
-// Placeholder for future API actions and queries
-// These will be implemented once the core service is tested
+pub mod conflicts;
+pub mod create;
+pub mod delete;
+pub mod generations;
+pub mod list;
+pub mod resolve_conflict;
+pub mod set_enabled;
+pub mod status;
+pub mod sync_now;
+pub mod types;
+pub mod update;
+
+pub use conflicts::*;
+pub use create::*;
+pub use delete::*;
+pub use generations::*;
+pub use list::*;
+pub use resolve_conflict::*;
+pub use set_enabled::*;
+pub use status::*;
+pub use sync_now::*;
+pub use types::*;
+pub use update::*;
+
+use crate::{
+	infra::db::entities::sync_conduit, library::Library, service::file_sync::FileSyncService,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Upper bound for parallel transfers of a conduit
+pub const MAX_PARALLEL_TRANSFERS: i32 = 32;
+
+fn file_sync_service(library: &Library) -> Result<&Arc<FileSyncService>, String> {
+	library
+		.file_sync_service()
+		.ok_or_else(|| "File sync service is not running for this library".to_string())
+}
+
+async fn find_conduit(
+	service: &FileSyncService,
+	conduit_id: Uuid,
+) -> Result<sync_conduit::Model, String> {
+	service
+		.conduit_manager()
+		.get_conduit_by_uuid(conduit_id)
+		.await
+		.map_err(|_| format!("Sync conduit {} not found", conduit_id))
+}
+
+/// Check a conduit schedule
+///
+/// Conduits only sync when asked to through `file_sync.sync_now`, so "manual"
+/// is the only schedule accepted until something runs the others.
+pub fn validate_schedule(schedule: &str) -> Result<(), String> {
+	match schedule {
+		"manual" => Ok(()),
+		_ => Err(format!(
+			"Unsupported schedule '{}', only manual conduits are supported",
+			schedule
+		)),
+	}
+}
+
+fn validate_parallel_transfers(parallel_transfers: i32) -> Result<(), String> {
+	if !(1..=MAX_PARALLEL_TRANSFERS).contains(&parallel_transfers) {
+		return Err(format!(
+			"Parallel transfers must be between 1 and {}",
+			MAX_PARALLEL_TRANSFERS
+		));
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn schedules() {
+		assert!(validate_schedule("manual").is_ok());
+
+		assert!(validate_schedule("instant").is_err());
+		assert!(validate_schedule("interval:5m").is_err());
+		assert!(validate_schedule("hourly").is_err());
+	}
+}
@@ -0,0 +1,76 @@
+//! Decide a file sync conflict
+
+use super::{file_sync_service, FileSyncConflict};
+use crate::{
+	context::CoreContext,
+	infra::{
+		action::{
+			error::{ActionError, ActionResult},
+			LibraryAction,
+		},
+		db::entities::{sync_conflict::Resolution, sync_generation},
+	},
+};
+use sea_orm::EntityTrait;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncResolveConflictInput {
+	pub conflict_id: Uuid,
+	pub resolution: Resolution,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSyncResolveConflictAction {
+	input: FileSyncResolveConflictInput,
+}
+
+impl LibraryAction for FileSyncResolveConflictAction {
+	type Input = FileSyncResolveConflictInput;
+	type Output = FileSyncConflict;
+
+	fn from_input(input: FileSyncResolveConflictInput) -> Result<Self, String> {
+		Ok(Self { input })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"file_sync.resolve_conflict"
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> ActionResult<Self::Output> {
+		let service = file_sync_service(&library).map_err(ActionError::Internal)?;
+		let manager = service.conduit_manager();
+
+		let conflict = manager
+			.get_conflict_by_uuid(self.input.conflict_id)
+			.await
+			.map_err(|_| ActionError::Validation {
+				field: "conflict_id".to_string(),
+				message: format!("Conflict {} not found", self.input.conflict_id),
+			})?;
+
+		// Applied by the next generation of the conduit
+		let conflict = manager
+			.resolve_conflict(conflict.id, self.input.resolution)
+			.await
+			.map_err(|e| ActionError::Internal(e.to_string()))?;
+
+		let db = library.db().conn();
+		let generation = sync_generation::Entity::find_by_id(conflict.generation_id)
+			.one(db)
+			.await?;
+
+		FileSyncConflict::from_model(db, conflict, generation)
+			.await
+			.map_err(ActionError::Internal)
+	}
+}
+
+crate::register_library_action!(FileSyncResolveConflictAction, "file_sync.resolve_conflict");
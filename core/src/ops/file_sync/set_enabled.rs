@@ -0,0 +1,66 @@
+//! Enable or disable a sync conduit
+
+use super::{file_sync_service, find_conduit, FileSyncConduit};
+use crate::{
+	context::CoreContext,
+	infra::action::{
+		error::{ActionError, ActionResult},
+		LibraryAction,
+	},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncSetEnabledInput {
+	pub conduit_id: Uuid,
+	/// Disabled conduits keep their history but refuse to sync
+	pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSyncSetEnabledAction {
+	input: FileSyncSetEnabledInput,
+}
+
+impl LibraryAction for FileSyncSetEnabledAction {
+	type Input = FileSyncSetEnabledInput;
+	type Output = FileSyncConduit;
+
+	fn from_input(input: FileSyncSetEnabledInput) -> Result<Self, String> {
+		Ok(Self { input })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"file_sync.set_enabled"
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> ActionResult<Self::Output> {
+		let service = file_sync_service(&library).map_err(ActionError::Internal)?;
+		let conduit = find_conduit(service, self.input.conduit_id)
+			.await
+			.map_err(ActionError::Internal)?;
+
+		let manager = service.conduit_manager();
+		manager
+			.set_enabled(conduit.id, self.input.enabled)
+			.await
+			.map_err(|e| ActionError::Internal(e.to_string()))?;
+		let conduit = manager
+			.get_conduit(conduit.id)
+			.await
+			.map_err(|e| ActionError::Internal(e.to_string()))?;
+
+		FileSyncConduit::from_model(library.db().conn(), service, conduit)
+			.await
+			.map_err(ActionError::Internal)
+	}
+}
+
+crate::register_library_action!(FileSyncSetEnabledAction, "file_sync.set_enabled");
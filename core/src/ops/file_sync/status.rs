@@ -0,0 +1,77 @@
+//! Status of a single sync conduit
+
+use super::{file_sync_service, find_conduit, FileSyncConduit, FileSyncGeneration};
+use crate::{
+	context::CoreContext,
+	infra::query::{LibraryQuery, QueryError, QueryResult},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncStatusInput {
+	pub conduit_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncStatusOutput {
+	pub conduit: FileSyncConduit,
+	/// Most recent generation, running or finished
+	pub latest_generation: Option<FileSyncGeneration>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncStatusQuery {
+	pub input: FileSyncStatusInput,
+}
+
+impl LibraryQuery for FileSyncStatusQuery {
+	type Input = FileSyncStatusInput;
+	type Output = FileSyncStatusOutput;
+
+	fn from_input(input: Self::Input) -> QueryResult<Self> {
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		context: Arc<CoreContext>,
+		session: crate::infra::api::SessionContext,
+	) -> QueryResult<Self::Output> {
+		let library_id = session
+			.current_library_id
+			.ok_or_else(|| QueryError::Internal("No library selected".to_string()))?;
+		let library = context
+			.libraries()
+			.await
+			.get_library(library_id)
+			.await
+			.ok_or_else(|| QueryError::LibraryNotFound(library_id))?;
+		let service = file_sync_service(&library).map_err(QueryError::Internal)?;
+
+		let model = find_conduit(service, self.input.conduit_id)
+			.await
+			.map_err(QueryError::InvalidInput)?;
+		let latest_generation = service
+			.conduit_manager()
+			.list_generations(model.id, Some(1))
+			.await
+			.map_err(|e| QueryError::Internal(e.to_string()))?
+			.into_iter()
+			.next()
+			.map(Into::into);
+
+		let conduit = FileSyncConduit::from_model(library.db().conn(), service, model)
+			.await
+			.map_err(QueryError::Internal)?;
+
+		Ok(FileSyncStatusOutput {
+			conduit,
+			latest_generation,
+		})
+	}
+}
+
+crate::register_library_query!(FileSyncStatusQuery, "file_sync.status");
@@ -0,0 +1,78 @@
+//! Start a sync of a conduit right away
+
+use super::{file_sync_service, find_conduit};
+use crate::{
+	context::CoreContext,
+	infra::action::{
+		error::{ActionError, ActionResult},
+		LibraryAction,
+	},
+	service::file_sync::JobBatch,
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncNowInput {
+	pub conduit_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncNowOutput {
+	pub conduit_id: Uuid,
+	pub generation: i64,
+	/// Copy, delete and conflict copy jobs dispatched for this sync, empty when nothing changed
+	pub job_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSyncNowAction {
+	input: FileSyncNowInput,
+}
+
+impl LibraryAction for FileSyncNowAction {
+	type Input = FileSyncNowInput;
+	type Output = FileSyncNowOutput;
+
+	fn from_input(input: FileSyncNowInput) -> Result<Self, String> {
+		Ok(Self { input })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"file_sync.sync_now"
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> ActionResult<Self::Output> {
+		let service = file_sync_service(&library).map_err(ActionError::Internal)?;
+		let conduit = find_conduit(service, self.input.conduit_id)
+			.await
+			.map_err(ActionError::Internal)?;
+
+		let handle = service
+			.sync_now(conduit.id)
+			.await
+			.map_err(|e| ActionError::Internal(e.to_string()))?;
+
+		let job_ids = std::iter::once(&handle.source_to_target)
+			.chain(handle.target_to_source.as_ref())
+			.flat_map(|batch: &JobBatch| [batch.copy_job_id, batch.delete_job_id])
+			.flatten()
+			.chain(handle.conflict_copy_job_ids.iter().copied())
+			.map(|job_id| job_id.0)
+			.collect();
+
+		Ok(FileSyncNowOutput {
+			conduit_id: self.input.conduit_id,
+			generation: handle.generation,
+			job_ids,
+		})
+	}
+}
+
+crate::register_library_action!(FileSyncNowAction, "file_sync.sync_now");
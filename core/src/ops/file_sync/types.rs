@@ -0,0 +1,216 @@
+//! API types for sync conduits
+
+use crate::{
+	infra::db::entities::{entry, sync_conduit, sync_conflict, sync_generation},
+	ops::indexing::PathResolver,
+	service::file_sync::{conflict::ConflictStrategy, resolver::ConflictType, FileSyncService},
+};
+use chrono::{DateTime, Utc};
+use sea_orm::{ConnectionTrait, EntityTrait};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// A sync relationship between two indexed directories
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncConduit {
+	pub id: Uuid,
+	pub source_path: PathBuf,
+	pub target_path: PathBuf,
+	pub mode: sync_conduit::SyncMode,
+	pub enabled: bool,
+	/// Always "manual" for now
+	pub schedule: String,
+	pub conflict_strategy: ConflictStrategy,
+	pub use_index_rules: bool,
+	pub parallel_transfers: i32,
+	pub bandwidth_limit_mbps: Option<i32>,
+	/// Whether a sync of this conduit is running right now
+	pub syncing: bool,
+	/// Number of the last generation started
+	pub generation: i64,
+	pub last_sync_completed_at: Option<DateTime<Utc>>,
+	pub last_sync_error: Option<String>,
+	pub total_syncs: i64,
+	pub files_synced: i64,
+	pub bytes_transferred: i64,
+	pub created_at: DateTime<Utc>,
+	pub updated_at: DateTime<Utc>,
+}
+
+impl FileSyncConduit {
+	pub async fn from_model<C: ConnectionTrait>(
+		db: &C,
+		service: &FileSyncService,
+		model: sync_conduit::Model,
+	) -> Result<Self, String> {
+		let source_path = PathResolver::get_full_path(db, model.source_entry_id)
+			.await
+			.map_err(|e| format!("Failed to resolve sync source: {}", e))?;
+		let target_path = PathResolver::get_full_path(db, model.target_entry_id)
+			.await
+			.map_err(|e| format!("Failed to resolve sync target: {}", e))?;
+		let mode = sync_conduit::SyncMode::from_str(&model.sync_mode)
+			.ok_or_else(|| format!("Invalid sync mode '{}'", model.sync_mode))?;
+		let conflict_strategy = ConflictStrategy::from_str(&model.conflict_strategy)
+			.ok_or_else(|| format!("Invalid conflict strategy '{}'", model.conflict_strategy))?;
+
+		Ok(Self {
+			id: model.uuid,
+			source_path,
+			target_path,
+			mode,
+			enabled: model.enabled,
+			schedule: model.schedule,
+			conflict_strategy,
+			use_index_rules: model.use_index_rules,
+			parallel_transfers: model.parallel_transfers,
+			bandwidth_limit_mbps: model.bandwidth_limit_mbps,
+			syncing: service.is_syncing(model.id).await,
+			generation: model.sync_generation,
+			last_sync_completed_at: model.last_sync_completed_at,
+			last_sync_error: model.last_sync_error,
+			total_syncs: model.total_syncs,
+			files_synced: model.files_synced,
+			bytes_transferred: model.bytes_transferred,
+			created_at: model.created_at,
+			updated_at: model.updated_at,
+		})
+	}
+}
+
+/// One sync run of a conduit
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncGeneration {
+	pub generation: i64,
+	pub started_at: DateTime<Utc>,
+	/// None while the sync is still running
+	pub completed_at: Option<DateTime<Utc>>,
+	pub files_copied: i32,
+	pub files_deleted: i32,
+	pub conflicts_resolved: i32,
+	pub bytes_transferred: i64,
+	pub errors_encountered: i32,
+	pub verified_at: Option<DateTime<Utc>>,
+	/// "unverified", "waiting_watcher", "waiting_library_sync", "verified" or "failed:<reason>"
+	pub verification_status: String,
+}
+
+impl From<sync_generation::Model> for FileSyncGeneration {
+	fn from(model: sync_generation::Model) -> Self {
+		Self {
+			generation: model.generation,
+			started_at: model.started_at,
+			completed_at: model.completed_at,
+			files_copied: model.files_copied,
+			files_deleted: model.files_deleted,
+			conflicts_resolved: model.conflicts_resolved,
+			bytes_transferred: model.bytes_transferred,
+			errors_encountered: model.errors_encountered,
+			verified_at: model.verified_at,
+			verification_status: model.verification_status,
+		}
+	}
+}
+
+/// A file changed on both sides of a conduit since its last sync
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncConflict {
+	pub id: Uuid,
+	/// Generation that detected the conflict
+	pub generation: Option<i64>,
+	/// Path relative to the conduit's source and target directories
+	pub path: PathBuf,
+	pub conflict_type: ConflictType,
+	pub source: FileSyncConflictSide,
+	pub target: FileSyncConflictSide,
+	/// None while waiting for a decision
+	pub resolution: Option<sync_conflict::Resolution>,
+	pub detected_at: DateTime<Utc>,
+	pub resolved_at: Option<DateTime<Utc>>,
+	/// When a sync carried out the resolution
+	pub applied_at: Option<DateTime<Utc>>,
+}
+
+/// One side of a conflict as it was when the conflict was detected
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncConflictSide {
+	/// None once the file is no longer indexed
+	pub entry_id: Option<Uuid>,
+	pub path: Option<PathBuf>,
+	pub size: i64,
+	pub modified_at: DateTime<Utc>,
+}
+
+impl FileSyncConflictSide {
+	async fn load<C: ConnectionTrait>(
+		db: &C,
+		entry_id: Option<i32>,
+		size: i64,
+		modified_at: DateTime<Utc>,
+	) -> Result<Self, String> {
+		let entry = match entry_id {
+			Some(id) => entry::Entity::find_by_id(id)
+				.one(db)
+				.await
+				.map_err(|e| e.to_string())?,
+			None => None,
+		};
+		let path = match &entry {
+			Some(entry) => PathResolver::get_full_path(db, entry.id).await.ok(),
+			None => None,
+		};
+
+		Ok(Self {
+			entry_id: entry.and_then(|entry| entry.uuid),
+			path,
+			size,
+			modified_at,
+		})
+	}
+}
+
+impl FileSyncConflict {
+	pub async fn from_model<C: ConnectionTrait>(
+		db: &C,
+		model: sync_conflict::Model,
+		generation: Option<sync_generation::Model>,
+	) -> Result<Self, String> {
+		let conflict_type = ConflictType::from_str(&model.conflict_type)
+			.ok_or_else(|| format!("Invalid conflict type '{}'", model.conflict_type))?;
+		let resolution = model
+			.resolution
+			.as_deref()
+			.map(|resolution| {
+				sync_conflict::Resolution::from_str(resolution)
+					.ok_or_else(|| format!("Invalid conflict resolution '{}'", resolution))
+			})
+			.transpose()?;
+
+		Ok(Self {
+			id: model.uuid,
+			generation: generation.map(|generation| generation.generation),
+			path: PathBuf::from(&model.relative_path),
+			conflict_type,
+			source: FileSyncConflictSide::load(
+				db,
+				model.source_entry_id,
+				model.source_size,
+				model.source_modified_at,
+			)
+			.await?,
+			target: FileSyncConflictSide::load(
+				db,
+				model.target_entry_id,
+				model.target_size,
+				model.target_modified_at,
+			)
+			.await?,
+			resolution,
+			detected_at: model.detected_at,
+			resolved_at: model.resolved_at,
+			applied_at: model.applied_at,
+		})
+	}
+}
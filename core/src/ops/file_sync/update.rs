@@ -0,0 +1,111 @@
+//! Update the settings of a sync conduit
+
+use super::{
+	file_sync_service, find_conduit, validate_parallel_transfers, validate_schedule,
+	FileSyncConduit,
+};
+use crate::{
+	context::CoreContext,
+	infra::{
+		action::{
+			error::{ActionError, ActionResult},
+			LibraryAction,
+		},
+		db::entities::sync_conduit::SyncMode,
+	},
+	service::file_sync::{conduit::ConduitUpdate, conflict::ConflictStrategy},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileSyncUpdateInput {
+	pub conduit_id: Uuid,
+	#[serde(default)]
+	pub mode: Option<SyncMode>,
+	#[serde(default)]
+	pub schedule: Option<String>,
+	#[serde(default)]
+	pub conflict_strategy: Option<ConflictStrategy>,
+	#[serde(default)]
+	pub use_index_rules: Option<bool>,
+	#[serde(default)]
+	pub parallel_transfers: Option<i32>,
+	/// New bandwidth limit, 0 removes the limit
+	#[serde(default)]
+	pub bandwidth_limit_mbps: Option<i32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileSyncUpdateAction {
+	input: FileSyncUpdateInput,
+}
+
+impl LibraryAction for FileSyncUpdateAction {
+	type Input = FileSyncUpdateInput;
+	type Output = FileSyncConduit;
+
+	fn from_input(input: FileSyncUpdateInput) -> Result<Self, String> {
+		if let Some(schedule) = &input.schedule {
+			validate_schedule(schedule)?;
+		}
+		if let Some(parallel_transfers) = input.parallel_transfers {
+			validate_parallel_transfers(parallel_transfers)?;
+		}
+		if input.bandwidth_limit_mbps.is_some_and(|limit| limit < 0) {
+			return Err("Bandwidth limit can't be negative".to_string());
+		}
+		if input.mode == Some(SyncMode::Selective) {
+			return Err("Selective sync is not supported yet".to_string());
+		}
+
+		Ok(Self { input })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"file_sync.update"
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> ActionResult<Self::Output> {
+		let service = file_sync_service(&library).map_err(ActionError::Internal)?;
+		let input = self.input;
+		let conduit = find_conduit(service, input.conduit_id)
+			.await
+			.map_err(ActionError::Internal)?;
+
+		if service.is_syncing(conduit.id).await {
+			return Err(ActionError::Internal(
+				"Can't change a conduit while it is syncing".to_string(),
+			));
+		}
+
+		let update = ConduitUpdate {
+			mode: input.mode,
+			schedule: input.schedule,
+			conflict_strategy: input.conflict_strategy,
+			use_index_rules: input.use_index_rules,
+			parallel_transfers: input.parallel_transfers,
+			bandwidth_limit_mbps: input
+				.bandwidth_limit_mbps
+				.map(|limit| (limit > 0).then_some(limit)),
+		};
+
+		let conduit = service
+			.conduit_manager()
+			.update_conduit(conduit.id, update)
+			.await
+			.map_err(|e| ActionError::Internal(e.to_string()))?;
+
+		FileSyncConduit::from_model(library.db().conn(), service, conduit)
+			.await
+			.map_err(ActionError::Internal)
+	}
+}
+
+crate::register_library_action!(FileSyncUpdateAction, "file_sync.update");
@@ -1,6 +1,7 @@
 //! File copy action handler
 
 use super::{
+	archive::{archive_member_endpoint, archive_member_path, filesystem_path},
 	input::FileCopyInput,
 	job::{CopyOptions, FileCopyJob},
 };
@@ -151,7 +152,15 @@ impl FileCopyActionBuilder {
 
 		// Then do filesystem validation for local paths only
 		for source in &self.input.sources.paths {
-			if let Some(local_path) = source.as_local_path() {
+			if let Some(member_path) = archive_member_path(source) {
+				// Archive members aren't on the filesystem, the job reads them from the archive
+				if self.input.move_files {
+					self.errors.push(format!(
+						"Cannot move out of an archive, archives are read-only: {}",
+						member_path.display()
+					));
+				}
+			} else if let Some(local_path) = source.as_local_path() {
 				if !local_path.exists() {
 					self.errors.push(format!(
 						"Source file does not exist: {}",
@@ -172,7 +181,12 @@ impl FileCopyActionBuilder {
 	fn validate_destination(&mut self) {
 		// Ensure destination device id is normalized for local paths
 		self.normalize_local_device_ids();
-		if let Some(dest_path) = self.input.destination.as_local_path() {
+		if let Some(member_path) = archive_member_path(&self.input.destination) {
+			self.errors.push(format!(
+				"Cannot copy into an archive, archives are read-only: {}",
+				member_path.display()
+			));
+		} else if let Some(dest_path) = self.input.destination.as_local_path() {
 			if let Some(parent) = dest_path.parent() {
 				if !parent.exists() {
 					self.errors.push(format!(
@@ -428,7 +442,7 @@ impl FileCopyAction {
 		let mut total_bytes = 0u64;
 
 		for source in &self.sources.paths {
-			if let Some(local_path) = source.as_local_path() {
+			if let Some(local_path) = filesystem_path(source) {
 				let metadata = tokio::fs::metadata(local_path).await.map_err(|e| {
 					ActionError::Internal(format!("Failed to read metadata: {}", e))
 				})?;
@@ -441,6 +455,12 @@ impl FileCopyAction {
 					total_files += count;
 					total_bytes += size;
 				}
+			} else if let Some(endpoint) = archive_member_endpoint(source) {
+				let (size, count) = super::cloud::measure(&endpoint)
+					.await
+					.map_err(|e| ActionError::Internal(format!("Failed to read archive: {}", e)))?;
+				total_files += count;
+				total_bytes += size;
 			}
 		}
 
@@ -0,0 +1,175 @@
+//! # Archive Extraction
+//!
+//! Copying a path inside an archive (`/photos/trip.zip/day1`) extracts it. The members
+//! are streamed out of the archive through [`ArchiveBackend`] by the same chunked
+//! transfer cloud volumes use, so a directory copy decompresses the archive once per
+//! file rather than into a temporary directory first. Archives are read-only: members
+//! can't be moved out, and nothing can be copied into one.
+
+use super::{
+	cloud::{BackendTransfer, TransferEndpoint},
+	strategy::{CopyStrategy, ProgressCallback},
+};
+use crate::{domain::addressing::SdPath, infra::job::prelude::*, volume::ArchiveBackend};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::{path::Path, sync::Arc};
+
+/// Local path of a member of an archive on this device
+///
+/// The archive file itself isn't a member, it's copied like any other file.
+pub fn archive_member_path(path: &SdPath) -> Option<&Path> {
+	path.as_local_path()
+		.filter(|local_path| ArchiveBackend::is_member_path(local_path))
+}
+
+/// Local path of a source the filesystem can read directly, which archive members aren't
+pub fn filesystem_path(path: &SdPath) -> Option<&Path> {
+	path.as_local_path()
+		.filter(|local_path| !ArchiveBackend::is_member_path(local_path))
+}
+
+/// Transfer endpoint reading a member out of its archive
+pub fn archive_member_endpoint(path: &SdPath) -> Option<TransferEndpoint> {
+	let local_path = archive_member_path(path)?;
+	let backend = ArchiveBackend::for_path(local_path)?;
+	Some(TransferEndpoint::new(Arc::new(backend), local_path))
+}
+
+/// Whether copying this source goes through [`ArchiveExtractStrategy`]
+pub fn is_archive_extraction(source: &SdPath) -> bool {
+	archive_member_path(source).is_some()
+}
+
+/// Short description of an extraction, for progress and logs
+pub fn describe_archive_extraction(source: &SdPath, is_move: bool) -> String {
+	let archive = archive_member_path(source)
+		.and_then(ArchiveBackend::locate)
+		.and_then(|(archive, _)| {
+			archive
+				.file_name()
+				.map(|name| name.to_string_lossy().into_owned())
+		})
+		.unwrap_or_default();
+	if is_move {
+		format!("Move out of {} (not supported)", archive)
+	} else {
+		format!("Extract from {}", archive)
+	}
+}
+
+/// Strategy for copies out of an archive
+pub struct ArchiveExtractStrategy {
+	pub is_move: bool,
+}
+
+#[async_trait]
+impl CopyStrategy for ArchiveExtractStrategy {
+	async fn execute<'a>(
+		&self,
+		ctx: &JobContext<'a>,
+		source: &SdPath,
+		destination: &SdPath,
+		verify_checksum: bool,
+		progress_callback: Option<&ProgressCallback<'a>>,
+	) -> Result<u64> {
+		if self.is_move {
+			anyhow::bail!(
+				"{} is inside an archive, archives are read-only so it can only be copied",
+				source.display()
+			);
+		}
+
+		let source_endpoint = archive_member_endpoint(source)
+			.ok_or_else(|| anyhow::anyhow!("{} is not inside an archive", source.display()))?;
+		let volume_manager = ctx.volume_manager();
+		let destination_endpoint =
+			TransferEndpoint::resolve(destination, volume_manager.as_deref()).await?;
+
+		let bytes = BackendTransfer::new()
+			.with_checksum(verify_checksum)
+			.copy(
+				&source_endpoint,
+				&destination_endpoint,
+				progress_callback,
+				move || async move { ctx.check_interrupt().await.map_err(anyhow::Error::from) },
+			)
+			.await?;
+
+		ctx.log(format!(
+			"Extracted: {} -> {} ({} bytes)",
+			source.display(),
+			destination.display(),
+			bytes
+		));
+
+		Ok(bytes)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::volume::{LocalBackend, VolumeBackend};
+	use std::io::Write;
+	use tempfile::TempDir;
+
+	async fn no_interrupt() -> Result<()> {
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn extracts_a_directory_out_of_a_zip() {
+		let temp_dir = TempDir::new().unwrap();
+		let archive = temp_dir.path().join("trip.zip");
+		let mut zip = zip::ZipWriter::new(std::fs::File::create(&archive).unwrap());
+		for (name, data) in [("day1/a.txt", "first"), ("day1/nested/b.txt", "second")] {
+			zip.start_file(name, zip::write::SimpleFileOptions::default())
+				.unwrap();
+			zip.write_all(data.as_bytes()).unwrap();
+		}
+		zip.finish().unwrap();
+
+		let source = SdPath::local(archive.join("day1"));
+		assert!(is_archive_extraction(&source));
+		assert!(!is_archive_extraction(&SdPath::local(archive.clone())));
+		assert_eq!(filesystem_path(&source), None);
+
+		let destination = temp_dir.path().join("out");
+		let bytes = BackendTransfer::new()
+			.with_checksum(true)
+			.copy(
+				&archive_member_endpoint(&source).unwrap(),
+				&TransferEndpoint::new(Arc::new(LocalBackend::new(&destination)), &destination),
+				None,
+				no_interrupt,
+			)
+			.await
+			.unwrap();
+
+		assert_eq!(bytes, 11);
+		assert_eq!(
+			std::fs::read_to_string(destination.join("nested/b.txt")).unwrap(),
+			"second"
+		);
+	}
+
+	#[tokio::test]
+	async fn archives_are_not_writable_destinations() {
+		let temp_dir = TempDir::new().unwrap();
+		let archive = temp_dir.path().join("docs.zip");
+		let mut zip = zip::ZipWriter::new(std::fs::File::create(&archive).unwrap());
+		zip.start_file("a.txt", zip::write::SimpleFileOptions::default())
+			.unwrap();
+		zip.finish().unwrap();
+
+		let endpoint = TransferEndpoint::resolve(&SdPath::local(archive.join("b.txt")), None)
+			.await
+			.unwrap();
+		assert!(endpoint
+			.backend
+			.write(&endpoint.path, bytes::Bytes::from_static(b"new"))
+			.await
+			.is_err());
+	}
+}
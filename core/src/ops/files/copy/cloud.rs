@@ -0,0 +1,773 @@
+//! # Cloud Transfers
+//!
+//! Copies between local paths and cloud volumes, or between two cloud volumes, through
+//! [`VolumeBackend`]. Files are streamed from the source's reader into a chunked writer, so
+//! cloud services receive multipart uploads and a file never has to fit in memory. Within a
+//! single volume the storage's own copy and rename are used where the backend has them.
+//!
+//! A file interrupted mid-transfer is resumed when the destination can append: the part
+//! already there is compared with the same range of the source and only the rest is sent.
+//! Destinations that can't append (S3 and most object stores) restart the file, their
+//! incomplete multipart uploads are discarded so no truncated object is left behind.
+
+use super::strategy::{CopyStrategy, ProgressCallback};
+use crate::{
+	domain::addressing::SdPath,
+	infra::job::prelude::*,
+	ops::indexing::state::EntryKind,
+	volume::{LocalBackend, VolumeBackend, VolumeManager},
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::{
+	future::Future,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+use tracing::{debug, warn};
+
+/// Size of the ranges read from the source, and of the chunks handed to the writer
+pub const TRANSFER_CHUNK_SIZE: u64 = 8 * 1024 * 1024;
+
+/// One side of a transfer, a path on the backend that serves it
+#[derive(Debug, Clone)]
+pub struct TransferEndpoint {
+	pub backend: Arc<dyn VolumeBackend>,
+	pub path: PathBuf,
+}
+
+impl TransferEndpoint {
+	pub fn new(backend: Arc<dyn VolumeBackend>, path: impl Into<PathBuf>) -> Self {
+		Self {
+			backend,
+			path: path.into(),
+		}
+	}
+
+	/// Find the backend for a local, archive member or cloud path
+	pub async fn resolve(path: &SdPath, volume_manager: Option<&VolumeManager>) -> Result<Self> {
+		if let Some(endpoint) = super::archive::archive_member_endpoint(path) {
+			return Ok(endpoint);
+		}
+
+		if let Some(local_path) = path.as_local_path() {
+			return Ok(Self::new(
+				Arc::new(LocalBackend::new(local_path)),
+				local_path,
+			));
+		}
+
+		let (service, identifier, cloud_path) = path.as_cloud().ok_or_else(|| {
+			anyhow::anyhow!(
+				"{} is neither on this device nor on a cloud volume",
+				path.display()
+			)
+		})?;
+
+		let volume_manager =
+			volume_manager.ok_or_else(|| anyhow::anyhow!("Volume manager not available"))?;
+		let volume = volume_manager
+			.find_cloud_volume(service, identifier)
+			.await
+			.ok_or_else(|| {
+				anyhow::anyhow!(
+					"Cloud volume not found: {} ({})",
+					service.scheme(),
+					identifier
+				)
+			})?;
+		let backend = volume.backend.clone().ok_or_else(|| {
+			anyhow::anyhow!("Backend of cloud volume {} not available", volume.name)
+		})?;
+
+		Ok(Self::new(backend, cloud_path))
+	}
+
+	fn join(&self, name: &str) -> Self {
+		Self::new(self.backend.clone(), self.path.join(name))
+	}
+
+	/// Whether both endpoints are served by the same backend, so native copy and rename apply
+	pub fn same_backend(&self, other: &Self) -> bool {
+		Arc::ptr_eq(&self.backend, &other.backend)
+	}
+}
+
+/// Result of copying one file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileTransfer {
+	/// Size of the file
+	pub size: u64,
+	/// Bytes that were already at the destination from an interrupted transfer
+	pub resumed_from: u64,
+}
+
+/// Chunked copy between two [`TransferEndpoint`]s
+#[derive(Debug, Clone)]
+pub struct BackendTransfer {
+	chunk_size: u64,
+	verify_checksum: bool,
+}
+
+impl Default for BackendTransfer {
+	fn default() -> Self {
+		Self {
+			chunk_size: TRANSFER_CHUNK_SIZE,
+			verify_checksum: false,
+		}
+	}
+}
+
+impl BackendTransfer {
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	pub fn with_chunk_size(mut self, chunk_size: u64) -> Self {
+		self.chunk_size = chunk_size.max(1);
+		self
+	}
+
+	/// Read the destination back after each file and compare BLAKE3 hashes
+	pub fn with_checksum(mut self, verify_checksum: bool) -> Self {
+		self.verify_checksum = verify_checksum;
+		self
+	}
+
+	/// Copy a file or a directory tree, returns the bytes of all files
+	///
+	/// Progress is reported per file like the local strategies do: bytes of the current
+	/// file while it's copied, then its size with `u64::MAX` once it's done. `check` runs
+	/// before every chunk, an error from it stops the transfer.
+	pub async fn copy<F, Fut>(
+		&self,
+		source: &TransferEndpoint,
+		destination: &TransferEndpoint,
+		progress_callback: Option<&ProgressCallback<'_>>,
+		check: F,
+	) -> Result<u64>
+	where
+		F: Fn() -> Fut,
+		Fut: Future<Output = Result<()>>,
+	{
+		let metadata = source.backend.metadata(&source.path).await?;
+		if metadata.kind != EntryKind::Directory {
+			let transfer = self
+				.copy_file(source, destination, progress_callback, &check)
+				.await?;
+			if let Some(callback) = progress_callback {
+				callback(transfer.size, u64::MAX);
+			}
+			return Ok(transfer.size);
+		}
+
+		// Walk the tree first so no listing is held open during transfers
+		let mut files = Vec::new();
+		let mut stack = vec![(source.clone(), destination.clone())];
+		while let Some((source_dir, destination_dir)) = stack.pop() {
+			destination_dir
+				.backend
+				.create_directory(&destination_dir.path, true)
+				.await?;
+
+			for entry in source_dir.backend.read_dir(&source_dir.path).await? {
+				let pair = (
+					source_dir.join(&entry.name),
+					destination_dir.join(&entry.name),
+				);
+				match entry.kind {
+					EntryKind::Directory => stack.push(pair),
+					EntryKind::File => files.push(pair),
+					EntryKind::Symlink => {
+						debug!("Skipping symlink {}", pair.0.path.display());
+					}
+				}
+			}
+		}
+
+		let mut total = 0;
+		for (source_file, destination_file) in files {
+			let transfer = self
+				.copy_file(&source_file, &destination_file, progress_callback, &check)
+				.await?;
+			total += transfer.size;
+
+			if let Some(callback) = progress_callback {
+				callback(transfer.size, u64::MAX);
+			}
+		}
+
+		Ok(total)
+	}
+
+	/// Copy a single file, resuming an interrupted transfer when possible
+	pub async fn copy_file<F, Fut>(
+		&self,
+		source: &TransferEndpoint,
+		destination: &TransferEndpoint,
+		progress_callback: Option<&ProgressCallback<'_>>,
+		check: &F,
+	) -> Result<FileTransfer>
+	where
+		F: Fn() -> Fut,
+		Fut: Future<Output = Result<()>>,
+	{
+		let size = source.backend.metadata(&source.path).await?.size;
+		let capabilities = destination.backend.capabilities();
+
+		// Let the storage copy it when both paths are on the same volume
+		if source.same_backend(destination) && capabilities.server_side_copy {
+			check().await?;
+			destination
+				.backend
+				.copy(&source.path, &destination.path)
+				.await?;
+			if let Some(callback) = progress_callback {
+				callback(size, size);
+			}
+			return Ok(FileTransfer {
+				size,
+				resumed_from: 0,
+			});
+		}
+
+		// Pick up what an earlier attempt left at the destination
+		let mut source_hasher = blake3::Hasher::new();
+		let mut resumed_from = 0;
+		if let Ok(existing) = destination.backend.metadata(&destination.path).await {
+			let usable = existing.kind == EntryKind::File
+				&& existing.size > 0
+				&& (existing.size == size || (existing.size < size && capabilities.append));
+			if usable {
+				if let Some(prefix_hasher) = self
+					.matching_prefix(source, destination, existing.size)
+					.await?
+				{
+					source_hasher = prefix_hasher;
+					resumed_from = existing.size;
+				}
+			}
+		}
+
+		if resumed_from > 0 {
+			debug!(
+				"Resuming transfer of {} at {} of {} bytes",
+				source.path.display(),
+				resumed_from,
+				size
+			);
+		}
+
+		if resumed_from < size || size == 0 {
+			let mut reader = source
+				.backend
+				.open_reader(&source.path, Some(resumed_from..size))
+				.await?;
+			let mut writer = destination
+				.backend
+				.open_writer(&destination.path, resumed_from > 0)
+				.await?;
+			let mut pending = Bytes::new();
+			let mut position = resumed_from;
+
+			while position < size {
+				let chunk = async {
+					check().await?;
+					if pending.is_empty() {
+						pending = reader.next_chunk().await?.ok_or_else(|| {
+							anyhow::anyhow!(
+								"{} ended after {} bytes",
+								source.path.display(),
+								position
+							)
+						})?;
+					}
+					let data = pending.split_to(pending.len().min(self.chunk_size as usize));
+					source_hasher.update(&data);
+					let len = data.len() as u64;
+					writer.write(data).await?;
+					Ok::<_, anyhow::Error>(len)
+				}
+				.await;
+
+				match chunk {
+					Ok(len) => position += len,
+					Err(e) => {
+						// Keep the partial file where the next attempt can append to it
+						let cleanup = if capabilities.append {
+							writer.close().await
+						} else {
+							writer.abort().await
+						};
+						if let Err(cleanup_error) = cleanup {
+							warn!(
+								"Failed to clean up interrupted transfer to {}: {}",
+								destination.path.display(),
+								cleanup_error
+							);
+						}
+						return Err(e);
+					}
+				}
+
+				if let Some(callback) = progress_callback {
+					callback(position, size);
+				}
+			}
+
+			writer.close().await?;
+		} else if let Some(callback) = progress_callback {
+			callback(size, size);
+		}
+
+		if self.verify_checksum {
+			let source_hash = source_hasher.finalize();
+			let destination_hash = Self::hash_file(destination).await?.finalize();
+			if source_hash != destination_hash {
+				let _ = destination.backend.delete(&destination.path).await;
+				anyhow::bail!(
+					"Checksum verification failed: source={}, dest={}",
+					source_hash.to_hex(),
+					destination_hash.to_hex()
+				);
+			}
+		}
+
+		Ok(FileTransfer { size, resumed_from })
+	}
+
+	/// Hash of the first `len` bytes of the source if the destination starts with them
+	async fn matching_prefix(
+		&self,
+		source: &TransferEndpoint,
+		destination: &TransferEndpoint,
+		len: u64,
+	) -> Result<Option<blake3::Hasher>> {
+		let mut hasher = blake3::Hasher::new();
+		let mut position = 0;
+
+		while position < len {
+			let end = (position + self.chunk_size).min(len);
+			let source_chunk = source
+				.backend
+				.read_range(&source.path, position..end)
+				.await?;
+			let destination_chunk = destination
+				.backend
+				.read_range(&destination.path, position..end)
+				.await?;
+			if source_chunk != destination_chunk {
+				return Ok(None);
+			}
+
+			hasher.update(&source_chunk);
+			position = end;
+		}
+
+		Ok(Some(hasher))
+	}
+
+	async fn hash_file(endpoint: &TransferEndpoint) -> Result<blake3::Hasher> {
+		let mut hasher = blake3::Hasher::new();
+		let mut reader = endpoint.backend.open_reader(&endpoint.path, None).await?;
+		while let Some(data) = reader.next_chunk().await? {
+			hasher.update(&data);
+		}
+
+		Ok(hasher)
+	}
+}
+
+/// Strategy for copies from, to or between cloud volumes
+pub struct CloudTransferStrategy {
+	/// Delete the source once it's safely at the destination
+	pub delete_source: bool,
+}
+
+#[async_trait]
+impl CopyStrategy for CloudTransferStrategy {
+	async fn execute<'a>(
+		&self,
+		ctx: &JobContext<'a>,
+		source: &SdPath,
+		destination: &SdPath,
+		verify_checksum: bool,
+		progress_callback: Option<&ProgressCallback<'a>>,
+	) -> Result<u64> {
+		let volume_manager = ctx.volume_manager();
+		let source_endpoint = TransferEndpoint::resolve(source, volume_manager.as_deref()).await?;
+		let destination_endpoint =
+			TransferEndpoint::resolve(destination, volume_manager.as_deref()).await?;
+
+		// A move within one volume is a rename when the storage supports it
+		if self.delete_source
+			&& source_endpoint.same_backend(&destination_endpoint)
+			&& source_endpoint.backend.capabilities().rename
+		{
+			let (bytes, _) = measure(&source_endpoint).await?;
+			source_endpoint
+				.backend
+				.rename(&source_endpoint.path, &destination_endpoint.path)
+				.await?;
+			if let Some(callback) = progress_callback {
+				callback(bytes, u64::MAX);
+			}
+
+			ctx.log(format!(
+				"Cloud rename: {} -> {}",
+				source.display(),
+				destination.display()
+			));
+			return Ok(bytes);
+		}
+
+		let bytes = BackendTransfer::new()
+			.with_checksum(verify_checksum)
+			.copy(
+				&source_endpoint,
+				&destination_endpoint,
+				progress_callback,
+				move || async move { ctx.check_interrupt().await.map_err(anyhow::Error::from) },
+			)
+			.await?;
+
+		if self.delete_source {
+			source_endpoint
+				.backend
+				.delete(&source_endpoint.path)
+				.await?;
+		}
+
+		ctx.log(format!(
+			"Cloud transfer: {} -> {} ({} bytes)",
+			source.display(),
+			destination.display(),
+			bytes
+		));
+
+		Ok(bytes)
+	}
+}
+
+/// Short description of a transfer involving a cloud path, for progress and logs
+pub fn describe_cloud_transfer(source: &SdPath, destination: &SdPath, is_move: bool) -> String {
+	let verb = if is_move { "move" } else { "copy" };
+	match (source.as_cloud(), destination.as_cloud()) {
+		(Some((from, ..)), Some((to, ..))) => {
+			format!("Cloud {} ({} -> {})", verb, from.scheme(), to.scheme())
+		}
+		(Some((from, ..)), None) => format!("Download from {}", from.scheme()),
+		(None, Some((to, ..))) => format!("Upload to {}", to.scheme()),
+		(None, None) => format!("Streaming {}", verb),
+	}
+}
+
+/// Bytes and file count under a local or cloud path, for paths the filesystem can't stat
+pub async fn measure(endpoint: &TransferEndpoint) -> Result<(u64, usize)> {
+	let metadata = endpoint.backend.metadata(&endpoint.path).await?;
+	if metadata.kind != EntryKind::Directory {
+		return Ok((metadata.size, 1));
+	}
+
+	let mut bytes = 0;
+	let mut files = 0;
+	let mut stack = vec![endpoint.path.clone()];
+	while let Some(dir) = stack.pop() {
+		for entry in endpoint.backend.read_dir(&dir).await? {
+			match entry.kind {
+				EntryKind::Directory => stack.push(dir.join(&entry.name)),
+				_ => {
+					bytes += entry.size;
+					files += 1;
+				}
+			}
+		}
+	}
+
+	Ok((bytes, files))
+}
+
+/// Whether a copy between these paths goes through [`CloudTransferStrategy`]
+pub fn is_cloud_transfer(source: &SdPath, destination: &SdPath) -> bool {
+	source.is_cloud() || destination.is_cloud()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::volume::{CloudBackend, CloudServiceType};
+	use std::sync::Mutex;
+	use tempfile::TempDir;
+
+	fn memory_backend() -> Arc<dyn VolumeBackend> {
+		let operator = opendal::Operator::new(opendal::services::Memory::default())
+			.unwrap()
+			.finish();
+		Arc::new(CloudBackend::from_operator(
+			operator,
+			CloudServiceType::Other,
+		))
+	}
+
+	/// A cloud backend that can append, backed by a directory
+	fn fs_backend(root: &Path) -> Arc<dyn VolumeBackend> {
+		let operator =
+			opendal::Operator::new(opendal::services::Fs::default().root(&root.to_string_lossy()))
+				.unwrap()
+				.finish();
+		Arc::new(CloudBackend::from_operator(
+			operator,
+			CloudServiceType::Other,
+		))
+	}
+
+	fn sample(len: usize) -> Bytes {
+		(0..len).map(|i| (i % 251) as u8).collect::<Vec<_>>().into()
+	}
+
+	async fn no_interrupt() -> Result<()> {
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn uploads_in_chunks_with_progress() {
+		let temp_dir = TempDir::new().unwrap();
+		let data = sample(10_000);
+		tokio::fs::write(temp_dir.path().join("photo.raw"), &data)
+			.await
+			.unwrap();
+
+		let local = Arc::new(LocalBackend::new(temp_dir.path()));
+		let cloud = memory_backend();
+		let source = TransferEndpoint::new(local, temp_dir.path().join("photo.raw"));
+		let destination = TransferEndpoint::new(cloud.clone(), "backups/photo.raw");
+
+		let reports = Mutex::new(Vec::new());
+		let callback: ProgressCallback = Box::new(|done, total| {
+			reports.lock().unwrap().push((done, total));
+		});
+
+		let bytes = BackendTransfer::new()
+			.with_chunk_size(4096)
+			.with_checksum(true)
+			.copy(&source, &destination, Some(&callback), no_interrupt)
+			.await
+			.unwrap();
+
+		assert_eq!(bytes, 10_000);
+		assert_eq!(
+			cloud.read(Path::new("backups/photo.raw")).await.unwrap(),
+			data
+		);
+		assert_eq!(
+			*reports.lock().unwrap(),
+			vec![
+				(4096, 10_000),
+				(8192, 10_000),
+				(10_000, 10_000),
+				(10_000, u64::MAX)
+			]
+		);
+	}
+
+	#[tokio::test]
+	async fn copies_directories_between_cloud_volumes() {
+		let temp_dir = TempDir::new().unwrap();
+		let source_backend = fs_backend(&temp_dir.path().join("a"));
+		let destination_backend = memory_backend();
+
+		source_backend
+			.write(Path::new("album/one.jpg"), sample(300))
+			.await
+			.unwrap();
+		source_backend
+			.write(Path::new("album/nested/two.jpg"), sample(500))
+			.await
+			.unwrap();
+
+		let source = TransferEndpoint::new(source_backend.clone(), "album");
+		let destination = TransferEndpoint::new(destination_backend.clone(), "copy/album");
+		assert_eq!(measure(&source).await.unwrap(), (800, 2));
+
+		let bytes = BackendTransfer::new()
+			.with_chunk_size(128)
+			.copy(&source, &destination, None, no_interrupt)
+			.await
+			.unwrap();
+
+		assert_eq!(bytes, 800);
+		assert_eq!(
+			destination_backend
+				.read(Path::new("copy/album/nested/two.jpg"))
+				.await
+				.unwrap(),
+			sample(500)
+		);
+	}
+
+	#[tokio::test]
+	async fn resumes_a_partial_file() {
+		let temp_dir = TempDir::new().unwrap();
+		let source_backend = memory_backend();
+		let destination_backend = fs_backend(temp_dir.path());
+		assert!(destination_backend.capabilities().append);
+
+		let data = sample(9_000);
+		source_backend
+			.write(Path::new("video.mov"), data.clone())
+			.await
+			.unwrap();
+
+		// Stop the first attempt after two chunks
+		let source = TransferEndpoint::new(source_backend, "video.mov");
+		let destination = TransferEndpoint::new(destination_backend.clone(), "video.mov");
+		let transfer = BackendTransfer::new().with_chunk_size(2048);
+		let chunks = Mutex::new(0);
+		let interrupt_after_two = || {
+			let mut chunks = chunks.lock().unwrap();
+			*chunks += 1;
+			let result = if *chunks > 2 {
+				Err(anyhow::anyhow!("interrupted"))
+			} else {
+				Ok(())
+			};
+			async move { result }
+		};
+		assert!(transfer
+			.copy_file(&source, &destination, None, &interrupt_after_two)
+			.await
+			.is_err());
+		assert_eq!(
+			destination_backend
+				.metadata(Path::new("video.mov"))
+				.await
+				.unwrap()
+				.size,
+			4096
+		);
+
+		let resumed = transfer
+			.with_checksum(true)
+			.copy_file(&source, &destination, None, &no_interrupt)
+			.await
+			.unwrap();
+		assert_eq!(
+			resumed,
+			FileTransfer {
+				size: 9_000,
+				resumed_from: 4096
+			}
+		);
+		assert_eq!(
+			destination_backend
+				.read(Path::new("video.mov"))
+				.await
+				.unwrap(),
+			data
+		);
+	}
+
+	#[tokio::test]
+	async fn restarts_when_the_partial_file_differs() {
+		let temp_dir = TempDir::new().unwrap();
+		let source_backend = memory_backend();
+		let destination_backend = fs_backend(temp_dir.path());
+
+		let data = sample(5_000);
+		source_backend
+			.write(Path::new("notes.txt"), data.clone())
+			.await
+			.unwrap();
+		destination_backend
+			.write(
+				Path::new("notes.txt"),
+				Bytes::from_static(b"something else"),
+			)
+			.await
+			.unwrap();
+
+		let transfer = BackendTransfer::new()
+			.with_chunk_size(1024)
+			.copy_file(
+				&TransferEndpoint::new(source_backend, "notes.txt"),
+				&TransferEndpoint::new(destination_backend.clone(), "notes.txt"),
+				None,
+				&no_interrupt,
+			)
+			.await
+			.unwrap();
+
+		assert_eq!(transfer.resumed_from, 0);
+		assert_eq!(
+			destination_backend
+				.read(Path::new("notes.txt"))
+				.await
+				.unwrap(),
+			data
+		);
+	}
+
+	#[tokio::test]
+	async fn interrupted_upload_leaves_nothing_without_append() {
+		let source_backend = memory_backend();
+		let destination_backend = memory_backend();
+		source_backend
+			.write(Path::new("archive.zip"), sample(4_000))
+			.await
+			.unwrap();
+
+		let failing = || async { Err::<(), _>(anyhow::anyhow!("interrupted")) };
+		let result = BackendTransfer::new()
+			.with_chunk_size(1024)
+			.copy_file(
+				&TransferEndpoint::new(source_backend, "archive.zip"),
+				&TransferEndpoint::new(destination_backend.clone(), "archive.zip"),
+				None,
+				&failing,
+			)
+			.await;
+
+		assert!(result.is_err());
+		assert!(!destination_backend
+			.exists(Path::new("archive.zip"))
+			.await
+			.unwrap());
+	}
+
+	#[tokio::test]
+	async fn copies_within_one_backend_natively() {
+		let temp_dir = TempDir::new().unwrap();
+		let backend = fs_backend(temp_dir.path());
+		assert!(backend.capabilities().server_side_copy);
+
+		backend
+			.write(Path::new("docs/report.pdf"), sample(3_000))
+			.await
+			.unwrap();
+
+		let reports = Mutex::new(Vec::new());
+		let callback: ProgressCallback = Box::new(|done, total| {
+			reports.lock().unwrap().push((done, total));
+		});
+		let bytes = BackendTransfer::new()
+			.copy(
+				&TransferEndpoint::new(backend.clone(), "docs/report.pdf"),
+				&TransferEndpoint::new(backend.clone(), "archive/report.pdf"),
+				Some(&callback),
+				no_interrupt,
+			)
+			.await
+			.unwrap();
+
+		assert_eq!(bytes, 3_000);
+		assert_eq!(
+			*reports.lock().unwrap(),
+			vec![(3_000, 3_000), (3_000, u64::MAX)]
+		);
+		assert_eq!(
+			backend.read(Path::new("archive/report.pdf")).await.unwrap(),
+			sample(3_000)
+		);
+	}
+}
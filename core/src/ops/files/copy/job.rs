@@ -3,7 +3,10 @@
 //! Implements file copy and move operations using the Strategy Pattern with real-time
 //! progress tracking and transfer speed calculation. Supports resume on interruption.
 
-use super::{database::CopyDatabaseQuery, input::CopyMethod, routing::CopyStrategyRouter};
+use super::{
+	archive::filesystem_path, database::CopyDatabaseQuery, input::CopyMethod,
+	routing::CopyStrategyRouter,
+};
 use crate::{
 	domain::addressing::{SdPath, SdPathBatch},
 	infra::job::generic_progress::{GenericProgress, ToGenericProgress},
@@ -298,7 +301,7 @@ impl JobHandler for FileCopyJob {
 		ctx.progress(Progress::generic(progress.to_generic_progress()));
 
 		// Calculate actual file count and total size, and collect file metadata
-		let actual_file_count = self.count_total_files().await?;
+		let actual_file_count = self.count_total_files(&ctx).await?;
 		let estimated_total_bytes = self.calculate_total_size(&ctx).await?;
 
 		// Collect file metadata for queryable list
@@ -362,7 +365,7 @@ impl JobHandler for FileCopyJob {
 				));
 
 				// Update progress aggregator to account for already completed files
-				let files_in_source = if let Some(local_path) = resolved_source.as_local_path() {
+				let files_in_source = if let Some(local_path) = filesystem_path(&resolved_source) {
 					let file_size = self.get_path_size(local_path).await.unwrap_or(0);
 					let file_count = self.count_files_in_path(local_path).await.unwrap_or(1);
 					progress_aggregator.skip_completed_file(file_size, file_count);
@@ -416,8 +419,11 @@ impl JobHandler for FileCopyJob {
 			));
 
 			// Count files in this source path for accurate progress tracking
-			let files_in_source = if let Some(local_path) = resolved_source.as_local_path() {
+			let files_in_source = if let Some(local_path) = filesystem_path(&resolved_source) {
 				self.count_files_in_path(local_path).await.unwrap_or(1)
+			} else if let Some((_, files)) = self.measure_cloud_source(&ctx, &resolved_source).await
+			{
+				files
 			} else {
 				1
 			};
@@ -1058,9 +1064,11 @@ impl FileCopyJob {
 		let mut total = 0u64;
 
 		for source in &self.sources.paths {
-			if let Some(local_path) = source.as_local_path() {
+			if let Some(local_path) = filesystem_path(source) {
 				// Local path - calculate directly from filesystem
 				total += self.get_path_size(local_path).await.unwrap_or(0);
+			} else if let Some((size, _)) = self.measure_cloud_source(ctx, source).await {
+				total += size;
 			} else {
 				// Non-local path - query database for synced metadata
 				match PathResolver::resolve_to_entry(ctx.library_db(), source).await {
@@ -1141,7 +1149,7 @@ impl FileCopyJob {
 			})?;
 
 			let (size_bytes, is_directory, entry_id) = if let Some(local_path) =
-				resolved_source.as_local_path()
+				filesystem_path(&resolved_source)
 			{
 				// Local path - get from filesystem
 				let metadata = tokio::fs::metadata(local_path)
@@ -1162,6 +1170,23 @@ impl FileCopyJob {
 					.and_then(|e| e.uuid);
 
 				(size, metadata.is_dir(), entry_id)
+			} else if let Some(endpoint) = self.cloud_endpoint(ctx, &resolved_source).await {
+				// Cloud path or archive member - ask the volume backend
+				let metadata = endpoint
+					.backend
+					.metadata(&endpoint.path)
+					.await
+					.map_err(|e| JobError::execution(format!("Failed to read metadata: {}", e)))?;
+				let is_dir = metadata.kind == crate::ops::indexing::state::EntryKind::Directory;
+				let size = if is_dir {
+					super::cloud::measure(&endpoint)
+						.await
+						.map(|(size, _)| size)
+						.unwrap_or(0)
+				} else {
+					metadata.size
+				};
+				(size, is_dir, None)
 			} else {
 				// Remote path - query database for synced metadata
 				match PathResolver::resolve_to_entry(ctx.library_db(), &resolved_source).await {
@@ -1221,13 +1246,15 @@ impl FileCopyJob {
 	}
 
 	/// Count total number of files to be copied (including files within directories)
-	async fn count_total_files(&self) -> JobResult<usize> {
+	async fn count_total_files(&self, ctx: &JobContext<'_>) -> JobResult<usize> {
 		let mut total_count = 0;
 
 		for source in &self.sources.paths {
-			if let Some(local_path) = source.as_local_path() {
+			if let Some(local_path) = filesystem_path(source) {
 				// Local path - count directly from filesystem
 				total_count += self.count_files_in_path(local_path).await.unwrap_or(0);
+			} else if let Some((_, files)) = self.measure_cloud_source(ctx, source).await {
+				total_count += files;
 			} else {
 				// Non-local path - use database estimate
 				// For now, count as 1 item (will be refined during actual transfer)
@@ -1238,6 +1265,49 @@ impl FileCopyJob {
 		Ok(total_count)
 	}
 
+	/// Backend and path of a source on a cloud volume or inside an archive
+	async fn cloud_endpoint(
+		&self,
+		ctx: &JobContext<'_>,
+		source: &SdPath,
+	) -> Option<super::cloud::TransferEndpoint> {
+		if let Some(endpoint) = super::archive::archive_member_endpoint(source) {
+			return Some(endpoint);
+		}
+		if !source.is_cloud() {
+			return None;
+		}
+
+		let volume_manager = ctx.volume_manager();
+		match super::cloud::TransferEndpoint::resolve(source, volume_manager.as_deref()).await {
+			Ok(endpoint) => Some(endpoint),
+			Err(e) => {
+				ctx.log(format!("Cloud source '{}': {}", source.display(), e));
+				None
+			}
+		}
+	}
+
+	/// Bytes and file count of a source on a cloud volume or inside an archive
+	async fn measure_cloud_source(
+		&self,
+		ctx: &JobContext<'_>,
+		source: &SdPath,
+	) -> Option<(u64, usize)> {
+		let endpoint = self.cloud_endpoint(ctx, source).await?;
+		match super::cloud::measure(&endpoint).await {
+			Ok(measured) => Some(measured),
+			Err(e) => {
+				ctx.log(format!(
+					"Cloud source '{}': failed to measure: {}",
+					source.display(),
+					e
+				));
+				None
+			}
+		}
+	}
+
 	/// Count files in a path (recursive for directories)
 	async fn count_files_in_path(&self, path: &std::path::Path) -> Result<usize, std::io::Error> {
 		let mut count = 0;
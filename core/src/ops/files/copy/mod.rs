@@ -1,6 +1,8 @@
 //! Modular file copy operations using the Strategy Pattern
 
 pub mod action;
+pub mod archive;
+pub mod cloud;
 pub mod database;
 pub mod input;
 pub mod job;
@@ -9,6 +11,8 @@ pub mod output;
 pub mod routing;
 pub mod strategy;
 
+pub use archive::ArchiveExtractStrategy;
+pub use cloud::{BackendTransfer, CloudTransferStrategy, TransferEndpoint};
 pub use job::{CopyError, CopyOptions, CopyProgress, FileCopyJob, MoveMode};
 pub use metadata::{CopyFileEntry, CopyFileStatus, CopyJobMetadata};
 pub use output::FileCopyActionOutput;
@@ -4,6 +4,8 @@
 //! Exposes metadata about the selected strategy for UI display.
 
 use super::{
+	archive::{describe_archive_extraction, is_archive_extraction, ArchiveExtractStrategy},
+	cloud::{describe_cloud_transfer, is_cloud_transfer, CloudTransferStrategy},
 	input::CopyMethod,
 	strategy::{
 		CopyStrategy, FastCopyStrategy, LocalMoveStrategy, LocalStreamCopyStrategy,
@@ -19,7 +21,8 @@ use tracing::info;
 /// Metadata about the selected copy strategy for UI display.
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct CopyStrategyMetadata {
-	/// Internal strategy name (e.g., "LocalMove", "FastCopy", "LocalStream", "RemoteTransfer")
+	/// Internal strategy name (e.g., "LocalMove", "FastCopy", "LocalStream", "RemoteTransfer",
+	/// "CloudTransfer", "ArchiveExtract")
 	pub strategy_name: String,
 	/// Human-readable description (e.g., "Atomic move (same storage)")
 	pub strategy_description: String,
@@ -50,6 +53,20 @@ impl CopyStrategyRouter {
 			destination.device_slug()
 		);
 
+		// Members of archives are read out of the archive, wherever they're going
+		if is_archive_extraction(source) {
+			info!("[ROUTING] Archive member detected - selecting ArchiveExtractStrategy");
+			return Box::new(ArchiveExtractStrategy { is_move });
+		}
+
+		// Anything touching a cloud volume goes through its backend
+		if is_cloud_transfer(source, destination) {
+			info!("[ROUTING] Cloud path detected - selecting CloudTransferStrategy");
+			return Box::new(CloudTransferStrategy {
+				delete_source: is_move,
+			});
+		}
+
 		// Cross-device transfer - always use network strategy
 		// Compare device slugs to detect if paths are on different devices
 		let is_cross_device = match (source.device_slug(), destination.device_slug()) {
@@ -141,6 +158,35 @@ impl CopyStrategyRouter {
 		copy_method: &CopyMethod,
 		volume_manager: Option<&VolumeManager>,
 	) -> (Box<dyn CopyStrategy>, CopyStrategyMetadata) {
+		if is_archive_extraction(source) {
+			let metadata = CopyStrategyMetadata {
+				strategy_name: "ArchiveExtract".to_string(),
+				strategy_description: describe_archive_extraction(source, is_move),
+				is_cross_device: false,
+				is_cross_volume: false,
+				is_fast_operation: false,
+				copy_method: copy_method.clone(),
+			};
+			return (Box::new(ArchiveExtractStrategy { is_move }), metadata);
+		}
+
+		if is_cloud_transfer(source, destination) {
+			let metadata = CopyStrategyMetadata {
+				strategy_name: "CloudTransfer".to_string(),
+				strategy_description: describe_cloud_transfer(source, destination, is_move),
+				is_cross_device: false,
+				is_cross_volume: true,
+				is_fast_operation: false,
+				copy_method: copy_method.clone(),
+			};
+			return (
+				Box::new(CloudTransferStrategy {
+					delete_source: is_move,
+				}),
+				metadata,
+			);
+		}
+
 		let is_cross_device = match (source.device_slug(), destination.device_slug()) {
 			(Some(src_slug), Some(dst_slug)) => src_slug != dst_slug,
 			_ => false,
@@ -332,6 +378,14 @@ impl CopyStrategyRouter {
 		copy_method: &CopyMethod,
 		volume_manager: Option<&VolumeManager>,
 	) -> String {
+		if is_archive_extraction(source) {
+			return describe_archive_extraction(source, is_move);
+		}
+
+		if is_cloud_transfer(source, destination) {
+			return describe_cloud_transfer(source, destination, is_move);
+		}
+
 		// Check if cross-device using device slugs
 		let is_cross_device = match (source.device_slug(), destination.device_slug()) {
 			(Some(src_slug), Some(dst_slug)) => src_slug != dst_slug,
@@ -430,7 +484,26 @@ impl CopyStrategyRouter {
 		copy_method: &CopyMethod,
 		volume_manager: Option<&VolumeManager>,
 	) -> PerformanceEstimate {
-		// Cross-device transfers always use network
+		// Extraction streams out of a local file, resuming like any backend transfer
+		if is_archive_extraction(source) && !destination.is_cloud() {
+			return PerformanceEstimate {
+				speed_category: SpeedCategory::LocalDisk,
+				supports_resume: true,
+				requires_network: false,
+				is_atomic: false,
+			};
+		}
+
+		// Cloud and cross-device transfers always use network
+		if is_cloud_transfer(source, destination) {
+			return PerformanceEstimate {
+				speed_category: SpeedCategory::Network,
+				supports_resume: true,
+				requires_network: true,
+				is_atomic: false,
+			};
+		}
+
 		let is_cross_device = match (source.device_slug(), destination.device_slug()) {
 			(Some(src_slug), Some(dst_slug)) => src_slug != dst_slug,
 			_ => false,
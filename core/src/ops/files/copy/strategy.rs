@@ -20,7 +20,10 @@
 //!
 //! The router picks RemoteTransferStrategy for cross-device transfers, LocalMoveStrategy for
 //! same-volume moves, and FastCopyStrategy for same-volume copies in Atomic mode. Streaming
-//! mode or cross-volume operations use LocalStreamCopyStrategy for progress tracking.
+//! mode or cross-volume operations use LocalStreamCopyStrategy for progress tracking. Paths on
+//! cloud volumes always go through [`CloudTransferStrategy`](super::cloud::CloudTransferStrategy),
+//! which streams through the volume backends, and members of archives are extracted by
+//! [`ArchiveExtractStrategy`](super::archive::ArchiveExtractStrategy).
 //!
 //! ## Example
 //! ```rust,no_run
@@ -507,9 +510,8 @@ impl RemoteTransferStrategy {
 		let current_device_id = crate::device::get_current_device_id();
 		// Normalize path separators to forward slashes for cross-platform transmission.
 		// The receiving device may use a different OS separator (Windows \ vs Unix /).
-		let normalized_source_path = PathBuf::from(
-			source_path.to_string_lossy().replace('\\', "/"),
-		);
+		let normalized_source_path =
+			PathBuf::from(source_path.to_string_lossy().replace('\\', "/"));
 		let pull_request =
 			crate::service::network::protocol::file_transfer::FileTransferMessage::PullRequest {
 				transfer_id,
@@ -2,6 +2,7 @@
 
 use super::input::DuplicateDetectionInput;
 use super::job::{DetectionMode, DuplicateDetectionJob};
+use super::perceptual;
 use crate::{
 	context::CoreContext,
 	domain::addressing::{SdPath, SdPathBatch},
@@ -70,10 +71,12 @@ impl LibraryAction for DuplicateDetectionAction {
 			"size_only" => DetectionMode::SizeOnly,
 			"name_and_size" => DetectionMode::NameAndSize,
 			"deep_scan" => DetectionMode::DeepScan,
+			"perceptual" => DetectionMode::Perceptual,
 			_ => DetectionMode::ContentHash,
 		};
 
-		let job = DuplicateDetectionJob::new(self.paths, mode);
+		let job = DuplicateDetectionJob::new(self.paths, mode)
+			.with_max_distance(perceptual::max_distance_for_similarity(self.threshold));
 
 		// Dispatch job and return handle directly
 		let job_handle = library
@@ -12,6 +12,7 @@ pub struct DuplicateDetectionInput {
 	pub paths: Vec<PathBuf>,
 	/// Detection algorithm to use
 	pub algorithm: String,
-	/// Similarity threshold (0.0 to 1.0)
+	/// Similarity threshold (0.0 to 1.0), used by the "perceptual" algorithm
+	/// where 1.0 only matches visually identical files
 	pub threshold: f64,
 }
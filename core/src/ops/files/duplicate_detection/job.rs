@@ -1,12 +1,14 @@
 //! Duplicate detection job implementation
 
+use super::perceptual::{self, PerceptualHashes, PerceptualMedia};
 use crate::{
+	domain::addressing::{SdPath, SdPathBatch},
 	domain::content_identity::ContentHashGenerator,
 	infra::job::prelude::*,
-	domain::addressing::{SdPath, SdPathBatch},
 };
 use serde::{Deserialize, Serialize};
 use std::{
+	cmp::Reverse,
 	collections::{HashMap, HashSet},
 	path::PathBuf,
 	time::{Duration, Instant},
@@ -25,6 +27,8 @@ pub enum DetectionMode {
 	NameAndSize,
 	/// Deep comparison with full content verification
 	DeepScan,
+	/// Visually similar images and videos, by perceptual hash distance
+	Perceptual,
 }
 
 /// Duplicate detection job for finding duplicate files
@@ -35,6 +39,9 @@ pub struct DuplicateDetectionJob {
 	pub min_file_size: u64,
 	pub max_file_size: Option<u64>,
 	pub file_extensions: Option<HashSet<String>>,
+	/// Maximum Hamming distance (out of 64 bits) for perceptual matches
+	#[serde(default = "default_max_distance")]
+	pub max_distance: u32,
 
 	// Internal state for resumption
 	#[serde(skip)]
@@ -52,6 +59,9 @@ pub struct FileInfo {
 	pub size: u64,
 	pub content_hash: Option<String>,
 	pub modified: Option<std::time::SystemTime>,
+	/// Pixel dimensions, known for perceptually hashed images
+	#[serde(default)]
+	pub dimensions: Option<(u32, u32)>,
 }
 
 /// Duplicate group containing files with same content
@@ -61,6 +71,9 @@ pub struct DuplicateGroup {
 	pub total_size: u64,
 	pub wasted_space: u64, // Size that could be saved by keeping only one copy
 	pub detection_method: String,
+	/// Copy suggested to keep: highest resolution, then largest, then oldest
+	#[serde(default)]
+	pub best_copy: Option<SdPath>,
 }
 
 /// Duplicate detection progress information
@@ -119,6 +132,7 @@ impl JobHandler for DuplicateDetectionJob {
 			DetectionMode::ContentHash => self.find_content_duplicates(&ctx).await?,
 			DetectionMode::NameAndSize => self.find_name_size_duplicates(&ctx).await?,
 			DetectionMode::DeepScan => self.find_deep_scan_duplicates(&ctx).await?,
+			DetectionMode::Perceptual => self.find_perceptual_duplicates(&ctx).await?,
 		};
 
 		let total_duplicates = duplicate_groups.iter().map(|g| g.files.len() - 1).sum();
@@ -147,6 +161,7 @@ impl DuplicateDetectionJob {
 			min_file_size: 1024, // 1KB minimum
 			max_file_size: None,
 			file_extensions: None,
+			max_distance: perceptual::DEFAULT_MAX_DISTANCE,
 			processed_files: HashSet::new(),
 			size_groups: HashMap::new(),
 			started_at: Instant::now(),
@@ -171,6 +186,12 @@ impl DuplicateDetectionJob {
 		self
 	}
 
+	/// Set maximum perceptual hash distance for near-duplicates
+	pub fn with_max_distance(mut self, max_distance: u32) -> Self {
+		self.max_distance = max_distance;
+		self
+	}
+
 	/// Collect all files to scan
 	async fn collect_files(&self, ctx: &JobContext<'_>) -> JobResult<Vec<FileInfo>> {
 		let mut files = Vec::new();
@@ -209,6 +230,7 @@ impl DuplicateDetectionJob {
 						size: metadata.len(),
 						content_hash: None,
 						modified: metadata.modified().ok(),
+						dimensions: None,
 					});
 				}
 			} else if metadata.is_dir() {
@@ -298,6 +320,7 @@ impl DuplicateDetectionJob {
 					total_size: *size * files.len() as u64,
 					wasted_space,
 					detection_method: "Size comparison".to_string(),
+					best_copy: best_copy(files),
 				});
 			}
 		}
@@ -366,6 +389,7 @@ impl DuplicateDetectionJob {
 					if hash_files.len() > 1 {
 						let wasted_space = *size * (hash_files.len() as u64 - 1);
 						groups.push(DuplicateGroup {
+							best_copy: best_copy(&hash_files),
 							files: hash_files,
 							total_size: *size * files.len() as u64,
 							wasted_space,
@@ -406,6 +430,7 @@ impl DuplicateDetectionJob {
 				let file_count = files.len() as u64;
 				let wasted_space = size * (file_count - 1);
 				groups.push(DuplicateGroup {
+					best_copy: best_copy(&files),
 					files,
 					total_size: size * file_count,
 					wasted_space,
@@ -434,6 +459,140 @@ impl DuplicateDetectionJob {
 
 		Ok(hash_groups)
 	}
+
+	/// Find visually similar images and videos with perceptual hashes
+	///
+	/// Files are not grouped by size first since re-exports rarely share one.
+	async fn find_perceptual_duplicates(
+		&self,
+		ctx: &JobContext<'_>,
+	) -> JobResult<Vec<DuplicateGroup>> {
+		let db = ctx.library_db();
+		let registry = ctx.library().file_types();
+
+		let candidates: Vec<(&FileInfo, PerceptualMedia)> = self
+			.size_groups
+			.values()
+			.flatten()
+			.filter_map(|file| {
+				let media = PerceptualMedia::from_path(&registry, file.path.as_local_path()?)?;
+				Some((file, media))
+			})
+			.collect();
+		let total_candidates = candidates.len();
+
+		let mut files = Vec::with_capacity(total_candidates);
+		let mut hashes = Vec::with_capacity(total_candidates);
+
+		for (index, (file, media)) in candidates.into_iter().enumerate() {
+			ctx.check_interrupt().await?;
+
+			ctx.progress(Progress::structured(DuplicateProgress {
+				current_file: file.path.display(),
+				files_scanned: index + 1,
+				total_files: total_candidates,
+				duplicates_found: 0,
+				potential_savings: 0,
+				current_operation: "Computing perceptual hashes".to_string(),
+			}));
+
+			let Some(local_path) = file.path.as_local_path() else {
+				continue;
+			};
+
+			// Content hash keys the cache, so unchanged files are never decoded twice
+			let content_hash = match ContentHashGenerator::generate_content_hash(local_path).await {
+				Ok(content_hash) => content_hash,
+				Err(e) => {
+					ctx.add_non_critical_error(format!(
+						"Failed to generate CAS ID for {}: {}",
+						file.path.display(),
+						e
+					));
+					continue;
+				}
+			};
+
+			let file_hashes = match PerceptualHashes::load(db, &content_hash).await? {
+				Some(cached) => cached,
+				None => match PerceptualHashes::compute(local_path, media).await {
+					Ok(computed) => {
+						computed.store(db, &content_hash).await?;
+						computed
+					}
+					Err(e) => {
+						ctx.add_non_critical_error(format!(
+							"Failed to compute perceptual hash for {}: {}",
+							file.path.display(),
+							e
+						));
+						continue;
+					}
+				},
+			};
+
+			let mut file = file.clone();
+			file.content_hash = Some(content_hash);
+			file.dimensions = file_hashes.dimensions;
+			files.push(file);
+			hashes.push(file_hashes);
+		}
+
+		ctx.log(format!(
+			"Comparing {} perceptual hashes with max distance {}",
+			hashes.len(),
+			self.max_distance
+		));
+
+		let groups = perceptual::cluster(&hashes, self.max_distance)
+			.into_iter()
+			.map(|indices| {
+				let group_files: Vec<FileInfo> =
+					indices.into_iter().map(|i| files[i].clone()).collect();
+				let total_size = group_files.iter().map(|f| f.size).sum::<u64>();
+				let best = best_copy(&group_files);
+				let kept_size = group_files
+					.iter()
+					.find(|f| Some(&f.path) == best.as_ref())
+					.map_or(0, |f| f.size);
+
+				DuplicateGroup {
+					files: group_files,
+					total_size,
+					wasted_space: total_size - kept_size,
+					detection_method: format!(
+						"Perceptual hash (distance <= {})",
+						self.max_distance
+					),
+					best_copy: best,
+				}
+			})
+			.collect();
+
+		Ok(groups)
+	}
+}
+
+fn default_max_distance() -> u32 {
+	perceptual::DEFAULT_MAX_DISTANCE
+}
+
+/// Pick the copy to keep in a duplicate group
+///
+/// Prefers the highest resolution, then the largest file (least compressed),
+/// then the oldest modification time, which is usually the original.
+fn best_copy(files: &[FileInfo]) -> Option<SdPath> {
+	files
+		.iter()
+		.max_by(|a, b| {
+			let pixels =
+				|f: &FileInfo| f.dimensions.map_or(0, |(w, h)| u64::from(w) * u64::from(h));
+			pixels(a)
+				.cmp(&pixels(b))
+				.then(a.size.cmp(&b.size))
+				.then(a.modified.map(Reverse).cmp(&b.modified.map(Reverse)))
+		})
+		.map(|f| f.path.clone())
 }
 
 /// Job output for duplicate detection
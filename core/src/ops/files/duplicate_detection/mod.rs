@@ -3,6 +3,7 @@
 pub mod action;
 pub mod input;
 pub mod job;
+pub mod perceptual;
 
 pub use action::DuplicateDetectionAction;
 pub use input::DuplicateDetectionInput;
@@ -0,0 +1,382 @@
+//! Perceptual hashing for near-duplicate detection
+//!
+//! Images are hashed with both dHash and pHash, videos with the pHash of a few
+//! keyframes. Hashes are cached in the `perceptual_hashes` table by content
+//! hash so re-running detection only decodes new or modified files.
+
+use crate::{
+	domain::ContentKind,
+	filetype::FileTypeRegistry,
+	infra::db::entities::perceptual_hash,
+	ops::media::thumbnail::{load_oriented_image, ThumbnailError, ThumbnailResult, VideoGenerator},
+};
+use sea_orm::{
+	sea_query::OnConflict, ActiveValue::Set, ColumnTrait, DatabaseConnection, DbErr, EntityTrait,
+	QueryFilter,
+};
+use std::path::Path;
+
+/// Number of frames sampled from each video
+const VIDEO_KEYFRAMES: u32 = 5;
+
+/// Frames are decoded at this size, pHash only looks at 32x32 anyway
+const KEYFRAME_SIZE: u32 = 128;
+
+/// Keyframes closer than this, as a fraction of duration, were sampled at the same point
+const POSITION_TOLERANCE: f64 = 0.5 / VIDEO_KEYFRAMES as f64;
+
+/// Default maximum Hamming distance for two files to be near-duplicates
+pub const DEFAULT_MAX_DISTANCE: u32 = 10;
+
+/// Convert a similarity threshold (0.0 to 1.0) into a maximum Hamming distance
+///
+/// Out of range thresholds fall back to `DEFAULT_MAX_DISTANCE`.
+pub fn max_distance_for_similarity(similarity: f64) -> u32 {
+	if similarity > 0.0 && similarity <= 1.0 {
+		((1.0 - similarity) * f64::from(sd_images::HASH_BITS)).round() as u32
+	} else {
+		DEFAULT_MAX_DISTANCE
+	}
+}
+
+/// Kind of media that can be perceptually hashed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerceptualMedia {
+	Image,
+	Video,
+}
+
+impl PerceptualMedia {
+	/// Detect the media kind of a file from its extension
+	pub fn from_path(registry: &FileTypeRegistry, path: &Path) -> Option<Self> {
+		match registry.identify_by_extension(path) {
+			ContentKind::Image => Some(Self::Image),
+			ContentKind::Video => Some(Self::Video),
+			_ => None,
+		}
+	}
+
+	fn as_str(&self) -> &'static str {
+		match self {
+			Self::Image => "image",
+			Self::Video => "video",
+		}
+	}
+}
+
+/// pHash of one video frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyframeHash {
+	/// Where the frame was taken, as a fraction of the video's duration
+	pub position: f64,
+	pub phash: u64,
+}
+
+/// Perceptual hashes of a single image or video
+#[derive(Debug, Clone, PartialEq)]
+pub struct PerceptualHashes {
+	pub media: PerceptualMedia,
+	/// pHash of the image, or of the middle keyframe of a video
+	pub phash: u64,
+	/// dHash, images only
+	pub dhash: Option<u64>,
+	/// pHash of each sampled keyframe, videos only
+	pub keyframes: Vec<KeyframeHash>,
+	/// Pixel dimensions, images only
+	pub dimensions: Option<(u32, u32)>,
+}
+
+impl PerceptualHashes {
+	/// Decode a file and compute its hashes
+	pub async fn compute(path: &Path, media: PerceptualMedia) -> ThumbnailResult<Self> {
+		match media {
+			PerceptualMedia::Image => {
+				let path = path.to_path_buf();
+				tokio::task::spawn_blocking(move || {
+					let img = load_oriented_image(&path)?;
+
+					Ok(Self {
+						media,
+						phash: sd_images::phash(&img),
+						dhash: Some(sd_images::dhash(&img)),
+						keyframes: Vec::new(),
+						dimensions: Some((img.width(), img.height())),
+					})
+				})
+				.await
+				.map_err(|e| ThumbnailError::other(format!("Task join error: {}", e)))?
+			}
+			PerceptualMedia::Video => {
+				let frames = VideoGenerator::new()
+					.keyframes(path, VIDEO_KEYFRAMES, KEYFRAME_SIZE)
+					.await?;
+
+				let keyframes: Vec<KeyframeHash> = tokio::task::spawn_blocking(move || {
+					frames
+						.iter()
+						.map(|(position, frame)| KeyframeHash {
+							position: *position,
+							phash: sd_images::phash(frame),
+						})
+						.collect()
+				})
+				.await
+				.map_err(|e| ThumbnailError::other(format!("Task join error: {}", e)))?;
+
+				let phash = keyframes
+					.get(keyframes.len() / 2)
+					.ok_or_else(|| ThumbnailError::video_processing("No frames could be decoded"))?
+					.phash;
+
+				Ok(Self {
+					media,
+					phash,
+					dhash: None,
+					keyframes,
+					dimensions: None,
+				})
+			}
+		}
+	}
+
+	/// Distance between two files, `None` when they can't be compared
+	///
+	/// Images must be close on both hashes. Videos are compared on frames taken
+	/// at the same position and the mean distance is used, so one re-encoded scene
+	/// doesn't split them. Frames that only one video could decode are left out.
+	pub fn distance(&self, other: &Self) -> Option<u32> {
+		if self.media != other.media {
+			return None;
+		}
+
+		match self.media {
+			PerceptualMedia::Image => {
+				let phash = sd_images::hamming_distance(self.phash, other.phash);
+				let dhash = match (self.dhash, other.dhash) {
+					(Some(a), Some(b)) => sd_images::hamming_distance(a, b),
+					_ => phash,
+				};
+				Some(phash.max(dhash))
+			}
+			PerceptualMedia::Video => {
+				if self.keyframes.is_empty() || other.keyframes.is_empty() {
+					return Some(sd_images::hamming_distance(self.phash, other.phash));
+				}
+
+				let distances: Vec<u32> = self
+					.keyframes
+					.iter()
+					.filter_map(|a| {
+						other
+							.keyframes
+							.iter()
+							.find(|b| (a.position - b.position).abs() < POSITION_TOLERANCE)
+							.map(|b| sd_images::hamming_distance(a.phash, b.phash))
+					})
+					.collect();
+				if distances.is_empty() {
+					return None;
+				}
+
+				Some(distances.iter().sum::<u32>() / distances.len() as u32)
+			}
+		}
+	}
+
+	fn from_model(model: perceptual_hash::Model) -> Option<Self> {
+		let media = match model.media_kind.as_str() {
+			"image" => PerceptualMedia::Image,
+			"video" => PerceptualMedia::Video,
+			_ => return None,
+		};
+		let keyframes = model
+			.keyframe_hashes
+			.and_then(|json| serde_json::from_value::<Vec<(f64, i64)>>(json).ok())
+			.unwrap_or_default();
+
+		Some(Self {
+			media,
+			phash: model.phash as u64,
+			dhash: model.dhash.map(|hash| hash as u64),
+			keyframes: keyframes
+				.into_iter()
+				.map(|(position, hash)| KeyframeHash {
+					position,
+					phash: hash as u64,
+				})
+				.collect(),
+			dimensions: model
+				.width
+				.zip(model.height)
+				.map(|(width, height)| (width as u32, height as u32)),
+		})
+	}
+
+	/// Load cached hashes for a content hash
+	pub async fn load(db: &DatabaseConnection, content_hash: &str) -> Result<Option<Self>, DbErr> {
+		Ok(perceptual_hash::Entity::find()
+			.filter(perceptual_hash::Column::ContentHash.eq(content_hash))
+			.one(db)
+			.await?
+			.and_then(Self::from_model))
+	}
+
+	/// Cache hashes for a content hash, keeping any existing row
+	pub async fn store(&self, db: &DatabaseConnection, content_hash: &str) -> Result<(), DbErr> {
+		let keyframes: Vec<(f64, i64)> = self
+			.keyframes
+			.iter()
+			.map(|keyframe| (keyframe.position, keyframe.phash as i64))
+			.collect();
+
+		let model = perceptual_hash::ActiveModel {
+			content_hash: Set(content_hash.to_string()),
+			media_kind: Set(self.media.as_str().to_string()),
+			phash: Set(self.phash as i64),
+			dhash: Set(self.dhash.map(|hash| hash as i64)),
+			keyframe_hashes: Set((!keyframes.is_empty()).then(|| serde_json::json!(keyframes))),
+			width: Set(self.dimensions.map(|(width, _)| width as i32)),
+			height: Set(self.dimensions.map(|(_, height)| height as i32)),
+			created_at: Set(chrono::Utc::now()),
+			..Default::default()
+		};
+
+		perceptual_hash::Entity::insert(model)
+			.on_conflict(
+				OnConflict::column(perceptual_hash::Column::ContentHash)
+					.do_nothing()
+					.to_owned(),
+			)
+			.exec_without_returning(db)
+			.await?;
+
+		Ok(())
+	}
+}
+
+/// Group hashes whose distance is at most `max_distance`
+///
+/// Matches are transitive: if A is close to B and B to C, all three end up in
+/// the same group. Returns indices into `hashes`, only for groups of two or more.
+pub fn cluster(hashes: &[PerceptualHashes], max_distance: u32) -> Vec<Vec<usize>> {
+	let mut parents: Vec<usize> = (0..hashes.len()).collect();
+
+	fn root(parents: &mut [usize], mut i: usize) -> usize {
+		while parents[i] != i {
+			parents[i] = parents[parents[i]];
+			i = parents[i];
+		}
+		i
+	}
+
+	for i in 0..hashes.len() {
+		for j in (i + 1)..hashes.len() {
+			if matches!(hashes[i].distance(&hashes[j]), Some(d) if d <= max_distance) {
+				let (a, b) = (root(&mut parents, i), root(&mut parents, j));
+				if a != b {
+					parents[b] = a;
+				}
+			}
+		}
+	}
+
+	let mut groups: std::collections::HashMap<usize, Vec<usize>> = std::collections::HashMap::new();
+	for i in 0..hashes.len() {
+		let r = root(&mut parents, i);
+		groups.entry(r).or_default().push(i);
+	}
+
+	let mut groups: Vec<Vec<usize>> = groups.into_values().filter(|g| g.len() > 1).collect();
+	groups.sort_by_key(|g| g[0]);
+	groups
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn image(phash: u64, dhash: u64) -> PerceptualHashes {
+		PerceptualHashes {
+			media: PerceptualMedia::Image,
+			phash,
+			dhash: Some(dhash),
+			keyframes: Vec::new(),
+			dimensions: None,
+		}
+	}
+
+	/// A video with frames at some of three evenly spaced slots
+	fn video_frames(frames: &[(u32, u64)]) -> PerceptualHashes {
+		let keyframes: Vec<KeyframeHash> = frames
+			.iter()
+			.map(|(slot, phash)| KeyframeHash {
+				position: (f64::from(*slot) + 0.5) / 3.0,
+				phash: *phash,
+			})
+			.collect();
+
+		PerceptualHashes {
+			media: PerceptualMedia::Video,
+			phash: keyframes[keyframes.len() / 2].phash,
+			dhash: None,
+			keyframes,
+			dimensions: None,
+		}
+	}
+
+	fn video(keyframes: Vec<u64>) -> PerceptualHashes {
+		let frames: Vec<(u32, u64)> = (0..).zip(keyframes).collect();
+		video_frames(&frames)
+	}
+
+	#[test]
+	fn image_distance_uses_worst_hash() {
+		let a = image(0, 0);
+		let b = image(0b111, 0b1);
+
+		assert_eq!(a.distance(&b), Some(3));
+	}
+
+	#[test]
+	fn images_and_videos_never_match() {
+		assert_eq!(image(0, 0).distance(&video(vec![0, 0, 0])), None);
+	}
+
+	#[test]
+	fn video_distance_is_mean_over_keyframes() {
+		let a = video(vec![0, 0, 0]);
+		let b = video(vec![0b1111, 0b11, 0]);
+
+		assert_eq!(a.distance(&b), Some(2));
+	}
+
+	#[test]
+	fn video_distance_aligns_frames_by_position() {
+		let a = video(vec![0, u64::MAX, 0b11]);
+		// The middle frame failed to decode
+		let b = video_frames(&[(0, 0), (2, 0b11)]);
+
+		assert_eq!(a.distance(&b), Some(0));
+		assert_eq!(b.distance(&a), Some(0));
+	}
+
+	#[test]
+	fn similarity_maps_to_distance() {
+		assert_eq!(max_distance_for_similarity(1.0), 0);
+		assert_eq!(max_distance_for_similarity(0.9), 6);
+		assert_eq!(max_distance_for_similarity(0.0), DEFAULT_MAX_DISTANCE);
+	}
+
+	#[test]
+	fn clusters_are_transitive() {
+		let hashes = vec![
+			image(0, 0),
+			image(0b1111, 0b1111),
+			image(0xFF, 0xFF),
+			image(u64::MAX, u64::MAX),
+		];
+
+		assert_eq!(cluster(&hashes, 4), vec![vec![0, 1, 2]]);
+		assert!(cluster(&hashes, 2).is_empty());
+	}
+}
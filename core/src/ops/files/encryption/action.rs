@@ -0,0 +1,159 @@
+//! File encryption action handlers
+
+use super::input::{FileDecryptInput, FileEncryptInput};
+use super::job::{EncryptionMode, FileEncryptionJob};
+use crate::{
+	context::CoreContext,
+	domain::SdPathBatch,
+	infra::action::{error::ActionError, LibraryAction},
+};
+use sd_crypto::Protected;
+use std::sync::Arc;
+
+/// Shortest password accepted, anything shorter is trivially brute forced
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+fn check_input(targets: &SdPathBatch, password: &str) -> Result<(), String> {
+	if targets.paths.is_empty() {
+		return Err("At least one target file must be specified".to_string());
+	}
+
+	if password.chars().count() < MIN_PASSWORD_LENGTH {
+		return Err(format!(
+			"Password must be at least {} characters long",
+			MIN_PASSWORD_LENGTH
+		));
+	}
+
+	Ok(())
+}
+
+async fn dispatch(
+	library: Arc<crate::library::Library>,
+	job: FileEncryptionJob,
+) -> Result<crate::infra::job::handle::JobReceipt, ActionError> {
+	let job_handle = library
+		.jobs()
+		.dispatch(job)
+		.await
+		.map_err(ActionError::Job)?;
+
+	Ok(job_handle.into())
+}
+
+#[derive(Debug, Clone)]
+pub struct FileEncryptAction {
+	pub targets: SdPathBatch,
+	pub password: Protected<String>,
+	pub secure_erase: bool,
+}
+
+impl LibraryAction for FileEncryptAction {
+	type Input = FileEncryptInput;
+	type Output = crate::infra::job::handle::JobReceipt;
+
+	fn from_input(input: Self::Input) -> Result<Self, String> {
+		check_input(&input.targets, &input.password)?;
+
+		Ok(FileEncryptAction {
+			targets: input.targets,
+			password: Protected::new(input.password),
+			secure_erase: input.secure_erase,
+		})
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let job = FileEncryptionJob::new(
+			self.targets,
+			EncryptionMode::Encrypt {
+				secure_erase: self.secure_erase,
+			},
+			self.password,
+		);
+
+		dispatch(library, job).await
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"files.encrypt"
+	}
+}
+
+#[derive(Debug, Clone)]
+pub struct FileDecryptAction {
+	pub targets: SdPathBatch,
+	pub password: Protected<String>,
+}
+
+impl LibraryAction for FileDecryptAction {
+	type Input = FileDecryptInput;
+	type Output = crate::infra::job::handle::JobReceipt;
+
+	fn from_input(input: Self::Input) -> Result<Self, String> {
+		if input.targets.paths.is_empty() {
+			return Err("At least one target file must be specified".to_string());
+		}
+
+		Ok(FileDecryptAction {
+			targets: input.targets,
+			password: Protected::new(input.password),
+		})
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let job = FileEncryptionJob::new(self.targets, EncryptionMode::Decrypt, self.password);
+
+		dispatch(library, job).await
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"files.decrypt"
+	}
+}
+
+crate::register_library_action!(FileEncryptAction, "files.encrypt");
+crate::register_library_action!(FileDecryptAction, "files.decrypt");
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::domain::SdPath;
+
+	fn targets() -> SdPathBatch {
+		SdPathBatch::new(vec![SdPath::local("/tmp/report.pdf")])
+	}
+
+	#[test]
+	fn rejects_short_passwords() {
+		let input = FileEncryptInput {
+			targets: targets(),
+			password: "hunter2".to_string(),
+			secure_erase: false,
+		};
+		assert!(FileEncryptAction::from_input(input).is_err());
+
+		let input = FileEncryptInput {
+			targets: targets(),
+			password: "correct horse battery staple".to_string(),
+			secure_erase: false,
+		};
+		assert!(FileEncryptAction::from_input(input).is_ok());
+	}
+
+	#[test]
+	fn requires_targets() {
+		let input = FileDecryptInput {
+			targets: SdPathBatch::new(vec![]),
+			password: "correct horse battery staple".to_string(),
+		};
+		assert!(FileDecryptAction::from_input(input).is_err());
+	}
+}
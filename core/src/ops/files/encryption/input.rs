@@ -0,0 +1,47 @@
+//! Input types for file encryption operations
+
+use crate::domain::SdPathBatch;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Input for encrypting files in place
+#[derive(Clone, Serialize, Deserialize, Type)]
+pub struct FileEncryptInput {
+	/// Files to encrypt, each one is replaced by `<name>.sdenc`
+	pub targets: SdPathBatch,
+
+	/// Password the encryption key is derived from
+	pub password: String,
+
+	/// Overwrite the original file with random data before removing it
+	#[serde(default)]
+	pub secure_erase: bool,
+}
+
+/// Input for decrypting files encrypted with `files.encrypt`
+#[derive(Clone, Serialize, Deserialize, Type)]
+pub struct FileDecryptInput {
+	/// `.sdenc` files to decrypt, each one is replaced by the original file
+	pub targets: SdPathBatch,
+
+	/// Password the files were encrypted with
+	pub password: String,
+}
+
+// Keep passwords out of logs
+impl std::fmt::Debug for FileEncryptInput {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("FileEncryptInput")
+			.field("targets", &self.targets)
+			.field("secure_erase", &self.secure_erase)
+			.finish_non_exhaustive()
+	}
+}
+
+impl std::fmt::Debug for FileDecryptInput {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_struct("FileDecryptInput")
+			.field("targets", &self.targets)
+			.finish_non_exhaustive()
+	}
+}
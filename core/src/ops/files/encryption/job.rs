@@ -0,0 +1,431 @@
+//! File encryption job implementation
+
+use crate::{
+	domain::addressing::SdPathBatch,
+	infra::job::{generic_progress::GenericProgress, prelude::*},
+};
+use futures::StreamExt;
+use sd_crypto::{
+	cloud::{Argon2Params, Header, Kdf, SecretKey, StreamDecryption, StreamEncryption},
+	erase::erase,
+	CryptoRng, Protected,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+	path::{Path, PathBuf},
+	time::{Duration, Instant},
+};
+use tokio::{
+	fs::{self, File, OpenOptions},
+	io::{AsyncWriteExt, BufReader, BufWriter},
+};
+
+/// Extension given to encrypted files
+pub const ENCRYPTED_EXTENSION: &str = "sdenc";
+
+/// Overwrite passes when securely erasing an original file
+const ERASE_PASSES: usize = 2;
+
+/// Whether the job encrypts or decrypts its targets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EncryptionMode {
+	Encrypt {
+		/// Overwrite originals with random data before removing them
+		secure_erase: bool,
+	},
+	Decrypt,
+}
+
+/// Encrypts or decrypts files in place
+///
+/// The password is never persisted, so the job can't be resumed after a restart.
+#[derive(Debug, Serialize, Deserialize, Job)]
+pub struct FileEncryptionJob {
+	pub targets: SdPathBatch,
+	pub mode: EncryptionMode,
+
+	#[serde(skip)]
+	password: Option<Protected<String>>,
+	#[serde(skip, default = "Instant::now")]
+	started_at: Instant,
+}
+
+impl Job for FileEncryptionJob {
+	const NAME: &'static str = "file_encryption";
+	const RESUMABLE: bool = false;
+	const DESCRIPTION: Option<&'static str> = Some("Encrypt or decrypt files with a password");
+}
+
+impl crate::infra::job::traits::DynJob for FileEncryptionJob {
+	fn job_name(&self) -> &'static str {
+		Self::NAME
+	}
+}
+
+#[async_trait::async_trait]
+impl JobHandler for FileEncryptionJob {
+	type Output = FileEncryptionOutput;
+
+	async fn run(&mut self, ctx: JobContext<'_>) -> JobResult<Self::Output> {
+		let password = self.password.clone().ok_or_else(|| {
+			JobError::execution("The password isn't kept across restarts, run the job again")
+		})?;
+
+		let total_files = self.targets.paths.len() as u64;
+		let verb = match self.mode {
+			EncryptionMode::Encrypt { .. } => "Encrypting",
+			EncryptionMode::Decrypt => "Decrypting",
+		};
+		ctx.log(format!("{} {} files", verb, total_files));
+
+		// Resolve Content paths to Physical paths, only local files can be processed
+		ctx.progress(Progress::Indeterminate("Resolving paths".to_string()));
+		let mut files = Vec::with_capacity(self.targets.paths.len());
+		for path in &self.targets.paths {
+			let resolved = path
+				.resolve_in_job(&ctx)
+				.await
+				.map_err(|e| JobError::execution(format!("Failed to resolve path: {e}")))?;
+
+			match resolved.as_local_path() {
+				Some(local) => files.push(local.to_path_buf()),
+				None => ctx.add_non_critical_error(format!(
+					"{} isn't a local file, skipping it",
+					resolved.display()
+				)),
+			}
+		}
+
+		let mut keys = KeyCache::new(password);
+		let mut processed = Vec::new();
+		let mut failed_count = (self.targets.paths.len() - files.len()) as u64;
+		let mut total_bytes = 0;
+
+		for (index, path) in files.iter().enumerate() {
+			ctx.check_interrupt().await?;
+
+			ctx.progress(Progress::Generic(
+				GenericProgress::new(
+					index as f32 / files.len() as f32,
+					verb,
+					format!("{} {}", verb, path.display()),
+				)
+				.with_completion(index as u64, total_files),
+			));
+
+			let result = match &self.mode {
+				EncryptionMode::Encrypt { secure_erase } => {
+					encrypt_file(path, &mut keys, *secure_erase).await
+				}
+				EncryptionMode::Decrypt => decrypt_file(path, &mut keys).await,
+			};
+
+			match result {
+				Ok((output, bytes)) => {
+					total_bytes += bytes;
+					processed.push(output);
+				}
+				Err(e) => {
+					failed_count += 1;
+					ctx.add_non_critical_error(format!("{}: {}", path.display(), e));
+				}
+			}
+		}
+
+		let processed_count = processed.len();
+		ctx.progress(Progress::Generic(
+			GenericProgress::new(
+				1.0,
+				"Complete",
+				format!("{} processed, {} failed", processed_count, failed_count),
+			)
+			.with_completion(processed_count as u64, total_files)
+			.with_bytes(total_bytes, total_bytes)
+			.with_errors(failed_count, 0),
+		));
+
+		ctx.log(format!(
+			"{} completed: {} processed, {} failed",
+			verb, processed_count, failed_count
+		));
+
+		Ok(FileEncryptionOutput {
+			processed,
+			failed_count: failed_count as usize,
+			total_bytes,
+			duration: self.started_at.elapsed(),
+		})
+	}
+}
+
+impl FileEncryptionJob {
+	pub fn new(targets: SdPathBatch, mode: EncryptionMode, password: Protected<String>) -> Self {
+		Self {
+			targets,
+			mode,
+			password: Some(password),
+			started_at: Instant::now(),
+		}
+	}
+}
+
+/// Derives keys off the async runtime and remembers the last one
+///
+/// Encryption uses the same salt for every file of a job, so the expensive derivation
+/// only runs once, and decrypting files that came from the same job benefits too.
+struct KeyCache {
+	password: Protected<String>,
+	last: Option<(Argon2Params, SecretKey)>,
+}
+
+impl KeyCache {
+	fn new(password: Protected<String>) -> Self {
+		Self {
+			password,
+			last: None,
+		}
+	}
+
+	async fn get(&mut self, params: Argon2Params) -> Result<SecretKey, String> {
+		if let Some((cached, key)) = &self.last {
+			if *cached == params {
+				return Ok(key.clone());
+			}
+		}
+
+		let password = self.password.clone();
+		let key = tokio::task::spawn_blocking(move || params.derive(password.expose().as_bytes()))
+			.await
+			.map_err(|e| format!("Key derivation task failed: {e}"))?
+			.map_err(|e| e.to_string())?;
+
+		self.last = Some((params, key.clone()));
+		Ok(key)
+	}
+
+	/// Parameters for new files, reusing the ones of the last derived key
+	fn params(&self, rng: &mut CryptoRng) -> Argon2Params {
+		self.last
+			.as_ref()
+			.map(|(params, _)| *params)
+			.unwrap_or_else(|| Argon2Params::generate(rng))
+	}
+}
+
+async fn encrypt_file(
+	path: &Path,
+	keys: &mut KeyCache,
+	secure_erase: bool,
+) -> Result<(PathBuf, u64), String> {
+	let metadata = fs::metadata(path).await.map_err(|e| e.to_string())?;
+	if !metadata.is_file() {
+		return Err("only files can be encrypted".to_string());
+	}
+	if is_encrypted_name(path) {
+		return Err("file is already encrypted".to_string());
+	}
+
+	let file_name = file_name(path)?;
+	let output = path.with_file_name(format!("{file_name}.{ENCRYPTED_EXTENSION}"));
+	ensure_missing(&output).await?;
+
+	let mut rng = CryptoRng::new().map_err(|e| e.to_string())?;
+	let params = keys.params(&mut rng);
+	let key = keys.get(params).await?;
+	let header = Header::new(Kdf::Argon2id(params), &mut rng);
+
+	let source = File::open(path).await.map_err(|e| e.to_string())?;
+	let partial = partial_path(&output)?;
+	let result = async {
+		let mut writer = BufWriter::new(File::create(&partial).await?);
+		let mut chunks = std::pin::pin!(key.encrypt(header, BufReader::new(source)));
+		while let Some(chunk) = chunks.next().await {
+			writer
+				.write_all(&chunk.map_err(std::io::Error::other)?)
+				.await?;
+		}
+		writer.flush().await?;
+		writer.into_inner().sync_all().await
+	}
+	.await;
+
+	finish(&partial, &output, result).await?;
+
+	if secure_erase {
+		let mut original = OpenOptions::new()
+			.read(true)
+			.write(true)
+			.open(path)
+			.await
+			.map_err(|e| e.to_string())?;
+		let size = usize::try_from(metadata.len()).map_err(|e| e.to_string())?;
+		erase(&mut original, size, ERASE_PASSES)
+			.await
+			.map_err(|e| format!("failed to erase the original: {e}"))?;
+		original.sync_all().await.map_err(|e| e.to_string())?;
+	}
+	fs::remove_file(path)
+		.await
+		.map_err(|e| format!("encrypted, but failed to remove the original: {e}"))?;
+
+	Ok((output, metadata.len()))
+}
+
+async fn decrypt_file(path: &Path, keys: &mut KeyCache) -> Result<(PathBuf, u64), String> {
+	if !is_encrypted_name(path) {
+		return Err(format!("not a .{ENCRYPTED_EXTENSION} file"));
+	}
+
+	let output = path.with_extension("");
+	ensure_missing(&output).await?;
+
+	let mut reader = BufReader::new(File::open(path).await.map_err(|e| e.to_string())?);
+	let header = Header::read(&mut reader).await.map_err(|e| e.to_string())?;
+	let key = match header.kdf {
+		Kdf::Argon2id(params) => keys.get(params).await?,
+		Kdf::None => return Err("file wasn't encrypted with a password".to_string()),
+	};
+
+	let partial = partial_path(&output)?;
+	let result = async {
+		let mut file = File::create(&partial).await?;
+		let written = key
+			.decrypt(&header, reader, &mut file)
+			.await
+			.map_err(|e| match e {
+				sd_crypto::Error::Decrypt => std::io::Error::other(
+					"wrong password, or the file is corrupted or was tampered with",
+				),
+				e => std::io::Error::other(e),
+			})?;
+		file.sync_all().await?;
+		Ok(written)
+	}
+	.await;
+
+	let written = finish(&partial, &output, result).await?;
+
+	fs::remove_file(path)
+		.await
+		.map_err(|e| format!("decrypted, but failed to remove the encrypted file: {e}"))?;
+
+	Ok((output, written))
+}
+
+/// Move a fully written file in place, or clean up after a failure
+async fn finish<T>(partial: &Path, output: &Path, result: std::io::Result<T>) -> Result<T, String> {
+	match result {
+		Ok(value) => fs::rename(partial, output)
+			.await
+			.map(|()| value)
+			.map_err(|e| e.to_string()),
+		Err(e) => {
+			let _ = fs::remove_file(partial).await;
+			Err(e.to_string())
+		}
+	}
+}
+
+async fn ensure_missing(path: &Path) -> Result<(), String> {
+	if fs::try_exists(path).await.unwrap_or(false) {
+		return Err(format!("{} already exists", path.display()));
+	}
+	Ok(())
+}
+
+fn is_encrypted_name(path: &Path) -> bool {
+	path.extension()
+		.is_some_and(|extension| extension == ENCRYPTED_EXTENSION)
+}
+
+fn file_name(path: &Path) -> Result<&str, String> {
+	path.file_name()
+		.and_then(|name| name.to_str())
+		.ok_or_else(|| "file name isn't valid UTF-8".to_string())
+}
+
+/// Hidden sibling the output is written to before being renamed into place
+fn partial_path(output: &Path) -> Result<PathBuf, String> {
+	Ok(output.with_file_name(format!(".{}.partial", file_name(output)?)))
+}
+
+/// Job output for encryption and decryption
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FileEncryptionOutput {
+	/// Files written by the job
+	pub processed: Vec<PathBuf>,
+	pub failed_count: usize,
+	/// Bytes of plain text processed
+	pub total_bytes: u64,
+	pub duration: Duration,
+}
+
+impl From<FileEncryptionOutput> for JobOutput {
+	fn from(output: FileEncryptionOutput) -> Self {
+		JobOutput::FileEncryption {
+			processed_count: output.processed.len(),
+			failed_count: output.failed_count,
+			total_bytes: output.total_bytes,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	fn cache() -> KeyCache {
+		let mut keys = KeyCache::new(Protected::new("correct horse battery staple".to_string()));
+		// Cheap parameters so the tests don't spend their time in Argon2
+		let mut params = Argon2Params::generate(&mut CryptoRng::new().unwrap());
+		params.memory_kib = 256;
+		params.iterations = 1;
+		params.parallelism = 1;
+		let key = params.derive(b"correct horse battery staple").unwrap();
+		keys.last = Some((params, key));
+		keys
+	}
+
+	#[tokio::test]
+	async fn encrypts_and_decrypts_in_place() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("notes.txt");
+		let contents = b"meeting at noon".repeat(100_000);
+		fs::write(&path, &contents).await.unwrap();
+
+		let mut keys = cache();
+		let (encrypted, bytes) = encrypt_file(&path, &mut keys, true).await.unwrap();
+		assert_eq!(bytes, contents.len() as u64);
+		assert_eq!(encrypted, dir.path().join("notes.txt.sdenc"));
+		assert!(!fs::try_exists(&path).await.unwrap());
+		assert_ne!(fs::read(&encrypted).await.unwrap()[64..], contents[..]);
+
+		assert!(encrypt_file(&encrypted, &mut keys, false).await.is_err());
+
+		let (decrypted, bytes) = decrypt_file(&encrypted, &mut keys).await.unwrap();
+		assert_eq!(decrypted, path);
+		assert_eq!(bytes, contents.len() as u64);
+		assert_eq!(fs::read(&path).await.unwrap(), contents);
+		assert!(!fs::try_exists(&encrypted).await.unwrap());
+	}
+
+	#[tokio::test]
+	async fn keeps_the_encrypted_file_on_a_wrong_password() {
+		let dir = TempDir::new().unwrap();
+		let path = dir.path().join("notes.txt");
+		fs::write(&path, b"meeting at noon").await.unwrap();
+
+		let (encrypted, _) = encrypt_file(&path, &mut cache(), false).await.unwrap();
+
+		let mut wrong = KeyCache::new(Protected::new("battery horse staple correct".to_string()));
+		let err = decrypt_file(&encrypted, &mut wrong).await.unwrap_err();
+		assert!(err.contains("wrong password"));
+
+		assert!(fs::try_exists(&encrypted).await.unwrap());
+		assert!(!fs::try_exists(&path).await.unwrap());
+		assert!(!fs::try_exists(dir.path().join(".notes.txt.partial"))
+			.await
+			.unwrap());
+	}
+}
@@ -0,0 +1,12 @@
+//! File encryption operations
+//!
+//! Encrypts files in place into `<name>.sdenc` using the streaming format of `sd-crypto`,
+//! with a key derived from a password, and reverses it.
+
+pub mod action;
+pub mod input;
+pub mod job;
+
+pub use action::{FileDecryptAction, FileEncryptAction};
+pub use input::{FileDecryptInput, FileEncryptInput};
+pub use job::*;
@@ -3,9 +3,11 @@
 pub mod copy;
 pub mod create_folder;
 pub mod delete;
+pub mod encryption;
 pub mod query;
 pub mod rename;
 
 pub use create_folder::{CreateFolderAction, CreateFolderInput, CreateFolderOutput};
+pub use encryption::{FileDecryptAction, FileDecryptInput, FileEncryptAction, FileEncryptInput};
 pub use query::*;
 pub use rename::{FileRenameAction, FileRenameInput};
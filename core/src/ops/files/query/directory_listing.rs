@@ -12,7 +12,9 @@ use crate::{
 		video_media_data,
 	},
 	infra::query::LibraryQuery,
-	ops::indexing::ephemeral::{cache::EphemeralIndexCache, EphemeralIndex},
+	library::Library,
+	ops::indexing::ephemeral::{archive, cache::EphemeralIndexCache, EphemeralIndex},
+	volume::ArchiveBackend,
 };
 use sea_orm::{
 	ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, JoinType, QueryFilter,
@@ -20,7 +22,7 @@ use sea_orm::{
 };
 use serde::{Deserialize, Serialize};
 use specta::Type;
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 use tokio::sync::RwLock as TokioRwLock;
 use tracing;
 use uuid::Uuid;
@@ -136,6 +138,15 @@ impl LibraryQuery for DirectoryListingQuery {
 			.await
 			.ok_or_else(|| QueryError::Internal("Library not found".to_string()))?;
 
+		// Archives are never in the database, their contents are indexed ephemerally on visit
+		if let Some(local_path) = self.input.path.as_local_path() {
+			if let Some((archive, _)) = ArchiveBackend::locate(local_path) {
+				return self
+					.query_archive_directory_impl(&context, &library, &archive, local_path)
+					.await;
+			}
+		}
+
 		let db = library.db();
 
 		// Check if this path's location has IndexMode::None
@@ -764,6 +775,34 @@ impl DirectoryListingQuery {
 		})
 	}
 
+	/// List a directory inside an archive (or the archive's root)
+	async fn query_archive_directory_impl(
+		&self,
+		context: &Arc<CoreContext>,
+		library: &Arc<Library>,
+		archive: &Path,
+		local_path: &Path,
+	) -> QueryResult<DirectoryListingOutput> {
+		let cache = context.ephemeral_cache();
+		archive::index_archive(cache, archive, library.file_types())
+			.await
+			.map_err(|e| QueryError::Internal(format!("Failed to read archive: {}", e)))?;
+
+		let Some(mut output) = self
+			.read_ephemeral_listing(&cache.get_global_index(), local_path)
+			.await
+		else {
+			return Err(QueryError::Internal(format!(
+				"{} not found in {}",
+				local_path.display(),
+				archive.display()
+			)));
+		};
+
+		archive::identify_archive_members(context, library, &mut output.files).await;
+		Ok(output)
+	}
+
 	/// Read children from the ephemeral index and convert to a DirectoryListingOutput.
 	///
 	/// Shared by all ephemeral code paths (cache hit, wait-for-indexing, post-dispatch).
@@ -0,0 +1,116 @@
+//! Add custom file type action handler
+
+use super::reidentify::FileTypeReidentifyJob;
+use crate::{
+	context::CoreContext,
+	filetype::CustomFileType,
+	infra::{
+		action::{error::ActionError, LibraryAction, ValidationResult},
+		job::handle::JobReceipt,
+	},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use tracing::info;
+
+fn default_true() -> bool {
+	true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileTypeAddInput {
+	pub file_type: CustomFileType,
+	/// Identify files the new type may match again
+	#[serde(default = "default_true")]
+	pub reidentify: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileTypeAddOutput {
+	/// The definition as stored, with normalized extensions
+	pub file_type: CustomFileType,
+	/// Re-identification job, if one was started
+	pub job: Option<JobReceipt>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileTypeAddAction {
+	pub file_type: CustomFileType,
+	pub reidentify: bool,
+}
+
+impl LibraryAction for FileTypeAddAction {
+	type Input = FileTypeAddInput;
+	type Output = FileTypeAddOutput;
+
+	fn from_input(input: Self::Input) -> Result<Self, String> {
+		Ok(FileTypeAddAction {
+			file_type: input.file_type.normalized()?,
+			reidentify: input.reidentify,
+		})
+	}
+
+	async fn validate(
+		&self,
+		library: &Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> Result<ValidationResult, ActionError> {
+		// Built-in types can't be replaced, a higher priority overrides them instead
+		if library.file_types().get(&self.file_type.id).is_some() {
+			return Err(ActionError::Validation {
+				field: "file_type".to_string(),
+				message: format!("A file type with id '{}' already exists", self.file_type.id),
+			});
+		}
+
+		Ok(ValidationResult::Success { metadata: None })
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let file_type = self.file_type;
+
+		library
+			.update_config(|config| {
+				config.settings.custom_file_types.push(file_type.clone());
+			})
+			.await
+			.map_err(|e| ActionError::Internal(format!("Failed to update config: {}", e)))?;
+		library.reload_file_types(&library.config().await.settings.custom_file_types);
+
+		info!(
+			library_id = %library.id(),
+			file_type = %file_type.id,
+			"Custom file type added"
+		);
+
+		let job = if self.reidentify {
+			// Magic bytes can identify files whatever their extension, the ones no type
+			// matched before are the candidates
+			let job_handle = library
+				.jobs()
+				.dispatch(FileTypeReidentifyJob {
+					extensions: file_type.extensions.clone(),
+					mime_types: Vec::new(),
+					include_unknown: !file_type.magic_bytes.is_empty(),
+				})
+				.await
+				.map_err(ActionError::Job)?;
+			Some(job_handle.into())
+		} else {
+			None
+		};
+
+		Ok(FileTypeAddOutput { file_type, job })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"filetypes.add"
+	}
+}
+
+crate::register_library_action!(FileTypeAddAction, "filetypes.add");
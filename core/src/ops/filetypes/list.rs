@@ -0,0 +1,70 @@
+//! List custom file types query
+
+use crate::{
+	context::CoreContext,
+	filetype::{CustomFileType, CustomFileTypeSource},
+	infra::query::{LibraryQuery, QueryError, QueryResult},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileTypeListInput {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CustomFileTypeInfo {
+	pub file_type: CustomFileType,
+	pub source: CustomFileTypeSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileTypeListOutput {
+	/// Custom types from the app data directory and the library, sorted by id
+	pub file_types: Vec<CustomFileTypeInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileTypeListQuery {
+	pub input: FileTypeListInput,
+}
+
+impl LibraryQuery for FileTypeListQuery {
+	type Input = FileTypeListInput;
+	type Output = FileTypeListOutput;
+
+	fn from_input(input: Self::Input) -> QueryResult<Self> {
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		context: Arc<CoreContext>,
+		session: crate::infra::api::SessionContext,
+	) -> QueryResult<Self::Output> {
+		let library_id = session
+			.current_library_id
+			.ok_or_else(|| QueryError::Internal("No library selected".to_string()))?;
+
+		let library = context
+			.libraries()
+			.await
+			.get_library(library_id)
+			.await
+			.ok_or_else(|| QueryError::Internal("Library not found".to_string()))?;
+
+		let file_types = library
+			.file_types()
+			.custom_types()
+			.into_iter()
+			.map(|(file_type, source)| CustomFileTypeInfo {
+				file_type: CustomFileType::from(file_type),
+				source,
+			})
+			.collect();
+
+		Ok(FileTypeListOutput { file_types })
+	}
+}
+
+crate::register_library_query!(FileTypeListQuery, "filetypes.list");
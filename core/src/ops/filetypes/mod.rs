@@ -0,0 +1,15 @@
+//! User-defined file types
+//!
+//! Adds and removes the custom file types stored in a library's settings, lists every
+//! custom type the library sees, and identifies existing files again once the
+//! definitions change. Types from the app data directory are listed but edited as files.
+
+pub mod add;
+pub mod list;
+pub mod reidentify;
+pub mod remove;
+
+pub use add::{FileTypeAddAction, FileTypeAddInput, FileTypeAddOutput};
+pub use list::{CustomFileTypeInfo, FileTypeListInput, FileTypeListOutput, FileTypeListQuery};
+pub use reidentify::{FileTypeReidentifyJob, FileTypeReidentifyOutput};
+pub use remove::{FileTypeRemoveAction, FileTypeRemoveInput, FileTypeRemoveOutput};
@@ -0,0 +1,319 @@
+//! Re-identify files after the library's file types change
+
+use crate::{
+	domain::ContentKind,
+	filetype::FileTypeError,
+	infra::{
+		db::entities::{content_identity, entry, mime_type, volume},
+		job::{generic_progress::GenericProgress, prelude::*},
+		sync::ChangeType,
+	},
+	ops::indexing::PathResolver,
+};
+use sea_orm::{
+	ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait, JoinType,
+	QueryFilter, QuerySelect, RelationTrait, Set,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use uuid::Uuid;
+
+/// Content identities updated before their changes are synced
+const SYNC_BATCH_SIZE: usize = 100;
+
+/// Identifies again the files a file type definition may now match, or no longer does
+///
+/// Content identities store the kind and MIME type found when the content was first
+/// seen, so adding or removing a custom type only affects new files until this runs.
+#[derive(Debug, Serialize, Deserialize, Job)]
+pub struct FileTypeReidentifyJob {
+	/// Files with these extensions
+	pub extensions: Vec<String>,
+	/// Files identified as one of these MIME types
+	pub mime_types: Vec<String>,
+	/// Files whose kind is unknown, which magic bytes may now identify
+	pub include_unknown: bool,
+}
+
+impl Job for FileTypeReidentifyJob {
+	const NAME: &'static str = "file_type_reidentify";
+	const RESUMABLE: bool = true;
+	const DESCRIPTION: Option<&'static str> =
+		Some("Identify files again after their file type definitions changed");
+}
+
+impl crate::infra::job::traits::DynJob for FileTypeReidentifyJob {
+	fn job_name(&self) -> &'static str {
+		Self::NAME
+	}
+}
+
+#[async_trait::async_trait]
+impl JobHandler for FileTypeReidentifyJob {
+	type Output = FileTypeReidentifyOutput;
+
+	async fn run(&mut self, ctx: JobContext<'_>) -> JobResult<Self::Output> {
+		let db = ctx.library_db();
+		let registry = ctx.library().file_types();
+
+		ctx.progress(Progress::Indeterminate(
+			"Finding files to identify".to_string(),
+		));
+		let targets = self.find_targets(db).await?;
+		let total = targets.len() as u64;
+		ctx.log(format!("Identifying {} files again", total));
+
+		let mut output = FileTypeReidentifyOutput::default();
+		let mut mime_types_to_sync = Vec::new();
+		let mut content_identities_to_sync = Vec::new();
+
+		for (index, (entry_id, content_id)) in targets.into_iter().enumerate() {
+			ctx.check_interrupt().await?;
+
+			if index % 50 == 0 {
+				ctx.progress(Progress::Generic(
+					GenericProgress::new(
+						index as f32 / total.max(1) as f32,
+						"Identifying",
+						format!("Identifying file {} of {}", index + 1, total),
+					)
+					.with_completion(index as u64, total),
+				));
+			}
+
+			output.checked_count += 1;
+
+			let path = match PathResolver::get_full_path(db, entry_id).await {
+				Ok(path) => path,
+				Err(e) => {
+					ctx.add_non_critical_error(format!(
+						"Failed to resolve the path of entry {}: {}",
+						entry_id, e
+					));
+					output.skipped_count += 1;
+					continue;
+				}
+			};
+
+			let (kind, mime) = match registry.identify(&path).await {
+				Ok(result) => (
+					result.file_type.category,
+					result.file_type.primary_mime_type().map(str::to_string),
+				),
+				Err(FileTypeError::UnknownType) => (ContentKind::Unknown, None),
+				Err(_) => {
+					output.skipped_count += 1;
+					continue;
+				}
+			};
+
+			let Some(content) = content_identity::Entity::find_by_id(content_id)
+				.one(db)
+				.await?
+			else {
+				output.skipped_count += 1;
+				continue;
+			};
+
+			let mime_type_id = match &mime {
+				Some(mime) => {
+					let (model, is_new) = find_or_create_mime_type(db, mime).await?;
+					let id = model.id;
+					if is_new {
+						mime_types_to_sync.push(model);
+					}
+					Some(id)
+				}
+				None => None,
+			};
+
+			if content.kind_id == kind as i32 && content.mime_type_id == mime_type_id {
+				continue;
+			}
+
+			let mut active: content_identity::ActiveModel = content.into();
+			active.kind_id = Set(kind as i32);
+			active.mime_type_id = Set(mime_type_id);
+			content_identities_to_sync.push(active.update(db).await?);
+			output.updated_count += 1;
+
+			if content_identities_to_sync.len() >= SYNC_BATCH_SIZE {
+				sync_changes(
+					&ctx,
+					&mut mime_types_to_sync,
+					&mut content_identities_to_sync,
+				)
+				.await;
+			}
+		}
+
+		sync_changes(
+			&ctx,
+			&mut mime_types_to_sync,
+			&mut content_identities_to_sync,
+		)
+		.await;
+
+		ctx.progress(Progress::Generic(
+			GenericProgress::new(
+				1.0,
+				"Complete",
+				format!(
+					"{} files checked, {} updated",
+					output.checked_count, output.updated_count
+				),
+			)
+			.with_completion(output.checked_count as u64, total),
+		));
+
+		ctx.log(format!(
+			"File type re-identification completed: {} checked, {} updated, {} skipped",
+			output.checked_count, output.updated_count, output.skipped_count
+		));
+
+		Ok(output)
+	}
+}
+
+impl FileTypeReidentifyJob {
+	/// One file per content identity that may be affected, as (entry ID, content ID)
+	///
+	/// Only files on this device's local volumes are considered, the others can't be read.
+	async fn find_targets(&self, db: &DatabaseConnection) -> JobResult<Vec<(i32, i32)>> {
+		let local_volume_ids: Vec<i32> = volume::Entity::find()
+			.select_only()
+			.column(volume::Column::Id)
+			.filter(volume::Column::DeviceId.eq(crate::device::get_current_device_id()))
+			.filter(volume::Column::CloudIdentifier.is_null())
+			.into_tuple()
+			.all(db)
+			.await?;
+		if local_volume_ids.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		let mut condition = Condition::any();
+		if !self.extensions.is_empty() {
+			condition = condition.add(entry::Column::Extension.is_in(self.extensions.clone()));
+		}
+		if !self.mime_types.is_empty() {
+			let mime_type_ids: Vec<i32> = mime_type::Entity::find()
+				.filter(mime_type::Column::MimeType.is_in(self.mime_types.clone()))
+				.all(db)
+				.await?
+				.into_iter()
+				.map(|model| model.id)
+				.collect();
+			if !mime_type_ids.is_empty() {
+				condition =
+					condition.add(content_identity::Column::MimeTypeId.is_in(mime_type_ids));
+			}
+		}
+		if self.include_unknown {
+			condition =
+				condition.add(content_identity::Column::KindId.eq(ContentKind::Unknown as i32));
+		}
+
+		if condition.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		let rows: Vec<(i32, Option<i32>)> = entry::Entity::find()
+			.select_only()
+			.column(entry::Column::Id)
+			.column(entry::Column::ContentId)
+			.join(JoinType::InnerJoin, entry::Relation::ContentIdentity.def())
+			.filter(entry::Column::Kind.eq(0))
+			.filter(entry::Column::VolumeId.is_in(local_volume_ids))
+			.filter(condition)
+			.into_tuple()
+			.all(db)
+			.await?;
+
+		let mut seen = HashSet::new();
+		Ok(rows
+			.into_iter()
+			.filter_map(|(entry_id, content_id)| Some((entry_id, content_id?)))
+			.filter(|(_, content_id)| seen.insert(*content_id))
+			.collect())
+	}
+}
+
+async fn find_or_create_mime_type(
+	db: &DatabaseConnection,
+	mime: &str,
+) -> JobResult<(mime_type::Model, bool)> {
+	if let Some(existing) = mime_type::Entity::find()
+		.filter(mime_type::Column::MimeType.eq(mime))
+		.one(db)
+		.await?
+	{
+		return Ok((existing, false));
+	}
+
+	let model = mime_type::ActiveModel {
+		uuid: Set(Uuid::new_v4()),
+		mime_type: Set(mime.to_string()),
+		created_at: Set(chrono::Utc::now()),
+		..Default::default()
+	}
+	.insert(db)
+	.await?;
+
+	Ok((model, true))
+}
+
+/// Sync new MIME types, then the content identities referencing them
+async fn sync_changes(
+	ctx: &JobContext<'_>,
+	mime_types: &mut Vec<mime_type::Model>,
+	content_identities: &mut Vec<content_identity::Model>,
+) {
+	let library = ctx.library();
+
+	if let Err(e) = library
+		.sync_models_batch(mime_types.as_slice(), ChangeType::Insert, ctx.library_db())
+		.await
+	{
+		tracing::warn!("Failed to sync {} mime types: {}", mime_types.len(), e);
+	}
+	mime_types.clear();
+
+	// Let the mime types reach peers before the content identities pointing at them
+	tokio::task::yield_now().await;
+
+	if let Err(e) = library
+		.sync_models_batch(
+			content_identities.as_slice(),
+			ChangeType::Update,
+			ctx.library_db(),
+		)
+		.await
+	{
+		tracing::warn!(
+			"Failed to sync {} content identities: {}",
+			content_identities.len(),
+			e
+		);
+	}
+	content_identities.clear();
+}
+
+/// Job output for file type re-identification
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct FileTypeReidentifyOutput {
+	pub checked_count: usize,
+	pub updated_count: usize,
+	/// Files that couldn't be read or resolved
+	pub skipped_count: usize,
+}
+
+impl From<FileTypeReidentifyOutput> for JobOutput {
+	fn from(output: FileTypeReidentifyOutput) -> Self {
+		JobOutput::FileTypeReidentify {
+			checked_count: output.checked_count,
+			updated_count: output.updated_count,
+			skipped_count: output.skipped_count,
+		}
+	}
+}
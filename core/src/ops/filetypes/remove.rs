@@ -0,0 +1,143 @@
+//! Remove custom file type action handler
+
+use super::reidentify::FileTypeReidentifyJob;
+use crate::{
+	context::CoreContext,
+	filetype::{custom::CUSTOM_DEFINITIONS_DIR, CustomFileTypeSource},
+	infra::{
+		action::{error::ActionError, LibraryAction, ValidationResult},
+		job::handle::JobReceipt,
+	},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use tracing::info;
+
+fn default_true() -> bool {
+	true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileTypeRemoveInput {
+	pub id: String,
+	/// Identify the files the type matched again
+	#[serde(default = "default_true")]
+	pub reidentify: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct FileTypeRemoveOutput {
+	pub id: String,
+	/// Re-identification job, if one was started
+	pub job: Option<JobReceipt>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FileTypeRemoveAction {
+	pub id: String,
+	pub reidentify: bool,
+}
+
+impl LibraryAction for FileTypeRemoveAction {
+	type Input = FileTypeRemoveInput;
+	type Output = FileTypeRemoveOutput;
+
+	fn from_input(input: Self::Input) -> Result<Self, String> {
+		Ok(FileTypeRemoveAction {
+			id: input.id.trim().to_string(),
+			reidentify: input.reidentify,
+		})
+	}
+
+	async fn validate(
+		&self,
+		library: &Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> Result<ValidationResult, ActionError> {
+		let file_types = library.file_types();
+		let source = file_types
+			.custom_types()
+			.into_iter()
+			.find(|(file_type, _)| file_type.id == self.id)
+			.map(|(_, source)| source);
+
+		let message = match source {
+			Some(CustomFileTypeSource::Library) => {
+				return Ok(ValidationResult::Success { metadata: None })
+			}
+			Some(CustomFileTypeSource::AppData) => format!(
+				"File type '{}' is defined in the '{}' directory of the app data directory, \
+				 remove it from there",
+				self.id, CUSTOM_DEFINITIONS_DIR
+			),
+			None if file_types.is_builtin(&self.id) => {
+				format!("File type '{}' is built in and can't be removed", self.id)
+			}
+			None => format!("No custom file type with id '{}'", self.id),
+		};
+
+		Err(ActionError::Validation {
+			field: "id".to_string(),
+			message,
+		})
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let mut removed = None;
+
+		library
+			.update_config(|config| {
+				let custom_types = &mut config.settings.custom_file_types;
+				if let Some(index) = custom_types.iter().position(|ty| ty.id == self.id) {
+					removed = Some(custom_types.remove(index));
+				}
+			})
+			.await
+			.map_err(|e| ActionError::Internal(format!("Failed to update config: {}", e)))?;
+
+		let removed = removed.ok_or_else(|| ActionError::Validation {
+			field: "id".to_string(),
+			message: format!("No custom file type with id '{}'", self.id),
+		})?;
+		library.reload_file_types(&library.config().await.settings.custom_file_types);
+
+		info!(
+			library_id = %library.id(),
+			file_type = %removed.id,
+			"Custom file type removed"
+		);
+
+		let job = if self.reidentify {
+			let mime_types = if removed.mime_types.is_empty() {
+				vec![removed.id.clone()]
+			} else {
+				removed.mime_types
+			};
+			let job_handle = library
+				.jobs()
+				.dispatch(FileTypeReidentifyJob {
+					extensions: removed.extensions,
+					mime_types,
+					include_unknown: false,
+				})
+				.await
+				.map_err(ActionError::Job)?;
+			Some(job_handle.into())
+		} else {
+			None
+		};
+
+		Ok(FileTypeRemoveOutput { id: self.id, job })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"filetypes.remove"
+	}
+}
+
+crate::register_library_action!(FileTypeRemoveAction, "filetypes.remove");
@@ -5,10 +5,12 @@
 //! (in-memory) handlers implement this trait.
 
 use super::types::{ChangeConfig, ChangeType, EntryRef};
-use crate::ops::indexing::rules::{build_default_ruler, RuleToggles, RulerDecision};
+use crate::ops::indexing::rules::{
+	LocationRules, RuleToggles, RulerDecision, SPACEDRIVE_IGNORE_FILE,
+};
 use crate::ops::indexing::state::{DirEntry, EntryKind};
 use anyhow::Result;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 /// Abstracts storage operations for filesystem change handling.
@@ -115,10 +117,11 @@ pub async fn path_exists_safe(
 pub async fn should_filter_path(
 	path: &Path,
 	rule_toggles: RuleToggles,
-	location_root: &Path,
+	location_rules: &LocationRules,
 	backend: Option<&Arc<dyn crate::volume::VolumeBackend>>,
 ) -> Result<bool> {
-	let ruler = build_default_ruler(rule_toggles, location_root, path).await;
+	let dir = path.parent().unwrap_or(path);
+	let ruler = location_rules.ruler(rule_toggles, dir).await;
 
 	let metadata = if let Some(backend) = backend {
 		backend
@@ -144,20 +147,14 @@ pub async fn should_filter_path(
 		}
 	};
 
-	struct SimpleMetadata {
-		is_dir: bool,
-	}
-	impl crate::ops::indexing::rules::MetadataForIndexerRules for SimpleMetadata {
-		fn is_dir(&self) -> bool {
-			self.is_dir
-		}
-	}
-
-	let simple_meta = SimpleMetadata {
-		is_dir: metadata.kind == EntryKind::Directory,
-	};
+	let rule_meta = location_rules.metadata(
+		path,
+		metadata.kind == EntryKind::Directory,
+		Some(metadata.size),
+		metadata.modified,
+	);
 
-	match ruler.evaluate_path(path, &simple_meta).await {
+	match ruler.evaluate_path(path, &rule_meta).await {
 		Ok(RulerDecision::Reject) => {
 			tracing::debug!("Filtered path by indexing rules: {}", path.display());
 			Ok(true)
@@ -216,6 +213,14 @@ pub async fn apply_batch<H: ChangeHandler>(
 		}
 	}
 
+	let ignore_dirs = changed_ignore_dirs(
+		creates
+			.iter()
+			.chain(&modifies)
+			.chain(&removes)
+			.chain(renames.iter().flat_map(|(from, to)| [from, to])),
+	);
+
 	// Deduplicate
 	creates.sort();
 	creates.dedup();
@@ -262,6 +267,88 @@ pub async fn apply_batch<H: ChangeHandler>(
 		}
 	}
 
+	for dir in ignore_dirs {
+		if let Err(e) = reapply_rules(handler, &dir, config).await {
+			tracing::error!("Failed to re-evaluate rules below {}: {}", dir.display(), e);
+		}
+	}
+
+	Ok(())
+}
+
+/// Directories whose ignore file changed, without those nested in another one.
+fn changed_ignore_dirs<'a>(paths: impl Iterator<Item = &'a PathBuf>) -> Vec<PathBuf> {
+	let mut dirs = paths
+		.filter(|path| {
+			path.file_name()
+				.is_some_and(|name| name == SPACEDRIVE_IGNORE_FILE)
+		})
+		.filter_map(|path| path.parent().map(Path::to_path_buf))
+		.collect::<Vec<_>>();
+	dirs.sort();
+	dirs.dedup();
+
+	let mut outermost: Vec<PathBuf> = Vec::with_capacity(dirs.len());
+	for dir in dirs {
+		if !outermost.iter().any(|parent| dir.starts_with(parent)) {
+			outermost.push(dir);
+		}
+	}
+	outermost
+}
+
+/// Re-evaluate the rules for everything below `dir` after its ignore rules changed.
+///
+/// Entries the rules now reject are removed from the index, and entries they no
+/// longer reject are added back. A directory that is added back gets its subtree
+/// indexed through [`ChangeHandler::handle_new_directory`], which spawns an indexer
+/// job for persistent locations.
+pub async fn reapply_rules<H: ChangeHandler>(
+	handler: &mut H,
+	dir: &Path,
+	config: &ChangeConfig<'_>,
+) -> Result<()> {
+	tracing::debug!("Re-evaluating rules below {}", dir.display());
+
+	let mut dirs = vec![dir.to_path_buf()];
+	while let Some(current) = dirs.pop() {
+		let ruler = config
+			.location_rules
+			.ruler(config.rule_toggles, &current)
+			.await;
+
+		let mut read_dir = match tokio::fs::read_dir(&current).await {
+			Ok(read_dir) => read_dir,
+			Err(e) => {
+				tracing::warn!("Failed to read directory {}: {}", current.display(), e);
+				continue;
+			}
+		};
+
+		while let Some(child) = read_dir.next_entry().await? {
+			let path = child.path();
+			let child_meta = child.metadata().await.ok();
+			let rule_meta = config.location_rules.metadata(
+				&path,
+				child_meta.as_ref().map_or(false, |m| m.is_dir()),
+				child_meta.as_ref().map(|m| m.len()),
+				child_meta.as_ref().and_then(|m| m.modified().ok()),
+			);
+
+			let rejected = matches!(
+				ruler.evaluate_path(&path, &rule_meta).await,
+				Ok(RulerDecision::Reject)
+			);
+
+			match (rejected, handler.find_by_path(&path).await?) {
+				(true, Some(_)) => handle_remove(handler, &path).await?,
+				(false, None) => handle_create(handler, &path, config).await?,
+				(false, Some(entry)) if entry.is_directory() => dirs.push(path),
+				_ => {}
+			}
+		}
+	}
+
 	Ok(())
 }
 
@@ -292,7 +379,7 @@ pub async fn handle_create<H: ChangeHandler>(
 	if should_filter_path(
 		path,
 		config.rule_toggles,
-		config.location_root,
+		config.location_rules,
 		config.volume_backend,
 	)
 	.await?
@@ -379,7 +466,7 @@ pub async fn handle_modify<H: ChangeHandler>(
 	if should_filter_path(
 		path,
 		config.rule_toggles,
-		config.location_root,
+		config.location_rules,
 		config.volume_backend,
 	)
 	.await?
@@ -486,7 +573,7 @@ pub async fn handle_rename<H: ChangeHandler>(
 	if should_filter_path(
 		to,
 		config.rule_toggles,
-		config.location_root,
+		config.location_rules,
 		config.volume_backend,
 	)
 	.await?
@@ -513,3 +600,142 @@ pub async fn handle_rename<H: ChangeHandler>(
 
 	Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ops::indexing::rules::{IndexerRule, RulePerKind};
+	use chrono::Utc;
+	use std::collections::HashMap;
+	use std::sync::Mutex;
+
+	/// Handler keeping entries in memory and recording the directories it was asked to index
+	#[derive(Default)]
+	struct RecordingHandler {
+		entries: HashMap<PathBuf, EntryRef>,
+		indexed_dirs: Mutex<Vec<PathBuf>>,
+	}
+
+	impl RecordingHandler {
+		async fn insert(&mut self, path: PathBuf) {
+			let metadata = build_dir_entry(&path, None).await.unwrap();
+			let parent = path.parent().unwrap().to_path_buf();
+			self.create(&metadata, &parent).await.unwrap();
+		}
+	}
+
+	#[async_trait::async_trait]
+	impl ChangeHandler for RecordingHandler {
+		async fn find_by_path(&self, path: &Path) -> Result<Option<EntryRef>> {
+			Ok(self.entries.get(path).cloned())
+		}
+
+		async fn find_by_inode(&self, _inode: u64) -> Result<Option<EntryRef>> {
+			Ok(None)
+		}
+
+		async fn create(&mut self, metadata: &DirEntry, _parent_path: &Path) -> Result<EntryRef> {
+			let entry = EntryRef {
+				id: self.entries.len() as i32,
+				uuid: None,
+				path: metadata.path.clone(),
+				kind: metadata.kind,
+			};
+			self.entries.insert(metadata.path.clone(), entry.clone());
+			Ok(entry)
+		}
+
+		async fn update(&mut self, _entry: &EntryRef, _metadata: &DirEntry) -> Result<()> {
+			Ok(())
+		}
+
+		async fn move_entry(
+			&mut self,
+			_entry: &EntryRef,
+			_old_path: &Path,
+			_new_path: &Path,
+			_new_parent_path: &Path,
+		) -> Result<()> {
+			unreachable!("rules never move entries")
+		}
+
+		async fn delete(&mut self, entry: &EntryRef) -> Result<()> {
+			self.entries
+				.retain(|path, _| !path.starts_with(&entry.path));
+			Ok(())
+		}
+
+		async fn run_processors(&self, _entry: &EntryRef, _is_new: bool) -> Result<()> {
+			Ok(())
+		}
+
+		async fn emit_change_event(
+			&self,
+			_entry: &EntryRef,
+			_change_type: ChangeType,
+		) -> Result<()> {
+			Ok(())
+		}
+
+		async fn handle_new_directory(&self, path: &Path) -> Result<()> {
+			self.indexed_dirs.lock().unwrap().push(path.to_path_buf());
+			Ok(())
+		}
+	}
+
+	#[tokio::test]
+	async fn reapply_rules_indexes_newly_included_subtrees() {
+		let temp = tempfile::TempDir::new().unwrap();
+		let root = temp.path().to_path_buf();
+		for dir in ["kept", "photos", "photos/2024", "old"] {
+			tokio::fs::create_dir_all(root.join(dir)).await.unwrap();
+		}
+		for file in ["kept/a.txt", "photos/2024/b.jpg", "old/c.txt", "debug.log"] {
+			tokio::fs::write(root.join(file), file).await.unwrap();
+		}
+
+		// `photos` and `debug.log` were ignored when the location was indexed
+		let mut handler = RecordingHandler::default();
+		for path in ["kept", "kept/a.txt", "old", "old/c.txt"] {
+			handler.insert(root.join(path)).await;
+		}
+
+		// The ignore file now excludes `old` instead, while the location still rejects logs
+		tokio::fs::write(root.join(SPACEDRIVE_IGNORE_FILE), "old/\n")
+			.await
+			.unwrap();
+		let location_rules = LocationRules {
+			root: root.clone(),
+			rules: vec![IndexerRule {
+				id: None,
+				name: "No logs".to_string(),
+				default: false,
+				rules: vec![RulePerKind::new_reject_files_by_globs_str(["*.log"]).unwrap()],
+				date_created: Utc::now(),
+				date_modified: Utc::now(),
+			}],
+			file_types: None,
+		};
+		let config = ChangeConfig {
+			rule_toggles: RuleToggles::default(),
+			location_root: &root,
+			location_rules: &location_rules,
+			volume_backend: None,
+		};
+
+		reapply_rules(&mut handler, &root, &config).await.unwrap();
+
+		assert!(handler.entries.contains_key(&root.join("kept/a.txt")));
+		assert!(!handler.entries.contains_key(&root.join("old")));
+		assert!(!handler.entries.contains_key(&root.join("old/c.txt")));
+		assert!(!handler.entries.contains_key(&root.join("debug.log")));
+
+		// The new directory is added and its subtree left to an indexing job
+		assert!(handler.entries.contains_key(&root.join("photos")));
+		assert!(!handler.entries.contains_key(&root.join("photos/2024")));
+		assert_eq!(
+			*handler.indexed_dirs.lock().unwrap(),
+			vec![root.join("photos")]
+		);
+	}
+}
@@ -19,7 +19,7 @@ pub mod types;
 pub use detector::ChangeDetector;
 pub use handler::{
 	apply_batch, build_dir_entry, handle_create, handle_modify, handle_remove, handle_rename,
-	path_exists_safe, should_filter_path, ChangeHandler,
+	path_exists_safe, reapply_rules, should_filter_path, ChangeHandler,
 };
 pub use persistent::{DatabaseAdapter, DatabaseAdapterForJob};
 pub use types::{Change, ChangeConfig, ChangeMetadata, ChangeType, EntryRef};
@@ -8,9 +8,11 @@
 use super::handler::ChangeHandler;
 use super::types::{ChangeType, EntryRef};
 use crate::context::CoreContext;
+use crate::filetype::FileTypeRegistry;
 use crate::infra::db::entities;
 use crate::infra::job::prelude::{JobContext, JobError, JobResult};
 use crate::ops::indexing::persistence::IndexPersistence;
+use crate::ops::indexing::rules::LocationRules;
 use crate::ops::indexing::state::{DirEntry, EntryKind};
 use anyhow::Result;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter, TransactionTrait};
@@ -31,9 +33,11 @@ pub struct DatabaseAdapter {
 	context: Arc<CoreContext>,
 	library_id: Uuid,
 	location_id: Uuid,
+	location_db_id: i32,
 	location_root_entry_id: i32,
 	volume_id: i32,
 	db: sea_orm::DatabaseConnection,
+	file_types: Arc<FileTypeRegistry>,
 	volume_backend: Option<Arc<dyn crate::volume::VolumeBackend>>,
 	entry_id_cache: HashMap<PathBuf, i32>,
 }
@@ -74,14 +78,27 @@ impl DatabaseAdapter {
 			context,
 			library_id,
 			location_id,
+			location_db_id: location_record.id,
 			location_root_entry_id,
 			volume_id,
 			db,
+			file_types: library.file_types(),
 			volume_backend,
 			entry_id_cache: HashMap::new(),
 		})
 	}
 
+	/// Indexer rules attached to this location, rooted at `location_root`.
+	pub async fn location_rules(&self, location_root: &Path) -> Result<LocationRules> {
+		Ok(LocationRules::load(
+			&self.db,
+			self.location_db_id,
+			location_root,
+			self.file_types.clone(),
+		)
+		.await?)
+	}
+
 	async fn resolve_entry_id(&self, path: &Path) -> Result<Option<i32>> {
 		if let Some(id) = self.resolve_directory_entry_id(path).await? {
 			return Ok(Some(id));
@@ -405,13 +422,11 @@ impl ChangeHandler for DatabaseAdapter {
 		use crate::ops::indexing::processor::{
 			load_location_processor_config, ContentHashProcessor, ProcessorEntry,
 		};
-		use crate::ops::media::{ocr::OcrProcessor, proxy::ProxyProcessor};
-		#[cfg(feature = "ffmpeg")]
-		use crate::ops::media::{
-			thumbnail::ThumbnailProcessor, thumbstrip::ThumbstripProcessor,
-		};
 		#[cfg(feature = "speech-to-text")]
 		use crate::ops::media::speech::SpeechToTextProcessor;
+		use crate::ops::media::{ocr::OcrProcessor, proxy::ProxyProcessor};
+		#[cfg(feature = "ffmpeg")]
+		use crate::ops::media::{thumbnail::ThumbnailProcessor, thumbstrip::ThumbstripProcessor};
 
 		if entry.is_directory() {
 			return Ok(());
@@ -483,7 +498,7 @@ impl ChangeHandler for DatabaseAdapter {
 			let proc_entry = build_proc_entry(&self.db, entry).await?;
 			let content_proc = ContentHashProcessor::new(self.library_id);
 			if let Err(e) = content_proc
-				.process(&self.db, &proc_entry, self.context.file_type_registry())
+				.process(&self.db, &proc_entry, &library.file_types())
 				.await
 			{
 				tracing::warn!("Content hash processing failed: {}", e);
@@ -800,7 +815,7 @@ impl<'a> IndexPersistence for DatabaseAdapterForJob<'a> {
 			entry_id,
 			path,
 			cas_id,
-			self.ctx.library().core_context().file_type_registry(),
+			&self.ctx.library().file_types(),
 		)
 		.await
 		.map(|_| ())
@@ -131,5 +131,6 @@ impl EntryRef {
 pub struct ChangeConfig<'a> {
 	pub rule_toggles: crate::ops::indexing::rules::RuleToggles,
 	pub location_root: &'a std::path::Path,
+	pub location_rules: &'a crate::ops::indexing::rules::LocationRules,
 	pub volume_backend: Option<&'a std::sync::Arc<dyn crate::volume::VolumeBackend>>,
 }
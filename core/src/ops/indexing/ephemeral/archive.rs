@@ -0,0 +1,552 @@
+//! # Archive Contents
+//!
+//! Archives are browsed like directories. The members of `/photos/trip.zip` are added
+//! to the global ephemeral index under `/photos/trip.zip/...`, below the archive's own
+//! file entry, so directory listings and search work on them unchanged.
+//!
+//! An archive is listed whole on first visit, from the central directory of zips, the
+//! header of 7z archives and the member headers of tars. Nothing is hashed while the
+//! listing is waited on: file contents are hashed afterwards in one background pass and
+//! the hashes kept in the cache, which gives members content identities (and through
+//! those, thumbnails) from the next listing on. The archive's size and modification time
+//! are remembered, so a changed archive is listed again.
+
+use super::EphemeralIndexCache;
+use crate::{
+	context::CoreContext,
+	domain::{
+		content_identity::{ContentHashError, ContentHashGenerator, ContentIdentity},
+		file::{EntryKind as FileKind, File, Sidecar},
+	},
+	filetype::FileTypeRegistry,
+	infra::db::entities::{content_identity, sidecar},
+	library::Library,
+	ops::indexing::{
+		database_storage::EntryMetadata,
+		state::{DirEntry, EntryKind},
+	},
+	ops::media::thumbnail::{generate_thumbnails_for_file, ThumbnailUtils},
+	ops::sidecar::types::SidecarKind,
+	volume::{ArchiveBackend, VolumeError},
+};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use std::{
+	collections::{HashMap, HashSet},
+	path::{Path, PathBuf},
+	sync::Arc,
+	time::{Duration, SystemTime},
+};
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// How long to wait for another request that is indexing the same archive
+const INDEXING_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Content being thumbnailed from an archive, so repeated listings don't extract it again
+static THUMBNAILING: Lazy<Mutex<HashSet<Uuid>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Size and modification time of an archive when its members were listed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ArchiveStamp {
+	size: u64,
+	modified: Option<SystemTime>,
+}
+
+impl ArchiveStamp {
+	fn of(metadata: &std::fs::Metadata) -> Self {
+		Self {
+			size: metadata.len(),
+			modified: metadata.modified().ok(),
+		}
+	}
+}
+
+/// Make sure the contents of an archive are in the ephemeral index
+///
+/// Returns the number of entries added, 0 when the archive was already indexed and
+/// hasn't changed since. Member contents are hashed in the background afterwards.
+pub async fn index_archive(
+	cache: &Arc<EphemeralIndexCache>,
+	archive: &Path,
+	file_types: Arc<FileTypeRegistry>,
+) -> Result<usize, VolumeError> {
+	let deadline = tokio::time::Instant::now() + INDEXING_TIMEOUT;
+	while cache.is_indexing(archive) && tokio::time::Instant::now() < deadline {
+		tokio::time::sleep(Duration::from_millis(25)).await;
+	}
+
+	let index = cache.get_global_index();
+	let archive_metadata = tokio::fs::metadata(archive).await?;
+	let stamp = ArchiveStamp::of(&archive_metadata);
+
+	// Re-indexing the parent directory drops the members, the indexed flag alone isn't enough
+	if cache.is_indexed(archive) && cache.archive_stamp(archive) == Some(stamp) {
+		let listed = index
+			.read()
+			.await
+			.list_directory(archive)
+			.is_some_and(|children| !children.is_empty());
+		if listed {
+			return Ok(0);
+		}
+	}
+
+	let backend = ArchiveBackend::open(archive).ok_or_else(|| {
+		VolumeError::InvalidData(format!("{} is not a supported archive", archive.display()))
+	})?;
+
+	cache.create_for_indexing(archive.to_path_buf());
+	let listing = {
+		let backend = backend.clone();
+		tokio::task::spawn_blocking(move || backend.list_members()).await
+	};
+	let members = match listing {
+		Ok(Ok(members)) => members,
+		Ok(Err(e)) => {
+			cache.mark_indexing_complete(archive);
+			cache.invalidate_path(archive);
+			return Err(e);
+		}
+		Err(e) => {
+			cache.mark_indexing_complete(archive);
+			cache.invalidate_path(archive);
+			return Err(VolumeError::Platform(format!(
+				"Archive indexing failed: {}",
+				e
+			)));
+		}
+	};
+
+	let mut entries = Vec::with_capacity(members.len() + 1);
+	entries.push(entry(
+		archive.to_path_buf(),
+		EntryKind::File,
+		stamp.size,
+		stamp.modified,
+	));
+	entries.extend(members.into_iter().map(|member| {
+		entry(
+			archive.join(&member.path),
+			member.kind,
+			member.size,
+			member.modified,
+		)
+	}));
+	let added = entries.len() - 1;
+
+	{
+		let mut index = index.write().await;
+		index.remove_directory_tree(archive);
+		index.set_file_types(file_types);
+		if let Err(e) = index.add_entries_batch(entries) {
+			drop(index);
+			cache.mark_indexing_complete(archive);
+			cache.invalidate_path(archive);
+			return Err(VolumeError::Io(e));
+		}
+	}
+
+	cache.clear_content_hashes(archive);
+	cache.set_archive_stamp(archive.to_path_buf(), stamp);
+	cache.mark_indexing_complete(archive);
+
+	debug!("Indexed {} entries of archive {}", added, archive.display());
+
+	let cache = cache.clone();
+	tokio::spawn(async move {
+		let archive = backend.archive_path().to_path_buf();
+		let hashed = tokio::task::spawn_blocking(move || hash_members(&backend)).await;
+		match hashed {
+			// A newer listing owns the hashes if the archive changed meanwhile
+			Ok(hashes) if cache.archive_stamp(&archive) == Some(stamp) => {
+				cache.set_content_hashes(
+					hashes
+						.into_iter()
+						.map(|(path, hash)| (archive.join(path), hash)),
+				);
+			}
+			Ok(_) => debug!("{} changed while hashing", archive.display()),
+			Err(e) => warn!("Hashing {} failed: {}", archive.display(), e),
+		}
+	});
+
+	Ok(added)
+}
+
+/// Hash the files of an archive in archive order (blocking)
+fn hash_members(backend: &ArchiveBackend) -> Vec<(PathBuf, String)> {
+	let mut hashes = Vec::new();
+	let hashed = backend.for_each_file(
+		|_| true,
+		|member, data| {
+			match ContentHashGenerator::generate_content_hash_from_reader(data, member.size) {
+				Ok(hash) => hashes.push((member.path.clone(), hash)),
+				Err(ContentHashError::EmptyFile) => {}
+				Err(ContentHashError::Io(e)) => return Err(e),
+				Err(e) => warn!("Failed to hash {}: {}", member.path.display(), e),
+			}
+			Ok(true)
+		},
+	);
+
+	// A damaged member stops the stream, the hashes so far are still good
+	if let Err(e) = hashed {
+		warn!(
+			"Hashed {} files of {} before failing: {}",
+			hashes.len(),
+			backend.archive_path().display(),
+			e
+		);
+	}
+
+	hashes
+}
+
+fn entry(
+	path: PathBuf,
+	kind: EntryKind,
+	size: u64,
+	modified: Option<std::time::SystemTime>,
+) -> (PathBuf, Option<Uuid>, EntryMetadata) {
+	let metadata = EntryMetadata::from(DirEntry {
+		path: path.clone(),
+		kind,
+		size,
+		modified,
+		inode: None,
+	});
+	(path, None, metadata)
+}
+
+/// Give archive members among `files` their content identities and sidecars
+///
+/// Identities already in the library are used as they are, others are built from the
+/// hash alone. Members that can have thumbnails but don't yet are extracted to a
+/// temporary file and thumbnailed in the background.
+pub async fn identify_archive_members(
+	context: &CoreContext,
+	library: &Arc<Library>,
+	files: &mut [File],
+) {
+	let cache = context.ephemeral_cache();
+
+	let hashed: Vec<(usize, String)> = files
+		.iter()
+		.enumerate()
+		.filter(|(_, file)| file.kind == FileKind::File)
+		.filter_map(|(i, file)| Some((i, cache.content_hash(file.sd_path.as_local_path()?)?)))
+		.collect();
+	if hashed.is_empty() {
+		return;
+	}
+
+	let uuids: Vec<Uuid> = hashed
+		.iter()
+		.map(|(_, hash)| content_identity::Model::deterministic_uuid(hash))
+		.collect();
+
+	let db = library.db().conn();
+	let known: HashMap<Uuid, content_identity::Model> = content_identity::Entity::find()
+		.filter(content_identity::Column::Uuid.is_in(uuids.clone()))
+		.all(db)
+		.await
+		.unwrap_or_default()
+		.into_iter()
+		.filter_map(|model| Some((model.uuid?, model)))
+		.collect();
+
+	let mut sidecars: HashMap<Uuid, Vec<Sidecar>> = HashMap::new();
+	for s in sidecar::Entity::find()
+		.filter(sidecar::Column::ContentUuid.is_in(uuids.clone()))
+		.all(db)
+		.await
+		.unwrap_or_default()
+	{
+		sidecars.entry(s.content_uuid).or_default().push(Sidecar {
+			id: s.id,
+			content_uuid: s.content_uuid,
+			kind: s.kind,
+			variant: s.variant,
+			format: s.format,
+			status: s.status,
+			size: s.size,
+			created_at: s.created_at,
+			updated_at: s.updated_at,
+		});
+	}
+
+	let file_types = library.file_types();
+	let thumb_kind = SidecarKind::Thumb.as_str();
+	let mut to_thumbnail = Vec::new();
+	for ((i, hash), uuid) in hashed.into_iter().zip(uuids) {
+		let file = &mut files[i];
+
+		let identity = match known.get(&uuid) {
+			Some(model) => ContentIdentity::from(model.clone()),
+			None => {
+				let now = chrono::Utc::now();
+				ContentIdentity {
+					uuid,
+					kind: file.content_kind,
+					content_hash: hash,
+					integrity_hash: None,
+					mime_type_id: None,
+					text_content: None,
+					total_size: file.size as i64,
+					entry_count: 0,
+					first_seen_at: now,
+					last_verified_at: now,
+				}
+			}
+		};
+		file.content_identity = Some(identity);
+		file.sidecars = sidecars.remove(&uuid).unwrap_or_default();
+
+		let has_thumbnail = file.sidecars.iter().any(|s| s.kind == thumb_kind);
+		if has_thumbnail {
+			continue;
+		}
+		let mime_type = file.extension.as_deref().and_then(|extension| {
+			file_types
+				.get_by_extension(extension)
+				.into_iter()
+				.max_by_key(|file_type| file_type.priority)
+				.and_then(|file_type| file_type.mime_types.first().cloned())
+		});
+		if let (Some(mime_type), Some(path)) = (mime_type, file.sd_path.as_local_path()) {
+			if ThumbnailUtils::is_thumbnail_supported(&mime_type)
+				&& THUMBNAILING.lock().insert(uuid)
+			{
+				to_thumbnail.push((path.to_path_buf(), uuid, mime_type));
+			}
+		}
+	}
+
+	if !to_thumbnail.is_empty() {
+		let library = library.clone();
+		tokio::spawn(async move {
+			for (path, uuid, mime_type) in to_thumbnail {
+				if let Err(e) = thumbnail_member(&library, &path, &uuid, &mime_type).await {
+					debug!("No thumbnail for {}: {}", path.display(), e);
+				}
+				THUMBNAILING.lock().remove(&uuid);
+			}
+		});
+	}
+}
+
+/// Extract an archive member to a temporary file and generate its thumbnails
+async fn thumbnail_member(
+	library: &Arc<Library>,
+	path: &Path,
+	content_uuid: &Uuid,
+	mime_type: &str,
+) -> anyhow::Result<usize> {
+	let backend = ArchiveBackend::for_path(path)
+		.ok_or_else(|| anyhow::anyhow!("{} is not inside an archive", path.display()))?;
+
+	// Generators look at the extension, keep it on the temporary file
+	let suffix = path
+		.extension()
+		.map(|extension| format!(".{}", extension.to_string_lossy()))
+		.unwrap_or_default();
+	let temp = tempfile::Builder::new()
+		.prefix("sd-archive-")
+		.suffix(&suffix)
+		.tempfile()?;
+
+	let mut out = temp.reopen()?;
+	let member = path.to_path_buf();
+	tokio::task::spawn_blocking(move || backend.extract(&member, None, &mut out)).await??;
+
+	Ok(generate_thumbnails_for_file(library, content_uuid, temp.path(), mime_type).await?)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::volume::VolumeBackend;
+	use std::io::Write;
+	use tempfile::TempDir;
+
+	fn write_zip(path: &Path, files: &[(&str, Vec<u8>)]) {
+		let mut zip = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+		for (name, data) in files {
+			zip.start_file(*name, zip::write::SimpleFileOptions::default())
+				.unwrap();
+			zip.write_all(data).unwrap();
+		}
+		zip.finish().unwrap();
+	}
+
+	fn write_seven_zip(path: &Path, files: &[(&str, Vec<u8>)]) {
+		let mut writer = sevenz_rust::SevenZWriter::create(path).unwrap();
+		for (name, data) in files {
+			let mut entry = sevenz_rust::SevenZArchiveEntry::new();
+			entry.name = name.to_string();
+			let reader = (!data.is_empty()).then_some(data.as_slice());
+			writer.push_archive_entry(entry, reader).unwrap();
+		}
+		writer.finish().unwrap();
+	}
+
+	fn write_tar_zst(path: &Path, files: &[(&str, Vec<u8>)]) {
+		let encoder = zstd::stream::write::Encoder::new(std::fs::File::create(path).unwrap(), 3)
+			.unwrap()
+			.auto_finish();
+		let mut tar = tar::Builder::new(encoder);
+		for (name, data) in files {
+			let mut header = tar::Header::new_gnu();
+			header.set_size(data.len() as u64);
+			header.set_mode(0o644);
+			header.set_cksum();
+			tar.append_data(&mut header, name, data.as_slice()).unwrap();
+		}
+		tar.finish().unwrap();
+	}
+
+	/// Wait for the background pass to hash a member
+	async fn wait_for_hash(cache: &EphemeralIndexCache, path: &Path) -> Option<String> {
+		for _ in 0..400 {
+			if let Some(hash) = cache.content_hash(path) {
+				return Some(hash);
+			}
+			tokio::time::sleep(Duration::from_millis(25)).await;
+		}
+		None
+	}
+
+	#[tokio::test]
+	async fn indexes_members_under_the_archive() {
+		let temp = TempDir::new().unwrap();
+		let large: Vec<u8> = (0..300_000u32).map(|i| (i % 251) as u8).collect();
+		let files = [
+			("notes.txt", b"hello".to_vec()),
+			("trip/day1/large.bin", large),
+			("trip/empty.txt", Vec::new()),
+		];
+
+		let writers: [(&str, fn(&Path, &[(&str, Vec<u8>)])); 3] = [
+			("photos.zip", write_zip),
+			("photos.7z", write_seven_zip),
+			("photos.tar.zst", write_tar_zst),
+		];
+		for (name, write) in writers {
+			let archive = temp.path().join(name);
+			write(&archive, &files);
+
+			let cache = Arc::new(EphemeralIndexCache::new().unwrap());
+			// Three files and the two implicit directories
+			assert_eq!(
+				index_archive(&cache, &archive, Default::default()).await.unwrap(),
+				5,
+				"{}",
+				name
+			);
+			assert_eq!(
+				index_archive(&cache, &archive, Default::default()).await.unwrap(),
+				0,
+				"{}",
+				name
+			);
+			assert!(cache.is_indexed(&archive));
+
+			{
+				let index = cache.get_global_index();
+				let index = index.read().await;
+				let mut root = index.list_directory(&archive).unwrap();
+				root.sort();
+				assert_eq!(root, vec![archive.join("notes.txt"), archive.join("trip")]);
+				let day1 = index.list_directory(&archive.join("trip/day1")).unwrap();
+				assert_eq!(day1, vec![archive.join("trip/day1/large.bin")]);
+				assert_eq!(index.get_entry_ref(&archive).unwrap().kind, EntryKind::File);
+			}
+
+			// Hashes match what hashing through the backend gives, sampled or not
+			let backend = ArchiveBackend::open(&archive).unwrap();
+			for (member, size) in [("notes.txt", 5), ("trip/day1/large.bin", 300_000)] {
+				let expected = ContentHashGenerator::generate_content_hash_with_backend(
+					&backend,
+					Path::new(member),
+					size,
+				)
+				.await
+				.unwrap();
+				assert_eq!(
+					wait_for_hash(&cache, &archive.join(member)).await,
+					Some(expected),
+					"{}",
+					name
+				);
+			}
+			assert_eq!(cache.content_hash(&archive.join("trip/empty.txt")), None);
+		}
+	}
+
+	#[tokio::test]
+	async fn reindexes_after_the_parent_dropped_the_members() {
+		let temp = TempDir::new().unwrap();
+		let archive = temp.path().join("docs.zip");
+		write_zip(&archive, &[("a.txt", b"a".to_vec())]);
+
+		let cache = Arc::new(EphemeralIndexCache::new().unwrap());
+		index_archive(&cache, &archive, Default::default()).await.unwrap();
+		cache
+			.get_global_index()
+			.write()
+			.await
+			.remove_directory_tree(&archive);
+
+		assert_eq!(index_archive(&cache, &archive, Default::default()).await.unwrap(), 1);
+		assert!(backend_exists(&archive, "a.txt").await);
+	}
+
+	#[tokio::test]
+	async fn reindexes_a_changed_archive() {
+		let temp = TempDir::new().unwrap();
+		let archive = temp.path().join("docs.zip");
+		write_zip(&archive, &[("a.txt", b"a".to_vec())]);
+
+		let cache = Arc::new(EphemeralIndexCache::new().unwrap());
+		assert_eq!(index_archive(&cache, &archive, Default::default()).await.unwrap(), 1);
+		assert!(wait_for_hash(&cache, &archive.join("a.txt"))
+			.await
+			.is_some());
+
+		write_zip(
+			&archive,
+			&[("b.txt", b"bb".to_vec()), ("c.txt", b"ccc".to_vec())],
+		);
+		assert_eq!(index_archive(&cache, &archive, Default::default()).await.unwrap(), 2);
+
+		let index = cache.get_global_index();
+		let mut root = index.read().await.list_directory(&archive).unwrap();
+		root.sort();
+		assert_eq!(root, vec![archive.join("b.txt"), archive.join("c.txt")]);
+		assert_eq!(cache.content_hash(&archive.join("a.txt")), None);
+		assert!(wait_for_hash(&cache, &archive.join("c.txt"))
+			.await
+			.is_some());
+	}
+
+	#[tokio::test]
+	async fn rejects_files_that_are_not_archives() {
+		let temp = TempDir::new().unwrap();
+		let path = temp.path().join("plain.txt");
+		std::fs::write(&path, b"not an archive").unwrap();
+
+		let cache = Arc::new(EphemeralIndexCache::new().unwrap());
+		assert!(index_archive(&cache, &path, Default::default()).await.is_err());
+		assert!(!cache.is_indexing(&path));
+	}
+
+	async fn backend_exists(archive: &Path, member: &str) -> bool {
+		ArchiveBackend::open(archive)
+			.unwrap()
+			.exists(Path::new(member))
+			.await
+			.unwrap()
+	}
+}
@@ -6,10 +6,10 @@
 //! paths are indexed (queryable), in-progress (being scanned), or watched
 //! (receiving live filesystem updates via `MemoryAdapter`).
 
-use super::EphemeralIndex;
+use super::{archive::ArchiveStamp, EphemeralIndex};
 use parking_lot::RwLock;
 use std::{
-	collections::HashSet,
+	collections::{HashMap, HashSet},
 	path::{Path, PathBuf},
 	sync::Arc,
 	time::Instant,
@@ -33,6 +33,13 @@ pub struct EphemeralIndexCache {
 	/// Paths registered for filesystem watching (subset of indexed_paths)
 	watched_paths: RwLock<HashSet<PathBuf>>,
 
+	/// Content hashes computed while indexing, for entries that can't be cheaply
+	/// re-read later (archive members)
+	content_hashes: RwLock<HashMap<PathBuf, String>>,
+
+	/// Size and modification time of listed archives, to notice when they change
+	archive_stamps: RwLock<HashMap<PathBuf, ArchiveStamp>>,
+
 	/// When the cache was created
 	created_at: Instant,
 }
@@ -45,6 +52,8 @@ impl EphemeralIndexCache {
 			indexed_paths: RwLock::new(HashSet::new()),
 			indexing_in_progress: RwLock::new(HashSet::new()),
 			watched_paths: RwLock::new(HashSet::new()),
+			content_hashes: RwLock::new(HashMap::new()),
+			archive_stamps: RwLock::new(HashMap::new()),
 			created_at: Instant::now(),
 		})
 	}
@@ -231,6 +240,33 @@ impl EphemeralIndexCache {
 		indexed.remove(path);
 	}
 
+	/// Remember content hashes computed while indexing
+	pub fn set_content_hashes(&self, hashes: impl IntoIterator<Item = (PathBuf, String)>) {
+		self.content_hashes.write().extend(hashes);
+	}
+
+	/// Content hash computed for a path while indexing, if any
+	pub fn content_hash(&self, path: &Path) -> Option<String> {
+		self.content_hashes.read().get(path).cloned()
+	}
+
+	/// Forget the content hashes of a path and everything under it
+	pub fn clear_content_hashes(&self, path: &Path) {
+		self.content_hashes
+			.write()
+			.retain(|hashed, _| !hashed.starts_with(path));
+	}
+
+	/// Remember the state of an archive whose members were just listed
+	pub fn set_archive_stamp(&self, archive: PathBuf, stamp: ArchiveStamp) {
+		self.archive_stamps.write().insert(archive, stamp);
+	}
+
+	/// State of an archive when its members were last listed
+	pub fn archive_stamp(&self, archive: &Path) -> Option<ArchiveStamp> {
+		self.archive_stamps.read().get(archive).copied()
+	}
+
 	/// Get the number of indexed paths
 	pub fn len(&self) -> usize {
 		self.indexed_paths.read().len()
@@ -334,6 +370,8 @@ impl EphemeralIndexCache {
 			indexed.clear();
 			in_progress.clear();
 			watched.clear();
+			self.content_hashes.write().clear();
+			self.archive_stamps.write().clear();
 
 			count
 		};
@@ -38,6 +38,7 @@ pub struct EphemeralIndex {
 	id_to_path: HashMap<EntryId, PathBuf>,
 	entry_uuids: HashMap<EntryId, Uuid>,
 	content_kinds: HashMap<EntryId, ContentKind>,
+	file_types: Arc<FileTypeRegistry>,
 	created_at: Instant,
 	last_accessed: Instant,
 	pub stats: IndexerStats,
@@ -117,12 +118,18 @@ impl EphemeralIndex {
 			id_to_path: HashMap::new(),
 			entry_uuids: HashMap::new(),
 			content_kinds: HashMap::new(),
+			file_types: Arc::new(FileTypeRegistry::default()),
 			created_at: now,
 			last_accessed: now,
 			stats: IndexerStats::default(),
 		})
 	}
 
+	/// Use a library's file types to identify entries added from now on
+	pub fn set_file_types(&mut self, file_types: Arc<FileTypeRegistry>) {
+		self.file_types = file_types;
+	}
+
 	/// Ensures a directory exists, creating all missing ancestors recursively.
 	///
 	/// This method guarantees that `list_directory()` works immediately after
@@ -185,7 +192,7 @@ impl EphemeralIndex {
 		uuid: Uuid,
 		metadata: EntryMetadata,
 	) -> std::io::Result<Option<ContentKind>> {
-		let registry = FileTypeRegistry::default();
+		let registry = self.file_types.clone();
 		self.add_entry_with_registry(path, Some(uuid), metadata, &registry)
 	}
 
@@ -274,8 +281,7 @@ impl EphemeralIndex {
 	) -> std::io::Result<Vec<Option<ContentKind>>> {
 		let mut results = Vec::with_capacity(entries.len());
 
-		// Create registry once for entire batch instead of per-file
-		let registry = FileTypeRegistry::default();
+		let registry = self.file_types.clone();
 
 		for (path, uuid, metadata) in entries {
 			let result = self.add_entry_with_registry(path, uuid, metadata, &registry)?;
@@ -653,7 +659,9 @@ impl EphemeralIndex {
 			.keys()
 			.filter(|k| {
 				let k_str = k.to_string_lossy();
-				k_str == prefix || k_str.starts_with(&format!("{}/", prefix)) || k_str.starts_with(&format!("{}\\", prefix))
+				k_str == prefix
+					|| k_str.starts_with(&format!("{}/", prefix))
+					|| k_str.starts_with(&format!("{}\\", prefix))
 			})
 			.cloned()
 			.collect();
@@ -784,6 +792,7 @@ impl EphemeralIndex {
 			id_to_path,
 			entry_uuids,
 			content_kinds,
+			file_types: Arc::new(FileTypeRegistry::default()),
 			created_at: now,
 			last_accessed: now,
 			stats,
@@ -32,6 +32,7 @@
 //! let children = index.list_directory(&parent);
 //! ```
 
+pub mod archive;
 pub mod arena;
 pub mod cache;
 pub mod index;
@@ -17,7 +17,7 @@
 
 use crate::context::CoreContext;
 use crate::ops::indexing::change_detection::{self, ChangeConfig};
-use crate::ops::indexing::rules::RuleToggles;
+use crate::ops::indexing::rules::{LocationRules, RuleToggles};
 use anyhow::Result;
 use sd_fs_watcher::{FsEvent, FsEventKind};
 use std::path::{Path, PathBuf};
@@ -78,10 +78,12 @@ pub async fn apply_batch(
 	let event_bus = context.events.clone();
 
 	let mut writer = MemoryAdapter::new(index, event_bus, root_path.to_path_buf());
+	let location_rules = LocationRules::none(root_path);
 
 	let config = ChangeConfig {
 		rule_toggles,
 		location_root: root_path,
+		location_rules: &location_rules,
 		volume_backend: None, // Ephemeral paths typically don't use volume backends
 	};
 
@@ -29,6 +29,7 @@ use super::{
 	ephemeral::EphemeralIndex,
 	metrics::{IndexerMetrics, PhaseTimer},
 	phases,
+	rules::LocationRules,
 	state::{IndexError, IndexPhase, IndexerProgress, IndexerState, IndexerStats, Phase},
 	PathResolver,
 };
@@ -338,6 +339,9 @@ impl IndexerJob {
 			state.dirs_to_walk.push_back(root_path.to_path_buf());
 		}
 
+		let location_rules =
+			Arc::new(Self::load_location_rules(&self.config, ctx, root_path).await?);
+
 		loop {
 			ctx.check_interrupt().await?;
 
@@ -352,13 +356,20 @@ impl IndexerJob {
 						};
 
 					if self.config.is_current_scope() {
-						Self::run_current_scope_discovery_static(state, &ctx, root_path).await?;
+						Self::run_current_scope_discovery_static(
+							state,
+							&ctx,
+							root_path,
+							&location_rules,
+						)
+						.await?;
 					} else {
 						phases::run_discovery_phase(
 							state,
 							&ctx,
 							root_path,
 							self.config.rule_toggles.clone(),
+							Arc::clone(&location_rules),
 							volume_backend.as_ref(),
 							cloud_url_base,
 						)
@@ -818,15 +829,64 @@ impl IndexerJob {
 		Self::new(IndexerJobConfig::ephemeral_browse(path, scope, is_volume))
 	}
 
+	/// Indexer rules stored for the job's location, none for ephemeral jobs
+	///
+	/// Depth limits count from the location root, which differs from the job's
+	/// root when a subdirectory is reindexed.
+	async fn load_location_rules(
+		config: &IndexerJobConfig,
+		ctx: &JobContext<'_>,
+		root_path: &Path,
+	) -> JobResult<LocationRules> {
+		let Some(location_uuid) = config.location_id.filter(|_| !config.is_ephemeral()) else {
+			return Ok(LocationRules::none(root_path));
+		};
+
+		let db = ctx.library().db().conn();
+		let Some(location) = entities::location::Entity::find()
+			.filter(entities::location::Column::Uuid.eq(location_uuid))
+			.one(db)
+			.await
+			.map_err(|e| JobError::execution(e.to_string()))?
+		else {
+			return Ok(LocationRules::none(root_path));
+		};
+
+		let mut location_root = root_path.to_path_buf();
+		if let Some(entry_id) = location.entry_id {
+			if let Ok(path) = PathResolver::get_directory_path(db, entry_id).await {
+				let path = PathBuf::from(path);
+				if root_path.starts_with(&path) {
+					location_root = path;
+				}
+			}
+		}
+
+		LocationRules::load(db, location.id, location_root, ctx.library().file_types())
+			.await
+			.map_err(|e| JobError::execution(format!("Failed to load indexer rules: {}", e)))
+	}
+
 	async fn run_current_scope_discovery_static(
 		state: &mut IndexerState,
 		ctx: &JobContext<'_>,
 		root_path: &std::path::Path,
+		location_rules: &LocationRules,
 	) -> JobResult<()> {
 		use super::database_storage::DatabaseStorage;
+		use super::rules::{IndexerRuler, RulerDecision, SpacedriveIgnoreRules};
 		use super::state::{DirEntry, EntryKind};
 		use tokio::fs;
 
+		// Browsing a single level shows everything except what ignore files exclude
+		let ignore_ruler = IndexerRuler::new(
+			SpacedriveIgnoreRules::get_rules(&location_rules.root, root_path)
+				.await
+				.into_iter()
+				.map(Into::into)
+				.collect(),
+		);
+
 		let mut entries = fs::read_dir(root_path)
 			.await
 			.map_err(|e| JobError::execution(format!("Failed to read directory: {}", e)))?;
@@ -842,6 +902,14 @@ impl IndexerJob {
 				.await
 				.map_err(|e| JobError::execution(format!("Failed to read metadata: {}", e)))?;
 
+			if matches!(
+				ignore_ruler.evaluate_path(&path, &metadata).await,
+				Ok(RulerDecision::Reject)
+			) {
+				state.stats.skipped += 1;
+				continue;
+			}
+
 			let entry_kind = if metadata.is_dir() {
 				EntryKind::Directory
 			} else if metadata.is_symlink() {
@@ -898,6 +966,10 @@ impl IndexerJob {
 
 		let event_bus = ctx.library().event_bus().clone();
 		let total_batches = state.entry_batches.len();
+		ephemeral_index
+			.write()
+			.await
+			.set_file_types(ctx.library().file_types());
 		let mut batch_number = 0;
 
 		while let Some(batch) = state.entry_batches.pop() {
@@ -57,8 +57,8 @@ pub use crate::domain::location::IndexMode;
 pub use path_resolver::PathResolver;
 pub use persistence::{IndexPersistence as PersistenceTrait, PersistenceFactory};
 pub use rules::{
-	build_default_ruler, IndexerRule, IndexerRuler, RuleKind, RulePerKind, RuleToggles,
-	RulerDecision,
+	build_default_ruler, IndexerLimits, IndexerRule, IndexerRuler, LocationRules, RuleKind,
+	RulePerKind, RuleToggles, RulerDecision,
 };
 pub use state::{IndexPhase, IndexerProgress, IndexerState, IndexerStats};
 pub use verify::{IndexVerifyAction, IndexVerifyInput, IndexVerifyOutput, IntegrityReport};
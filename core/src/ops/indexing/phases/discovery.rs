@@ -10,7 +10,7 @@ use crate::{
 	infra::job::prelude::{JobContext, JobError, Progress},
 	ops::indexing::{
 		database_storage::DatabaseStorage,
-		rules::{build_default_ruler, RuleToggles, RulerDecision},
+		rules::{LocationEntryMetadata, LocationRules, RuleToggles, RulerDecision},
 		state::{DirEntry, EntryKind, IndexError, IndexPhase, IndexerProgress, IndexerState},
 	},
 };
@@ -20,13 +20,14 @@ use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::time::Instant;
 use std::{path::Path, sync::Arc};
 
-struct SimpleMetadata {
-	is_dir: bool,
-}
-impl crate::ops::indexing::rules::MetadataForIndexerRules for SimpleMetadata {
-	fn is_dir(&self) -> bool {
-		self.is_dir
-	}
+/// Metadata of a discovered entry for the location's rules
+fn rule_metadata(entry: &DirEntry, location_rules: &LocationRules) -> LocationEntryMetadata {
+	location_rules.metadata(
+		&entry.path,
+		matches!(entry.kind, EntryKind::Directory),
+		Some(entry.size),
+		entry.modified,
+	)
 }
 
 /// Runs parallel directory discovery or falls back to sequential for concurrency = 1.
@@ -39,6 +40,7 @@ pub async fn run_discovery_phase(
 	ctx: &JobContext<'_>,
 	root_path: &Path,
 	rule_toggles: RuleToggles,
+	location_rules: Arc<LocationRules>,
 	volume_backend: Option<&Arc<dyn crate::volume::VolumeBackend>>,
 	cloud_url_base: Option<String>,
 ) -> Result<(), JobError> {
@@ -50,6 +52,7 @@ pub async fn run_discovery_phase(
 			ctx,
 			root_path,
 			rule_toggles,
+			location_rules,
 			volume_backend,
 			cloud_url_base,
 		)
@@ -71,6 +74,7 @@ pub async fn run_discovery_phase(
 		ctx,
 		root_path,
 		rule_toggles,
+		location_rules,
 		volume_backend,
 		cloud_url_base,
 	)
@@ -88,6 +92,7 @@ async fn run_parallel_discovery(
 	ctx: &JobContext<'_>,
 	root_path: &Path,
 	rule_toggles: RuleToggles,
+	location_rules: Arc<LocationRules>,
 	volume_backend: Option<&Arc<dyn crate::volume::VolumeBackend>>,
 	cloud_url_base: Option<String>,
 ) -> Result<(), JobError> {
@@ -124,9 +129,9 @@ async fn run_parallel_discovery(
 		let skipped_count = Arc::clone(&skipped_count);
 		let shutdown = Arc::clone(&shutdown);
 		let seen_paths = Arc::clone(&seen_paths);
-		let root_path = root_path.to_path_buf();
 		let volume_backend = volume_backend.cloned();
 		let cloud_url_base = cloud_url_base.clone();
+		let location_rules = Arc::clone(&location_rules);
 
 		let worker = tokio::spawn(async move {
 			discovery_worker_rayon(
@@ -138,8 +143,8 @@ async fn run_parallel_discovery(
 				skipped_count,
 				shutdown,
 				seen_paths,
-				root_path,
 				rule_toggles,
+				location_rules,
 				volume_backend,
 				cloud_url_base,
 			)
@@ -289,8 +294,8 @@ async fn discovery_worker_rayon(
 	skipped_count: Arc<AtomicU64>,
 	shutdown: Arc<AtomicBool>,
 	seen_paths: Arc<parking_lot::RwLock<std::collections::HashSet<PathBuf>>>,
-	root_path: PathBuf,
 	rule_toggles: RuleToggles,
+	location_rules: Arc<LocationRules>,
 	volume_backend: Option<Arc<dyn crate::volume::VolumeBackend>>,
 	cloud_url_base: Option<String>,
 ) {
@@ -318,7 +323,7 @@ async fn discovery_worker_rayon(
 			}
 		}
 
-		let dir_ruler = build_default_ruler(rule_toggles, &root_path, &dir_path).await;
+		let dir_ruler = location_rules.ruler(rule_toggles, &dir_path).await;
 
 		match read_directory(
 			&dir_path,
@@ -332,12 +337,7 @@ async fn discovery_worker_rayon(
 
 				for entry in entries {
 					let decision = dir_ruler
-						.evaluate_path(
-							&entry.path,
-							&SimpleMetadata {
-								is_dir: matches!(entry.kind, EntryKind::Directory),
-							},
-						)
+						.evaluate_path(&entry.path, &rule_metadata(&entry, &location_rules))
 						.await;
 
 					if matches!(decision, Ok(RulerDecision::Reject)) {
@@ -424,6 +424,7 @@ async fn run_discovery_phase_sequential(
 	ctx: &JobContext<'_>,
 	root_path: &Path,
 	rule_toggles: RuleToggles,
+	location_rules: Arc<LocationRules>,
 	volume_backend: Option<&Arc<dyn crate::volume::VolumeBackend>>,
 	cloud_url_base: Option<String>,
 ) -> Result<(), JobError> {
@@ -441,7 +442,7 @@ async fn run_discovery_phase_sequential(
 			continue;
 		}
 
-		let dir_ruler = build_default_ruler(rule_toggles, root_path, &dir_path).await;
+		let dir_ruler = location_rules.ruler(rule_toggles, &dir_path).await;
 
 		let indexer_progress = IndexerProgress {
 			phase: IndexPhase::Discovery {
@@ -468,12 +469,7 @@ async fn run_discovery_phase_sequential(
 					ctx.check_interrupt().await?;
 
 					let decision = dir_ruler
-						.evaluate_path(
-							&entry.path,
-							&SimpleMetadata {
-								is_dir: matches!(entry.kind, EntryKind::Directory),
-							},
-						)
+						.evaluate_path(&entry.path, &rule_metadata(&entry, &location_rules))
 						.await;
 					if matches!(decision, Ok(RulerDecision::Reject)) {
 						state.stats.skipped += 1;
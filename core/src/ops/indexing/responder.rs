@@ -70,10 +70,12 @@ pub async fn apply_batch(
 		volume_backend.cloned(),
 	)
 	.await?;
+	let location_rules = handler.location_rules(location_root).await?;
 
 	let config = ChangeConfig {
 		rule_toggles,
 		location_root,
+		location_rules: &location_rules,
 		volume_backend,
 	};
 
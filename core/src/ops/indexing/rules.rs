@@ -2,6 +2,7 @@ use std::{
 	collections::{HashMap, HashSet},
 	path::{Path, PathBuf},
 	sync::Arc,
+	time::{Duration, SystemTime},
 };
 
 use chrono::{DateTime, Utc};
@@ -15,6 +16,8 @@ use serde::{de::VariantAccess, Deserialize, Serialize};
 use specta::Type;
 use thiserror::Error;
 
+use crate::{domain::content_identity::ContentKind, filetype::FileTypeRegistry};
+
 #[derive(Error, Debug)]
 pub enum Error {
 	#[error("invalid indexer rule kind integer: {0}")]
@@ -28,6 +31,8 @@ pub enum Error {
 	RuleParametersRMPDecode(#[from] decode::Error),
 	#[error("io error: {0}")]
 	Io(#[from] std::io::Error),
+	#[error("database error: {0}")]
+	Database(#[from] sea_orm::DbErr),
 }
 
 #[repr(i32)]
@@ -39,15 +44,23 @@ pub enum RuleKind {
 	AcceptIfChildrenDirectoriesArePresent = 2,
 	RejectIfChildrenDirectoriesArePresent = 3,
 	IgnoredByGit = 4,
+	RejectFilesLargerThan = 5,
+	RejectFilesNotModifiedWithin = 6,
+	RejectFilesByContentKind = 7,
+	RejectIfDeeperThan = 8,
+	IgnoredBySpacedrive = 9,
 }
 
 impl RuleKind {
 	#[must_use]
 	pub const fn variant_count() -> usize {
-		5
+		10
 	}
 }
 
+/// Per-directory ignore file, in gitignore syntax
+pub const SPACEDRIVE_IGNORE_FILE: &str = ".spacedriveignore";
+
 #[derive(Debug, Clone)]
 pub enum RulePerKind {
 	AcceptFilesByGlob(Vec<Glob>, GlobSet),
@@ -55,6 +68,16 @@ pub enum RulePerKind {
 	AcceptIfChildrenDirectoriesArePresent(HashSet<String>),
 	RejectIfChildrenDirectoriesArePresent(HashSet<String>),
 	IgnoredByGit(PathBuf, Search),
+	/// Size in bytes above which files are rejected
+	RejectFilesLargerThan(u64),
+	/// Seconds since the last modification after which files are rejected
+	RejectFilesNotModifiedWithin(u64),
+	/// Content kinds, identified by extension, whose files are rejected
+	RejectFilesByContentKind(Vec<ContentKind>),
+	/// Depth below the location root past which entries are rejected, direct children are at 1
+	RejectIfDeeperThan(u32),
+	/// Patterns from `.spacedriveignore` files, relative to the location root
+	IgnoredBySpacedrive(PathBuf, Search),
 }
 
 impl RulePerKind {
@@ -93,14 +116,43 @@ impl RulePerKind {
 	}
 }
 
+/// Metadata the rules evaluate against
+///
+/// Rules pass entries whose size, modification time or depth is unknown.
 pub trait MetadataForIndexerRules: Send + Sync + 'static {
 	fn is_dir(&self) -> bool;
+
+	fn size(&self) -> Option<u64> {
+		None
+	}
+
+	fn modified(&self) -> Option<SystemTime> {
+		None
+	}
+
+	/// Depth below the location root, direct children are at 1
+	fn depth(&self) -> Option<u32> {
+		None
+	}
+
+	/// Content kind the library's file types give the entry's extension
+	fn content_kind(&self) -> Option<ContentKind> {
+		None
+	}
 }
 
 impl MetadataForIndexerRules for std::fs::Metadata {
 	fn is_dir(&self) -> bool {
 		self.is_dir()
 	}
+
+	fn size(&self) -> Option<u64> {
+		Some(self.len())
+	}
+
+	fn modified(&self) -> Option<SystemTime> {
+		self.modified().ok()
+	}
 }
 
 impl RulePerKind {
@@ -132,6 +184,34 @@ impl RulePerKind {
 				RuleKind::IgnoredByGit,
 				accept_by_git_pattern(source, base_dir, patterns),
 			)),
+			Self::RejectFilesLargerThan(max_size) => Ok((
+				RuleKind::RejectFilesLargerThan,
+				metadata.is_dir() || metadata.size().map_or(true, |size| size <= *max_size),
+			)),
+			Self::RejectFilesNotModifiedWithin(max_age) => Ok((
+				RuleKind::RejectFilesNotModifiedWithin,
+				metadata.is_dir()
+					|| metadata.modified().map_or(true, |modified| {
+						SystemTime::now()
+							.duration_since(modified)
+							.map_or(true, |age| age <= Duration::from_secs(*max_age))
+					}),
+			)),
+			Self::RejectFilesByContentKind(kinds) => Ok((
+				RuleKind::RejectFilesByContentKind,
+				metadata.is_dir()
+					|| metadata
+						.content_kind()
+						.map_or(true, |kind| !kinds.contains(&kind)),
+			)),
+			Self::RejectIfDeeperThan(max_depth) => Ok((
+				RuleKind::RejectIfDeeperThan,
+				metadata.depth().map_or(true, |depth| depth <= *max_depth),
+			)),
+			Self::IgnoredBySpacedrive(base_dir, patterns) => Ok((
+				RuleKind::IgnoredBySpacedrive,
+				accept_by_git_pattern(source, base_dir, patterns),
+			)),
 		}
 	}
 }
@@ -262,8 +342,10 @@ impl IndexerRuler {
 	) -> bool {
 		Self::rejected_by_reject_glob(acceptance_per_rule_kind)
 			|| Self::rejected_by_git_ignore(acceptance_per_rule_kind)
+			|| Self::rejected_by_spacedrive_ignore(acceptance_per_rule_kind)
 			|| (is_dir && Self::rejected_by_children_directories(acceptance_per_rule_kind))
 			|| Self::rejected_by_accept_glob(acceptance_per_rule_kind)
+			|| Self::rejected_by_limits(acceptance_per_rule_kind)
 	}
 
 	pub fn rejected_by_accept_glob(
@@ -303,6 +385,33 @@ impl IndexerRuler {
 				reject_results.iter().any(|reject| !reject)
 			})
 	}
+
+	pub fn rejected_by_spacedrive_ignore(
+		acceptance_per_rule_kind: &HashMap<RuleKind, Vec<bool>>,
+	) -> bool {
+		acceptance_per_rule_kind
+			.get(&RuleKind::IgnoredBySpacedrive)
+			.map_or(false, |reject_results| {
+				reject_results.iter().any(|reject| !reject)
+			})
+	}
+
+	pub fn rejected_by_limits(acceptance_per_rule_kind: &HashMap<RuleKind, Vec<bool>>) -> bool {
+		[
+			RuleKind::RejectFilesLargerThan,
+			RuleKind::RejectFilesNotModifiedWithin,
+			RuleKind::RejectFilesByContentKind,
+			RuleKind::RejectIfDeeperThan,
+		]
+		.iter()
+		.any(|kind| {
+			acceptance_per_rule_kind
+				.get(kind)
+				.map_or(false, |reject_results| {
+					reject_results.iter().any(|reject| !reject)
+				})
+		})
+	}
 }
 
 // Serialization for RulePerKind (GlobSet is not serializable)
@@ -339,8 +448,32 @@ impl Serialize for RulePerKind {
 					"RejectIfChildrenDirectoriesArePresent",
 					children,
 				),
-			Self::IgnoredByGit(_, _) => {
-				unreachable!("git ignore rules are dynamic and not serialized")
+			Self::RejectFilesLargerThan(max_size) => serializer.serialize_newtype_variant(
+				"ParametersPerKind",
+				4,
+				"RejectFilesLargerThan",
+				&max_size,
+			),
+			Self::RejectFilesNotModifiedWithin(max_age) => serializer.serialize_newtype_variant(
+				"ParametersPerKind",
+				5,
+				"RejectFilesNotModifiedWithin",
+				&max_age,
+			),
+			Self::RejectFilesByContentKind(ref kinds) => serializer.serialize_newtype_variant(
+				"ParametersPerKind",
+				6,
+				"RejectFilesByContentKind",
+				kinds,
+			),
+			Self::RejectIfDeeperThan(max_depth) => serializer.serialize_newtype_variant(
+				"ParametersPerKind",
+				7,
+				"RejectIfDeeperThan",
+				&max_depth,
+			),
+			Self::IgnoredByGit(_, _) | Self::IgnoredBySpacedrive(_, _) => {
+				unreachable!("ignore file rules are dynamic and not serialized")
 			}
 		}
 	}
@@ -359,6 +492,10 @@ impl<'de> Deserialize<'de> for RulePerKind {
 			"RejectFilesByGlob",
 			"AcceptIfChildrenDirectoriesArePresent",
 			"RejectIfChildrenDirectoriesArePresent",
+			"RejectFilesLargerThan",
+			"RejectFilesNotModifiedWithin",
+			"RejectFilesByContentKind",
+			"RejectIfDeeperThan",
 		];
 
 		enum Fields {
@@ -366,13 +503,17 @@ impl<'de> Deserialize<'de> for RulePerKind {
 			RejectFilesByGlob,
 			AcceptIfChildrenDirectoriesArePresent,
 			RejectIfChildrenDirectoriesArePresent,
+			RejectFilesLargerThan,
+			RejectFilesNotModifiedWithin,
+			RejectFilesByContentKind,
+			RejectIfDeeperThan,
 		}
 
 		struct FieldsVisitor;
 		impl de::Visitor<'_> for FieldsVisitor {
 			type Value = Fields;
 			fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-				f.write_str("`AcceptFilesByGlob` or `RejectFilesByGlob` or `AcceptIfChildrenDirectoriesArePresent` or `RejectIfChildrenDirectoriesArePresent`")
+				f.write_str("`AcceptFilesByGlob`, `RejectFilesByGlob`, `AcceptIfChildrenDirectoriesArePresent`, `RejectIfChildrenDirectoriesArePresent`, `RejectFilesLargerThan`, `RejectFilesNotModifiedWithin`, `RejectFilesByContentKind` or `RejectIfDeeperThan`")
 			}
 			fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
 				Ok(match value {
@@ -384,6 +525,10 @@ impl<'de> Deserialize<'de> for RulePerKind {
 					"RejectIfChildrenDirectoriesArePresent" => {
 						Fields::RejectIfChildrenDirectoriesArePresent
 					}
+					"RejectFilesLargerThan" => Fields::RejectFilesLargerThan,
+					"RejectFilesNotModifiedWithin" => Fields::RejectFilesNotModifiedWithin,
+					"RejectFilesByContentKind" => Fields::RejectFilesByContentKind,
+					"RejectIfDeeperThan" => Fields::RejectIfDeeperThan,
 					_ => return Err(E::unknown_variant(value, VARIANTS)),
 				})
 			}
@@ -436,6 +581,18 @@ impl<'de> Deserialize<'de> for RulePerKind {
 						let children = access.newtype_variant::<HashSet<String>>()?;
 						Ok(RulePerKind::RejectIfChildrenDirectoriesArePresent(children))
 					}
+					Fields::RejectFilesLargerThan => Ok(RulePerKind::RejectFilesLargerThan(
+						access.newtype_variant::<u64>()?,
+					)),
+					Fields::RejectFilesNotModifiedWithin => Ok(
+						RulePerKind::RejectFilesNotModifiedWithin(access.newtype_variant::<u64>()?),
+					),
+					Fields::RejectFilesByContentKind => Ok(RulePerKind::RejectFilesByContentKind(
+						access.newtype_variant::<Vec<ContentKind>>()?,
+					)),
+					Fields::RejectIfDeeperThan => Ok(RulePerKind::RejectIfDeeperThan(
+						access.newtype_variant::<u32>()?,
+					)),
 				}
 			}
 		}
@@ -771,6 +928,56 @@ impl GitIgnoreRules {
 	}
 }
 
+/// Rules from the `.spacedriveignore` files between a location root and a directory
+pub struct SpacedriveIgnoreRules {
+	rules: RulePerKind,
+}
+
+impl SpacedriveIgnoreRules {
+	pub async fn get_rules(location_root: &Path, current: &Path) -> Option<Self> {
+		use gix_ignore::glob::search::pattern::List;
+
+		let files = current
+			.ancestors()
+			.take_while(|p| p.starts_with(location_root))
+			.map(|p| p.join(SPACEDRIVE_IGNORE_FILE))
+			.collect::<Vec<_>>();
+		let location_root = location_root.to_path_buf();
+
+		tokio::task::spawn_blocking(move || {
+			let mut search = Search::default();
+			let mut buf = Vec::new();
+			// Later lists take precedence, so files closer to `current` go last. Each list
+			// only matches paths below its own directory.
+			for file in files.into_iter().rev() {
+				if let Ok(Some(list)) = List::from_file(file, Some(&location_root), true, &mut buf)
+				{
+					search.patterns.push(list);
+				}
+			}
+			(!search.patterns.is_empty()).then(|| Self {
+				rules: RulePerKind::IgnoredBySpacedrive(location_root, search),
+			})
+		})
+		.await
+		.ok()
+		.flatten()
+	}
+}
+
+impl From<SpacedriveIgnoreRules> for IndexerRule {
+	fn from(ignore: SpacedriveIgnoreRules) -> Self {
+		Self {
+			id: None,
+			name: "Spacedrive ignore".to_string(),
+			default: true,
+			rules: vec![ignore.rules],
+			date_created: Utc::now(),
+			date_modified: Utc::now(),
+		}
+	}
+}
+
 pub async fn build_default_ruler(
 	toggles: RuleToggles,
 	location_root: &Path,
@@ -805,5 +1012,447 @@ pub async fn build_default_ruler(
 			base.push(rule);
 		}
 	}
+	if let Some(ignore) = SpacedriveIgnoreRules::get_rules(location_root, current).await {
+		base.push(ignore.into());
+	}
 	IndexerRuler::new(base)
 }
+
+// -------- Location rules (stored in indexer_rules, linked per location) --------
+
+/// Limits a location applies on top of the rule toggles
+///
+/// Set through the location add and update actions and stored as a single
+/// indexer rule linked to the location.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct IndexerLimits {
+	/// Skip files larger than this many bytes
+	pub max_file_size: Option<u64>,
+	/// Skip files not modified within this many days
+	pub modified_within_days: Option<u32>,
+	/// Skip files of these content kinds, identified by extension
+	#[serde(default)]
+	pub excluded_kinds: Vec<ContentKind>,
+	/// Skip entries more than this many levels below the location root
+	pub max_depth: Option<u32>,
+}
+
+impl IndexerLimits {
+	pub fn validate(&self) -> Result<(), String> {
+		if self.modified_within_days == Some(0) {
+			return Err("modified_within_days must be at least 1".to_string());
+		}
+		if self.max_depth == Some(0) {
+			return Err("max_depth must be at least 1".to_string());
+		}
+		Ok(())
+	}
+
+	pub fn to_rules(&self) -> Vec<RulePerKind> {
+		let mut rules = Vec::new();
+		if let Some(max_size) = self.max_file_size {
+			rules.push(RulePerKind::RejectFilesLargerThan(max_size));
+		}
+		if let Some(days) = self.modified_within_days {
+			rules.push(RulePerKind::RejectFilesNotModifiedWithin(
+				u64::from(days) * 24 * 60 * 60,
+			));
+		}
+		if !self.excluded_kinds.is_empty() {
+			rules.push(RulePerKind::RejectFilesByContentKind(
+				self.excluded_kinds.clone(),
+			));
+		}
+		if let Some(max_depth) = self.max_depth {
+			rules.push(RulePerKind::RejectIfDeeperThan(max_depth));
+		}
+		rules
+	}
+}
+
+/// Indexer rules stored for a location, and the root its depth limits count from
+#[derive(Debug, Clone)]
+pub struct LocationRules {
+	pub root: PathBuf,
+	pub rules: Vec<IndexerRule>,
+	/// The library's file types, which content kind limits identify files with
+	pub file_types: Option<Arc<FileTypeRegistry>>,
+}
+
+impl LocationRules {
+	/// No extra rules, for ephemeral indexing
+	pub fn none(root: impl Into<PathBuf>) -> Self {
+		Self {
+			root: root.into(),
+			rules: Vec::new(),
+			file_types: None,
+		}
+	}
+
+	pub async fn load(
+		db: &impl sea_orm::ConnectionTrait,
+		location_id: i32,
+		root: impl Into<PathBuf>,
+		file_types: Arc<FileTypeRegistry>,
+	) -> Result<Self, Error> {
+		use crate::infra::db::entities::{indexer_rule, location_indexer_rule};
+		use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+
+		let rule_ids = location_indexer_rule::Entity::find()
+			.filter(location_indexer_rule::Column::LocationId.eq(location_id))
+			.all(db)
+			.await?
+			.into_iter()
+			.map(|link| link.indexer_rule_id)
+			.collect::<Vec<_>>();
+
+		let mut rules = Vec::with_capacity(rule_ids.len());
+		if !rule_ids.is_empty() {
+			for model in indexer_rule::Entity::find()
+				.filter(indexer_rule::Column::Id.is_in(rule_ids))
+				.all(db)
+				.await?
+			{
+				rules.push(IndexerRule {
+					id: Some(model.id),
+					name: model.name,
+					default: model.default,
+					rules: rmp_serde::from_slice(&model.rules_blob)?,
+					date_created: model.created_at,
+					date_modified: model.updated_at,
+				});
+			}
+		}
+
+		Ok(Self {
+			root: root.into(),
+			rules,
+			file_types: Some(file_types),
+		})
+	}
+
+	/// Depth of `path` below the root, `None` when it is outside of it
+	pub fn depth(&self, path: &Path) -> Option<u32> {
+		path.strip_prefix(&self.root)
+			.ok()
+			.map(|relative| relative.components().count() as u32)
+	}
+
+	/// Ruler for the entries of `dir`: the toggled defaults plus the location's rules
+	pub async fn ruler(&self, toggles: RuleToggles, dir: &Path) -> IndexerRuler {
+		let mut ruler = build_default_ruler(toggles, &self.root, dir).await;
+		ruler.extend(self.rules.iter().cloned());
+		ruler
+	}
+
+	/// Metadata of an entry for the rules, with its depth and content kind filled in
+	pub fn metadata(
+		&self,
+		path: &Path,
+		is_dir: bool,
+		size: Option<u64>,
+		modified: Option<SystemTime>,
+	) -> LocationEntryMetadata {
+		LocationEntryMetadata {
+			is_dir,
+			size,
+			modified,
+			depth: self.depth(path),
+			content_kind: self
+				.file_types
+				.as_ref()
+				.filter(|_| !is_dir)
+				.map(|file_types| file_types.identify_by_extension(path)),
+		}
+	}
+}
+
+/// Metadata of an entry below a location root, see [`LocationRules::metadata`]
+#[derive(Debug, Clone, Copy)]
+pub struct LocationEntryMetadata {
+	pub is_dir: bool,
+	pub size: Option<u64>,
+	pub modified: Option<SystemTime>,
+	pub depth: Option<u32>,
+	pub content_kind: Option<ContentKind>,
+}
+
+impl MetadataForIndexerRules for LocationEntryMetadata {
+	fn is_dir(&self) -> bool {
+		self.is_dir
+	}
+
+	fn size(&self) -> Option<u64> {
+		self.size
+	}
+
+	fn modified(&self) -> Option<SystemTime> {
+		self.modified
+	}
+
+	fn depth(&self) -> Option<u32> {
+		self.depth
+	}
+
+	fn content_kind(&self) -> Option<ContentKind> {
+		self.content_kind
+	}
+}
+
+/// Replace the limits stored for a location, removing them when `limits` is empty
+pub async fn save_location_limits(
+	db: &impl sea_orm::ConnectionTrait,
+	location_id: i32,
+	location_uuid: uuid::Uuid,
+	limits: &IndexerLimits,
+) -> Result<(), Error> {
+	use crate::infra::db::entities::{indexer_rule, location_indexer_rule};
+	use sea_orm::{ActiveModelTrait, ActiveValue::Set, ColumnTrait, EntityTrait, QueryFilter};
+
+	let linked = location_indexer_rule::Entity::find()
+		.filter(location_indexer_rule::Column::LocationId.eq(location_id))
+		.all(db)
+		.await?
+		.into_iter()
+		.map(|link| link.indexer_rule_id)
+		.collect::<Vec<_>>();
+
+	// Removing the rules cascades to their links
+	if !linked.is_empty() {
+		indexer_rule::Entity::delete_many()
+			.filter(indexer_rule::Column::Id.is_in(linked))
+			.exec(db)
+			.await?;
+	}
+
+	let rules = limits.to_rules();
+	if rules.is_empty() {
+		return Ok(());
+	}
+
+	let now = Utc::now();
+	let rule = indexer_rule::ActiveModel {
+		name: Set(format!("Location limits {}", location_uuid)),
+		default: Set(false),
+		rules_blob: Set(rmp_serde::to_vec_named(&rules)?),
+		created_at: Set(now),
+		updated_at: Set(now),
+		..Default::default()
+	}
+	.insert(db)
+	.await?;
+
+	location_indexer_rule::ActiveModel {
+		location_id: Set(location_id),
+		indexer_rule_id: Set(rule.id),
+	}
+	.insert(db)
+	.await?;
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type Meta = LocationEntryMetadata;
+
+	fn file(size: u64, age_days: u64, depth: u32) -> Meta {
+		Meta {
+			is_dir: false,
+			size: Some(size),
+			modified: Some(SystemTime::now() - Duration::from_secs(age_days * 24 * 60 * 60)),
+			depth: Some(depth),
+			content_kind: Some(ContentKind::Video),
+		}
+	}
+
+	fn ruler(limits: IndexerLimits) -> IndexerRuler {
+		IndexerRuler::new(vec![IndexerRule {
+			id: None,
+			name: "limits".to_string(),
+			default: false,
+			rules: limits.to_rules(),
+			date_created: Utc::now(),
+			date_modified: Utc::now(),
+		}])
+	}
+
+	#[tokio::test]
+	async fn limits_reject_files() {
+		let ruler = ruler(IndexerLimits {
+			max_file_size: Some(1024),
+			modified_within_days: Some(365),
+			excluded_kinds: vec![ContentKind::Executable],
+			max_depth: Some(2),
+		});
+
+		let cases = [
+			("movie.mkv", file(512, 10, 1), RulerDecision::Accept),
+			("movie.mkv", file(4096, 10, 1), RulerDecision::Reject),
+			("movie.mkv", file(512, 3 * 365, 1), RulerDecision::Reject),
+			(
+				"setup.exe",
+				Meta {
+					content_kind: Some(ContentKind::Executable),
+					..file(512, 10, 1)
+				},
+				RulerDecision::Reject,
+			),
+			("movie.mkv", file(512, 10, 3), RulerDecision::Reject),
+		];
+		for (name, metadata, expected) in cases {
+			assert_eq!(
+				ruler.evaluate_path(name, &metadata).await.unwrap(),
+				expected,
+				"{name}"
+			);
+		}
+	}
+
+	#[tokio::test]
+	async fn limits_skip_directories_and_unknown_metadata() {
+		let ruler = ruler(IndexerLimits {
+			max_file_size: Some(1024),
+			modified_within_days: Some(1),
+			excluded_kinds: vec![ContentKind::Executable],
+			max_depth: Some(2),
+		});
+
+		let big_old_dir = Meta {
+			is_dir: true,
+			size: Some(u64::MAX),
+			modified: Some(SystemTime::UNIX_EPOCH),
+			depth: Some(2),
+			content_kind: None,
+		};
+		assert_eq!(
+			ruler.evaluate_path("bin.exe", &big_old_dir).await.unwrap(),
+			RulerDecision::Accept
+		);
+
+		let unknown = Meta {
+			is_dir: false,
+			size: None,
+			modified: None,
+			depth: None,
+			content_kind: None,
+		};
+		assert_eq!(
+			ruler.evaluate_path("notes.txt", &unknown).await.unwrap(),
+			RulerDecision::Accept
+		);
+
+		let deep_dir = Meta {
+			depth: Some(3),
+			..big_old_dir
+		};
+		assert_eq!(
+			ruler.evaluate_path("nested", &deep_dir).await.unwrap(),
+			RulerDecision::Reject
+		);
+	}
+
+	#[test]
+	fn location_metadata_uses_the_library_file_types() {
+		let mut location = LocationRules::none("/photos");
+		let metadata = location.metadata(Path::new("/photos/a/setup.exe"), false, None, None);
+		assert_eq!(metadata.depth, Some(2));
+		assert_eq!(metadata.content_kind, None);
+
+		location.file_types = Some(Arc::new(FileTypeRegistry::new()));
+		let metadata = location.metadata(Path::new("/photos/a/setup.exe"), false, None, None);
+		assert_eq!(metadata.content_kind, Some(ContentKind::Executable));
+		let metadata = location.metadata(Path::new("/photos/a.exe"), true, None, None);
+		assert_eq!(metadata.content_kind, None);
+	}
+
+	#[tokio::test]
+	async fn spacedrive_ignore_files_nest() {
+		let root = tempfile::tempdir().unwrap();
+		let root = root.path();
+		let sub = root.join("sub");
+		std::fs::create_dir_all(sub.join("renders")).unwrap();
+		std::fs::write(root.join(SPACEDRIVE_IGNORE_FILE), "*.log\nrenders/\n").unwrap();
+		std::fs::write(sub.join(SPACEDRIVE_IGNORE_FILE), "!keep.log\n*.tmp\n").unwrap();
+
+		let toggles = RuleToggles {
+			no_system_files: false,
+			no_hidden: false,
+			no_git: false,
+			gitignore: false,
+			only_images: false,
+			no_dev_dirs: false,
+		};
+		let log = file(1, 0, 1);
+		let in_root = build_default_ruler(toggles, root, root).await;
+		assert_eq!(
+			in_root
+				.evaluate_path(root.join("debug.log"), &log)
+				.await
+				.unwrap(),
+			RulerDecision::Reject
+		);
+		assert_eq!(
+			in_root
+				.evaluate_path(root.join("cache.tmp"), &log)
+				.await
+				.unwrap(),
+			RulerDecision::Accept
+		);
+
+		let in_sub = build_default_ruler(toggles, root, &sub).await;
+		for (name, expected) in [
+			("debug.log", RulerDecision::Reject),
+			("keep.log", RulerDecision::Accept),
+			("cache.tmp", RulerDecision::Reject),
+			("notes.txt", RulerDecision::Accept),
+		] {
+			assert_eq!(
+				in_sub.evaluate_path(sub.join(name), &log).await.unwrap(),
+				expected,
+				"{name}"
+			);
+		}
+
+		let dir = Meta {
+			is_dir: true,
+			size: None,
+			modified: None,
+			depth: None,
+			content_kind: None,
+		};
+		assert_eq!(
+			in_sub
+				.evaluate_path(sub.join("renders"), &dir)
+				.await
+				.unwrap(),
+			RulerDecision::Reject
+		);
+	}
+
+	#[test]
+	fn limits_round_trip_through_rmp() {
+		let rules = IndexerLimits {
+			max_file_size: Some(10 * 1024 * 1024 * 1024),
+			modified_within_days: Some(730),
+			excluded_kinds: vec![ContentKind::Executable, ContentKind::Package],
+			max_depth: Some(4),
+		}
+		.to_rules();
+
+		let blob = rmp_serde::to_vec_named(&rules).unwrap();
+		let decoded: Vec<RulePerKind> = rmp_serde::from_slice(&blob).unwrap();
+
+		assert!(matches!(
+			decoded.as_slice(),
+			[
+				RulePerKind::RejectFilesLargerThan(10737418240),
+				RulePerKind::RejectFilesNotModifiedWithin(63072000),
+				RulePerKind::RejectFilesByContentKind(kinds),
+				RulePerKind::RejectIfDeeperThan(4),
+			] if kinds == &[ContentKind::Executable, ContentKind::Package]
+		));
+	}
+}
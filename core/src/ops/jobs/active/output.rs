@@ -1,4 +1,7 @@
-use crate::infra::job::types::{ActionContextInfo, JobStatus};
+use crate::infra::job::{
+	scheduler::WaitReason,
+	types::{ActionContextInfo, JobStatus},
+};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use uuid::Uuid;
@@ -11,6 +14,7 @@ pub struct ActiveJobItem {
 	pub progress: f32,
 	pub action_type: Option<String>,
 	pub action_context: Option<ActionContextInfo>,
+	pub waiting_reason: Option<WaitReason>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -61,6 +61,7 @@ impl LibraryQuery for ActiveJobsQuery {
 					progress: j.progress,
 					action_type: j.action_type,
 					action_context: j.action_context,
+					waiting_reason: j.waiting_reason,
 				}
 			})
 			.collect();
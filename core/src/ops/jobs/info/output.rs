@@ -13,4 +13,5 @@ pub struct JobInfoOutput {
 	pub started_at: Option<DateTime<Utc>>,
 	pub completed_at: Option<DateTime<Utc>>,
 	pub error_message: Option<String>,
+	pub waiting_reason: Option<crate::infra::job::scheduler::WaitReason>,
 }
@@ -53,6 +53,7 @@ impl LibraryQuery for JobInfoQuery {
 			started_at: j.started_at,
 			completed_at: j.completed_at,
 			error_message: j.error_message,
+			waiting_reason: j.waiting_reason,
 		}))
 	}
 }
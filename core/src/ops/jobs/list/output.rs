@@ -1,4 +1,4 @@
-use crate::infra::job::types::ActionContextInfo;
+use crate::infra::job::{scheduler::WaitReason, types::ActionContextInfo};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -16,6 +16,7 @@ pub struct JobListItem {
 	pub created_at: DateTime<Utc>,
 	pub started_at: Option<DateTime<Utc>>,
 	pub completed_at: Option<DateTime<Utc>>,
+	pub waiting_reason: Option<WaitReason>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -4,6 +4,7 @@ pub mod copy_metadata;
 pub mod info;
 pub mod list;
 pub mod remote_list;
+pub mod schedule;
 
 pub use active::*;
 pub use control::*;
@@ -0,0 +1,74 @@
+//! Create a job schedule
+
+use crate::{
+	context::CoreContext,
+	infra::{
+		action::{error::ActionResult, LibraryAction},
+		job::schedule::{JobSchedule, ScheduleTrigger},
+	},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct JobScheduleCreateInput {
+	/// Display name of the schedule
+	pub name: String,
+	/// Registered job type to dispatch, e.g. "indexer"
+	pub job_name: String,
+	/// Job arguments, deserialized into the job like `dispatch_by_name` does
+	#[serde(default)]
+	pub job_args: serde_json::Value,
+	pub trigger: ScheduleTrigger,
+	/// Run once after a restart if runs were missed while the daemon was down
+	#[serde(default = "default_catch_up")]
+	pub catch_up: bool,
+}
+
+fn default_catch_up() -> bool {
+	true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobScheduleCreateAction {
+	input: JobScheduleCreateInput,
+}
+
+impl LibraryAction for JobScheduleCreateAction {
+	type Input = JobScheduleCreateInput;
+	type Output = JobSchedule;
+
+	fn from_input(input: JobScheduleCreateInput) -> Result<Self, String> {
+		if input.name.trim().is_empty() {
+			return Err("Schedule name cannot be empty".to_string());
+		}
+
+		Ok(Self { input })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"jobs.schedule.create"
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> ActionResult<Self::Output> {
+		let input = self.input;
+
+		Ok(library
+			.jobs()
+			.create_schedule(
+				input.name,
+				input.job_name,
+				input.job_args,
+				input.trigger,
+				input.catch_up,
+			)
+			.await?)
+	}
+}
+
+crate::register_library_action!(JobScheduleCreateAction, "jobs.schedule.create");
@@ -0,0 +1,55 @@
+//! Delete a job schedule
+
+use crate::{
+	context::CoreContext,
+	infra::action::{error::ActionResult, LibraryAction},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct JobScheduleDeleteInput {
+	pub schedule_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct JobScheduleDeleteOutput {
+	pub schedule_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobScheduleDeleteAction {
+	input: JobScheduleDeleteInput,
+}
+
+impl LibraryAction for JobScheduleDeleteAction {
+	type Input = JobScheduleDeleteInput;
+	type Output = JobScheduleDeleteOutput;
+
+	fn from_input(input: JobScheduleDeleteInput) -> Result<Self, String> {
+		Ok(Self { input })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"jobs.schedule.delete"
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> ActionResult<Self::Output> {
+		library
+			.jobs()
+			.delete_schedule(self.input.schedule_id)
+			.await?;
+
+		Ok(JobScheduleDeleteOutput {
+			schedule_id: self.input.schedule_id,
+		})
+	}
+}
+
+crate::register_library_action!(JobScheduleDeleteAction, "jobs.schedule.delete");
@@ -0,0 +1,61 @@
+//! List job schedules
+
+use crate::{
+	context::CoreContext,
+	infra::{
+		job::schedule::JobSchedule,
+		query::{LibraryQuery, QueryError, QueryResult},
+	},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct JobScheduleListInput {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct JobScheduleListOutput {
+	pub schedules: Vec<JobSchedule>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct JobScheduleListQuery {
+	pub input: JobScheduleListInput,
+}
+
+impl LibraryQuery for JobScheduleListQuery {
+	type Input = JobScheduleListInput;
+	type Output = JobScheduleListOutput;
+
+	fn from_input(input: Self::Input) -> QueryResult<Self> {
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		context: Arc<CoreContext>,
+		session: crate::infra::api::SessionContext,
+	) -> QueryResult<Self::Output> {
+		let library_id = session
+			.current_library_id
+			.ok_or_else(|| QueryError::Internal("No library selected".to_string()))?;
+		let library = context
+			.libraries()
+			.await
+			.get_library(library_id)
+			.await
+			.ok_or_else(|| QueryError::LibraryNotFound(library_id))?;
+
+		let mut schedules = library
+			.jobs()
+			.list_schedules()
+			.await
+			.map_err(|e| QueryError::Internal(e.to_string()))?;
+		schedules.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+		Ok(JobScheduleListOutput { schedules })
+	}
+}
+
+crate::register_library_query!(JobScheduleListQuery, "jobs.schedule.list");
@@ -0,0 +1,13 @@
+//! Recurring job schedules
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod pause;
+pub mod resume;
+
+pub use create::*;
+pub use delete::*;
+pub use list::*;
+pub use pause::*;
+pub use resume::*;
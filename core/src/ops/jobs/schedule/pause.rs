@@ -0,0 +1,49 @@
+//! Pause a job schedule
+
+use crate::{
+	context::CoreContext,
+	infra::{
+		action::{error::ActionResult, LibraryAction},
+		job::schedule::JobSchedule,
+	},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct JobSchedulePauseInput {
+	pub schedule_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobSchedulePauseAction {
+	input: JobSchedulePauseInput,
+}
+
+impl LibraryAction for JobSchedulePauseAction {
+	type Input = JobSchedulePauseInput;
+	type Output = JobSchedule;
+
+	fn from_input(input: JobSchedulePauseInput) -> Result<Self, String> {
+		Ok(Self { input })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"jobs.schedule.pause"
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> ActionResult<Self::Output> {
+		Ok(library
+			.jobs()
+			.set_schedule_paused(self.input.schedule_id, true)
+			.await?)
+	}
+}
+
+crate::register_library_action!(JobSchedulePauseAction, "jobs.schedule.pause");
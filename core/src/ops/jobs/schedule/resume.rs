@@ -0,0 +1,49 @@
+//! Resume a paused job schedule
+
+use crate::{
+	context::CoreContext,
+	infra::{
+		action::{error::ActionResult, LibraryAction},
+		job::schedule::JobSchedule,
+	},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct JobScheduleResumeInput {
+	pub schedule_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobScheduleResumeAction {
+	input: JobScheduleResumeInput,
+}
+
+impl LibraryAction for JobScheduleResumeAction {
+	type Input = JobScheduleResumeInput;
+	type Output = JobSchedule;
+
+	fn from_input(input: JobScheduleResumeInput) -> Result<Self, String> {
+		Ok(Self { input })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"jobs.schedule.resume"
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> ActionResult<Self::Output> {
+		Ok(library
+			.jobs()
+			.set_schedule_paused(self.input.schedule_id, false)
+			.await?)
+	}
+}
+
+crate::register_library_action!(JobScheduleResumeAction, "jobs.schedule.resume");
@@ -10,7 +10,7 @@ use crate::{
 	},
 	infra::db::entities,
 	location::manager::LocationManager,
-	ops::indexing::IndexMode,
+	ops::indexing::{IndexMode, IndexerLimits},
 };
 use async_trait::async_trait;
 use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
@@ -26,6 +26,9 @@ pub struct LocationAddInput {
 	pub name: Option<String>,
 	pub mode: IndexMode,
 	pub job_policies: Option<serde_json::Value>,
+	/// Size, age, content kind and depth limits applied when indexing
+	#[serde(default)]
+	pub indexer_limits: Option<IndexerLimits>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -97,6 +100,7 @@ impl LibraryAction for LocationAddAction {
 				location_mode,
 				Some(action_context),
 				job_policies_json,
+				self.input.indexer_limits.clone(),
 				&context.volume_manager,
 			)
 			.await
@@ -213,6 +217,15 @@ impl LibraryAction for LocationAddAction {
 			}
 		}
 
+		if let Some(limits) = &self.input.indexer_limits {
+			limits
+				.validate()
+				.map_err(|message| ActionError::Validation {
+					field: "indexer_limits".to_string(),
+					message,
+				})?;
+		}
+
 		// Check for duplicate locations
 		// TODO: Implement proper duplicate detection for both Physical and Cloud paths
 
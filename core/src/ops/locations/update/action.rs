@@ -10,6 +10,7 @@ use crate::{
 		LibraryAction,
 	},
 	infra::db::entities,
+	ops::indexing::{rules::save_location_limits, IndexerLimits},
 };
 use async_trait::async_trait;
 use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
@@ -29,6 +30,10 @@ pub struct LocationUpdateInput {
 
 	/// Optional job policies to update
 	pub job_policies: Option<JobPolicies>,
+
+	/// Optional indexer limits, replacing the current ones. Empty limits remove them.
+	#[serde(default)]
+	pub indexer_limits: Option<IndexerLimits>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,6 +89,15 @@ impl LibraryAction for LocationUpdateAction {
 		// Execute update
 		let updated_location = active.update(db).await.map_err(ActionError::SeaOrm)?;
 
+		// Applied from the next index of the location
+		if let Some(ref limits) = self.input.indexer_limits {
+			save_location_limits(db, location.id, location.uuid, limits)
+				.await
+				.map_err(|e| {
+					ActionError::Internal(format!("Failed to save indexer limits: {}", e))
+				})?;
+		}
+
 		// Emit ResourceChanged event for UI reactivity using EventEmitter trait
 		use crate::domain::resource::EventEmitter;
 		crate::domain::Location::emit_changed_batch(db, &context.events, &[updated_location.uuid])
@@ -115,6 +129,15 @@ impl LibraryAction for LocationUpdateAction {
 			return Err(ActionError::LocationNotFound(self.input.id));
 		}
 
+		if let Some(limits) = &self.input.indexer_limits {
+			limits
+				.validate()
+				.map_err(|message| ActionError::Validation {
+					field: "indexer_limits".to_string(),
+					message,
+				})?;
+		}
+
 		Ok(crate::infra::action::ValidationResult::Success { metadata: None })
 	}
 }
@@ -0,0 +1,79 @@
+//! Embedding generation action handlers
+
+use super::job::{EmbeddingJob, EmbeddingJobConfig};
+use crate::{
+	context::CoreContext,
+	infra::action::{error::ActionError, LibraryAction},
+	ops::models::EmbeddingModel,
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GenerateEmbeddingsInput {
+	/// Embedding model id, defaults to "all-MiniLM-L6-v2"
+	pub model: Option<String>,
+	/// Recompute vectors for files that already have one
+	#[serde(default)]
+	pub reprocess: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct GenerateEmbeddingsOutput {
+	/// Job ID for tracking embedding progress
+	pub job_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GenerateEmbeddingsAction {
+	input: GenerateEmbeddingsInput,
+}
+
+impl LibraryAction for GenerateEmbeddingsAction {
+	type Input = GenerateEmbeddingsInput;
+	type Output = GenerateEmbeddingsOutput;
+
+	fn from_input(input: GenerateEmbeddingsInput) -> Result<Self, String> {
+		if let Some(model) = &input.model {
+			EmbeddingModel::from_str(model)
+				.ok_or_else(|| format!("Invalid embedding model: {}", model))?;
+		}
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let model = self
+			.input
+			.model
+			.as_deref()
+			.and_then(EmbeddingModel::from_str)
+			.unwrap_or_default();
+
+		// The job downloads the model during discovery if needed
+		let job = EmbeddingJob::new(EmbeddingJobConfig {
+			model: model.id().to_string(),
+			reprocess: self.input.reprocess,
+		});
+
+		let job_handle = library
+			.jobs()
+			.dispatch(job)
+			.await
+			.map_err(|e| ActionError::Internal(format!("Failed to dispatch job: {}", e)))?;
+
+		Ok(GenerateEmbeddingsOutput {
+			job_id: job_handle.id().to_string(),
+		})
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"media.embeddings.generate"
+	}
+}
+
+crate::register_library_action!(GenerateEmbeddingsAction, "media.embeddings.generate");
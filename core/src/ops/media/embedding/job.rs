@@ -0,0 +1,399 @@
+//! Embedding generation job for semantic search
+
+use super::{embedding_text, read_embedding_sidecar, write_embedding_sidecar, EmbeddingSidecar};
+use crate::{
+	infra::{
+		db::entities::sidecar,
+		job::{prelude::*, traits::DynJob},
+	},
+	ops::{
+		models::{EmbeddingModel, TextEmbedder},
+		sidecar::types::SidecarKind,
+	},
+};
+use sea_orm::{ColumnTrait, ConnectionTrait, EntityTrait, QueryFilter, QuerySelect, Statement};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashSet;
+use tracing::warn;
+use uuid::Uuid;
+
+/// Number of texts embedded per model invocation
+const BATCH_SIZE: usize = 32;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct EmbeddingJobConfig {
+	/// Embedding model id (see `EmbeddingModel`)
+	pub model: String,
+	/// Recompute vectors that already have an embeddings sidecar
+	pub reprocess: bool,
+}
+
+impl Default for EmbeddingJobConfig {
+	fn default() -> Self {
+		Self {
+			model: EmbeddingModel::default().id().to_string(),
+			reprocess: false,
+		}
+	}
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmbeddingJobState {
+	phase: EmbeddingPhase,
+	/// Content identity ids still to embed
+	pending: Vec<i32>,
+	processed: usize,
+	success_count: usize,
+	error_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum EmbeddingPhase {
+	Discovery,
+	Processing,
+	Complete,
+}
+
+#[derive(Debug, Serialize, Deserialize, Job)]
+pub struct EmbeddingJob {
+	config: EmbeddingJobConfig,
+	state: EmbeddingJobState,
+}
+
+impl EmbeddingJob {
+	pub fn new(config: EmbeddingJobConfig) -> Self {
+		Self {
+			config,
+			state: EmbeddingJobState {
+				phase: EmbeddingPhase::Discovery,
+				pending: Vec::new(),
+				processed: 0,
+				success_count: 0,
+				error_count: 0,
+			},
+		}
+	}
+
+	fn output(&self) -> EmbeddingJobOutput {
+		EmbeddingJobOutput {
+			total_processed: self.state.processed,
+			success_count: self.state.success_count,
+			error_count: self.state.error_count,
+		}
+	}
+}
+
+impl Job for EmbeddingJob {
+	const NAME: &'static str = "embedding_generation";
+	const RESUMABLE: bool = true;
+	const DESCRIPTION: Option<&'static str> = Some("Generate text embeddings for semantic search");
+	// Embeddings are built from extracted text, so wait for the extractors
+	const DEPENDENCIES: &'static [&'static str] = &["ocr", "speech_to_text"];
+
+	fn required_resources() -> Vec<ResourceRequirement> {
+		vec![ResourceRequirement::Named("ml_inference")]
+	}
+}
+
+#[async_trait::async_trait]
+impl JobHandler for EmbeddingJob {
+	type Output = EmbeddingJobOutput;
+
+	async fn run(&mut self, ctx: JobContext<'_>) -> JobResult<Self::Output> {
+		let model = EmbeddingModel::from_str(&self.config.model)
+			.ok_or_else(|| JobError::execution(format!("Invalid model: {}", self.config.model)))?;
+
+		match self.state.phase {
+			EmbeddingPhase::Discovery => {
+				self.run_discovery(&ctx, model).await?;
+				self.state.phase = EmbeddingPhase::Processing;
+				ctx.checkpoint().await?;
+			}
+			EmbeddingPhase::Processing => {}
+			EmbeddingPhase::Complete => return Ok(self.output()),
+		}
+
+		let data_dir = crate::config::default_data_dir()
+			.map_err(|e| JobError::execution(format!("Failed to get data dir: {}", e)))?;
+		let embedder = TextEmbedder::shared(model, &data_dir)
+			.await
+			.map_err(|e| JobError::execution(e.to_string()))?;
+
+		let library = ctx.library_arc();
+		let index = library
+			.vector_index()
+			.await
+			.map_err(|e| JobError::execution(e.to_string()))?;
+
+		let total = self.state.processed + self.state.pending.len();
+		ctx.log(format!("Embedding {} files", self.state.pending.len()));
+
+		while !self.state.pending.is_empty() {
+			ctx.check_interrupt().await?;
+
+			let batch_len = self.state.pending.len().min(BATCH_SIZE);
+			let batch: Vec<i32> = self.state.pending[..batch_len].to_vec();
+			let documents = load_documents(ctx.library_db(), &batch).await?;
+
+			let texts = documents.iter().map(|doc| doc.text.clone()).collect();
+			match embedder.embed(texts).await {
+				Ok(vectors) => {
+					for (doc, vector) in documents.iter().zip(vectors) {
+						let sidecar = EmbeddingSidecar {
+							model: model.id().to_string(),
+							dimensions: vector.len(),
+							vector,
+						};
+
+						if let Err(e) = write_embedding_sidecar(&library, &doc.uuid, &sidecar).await
+						{
+							ctx.log(format!(
+								"ERROR: Failed to write embedding for {}: {}",
+								doc.uuid, e
+							));
+							self.state.error_count += 1;
+							continue;
+						}
+
+						match index.write().await.insert(doc.uuid, sidecar.vector) {
+							Ok(()) => self.state.success_count += 1,
+							Err(e) => {
+								warn!("Failed to index embedding for {}: {}", doc.uuid, e);
+								self.state.error_count += 1;
+							}
+						}
+					}
+				}
+				Err(e) => {
+					ctx.log(format!("ERROR: Embedding batch failed: {}", e));
+					self.state.error_count += documents.len();
+				}
+			}
+
+			self.state.pending.drain(..batch_len);
+			self.state.processed += batch_len;
+
+			ctx.progress(Progress::Count {
+				current: self.state.processed,
+				total,
+			});
+
+			if let Err(e) = index.write().await.save().await {
+				warn!("Failed to save vector index: {}", e);
+			}
+			ctx.checkpoint().await?;
+		}
+
+		self.state.phase = EmbeddingPhase::Complete;
+		ctx.log(format!(
+			"Embedding complete: {} success, {} errors",
+			self.state.success_count, self.state.error_count
+		));
+
+		Ok(self.output())
+	}
+}
+
+impl EmbeddingJob {
+	async fn run_discovery(
+		&mut self,
+		ctx: &JobContext<'_>,
+		model: EmbeddingModel,
+	) -> JobResult<()> {
+		let data_dir = crate::config::default_data_dir()
+			.map_err(|e| JobError::execution(format!("Failed to get data dir: {}", e)))?;
+		crate::ops::models::ensure_embedding_model(ctx, model, &data_dir).await?;
+
+		ctx.log("Model ready, discovering files...");
+		ctx.progress(Progress::indeterminate("Discovering files to embed"));
+
+		let library = ctx.library_arc();
+		let db = ctx.library_db();
+		let index = library
+			.vector_index()
+			.await
+			.map_err(|e| JobError::execution(e.to_string()))?;
+
+		if index.read().await.model() != model.id() {
+			return Err(JobError::execution(format!(
+				"Library vector index uses a different model than {}",
+				model.id()
+			)));
+		}
+
+		let rows = db
+			.query_all(Statement::from_string(
+				sea_orm::DatabaseBackend::Sqlite,
+				r#"
+				SELECT ci.id AS id, ci.uuid AS uuid
+				FROM content_identities ci
+				WHERE ci.uuid IS NOT NULL
+				AND EXISTS (SELECT 1 FROM entries e WHERE e.content_id = ci.id)
+				"#
+				.to_string(),
+			))
+			.await?;
+		let candidates: Vec<(i32, Uuid)> = rows
+			.iter()
+			.filter_map(|row| {
+				Some((
+					row.try_get::<i32>("", "id").ok()?,
+					row.try_get::<Uuid>("", "uuid").ok()?,
+				))
+			})
+			.collect();
+
+		let embedded: HashSet<Uuid> = sidecar::Entity::find()
+			.select_only()
+			.column(sidecar::Column::ContentUuid)
+			.filter(sidecar::Column::Kind.eq(SidecarKind::Embeddings.as_str()))
+			.filter(sidecar::Column::Variant.eq(model.id()))
+			.into_tuple::<Uuid>()
+			.all(db)
+			.await?
+			.into_iter()
+			.collect();
+
+		let mut index = index.write().await;
+		let mut restored = 0;
+
+		for (id, uuid) in &candidates {
+			if self.config.reprocess || !embedded.contains(uuid) {
+				self.state.pending.push(*id);
+				continue;
+			}
+			if index.contains(uuid) {
+				continue;
+			}
+
+			// The sidecar exists (possibly synced from another device) but the
+			// local index lost it, restore the vector without recomputing it
+			match read_embedding_sidecar(&library, uuid, model.id()).await {
+				Ok(Some(sidecar)) if index.insert(*uuid, sidecar.vector).is_ok() => restored += 1,
+				_ => self.state.pending.push(*id),
+			}
+		}
+
+		// Drop vectors of content that no longer exists in the library
+		let live: HashSet<Uuid> = candidates.iter().map(|(_, uuid)| *uuid).collect();
+		let stale: Vec<Uuid> = index
+			.ids()
+			.iter()
+			.filter(|uuid| !live.contains(uuid))
+			.copied()
+			.collect();
+		for uuid in &stale {
+			index.remove(uuid);
+		}
+
+		if let Err(e) = index.save().await {
+			warn!("Failed to save vector index: {}", e);
+		}
+
+		ctx.log(format!(
+			"Discovery complete: {} to embed, {} restored from sidecars, {} stale removed",
+			self.state.pending.len(),
+			restored,
+			stale.len()
+		));
+
+		Ok(())
+	}
+}
+
+/// Text to embed for a content identity
+struct EmbeddingDocument {
+	uuid: Uuid,
+	text: String,
+}
+
+/// Build embedding texts for a batch of content identities
+///
+/// When several entries share the content, the oldest entry names the document.
+async fn load_documents(
+	db: &sea_orm::DatabaseConnection,
+	content_ids: &[i32],
+) -> JobResult<Vec<EmbeddingDocument>> {
+	let ids = content_ids
+		.iter()
+		.map(|id| id.to_string())
+		.collect::<Vec<_>>()
+		.join(",");
+
+	let rows = db
+		.query_all(Statement::from_string(
+			sea_orm::DatabaseBackend::Sqlite,
+			format!(
+				r#"
+				SELECT
+					ci.uuid AS uuid,
+					ci.text_content AS text_content,
+					e.name AS name,
+					e.extension AS extension,
+					dp.path AS parent_path
+				FROM content_identities ci
+				JOIN entries e ON e.id = (
+					SELECT MIN(id) FROM entries WHERE content_id = ci.id
+				)
+				LEFT JOIN directory_paths dp ON dp.entry_id = e.parent_id
+				WHERE ci.id IN ({}) AND ci.uuid IS NOT NULL
+				"#,
+				ids
+			),
+		))
+		.await?;
+
+	Ok(rows
+		.iter()
+		.filter_map(|row| {
+			let uuid = row.try_get::<Uuid>("", "uuid").ok()?;
+			let name = row.try_get::<String>("", "name").ok()?;
+			let extension = row
+				.try_get::<Option<String>>("", "extension")
+				.ok()
+				.flatten();
+			let parent_path = row
+				.try_get::<Option<String>>("", "parent_path")
+				.ok()
+				.flatten();
+			let text_content = row
+				.try_get::<Option<String>>("", "text_content")
+				.ok()
+				.flatten();
+
+			Some(EmbeddingDocument {
+				uuid,
+				text: embedding_text(
+					&name,
+					extension.as_deref(),
+					parent_path.as_deref(),
+					text_content.as_deref(),
+				),
+			})
+		})
+		.collect())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct EmbeddingJobOutput {
+	pub total_processed: usize,
+	pub success_count: usize,
+	pub error_count: usize,
+}
+
+impl From<EmbeddingJobOutput> for JobOutput {
+	fn from(output: EmbeddingJobOutput) -> Self {
+		JobOutput::EmbeddingGeneration {
+			total_processed: output.total_processed,
+			success_count: output.success_count,
+			error_count: output.error_count,
+		}
+	}
+}
+
+impl DynJob for EmbeddingJob {
+	fn job_name(&self) -> &'static str {
+		"Embedding Generation"
+	}
+}
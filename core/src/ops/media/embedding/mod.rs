@@ -0,0 +1,182 @@
+//! Text embeddings for semantic search
+//!
+//! Each content identity gets one vector per embedding model, computed from its
+//! file name, containing folders and any extracted text (OCR, transcripts). The
+//! vector is stored as an `Embeddings` sidecar whose variant is the model id,
+//! and mirrored into the library's vector index for nearest neighbour search.
+
+pub mod action;
+pub mod job;
+
+pub use action::{GenerateEmbeddingsAction, GenerateEmbeddingsInput, GenerateEmbeddingsOutput};
+pub use job::{EmbeddingJob, EmbeddingJobConfig};
+
+use crate::{
+	library::Library,
+	ops::sidecar::types::{SidecarFormat, SidecarKind, SidecarVariant},
+};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Maximum number of characters of extracted text fed to the model
+///
+/// Sentence transformers truncate input to a few hundred tokens anyway.
+const MAX_TEXT_CHARS: usize = 2000;
+
+/// Number of trailing folders of the containing path included in the text
+const PATH_SEGMENTS: usize = 3;
+
+/// Contents of an embeddings sidecar
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingSidecar {
+	pub model: String,
+	pub dimensions: usize,
+	pub vector: Vec<f32>,
+}
+
+/// Build the text embedded for a file
+///
+/// File names are split on separators so "planning_meeting-whiteboard.jpg"
+/// reads as words, and the last few folders add context such as event names.
+pub fn embedding_text(
+	name: &str,
+	extension: Option<&str>,
+	parent_path: Option<&str>,
+	text_content: Option<&str>,
+) -> String {
+	let mut parts = Vec::new();
+
+	parts.push(humanize(name));
+	if let Some(extension) = extension.filter(|ext| !ext.is_empty()) {
+		parts.push(extension.to_lowercase());
+	}
+
+	if let Some(parent_path) = parent_path {
+		let segments: Vec<&str> = parent_path
+			.split(['/', '\\'])
+			.filter(|segment| !segment.is_empty())
+			.collect();
+		let folders = segments[segments.len().saturating_sub(PATH_SEGMENTS)..]
+			.iter()
+			.map(|segment| humanize(segment))
+			.collect::<Vec<_>>()
+			.join(" / ");
+		if !folders.is_empty() {
+			parts.push(format!("in {}", folders));
+		}
+	}
+
+	if let Some(text) = text_content.map(str::trim).filter(|text| !text.is_empty()) {
+		parts.push(text.chars().take(MAX_TEXT_CHARS).collect());
+	}
+
+	parts.join(". ")
+}
+
+/// Replace separator characters with spaces
+fn humanize(text: &str) -> String {
+	text.split(['_', '-', '.'])
+		.filter(|word| !word.is_empty())
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+fn sidecar_variant(model: &str) -> SidecarVariant {
+	SidecarVariant::new(model)
+}
+
+/// Write a vector as an embeddings sidecar and record it in the sidecar table
+pub async fn write_embedding_sidecar(
+	library: &Library,
+	content_uuid: &Uuid,
+	sidecar: &EmbeddingSidecar,
+) -> Result<()> {
+	let sidecar_manager = library
+		.core_context()
+		.get_sidecar_manager()
+		.await
+		.ok_or_else(|| anyhow::anyhow!("SidecarManager not available"))?;
+
+	let variant = sidecar_variant(&sidecar.model);
+	let path = sidecar_manager
+		.compute_path(
+			&library.id(),
+			content_uuid,
+			&SidecarKind::Embeddings,
+			&variant,
+			&SidecarFormat::MessagePack,
+		)
+		.await?;
+
+	if let Some(parent) = path.absolute_path.parent() {
+		tokio::fs::create_dir_all(parent).await?;
+	}
+	let bytes = rmp_serde::to_vec(sidecar)?;
+	tokio::fs::write(&path.absolute_path, &bytes).await?;
+
+	sidecar_manager
+		.record_sidecar(
+			library,
+			content_uuid,
+			&SidecarKind::Embeddings,
+			&variant,
+			&SidecarFormat::MessagePack,
+			bytes.len() as u64,
+			None,
+		)
+		.await
+}
+
+/// Read the embeddings sidecar of a content identity, if one exists for the model
+pub async fn read_embedding_sidecar(
+	library: &Library,
+	content_uuid: &Uuid,
+	model: &str,
+) -> Result<Option<EmbeddingSidecar>> {
+	let sidecar_manager = library
+		.core_context()
+		.get_sidecar_manager()
+		.await
+		.ok_or_else(|| anyhow::anyhow!("SidecarManager not available"))?;
+
+	let path = sidecar_manager
+		.compute_path(
+			&library.id(),
+			content_uuid,
+			&SidecarKind::Embeddings,
+			&sidecar_variant(model),
+			&SidecarFormat::MessagePack,
+		)
+		.await?;
+
+	match tokio::fs::read(&path.absolute_path).await {
+		Ok(bytes) => Ok(Some(rmp_serde::from_slice(&bytes)?)),
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+		Err(e) => Err(e.into()),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn text_includes_words_folders_and_content() {
+		let text = embedding_text(
+			"IMG_2041-whiteboard",
+			Some("JPG"),
+			Some("/Users/jamie/Pictures/2026/Q3 planning meeting"),
+			Some("  Roadmap: ship search  "),
+		);
+		assert_eq!(
+			text,
+			"IMG 2041 whiteboard. jpg. in Pictures / 2026 / Q3 planning meeting. Roadmap: ship search"
+		);
+	}
+
+	#[test]
+	fn text_skips_missing_parts() {
+		assert_eq!(embedding_text("notes", None, None, Some("")), "notes");
+	}
+}
@@ -57,8 +57,10 @@ pub async fn extract_image_metadata_with_blurhash(
 		camera_model: Set(exif.camera_data.device_model),
 		lens_model: Set(exif.camera_data.lens_model),
 		focal_length: Set(exif.camera_data.focal_length.map(|f| f.to_string())),
-		aperture: Set(None), // TODO: Extract from EXIF
+		aperture: Set(exif.camera_data.aperture.map(|a| a.to_string())),
 		shutter_speed: Set(exif.camera_data.shutter_speed.map(|s| s.to_string())),
+		exposure_time: Set(exif.camera_data.exposure_time.map(|t| t.to_string())),
+		exposure_bias: Set(exif.camera_data.exposure_bias.map(|b| b.to_string())),
 		iso: Set(exif.camera_data.iso),
 		orientation: Set(Some(exif.camera_data.orientation as i16)),
 		color_space: Set(exif.camera_data.color_space),
@@ -4,6 +4,7 @@
 //! - Thumbnail generation
 //! - OCR (text extraction from images/PDFs)
 //! - Speech-to-text (audio/video transcription)
+//! - Text embeddings for semantic search
 //! - Gaussian splat generation (3D view synthesis from images)
 //! - Video transcoding
 //! - Audio metadata extraction
@@ -11,6 +12,7 @@
 //! - Blurhash generation for image placeholders
 
 pub mod blurhash;
+pub mod embedding;
 pub mod metadata_extractor;
 pub mod ocr;
 pub mod proxy;
@@ -26,6 +28,7 @@ pub use metadata_extractor::{extract_image_metadata, extract_image_metadata_with
 pub use metadata_extractor::{
 	extract_audio_metadata, extract_video_metadata, extract_video_metadata_with_blurhash,
 };
+pub use embedding::EmbeddingJob;
 pub use ocr::{OcrJob, OcrProcessor};
 pub use proxy::{ProxyJob, ProxyProcessor};
 pub use splat::{GaussianSplatJob, GaussianSplatProcessor};
@@ -89,6 +89,11 @@ impl Job for OcrJob {
 	const NAME: &'static str = "ocr";
 	const RESUMABLE: bool = true;
 	const DESCRIPTION: Option<&'static str> = Some("Extract text from images and PDFs using OCR");
+	const MAX_CONCURRENT: Option<usize> = Some(1);
+
+	fn required_resources() -> Vec<ResourceRequirement> {
+		vec![ResourceRequirement::Named("ml_inference")]
+	}
 }
 
 #[async_trait::async_trait]
@@ -242,10 +247,8 @@ impl OcrJob {
 					if let Some(mime_id) = ci.mime_type_id {
 						if let Ok(Some(mime)) = mime_type::Entity::find_by_id(mime_id).one(db).await
 						{
-							if super::is_ocr_supported(
-								&mime.mime_type,
-								ctx.library().core_context().file_type_registry(),
-							) {
+							if super::is_ocr_supported(&mime.mime_type, &ctx.library().file_types())
+							{
 								if let Ok(path) = crate::ops::indexing::PathResolver::get_full_path(
 									db,
 									entry_model.id,
@@ -306,7 +309,7 @@ impl OcrJob {
 							{
 								if super::is_ocr_supported(
 									&mime.mime_type,
-									ctx.library().core_context().file_type_registry(),
+									&ctx.library().file_types(),
 								) {
 									// Get full path
 									if let Ok(path) =
@@ -61,7 +61,7 @@ impl OcrProcessor {
 		}
 
 		entry.mime_type.as_ref().map_or(false, |m| {
-			super::is_ocr_supported(m, self.library.core_context().file_type_registry())
+			super::is_ocr_supported(m, &self.library.file_types())
 		})
 	}
 
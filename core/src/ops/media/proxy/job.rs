@@ -81,6 +81,14 @@ impl Job for ProxyJob {
 	const NAME: &'static str = "proxy_generation";
 	const RESUMABLE: bool = true;
 	const DESCRIPTION: Option<&'static str> = Some("Generate video proxies for smooth playback");
+	const MAX_CONCURRENT: Option<usize> = Some(1);
+
+	fn required_resources() -> Vec<ResourceRequirement> {
+		vec![
+			ResourceRequirement::Named("video_encoder"),
+			ResourceRequirement::Memory(1024 * 1024 * 1024),
+		]
+	}
 }
 
 impl DynJob for ProxyJob {
@@ -89,6 +89,15 @@ impl Job for SpeechToTextJob {
 	const NAME: &'static str = "speech_to_text";
 	const RESUMABLE: bool = true;
 	const DESCRIPTION: Option<&'static str> = Some("Transcribe audio/video to text subtitles");
+	const MAX_CONCURRENT: Option<usize> = Some(1);
+
+	fn required_resources() -> Vec<ResourceRequirement> {
+		// Whisper models are loaded fully into memory
+		vec![
+			ResourceRequirement::Named("ml_inference"),
+			ResourceRequirement::Memory(2 * 1024 * 1024 * 1024),
+		]
+	}
 }
 
 #[async_trait::async_trait]
@@ -265,7 +274,7 @@ impl SpeechToTextJob {
 						{
 							if super::is_speech_supported(
 								&mime.mime_type,
-								ctx.library().core_context().file_type_registry(),
+								&ctx.library().file_types(),
 							) {
 								if let Ok(path) = crate::ops::indexing::PathResolver::get_full_path(
 									db,
@@ -311,7 +320,7 @@ impl SpeechToTextJob {
 						{
 							if super::is_speech_supported(
 								&mime.mime_type,
-								ctx.library().core_context().file_type_registry(),
+								&ctx.library().file_types(),
 							) {
 								if let Ok(path) = crate::ops::indexing::PathResolver::get_full_path(
 									db,
@@ -56,7 +56,7 @@ impl SpeechToTextProcessor {
 		}
 
 		entry.mime_type.as_ref().map_or(false, |m| {
-			super::is_speech_supported(m, self.library.core_context().file_type_registry())
+			super::is_speech_supported(m, &self.library.file_types())
 		})
 	}
 
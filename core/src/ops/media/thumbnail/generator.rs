@@ -1,6 +1,7 @@
 //! Thumbnail generation engine using existing Spacedrive crates
 
 use super::error::{ThumbnailError, ThumbnailResult};
+use image::DynamicImage;
 use sd_media_metadata::exif::Orientation;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
@@ -60,6 +61,22 @@ impl ThumbnailGenerator {
 	}
 }
 
+/// Load an image with sd-images and apply its EXIF orientation
+///
+/// Blocking, call from `spawn_blocking`. Shared with perceptual hashing so
+/// hashes are computed on the same pixels thumbnails are made from.
+pub fn load_oriented_image(source_path: &Path) -> ThumbnailResult<DynamicImage> {
+	let mut img = sd_images::format_image(source_path)
+		.map_err(|e| ThumbnailError::other(format!("Failed to load image: {}", e)))?;
+
+	// Apply EXIF orientation correction if available
+	if let Some(orientation) = Orientation::from_path(source_path) {
+		img = orientation.correct_thumbnail(img);
+	}
+
+	Ok(img)
+}
+
 /// Image thumbnail generator using sd-images crate
 #[derive(Debug)]
 pub struct ImageGenerator;
@@ -90,14 +107,7 @@ impl ImageGenerator {
 		let output_path = output_path.to_path_buf();
 
 		let thumbnail_info = tokio::task::spawn_blocking(move || {
-			// Use sd-images to load and process the image
-			let mut img = sd_images::format_image(&source_path)
-				.map_err(|e| ThumbnailError::other(format!("Failed to load image: {}", e)))?;
-
-			// Apply EXIF orientation correction if available
-			if let Some(orientation) = Orientation::from_path(&source_path) {
-				img = orientation.correct_thumbnail(img);
-			}
+			let img = load_oriented_image(&source_path)?;
 
 			// Blurhash generation disabled for performance
 			let blurhash: Option<String> = None;
@@ -213,6 +223,68 @@ impl VideoGenerator {
 			))
 		}
 	}
+
+	/// Decode `count` frames spread evenly through a video, scaled to `size`
+	///
+	/// Each frame comes with its position in the video, as a fraction of the
+	/// duration. Frames that fail to seek or decode are skipped, so fewer frames
+	/// than requested may be returned.
+	pub async fn keyframes(
+		&self,
+		source_path: &Path,
+		count: u32,
+		size: u32,
+	) -> ThumbnailResult<Vec<(f64, DynamicImage)>> {
+		#[cfg(feature = "ffmpeg")]
+		{
+			use sd_ffmpeg::{FrameDecoder, ThumbnailSize};
+
+			let source_path = source_path.to_path_buf();
+
+			tokio::task::spawn_blocking(move || {
+				let mut decoder = FrameDecoder::new(&source_path, true, false)
+					.map_err(|e| ThumbnailError::video_processing(e.to_string()))?;
+				let duration = decoder
+					.get_duration_secs()
+					.ok_or_else(|| ThumbnailError::video_processing("Video has no duration"))?;
+
+				let mut frames = Vec::with_capacity(count as usize);
+				for i in 0..count {
+					// Sample the middle of each segment to stay clear of intros and fades
+					let position = (f64::from(i) + 0.5) / f64::from(count);
+					let timestamp = duration * position;
+
+					if decoder.seek(timestamp as i64).is_err()
+						|| decoder.decode_video_frame().is_err()
+					{
+						continue;
+					}
+
+					if let Ok(frame) =
+						decoder.get_scaled_video_frame(Some(ThumbnailSize::Scale(size)), true)
+					{
+						if let Some(img) =
+							image::RgbImage::from_raw(frame.width, frame.height, frame.data)
+						{
+							frames.push((position, DynamicImage::ImageRgb8(img)));
+						}
+					}
+				}
+
+				Ok(frames)
+			})
+			.await
+			.map_err(|e| ThumbnailError::other(format!("Task join error: {}", e)))?
+		}
+
+		#[cfg(not(feature = "ffmpeg"))]
+		{
+			let _ = (source_path, count, size);
+			Err(ThumbnailError::other(
+				"Video frame extraction requires FFmpeg feature to be enabled",
+			))
+		}
+	}
 }
 
 /// Document thumbnail generator using sd-images crate (PDF support)
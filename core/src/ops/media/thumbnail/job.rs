@@ -110,6 +110,12 @@ impl Job for ThumbnailJob {
 	const NAME: &'static str = "thumbnail_generation";
 	const RESUMABLE: bool = true;
 	const DESCRIPTION: Option<&'static str> = Some("Generate thumbnails for media files");
+	const MAX_CONCURRENT: Option<usize> = Some(1);
+
+	fn required_resources() -> Vec<ResourceRequirement> {
+		// Decoded RAW and HEIF images can be several hundred megabytes each
+		vec![ResourceRequirement::Memory(512 * 1024 * 1024)]
+	}
 }
 
 impl crate::infra::job::traits::DynJob for ThumbnailJob {
@@ -583,6 +589,7 @@ impl ThumbnailJob {
 					"webp" => Some("image/webp"),
 					"bmp" => Some("image/bmp"),
 					"pdf" => Some("application/pdf"),
+					ext if sd_images::RAW_EXTENSIONS.contains(&ext) => Some("image/x-raw"),
 					#[cfg(feature = "ffmpeg")]
 					"mp4" | "mov" | "avi" | "mkv" | "webm" | "flv" | "wmv" | "m4v" => Some("video/mp4"),
 					_ => None,
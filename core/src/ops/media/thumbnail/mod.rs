@@ -17,7 +17,9 @@ mod utils;
 pub use action::ThumbnailAction;
 pub use config::{ThumbnailVariantConfig, ThumbnailVariants};
 pub use error::{ThumbnailError, ThumbnailResult};
-pub use generator::{ImageGenerator, ThumbnailGenerator, ThumbnailInfo, VideoGenerator};
+pub use generator::{
+	load_oriented_image, ImageGenerator, ThumbnailGenerator, ThumbnailInfo, VideoGenerator,
+};
 pub use job::{ThumbnailJob, ThumbnailJobConfig};
 pub use processor::ThumbnailProcessor;
 pub use state::{ThumbnailEntry, ThumbnailPhase, ThumbnailState, ThumbnailStats};
@@ -9,12 +9,15 @@
 //! - Metadata operations (hierarchical tagging)
 
 pub mod addressing;
+pub mod collections;
 pub mod config;
 // pub mod content;
 pub mod core;
 pub mod devices;
 pub mod extension_test;
+pub mod file_sync;
 pub mod files;
+pub mod filetypes;
 pub mod indexing;
 pub mod jobs;
 pub mod libraries;
@@ -1,6 +1,6 @@
 //! Model download job with progress tracking
 
-use super::{types::ModelInfo, whisper::WhisperModel};
+use super::{embedding::EmbeddingModel, types::ModelInfo, whisper::WhisperModel};
 use crate::infra::job::{prelude::*, traits::DynJob};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
@@ -26,6 +26,15 @@ pub struct ModelDownloadState {
 	temp_path: PathBuf,
 	total_bytes: u64,
 	downloaded_bytes: u64,
+	/// Further files of a multi-file model, downloaded after the current one
+	#[serde(default)]
+	pending_files: Vec<PendingFile>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PendingFile {
+	download_url: String,
+	target_path: PathBuf,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -53,6 +62,7 @@ impl ModelDownloadJob {
 				temp_path: PathBuf::new(),
 				total_bytes: 0,
 				downloaded_bytes: 0,
+				pending_files: Vec::new(),
 			},
 			config,
 		}
@@ -64,6 +74,13 @@ impl ModelDownloadJob {
 			data_dir,
 		})
 	}
+
+	pub fn for_embedding_model(model: EmbeddingModel, data_dir: PathBuf) -> Self {
+		Self::new(ModelDownloadConfig {
+			model_id: format!("embedding-{}", model.id()),
+			data_dir,
+		})
+	}
 }
 
 impl Job for ModelDownloadJob {
@@ -93,16 +110,29 @@ impl JobHandler for ModelDownloadJob {
 			}
 		}
 
-		// Download phase
-		if matches!(self.state.phase, DownloadPhase::Downloading) {
-			self.download(&ctx).await?;
-			self.state.phase = DownloadPhase::Verifying;
-		}
+		loop {
+			// Download phase
+			if matches!(self.state.phase, DownloadPhase::Downloading) {
+				self.download(&ctx).await?;
+				self.state.phase = DownloadPhase::Verifying;
+			}
+
+			// Verify phase
+			if matches!(self.state.phase, DownloadPhase::Verifying) {
+				self.verify(&ctx).await?;
+			}
 
-		// Verify phase
-		if matches!(self.state.phase, DownloadPhase::Verifying) {
-			self.verify(&ctx).await?;
-			self.state.phase = DownloadPhase::Complete;
+			// Move on to the next file of a multi-file model
+			if self.state.pending_files.is_empty() {
+				self.state.phase = DownloadPhase::Complete;
+				break;
+			}
+			let next = self.state.pending_files.remove(0);
+			self.state.download_url = next.download_url;
+			self.state.target_path = next.target_path;
+			self.state.temp_path = self.state.target_path.with_extension("tmp");
+			self.state.phase = DownloadPhase::Downloading;
+			ctx.checkpoint().await?;
 		}
 
 		ctx.log("Model download complete");
@@ -137,6 +167,38 @@ impl ModelDownloadJob {
 				model.display_name(),
 				self.state.total_bytes / 1024 / 1024
 			));
+		} else if let Some(model) = self
+			.config
+			.model_id
+			.strip_prefix("embedding-")
+			.and_then(EmbeddingModel::from_str)
+		{
+			let model_dir = super::get_embedding_models_dir(&self.config.data_dir).join(model.id());
+			tokio::fs::create_dir_all(&model_dir).await?;
+
+			let mut files = model
+				.files()
+				.iter()
+				.map(|(repo_path, filename)| PendingFile {
+					download_url: model.download_url(repo_path),
+					target_path: model_dir.join(filename),
+				});
+			let first = files
+				.next()
+				.ok_or_else(|| JobError::execution("Embedding model has no files"))?;
+
+			self.state.download_url = first.download_url;
+			self.state.target_path = first.target_path;
+			self.state.temp_path = self.state.target_path.with_extension("tmp");
+			self.state.total_bytes = model.size_bytes();
+			self.state.pending_files = files.collect();
+
+			ctx.log(format!(
+				"Downloading {} ({} MB, {} files) from Hugging Face",
+				model.display_name(),
+				self.state.total_bytes / 1024 / 1024,
+				model.files().len()
+			));
 		} else {
 			return Err(JobError::execution(format!(
 				"Unknown model ID: {}",
@@ -150,7 +212,13 @@ impl ModelDownloadJob {
 	async fn download(&mut self, ctx: &JobContext<'_>) -> JobResult<()> {
 		use futures::StreamExt;
 
-		ctx.log("Starting download...");
+		ctx.log(format!(
+			"Starting download of {}...",
+			self.state.download_url
+		));
+
+		// A resumed or follow-up download starts over with a fresh temp file
+		self.state.downloaded_bytes = 0;
 
 		// Start download
 		let client = reqwest::Client::new();
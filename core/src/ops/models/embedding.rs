@@ -0,0 +1,296 @@
+//! Text embedding model management
+//!
+//! Embedding models are sentence-transformer checkpoints exported to ONNX. Each
+//! model is a directory holding the ONNX graph plus the tokenizer files, all
+//! fetched from Hugging Face by `ModelDownloadJob`.
+
+use super::types::{ModelInfo, ModelProvider, ModelType};
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use std::{
+	collections::HashMap,
+	path::{Path, PathBuf},
+	sync::Arc,
+};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EmbeddingModel {
+	/// all-MiniLM-L6-v2, 384 dimensions, English
+	AllMiniLmL6V2,
+}
+
+impl EmbeddingModel {
+	/// Model identifier, also used as the embeddings sidecar variant
+	pub fn id(&self) -> &'static str {
+		match self {
+			Self::AllMiniLmL6V2 => "all-MiniLM-L6-v2",
+		}
+	}
+
+	pub fn display_name(&self) -> &'static str {
+		match self {
+			Self::AllMiniLmL6V2 => "MiniLM L6 v2",
+		}
+	}
+
+	pub fn description(&self) -> &'static str {
+		match self {
+			Self::AllMiniLmL6V2 => "Fast general purpose sentence embeddings (90 MB)",
+		}
+	}
+
+	/// Hugging Face repository the model files are fetched from
+	pub fn repo(&self) -> &'static str {
+		match self {
+			Self::AllMiniLmL6V2 => "sentence-transformers/all-MiniLM-L6-v2",
+		}
+	}
+
+	/// Number of dimensions of the produced vectors
+	pub fn dimensions(&self) -> usize {
+		match self {
+			Self::AllMiniLmL6V2 => 384,
+		}
+	}
+
+	/// Approximate total download size
+	pub fn size_bytes(&self) -> u64 {
+		match self {
+			Self::AllMiniLmL6V2 => 91 * 1024 * 1024, // 91 MB
+		}
+	}
+
+	/// Files making up the model as (path in repository, local filename)
+	pub fn files(&self) -> &'static [(&'static str, &'static str)] {
+		match self {
+			Self::AllMiniLmL6V2 => &[
+				("onnx/model.onnx", ONNX_FILENAME),
+				("tokenizer.json", "tokenizer.json"),
+				("config.json", "config.json"),
+				("special_tokens_map.json", "special_tokens_map.json"),
+				("tokenizer_config.json", "tokenizer_config.json"),
+			],
+		}
+	}
+
+	/// Download URL for a file of this model
+	pub fn download_url(&self, repo_path: &str) -> String {
+		format!(
+			"https://huggingface.co/{}/resolve/main/{}",
+			self.repo(),
+			repo_path
+		)
+	}
+
+	pub fn from_str(s: &str) -> Option<Self> {
+		match s.to_lowercase().as_str() {
+			"all-minilm-l6-v2" | "minilm" => Some(Self::AllMiniLmL6V2),
+			_ => None,
+		}
+	}
+
+	pub fn all() -> Vec<Self> {
+		vec![Self::AllMiniLmL6V2]
+	}
+}
+
+impl Default for EmbeddingModel {
+	fn default() -> Self {
+		Self::AllMiniLmL6V2
+	}
+}
+
+const ONNX_FILENAME: &str = "model.onnx";
+
+pub struct EmbeddingModelManager {
+	models_dir: PathBuf,
+}
+
+impl EmbeddingModelManager {
+	pub fn new(data_dir: &Path) -> Self {
+		Self {
+			models_dir: super::get_embedding_models_dir(data_dir),
+		}
+	}
+
+	/// Directory holding all files of a model
+	pub fn get_model_dir(&self, model: &EmbeddingModel) -> PathBuf {
+		self.models_dir.join(model.id())
+	}
+
+	/// Check if every file of a model is downloaded
+	pub async fn is_downloaded(&self, model: &EmbeddingModel) -> bool {
+		let model_dir = self.get_model_dir(model);
+
+		for (_, filename) in model.files() {
+			match tokio::fs::metadata(model_dir.join(filename)).await {
+				Ok(metadata) if metadata.len() > 0 => {}
+				_ => return false,
+			}
+		}
+
+		true
+	}
+
+	/// List all available models with download status
+	pub async fn list_models(&self) -> Result<Vec<ModelInfo>> {
+		let mut models = Vec::new();
+
+		for model in EmbeddingModel::all() {
+			let downloaded = self.is_downloaded(&model).await;
+
+			models.push(ModelInfo {
+				id: model.id().to_string(),
+				name: model.display_name().to_string(),
+				model_type: ModelType::Embedding,
+				size_bytes: model.size_bytes(),
+				provider: ModelProvider::HuggingFace {
+					repo: model.repo().to_string(),
+				},
+				filename: ONNX_FILENAME.to_string(),
+				downloaded,
+				description: Some(model.description().to_string()),
+			});
+		}
+
+		Ok(models)
+	}
+
+	/// Delete a model and all of its files
+	pub async fn delete_model(&self, model: &EmbeddingModel) -> Result<()> {
+		let model_dir = self.get_model_dir(model);
+		if model_dir.exists() {
+			tokio::fs::remove_dir_all(&model_dir).await?;
+		}
+		Ok(())
+	}
+
+	/// Get total size of all downloaded models
+	pub async fn total_downloaded_size(&self) -> u64 {
+		let mut total = 0u64;
+
+		for model in EmbeddingModel::all() {
+			if self.is_downloaded(&model).await {
+				total += model.size_bytes();
+			}
+		}
+
+		total
+	}
+}
+
+/// Loaded embedders, shared between the embedding job and semantic search
+static LOADED_EMBEDDERS: Lazy<Mutex<HashMap<EmbeddingModel, Arc<TextEmbedder>>>> =
+	Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A loaded text embedding model
+pub struct TextEmbedder {
+	model: EmbeddingModel,
+	#[cfg(feature = "embeddings")]
+	inner: std::sync::Mutex<fastembed::TextEmbedding>,
+}
+
+impl TextEmbedder {
+	/// Get a loaded embedder, loading the model from disk on first use
+	pub async fn shared(model: EmbeddingModel, data_dir: &Path) -> Result<Arc<Self>> {
+		let mut loaded = LOADED_EMBEDDERS.lock().await;
+		if let Some(embedder) = loaded.get(&model) {
+			return Ok(embedder.clone());
+		}
+
+		let manager = EmbeddingModelManager::new(data_dir);
+		if !manager.is_downloaded(&model).await {
+			return Err(anyhow::anyhow!(
+				"Embedding model {} is not downloaded",
+				model.id()
+			));
+		}
+
+		let model_dir = manager.get_model_dir(&model);
+		let embedder =
+			Arc::new(tokio::task::spawn_blocking(move || Self::load(model, &model_dir)).await??);
+		loaded.insert(model, embedder.clone());
+
+		Ok(embedder)
+	}
+
+	#[cfg(feature = "embeddings")]
+	fn load(model: EmbeddingModel, model_dir: &Path) -> Result<Self> {
+		use fastembed::{
+			InitOptionsUserDefined, Pooling, TextEmbedding, TokenizerFiles,
+			UserDefinedEmbeddingModel,
+		};
+
+		let read = |name: &str| std::fs::read(model_dir.join(name));
+
+		let tokenizer_files = TokenizerFiles {
+			tokenizer_file: read("tokenizer.json")?,
+			config_file: read("config.json")?,
+			special_tokens_map_file: read("special_tokens_map.json")?,
+			tokenizer_config_file: read("tokenizer_config.json")?,
+		};
+		let user_model = UserDefinedEmbeddingModel::new(read(ONNX_FILENAME)?, tokenizer_files)
+			.with_pooling(Pooling::Mean);
+
+		let inner =
+			TextEmbedding::try_new_from_user_defined(user_model, InitOptionsUserDefined::default())
+				.map_err(|e| anyhow::anyhow!("Failed to load embedding model: {}", e))?;
+
+		Ok(Self {
+			model,
+			inner: std::sync::Mutex::new(inner),
+		})
+	}
+
+	#[cfg(not(feature = "embeddings"))]
+	fn load(_model: EmbeddingModel, _model_dir: &Path) -> Result<Self> {
+		Err(anyhow::anyhow!(
+			"Embeddings feature not enabled. Rebuild with --features embeddings"
+		))
+	}
+
+	pub fn model(&self) -> EmbeddingModel {
+		self.model
+	}
+
+	/// Embed a batch of texts into L2-normalized vectors
+	///
+	/// Runs inference on a blocking thread since it is CPU bound.
+	pub async fn embed(self: &Arc<Self>, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+		let embedder = self.clone();
+		tokio::task::spawn_blocking(move || embedder.embed_blocking(texts)).await?
+	}
+
+	#[cfg(feature = "embeddings")]
+	fn embed_blocking(&self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+		let inner = self
+			.inner
+			.lock()
+			.map_err(|_| anyhow::anyhow!("Embedding model lock poisoned"))?;
+
+		let mut vectors = inner
+			.embed(texts, None)
+			.map_err(|e| anyhow::anyhow!("Embedding failed: {}", e))?;
+		for vector in &mut vectors {
+			normalize(vector);
+		}
+
+		Ok(vectors)
+	}
+
+	#[cfg(not(feature = "embeddings"))]
+	fn embed_blocking(&self, _texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+		Err(anyhow::anyhow!("Embeddings feature not enabled"))
+	}
+}
+
+/// Scale a vector to unit length so cosine similarity becomes a dot product
+pub fn normalize(vector: &mut [f32]) {
+	let norm = vector.iter().map(|v| v * v).sum::<f32>().sqrt();
+	if norm > 0.0 {
+		for v in vector.iter_mut() {
+			*v /= norm;
+		}
+	}
+}
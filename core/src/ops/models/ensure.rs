@@ -1,6 +1,11 @@
 //! Model availability helpers - ensure models are downloaded before use
 
-use super::{download::ModelDownloadJob, whisper::WhisperModel, whisper::WhisperModelManager};
+use super::{
+	download::ModelDownloadJob,
+	embedding::{EmbeddingModel, EmbeddingModelManager},
+	whisper::WhisperModel,
+	whisper::WhisperModelManager,
+};
 use crate::infra::{
 	event::Event,
 	job::{prelude::*, types::JobId},
@@ -73,6 +78,49 @@ pub async fn ensure_whisper_model(
 	Ok(model_path)
 }
 
+/// Ensure an embedding model is downloaded and ready to use
+///
+/// Same contract as `ensure_whisper_model`, returning the model directory.
+pub async fn ensure_embedding_model(
+	ctx: &JobContext<'_>,
+	model: EmbeddingModel,
+	data_dir: &Path,
+) -> JobResult<PathBuf> {
+	let manager = EmbeddingModelManager::new(data_dir);
+	let model_dir = manager.get_model_dir(&model);
+
+	if manager.is_downloaded(&model).await {
+		debug!("Model {} already downloaded", model.display_name());
+		return Ok(model_dir);
+	}
+
+	ctx.log(format!(
+		"Downloading model {} ({} MB)...",
+		model.display_name(),
+		model.size_bytes() / 1024 / 1024
+	));
+
+	let download_job = ModelDownloadJob::for_embedding_model(model, data_dir.to_path_buf());
+	let handle = ctx
+		.library()
+		.jobs()
+		.dispatch(download_job)
+		.await
+		.map_err(|e| JobError::execution(format!("Failed to dispatch download job: {}", e)))?;
+
+	wait_for_job_completion(ctx, &handle.id()).await?;
+
+	ctx.log(format!("Model {} ready", model.display_name()));
+
+	if !manager.is_downloaded(&model).await {
+		return Err(JobError::execution(
+			"Model download completed but files not found".to_string(),
+		));
+	}
+
+	Ok(model_dir)
+}
+
 /// Wait for a job to reach a terminal state (completed, failed, or cancelled)
 ///
 /// This function subscribes to job events and waits for the specified job
@@ -3,19 +3,24 @@
 //! Downloads and manages models for:
 //! - Whisper (speech-to-text)
 //! - Tesseract (OCR language data)
+//! - Text embeddings (semantic search)
 //! - Future: CLIP, Stable Diffusion, etc.
 
 pub mod action;
 pub mod download;
+pub mod embedding;
 pub mod ensure;
 pub mod query;
 pub mod types;
 pub mod whisper;
 
-pub use action::{DeleteWhisperModelAction, DownloadWhisperModelAction};
+pub use action::{
+	DeleteWhisperModelAction, DownloadEmbeddingModelAction, DownloadWhisperModelAction,
+};
 pub use download::ModelDownloadJob;
-pub use ensure::ensure_whisper_model;
-pub use query::ListWhisperModelsQuery;
+pub use embedding::{EmbeddingModel, EmbeddingModelManager, TextEmbedder};
+pub use ensure::{ensure_embedding_model, ensure_whisper_model};
+pub use query::{ListEmbeddingModelsQuery, ListWhisperModelsQuery};
 pub use types::{ModelInfo, ModelProvider, ModelType};
 pub use whisper::{WhisperModel, WhisperModelManager};
 
@@ -32,6 +37,11 @@ pub fn get_whisper_models_dir(data_dir: &Path) -> PathBuf {
 	get_models_dir(data_dir).join("whisper")
 }
 
+/// Get the text embedding models directory
+pub fn get_embedding_models_dir(data_dir: &Path) -> PathBuf {
+	get_models_dir(data_dir).join("embeddings")
+}
+
 /// Get the tesseract data directory
 pub fn get_tesseract_data_dir(data_dir: &Path) -> PathBuf {
 	get_models_dir(data_dir).join("tesseract")
@@ -0,0 +1,102 @@
+//! Text extraction for documents scored by Full search
+//!
+//! Only formats whose text can be read without a dedicated parser are handled:
+//! plain text, source code, configuration and markup. Binary documents (PDF,
+//! office formats) are rejected and rely on OCR populating `text_content`.
+
+use std::path::Path;
+use tokio::io::AsyncReadExt;
+
+/// Maximum number of bytes read from a single document
+pub const MAX_DOCUMENT_BYTES: u64 = 1024 * 1024;
+
+/// Number of leading bytes inspected to decide whether a file is binary
+const BINARY_SNIFF_BYTES: usize = 8 * 1024;
+
+/// Extensions whose content is markup that should be stripped before scoring
+const MARKUP_EXTENSIONS: &[&str] = &["html", "htm", "xhtml", "xml", "svg"];
+
+/// Read the text content of a local document
+///
+/// Returns `None` when the file cannot be read or looks binary.
+pub async fn extract_text(path: &Path, extension: Option<&str>) -> Option<String> {
+	let file = tokio::fs::File::open(path).await.ok()?;
+	let mut bytes = Vec::new();
+	file.take(MAX_DOCUMENT_BYTES)
+		.read_to_end(&mut bytes)
+		.await
+		.ok()?;
+
+	decode_text(&bytes, extension)
+}
+
+/// Decode raw bytes as text, stripping markup for markup formats
+pub fn decode_text(bytes: &[u8], extension: Option<&str>) -> Option<String> {
+	if bytes.is_empty() || is_binary(bytes) {
+		return None;
+	}
+
+	let text = String::from_utf8_lossy(bytes);
+	let is_markup = extension
+		.map(|ext| MARKUP_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+		.unwrap_or(false);
+
+	Some(if is_markup {
+		strip_markup(&text)
+	} else {
+		text.into_owned()
+	})
+}
+
+/// NUL bytes essentially never appear in text files but are common in binaries
+fn is_binary(bytes: &[u8]) -> bool {
+	bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0)
+}
+
+/// Remove tags from markup, keeping the text between them
+fn strip_markup(text: &str) -> String {
+	let mut stripped = String::with_capacity(text.len());
+	let mut in_tag = false;
+
+	for c in text.chars() {
+		match c {
+			'<' => in_tag = true,
+			'>' if in_tag => {
+				in_tag = false;
+				stripped.push(' ');
+			}
+			_ if !in_tag => stripped.push(c),
+			_ => {}
+		}
+	}
+
+	stripped
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn decodes_plain_text() {
+		assert_eq!(
+			decode_text(b"quarterly numbers", Some("txt")).as_deref(),
+			Some("quarterly numbers")
+		);
+	}
+
+	#[test]
+	fn rejects_binary_content() {
+		assert!(decode_text(b"%PDF-1.7\0\x01\x02", Some("pdf")).is_none());
+		assert!(decode_text(b"", Some("txt")).is_none());
+	}
+
+	#[test]
+	fn strips_markup_tags() {
+		let text = decode_text(b"<p>Hello <b>world</b></p>", Some("HTML")).unwrap();
+		assert_eq!(
+			text.split_whitespace().collect::<Vec<_>>(),
+			["Hello", "world"]
+		);
+	}
+}
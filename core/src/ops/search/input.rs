@@ -45,10 +45,12 @@ pub enum SearchScope {
 pub enum SearchMode {
 	/// Fast, metadata-only search (<10ms)
 	Fast,
-	/// Normal search with semantic ranking (<100ms)
+	/// Normal search with BM25 ranking over names, paths and extracted text (<100ms)
 	Normal,
-	/// Full search with content analysis (<500ms)
+	/// Normal ranking plus text read from local documents (<500ms)
 	Full,
+	/// Normal ranking blended with embedding similarity, matching files by meaning
+	Semantic,
 }
 
 /// Container for all structured filters
@@ -82,7 +84,7 @@ pub struct DateRangeFilter {
 }
 
 /// Time-based fields that can be filtered
-#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
 pub enum DateField {
 	CreatedAt,
 	ModifiedAt,
@@ -186,11 +188,12 @@ impl FileSearchInput {
 
 	/// Validate the search input
 	pub fn validate(&self) -> Result<(), String> {
-		// Allow empty queries when sorting by IndexedAt (for recents view)
+		// Allow empty queries when sorting by IndexedAt (for recents view), or when
+		// filters alone pick the files
 		let is_recents_query =
 			self.query.trim().is_empty() && matches!(self.sort.field, SortField::IndexedAt);
 
-		if self.query.trim().is_empty() && !is_recents_query {
+		if self.query.trim().is_empty() && !is_recents_query && !self.filters.narrows() {
 			return Err("Query cannot be empty".to_string());
 		}
 
@@ -228,6 +231,61 @@ impl FileSearchInput {
 	}
 }
 
+impl SearchFilters {
+	/// Whether any filter leaves files out
+	pub fn narrows(&self) -> bool {
+		fn non_empty<T>(list: &Option<Vec<T>>) -> bool {
+			list.as_ref().is_some_and(|list| !list.is_empty())
+		}
+
+		non_empty(&self.file_types)
+			|| non_empty(&self.locations)
+			|| non_empty(&self.content_types)
+			|| self.date_range.is_some()
+			|| self.size_range.is_some()
+			|| self
+				.tags
+				.as_ref()
+				.is_some_and(|tags| !tags.include.is_empty() || !tags.exclude.is_empty())
+	}
+
+	/// Add filters from `other`, its size and date ranges replace these
+	pub fn merge(&mut self, other: SearchFilters) {
+		fn extend<T: PartialEq>(list: &mut Option<Vec<T>>, other: Option<Vec<T>>) {
+			for item in other.into_iter().flatten() {
+				let list = list.get_or_insert_with(Vec::new);
+				if !list.contains(&item) {
+					list.push(item);
+				}
+			}
+		}
+
+		extend(&mut self.file_types, other.file_types);
+		extend(&mut self.locations, other.locations);
+		extend(&mut self.content_types, other.content_types);
+		if let Some(other_tags) = other.tags {
+			let tags = self.tags.get_or_insert_with(|| TagFilter {
+				include: Vec::new(),
+				exclude: Vec::new(),
+			});
+			tags.include.extend(other_tags.include);
+			tags.exclude.extend(other_tags.exclude);
+		}
+		if other.date_range.is_some() {
+			self.date_range = other.date_range;
+		}
+		if other.size_range.is_some() {
+			self.size_range = other.size_range;
+		}
+		if other.include_hidden.is_some() {
+			self.include_hidden = other.include_hidden;
+		}
+		if other.include_archived.is_some() {
+			self.include_archived = other.include_archived;
+		}
+	}
+}
+
 impl Default for SearchScope {
 	fn default() -> Self {
 		SearchScope::Library
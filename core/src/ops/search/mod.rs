@@ -3,13 +3,19 @@
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
+pub mod document_text;
 pub mod ephemeral_search;
 pub mod facets;
 pub mod filters;
 pub mod input;
 pub mod output;
+pub mod parse;
 pub mod query;
+pub mod ranking;
+pub mod saved;
 pub mod sorting;
+pub mod syntax;
+pub mod vector_index;
 
 #[cfg(test)]
 mod tests;
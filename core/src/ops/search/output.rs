@@ -1,7 +1,7 @@
 //! Output for file search operations
 
 use crate::domain::File;
-use crate::ops::search::{FilterKind, IndexType};
+use crate::ops::search::{ranking::RankingSignals, FilterKind, IndexType};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -45,6 +45,14 @@ pub struct ScoreBreakdown {
 	pub metadata_score: f32,
 	pub recency_boost: f32,
 	pub user_preference_boost: f32,
+	/// Weighted BM25 over the file name and extension
+	pub name_score: f32,
+	/// Weighted BM25 over the containing directory path
+	pub path_score: f32,
+	/// Weighted BM25 over extracted text (OCR, transcripts, documents)
+	pub content_score: f32,
+	/// Boost from how recently the file was accessed
+	pub access_boost: f32,
 	pub final_score: f32,
 }
 
@@ -373,9 +381,44 @@ impl ScoreBreakdown {
 			metadata_score,
 			recency_boost,
 			user_preference_boost,
+			name_score: 0.0,
+			path_score: 0.0,
+			content_score: 0.0,
+			access_boost: 0.0,
 			final_score,
 		}
 	}
+
+	/// Create a score breakdown from ranked search signals
+	///
+	/// The favorite boost is reported as the user preference boost.
+	pub fn from_signals(signals: &RankingSignals) -> Self {
+		Self {
+			temporal_score: 0.0,
+			semantic_score: (signals.semantic_score != 0.0).then_some(signals.semantic_score),
+			metadata_score: 0.0,
+			recency_boost: signals.recency_boost,
+			user_preference_boost: signals.favorite_boost,
+			name_score: signals.name_score,
+			path_score: signals.path_score,
+			content_score: signals.content_score,
+			access_boost: signals.access_boost,
+			final_score: signals.final_score(),
+		}
+	}
+
+	/// Recover the ranking signals this breakdown was built from
+	pub fn ranking_signals(&self) -> RankingSignals {
+		RankingSignals {
+			name_score: self.name_score,
+			path_score: self.path_score,
+			content_score: self.content_score,
+			semantic_score: self.semantic_score.unwrap_or(0.0),
+			recency_boost: self.recency_boost,
+			access_boost: self.access_boost,
+			favorite_boost: self.user_preference_boost,
+		}
+	}
 }
 
 // ============================================================================
@@ -0,0 +1,26 @@
+use crate::ops::search::{input::SearchFilters, syntax::SyntaxError};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SearchParseOutput {
+	/// Text to match, with the filters taken out
+	pub query: String,
+	/// Filters written in the query, tags resolved to ids
+	pub filters: SearchFilters,
+	/// The query in canonical form
+	pub canonical: String,
+	/// Why the query can't be used, the other fields are empty when set
+	pub error: Option<SyntaxError>,
+}
+
+impl SearchParseOutput {
+	pub fn invalid(error: SyntaxError) -> Self {
+		Self {
+			query: String::new(),
+			filters: SearchFilters::default(),
+			canonical: String::new(),
+			error: Some(error),
+		}
+	}
+}
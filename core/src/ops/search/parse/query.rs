@@ -0,0 +1,92 @@
+use super::output::SearchParseOutput;
+use crate::infra::query::{QueryError, QueryResult};
+use crate::ops::search::{input::TagFilter, syntax::SearchSyntax};
+use crate::ops::tags::manager::TagManager;
+use crate::{context::CoreContext, infra::query::LibraryQuery};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SearchParseQueryInput {
+	/// Query in search syntax, as in `kind:image size:>20MB beach`
+	pub query: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SearchParseQuery {
+	query: String,
+}
+
+impl LibraryQuery for SearchParseQuery {
+	type Input = SearchParseQueryInput;
+	type Output = SearchParseOutput;
+
+	fn from_input(input: Self::Input) -> QueryResult<Self> {
+		Ok(Self { query: input.query })
+	}
+
+	async fn execute(
+		self,
+		context: Arc<CoreContext>,
+		session: crate::infra::api::SessionContext,
+	) -> QueryResult<Self::Output> {
+		let syntax = match SearchSyntax::parse(&self.query) {
+			Ok(syntax) => syntax,
+			Err(error) => return Ok(SearchParseOutput::invalid(error)),
+		};
+
+		let mut filters = syntax.filters();
+		if !syntax.tags.is_empty() {
+			let library_id = session
+				.current_library_id
+				.ok_or_else(|| QueryError::Internal("No library selected".to_string()))?;
+
+			let library = context
+				.libraries()
+				.await
+				.get_library(library_id)
+				.await
+				.ok_or_else(|| QueryError::Internal("Library not found".to_string()))?;
+
+			let manager = TagManager::new(Arc::new(library.db().conn().clone()));
+			let mut tag_filter = TagFilter {
+				include: Vec::new(),
+				exclude: Vec::new(),
+			};
+			for term in &syntax.tags {
+				let tag_error =
+					|e: crate::domain::tag::TagError| QueryError::Internal(e.to_string());
+				let mut candidates = Vec::new();
+				for tag in manager
+					.find_tags_by_name(term.name())
+					.await
+					.map_err(tag_error)?
+				{
+					let ancestors = manager.get_ancestors(tag.id).await.map_err(tag_error)?;
+					candidates.push((tag, ancestors));
+				}
+
+				let tag_id = match term.select(&candidates) {
+					Ok(tag_id) => tag_id,
+					Err(error) => return Ok(SearchParseOutput::invalid(error)),
+				};
+				if term.exclude {
+					tag_filter.exclude.push(tag_id);
+				} else {
+					tag_filter.include.push(tag_id);
+				}
+			}
+			filters.tags = Some(tag_filter);
+		}
+
+		Ok(SearchParseOutput {
+			query: syntax.query(),
+			filters,
+			canonical: syntax.to_string(),
+			error: None,
+		})
+	}
+}
+
+crate::register_library_query!(SearchParseQuery, "search.parse");
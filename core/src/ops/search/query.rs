@@ -1,8 +1,10 @@
 //! File search query implementation
 
 use super::{
+	document_text,
 	input::{FileSearchInput, SearchScope},
 	output::{EnhancedFileSearchOutput, EnhancedFileSearchResult, FileSearchOutput},
+	ranking,
 };
 use crate::infra::query::{QueryError, QueryResult};
 use crate::{
@@ -13,6 +15,7 @@ use crate::{
 		content_identity, directory_paths, entry, sidecar, tag, user_metadata_tag,
 	},
 	infra::query::LibraryQuery,
+	volume::ArchiveBackend,
 };
 use chrono::{DateTime, Utc};
 use sea_orm::{
@@ -24,12 +27,75 @@ use specta::Type;
 use std::sync::Arc;
 use uuid::Uuid;
 
+/// Maximum candidates gathered from each field before ranking
+const RANKING_CANDIDATE_LIMIT: u32 = 2000;
+
+/// Maximum documents read from disk per Full search
+const DOCUMENT_SCAN_LIMIT: u32 = 500;
+
+/// Length of the extracted text excerpt returned as `matched_content`
+const MATCHED_CONTENT_CHARS: usize = 160;
+
+/// Maximum nearest neighbours taken from the vector index per Semantic search
+const SEMANTIC_CANDIDATE_LIMIT: usize = 200;
+
+/// Neighbours less similar than this are unrelated to the query
+const MIN_SEMANTIC_SIMILARITY: f32 = 0.2;
+
 /// File search query
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct FileSearchQuery {
 	pub input: FileSearchInput,
 }
 
+/// An entry loaded with the joined fields ranking needs
+struct LoadedSearchEntry {
+	entry_id: i32,
+	/// Entry name without extension
+	name: String,
+	/// Path of the containing directory
+	parent_path: Option<String>,
+	is_favorite: bool,
+	file: File,
+}
+
+impl LoadedSearchEntry {
+	/// Whether every phrase appears in the name, the directory path or the text
+	///
+	/// `text` defaults to the extracted text of the content identity.
+	fn matches_phrases(&self, phrases: &[Vec<String>], text: Option<&str>) -> bool {
+		if phrases.is_empty() {
+			return true;
+		}
+
+		let text = text.or_else(|| {
+			self.file
+				.content_identity
+				.as_ref()
+				.and_then(|ci| ci.text_content.as_deref())
+		});
+		let fields: Vec<Vec<String>> = [
+			Some(format!(
+				"{} {}",
+				self.name,
+				self.file.extension.as_deref().unwrap_or_default()
+			)),
+			self.parent_path.clone(),
+			text.map(str::to_string),
+		]
+		.into_iter()
+		.flatten()
+		.map(|field| ranking::tokenize(&field))
+		.collect();
+
+		phrases.iter().all(|phrase| {
+			fields
+				.iter()
+				.any(|tokens| ranking::contains_phrase(tokens, phrase))
+		})
+	}
+}
+
 impl FileSearchQuery {
 	pub fn new(input: FileSearchInput) -> Self {
 		Self { input }
@@ -109,13 +175,16 @@ impl LibraryQuery for FileSearchQuery {
 						self.execute_full_search(db.conn(), &device_slug_map)
 							.await?
 					}
+					crate::ops::search::input::SearchMode::Semantic => {
+						self.execute_semantic_search(&library).await?
+					}
 				};
 
 				let execution_time = start_time.elapsed().as_millis() as u64;
 
 				// Get actual total count for pagination
 				let total_count = self
-					.get_total_count(db.conn(), context.file_type_registry())
+					.get_total_count(db.conn(), &library.file_types())
 					.await
 					.unwrap_or(0);
 
@@ -131,7 +200,7 @@ impl LibraryQuery for FileSearchQuery {
 			}
 			crate::ops::search::IndexType::Ephemeral => {
 				// Use ephemeral search
-				self.execute_ephemeral_search(context, search_id, start_time)
+				self.execute_ephemeral_search(context, &library, search_id, start_time)
 					.await
 			}
 			crate::ops::search::IndexType::Hybrid => {
@@ -190,8 +259,6 @@ impl FileSearchQuery {
 		db: &DatabaseConnection,
 		_device_slug_map: &std::collections::HashMap<Uuid, String>,
 	) -> QueryResult<Vec<crate::ops::search::output::FileSearchResult>> {
-		use sea_orm::Statement;
-
 		// For empty queries (recents view), skip FTS and query entries directly
 		if self.input.query.trim().is_empty() {
 			return self.execute_fast_search_no_fts(db).await;
@@ -199,6 +266,10 @@ impl FileSearchQuery {
 
 		// Use FTS5 for high-performance text search
 		let fts_query = self.build_fts5_query();
+		if fts_query.is_empty() {
+			// Query is only punctuation, nothing to match on
+			return Ok(Vec::new());
+		}
 		let fts_results = self.execute_fts5_search(db, &fts_query).await?;
 
 		let fts_count = fts_results.len();
@@ -216,6 +287,67 @@ impl FileSearchQuery {
 		let score_map: std::collections::HashMap<i32, f64> = fts_results.iter().cloned().collect();
 		let entry_ids: Vec<i32> = fts_results.iter().map(|(id, _)| *id).collect();
 
+		let entries = self.load_search_entries(db, &entry_ids).await?;
+
+		// Convert results to FileSearchResult objects
+		let mut results = Vec::new();
+		let relevance_calc =
+			crate::ops::search::sorting::RelevanceCalculator::new(self.input.query.clone());
+
+		for entry in entries {
+			// Get BM25 score and calculate final score
+			let bm25_score = score_map.get(&entry.entry_id).copied().unwrap_or(0.0);
+			let recency_boost = relevance_calc.calculate_recency_boost(entry.file.modified_at);
+			let user_preference_boost =
+				relevance_calc.calculate_user_preference_boost(entry.entry_id);
+			let final_score = bm25_score as f32 + recency_boost + user_preference_boost;
+
+			let highlights =
+				self.extract_highlights(&fts_query, &entry.name, &entry.file.extension);
+
+			results.push(crate::ops::search::output::FileSearchResult {
+				file: entry.file,
+				score: final_score,
+				score_breakdown: crate::ops::search::output::ScoreBreakdown::new(
+					bm25_score as f32,
+					None,
+					0.0,
+					recency_boost,
+					user_preference_boost,
+				),
+				highlights,
+				matched_content: None,
+			});
+		}
+
+		tracing::info!(
+			"Built {} FileSearchResult objects from {} FTS5 results",
+			results.len(),
+			fts_count
+		);
+
+		// Sort by final score
+		results.sort_by(|a, b| {
+			b.score
+				.partial_cmp(&a.score)
+				.unwrap_or(std::cmp::Ordering::Equal)
+		});
+
+		Ok(results)
+	}
+
+	/// Load entries with all joined data needed to build `File` objects
+	///
+	/// Returned entries are in database order, callers are expected to sort.
+	async fn load_search_entries(
+		&self,
+		db: &DatabaseConnection,
+		entry_ids: &[i32],
+	) -> QueryResult<Vec<LoadedSearchEntry>> {
+		if entry_ids.is_empty() {
+			return Ok(Vec::new());
+		}
+
 		// Build single efficient query with all joins
 		let entry_ids_str = entry_ids
 			.iter()
@@ -223,6 +355,8 @@ impl FileSearchQuery {
 			.collect::<Vec<_>>()
 			.join(",");
 
+		let (tag_clause, tag_values) = self.tag_filter_clause("e.id");
+
 		// Join directory_paths on parent_id directly - every directory has its full
 		// absolute path stored, so the parent of any file gives us the containing folder path.
 		let sql_query = format!(
@@ -252,22 +386,30 @@ impl FileSearchQuery {
 				ci.total_size as ci_total_size,
 				ci.entry_count as ci_entry_count,
 				ci.first_seen_at as first_seen_at,
-				ci.last_verified_at as last_verified_at
+				ci.last_verified_at as last_verified_at,
+				um.favorite as is_favorite
 			FROM entries e
 			LEFT JOIN directory_paths dp ON dp.entry_id = e.parent_id
 			LEFT JOIN volumes v ON e.volume_id = v.id
 			LEFT JOIN devices d ON v.device_id = d.uuid
 			LEFT JOIN content_identities ci ON e.content_id = ci.id
 			LEFT JOIN content_kinds ck ON ci.kind_id = ck.id
-			WHERE e.id IN ({})
+			LEFT JOIN user_metadata um ON e.metadata_id = um.id
+			WHERE e.id IN ({}) {}
 			"#,
-			entry_ids_str
+			entry_ids_str,
+			if tag_clause.is_empty() {
+				String::new()
+			} else {
+				format!("AND {}", tag_clause)
+			}
 		);
 
 		let rows = db
-			.query_all(Statement::from_string(
+			.query_all(Statement::from_sql_and_values(
 				sea_orm::DatabaseBackend::Sqlite,
 				sql_query,
+				tag_values,
 			))
 			.await?;
 
@@ -313,10 +455,7 @@ impl FileSearchQuery {
 				});
 		}
 
-		// Convert results to FileSearchResult objects
-		let mut results = Vec::new();
-		let relevance_calc =
-			crate::ops::search::sorting::RelevanceCalculator::new(self.input.query.clone());
+		let mut entries = Vec::with_capacity(rows.len());
 
 		for row in rows {
 			let entry_id: i32 = row.try_get("", "entry_id").unwrap_or(0);
@@ -343,6 +482,11 @@ impl FileSearchQuery {
 			let content_kind_name: Option<String> = row.try_get("", "content_kind_name").ok();
 			let content_identity_uuid: Option<Uuid> =
 				row.try_get("", "content_identity_uuid").ok().flatten();
+			let is_favorite: bool = row
+				.try_get::<Option<bool>>("", "is_favorite")
+				.ok()
+				.flatten()
+				.unwrap_or(false);
 
 			// Content identity fields for building ContentIdentity object
 			let content_hash: Option<String> = row.try_get("", "content_hash").ok();
@@ -357,7 +501,7 @@ impl FileSearchQuery {
 				row.try_get("", "last_verified_at").ok();
 
 			// Build full path: parent directory path + filename (with extension)
-			let file_path = if let Some(dir_path) = parent_path {
+			let file_path = if let Some(dir_path) = &parent_path {
 				let file_name = if let Some(ext) = &entry_extension {
 					format!("{}.{}", entry_name, ext)
 				} else {
@@ -441,116 +585,363 @@ impl FileSearchQuery {
 				file.content_kind = crate::domain::ContentKind::from(kind_name.as_str());
 			}
 
-			// Get BM25 score and calculate final score
-			let bm25_score = score_map.get(&entry_id).copied().unwrap_or(0.0);
-			let recency_boost = relevance_calc.calculate_recency_boost(file.modified_at);
-			let user_preference_boost = relevance_calc.calculate_user_preference_boost(entry_id);
-			let final_score = bm25_score as f32 + recency_boost + user_preference_boost;
-
-			let result = crate::ops::search::output::FileSearchResult {
+			entries.push(LoadedSearchEntry {
+				entry_id,
+				name: entry_name,
+				parent_path,
+				is_favorite,
 				file,
-				score: final_score,
-				score_breakdown: crate::ops::search::output::ScoreBreakdown::new(
-					bm25_score as f32,
-					None,
-					0.0,
-					recency_boost,
-					user_preference_boost,
-				),
-				highlights: self.extract_highlights(&fts_query, &entry_name, &entry_extension),
-				matched_content: None,
-			};
+			});
+		}
+
+		Ok(entries)
+	}
 
-			results.push(result);
+	/// Execute normal search with BM25 ranking over names, paths and extracted text
+	///
+	/// Candidates come from the name index, the content index and directory path
+	/// matches. Every candidate is scored on all three fields, then boosted by
+	/// recency, recent access and favorite status.
+	async fn execute_normal_search(
+		&self,
+		db: &DatabaseConnection,
+		device_slug_map: &std::collections::HashMap<Uuid, String>,
+	) -> QueryResult<Vec<crate::ops::search::output::FileSearchResult>> {
+		if self.input.query.trim().is_empty() {
+			return self.execute_fast_search_no_fts(db).await;
 		}
 
-		tracing::info!(
-			"Built {} FileSearchResult objects from {} FTS5 results",
-			results.len(),
-			fts_count
-		);
+		let terms = ranking::tokenize(&self.input.query);
+		if terms.is_empty() {
+			// Query is only punctuation, nothing to rank on
+			return self.execute_fast_search(db, device_slug_map).await;
+		}
+
+		let results = self.rank_candidates(db, &terms).await?;
+		Ok(self.paginate_ranked(results))
+	}
+
+	/// Execute full search: normal ranking plus text read from local documents
+	///
+	/// Documents that have no extracted text yet are read from disk and scored
+	/// with the same BM25 model as the content index, so plain text, code and
+	/// markup files match on what they contain.
+	async fn execute_full_search(
+		&self,
+		db: &DatabaseConnection,
+		device_slug_map: &std::collections::HashMap<Uuid, String>,
+	) -> QueryResult<Vec<crate::ops::search::output::FileSearchResult>> {
+		if self.input.query.trim().is_empty() {
+			return self.execute_fast_search_no_fts(db).await;
+		}
+
+		let terms = ranking::tokenize(&self.input.query);
+		if terms.is_empty() {
+			return self.execute_fast_search(db, device_slug_map).await;
+		}
+
+		let mut results = self.rank_candidates(db, &terms).await?;
+		self.rank_document_text(db, &terms, &mut results).await?;
 
-		// Sort by final score
 		results.sort_by(|a, b| {
 			b.score
 				.partial_cmp(&a.score)
 				.unwrap_or(std::cmp::Ordering::Equal)
 		});
 
-		Ok(results)
+		Ok(self.paginate_ranked(results))
 	}
 
-	/// Execute normal search with FTS5 + enhanced ranking
-	async fn execute_normal_search(
+	/// Execute semantic search: normal ranking blended with embedding similarity
+	///
+	/// The query is embedded and matched against the library's vector index, so
+	/// files are found by what they are about rather than only the words in
+	/// their names. Falls back to Normal ranking when no embedding model is
+	/// available.
+	async fn execute_semantic_search(
 		&self,
-		db: &DatabaseConnection,
-		device_slug_map: &std::collections::HashMap<Uuid, String>,
+		library: &crate::library::Library,
 	) -> QueryResult<Vec<crate::ops::search::output::FileSearchResult>> {
-		// Use FTS5 as base, then enhance with additional ranking factors
-		let mut results = self.execute_fast_search(db, device_slug_map).await?;
+		let db = library.db().conn();
+		if self.input.query.trim().is_empty() {
+			return self.execute_fast_search_no_fts(db).await;
+		}
 
-		// Enhanced ranking for normal search
-		for result in &mut results {
-			let mut enhanced_score = result.score;
-
-			// Add metadata-based scoring
-			let size = result.file.size;
-			// Slightly boost files with reasonable sizes
-			if size > 1024 && size < 10_000_000 {
-				// 1KB to 10MB
-				enhanced_score += 0.1;
+		let terms = ranking::tokenize(&self.input.query);
+		let mut results = if terms.is_empty() {
+			Vec::new()
+		} else {
+			self.rank_candidates(db, &terms).await?
+		};
+
+		let similarities = match self.semantic_neighbours(library).await {
+			Ok(similarities) => similarities,
+			Err(e) => {
+				tracing::warn!("Semantic search unavailable, using lexical ranking: {}", e);
+				return Ok(self.paginate_ranked(results));
 			}
+		};
 
-			// Boost files with extensions that match common document types
-			if let Some(ref extension) = result.file.extension {
-				match extension.as_str() {
-					"pdf" | "doc" | "docx" | "txt" | "md" => enhanced_score += 0.2,
-					"jpg" | "png" | "gif" | "webp" => enhanced_score += 0.1,
-					_ => {}
-				}
+		let max_text_score = results
+			.iter()
+			.map(|result| result.score_breakdown.ranking_signals().text_score())
+			.fold(0.0, f32::max);
+		let now = Utc::now();
+
+		// Blend similarity into the lexical matches
+		for result in &mut results {
+			let similarity = result
+				.file
+				.content_identity
+				.as_ref()
+				.and_then(|ci| similarities.get(&ci.uuid))
+				.copied()
+				.unwrap_or(0.0);
+
+			let mut signals = result.score_breakdown.ranking_signals();
+			signals.semantic_score =
+				ranking::semantic_score(signals.text_score(), similarity, max_text_score);
+			signals.apply_boosts(
+				result.file.modified_at,
+				result.file.accessed_at,
+				signals.favorite_boost > 0.0,
+				now,
+			);
+
+			result.score = signals.final_score();
+			result.score_breakdown =
+				crate::ops::search::output::ScoreBreakdown::from_signals(&signals);
+		}
+
+		// Add neighbours that share no words with the query
+		let lexical_ids: std::collections::HashSet<Uuid> =
+			results.iter().map(|result| result.file.id).collect();
+		let semantic_only: std::collections::HashMap<i32, f32> = self
+			.semantic_entries(db, &similarities)
+			.await?
+			.into_iter()
+			.filter(|(_, entry_uuid, _)| !lexical_ids.contains(entry_uuid))
+			.map(|(entry_id, _, similarity)| (entry_id, similarity))
+			.collect();
+
+		let entry_ids: Vec<i32> = semantic_only.keys().copied().collect();
+		let phrases = ranking::phrases(&self.input.query);
+		for entry in self.load_search_entries(db, &entry_ids).await? {
+			if !entry.matches_phrases(&phrases, None) {
+				continue;
 			}
+			let similarity = semantic_only[&entry.entry_id];
+
+			let mut signals = ranking::RankingSignals {
+				semantic_score: ranking::semantic_score(0.0, similarity, max_text_score),
+				..Default::default()
+			};
+			signals.apply_boosts(
+				entry.file.modified_at,
+				entry.file.accessed_at,
+				entry.is_favorite,
+				now,
+			);
 
-			// Update the score
-			result.score = enhanced_score;
-			result.score_breakdown.metadata_score = enhanced_score
-				- result.score_breakdown.temporal_score
-				- result.score_breakdown.recency_boost
-				- result.score_breakdown.user_preference_boost;
+			results.push(self.ranked_result(entry, &terms, signals, None));
 		}
 
-		// Re-sort with enhanced scores
 		results.sort_by(|a, b| {
 			b.score
 				.partial_cmp(&a.score)
 				.unwrap_or(std::cmp::Ordering::Equal)
 		});
 
-		Ok(results)
+		Ok(self.paginate_ranked(results))
 	}
 
-	/// Execute full search with FTS5 + content analysis
-	async fn execute_full_search(
+	/// Nearest content identities to the query embedding, with their similarity
+	async fn semantic_neighbours(
+		&self,
+		library: &crate::library::Library,
+	) -> anyhow::Result<std::collections::HashMap<Uuid, f32>> {
+		use crate::ops::models::{EmbeddingModel, TextEmbedder};
+
+		let data_dir = crate::config::default_data_dir()?;
+		let embedder = TextEmbedder::shared(EmbeddingModel::default(), &data_dir).await?;
+		let query_vector = embedder
+			.embed(vec![self.input.query.clone()])
+			.await?
+			.pop()
+			.ok_or_else(|| anyhow::anyhow!("Embedding model returned no vector"))?;
+
+		let index = library.vector_index().await?;
+		let index = index.read().await;
+
+		Ok(index
+			.search(
+				&query_vector,
+				SEMANTIC_CANDIDATE_LIMIT,
+				index.default_nprobe(),
+			)
+			.into_iter()
+			.filter(|(_, similarity)| *similarity >= MIN_SEMANTIC_SIMILARITY)
+			.collect())
+	}
+
+	/// Entries holding the given content identities within the search scope
+	///
+	/// Returns (entry id, entry uuid, similarity) for every matching entry.
+	async fn semantic_entries(
 		&self,
 		db: &DatabaseConnection,
-		device_slug_map: &std::collections::HashMap<Uuid, String>,
+		similarities: &std::collections::HashMap<Uuid, f32>,
+	) -> QueryResult<Vec<(i32, Uuid, f32)>> {
+		if similarities.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		let placeholders = vec!["?"; similarities.len()].join(", ");
+		let (scope_clause, scope_values) = self.scope_path_clause();
+
+		let sql = format!(
+			r#"
+			SELECT e.id AS id, e.uuid AS entry_uuid, ci.uuid AS content_uuid
+			FROM entries e
+			JOIN content_identities ci ON e.content_id = ci.id
+			LEFT JOIN directory_paths dp ON dp.entry_id = e.parent_id
+			WHERE ci.uuid IN ({placeholders})
+				{scope_clause}
+			LIMIT {RANKING_CANDIDATE_LIMIT}
+			"#
+		);
+
+		let mut values: Vec<sea_orm::Value> =
+			similarities.keys().map(|uuid| (*uuid).into()).collect();
+		values.extend(scope_values);
+
+		let rows = db
+			.query_all(Statement::from_sql_and_values(
+				db.get_database_backend(),
+				&sql,
+				values,
+			))
+			.await?;
+
+		Ok(rows
+			.iter()
+			.filter_map(|row| {
+				let entry_id = row.try_get::<i32>("", "id").ok()?;
+				let entry_uuid = row.try_get::<Uuid>("", "entry_uuid").ok()?;
+				let content_uuid = row.try_get::<Uuid>("", "content_uuid").ok()?;
+				Some((entry_id, entry_uuid, *similarities.get(&content_uuid)?))
+			})
+			.collect())
+	}
+
+	/// Gather candidates from every field and score them, best first
+	async fn rank_candidates(
+		&self,
+		db: &DatabaseConnection,
+		terms: &[String],
 	) -> QueryResult<Vec<crate::ops::search::output::FileSearchResult>> {
-		// Start with normal search results
-		let mut results = self.execute_normal_search(db, device_slug_map).await?;
+		let match_expr = ranking::fts5_match_expression(terms);
 
-		// For full search, we would add content analysis here
-		// This is a placeholder for future implementation
-		for result in &mut results {
-			// TODO: Add content extraction and analysis
-			// For now, just add a small boost for files that might have content
-			let size = result.file.size;
-			if size > 0 && size < 100_000_000 {
-				// Up to 100MB
-				result.score += 0.05;
-				result.score_breakdown.metadata_score += 0.05;
+		let name_ranks = self
+			.fts_candidate_ranks(db, "search_index", "e.id = search_index.rowid", &match_expr)
+			.await?;
+		let content_ranks = self
+			.fts_candidate_ranks(
+				db,
+				"content_search_index",
+				"e.content_id = content_search_index.rowid",
+				&match_expr,
+			)
+			.await?;
+		let path_ids = self.path_candidate_ids(db, terms).await?;
+
+		let mut candidate_ids: Vec<i32> = name_ranks.keys().copied().collect();
+		candidate_ids.extend(
+			content_ranks
+				.keys()
+				.filter(|id| !name_ranks.contains_key(id)),
+		);
+		candidate_ids.extend(
+			path_ids
+				.into_iter()
+				.filter(|id| !name_ranks.contains_key(id) && !content_ranks.contains_key(id)),
+		);
+
+		tracing::debug!(
+			"Ranking {} candidates ({} name, {} content) for query '{}'",
+			candidate_ids.len(),
+			name_ranks.len(),
+			content_ranks.len(),
+			self.input.query
+		);
+
+		if candidate_ids.is_empty() {
+			return Ok(Vec::new());
+		}
+
+		let entries = self.load_search_entries(db, &candidate_ids).await?;
+		let path_stats = self.path_field_stats(db, terms).await?;
+		let params = ranking::Bm25Params::default();
+		let phrases = ranking::phrases(&self.input.query);
+		let now = Utc::now();
+
+		let mut results = Vec::with_capacity(entries.len());
+		for entry in entries {
+			// Quoted phrases must match as written, not as loose terms
+			if !entry.matches_phrases(&phrases, None) {
+				continue;
 			}
+
+			// FTS5 bm25() is negative, lower is better
+			let name_score = name_ranks
+				.get(&entry.entry_id)
+				.map(|rank| -(*rank as f32) * ranking::NAME_WEIGHT)
+				.unwrap_or(0.0);
+			let content_score = content_ranks
+				.get(&entry.entry_id)
+				.map(|rank| -(*rank as f32) * ranking::CONTENT_WEIGHT)
+				.unwrap_or(0.0);
+			let path_score = entry
+				.parent_path
+				.as_deref()
+				.map(|path| {
+					ranking::bm25(
+						terms,
+						&ranking::tokenize(path),
+						ranking::path_depth(path),
+						&path_stats,
+						params,
+					) * ranking::PATH_WEIGHT
+				})
+				.unwrap_or(0.0);
+
+			let signals = ranking::RankingSignals::compute(
+				name_score,
+				path_score,
+				content_score,
+				entry.file.modified_at,
+				entry.file.accessed_at,
+				entry.is_favorite,
+				now,
+			);
+			if signals.text_score() <= 0.0 {
+				continue;
+			}
+
+			let matched_content = if content_score > 0.0 {
+				entry
+					.file
+					.content_identity
+					.as_ref()
+					.and_then(|ci| ci.text_content.as_deref())
+					.and_then(|text| ranking::snippet(text, terms, MATCHED_CONTENT_CHARS))
+			} else {
+				None
+			};
+
+			results.push(self.ranked_result(entry, terms, signals, matched_content));
 		}
 
-		// Re-sort with content analysis scores
 		results.sort_by(|a, b| {
 			b.score
 				.partial_cmp(&a.score)
@@ -560,6 +951,400 @@ impl FileSearchQuery {
 		Ok(results)
 	}
 
+	/// Read local documents without extracted text and merge their content scores
+	async fn rank_document_text(
+		&self,
+		db: &DatabaseConnection,
+		terms: &[String],
+		results: &mut Vec<crate::ops::search::output::FileSearchResult>,
+	) -> QueryResult<()> {
+		use crate::domain::ContentKind;
+
+		let kind_ids = [
+			ContentKind::Document,
+			ContentKind::Code,
+			ContentKind::Text,
+			ContentKind::Config,
+			ContentKind::Email,
+			ContentKind::Web,
+		]
+		.iter()
+		.map(|kind| (*kind as i32).to_string())
+		.collect::<Vec<_>>()
+		.join(",");
+
+		let (scope_clause, mut values) = self.scope_path_clause();
+		let sql = format!(
+			r#"
+			SELECT e.id AS id
+			FROM entries e
+			JOIN content_identities ci ON ci.id = e.content_id
+			JOIN directory_paths dp ON dp.entry_id = e.parent_id
+			WHERE e.kind = 0
+				AND ci.kind_id IN ({})
+				AND (ci.text_content IS NULL OR ci.text_content = '')
+				AND e.size > 0 AND e.size <= ?
+				{}
+			ORDER BY e.modified_at DESC
+			LIMIT ?
+			"#,
+			kind_ids, scope_clause
+		);
+		values.insert(0, (document_text::MAX_DOCUMENT_BYTES as i64).into());
+		values.push((DOCUMENT_SCAN_LIMIT as i64).into());
+
+		let rows = db
+			.query_all(Statement::from_sql_and_values(
+				db.get_database_backend(),
+				&sql,
+				values,
+			))
+			.await?;
+		let entry_ids: Vec<i32> = rows
+			.iter()
+			.filter_map(|row| row.try_get::<i32>("", "id").ok())
+			.collect();
+
+		// Only files on this device can be read
+		let local_slug = crate::device::get_current_device_slug();
+		let mut documents = Vec::new();
+		for entry in self.load_search_entries(db, &entry_ids).await? {
+			let text = match &entry.file.sd_path {
+				SdPath::Physical { device_slug, path } if *device_slug == local_slug => {
+					document_text::extract_text(path, entry.file.extension.as_deref()).await
+				}
+				_ => None,
+			};
+
+			if let Some(text) = text {
+				let tokens = ranking::tokenize(&text);
+				documents.push((entry, text, tokens));
+			}
+		}
+
+		if documents.is_empty() {
+			return Ok(());
+		}
+
+		// Read documents join the content index corpus for IDF purposes
+		let mut stats = self.content_field_stats(db, terms).await?;
+		let total_len: usize = documents.iter().map(|(_, _, tokens)| tokens.len()).sum();
+		stats.avg_doc_len = total_len as f32 / documents.len() as f32;
+		stats.doc_count += documents.len() as u64;
+		for term in ranking::unique_terms(terms) {
+			let containing = documents
+				.iter()
+				.filter(|(_, _, tokens)| tokens.iter().any(|token| token.starts_with(&term)))
+				.count() as u64;
+			*stats.doc_freq.entry(term).or_insert(0) += containing;
+		}
+
+		let params = ranking::Bm25Params::default();
+		let phrases = ranking::phrases(&self.input.query);
+		let now = Utc::now();
+
+		for (entry, text, tokens) in documents {
+			let content_score = ranking::bm25(terms, &tokens, tokens.len() as f32, &stats, params)
+				* ranking::CONTENT_WEIGHT;
+			if content_score <= 0.0 || !entry.matches_phrases(&phrases, Some(&text)) {
+				continue;
+			}
+			let matched_content = ranking::snippet(&text, terms, MATCHED_CONTENT_CHARS);
+
+			// Already matched on name or path: add the content signal to the existing result
+			if let Some(existing) = results.iter_mut().find(|r| r.file.id == entry.file.id) {
+				let mut signals = existing.score_breakdown.ranking_signals();
+				signals.content_score += content_score;
+				signals.apply_boosts(
+					entry.file.modified_at,
+					entry.file.accessed_at,
+					entry.is_favorite,
+					now,
+				);
+				existing.score = signals.final_score();
+				existing.score_breakdown =
+					crate::ops::search::output::ScoreBreakdown::from_signals(&signals);
+				existing.matched_content = matched_content;
+				continue;
+			}
+
+			let signals = ranking::RankingSignals::compute(
+				0.0,
+				0.0,
+				content_score,
+				entry.file.modified_at,
+				entry.file.accessed_at,
+				entry.is_favorite,
+				now,
+			);
+			results.push(self.ranked_result(entry, terms, signals, matched_content));
+		}
+
+		Ok(())
+	}
+
+	/// Candidate entry ids and their FTS5 bm25() rank from one of the FTS tables
+	async fn fts_candidate_ranks(
+		&self,
+		db: &DatabaseConnection,
+		table: &str,
+		join_on: &str,
+		match_expr: &str,
+	) -> QueryResult<std::collections::HashMap<i32, f64>> {
+		let (scope_clause, scope_values) = self.scope_path_clause();
+		let scope_join = if scope_clause.is_empty() {
+			""
+		} else {
+			"JOIN directory_paths dp ON dp.entry_id = e.parent_id"
+		};
+
+		let sql = format!(
+			r#"
+			SELECT e.id AS id, bm25({table}) AS rank
+			FROM {table}
+			JOIN entries e ON {join_on}
+			{scope_join}
+			WHERE {table} MATCH ?
+				{scope_clause}
+			ORDER BY rank
+			LIMIT {RANKING_CANDIDATE_LIMIT}
+			"#
+		);
+
+		let mut values: Vec<sea_orm::Value> = vec![match_expr.into()];
+		values.extend(scope_values);
+
+		let rows = db
+			.query_all(Statement::from_sql_and_values(
+				db.get_database_backend(),
+				&sql,
+				values,
+			))
+			.await?;
+
+		let mut ranks = std::collections::HashMap::with_capacity(rows.len());
+		for row in rows {
+			let entry_id: i32 = row.try_get("", "id")?;
+			let rank: f64 = row.try_get("", "rank")?;
+			// Several entries can share one content identity, keep the best rank
+			ranks
+				.entry(entry_id)
+				.and_modify(|existing: &mut f64| *existing = existing.min(rank))
+				.or_insert(rank);
+		}
+
+		Ok(ranks)
+	}
+
+	/// Entries whose containing directory path mentions any of the terms
+	async fn path_candidate_ids(
+		&self,
+		db: &DatabaseConnection,
+		terms: &[String],
+	) -> QueryResult<Vec<i32>> {
+		let unique = ranking::unique_terms(terms);
+		let term_clause = unique
+			.iter()
+			.map(|_| "dp.path LIKE ?")
+			.collect::<Vec<_>>()
+			.join(" OR ");
+		let (scope_clause, scope_values) = self.scope_path_clause();
+
+		let sql = format!(
+			r#"
+			SELECT e.id AS id
+			FROM entries e
+			JOIN directory_paths dp ON dp.entry_id = e.parent_id
+			WHERE ({term_clause})
+				{scope_clause}
+			LIMIT {RANKING_CANDIDATE_LIMIT}
+			"#
+		);
+
+		let mut values: Vec<sea_orm::Value> = unique
+			.iter()
+			.map(|term| format!("%{}%", term).into())
+			.collect();
+		values.extend(scope_values);
+
+		let rows = db
+			.query_all(Statement::from_sql_and_values(
+				db.get_database_backend(),
+				&sql,
+				values,
+			))
+			.await?;
+
+		Ok(rows
+			.iter()
+			.filter_map(|row| row.try_get::<i32>("", "id").ok())
+			.collect())
+	}
+
+	/// Corpus statistics for directory paths
+	///
+	/// A path's document length is its number of segments, so the average
+	/// is the mean directory depth.
+	async fn path_field_stats(
+		&self,
+		db: &DatabaseConnection,
+		terms: &[String],
+	) -> QueryResult<ranking::FieldStats> {
+		let backend = db.get_database_backend();
+
+		let totals = db
+			.query_one(Statement::from_string(
+				backend,
+				r#"
+				SELECT
+					COUNT(*) AS doc_count,
+					AVG(LENGTH(path) - LENGTH(REPLACE(path, '/', ''))) AS avg_depth
+				FROM directory_paths
+				"#
+				.to_string(),
+			))
+			.await?;
+		let (doc_count, avg_doc_len) = totals
+			.map(|row| {
+				(
+					row.try_get::<i64>("", "doc_count").unwrap_or(0),
+					row.try_get::<Option<f64>>("", "avg_depth")
+						.ok()
+						.flatten()
+						.unwrap_or(0.0),
+				)
+			})
+			.unwrap_or((0, 0.0));
+
+		let mut doc_freq = std::collections::HashMap::new();
+		for term in ranking::unique_terms(terms) {
+			let count = db
+				.query_one(Statement::from_sql_and_values(
+					backend,
+					"SELECT COUNT(*) AS df FROM directory_paths WHERE path LIKE ?",
+					[format!("%{}%", term).into()],
+				))
+				.await?
+				.and_then(|row| row.try_get::<i64>("", "df").ok())
+				.unwrap_or(0);
+			doc_freq.insert(term, count as u64);
+		}
+
+		Ok(ranking::FieldStats {
+			doc_count: doc_count as u64,
+			avg_doc_len: avg_doc_len as f32,
+			doc_freq,
+		})
+	}
+
+	/// Corpus statistics for the content index (document count and term frequencies)
+	async fn content_field_stats(
+		&self,
+		db: &DatabaseConnection,
+		terms: &[String],
+	) -> QueryResult<ranking::FieldStats> {
+		let backend = db.get_database_backend();
+
+		let doc_count = db
+			.query_one(Statement::from_string(
+				backend,
+				"SELECT COUNT(*) AS doc_count FROM content_search_index".to_string(),
+			))
+			.await?
+			.and_then(|row| row.try_get::<i64>("", "doc_count").ok())
+			.unwrap_or(0);
+
+		let mut doc_freq = std::collections::HashMap::new();
+		for term in ranking::unique_terms(terms) {
+			let count = db
+				.query_one(Statement::from_sql_and_values(
+					backend,
+					"SELECT COUNT(*) AS df FROM content_search_index WHERE content_search_index MATCH ?",
+					[ranking::fts5_match_expression(std::slice::from_ref(&term)).into()],
+				))
+				.await?
+				.and_then(|row| row.try_get::<i64>("", "df").ok())
+				.unwrap_or(0);
+			doc_freq.insert(term, count as u64);
+		}
+
+		Ok(ranking::FieldStats {
+			doc_count: doc_count as u64,
+			avg_doc_len: 0.0,
+			doc_freq,
+		})
+	}
+
+	/// SQL fragment restricting `dp.path` to the search scope, with its bound values
+	fn scope_path_clause(&self) -> (String, Vec<sea_orm::Value>) {
+		match &self.input.scope {
+			SearchScope::Path { path } => match path.path() {
+				Some(path_str) => (
+					"AND dp.path LIKE ?".to_string(),
+					vec![format!("{}%", path_str.to_string_lossy()).into()],
+				),
+				None => (String::new(), Vec::new()),
+			},
+			_ => (String::new(), Vec::new()),
+		}
+	}
+
+	/// SQL fragment restricting `column` to entries passing the tag filter, with its bound values
+	///
+	/// Tags match when applied to the entry or its content, or when a smart tag's
+	/// rules match the entry.
+	fn tag_filter_clause(&self, column: &str) -> (String, Vec<sea_orm::Value>) {
+		let Some(filter) = &self.input.filters.tags else {
+			return (String::new(), Vec::new());
+		};
+
+		let mut clauses = Vec::new();
+		let mut values = Vec::new();
+		let included = filter.include.iter().map(|tag_id| (tag_id, "IN"));
+		let excluded = filter.exclude.iter().map(|tag_id| (tag_id, "NOT IN"));
+		for (tag_id, operator) in included.chain(excluded) {
+			let (sql, tag_values) = crate::ops::tags::smart::tagged_entries_sql(*tag_id);
+			clauses.push(format!("{} {} ({})", column, operator, sql));
+			values.extend(tag_values);
+		}
+
+		(clauses.join(" AND "), values)
+	}
+
+	/// Build a search result from ranking signals
+	fn ranked_result(
+		&self,
+		entry: LoadedSearchEntry,
+		terms: &[String],
+		signals: ranking::RankingSignals,
+		matched_content: Option<String>,
+	) -> crate::ops::search::output::FileSearchResult {
+		let highlights = ranking::unique_terms(terms)
+			.iter()
+			.flat_map(|term| self.extract_highlights(term, &entry.name, &entry.file.extension))
+			.collect();
+
+		crate::ops::search::output::FileSearchResult {
+			file: entry.file,
+			score: signals.final_score(),
+			score_breakdown: crate::ops::search::output::ScoreBreakdown::from_signals(&signals),
+			highlights,
+			matched_content,
+		}
+	}
+
+	/// Apply pagination to results ranked in memory
+	fn paginate_ranked(
+		&self,
+		results: Vec<crate::ops::search::output::FileSearchResult>,
+	) -> Vec<crate::ops::search::output::FileSearchResult> {
+		results
+			.into_iter()
+			.skip(self.input.pagination.offset as usize)
+			.take(self.input.pagination.limit as usize)
+			.collect()
+	}
+
 	/// Apply scope filters to the query condition
 	fn apply_scope_filter(&self, mut condition: Condition) -> Condition {
 		match &self.input.scope {
@@ -803,7 +1588,7 @@ impl FileSearchQuery {
 	}
 
 	/// Get total count of matching entries for pagination
-	async fn get_total_count(
+	pub(crate) async fn get_total_count(
 		&self,
 		db: &DatabaseConnection,
 		registry: &FileTypeRegistry,
@@ -823,6 +1608,13 @@ impl FileSearchQuery {
 			.filter(condition)
 			.filter(entry::Column::Kind.eq(0)); // Only files
 
+		let (tag_clause, tag_values) = self.tag_filter_clause("\"entries\".\"id\"");
+		if !tag_clause.is_empty() {
+			query = query.filter(sea_orm::sea_query::Expr::cust_with_values(
+				tag_clause, tag_values,
+			));
+		}
+
 		// Add location join if location filtering is needed
 		if self.input.filters.locations.is_some() {
 			query = query.join(
@@ -928,6 +1720,13 @@ impl FileSearchQuery {
 
 		query = query.filter(filter_builder.build());
 
+		let (tag_clause, tag_values) = self.tag_filter_clause("\"entries\".\"id\"");
+		if !tag_clause.is_empty() {
+			query = query.filter(sea_orm::sea_query::Expr::cust_with_values(
+				tag_clause, tag_values,
+			));
+		}
+
 		// Apply sorting
 		let sort_builder = SortBuilder::new().apply_sort(&self.input.sort);
 		for (column, order) in sort_builder.build() {
@@ -962,23 +1761,41 @@ impl FileSearchQuery {
 		Ok(results)
 	}
 
+	/// Build the FTS5 MATCH expression for Fast search
+	///
+	/// Every word must match, quoted phrases as written. Each word and phrase is
+	/// wrapped in FTS5 quotes so punctuation in the query is never parsed as
+	/// syntax. The last word matches by prefix for autocomplete.
 	pub fn build_fts5_query(&self) -> String {
-		// Escape special FTS5 characters and build query
-		let escaped_query = self
-			.input
-			.query
-			.replace('"', r#"\""#)
-			.replace('\'', r#"\'"#)
-			.replace('*', r#"\*"#)
-			.replace('(', r#"\("#)
-			.replace(')', r#"\)"#);
+		let query = self.input.query.trim();
+		let mut parts = Vec::new();
+
+		for (i, segment) in query.split('"').enumerate() {
+			// Odd segments sit between quotes
+			if i % 2 == 1 {
+				if segment.chars().any(char::is_alphanumeric) {
+					parts.push(format!("\"{}\"", segment));
+				}
+				continue;
+			}
+
+			parts.extend(
+				segment
+					.split_whitespace()
+					.filter(|word| word.chars().any(char::is_alphanumeric))
+					.map(|word| format!("\"{}\"", word)),
+			);
+		}
 
 		// Add prefix matching for autocomplete if query is long enough
-		if self.input.query.len() > 2 {
-			format!("{}*", escaped_query)
-		} else {
-			escaped_query
+		let ends_with_word = query.matches('"').count() % 2 == 0 && !query.ends_with('"');
+		if ends_with_word && query.len() > 2 {
+			if let Some(last) = parts.last_mut() {
+				last.push('*');
+			}
 		}
+
+		parts.join(" ")
 	}
 
 	/// Execute FTS5 search with BM25 ranking
@@ -1244,6 +2061,10 @@ impl FileSearchQuery {
 			crate::ops::search::input::SearchMode::Full => {
 				self.execute_full_search(db, &device_slug_map).await?
 			}
+			// No library handle here to reach the vector index, rank lexically
+			crate::ops::search::input::SearchMode::Semantic => {
+				self.execute_normal_search(db, &device_slug_map).await?
+			}
 		};
 
 		if entry_results.is_empty() {
@@ -1275,6 +2096,14 @@ impl FileSearchQuery {
 
 		match &self.input.scope {
 			SearchScope::Path { path } => {
+				// Archive contents only ever live in the ephemeral index
+				let in_archive = path
+					.as_local_path()
+					.is_some_and(|path| ArchiveBackend::locate(path).is_some());
+				if in_archive {
+					return Ok(IndexType::Ephemeral);
+				}
+
 				// Check if location has IndexMode::None
 				if let Some(should_use_ephemeral) = self.check_location_index_mode(path, db).await {
 					if should_use_ephemeral {
@@ -1399,6 +2228,7 @@ impl FileSearchQuery {
 	async fn execute_ephemeral_search(
 		&self,
 		context: Arc<CoreContext>,
+		library: &crate::library::Library,
 		search_id: Uuid,
 		start_time: std::time::Instant,
 	) -> QueryResult<FileSearchOutput> {
@@ -1412,12 +2242,24 @@ impl FileSearchQuery {
 		};
 
 		let cache = context.ephemeral_cache();
+
+		// Searching inside an archive that hasn't been opened yet indexes it first
+		if let Some((archive, _)) = path.as_local_path().and_then(ArchiveBackend::locate) {
+			crate::ops::indexing::ephemeral::archive::index_archive(
+				cache,
+				&archive,
+				library.file_types(),
+			)
+			.await
+			.map_err(|e| QueryError::Internal(format!("Failed to read archive: {}", e)))?;
+		}
+
 		let results = crate::ops::search::ephemeral_search::search_ephemeral_index(
 			&self.input.query,
 			path,
 			&self.input.filters,
 			cache,
-			context.file_type_registry(),
+			&library.file_types(),
 		)
 		.await?;
 
@@ -0,0 +1,433 @@
+//! Relevance ranking for Normal and Full search modes
+//!
+//! A result's score is the sum of independent signals so that `ScoreBreakdown`
+//! can report each of them: BM25 over the file name, the containing directory
+//! path and extracted text, an optional embedding similarity for Semantic
+//! search, plus recency, access and favorite boosts that scale with the
+//! relevance score (a strong match that is also recent beats a weak match that
+//! is recent).
+
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, HashSet};
+
+/// Weight applied to BM25 over the file name and extension
+pub const NAME_WEIGHT: f32 = 1.0;
+/// Weight applied to BM25 over the containing directory path
+pub const PATH_WEIGHT: f32 = 0.4;
+/// Weight applied to BM25 over extracted text (OCR, transcripts, documents)
+pub const CONTENT_WEIGHT: f32 = 0.7;
+
+/// Share of the relevance score given to embedding similarity in Semantic search
+pub const SEMANTIC_WEIGHT: f32 = 0.6;
+
+/// Maximum fraction of the text score added for a file modified right now
+const RECENCY_WEIGHT: f32 = 0.25;
+const RECENCY_HALF_LIFE_DAYS: f64 = 30.0;
+
+/// Maximum fraction of the text score added for a file accessed right now
+const ACCESS_WEIGHT: f32 = 0.15;
+const ACCESS_HALF_LIFE_DAYS: f64 = 7.0;
+
+/// Fraction of the text score added for files the user marked as favorite
+const FAVORITE_WEIGHT: f32 = 0.2;
+
+/// Standard BM25 tuning parameters
+#[derive(Debug, Clone, Copy)]
+pub struct Bm25Params {
+	/// Term frequency saturation
+	pub k1: f32,
+	/// Document length normalization
+	pub b: f32,
+}
+
+impl Default for Bm25Params {
+	fn default() -> Self {
+		Self { k1: 1.2, b: 0.75 }
+	}
+}
+
+/// Corpus statistics for a single field, used to compute IDF and length normalization
+#[derive(Debug, Clone, Default)]
+pub struct FieldStats {
+	/// Number of documents in the corpus
+	pub doc_count: u64,
+	/// Average document length, in the same unit as the scored document length
+	pub avg_doc_len: f32,
+	/// Number of documents containing each term
+	pub doc_freq: HashMap<String, u64>,
+}
+
+impl FieldStats {
+	/// Robertson-Sparck Jones IDF, floored at zero by the `+ 1` inside the log
+	pub fn idf(&self, term: &str) -> f32 {
+		let n = self.doc_count as f32;
+		let df = self.doc_freq.get(term).copied().unwrap_or(0) as f32;
+		((n - df + 0.5) / (df + 0.5) + 1.0).ln()
+	}
+}
+
+/// Per-result ranking signals, reported verbatim in `ScoreBreakdown`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct RankingSignals {
+	pub name_score: f32,
+	pub path_score: f32,
+	pub content_score: f32,
+	/// Adjustment from blending in embedding similarity, negative when a lexical
+	/// match is semantically unrelated (Semantic search only)
+	pub semantic_score: f32,
+	pub recency_boost: f32,
+	pub access_boost: f32,
+	pub favorite_boost: f32,
+}
+
+impl RankingSignals {
+	/// Combine weighted text scores with the boosts derived from them
+	pub fn compute(
+		name_score: f32,
+		path_score: f32,
+		content_score: f32,
+		modified_at: DateTime<Utc>,
+		accessed_at: Option<DateTime<Utc>>,
+		is_favorite: bool,
+		now: DateTime<Utc>,
+	) -> Self {
+		let mut signals = Self {
+			name_score,
+			path_score,
+			content_score,
+			..Default::default()
+		};
+		signals.apply_boosts(modified_at, accessed_at, is_favorite, now);
+		signals
+	}
+
+	/// Recompute the boosts after one of the relevance scores changed
+	pub fn apply_boosts(
+		&mut self,
+		modified_at: DateTime<Utc>,
+		accessed_at: Option<DateTime<Utc>>,
+		is_favorite: bool,
+		now: DateTime<Utc>,
+	) {
+		let text_score = self.relevance();
+
+		self.recency_boost = text_score
+			* RECENCY_WEIGHT
+			* decay(age_in_days(modified_at, now), RECENCY_HALF_LIFE_DAYS);
+		self.access_boost = accessed_at
+			.map(|accessed_at| {
+				text_score
+					* ACCESS_WEIGHT * decay(age_in_days(accessed_at, now), ACCESS_HALF_LIFE_DAYS)
+			})
+			.unwrap_or(0.0);
+		self.favorite_boost = if is_favorite {
+			text_score * FAVORITE_WEIGHT
+		} else {
+			0.0
+		};
+	}
+
+	/// Sum of the weighted BM25 field scores
+	pub fn text_score(&self) -> f32 {
+		self.name_score + self.path_score + self.content_score
+	}
+
+	/// Text score plus embedding similarity, the base the boosts scale with
+	pub fn relevance(&self) -> f32 {
+		self.text_score() + self.semantic_score
+	}
+
+	pub fn final_score(&self) -> f32 {
+		self.relevance() + self.recency_boost + self.access_boost + self.favorite_boost
+	}
+}
+
+/// Split text into lowercase alphanumeric tokens
+///
+/// Mirrors the `unicode61` FTS5 tokenizer closely enough that term statistics
+/// gathered from SQLite line up with tokens scored in Rust.
+pub fn tokenize(text: &str) -> Vec<String> {
+	text.split(|c: char| !c.is_alphanumeric())
+		.filter(|token| !token.is_empty())
+		.map(|token| token.to_lowercase())
+		.collect()
+}
+
+/// Deduplicate query terms while keeping their original order
+pub fn unique_terms(terms: &[String]) -> Vec<String> {
+	let mut seen = HashSet::new();
+	terms
+		.iter()
+		.filter(|term| seen.insert(term.as_str()))
+		.cloned()
+		.collect()
+}
+
+/// Quoted phrases of a query, tokenized, in the order they appear
+///
+/// An unclosed quote runs to the end of the query. Phrases without any token
+/// are dropped.
+pub fn phrases(query: &str) -> Vec<Vec<String>> {
+	query
+		.split('"')
+		.skip(1)
+		.step_by(2)
+		.map(tokenize)
+		.filter(|phrase| !phrase.is_empty())
+		.collect()
+}
+
+/// Whether the tokens hold the phrase as consecutive whole tokens
+pub fn contains_phrase(tokens: &[String], phrase: &[String]) -> bool {
+	!phrase.is_empty() && tokens.windows(phrase.len()).any(|window| window == phrase)
+}
+
+/// Build an FTS5 MATCH expression that matches any of the terms by prefix
+///
+/// Terms come from `tokenize`, so they never contain FTS5 syntax characters.
+pub fn fts5_match_expression(terms: &[String]) -> String {
+	unique_terms(terms)
+		.iter()
+		.map(|term| format!("\"{}\"*", term))
+		.collect::<Vec<_>>()
+		.join(" OR ")
+}
+
+/// BM25 score of a tokenized document against the query terms
+///
+/// Terms match by prefix, like the FTS5 queries used to gather candidates.
+pub fn bm25(
+	query_terms: &[String],
+	doc_terms: &[String],
+	doc_len: f32,
+	stats: &FieldStats,
+	params: Bm25Params,
+) -> f32 {
+	if doc_terms.is_empty() || stats.doc_count == 0 {
+		return 0.0;
+	}
+
+	let avg_doc_len = if stats.avg_doc_len > 0.0 {
+		stats.avg_doc_len
+	} else {
+		doc_len.max(1.0)
+	};
+	let length_norm = 1.0 - params.b + params.b * (doc_len / avg_doc_len);
+
+	unique_terms(query_terms)
+		.iter()
+		.map(|term| {
+			let tf = doc_terms
+				.iter()
+				.filter(|token| token.starts_with(term.as_str()))
+				.count() as f32;
+			if tf == 0.0 {
+				return 0.0;
+			}
+			stats.idf(term) * (tf * (params.k1 + 1.0)) / (tf + params.k1 * length_norm)
+		})
+		.sum()
+}
+
+/// Number of segments in a directory path, used as the path field's document length
+pub fn path_depth(path: &str) -> f32 {
+	path.split(['/', '\\'])
+		.filter(|segment| !segment.is_empty())
+		.count() as f32
+}
+
+/// Extract a short excerpt of `text` around the first occurrence of any term
+pub fn snippet(text: &str, terms: &[String], max_chars: usize) -> Option<String> {
+	let lower = text.to_lowercase();
+	// Lowercasing can change byte lengths for some scripts, only trust offsets when it didn't
+	if lower.len() != text.len() {
+		return None;
+	}
+
+	let start = terms
+		.iter()
+		.filter_map(|term| lower.find(term.as_str()))
+		.min()?;
+	let context = max_chars / 2;
+
+	let mut from = start.saturating_sub(context);
+	while !text.is_char_boundary(from) {
+		from -= 1;
+	}
+	let mut to = (start + context).min(text.len());
+	while !text.is_char_boundary(to) {
+		to += 1;
+	}
+
+	let excerpt = text[from..to]
+		.split_whitespace()
+		.collect::<Vec<_>>()
+		.join(" ");
+	Some(format!(
+		"{}{}{}",
+		if from > 0 { "…" } else { "" },
+		excerpt,
+		if to < text.len() { "…" } else { "" }
+	))
+}
+
+/// Blend lexical and embedding relevance for Semantic search
+///
+/// Text scores are rescaled so the best lexical match scores 1.0, then mixed
+/// with cosine similarity by `SEMANTIC_WEIGHT`. The result is expressed back in
+/// lexical units (`max_text_score`) so boosts keep their usual magnitude, and
+/// returned as the `semantic_score` to add to the unchanged text scores.
+pub fn semantic_score(text_score: f32, similarity: f32, max_text_score: f32) -> f32 {
+	let scale = if max_text_score > 0.0 {
+		max_text_score
+	} else {
+		1.0
+	};
+	let lexical = text_score / scale;
+	let blended = (1.0 - SEMANTIC_WEIGHT) * lexical + SEMANTIC_WEIGHT * similarity.max(0.0);
+	blended * scale - text_score
+}
+
+fn age_in_days(at: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+	((now - at).num_seconds().max(0) as f64) / 86_400.0
+}
+
+/// Exponential decay that halves every `half_life` days
+fn decay(age_days: f64, half_life: f64) -> f32 {
+	0.5f64.powf(age_days / half_life) as f32
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::Duration;
+
+	fn terms(query: &str) -> Vec<String> {
+		tokenize(query)
+	}
+
+	fn stats(doc_count: u64, avg_doc_len: f32, doc_freq: &[(&str, u64)]) -> FieldStats {
+		FieldStats {
+			doc_count,
+			avg_doc_len,
+			doc_freq: doc_freq
+				.iter()
+				.map(|(term, df)| (term.to_string(), *df))
+				.collect(),
+		}
+	}
+
+	#[test]
+	fn tokenize_splits_on_punctuation_and_lowercases() {
+		assert_eq!(
+			tokenize("Q3_Report-FINAL v2.pdf"),
+			vec!["q3", "report", "final", "v2", "pdf"]
+		);
+		assert!(tokenize("  --  ").is_empty());
+	}
+
+	#[test]
+	fn phrases_match_consecutive_whole_tokens() {
+		let found = phrases(r#"beach "Exact Phrase" "" "unclosed one"#);
+		assert_eq!(found, vec![terms("exact phrase"), terms("unclosed one")]);
+
+		let phrase = &found[0];
+		assert!(contains_phrase(&terms("an exact-phrase here"), phrase));
+		assert!(!contains_phrase(&terms("phrase exact"), phrase));
+		assert!(!contains_phrase(&terms("exact other phrase"), phrase));
+		assert!(!contains_phrase(&terms("exactly phrased"), phrase));
+	}
+
+	#[test]
+	fn match_expression_ors_unique_prefix_terms() {
+		assert_eq!(
+			fts5_match_expression(&terms("invoice acme invoice")),
+			"\"invoice\"* OR \"acme\"*"
+		);
+	}
+
+	#[test]
+	fn rare_terms_weigh_more() {
+		let stats = stats(1000, 3.0, &[("invoice", 10), ("pdf", 900)]);
+		assert!(stats.idf("invoice") > stats.idf("pdf"));
+		assert!(stats.idf("pdf") > 0.0);
+	}
+
+	#[test]
+	fn bm25_rewards_matches_and_penalizes_long_documents() {
+		let stats = stats(100, 4.0, &[("acme", 5)]);
+		let query = terms("acme");
+
+		let short = terms("clients acme");
+		let long = terms("archive clients old acme backups misc 2019 copies");
+		let none = terms("clients globex");
+
+		let short_score = bm25(
+			&query,
+			&short,
+			short.len() as f32,
+			&stats,
+			Bm25Params::default(),
+		);
+		let long_score = bm25(
+			&query,
+			&long,
+			long.len() as f32,
+			&stats,
+			Bm25Params::default(),
+		);
+		let none_score = bm25(
+			&query,
+			&none,
+			none.len() as f32,
+			&stats,
+			Bm25Params::default(),
+		);
+
+		assert!(short_score > long_score);
+		assert!(long_score > 0.0);
+		assert_eq!(none_score, 0.0);
+	}
+
+	#[test]
+	fn boosts_scale_with_text_score_and_decay() {
+		let now = Utc::now();
+		let fresh = RankingSignals::compute(2.0, 0.0, 0.0, now, Some(now), true, now);
+		assert!((fresh.recency_boost - 0.5).abs() < 1e-4);
+		assert!((fresh.access_boost - 0.3).abs() < 1e-4);
+		assert!((fresh.favorite_boost - 0.4).abs() < 1e-4);
+		assert!((fresh.final_score() - 3.2).abs() < 1e-4);
+
+		let month_old = now - Duration::days(30);
+		let old = RankingSignals::compute(2.0, 0.0, 0.0, month_old, None, false, now);
+		assert!((old.recency_boost - 0.25).abs() < 1e-3);
+		assert_eq!(old.access_boost, 0.0);
+
+		let unmatched = RankingSignals::compute(0.0, 0.0, 0.0, now, Some(now), true, now);
+		assert_eq!(unmatched.final_score(), 0.0);
+	}
+
+	#[test]
+	fn semantic_blend_ranks_meaning_over_weak_keyword_match() {
+		// Best lexical match with no semantic similarity keeps 40% of its score
+		let keyword_only = 4.0 + semantic_score(4.0, 0.0, 4.0);
+		assert!((keyword_only - 1.6).abs() < 1e-4);
+
+		// No keyword overlap but highly similar meaning
+		let meaning_only = semantic_score(0.0, 0.9, 4.0);
+		assert!((meaning_only - 2.16).abs() < 1e-4);
+		assert!(meaning_only > keyword_only);
+
+		// Without lexical matches similarity is used as is
+		assert!((semantic_score(0.0, 0.5, 0.0) - 0.3).abs() < 1e-4);
+	}
+
+	#[test]
+	fn snippet_centers_on_first_match() {
+		let text = "Minutes from the planning meeting. Whiteboard photos are attached below.";
+		let excerpt = snippet(text, &terms("whiteboard"), 30).unwrap();
+		assert!(excerpt.contains("Whiteboard"));
+		assert!(excerpt.starts_with('…'));
+		assert!(snippet(text, &terms("budget"), 30).is_none());
+	}
+}
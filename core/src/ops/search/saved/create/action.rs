@@ -0,0 +1,73 @@
+use super::{input::SavedSearchCreateInput, output::SavedSearchCreateOutput};
+use crate::{
+	context::CoreContext,
+	infra::action::{error::ActionError, LibraryAction},
+};
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, NotSet, Set};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSearchCreateAction {
+	input: SavedSearchCreateInput,
+}
+
+impl LibraryAction for SavedSearchCreateAction {
+	type Input = SavedSearchCreateInput;
+	type Output = SavedSearchCreateOutput;
+
+	fn from_input(input: SavedSearchCreateInput) -> Result<Self, String> {
+		if input.name.trim().is_empty() {
+			return Err("Saved search name cannot be empty".to_string());
+		}
+		input.definition.validate()?;
+
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		library: std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let db = library.db().conn();
+		let now = Utc::now();
+
+		let definition = serde_json::to_value(&self.input.definition)
+			.map_err(|e| ActionError::Internal(format!("Failed to serialize definition: {}", e)))?;
+
+		let active_model = crate::infra::db::entities::saved_search::ActiveModel {
+			id: NotSet,
+			uuid: Set(uuid::Uuid::new_v4()),
+			name: Set(self.input.name.trim().to_string()),
+			definition: Set(definition),
+			created_at: Set(now),
+			updated_at: Set(now),
+		};
+
+		let result = active_model.insert(db).await.map_err(ActionError::SeaOrm)?;
+
+		library
+			.sync_model(&result, crate::infra::sync::ChangeType::Insert)
+			.await
+			.map_err(|e| ActionError::Internal(format!("Failed to sync saved search: {}", e)))?;
+
+		let saved_search = super::super::saved_search_changed(&library, result).await?;
+
+		Ok(SavedSearchCreateOutput { saved_search })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"search.saved.create"
+	}
+
+	async fn validate(
+		&self,
+		_library: &std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<crate::infra::action::ValidationResult, ActionError> {
+		Ok(crate::infra::action::ValidationResult::Success { metadata: None })
+	}
+}
+
+crate::register_library_action!(SavedSearchCreateAction, "search.saved.create");
@@ -0,0 +1,9 @@
+use crate::domain::SearchDefinition;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SavedSearchCreateInput {
+	pub name: String,
+	pub definition: SearchDefinition,
+}
@@ -0,0 +1,63 @@
+use super::{input::SavedSearchDeleteInput, output::SavedSearchDeleteOutput};
+use crate::{
+	context::CoreContext,
+	domain::{resource::EventEmitter, SavedSearch},
+	infra::action::{error::ActionError, LibraryAction},
+};
+use sea_orm::ModelTrait;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSearchDeleteAction {
+	input: SavedSearchDeleteInput,
+}
+
+impl LibraryAction for SavedSearchDeleteAction {
+	type Input = SavedSearchDeleteInput;
+	type Output = SavedSearchDeleteOutput;
+
+	fn from_input(input: SavedSearchDeleteInput) -> Result<Self, String> {
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		library: std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let db = library.db().conn();
+
+		let saved_search_model =
+			super::super::find_saved_search(&library, self.input.saved_search_id).await?;
+		let saved_search_id = saved_search_model.uuid;
+
+		saved_search_model
+			.clone()
+			.delete(db)
+			.await
+			.map_err(ActionError::SeaOrm)?;
+
+		library
+			.sync_model(&saved_search_model, crate::infra::sync::ChangeType::Delete)
+			.await
+			.map_err(|e| ActionError::Internal(format!("Failed to sync saved search: {}", e)))?;
+
+		SavedSearch::emit_deleted(saved_search_id, library.event_bus());
+
+		Ok(SavedSearchDeleteOutput { success: true })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"search.saved.delete"
+	}
+
+	async fn validate(
+		&self,
+		_library: &std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<crate::infra::action::ValidationResult, ActionError> {
+		Ok(crate::infra::action::ValidationResult::Success { metadata: None })
+	}
+}
+
+crate::register_library_action!(SavedSearchDeleteAction, "search.saved.delete");
@@ -0,0 +1,57 @@
+use super::output::SavedSearchesListOutput;
+use crate::domain::SavedSearch;
+use crate::infra::query::{QueryError, QueryResult};
+use crate::{context::CoreContext, infra::query::LibraryQuery};
+use sea_orm::{EntityTrait, QueryOrder};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SavedSearchesListQueryInput;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SavedSearchesListQuery;
+
+impl LibraryQuery for SavedSearchesListQuery {
+	type Input = SavedSearchesListQueryInput;
+	type Output = SavedSearchesListOutput;
+
+	fn from_input(_input: Self::Input) -> QueryResult<Self> {
+		Ok(Self {})
+	}
+
+	async fn execute(
+		self,
+		context: Arc<CoreContext>,
+		session: crate::infra::api::SessionContext,
+	) -> QueryResult<Self::Output> {
+		let library_id = session
+			.current_library_id
+			.ok_or_else(|| QueryError::Internal("No library selected".to_string()))?;
+
+		let library = context
+			.libraries()
+			.await
+			.get_library(library_id)
+			.await
+			.ok_or_else(|| QueryError::Internal("Library not found".to_string()))?;
+
+		let saved_search_models = crate::infra::db::entities::saved_search::Entity::find()
+			.order_by_asc(crate::infra::db::entities::saved_search::Column::Name)
+			.all(library.db().conn())
+			.await?;
+
+		let saved_searches = saved_search_models
+			.into_iter()
+			.map(SavedSearch::from_db_model)
+			.collect::<Result<Vec<_>, _>>()
+			.map_err(|e| QueryError::Internal(e.to_string()))?;
+
+		Ok(SavedSearchesListOutput {
+			saved_searches: super::super::with_counts(&library, saved_searches).await,
+		})
+	}
+}
+
+crate::register_library_query!(SavedSearchesListQuery, "search.saved.list");
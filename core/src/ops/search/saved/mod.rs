@@ -0,0 +1,155 @@
+//! Saved search operations
+//!
+//! Saved searches persist a search definition under a name. Definitions sync across
+//! devices, result counts are computed on each device and refreshed as files change.
+
+pub mod create;
+pub mod delete;
+pub mod list;
+pub mod run;
+pub mod update;
+
+pub use create::*;
+pub use delete::*;
+pub use list::*;
+pub use run::*;
+pub use update::*;
+
+use super::{input::PaginationOptions, query::FileSearchQuery};
+use crate::{
+	domain::{resource::EventEmitter, SavedSearch, SearchDefinition},
+	infra::{
+		action::error::ActionError,
+		db::entities::saved_search,
+		event::Event,
+		query::{QueryError, QueryResult},
+	},
+	library::Library,
+};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Find a saved search by its UUID
+pub(crate) async fn find_saved_search(
+	library: &Library,
+	saved_search_id: Uuid,
+) -> Result<saved_search::Model, ActionError> {
+	saved_search::Entity::find()
+		.filter(saved_search::Column::Uuid.eq(saved_search_id))
+		.one(library.db().conn())
+		.await
+		.map_err(ActionError::SeaOrm)?
+		.ok_or_else(|| ActionError::Internal(format!("Saved search {} not found", saved_search_id)))
+}
+
+/// Count the files a definition matches on this device
+pub async fn count_results(library: &Library, definition: &SearchDefinition) -> QueryResult<u64> {
+	FileSearchQuery::new(definition.to_input(PaginationOptions::default()))
+		.get_total_count(library.db().conn(), &library.file_types())
+		.await
+}
+
+/// Fill in the result counts of saved searches, leaving out those that fail to count
+pub async fn with_counts(
+	library: &Library,
+	mut saved_searches: Vec<SavedSearch>,
+) -> Vec<SavedSearch> {
+	for saved_search in &mut saved_searches {
+		match count_results(library, &saved_search.definition).await {
+			Ok(count) => saved_search.result_count = Some(count),
+			Err(e) => tracing::warn!(
+				saved_search_id = %saved_search.id,
+				error = %e,
+				"Failed to count saved search results"
+			),
+		}
+	}
+	saved_searches
+}
+
+/// Emit a saved search's resource event with its result count and return it
+pub(crate) async fn saved_search_changed(
+	library: &Library,
+	model: saved_search::Model,
+) -> Result<SavedSearch, ActionError> {
+	let saved_search =
+		SavedSearch::from_db_model(model).map_err(|e| ActionError::Internal(e.to_string()))?;
+	let saved_search = with_counts(library, vec![saved_search])
+		.await
+		.pop()
+		.ok_or_else(|| {
+			ActionError::Internal("Saved search disappeared while counting".to_string())
+		})?;
+
+	saved_search
+		.emit_changed(library.event_bus())
+		.map_err(|e| ActionError::Internal(format!("Failed to emit resource event: {}", e)))?;
+
+	Ok(saved_search)
+}
+
+/// Result counts last sent to clients, so refreshes only emit the ones that moved
+#[derive(Debug, Default)]
+pub struct SavedSearchCounts {
+	counts: HashMap<Uuid, u64>,
+	/// Files changed since the last recount
+	stale: bool,
+}
+
+impl SavedSearchCounts {
+	/// Note a resource event, counts go stale when files change
+	pub fn observe(&mut self, event: &Event) {
+		if event.resource_type() == Some("file") {
+			self.stale = true;
+		}
+	}
+
+	/// Recount if files changed since the last recount
+	pub async fn refresh_if_stale(&mut self, library: &Library) -> QueryResult<()> {
+		if !std::mem::take(&mut self.stale) {
+			return Ok(());
+		}
+		self.refresh(library).await
+	}
+
+	/// Recount every saved search and emit those whose count changed
+	pub async fn refresh(&mut self, library: &Library) -> QueryResult<()> {
+		let models = saved_search::Entity::find()
+			.all(library.db().conn())
+			.await
+			.map_err(QueryError::SeaOrm)?;
+
+		let saved_searches = models
+			.into_iter()
+			.filter_map(|model| match SavedSearch::from_db_model(model) {
+				Ok(saved_search) => Some(saved_search),
+				Err(e) => {
+					tracing::warn!(error = %e, "Skipping saved search");
+					None
+				}
+			})
+			.collect();
+
+		let mut counts = HashMap::new();
+		for saved_search in with_counts(library, saved_searches).await {
+			let Some(count) = saved_search.result_count else {
+				continue;
+			};
+			counts.insert(saved_search.id, count);
+
+			if self.counts.get(&saved_search.id) != Some(&count) {
+				if let Err(e) = saved_search.emit_changed(library.event_bus()) {
+					tracing::warn!(
+						saved_search_id = %saved_search.id,
+						error = %e,
+						"Failed to emit saved search count"
+					);
+				}
+			}
+		}
+		self.counts = counts;
+
+		Ok(())
+	}
+}
@@ -0,0 +1,9 @@
+use crate::{domain::SavedSearch, ops::search::output::FileSearchOutput};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SavedSearchRunOutput {
+	pub saved_search: SavedSearch,
+	pub results: FileSearchOutput,
+}
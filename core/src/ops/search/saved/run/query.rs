@@ -0,0 +1,75 @@
+use super::output::SavedSearchRunOutput;
+use crate::domain::SavedSearch;
+use crate::infra::query::{QueryError, QueryResult};
+use crate::ops::search::{input::PaginationOptions, query::FileSearchQuery};
+use crate::{context::CoreContext, infra::query::LibraryQuery};
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SavedSearchRunQueryInput {
+	pub saved_search_id: Uuid,
+	/// Page of results to return, the first 50 if not provided
+	pub pagination: Option<PaginationOptions>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SavedSearchRunQuery {
+	saved_search_id: Uuid,
+	pagination: PaginationOptions,
+}
+
+impl LibraryQuery for SavedSearchRunQuery {
+	type Input = SavedSearchRunQueryInput;
+	type Output = SavedSearchRunOutput;
+
+	fn from_input(input: Self::Input) -> QueryResult<Self> {
+		Ok(Self {
+			saved_search_id: input.saved_search_id,
+			pagination: input.pagination.unwrap_or_default(),
+		})
+	}
+
+	async fn execute(
+		self,
+		context: Arc<CoreContext>,
+		session: crate::infra::api::SessionContext,
+	) -> QueryResult<Self::Output> {
+		let library_id = session
+			.current_library_id
+			.ok_or_else(|| QueryError::Internal("No library selected".to_string()))?;
+
+		let library = context
+			.libraries()
+			.await
+			.get_library(library_id)
+			.await
+			.ok_or_else(|| QueryError::Internal("Library not found".to_string()))?;
+
+		let saved_search_model = crate::infra::db::entities::saved_search::Entity::find()
+			.filter(crate::infra::db::entities::saved_search::Column::Uuid.eq(self.saved_search_id))
+			.one(library.db().conn())
+			.await?
+			.ok_or_else(|| {
+				QueryError::Internal(format!("Saved search {} not found", self.saved_search_id))
+			})?;
+
+		let mut saved_search = SavedSearch::from_db_model(saved_search_model)
+			.map_err(|e| QueryError::Internal(e.to_string()))?;
+
+		let results = FileSearchQuery::new(saved_search.definition.to_input(self.pagination))
+			.execute(context, session)
+			.await?;
+		saved_search.result_count = Some(results.total_found);
+
+		Ok(SavedSearchRunOutput {
+			saved_search,
+			results,
+		})
+	}
+}
+
+crate::register_library_query!(SavedSearchRunQuery, "search.saved.run");
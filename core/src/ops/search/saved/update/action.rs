@@ -0,0 +1,80 @@
+use super::{input::SavedSearchUpdateInput, output::SavedSearchUpdateOutput};
+use crate::{
+	context::CoreContext,
+	infra::action::{error::ActionError, LibraryAction},
+};
+use chrono::Utc;
+use sea_orm::{ActiveModelTrait, Set};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSearchUpdateAction {
+	input: SavedSearchUpdateInput,
+}
+
+impl LibraryAction for SavedSearchUpdateAction {
+	type Input = SavedSearchUpdateInput;
+	type Output = SavedSearchUpdateOutput;
+
+	fn from_input(input: SavedSearchUpdateInput) -> Result<Self, String> {
+		if let Some(name) = &input.name {
+			if name.trim().is_empty() {
+				return Err("Saved search name cannot be empty".to_string());
+			}
+		}
+		if let Some(definition) = &input.definition {
+			definition.validate()?;
+		}
+
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		library: std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let db = library.db().conn();
+
+		let saved_search_model =
+			super::super::find_saved_search(&library, self.input.saved_search_id).await?;
+
+		let mut active_model: crate::infra::db::entities::saved_search::ActiveModel =
+			saved_search_model.into();
+
+		if let Some(name) = self.input.name {
+			active_model.name = Set(name.trim().to_string());
+		}
+		if let Some(definition) = self.input.definition {
+			active_model.definition = Set(serde_json::to_value(&definition).map_err(|e| {
+				ActionError::Internal(format!("Failed to serialize definition: {}", e))
+			})?);
+		}
+		active_model.updated_at = Set(Utc::now());
+
+		let result = active_model.update(db).await.map_err(ActionError::SeaOrm)?;
+
+		library
+			.sync_model(&result, crate::infra::sync::ChangeType::Update)
+			.await
+			.map_err(|e| ActionError::Internal(format!("Failed to sync saved search: {}", e)))?;
+
+		let saved_search = super::super::saved_search_changed(&library, result).await?;
+
+		Ok(SavedSearchUpdateOutput { saved_search })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"search.saved.update"
+	}
+
+	async fn validate(
+		&self,
+		_library: &std::sync::Arc<crate::library::Library>,
+		_context: std::sync::Arc<CoreContext>,
+	) -> Result<crate::infra::action::ValidationResult, ActionError> {
+		Ok(crate::infra::action::ValidationResult::Success { metadata: None })
+	}
+}
+
+crate::register_library_action!(SavedSearchUpdateAction, "search.saved.update");
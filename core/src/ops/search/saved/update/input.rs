@@ -0,0 +1,13 @@
+use crate::domain::SearchDefinition;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SavedSearchUpdateInput {
+	pub saved_search_id: Uuid,
+	/// New name, unchanged if not provided
+	pub name: Option<String>,
+	/// New definition, unchanged if not provided
+	pub definition: Option<SearchDefinition>,
+}
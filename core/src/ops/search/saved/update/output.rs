@@ -0,0 +1,8 @@
+use crate::domain::SavedSearch;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SavedSearchUpdateOutput {
+	pub saved_search: SavedSearch,
+}
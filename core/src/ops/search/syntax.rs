@@ -0,0 +1,809 @@
+//! Structured search query syntax
+//!
+//! A search string can carry its own filters next to the text to match:
+//!
+//! - `kind:image` content kind, `kind:image,video` for either
+//! - `ext:raw` extension, case insensitive, `ext:cr2,nef` for either
+//! - `size:>20MB`, `size:<=500KB`, `size:1MB..5MB` size in B, KB, MB, GB or TB
+//!   (powers of 1024)
+//! - `modified:2024`, `created:>2024-06`, `accessed:2024-01-01..2024-03` date by
+//!   year, month or day in UTC, `indexed:` for when the file was indexed
+//! - `tag:clients/acme` carries a tag, named alone or after its parents, and
+//!   `-tag:archived` doesn't carry it
+//! - `"exact phrase"` text kept together
+//!
+//! Anything else is text to match. Values with spaces can be quoted, as in
+//! `tag:"client work"`. Filters combine with AND, comma separated values with OR.
+//! A query holds at most one size and one date filter, like `SearchFilters`.
+//!
+//! Parsing needs no library, tags are resolved to ids afterwards with
+//! [`TagTerm::select`]. A parsed query prints in canonical form: text first, then
+//! filters in a fixed order with normalized values. Parsing the canonical form
+//! gives back the same filters and text.
+
+use super::input::{DateField, DateRangeFilter, SearchFilters, SizeRangeFilter};
+use crate::domain::{tag::Tag, ContentKind};
+use crate::ops::tags::smart::{parse_size, Comparison};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::{fmt, ops::Range};
+use uuid::Uuid;
+
+const KEYS: &[&str] = &[
+	"kind", "ext", "size", "modified", "created", "accessed", "indexed", "tag",
+];
+
+/// Byte offsets of part of a query, `end` exclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct Span {
+	pub start: usize,
+	pub end: usize,
+}
+
+impl Span {
+	fn new(start: usize, end: usize) -> Self {
+		Self { start, end }
+	}
+}
+
+/// A query that can't be parsed, pointing at the part at fault
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type, thiserror::Error)]
+#[error("{message} (at {}..{})", .span.start, .span.end)]
+pub struct SyntaxError {
+	pub message: String,
+	pub span: Span,
+}
+
+impl SyntaxError {
+	fn new(message: impl Into<String>, span: Span) -> Self {
+		Self {
+			message: message.into(),
+			span,
+		}
+	}
+}
+
+/// Text to match
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TextTerm {
+	Word(String),
+	Phrase(String),
+}
+
+/// Value of a size or date filter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Bounds<T> {
+	/// `>v`, `<=v`, or a bare `v` for equal
+	Compare(Comparison, T),
+	/// `a..b`, either side may be left open
+	Between(Option<T>, Option<T>),
+}
+
+impl<T> Bounds<T> {
+	fn display(&self, value: impl Fn(&T) -> String) -> String {
+		match self {
+			Self::Compare(comparison, v) => {
+				let operator = match comparison {
+					Comparison::Less => "<",
+					Comparison::LessOrEqual => "<=",
+					Comparison::Equal => "",
+					Comparison::GreaterOrEqual => ">=",
+					Comparison::Greater => ">",
+				};
+				format!("{}{}", operator, value(v))
+			}
+			Self::Between(start, end) => format!(
+				"{}..{}",
+				start.as_ref().map(&value).unwrap_or_default(),
+				end.as_ref().map(&value).unwrap_or_default()
+			),
+		}
+	}
+}
+
+/// A year, month or day in UTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PartialDate {
+	year: i32,
+	month: Option<u32>,
+	day: Option<u32>,
+}
+
+impl PartialDate {
+	/// Parse `2024`, `2024-06` or `2024-06-30`
+	fn parse(value: &str) -> Option<Self> {
+		let number = |part: &str, digits: Range<usize>| -> Option<u32> {
+			(digits.contains(&part.len()) && part.bytes().all(|b| b.is_ascii_digit()))
+				.then(|| part.parse().ok())
+				.flatten()
+		};
+
+		let parts: Vec<&str> = value.split('-').collect();
+		let (year, month, day) = match parts.as_slice() {
+			[year] => (number(year, 4..5)?, None, None),
+			[year, month] => (number(year, 4..5)?, Some(number(month, 1..3)?), None),
+			[year, month, day] => (
+				number(year, 4..5)?,
+				Some(number(month, 1..3)?),
+				Some(number(day, 1..3)?),
+			),
+			_ => return None,
+		};
+
+		let date = Self {
+			year: year as i32,
+			month,
+			day,
+		};
+		NaiveDate::from_ymd_opt(date.year, month.unwrap_or(1), day.unwrap_or(1))?;
+		Some(date)
+	}
+
+	fn first_day(&self) -> NaiveDate {
+		NaiveDate::from_ymd_opt(self.year, self.month.unwrap_or(1), self.day.unwrap_or(1))
+			.expect("validated when parsed")
+	}
+
+	/// First day after the period
+	fn next_day(&self) -> NaiveDate {
+		let next = match (self.month, self.day) {
+			(_, Some(_)) => self.first_day().succ_opt(),
+			(Some(12), None) => NaiveDate::from_ymd_opt(self.year + 1, 1, 1),
+			(Some(month), None) => NaiveDate::from_ymd_opt(self.year, month + 1, 1),
+			(None, None) => NaiveDate::from_ymd_opt(self.year + 1, 1, 1),
+		};
+		next.expect("four digit years stay in range")
+	}
+
+	/// First instant of the period
+	pub fn start(&self) -> DateTime<Utc> {
+		self.first_day().and_time(NaiveTime::MIN).and_utc()
+	}
+
+	/// Last instant of the period
+	pub fn end(&self) -> DateTime<Utc> {
+		self.next_day().and_time(NaiveTime::MIN).and_utc() - Duration::nanoseconds(1)
+	}
+}
+
+impl fmt::Display for PartialDate {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{:04}", self.year)?;
+		if let Some(month) = self.month {
+			write!(f, "-{:02}", month)?;
+		}
+		if let Some(day) = self.day {
+			write!(f, "-{:02}", day)?;
+		}
+		Ok(())
+	}
+}
+
+/// A `tag:` or `-tag:` filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagTerm {
+	/// Names of parent tags, then the tag's own name
+	pub path: Vec<String>,
+	pub exclude: bool,
+	pub span: Span,
+}
+
+impl TagTerm {
+	/// The tag's own name, to look candidates up by
+	pub fn name(&self) -> &str {
+		self.path.last().expect("tag paths are never empty")
+	}
+
+	/// Pick the tag this term names
+	///
+	/// `candidates` are the tags matching [`Self::name`], each with its ancestors.
+	/// Every parent in the path has to be one of the ancestors or the namespace.
+	pub fn select(&self, candidates: &[(Tag, Vec<Tag>)]) -> Result<Uuid, SyntaxError> {
+		let parents = &self.path[..self.path.len() - 1];
+		let matching: Vec<&Tag> = candidates
+			.iter()
+			.filter(|(tag, ancestors)| {
+				parents.iter().all(|parent| {
+					tag.namespace
+						.as_deref()
+						.is_some_and(|namespace| namespace.eq_ignore_ascii_case(parent))
+						|| ancestors
+							.iter()
+							.any(|ancestor| ancestor.canonical_name.eq_ignore_ascii_case(parent))
+				})
+			})
+			.map(|(tag, _)| tag)
+			.collect();
+
+		match matching.as_slice() {
+			[tag] => Ok(tag.id),
+			[] => Err(SyntaxError::new(
+				format!("no tag named '{}'", self.path.join("/")),
+				self.span,
+			)),
+			_ => Err(SyntaxError::new(
+				format!(
+					"'{}' matches {} tags, name a parent too, as in parent/{}",
+					self.path.join("/"),
+					matching.len(),
+					self.name()
+				),
+				self.span,
+			)),
+		}
+	}
+}
+
+/// A parsed search query
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchSyntax {
+	pub text: Vec<TextTerm>,
+	pub kinds: Vec<ContentKind>,
+	pub extensions: Vec<String>,
+	pub size: Option<Bounds<u64>>,
+	pub date: Option<(DateField, Bounds<PartialDate>)>,
+	pub tags: Vec<TagTerm>,
+}
+
+impl SearchSyntax {
+	pub fn parse(input: &str) -> Result<Self, SyntaxError> {
+		let mut syntax = Self::default();
+		for span in token_spans(input)? {
+			syntax.add_token(input, span)?;
+		}
+		Ok(syntax)
+	}
+
+	/// Text to match, with the filters taken out
+	pub fn query(&self) -> String {
+		self.text
+			.iter()
+			.map(|term| match term {
+				TextTerm::Word(word) => word.clone(),
+				TextTerm::Phrase(phrase) => quote(phrase),
+			})
+			.collect::<Vec<_>>()
+			.join(" ")
+	}
+
+	/// Filters the query asks for, except tags which need resolving first
+	pub fn filters(&self) -> SearchFilters {
+		SearchFilters {
+			file_types: (!self.extensions.is_empty()).then(|| self.extensions.clone()),
+			content_types: (!self.kinds.is_empty()).then(|| self.kinds.clone()),
+			size_range: self.size.map(size_range),
+			date_range: self
+				.date
+				.as_ref()
+				.map(|(field, bounds)| date_range(field.clone(), *bounds)),
+			..Default::default()
+		}
+	}
+
+	fn add_token(&mut self, input: &str, span: Span) -> Result<(), SyntaxError> {
+		let raw = &input[span.start..span.end];
+		if raw.starts_with('"') {
+			let phrase = unquote(raw, span.start)?;
+			if !phrase.is_empty() {
+				self.text.push(TextTerm::Phrase(phrase));
+			}
+			return Ok(());
+		}
+
+		let (exclude, body) = match raw.strip_prefix('-') {
+			Some(body) => (true, body),
+			None => (false, raw),
+		};
+		let body_start = span.end - body.len();
+		let Some((key, value)) = body
+			.split_once(':')
+			.filter(|(key, _)| KEYS.iter().any(|known| known.eq_ignore_ascii_case(key)))
+		else {
+			self.text.push(TextTerm::Word(raw.to_string()));
+			return Ok(());
+		};
+
+		let key = key.to_ascii_lowercase();
+		let value_start = body_start + key.len() + 1;
+		if exclude && key != "tag" {
+			return Err(SyntaxError::new(
+				format!("{}: can't be negated, only tag: can", key),
+				Span::new(span.start, body_start),
+			));
+		}
+
+		let value = Value::parse(value, value_start)?;
+		if value.text.is_empty() {
+			return Err(SyntaxError::new(
+				format!("expected a value after {}:", key),
+				Span::new(body_start, value_start),
+			));
+		}
+
+		match key.as_str() {
+			"kind" => {
+				for (item, item_span) in value.items()? {
+					let name = item.to_lowercase();
+					let kind = ContentKind::from(name.as_str());
+					if kind == ContentKind::Unknown && name != "unknown" {
+						return Err(SyntaxError::new(
+							format!("unknown kind '{}'", item),
+							item_span,
+						));
+					}
+					if !self.kinds.contains(&kind) {
+						self.kinds.push(kind);
+					}
+				}
+			}
+			"ext" => {
+				for (item, item_span) in value.items()? {
+					let extension = item.trim_start_matches('.').to_lowercase();
+					if extension.is_empty() {
+						return Err(SyntaxError::new("expected an extension", item_span));
+					}
+					if !self.extensions.contains(&extension) {
+						self.extensions.push(extension);
+					}
+				}
+			}
+			"size" => {
+				if self.size.is_some() {
+					return Err(SyntaxError::new("only one size: filter is allowed", span));
+				}
+				let bounds = value.bounds(parse_size, "a size such as 20MB")?;
+				match bounds {
+					Bounds::Compare(Comparison::Less, 0) => {
+						return Err(SyntaxError::new("no file is smaller than 0B", value.span));
+					}
+					Bounds::Between(Some(min), Some(max)) if min > max => {
+						return Err(SyntaxError::new(
+							"size range starts after it ends",
+							value.span,
+						));
+					}
+					_ => {}
+				}
+				self.size = Some(bounds);
+			}
+			"tag" => {
+				let path: Vec<String> = value.text.split('/').map(str::to_string).collect();
+				if path.iter().any(String::is_empty) {
+					return Err(SyntaxError::new("expected a tag name", value.span));
+				}
+				self.tags.push(TagTerm {
+					path,
+					exclude,
+					span,
+				});
+			}
+			_ => {
+				if self.date.is_some() {
+					return Err(SyntaxError::new("only one date filter is allowed", span));
+				}
+				let field = match key.as_str() {
+					"created" => DateField::CreatedAt,
+					"accessed" => DateField::AccessedAt,
+					"indexed" => DateField::IndexedAt,
+					_ => DateField::ModifiedAt,
+				};
+				let bounds = value.bounds(
+					PartialDate::parse,
+					"a date such as 2024, 2024-06 or 2024-06-30",
+				)?;
+				if let Bounds::Between(Some(start), Some(end)) = bounds {
+					if start.start() > end.end() {
+						return Err(SyntaxError::new(
+							"date range starts after it ends",
+							value.span,
+						));
+					}
+				}
+				self.date = Some((field, bounds));
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl fmt::Display for SearchSyntax {
+	/// Canonical form of the query
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		let mut parts = Vec::new();
+		let query = self.query();
+		if !query.is_empty() {
+			parts.push(query);
+		}
+		if !self.kinds.is_empty() {
+			let kinds: Vec<String> = self.kinds.iter().map(ToString::to_string).collect();
+			parts.push(format!("kind:{}", kinds.join(",")));
+		}
+		if !self.extensions.is_empty() {
+			let extensions: Vec<String> = self.extensions.iter().map(|e| quote_value(e)).collect();
+			parts.push(format!("ext:{}", extensions.join(",")));
+		}
+		if let Some(size) = &self.size {
+			parts.push(format!(
+				"size:{}",
+				size.display(|bytes| format_size(*bytes))
+			));
+		}
+		if let Some((field, bounds)) = &self.date {
+			let key = match field {
+				DateField::CreatedAt => "created",
+				DateField::ModifiedAt => "modified",
+				DateField::AccessedAt => "accessed",
+				DateField::IndexedAt => "indexed",
+			};
+			parts.push(format!("{}:{}", key, bounds.display(ToString::to_string)));
+		}
+		for tag in &self.tags {
+			parts.push(format!(
+				"{}tag:{}",
+				if tag.exclude { "-" } else { "" },
+				quote_value(&tag.path.join("/"))
+			));
+		}
+
+		write!(f, "{}", parts.join(" "))
+	}
+}
+
+/// A filter value, unquoted
+struct Value {
+	text: String,
+	span: Span,
+	quoted: bool,
+}
+
+impl Value {
+	fn parse(raw: &str, start: usize) -> Result<Self, SyntaxError> {
+		let quoted = raw.starts_with('"');
+		Ok(Self {
+			text: if quoted {
+				unquote(raw, start)?
+			} else {
+				raw.to_string()
+			},
+			span: Span::new(start, start + raw.len()),
+			quoted,
+		})
+	}
+
+	/// Span of part of the text, or of the whole value when quotes moved offsets
+	fn span_of(&self, range: Range<usize>) -> Span {
+		if self.quoted {
+			self.span
+		} else {
+			Span::new(self.span.start + range.start, self.span.start + range.end)
+		}
+	}
+
+	/// Comma separated items with their spans
+	fn items(&self) -> Result<Vec<(&str, Span)>, SyntaxError> {
+		let mut offset = 0;
+		self.text
+			.split(',')
+			.map(|item| {
+				let span = self.span_of(offset..offset + item.len());
+				offset += item.len() + 1;
+				if item.is_empty() {
+					Err(SyntaxError::new("expected a value", span))
+				} else {
+					Ok((item, span))
+				}
+			})
+			.collect()
+	}
+
+	fn bounds<T>(
+		&self,
+		parse: impl Fn(&str) -> Option<T>,
+		expected: &str,
+	) -> Result<Bounds<T>, SyntaxError> {
+		let text = self.text.as_str();
+		let invalid = |range: Range<usize>| {
+			SyntaxError::new(format!("expected {}", expected), self.span_of(range))
+		};
+
+		let comparisons = [
+			(">=", Comparison::GreaterOrEqual),
+			("<=", Comparison::LessOrEqual),
+			(">", Comparison::Greater),
+			("<", Comparison::Less),
+			("=", Comparison::Equal),
+		];
+		for (operator, comparison) in comparisons {
+			if let Some(rest) = text.strip_prefix(operator) {
+				return parse(rest)
+					.map(|value| Bounds::Compare(comparison, value))
+					.ok_or_else(|| invalid(operator.len()..text.len()));
+			}
+		}
+
+		if let Some((start, end)) = text.split_once("..") {
+			let end_offset = start.len() + 2;
+			let side = |side: &str, range: Range<usize>| {
+				if side.is_empty() {
+					Ok(None)
+				} else {
+					parse(side).map(Some).ok_or_else(|| invalid(range))
+				}
+			};
+			let start = side(start, 0..start.len())?;
+			let end = side(end, end_offset..text.len())?;
+			if start.is_none() && end.is_none() {
+				return Err(invalid(0..text.len()));
+			}
+			return Ok(Bounds::Between(start, end));
+		}
+
+		parse(text)
+			.map(|value| Bounds::Compare(Comparison::Equal, value))
+			.ok_or_else(|| invalid(0..text.len()))
+	}
+}
+
+/// Split a query on whitespace outside quotes
+fn token_spans(input: &str) -> Result<Vec<Span>, SyntaxError> {
+	let mut spans = Vec::new();
+	let mut chars = input.char_indices().peekable();
+
+	while let Some(&(start, c)) = chars.peek() {
+		if c.is_whitespace() {
+			chars.next();
+			continue;
+		}
+
+		let mut end = input.len();
+		let mut open_quote = None;
+		let mut escaped = false;
+		while let Some(&(index, c)) = chars.peek() {
+			match open_quote {
+				Some(_) if escaped => escaped = false,
+				Some(_) if c == '\\' => escaped = true,
+				Some(_) if c == '"' => open_quote = None,
+				Some(_) => {}
+				None if c == '"' => open_quote = Some(index),
+				None if c.is_whitespace() => {
+					end = index;
+					break;
+				}
+				None => {}
+			}
+			chars.next();
+		}
+
+		if let Some(quote) = open_quote {
+			return Err(SyntaxError::new(
+				"missing closing quote",
+				Span::new(quote, input.len()),
+			));
+		}
+		spans.push(Span::new(start, end));
+	}
+
+	Ok(spans)
+}
+
+/// Contents of a quoted string starting at `start` in the query
+fn unquote(raw: &str, start: usize) -> Result<String, SyntaxError> {
+	let mut text = String::new();
+	let mut chars = raw.char_indices().skip(1);
+
+	while let Some((index, c)) = chars.next() {
+		match c {
+			'\\' => match chars.next() {
+				Some((_, escaped @ ('"' | '\\'))) => text.push(escaped),
+				Some((_, other)) => {
+					text.push('\\');
+					text.push(other);
+				}
+				None => text.push('\\'),
+			},
+			'"' => {
+				let rest = index + 1;
+				if rest < raw.len() {
+					return Err(SyntaxError::new(
+						"expected a space after the closing quote",
+						Span::new(start + rest, start + raw.len()),
+					));
+				}
+				return Ok(text);
+			}
+			c => text.push(c),
+		}
+	}
+
+	Err(SyntaxError::new(
+		"missing closing quote",
+		Span::new(start, start + raw.len()),
+	))
+}
+
+fn quote(text: &str) -> String {
+	format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Quote a filter value only if it couldn't be read back otherwise
+fn quote_value(value: &str) -> String {
+	if value.is_empty() || value.contains(|c: char| c.is_whitespace() || c == '"') {
+		quote(value)
+	} else {
+		value.to_string()
+	}
+}
+
+/// Bytes in the largest unit that holds them exactly
+fn format_size(bytes: u64) -> String {
+	for (unit, shift) in [("TB", 40), ("GB", 30), ("MB", 20), ("KB", 10)] {
+		if bytes != 0 && bytes % (1 << shift) == 0 {
+			return format!("{}{}", bytes >> shift, unit);
+		}
+	}
+	format!("{}B", bytes)
+}
+
+fn size_range(bounds: Bounds<u64>) -> SizeRangeFilter {
+	let (min, max) = match bounds {
+		Bounds::Compare(Comparison::Less, size) => (None, Some(size.saturating_sub(1))),
+		Bounds::Compare(Comparison::LessOrEqual, size) => (None, Some(size)),
+		Bounds::Compare(Comparison::Equal, size) => (Some(size), Some(size)),
+		Bounds::Compare(Comparison::GreaterOrEqual, size) => (Some(size), None),
+		Bounds::Compare(Comparison::Greater, size) => (Some(size.saturating_add(1)), None),
+		Bounds::Between(min, max) => (min, max),
+	};
+	SizeRangeFilter { min, max }
+}
+
+fn date_range(field: DateField, bounds: Bounds<PartialDate>) -> DateRangeFilter {
+	let (start, end) = match bounds {
+		Bounds::Compare(Comparison::Less, date) => {
+			(None, Some(date.start() - Duration::nanoseconds(1)))
+		}
+		Bounds::Compare(Comparison::LessOrEqual, date) => (None, Some(date.end())),
+		Bounds::Compare(Comparison::Equal, date) => (Some(date.start()), Some(date.end())),
+		Bounds::Compare(Comparison::GreaterOrEqual, date) => (Some(date.start()), None),
+		Bounds::Compare(Comparison::Greater, date) => {
+			(Some(date.end() + Duration::nanoseconds(1)), None)
+		}
+		Bounds::Between(start, end) => (start.map(|date| date.start()), end.map(|date| date.end())),
+	};
+	DateRangeFilter { field, start, end }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::TimeZone;
+
+	fn parse(input: &str) -> SearchSyntax {
+		SearchSyntax::parse(input).unwrap()
+	}
+
+	fn error_at(input: &str) -> &str {
+		let error = SearchSyntax::parse(input).unwrap_err();
+		&input[error.span.start..error.span.end]
+	}
+
+	#[test]
+	fn parses_filters_and_text() {
+		let syntax = parse(
+			r#"kind:image size:>20MB tag:clients/acme modified:2024..2025 ext:RAW -tag:archived "exact phrase" beach"#,
+		);
+
+		assert_eq!(syntax.query(), r#""exact phrase" beach"#);
+		assert_eq!(syntax.kinds, vec![ContentKind::Image]);
+		assert_eq!(syntax.extensions, vec!["raw"]);
+		assert_eq!(syntax.tags.len(), 2);
+		assert_eq!(syntax.tags[0].path, vec!["clients", "acme"]);
+		assert!(!syntax.tags[0].exclude);
+		assert_eq!(syntax.tags[1].path, vec!["archived"]);
+		assert!(syntax.tags[1].exclude);
+
+		let filters = syntax.filters();
+		let size = filters.size_range.unwrap();
+		assert_eq!(size.min, Some((20 << 20) + 1));
+		assert_eq!(size.max, None);
+
+		let date = filters.date_range.unwrap();
+		assert_eq!(date.field, DateField::ModifiedAt);
+		assert_eq!(
+			date.start,
+			Some(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+		);
+		assert_eq!(
+			date.end,
+			Some(Utc.with_ymd_and_hms(2026, 1, 1, 0, 0, 0).unwrap() - Duration::nanoseconds(1))
+		);
+	}
+
+	#[test]
+	fn unknown_keys_and_plain_dashes_are_text() {
+		let syntax = parse("10:30 -draft note:x");
+		assert_eq!(syntax.query(), "10:30 -draft note:x");
+		assert_eq!(syntax.filters().file_types, None);
+	}
+
+	#[test]
+	fn date_comparisons_cover_whole_periods() {
+		let after = parse("created:>2024-06").filters().date_range.unwrap();
+		assert_eq!(
+			after.start,
+			Some(Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap())
+		);
+
+		let before = parse("modified:<2024-06-15").filters().date_range.unwrap();
+		assert_eq!(
+			before.end,
+			Some(Utc.with_ymd_and_hms(2024, 6, 15, 0, 0, 0).unwrap() - Duration::nanoseconds(1))
+		);
+
+		let december = parse("indexed:2024-12").filters().date_range.unwrap();
+		assert_eq!(december.field, DateField::IndexedAt);
+		assert_eq!(
+			december.end,
+			Some(Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap() - Duration::nanoseconds(1))
+		);
+	}
+
+	#[test]
+	fn canonical_form_round_trips() {
+		let original = r#"  EXT:.JPG,png  -TAG:"old work"  size:1536KB..2GB kind:video,image "a \"b\"" created:2024-6 foo"#;
+		let syntax = parse(original);
+		let canonical = syntax.to_string();
+
+		assert_eq!(
+			canonical,
+			r#""a \"b\"" foo kind:video,image ext:jpg,png size:1536KB..2GB created:2024-06 -tag:"old work""#
+		);
+
+		let reparsed = parse(&canonical);
+		assert_eq!(reparsed.to_string(), canonical);
+		assert_eq!(reparsed.query(), syntax.query());
+		assert_eq!(reparsed.kinds, syntax.kinds);
+		assert_eq!(reparsed.extensions, syntax.extensions);
+		assert_eq!(reparsed.size, syntax.size);
+		assert_eq!(reparsed.date, syntax.date);
+		assert_eq!(reparsed.tags[0].path, syntax.tags[0].path);
+	}
+
+	#[test]
+	fn errors_point_at_the_problem() {
+		assert_eq!(error_at("kind:image,photo"), "photo");
+		assert_eq!(error_at("size:>20XB"), "20XB");
+		assert_eq!(error_at("modified:2024..2024-13"), "2024-13");
+		assert_eq!(error_at("a \"unclosed phrase"), "\"unclosed phrase");
+		assert_eq!(error_at("\"phrase\"tail"), "tail");
+		assert_eq!(error_at("x -kind:image"), "-");
+		assert_eq!(error_at("ext:"), "ext:");
+		assert_eq!(error_at("size:1MB size:2MB"), "size:2MB");
+		assert_eq!(error_at("created:2024 modified:2025"), "modified:2025");
+		assert_eq!(error_at("size:5MB..1MB"), "5MB..1MB");
+		assert_eq!(error_at("tag:clients/"), "clients/");
+	}
+
+	#[test]
+	fn tag_paths_select_by_parent() {
+		let device = Uuid::new_v4();
+		let clients = Tag::new("clients".to_string(), device);
+		let acme = Tag::new("acme".to_string(), device);
+		let mut legacy_acme = Tag::new("acme".to_string(), device);
+		legacy_acme.namespace = Some("legacy".to_string());
+		let candidates = vec![
+			(acme.clone(), vec![clients.clone()]),
+			(legacy_acme.clone(), Vec::new()),
+		];
+
+		let term = |query: &str| parse(query).tags.remove(0);
+		assert_eq!(term("tag:clients/acme").select(&candidates), Ok(acme.id));
+		assert_eq!(
+			term("tag:Legacy/acme").select(&candidates),
+			Ok(legacy_acme.id)
+		);
+		assert!(term("tag:acme").select(&candidates).is_err());
+		assert!(term("tag:other/acme").select(&candidates).is_err());
+	}
+}
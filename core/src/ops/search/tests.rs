@@ -105,7 +105,19 @@ mod tests {
 		let search_input_special = FileSearchInput::simple("test*query".to_string());
 		let query_special = FileSearchQuery::new(search_input_special);
 		let fts_query_special = query_special.build_fts5_query();
-		assert!(fts_query_special.contains("test\\*query"));
+		assert_eq!(fts_query_special, "\"test*query\"*");
+
+		// Quoted phrases stay FTS5 phrases
+		let search_input_phrase =
+			FileSearchInput::simple(r#""annual report" 2024 draf"#.to_string());
+		let query_phrase = FileSearchQuery::new(search_input_phrase);
+		assert_eq!(
+			query_phrase.build_fts5_query(),
+			r#""annual report" "2024" "draf"*"#
+		);
+		let search_input_punctuation = FileSearchInput::simple(r#"" ? ""#.to_string());
+		let query_punctuation = FileSearchQuery::new(search_input_punctuation);
+		assert_eq!(query_punctuation.build_fts5_query(), "");
 	}
 
 	#[test]
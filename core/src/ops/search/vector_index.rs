@@ -0,0 +1,481 @@
+//! Approximate nearest neighbour index over content embeddings
+//!
+//! An inverted file (IVF) index: vectors are clustered with spherical k-means
+//! and a query only scans the clusters whose centroids are closest to it.
+//! Small indexes skip clustering and are scanned exhaustively.
+//!
+//! Vectors are L2-normalized, so cosine similarity is a plain dot product. The
+//! index is a cache of the embeddings sidecars and is persisted per library
+//! under `<library>/embeddings/<model>.index`; losing it only means the
+//! embedding job has to reload vectors from the sidecars.
+
+use serde::{Deserialize, Serialize};
+use std::{
+	collections::HashMap,
+	path::{Path, PathBuf},
+};
+use uuid::Uuid;
+
+/// Bumped whenever the on-disk layout changes; older files are discarded
+const INDEX_FORMAT_VERSION: u32 = 1;
+
+/// Below this many vectors the index is scanned exhaustively
+const MIN_VECTORS_TO_CLUSTER: usize = 1024;
+
+/// The index is re-clustered once it has grown by this factor since the last training
+const RETRAIN_GROWTH_FACTOR: usize = 2;
+
+const KMEANS_ITERATIONS: usize = 10;
+
+/// Directory inside the library holding the persisted indexes
+const INDEX_DIR: &str = "embeddings";
+
+#[derive(Debug, thiserror::Error)]
+pub enum VectorIndexError {
+	#[error("Vector has {actual} dimensions, index expects {expected}")]
+	DimensionMismatch { expected: usize, actual: usize },
+
+	#[error("IO error: {0}")]
+	Io(#[from] std::io::Error),
+
+	#[error("Failed to encode index: {0}")]
+	Encode(#[from] rmp_serde::encode::Error),
+
+	#[error("Failed to decode index: {0}")]
+	Decode(#[from] rmp_serde::decode::Error),
+}
+
+pub type VectorIndexResult<T> = Result<T, VectorIndexError>;
+
+/// On-disk representation, cluster lists are rebuilt on load
+#[derive(Serialize, Deserialize)]
+struct PersistedIndex {
+	version: u32,
+	model: String,
+	dimensions: usize,
+	entries: Vec<(Uuid, Vec<f32>)>,
+	centroids: Vec<Vec<f32>>,
+	trained_size: usize,
+}
+
+pub struct VectorIndex {
+	model: String,
+	dimensions: usize,
+	path: PathBuf,
+	ids: Vec<Uuid>,
+	vectors: Vec<Vec<f32>>,
+	positions: HashMap<Uuid, usize>,
+	centroids: Vec<Vec<f32>>,
+	/// Cluster of each vector, parallel to `vectors`
+	assignments: Vec<usize>,
+	/// Vector positions per cluster
+	lists: Vec<Vec<usize>>,
+	/// Number of vectors when the centroids were last trained
+	trained_size: usize,
+	dirty: bool,
+}
+
+impl VectorIndex {
+	/// Create an empty index that will be persisted in the given library directory
+	pub fn new(library_path: &Path, model: &str, dimensions: usize) -> Self {
+		Self {
+			model: model.to_string(),
+			dimensions,
+			path: Self::index_path(library_path, model),
+			ids: Vec::new(),
+			vectors: Vec::new(),
+			positions: HashMap::new(),
+			centroids: Vec::new(),
+			assignments: Vec::new(),
+			lists: Vec::new(),
+			trained_size: 0,
+			dirty: false,
+		}
+	}
+
+	/// Location of the persisted index for a model
+	pub fn index_path(library_path: &Path, model: &str) -> PathBuf {
+		library_path
+			.join(INDEX_DIR)
+			.join(format!("{}.index", model))
+	}
+
+	/// Load the persisted index, starting empty when it is missing or outdated
+	pub async fn load(
+		library_path: &Path,
+		model: &str,
+		dimensions: usize,
+	) -> VectorIndexResult<Self> {
+		let mut index = Self::new(library_path, model, dimensions);
+
+		let bytes = match tokio::fs::read(&index.path).await {
+			Ok(bytes) => bytes,
+			Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(index),
+			Err(e) => return Err(e.into()),
+		};
+
+		let persisted: PersistedIndex = rmp_serde::from_slice(&bytes)?;
+		if persisted.version != INDEX_FORMAT_VERSION
+			|| persisted.model != model
+			|| persisted.dimensions != dimensions
+		{
+			tracing::info!(
+				"Discarding outdated vector index at {}",
+				index.path.display()
+			);
+			return Ok(index);
+		}
+
+		for (id, vector) in persisted.entries {
+			index.positions.insert(id, index.ids.len());
+			index.ids.push(id);
+			index.vectors.push(vector);
+		}
+		index.trained_size = persisted.trained_size;
+		index.set_centroids(persisted.centroids);
+
+		Ok(index)
+	}
+
+	/// Write the index to disk if it changed since it was loaded or last saved
+	pub async fn save(&mut self) -> VectorIndexResult<()> {
+		if !self.dirty {
+			return Ok(());
+		}
+
+		let persisted = PersistedIndex {
+			version: INDEX_FORMAT_VERSION,
+			model: self.model.clone(),
+			dimensions: self.dimensions,
+			entries: self
+				.ids
+				.iter()
+				.copied()
+				.zip(self.vectors.iter().cloned())
+				.collect(),
+			centroids: self.centroids.clone(),
+			trained_size: self.trained_size,
+		};
+		let bytes = rmp_serde::to_vec(&persisted)?;
+
+		if let Some(parent) = self.path.parent() {
+			tokio::fs::create_dir_all(parent).await?;
+		}
+		// Write to a temporary file first so a crash never leaves a truncated index
+		let temp_path = self.path.with_extension("index.tmp");
+		tokio::fs::write(&temp_path, bytes).await?;
+		tokio::fs::rename(&temp_path, &self.path).await?;
+
+		self.dirty = false;
+		Ok(())
+	}
+
+	pub fn model(&self) -> &str {
+		&self.model
+	}
+
+	pub fn dimensions(&self) -> usize {
+		self.dimensions
+	}
+
+	pub fn len(&self) -> usize {
+		self.ids.len()
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.ids.is_empty()
+	}
+
+	pub fn contains(&self, id: &Uuid) -> bool {
+		self.positions.contains_key(id)
+	}
+
+	/// All content ids present in the index
+	pub fn ids(&self) -> &[Uuid] {
+		&self.ids
+	}
+
+	/// Insert or replace the vector of a content identity
+	pub fn insert(&mut self, id: Uuid, vector: Vec<f32>) -> VectorIndexResult<()> {
+		if vector.len() != self.dimensions {
+			return Err(VectorIndexError::DimensionMismatch {
+				expected: self.dimensions,
+				actual: vector.len(),
+			});
+		}
+
+		self.remove(&id);
+
+		let position = self.ids.len();
+		let cluster = self.nearest_centroid(&vector);
+		self.ids.push(id);
+		self.vectors.push(vector);
+		self.positions.insert(id, position);
+		if let Some(cluster) = cluster {
+			self.assignments.push(cluster);
+			self.lists[cluster].push(position);
+		}
+		self.dirty = true;
+
+		if self.needs_training() {
+			self.train();
+		}
+
+		Ok(())
+	}
+
+	/// Remove the vector of a content identity, returning whether it was present
+	pub fn remove(&mut self, id: &Uuid) -> bool {
+		let Some(position) = self.positions.remove(id) else {
+			return false;
+		};
+
+		let last = self.ids.len() - 1;
+		if !self.centroids.is_empty() {
+			let cluster = self.assignments[position];
+			self.lists[cluster].retain(|&p| p != position);
+			if position != last {
+				// The last vector moves into the freed slot
+				let moved_cluster = self.assignments[last];
+				for p in self.lists[moved_cluster].iter_mut() {
+					if *p == last {
+						*p = position;
+					}
+				}
+			}
+			self.assignments.swap_remove(position);
+		}
+
+		self.ids.swap_remove(position);
+		self.vectors.swap_remove(position);
+		if position != last {
+			self.positions.insert(self.ids[position], position);
+		}
+		self.dirty = true;
+
+		true
+	}
+
+	/// Find the `limit` most similar vectors to a normalized query vector
+	///
+	/// Returns content ids with their cosine similarity, most similar first.
+	pub fn search(&self, query: &[f32], limit: usize, nprobe: usize) -> Vec<(Uuid, f32)> {
+		if query.len() != self.dimensions || limit == 0 {
+			return Vec::new();
+		}
+
+		let mut scored: Vec<(Uuid, f32)> = if self.centroids.is_empty() {
+			self.ids
+				.iter()
+				.zip(&self.vectors)
+				.map(|(id, vector)| (*id, dot(query, vector)))
+				.collect()
+		} else {
+			let mut clusters: Vec<(usize, f32)> = self
+				.centroids
+				.iter()
+				.enumerate()
+				.map(|(i, centroid)| (i, dot(query, centroid)))
+				.collect();
+			clusters.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+			clusters
+				.iter()
+				.take(nprobe.max(1))
+				.flat_map(|(cluster, _)| &self.lists[*cluster])
+				.map(|&p| (self.ids[p], dot(query, &self.vectors[p])))
+				.collect()
+		};
+
+		scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+		scored.truncate(limit);
+		scored
+	}
+
+	/// Number of clusters to scan for a query, trading recall for speed
+	pub fn default_nprobe(&self) -> usize {
+		(self.centroids.len() / 8).clamp(1, 32)
+	}
+
+	fn needs_training(&self) -> bool {
+		self.ids.len() >= MIN_VECTORS_TO_CLUSTER
+			&& (self.centroids.is_empty()
+				|| self.ids.len() >= self.trained_size * RETRAIN_GROWTH_FACTOR)
+	}
+
+	/// Cluster all vectors with spherical k-means
+	///
+	/// Centroids are seeded from evenly spaced vectors so training is deterministic.
+	pub fn train(&mut self) {
+		let n = self.vectors.len();
+		if n < MIN_VECTORS_TO_CLUSTER {
+			self.set_centroids(Vec::new());
+			self.trained_size = 0;
+			return;
+		}
+
+		let k = ((n as f64).sqrt() as usize).max(1);
+		let mut centroids: Vec<Vec<f32>> =
+			(0..k).map(|i| self.vectors[i * n / k].clone()).collect();
+		let mut assignments = vec![0usize; n];
+
+		for _ in 0..KMEANS_ITERATIONS {
+			let mut changed = false;
+			for (i, vector) in self.vectors.iter().enumerate() {
+				let cluster = nearest(&centroids, vector).unwrap_or(0);
+				if assignments[i] != cluster {
+					assignments[i] = cluster;
+					changed = true;
+				}
+			}
+
+			let mut sums = vec![vec![0.0f32; self.dimensions]; k];
+			for (vector, &cluster) in self.vectors.iter().zip(&assignments) {
+				for (sum, v) in sums[cluster].iter_mut().zip(vector) {
+					*sum += v;
+				}
+			}
+			for (centroid, mut sum) in centroids.iter_mut().zip(sums) {
+				// Empty clusters keep their previous centroid
+				if sum.iter().any(|v| *v != 0.0) {
+					crate::ops::models::embedding::normalize(&mut sum);
+					*centroid = sum;
+				}
+			}
+
+			if !changed {
+				break;
+			}
+		}
+
+		self.trained_size = n;
+		self.set_centroids(centroids);
+		self.dirty = true;
+	}
+
+	/// Replace the centroids and reassign every vector to its nearest one
+	fn set_centroids(&mut self, centroids: Vec<Vec<f32>>) {
+		self.lists = vec![Vec::new(); centroids.len()];
+		self.centroids = centroids;
+		self.assignments.clear();
+
+		if self.centroids.is_empty() {
+			return;
+		}
+		for (position, vector) in self.vectors.iter().enumerate() {
+			let cluster = nearest(&self.centroids, vector).unwrap_or(0);
+			self.assignments.push(cluster);
+			self.lists[cluster].push(position);
+		}
+	}
+
+	fn nearest_centroid(&self, vector: &[f32]) -> Option<usize> {
+		nearest(&self.centroids, vector)
+	}
+}
+
+fn nearest(centroids: &[Vec<f32>], vector: &[f32]) -> Option<usize> {
+	centroids
+		.iter()
+		.enumerate()
+		.map(|(i, centroid)| (i, dot(vector, centroid)))
+		.max_by(|a, b| a.1.total_cmp(&b.1))
+		.map(|(i, _)| i)
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+	a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::ops::models::embedding::normalize;
+
+	const DIMS: usize = 8;
+
+	/// Deterministic pseudo-random unit vector
+	fn vector(seed: u64) -> Vec<f32> {
+		let mut state = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+		let mut v: Vec<f32> = (0..DIMS)
+			.map(|_| {
+				state = state
+					.wrapping_mul(6364136223846793005)
+					.wrapping_add(1442695040888963407);
+				((state >> 33) as f32 / u32::MAX as f32) - 0.25
+			})
+			.collect();
+		normalize(&mut v);
+		v
+	}
+
+	fn index_with(count: u64) -> (tempfile::TempDir, VectorIndex, Vec<Uuid>) {
+		let dir = tempfile::tempdir().unwrap();
+		let mut index = VectorIndex::new(dir.path(), "test-model", DIMS);
+		let ids: Vec<Uuid> = (0..count).map(|_| Uuid::new_v4()).collect();
+		for (seed, id) in ids.iter().enumerate() {
+			index.insert(*id, vector(seed as u64)).unwrap();
+		}
+		(dir, index, ids)
+	}
+
+	#[test]
+	fn exact_search_finds_identical_vector_first() {
+		let (_dir, index, ids) = index_with(50);
+		let results = index.search(&vector(17), 3, 1);
+		assert_eq!(results[0].0, ids[17]);
+		assert!((results[0].1 - 1.0).abs() < 1e-5);
+		assert!(results[0].1 >= results[1].1);
+	}
+
+	#[test]
+	fn clustered_search_finds_identical_vector_first() {
+		let (_dir, index, ids) = index_with(MIN_VECTORS_TO_CLUSTER as u64 + 10);
+		assert!(!index.centroids.is_empty());
+
+		for seed in [3u64, 400, 1000] {
+			let results = index.search(&vector(seed), 5, index.default_nprobe());
+			assert_eq!(results[0].0, ids[seed as usize]);
+		}
+	}
+
+	#[test]
+	fn remove_keeps_positions_consistent() {
+		let (_dir, mut index, ids) = index_with(MIN_VECTORS_TO_CLUSTER as u64 + 10);
+		assert!(index.remove(&ids[5]));
+		assert!(!index.remove(&ids[5]));
+		assert_eq!(index.len(), MIN_VECTORS_TO_CLUSTER + 9);
+
+		let last = *ids.last().unwrap();
+		let results = index.search(&vector(ids.len() as u64 - 1), 1, index.centroids.len());
+		assert_eq!(results[0].0, last);
+		assert!(index.search(&vector(5), 1, index.centroids.len())[0].0 != ids[5]);
+	}
+
+	#[test]
+	fn rejects_wrong_dimensions() {
+		let (_dir, mut index, _) = index_with(0);
+		assert!(matches!(
+			index.insert(Uuid::new_v4(), vec![1.0; DIMS + 1]),
+			Err(VectorIndexError::DimensionMismatch { .. })
+		));
+	}
+
+	#[tokio::test]
+	async fn save_and_load_round_trip() {
+		let (dir, mut index, ids) = index_with(20);
+		index.save().await.unwrap();
+
+		let loaded = VectorIndex::load(dir.path(), "test-model", DIMS)
+			.await
+			.unwrap();
+		assert_eq!(loaded.len(), 20);
+		assert_eq!(loaded.search(&vector(7), 1, 1)[0].0, ids[7]);
+
+		// A different model never reuses the file
+		let other = VectorIndex::load(dir.path(), "other-model", DIMS)
+			.await
+			.unwrap();
+		assert!(other.is_empty());
+	}
+}
@@ -0,0 +1,56 @@
+//! Sidecar garbage collection action handler
+
+use super::job::SidecarGcJob;
+use crate::{
+	context::CoreContext,
+	infra::{
+		action::{error::ActionError, LibraryAction},
+		job::handle::JobReceipt,
+	},
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SidecarGcInput {
+	/// Only report what would be removed
+	#[serde(default)]
+	pub dry_run: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SidecarGcAction {
+	pub dry_run: bool,
+}
+
+impl LibraryAction for SidecarGcAction {
+	type Input = SidecarGcInput;
+	type Output = JobReceipt;
+
+	fn from_input(input: Self::Input) -> Result<Self, String> {
+		Ok(SidecarGcAction {
+			dry_run: input.dry_run,
+		})
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let job_handle = library
+			.jobs()
+			.dispatch(SidecarGcJob::new(self.dry_run))
+			.await
+			.map_err(ActionError::Job)?;
+
+		Ok(job_handle.into())
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"sidecars.gc"
+	}
+}
+
+crate::register_library_action!(SidecarGcAction, "sidecars.gc");
@@ -0,0 +1,464 @@
+//! Sidecar garbage collection job
+
+use crate::{
+	infra::{
+		db::entities::sidecar::{self, Entity as Sidecar},
+		job::{generic_progress::GenericProgress, prelude::*},
+	},
+	ops::{
+		media::{proxy::ProxyVariants, thumbnail::ThumbnailVariants},
+		sidecar::{SidecarKind, SidecarPathBuilder, SidecarStatus, SidecarVariant},
+	},
+	service::sidecar_manager::SidecarManager,
+};
+use chrono::{DateTime, Duration, Utc};
+use sea_orm::{ColumnTrait, DbBackend, EntityTrait, QueryFilter, Statement};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, path::Path, sync::Arc};
+
+/// Sidecars younger than this are never treated as orphans
+///
+/// Content is identified and its sidecars generated while the indexer is still writing
+/// entries, and archive members get thumbnails before they have any entries at all.
+const ORPHAN_GRACE_PERIOD: Duration = Duration::hours(24);
+
+/// Removes sidecars that are no longer needed and enforces the library's quotas
+///
+/// Three kinds of sidecars are removed, in this order:
+/// - orphans, whose content identity has no entries left in the library
+/// - stale variants, thumbnails, thumbstrips or proxies of a size no longer generated
+/// - the least recently used sidecars of a kind whose quota is exceeded
+#[derive(Debug, Serialize, Deserialize, Job)]
+pub struct SidecarGcJob {
+	/// Only count what would be removed
+	pub dry_run: bool,
+}
+
+impl Job for SidecarGcJob {
+	const NAME: &'static str = "sidecar_gc";
+	const RESUMABLE: bool = true;
+	const DESCRIPTION: Option<&'static str> =
+		Some("Remove orphaned and stale sidecars and enforce sidecar quotas");
+}
+
+impl crate::infra::job::traits::DynJob for SidecarGcJob {
+	fn job_name(&self) -> &'static str {
+		Self::NAME
+	}
+}
+
+#[async_trait::async_trait]
+impl JobHandler for SidecarGcJob {
+	type Output = SidecarGcOutput;
+
+	async fn run(&mut self, ctx: JobContext<'_>) -> JobResult<Self::Output> {
+		let library = ctx.library();
+		let sidecar_manager = library
+			.core_context()
+			.get_sidecar_manager()
+			.await
+			.ok_or_else(|| JobError::execution("SidecarManager not available"))?;
+
+		let mut output = SidecarGcOutput {
+			dry_run: self.dry_run,
+			..Default::default()
+		};
+		// Sidecars already removed, so later phases don't count them twice in a dry run
+		let mut removed = HashSet::new();
+
+		ctx.progress(Progress::Generic(GenericProgress::new(
+			0.0,
+			"Orphans",
+			"Finding sidecars without content",
+		)));
+		let orphans = find_orphans(ctx.library_db(), Utc::now() - ORPHAN_GRACE_PERIOD).await?;
+		ctx.log(format!("Found {} orphaned sidecars", orphans.len()));
+		for orphan in &orphans {
+			ctx.check_interrupt().await?;
+			if self
+				.delete(&ctx, &sidecar_manager, orphan, &mut output)
+				.await
+			{
+				output.orphaned_count += 1;
+				removed.insert(orphan.id);
+			}
+		}
+
+		ctx.progress(Progress::Generic(GenericProgress::new(
+			0.33,
+			"Stale variants",
+			"Finding sidecars of variants no longer generated",
+		)));
+		let stale = find_stale_variants(ctx.library_db()).await?;
+		ctx.log(format!("Found {} stale sidecar variants", stale.len()));
+		for sidecar in stale
+			.iter()
+			.filter(|sidecar| !removed.contains(&sidecar.id))
+		{
+			ctx.check_interrupt().await?;
+			if self
+				.delete(&ctx, &sidecar_manager, sidecar, &mut output)
+				.await
+			{
+				output.stale_count += 1;
+				removed.insert(sidecar.id);
+			}
+		}
+
+		let quotas = library.config().await.settings.sidecar_quotas;
+		let sidecars_dir = SidecarPathBuilder::new(library.path()).sidecars_dir();
+		for (index, quota) in quotas.iter().enumerate() {
+			ctx.progress(Progress::Generic(
+				GenericProgress::new(
+					0.66 + 0.34 * index as f32 / quotas.len() as f32,
+					"Quotas",
+					format!("Checking the {} quota", quota.kind),
+				)
+				.with_completion(index as u64, quotas.len() as u64),
+			));
+
+			let candidates: Vec<_> = find_local(ctx.library_db(), &quota.kind)
+				.await?
+				.into_iter()
+				.filter(|sidecar| !removed.contains(&sidecar.id))
+				.collect();
+
+			let used: u64 = candidates
+				.iter()
+				.map(|sidecar| sidecar.size.max(0) as u64)
+				.sum();
+			if used <= quota.max_bytes {
+				continue;
+			}
+
+			let mut usage = Vec::with_capacity(candidates.len());
+			for sidecar in &candidates {
+				ctx.check_interrupt().await?;
+				let last_used = last_used(&sidecars_dir.join(&sidecar.rel_path))
+					.await
+					.unwrap_or(sidecar.updated_at);
+				usage.push((last_used, sidecar.size.max(0) as u64));
+			}
+
+			let evictions = select_evictions(&usage, used, quota.max_bytes);
+			ctx.log(format!(
+				"{} sidecars use {} bytes of a {} byte quota, evicting {}",
+				quota.kind,
+				used,
+				quota.max_bytes,
+				evictions.len()
+			));
+			for index in evictions {
+				ctx.check_interrupt().await?;
+				let sidecar = &candidates[index];
+				if self
+					.delete(&ctx, &sidecar_manager, sidecar, &mut output)
+					.await
+				{
+					output.evicted_count += 1;
+					removed.insert(sidecar.id);
+				}
+			}
+		}
+
+		ctx.progress(Progress::Generic(
+			GenericProgress::new(
+				1.0,
+				"Complete",
+				format!(
+					"{} sidecars removed, {} bytes freed",
+					removed.len(),
+					output.freed_bytes
+				),
+			)
+			.with_errors(output.failed_count as u64, 0),
+		));
+
+		ctx.log(format!(
+			"Sidecar GC completed{}: {} orphaned, {} stale, {} evicted, {} bytes freed, {} failed",
+			if self.dry_run { " (dry run)" } else { "" },
+			output.orphaned_count,
+			output.stale_count,
+			output.evicted_count,
+			output.freed_bytes,
+			output.failed_count
+		));
+
+		Ok(output)
+	}
+}
+
+impl SidecarGcJob {
+	pub fn new(dry_run: bool) -> Self {
+		Self { dry_run }
+	}
+
+	/// Delete one sidecar, or count it in a dry run, returns whether it was removed
+	async fn delete(
+		&self,
+		ctx: &JobContext<'_>,
+		sidecar_manager: &Arc<SidecarManager>,
+		sidecar: &sidecar::Model,
+		output: &mut SidecarGcOutput,
+	) -> bool {
+		if self.dry_run {
+			output.freed_bytes += sidecar.size.max(0) as u64;
+			return true;
+		}
+
+		match sidecar_manager.delete_sidecar(ctx.library(), sidecar).await {
+			Ok(freed) => {
+				output.freed_bytes += freed;
+				true
+			}
+			Err(e) => {
+				output.failed_count += 1;
+				ctx.add_non_critical_error(format!(
+					"Failed to remove {} sidecar {} of {}: {}",
+					sidecar.kind, sidecar.variant, sidecar.content_uuid, e
+				));
+				false
+			}
+		}
+	}
+}
+
+/// Sidecars whose content identity is gone or has no entries left
+async fn find_orphans(
+	db: &sea_orm::DatabaseConnection,
+	older_than: DateTime<Utc>,
+) -> JobResult<Vec<sidecar::Model>> {
+	let query = r#"
+		SELECT s.*
+		FROM sidecar s
+		WHERE NOT EXISTS (
+			SELECT 1
+			FROM content_identities ci
+			INNER JOIN entries e ON e.content_id = ci.id
+			WHERE ci.uuid = s.content_uuid
+		)
+	"#;
+
+	let orphans = Sidecar::find()
+		.from_raw_sql(Statement::from_string(DbBackend::Sqlite, query))
+		.all(db)
+		.await?;
+
+	Ok(orphans
+		.into_iter()
+		.filter(|sidecar| sidecar.updated_at < older_than)
+		.collect())
+}
+
+/// Sidecars of a variant that's no longer generated for their kind
+async fn find_stale_variants(db: &sea_orm::DatabaseConnection) -> JobResult<Vec<sidecar::Model>> {
+	let mut stale = Vec::new();
+	for kind in [
+		SidecarKind::Thumb,
+		SidecarKind::Thumbstrip,
+		SidecarKind::Proxy,
+	] {
+		let Some(variants) = current_variants(&kind) else {
+			continue;
+		};
+
+		stale.extend(
+			Sidecar::find()
+				.filter(sidecar::Column::Kind.eq(kind.as_str()))
+				.filter(
+					sidecar::Column::Variant
+						.is_not_in(variants.iter().map(|variant| variant.as_str().to_string())),
+				)
+				.all(db)
+				.await?,
+		);
+	}
+
+	Ok(stale)
+}
+
+/// Variants currently generated for a kind, `None` if that isn't known
+fn current_variants(kind: &SidecarKind) -> Option<Vec<SidecarVariant>> {
+	match kind {
+		SidecarKind::Thumb => Some(
+			ThumbnailVariants::all()
+				.into_iter()
+				.map(|config| config.variant)
+				.collect(),
+		),
+		#[cfg(feature = "ffmpeg")]
+		SidecarKind::Thumbstrip => Some(
+			crate::ops::media::thumbstrip::ThumbstripVariants::all()
+				.into_iter()
+				.map(|config| config.variant)
+				.collect(),
+		),
+		SidecarKind::Proxy => Some(
+			ProxyVariants::all()
+				.into_iter()
+				.map(|config| config.variant)
+				.collect(),
+		),
+		_ => None,
+	}
+}
+
+/// Ready sidecars of a kind generated and stored on this device
+async fn find_local(
+	db: &sea_orm::DatabaseConnection,
+	kind: &SidecarKind,
+) -> JobResult<Vec<sidecar::Model>> {
+	let query = r#"
+		SELECT s.*
+		FROM sidecar s
+		INNER JOIN sidecar_availability a
+			ON a.content_uuid = s.content_uuid
+			AND a.kind = s.kind
+			AND a.variant = s.variant
+			AND a.device_uuid = ?
+			AND a.has = 1
+		WHERE s.kind = ?
+		  AND s.source_entry_id IS NULL
+		  AND s.status = ?
+	"#;
+
+	Ok(Sidecar::find()
+		.from_raw_sql(Statement::from_sql_and_values(
+			DbBackend::Sqlite,
+			query,
+			vec![
+				crate::device::get_current_device_id().into(),
+				kind.as_str().into(),
+				SidecarStatus::Ready.as_str().into(),
+			],
+		))
+		.all(db)
+		.await?)
+}
+
+/// When a sidecar file was last read or written
+///
+/// Sidecars are served straight from disk, so the file's access time is the only record
+/// of their use. It isn't updated on every mount, so the modification time counts too.
+async fn last_used(path: &Path) -> Option<DateTime<Utc>> {
+	let metadata = tokio::fs::metadata(path).await.ok()?;
+	let modified = metadata.modified().ok()?;
+	let last_used = match metadata.accessed() {
+		Ok(accessed) => accessed.max(modified),
+		Err(_) => modified,
+	};
+
+	Some(last_used.into())
+}
+
+/// Pick the sidecars to evict to bring usage within a quota, least recently used first
+///
+/// Takes the last use and size of each candidate, returns the indices of those to evict.
+fn select_evictions(candidates: &[(DateTime<Utc>, u64)], used: u64, max_bytes: u64) -> Vec<usize> {
+	let mut order: Vec<usize> = (0..candidates.len()).collect();
+	order.sort_by_key(|&index| candidates[index].0);
+
+	let mut used = used;
+	order
+		.into_iter()
+		.take_while(|&index| {
+			if used <= max_bytes {
+				return false;
+			}
+			used = used.saturating_sub(candidates[index].1);
+			true
+		})
+		.collect()
+}
+
+/// Job output for sidecar garbage collection
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SidecarGcOutput {
+	/// Nothing was removed, the counts are what would have been
+	pub dry_run: bool,
+	pub orphaned_count: usize,
+	pub stale_count: usize,
+	pub evicted_count: usize,
+	pub freed_bytes: u64,
+	pub failed_count: usize,
+}
+
+impl From<SidecarGcOutput> for JobOutput {
+	fn from(output: SidecarGcOutput) -> Self {
+		JobOutput::SidecarGc {
+			dry_run: output.dry_run,
+			orphaned_count: output.orphaned_count,
+			stale_count: output.stale_count,
+			evicted_count: output.evicted_count,
+			freed_bytes: output.freed_bytes,
+			failed_count: output.failed_count,
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn days_ago(days: i64) -> DateTime<Utc> {
+		Utc::now() - Duration::days(days)
+	}
+
+	#[test]
+	fn evicts_least_recently_used_first() {
+		let candidates = [
+			(days_ago(1), 100),
+			(days_ago(30), 100),
+			(days_ago(7), 100),
+			(days_ago(90), 100),
+		];
+
+		assert_eq!(select_evictions(&candidates, 400, 250), vec![3, 1]);
+	}
+
+	#[test]
+	fn evicts_nothing_within_quota() {
+		let candidates = [(days_ago(90), 100), (days_ago(1), 100)];
+
+		assert!(select_evictions(&candidates, 200, 200).is_empty());
+		assert!(select_evictions(&[], 0, 0).is_empty());
+	}
+
+	#[test]
+	fn evicts_everything_for_an_empty_quota() {
+		let candidates = [(days_ago(2), 10), (days_ago(1), 0), (days_ago(3), 10)];
+
+		// Usage reaches zero before the empty file's turn, so it stays
+		assert_eq!(select_evictions(&candidates, 20, 0), vec![2, 0]);
+	}
+
+	#[test]
+	fn knows_current_variants_of_regenerable_kinds() {
+		let thumbs = current_variants(&SidecarKind::Thumb).unwrap();
+		assert!(thumbs.contains(&ThumbnailVariants::grid_1x().variant));
+		assert!(current_variants(&SidecarKind::Proxy).is_some());
+		assert!(current_variants(&SidecarKind::Embeddings).is_none());
+	}
+
+	#[test]
+	fn quotas_only_apply_to_regenerable_kinds() {
+		use crate::ops::sidecar::SidecarQuota;
+
+		let quota = |kind| SidecarQuota {
+			kind,
+			max_bytes: 1 << 30,
+		};
+
+		assert!(SidecarQuota::validate_all(&[
+			quota(SidecarKind::Thumb),
+			quota(SidecarKind::Proxy)
+		])
+		.is_ok());
+		assert!(SidecarQuota::validate_all(&[quota(SidecarKind::Embeddings)]).is_err());
+		assert!(SidecarQuota::validate_all(&[
+			quota(SidecarKind::Thumb),
+			quota(SidecarKind::Thumb)
+		])
+		.is_err());
+	}
+}
@@ -0,0 +1,11 @@
+//! Sidecar garbage collection
+//!
+//! Removes sidecars whose content is gone from the library or whose variant is no longer
+//! generated, and evicts the least recently used regenerable sidecars of any kind above
+//! its quota in the library settings.
+
+pub mod action;
+pub mod job;
+
+pub use action::{SidecarGcAction, SidecarGcInput};
+pub use job::{SidecarGcJob, SidecarGcOutput};
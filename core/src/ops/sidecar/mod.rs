@@ -1,5 +1,7 @@
+pub mod gc;
 pub mod path;
 pub mod types;
+pub mod usage;
 
 pub use path::{SidecarPath, SidecarPathBuilder};
-pub use types::{SidecarFormat, SidecarKind, SidecarStatus, SidecarVariant};
+pub use types::{SidecarFormat, SidecarKind, SidecarQuota, SidecarStatus, SidecarVariant};
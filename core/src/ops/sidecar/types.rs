@@ -27,6 +27,14 @@ impl SidecarKind {
 		}
 	}
 
+	/// Whether the sidecar is cheap enough to generate again that it can be evicted
+	///
+	/// Thumbnails, thumbstrips and proxies are rebuilt on demand. Embeddings, text
+	/// extractions and splats feed search or take minutes per file, so they're kept.
+	pub fn is_regenerable(&self) -> bool {
+		matches!(self, Self::Thumb | Self::Thumbstrip | Self::Proxy)
+	}
+
 	pub fn directory(&self) -> &'static str {
 		match self {
 			Self::Thumb => "thumbs",
@@ -63,6 +71,35 @@ impl TryFrom<&str> for SidecarKind {
 	}
 }
 
+/// Disk space one kind of sidecar may take up on this device
+///
+/// Only regenerable kinds can have a quota. Above it, the least recently used
+/// sidecars of the kind are evicted by the sidecar garbage collector.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct SidecarQuota {
+	pub kind: SidecarKind,
+	pub max_bytes: u64,
+}
+
+impl SidecarQuota {
+	/// Check a set of quotas: regenerable kinds only, at most one per kind
+	pub fn validate_all(quotas: &[SidecarQuota]) -> Result<(), String> {
+		for (i, quota) in quotas.iter().enumerate() {
+			if !quota.kind.is_regenerable() {
+				return Err(format!(
+					"{} sidecars can't be regenerated cheaply, only thumb, thumbstrip and proxy quotas are supported",
+					quota.kind
+				));
+			}
+			if quotas[..i].iter().any(|other| other.kind == quota.kind) {
+				return Err(format!("More than one quota for {} sidecars", quota.kind));
+			}
+		}
+
+		Ok(())
+	}
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, Type)]
 pub struct SidecarVariant(pub String);
 
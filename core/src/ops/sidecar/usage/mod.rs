@@ -0,0 +1,8 @@
+//! Sidecar disk usage query module
+
+pub mod query;
+
+pub use query::{
+	local_usage, SidecarKindUsage, SidecarUsageInput, SidecarUsageOutput, SidecarUsageQuery,
+	SidecarVariantUsage,
+};
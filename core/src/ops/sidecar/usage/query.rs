@@ -0,0 +1,174 @@
+//! Sidecar disk usage query
+//!
+//! Reports how much space the sidecars generated on this device take up, per kind and
+//! variant, next to the quota configured for the kind. Reference sidecars point at the
+//! user's own files and other devices' sidecars aren't stored here, neither is counted.
+
+use crate::{
+	context::CoreContext,
+	infra::query::{LibraryQuery, QueryError, QueryResult},
+	ops::sidecar::{SidecarKind, SidecarStatus},
+};
+use sea_orm::{DatabaseConnection, DbBackend, DbErr, FromQueryResult, Statement};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SidecarUsageInput {}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SidecarVariantUsage {
+	pub variant: String,
+	pub count: u64,
+	pub bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SidecarKindUsage {
+	pub kind: SidecarKind,
+	pub count: u64,
+	pub bytes: u64,
+	/// Quota configured for the kind, if any
+	pub quota: Option<u64>,
+	/// Largest variants first
+	pub variants: Vec<SidecarVariantUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SidecarUsageOutput {
+	/// Largest kinds first
+	pub kinds: Vec<SidecarKindUsage>,
+	pub total_count: u64,
+	pub total_bytes: u64,
+}
+
+/// Usage of the sidecars stored on a device, by kind, without quotas
+pub async fn local_usage(
+	db: &DatabaseConnection,
+	device_uuid: Uuid,
+) -> Result<Vec<SidecarKindUsage>, DbErr> {
+	let query = r#"
+		SELECT s.kind, s.variant, COUNT(*) AS count, COALESCE(SUM(s.size), 0) AS bytes
+		FROM sidecar s
+		INNER JOIN sidecar_availability a
+			ON a.content_uuid = s.content_uuid
+			AND a.kind = s.kind
+			AND a.variant = s.variant
+			AND a.device_uuid = ?
+			AND a.has = 1
+		WHERE s.source_entry_id IS NULL
+		  AND s.status = ?
+		GROUP BY s.kind, s.variant
+	"#;
+
+	#[derive(FromQueryResult)]
+	struct UsageRow {
+		kind: String,
+		variant: String,
+		count: i64,
+		bytes: i64,
+	}
+
+	let rows = UsageRow::find_by_statement(Statement::from_sql_and_values(
+		DbBackend::Sqlite,
+		query,
+		vec![device_uuid.into(), SidecarStatus::Ready.as_str().into()],
+	))
+	.all(db)
+	.await?;
+
+	let mut kinds: Vec<SidecarKindUsage> = Vec::new();
+	for row in rows {
+		// Rows written by newer versions may use kinds this one doesn't know
+		let Ok(kind) = SidecarKind::try_from(row.kind.as_str()) else {
+			continue;
+		};
+
+		let variant = SidecarVariantUsage {
+			variant: row.variant,
+			count: row.count.max(0) as u64,
+			bytes: row.bytes.max(0) as u64,
+		};
+
+		match kinds.iter_mut().find(|usage| usage.kind == kind) {
+			Some(usage) => {
+				usage.count += variant.count;
+				usage.bytes += variant.bytes;
+				usage.variants.push(variant);
+			}
+			None => kinds.push(SidecarKindUsage {
+				kind,
+				count: variant.count,
+				bytes: variant.bytes,
+				quota: None,
+				variants: vec![variant],
+			}),
+		}
+	}
+
+	for usage in &mut kinds {
+		usage.variants.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+	}
+	kinds.sort_by(|a, b| b.bytes.cmp(&a.bytes));
+
+	Ok(kinds)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SidecarUsageQuery {
+	pub input: SidecarUsageInput,
+}
+
+impl LibraryQuery for SidecarUsageQuery {
+	type Input = SidecarUsageInput;
+	type Output = SidecarUsageOutput;
+
+	fn from_input(input: Self::Input) -> QueryResult<Self> {
+		Ok(Self { input })
+	}
+
+	async fn execute(
+		self,
+		context: Arc<CoreContext>,
+		session: crate::infra::api::SessionContext,
+	) -> QueryResult<Self::Output> {
+		let library_id = session
+			.current_library_id
+			.ok_or_else(|| QueryError::Internal("No library selected".to_string()))?;
+
+		let library = context
+			.libraries()
+			.await
+			.get_library(library_id)
+			.await
+			.ok_or_else(|| QueryError::Internal("Library not found".to_string()))?;
+
+		let device_uuid = crate::device::get_current_device_id();
+		let mut kinds = local_usage(library.db().conn(), device_uuid).await?;
+
+		// Kinds with a quota are listed even before anything of the kind is generated
+		let quotas = library.config().await.settings.sidecar_quotas;
+		for quota in quotas {
+			match kinds.iter_mut().find(|usage| usage.kind == quota.kind) {
+				Some(usage) => usage.quota = Some(quota.max_bytes),
+				None => kinds.push(SidecarKindUsage {
+					kind: quota.kind,
+					count: 0,
+					bytes: 0,
+					quota: Some(quota.max_bytes),
+					variants: Vec::new(),
+				}),
+			}
+		}
+
+		Ok(SidecarUsageOutput {
+			total_count: kinds.iter().map(|usage| usage.count).sum(),
+			total_bytes: kinds.iter().map(|usage| usage.bytes).sum(),
+			kinds,
+		})
+	}
+}
+
+crate::register_library_query!(SidecarUsageQuery, "sidecars.usage");
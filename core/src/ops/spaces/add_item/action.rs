@@ -83,6 +83,51 @@ impl LibraryAction for AddItemAction {
 		let item_id = uuid::Uuid::new_v4();
 		let now = Utc::now();
 
+		if let ItemType::SmartTag { tag_id } = self.input.item_type {
+			let is_smart = crate::infra::db::entities::tag::Entity::find()
+				.filter(crate::infra::db::entities::tag::Column::Uuid.eq(tag_id))
+				.one(db)
+				.await
+				.map_err(ActionError::SeaOrm)?
+				.is_some_and(|tag| tag.composition_rules.is_some());
+			if !is_smart {
+				return Err(ActionError::Validation {
+					field: "item_type".to_string(),
+					message: format!("Tag {} is not a smart tag", tag_id),
+				});
+			}
+		}
+
+		if let ItemType::Collection { collection_id } = self.input.item_type {
+			let exists = crate::infra::db::entities::collection::Entity::find()
+				.filter(crate::infra::db::entities::collection::Column::Uuid.eq(collection_id))
+				.one(db)
+				.await
+				.map_err(ActionError::SeaOrm)?
+				.is_some();
+			if !exists {
+				return Err(ActionError::Validation {
+					field: "item_type".to_string(),
+					message: format!("Collection {} not found", collection_id),
+				});
+			}
+		}
+
+		if let ItemType::SavedSearch { saved_search_id } = self.input.item_type {
+			let exists = crate::infra::db::entities::saved_search::Entity::find()
+				.filter(crate::infra::db::entities::saved_search::Column::Uuid.eq(saved_search_id))
+				.one(db)
+				.await
+				.map_err(ActionError::SeaOrm)?
+				.is_some();
+			if !exists {
+				return Err(ActionError::Validation {
+					field: "item_type".to_string(),
+					message: format!("Saved search {} not found", saved_search_id),
+				});
+			}
+		}
+
 		// Resolve entry_uuid if this is a Path item
 		let entry_uuid = if let ItemType::Path { ref sd_path } = self.input.item_type {
 			tracing::info!("Resolving SdPath to entry_uuid: {:?}", sd_path);
@@ -0,0 +1,67 @@
+//! Sync bundle export action handler
+
+use super::{input::SyncBundleExportInput, output::SyncBundleExportOutput};
+use crate::{
+	context::CoreContext,
+	infra::action::{error::ActionError, LibraryAction},
+	service::sync::bundle,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncBundleExportAction {
+	input: SyncBundleExportInput,
+}
+
+impl SyncBundleExportAction {
+	pub fn new(input: SyncBundleExportInput) -> Self {
+		Self { input }
+	}
+}
+
+impl LibraryAction for SyncBundleExportAction {
+	type Input = SyncBundleExportInput;
+	type Output = SyncBundleExportOutput;
+
+	fn from_input(input: SyncBundleExportInput) -> Result<Self, String> {
+		Ok(SyncBundleExportAction::new(input))
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		context: Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let sync = library
+			.sync_service()
+			.ok_or_else(|| ActionError::Internal("Sync is not enabled for this library".into()))?;
+
+		let export_path = &self.input.export_path;
+		if let Some(parent) = export_path.parent() {
+			tokio::fs::create_dir_all(parent)
+				.await
+				.map_err(|e| ActionError::io_error(parent.to_string_lossy().to_string(), e))?;
+		}
+
+		let signing_key = bundle::signing_key(&context.device_manager)
+			.await
+			.map_err(|e| ActionError::Internal(e.to_string()))?;
+		let (summary, size_bytes) =
+			bundle::export_changes(sync, signing_key, self.input.target_device, export_path)
+				.await
+				.map_err(|e| ActionError::Internal(format!("Failed to export bundle: {}", e)))?;
+
+		Ok(SyncBundleExportOutput {
+			export_path: self.input.export_path,
+			size_bytes,
+			summary,
+		})
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"sync.bundle.export"
+	}
+}
+
+crate::register_library_action!(SyncBundleExportAction, "sync.bundle.export");
@@ -0,0 +1,15 @@
+//! Input types for sync bundle export
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Input for exporting a sync bundle
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SyncBundleExportInput {
+	/// Device the bundle is written for
+	pub target_device: Uuid,
+	/// Path where the bundle will be written
+	pub export_path: PathBuf,
+}
@@ -0,0 +1,9 @@
+//! Sync bundle export operation
+
+pub mod action;
+pub mod input;
+pub mod output;
+
+pub use action::SyncBundleExportAction;
+pub use input::SyncBundleExportInput;
+pub use output::SyncBundleExportOutput;
@@ -0,0 +1,36 @@
+//! Sync bundle export output types
+
+use crate::{infra::action::output::ActionOutputTrait, service::sync::bundle::BundleSummary};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::PathBuf;
+
+/// Output from sync bundle export action
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SyncBundleExportOutput {
+	pub export_path: PathBuf,
+	pub size_bytes: u64,
+	pub summary: BundleSummary,
+}
+
+impl ActionOutputTrait for SyncBundleExportOutput {
+	fn to_json(&self) -> serde_json::Value {
+		serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+	}
+
+	fn display_message(&self) -> String {
+		format!(
+			"Exported sync bundle for {} to {} ({} records, {} deletions, {} shared changes, {} bytes)",
+			self.summary.target_device,
+			self.export_path.display(),
+			self.summary.state_records,
+			self.summary.deletions,
+			self.summary.shared_changes,
+			self.size_bytes
+		)
+	}
+
+	fn output_type(&self) -> &'static str {
+		"sync.bundle.exported"
+	}
+}
@@ -0,0 +1,137 @@
+//! Sync bundle import action handler
+
+use super::{input::SyncBundleImportInput, output::SyncBundleImportOutput};
+use crate::{
+	context::CoreContext,
+	infra::action::{error::ActionError, LibraryAction},
+	service::sync::bundle::{self, BundleKind, BundleReader, BUNDLE_EXTENSION},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::warn;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncBundleImportAction {
+	input: SyncBundleImportInput,
+}
+
+impl SyncBundleImportAction {
+	pub fn new(input: SyncBundleImportInput) -> Self {
+		Self { input }
+	}
+}
+
+impl LibraryAction for SyncBundleImportAction {
+	type Input = SyncBundleImportInput;
+	type Output = SyncBundleImportOutput;
+
+	fn from_input(input: SyncBundleImportInput) -> Result<Self, String> {
+		Ok(SyncBundleImportAction::new(input))
+	}
+
+	async fn validate(
+		&self,
+		_library: &Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> Result<crate::infra::action::ValidationResult, ActionError> {
+		if !self.input.import_path.is_file() {
+			return Err(ActionError::Validation {
+				field: "import_path".to_string(),
+				message: "Bundle file does not exist".to_string(),
+			});
+		}
+
+		Ok(crate::infra::action::ValidationResult::Success { metadata: None })
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		context: Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let sync = library
+			.sync_service()
+			.ok_or_else(|| ActionError::Internal("Sync is not enabled for this library".into()))?;
+
+		// Opening verifies the signature, whose key is checked against the source's below
+		let reader = BundleReader::open(&self.input.import_path)
+			.map_err(|e| ActionError::Internal(format!("Invalid sync bundle: {}", e)))?;
+		let header = reader.header().clone();
+		let signer = header
+			.signer_key()
+			.map_err(|e| ActionError::Internal(e.to_string()))?;
+
+		let known_node_id = match context.get_networking().await {
+			Some(networking) => networking
+				.device_registry()
+				.read()
+				.await
+				.get_node_id_for_device(header.source_device),
+			None => None,
+		};
+		match known_node_id {
+			Some(node_id) if node_id.as_bytes() == signer.as_bytes() => {}
+			Some(_) => {
+				return Err(ActionError::Internal(format!(
+					"Bundle is not signed by device {}",
+					header.source_device
+				)));
+			}
+			None if self.input.allow_unknown_signer => {
+				warn!(
+					source = %header.source_device,
+					signer = %header.signer,
+					"Importing sync bundle from a device with no known key"
+				);
+			}
+			None => {
+				return Err(ActionError::Internal(format!(
+					"No key is known for device {}, the bundle's signer can't be verified",
+					header.source_device
+				)));
+			}
+		}
+
+		let summary = bundle::import(sync, reader)
+			.await
+			.map_err(|e| ActionError::Internal(format!("Failed to import bundle: {}", e)))?;
+
+		if header.kind != BundleKind::Changes {
+			return Ok(SyncBundleImportOutput {
+				summary,
+				ack_path: None,
+				ack_size_bytes: None,
+			});
+		}
+
+		let ack_path = self.input.ack_path.clone().unwrap_or_else(|| {
+			self.input
+				.import_path
+				.with_extension(format!("ack.{}", BUNDLE_EXTENSION))
+		});
+		let signing_key = bundle::signing_key(&context.device_manager)
+			.await
+			.map_err(|e| ActionError::Internal(e.to_string()))?;
+		let (_, ack_size_bytes) = bundle::export_ack(
+			sync,
+			signing_key,
+			header.source_device,
+			Some(header.bundle_id),
+			&ack_path,
+		)
+		.await
+		.map_err(|e| ActionError::Internal(format!("Failed to write ack bundle: {}", e)))?;
+
+		Ok(SyncBundleImportOutput {
+			summary,
+			ack_path: Some(ack_path),
+			ack_size_bytes: Some(ack_size_bytes),
+		})
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"sync.bundle.import"
+	}
+}
+
+crate::register_library_action!(SyncBundleImportAction, "sync.bundle.import");
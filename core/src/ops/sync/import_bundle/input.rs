@@ -0,0 +1,17 @@
+//! Input types for sync bundle import
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::PathBuf;
+
+/// Input for importing a sync bundle
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SyncBundleImportInput {
+	/// Path to the bundle
+	pub import_path: PathBuf,
+	/// Where to write the ack bundle, next to the imported one by default
+	pub ack_path: Option<PathBuf>,
+	/// Import bundles signed by a key the device registry doesn't know for the source device
+	#[serde(default)]
+	pub allow_unknown_signer: bool,
+}
@@ -0,0 +1,9 @@
+//! Sync bundle import operation
+
+pub mod action;
+pub mod input;
+pub mod output;
+
+pub use action::SyncBundleImportAction;
+pub use input::SyncBundleImportInput;
+pub use output::SyncBundleImportOutput;
@@ -0,0 +1,39 @@
+//! Sync bundle import output types
+
+use crate::{infra::action::output::ActionOutputTrait, service::sync::bundle::BundleSummary};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::PathBuf;
+
+/// Output from sync bundle import action
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SyncBundleImportOutput {
+	pub summary: BundleSummary,
+	/// Ack bundle to take back to the source device, none for imported acks
+	pub ack_path: Option<PathBuf>,
+	pub ack_size_bytes: Option<u64>,
+}
+
+impl ActionOutputTrait for SyncBundleImportOutput {
+	fn to_json(&self) -> serde_json::Value {
+		serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+	}
+
+	fn display_message(&self) -> String {
+		let imported = format!(
+			"Imported sync bundle from {} ({} records, {} deletions, {} shared changes)",
+			self.summary.source_device,
+			self.summary.state_records,
+			self.summary.deletions,
+			self.summary.shared_changes
+		);
+		match &self.ack_path {
+			Some(ack_path) => format!("{}, ack written to {}", imported, ack_path.display()),
+			None => imported,
+		}
+	}
+
+	fn output_type(&self) -> &'static str {
+		"sync.bundle.imported"
+	}
+}
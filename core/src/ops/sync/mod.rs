@@ -1,6 +1,9 @@
 //! Sync operations
 
+pub mod export_bundle;
 pub mod get_activity;
 pub mod get_event_log;
 pub mod get_metrics;
 pub mod get_sync_partners;
+pub mod import_bundle;
+pub mod verify;
@@ -0,0 +1,53 @@
+//! Sync verify action handler
+
+use super::{input::SyncVerifyInput, output::SyncVerifyOutput};
+use crate::{
+	context::CoreContext,
+	infra::action::{error::ActionError, LibraryAction},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncVerifyAction {
+	input: SyncVerifyInput,
+}
+
+impl SyncVerifyAction {
+	pub fn new(input: SyncVerifyInput) -> Self {
+		Self { input }
+	}
+}
+
+impl LibraryAction for SyncVerifyAction {
+	type Input = SyncVerifyInput;
+	type Output = SyncVerifyOutput;
+
+	fn from_input(input: SyncVerifyInput) -> Result<Self, String> {
+		Ok(SyncVerifyAction::new(input))
+	}
+
+	async fn execute(
+		self,
+		library: Arc<crate::library::Library>,
+		_context: Arc<CoreContext>,
+	) -> Result<Self::Output, ActionError> {
+		let sync = library
+			.sync_service()
+			.ok_or_else(|| ActionError::Internal("Sync is not enabled for this library".into()))?;
+
+		let reports = sync
+			.verifier()
+			.verify(self.input.peer_id)
+			.await
+			.map_err(|e| ActionError::Internal(format!("Failed to verify sync: {}", e)))?;
+
+		Ok(SyncVerifyOutput { reports })
+	}
+
+	fn action_kind(&self) -> &'static str {
+		"sync.verify"
+	}
+}
+
+crate::register_library_action!(SyncVerifyAction, "sync.verify");
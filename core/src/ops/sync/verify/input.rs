@@ -0,0 +1,13 @@
+//! Input types for sync verification
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use uuid::Uuid;
+
+/// Input for verifying synced data against peers
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SyncVerifyInput {
+	/// Peer whose records to verify, every connected sync partner if unset
+	#[serde(default)]
+	pub peer_id: Option<Uuid>,
+}
@@ -0,0 +1,9 @@
+//! Sync verification operation
+
+pub mod action;
+pub mod input;
+pub mod output;
+
+pub use action::SyncVerifyAction;
+pub use input::SyncVerifyInput;
+pub use output::SyncVerifyOutput;
@@ -0,0 +1,34 @@
+//! Sync verification output types
+
+use crate::{
+	infra::action::output::ActionOutputTrait, service::sync::anti_entropy::VerificationReport,
+};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Output from sync verify action
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SyncVerifyOutput {
+	pub reports: Vec<VerificationReport>,
+}
+
+impl ActionOutputTrait for SyncVerifyOutput {
+	fn to_json(&self) -> serde_json::Value {
+		serde_json::to_value(self).unwrap_or(serde_json::Value::Null)
+	}
+
+	fn display_message(&self) -> String {
+		let mismatches: u64 = self.reports.iter().map(|report| report.mismatches()).sum();
+		let repairs: u64 = self.reports.iter().map(|report| report.repairs()).sum();
+		format!(
+			"Verified sync against {} peers ({} records differed, {} repaired)",
+			self.reports.len(),
+			mismatches,
+			repairs
+		)
+	}
+
+	fn output_type(&self) -> &'static str {
+		"sync.verified"
+	}
+}
@@ -9,7 +9,7 @@ use crate::{
 	domain::tag::{TagApplication, TagSource},
 	infra::action::{error::ActionError, LibraryAction},
 	library::Library,
-	ops::metadata::manager::UserMetadataManager,
+	ops::{metadata::manager::UserMetadataManager, tags::smart::SmartTagEvaluator},
 };
 use chrono::Utc;
 use sea_orm::{DatabaseConnection, EntityTrait};
@@ -74,7 +74,8 @@ impl LibraryAction for ApplyTagsAction {
 			})
 			.collect();
 
-		// Collect affected entry UUIDs for resource events
+		// Collect affected entries for smart tag evaluation and resource events
+		let mut affected_entry_ids = Vec::new();
 		let mut affected_entry_uuids = Vec::new();
 
 		// Handle both content-based and entry-based tagging
@@ -119,6 +120,7 @@ impl LibraryAction for ApplyTagsAction {
 									.all(db.conn())
 									.await
 								{
+									affected_entry_ids.extend(entries.iter().map(|e| e.id));
 									affected_entry_uuids
 										.extend(entries.into_iter().filter_map(|e| e.uuid));
 								}
@@ -161,7 +163,8 @@ impl LibraryAction for ApplyTagsAction {
 									})?;
 							}
 
-							// Track this entry for resource events
+							// Track this entry for smart tags and resource events
+							affected_entry_ids.push(entry_id);
 							affected_entry_uuids.push(entry_uuid);
 						}
 						Err(e) => {
@@ -172,6 +175,16 @@ impl LibraryAction for ApplyTagsAction {
 			}
 		}
 
+		// Smart tags built on the applied tags may now match these entries
+		if !affected_entry_ids.is_empty() {
+			if let Err(e) = SmartTagEvaluator::new(Arc::new(db.conn().clone()))
+				.refresh_entries(&affected_entry_ids)
+				.await
+			{
+				warnings.push(format!("Failed to update smart tags: {}", e));
+			}
+		}
+
 		// Emit resource events for affected files (frontend reactivity)
 		if !affected_entry_uuids.is_empty() {
 			let resource_manager = crate::domain::ResourceManager::new(
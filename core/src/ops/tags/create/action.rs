@@ -11,7 +11,7 @@ use crate::{
 	infra::action::{error::ActionError, LibraryAction},
 	library::Library,
 	ops::metadata::manager::UserMetadataManager,
-	ops::tags::manager::TagManager,
+	ops::tags::{manager::TagManager, smart::SmartTagEvaluator},
 };
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -65,6 +65,7 @@ impl LibraryAction for CreateTagAction {
 				self.input.privacy_level,
 				self.input.search_weight,
 				self.input.attributes.clone(),
+				self.input.composition_rules.clone().unwrap_or_default(),
 				device_id,
 			)
 			.await
@@ -218,6 +219,21 @@ impl LibraryAction for CreateTagAction {
 			}
 		}
 
+		// A new smart tag is evaluated against the whole library
+		if tag_entity.composition_rules.is_some() {
+			let changed = SmartTagEvaluator::new(Arc::new(library.db().conn().clone()))
+				.refresh_all()
+				.await
+				.map_err(|e| {
+					ActionError::Internal(format!("Failed to evaluate smart tag: {}", e))
+				})?;
+			tracing::debug!(
+				"Smart tag '{}' matched {} entries",
+				tag_entity.canonical_name,
+				changed
+			);
+		}
+
 		Ok(CreateTagOutput::from_entity(&tag_entity))
 	}
 
@@ -1,6 +1,7 @@
 //! Input for create semantic tag action
 
-use crate::domain::tag::{PrivacyLevel, TagType};
+use crate::domain::tag::{CompositionRule, PrivacyLevel, TagType};
+use crate::ops::tags::smart::SmartRules;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::collections::HashMap;
@@ -36,6 +37,9 @@ pub struct CreateTagInput {
 	/// Initial attributes
 	pub attributes: Option<HashMap<String, serde_json::Value>>,
 
+	/// Composition rules, making this a smart tag whose members are computed
+	pub composition_rules: Option<Vec<CompositionRule>>,
+
 	/// Optional: Targets to immediately apply this tag to after creation
 	pub apply_to: Option<ApplyToTargets>,
 }
@@ -68,6 +72,7 @@ impl CreateTagInput {
 			privacy_level: None,
 			search_weight: None,
 			attributes: None,
+			composition_rules: None,
 			apply_to: None,
 		}
 	}
@@ -108,6 +113,10 @@ impl CreateTagInput {
 			}
 		}
 
+		if let Some(rules) = &self.composition_rules {
+			SmartRules::parse(rules).map_err(|e| e.to_string())?;
+		}
+
 		// Validate color format (hex)
 		if let Some(color) = &self.color {
 			if !color.starts_with('#') || color.len() != 7 {
@@ -5,8 +5,8 @@
 //! context resolution, and conflict resolution during sync.
 
 use crate::domain::tag::{
-	OrganizationalPattern, PatternType, PrivacyLevel, RelationshipType, Tag, TagApplication,
-	TagError, TagMergeResult, TagRelationship, TagType,
+	CompositionRule, OrganizationalPattern, PatternType, PrivacyLevel, RelationshipType, Tag,
+	TagApplication, TagError, TagMergeResult, TagRelationship, TagType,
 };
 use crate::infra::db::entities::*;
 use anyhow::Result;
@@ -128,6 +128,7 @@ impl TagManager {
 		privacy_level: Option<PrivacyLevel>,
 		search_weight: Option<i32>,
 		attributes: Option<HashMap<String, serde_json::Value>>,
+		composition_rules: Vec<CompositionRule>,
 		created_by_device: Uuid,
 	) -> Result<tag::Model, TagError> {
 		let db = &*self.db;
@@ -177,7 +178,11 @@ impl TagManager {
 					Some(serde_json::to_value(&attrs).unwrap().into())
 				}
 			})),
-			composition_rules: Set(None), // Not exposed in CreateTagInput
+			composition_rules: Set(if composition_rules.is_empty() {
+				None
+			} else {
+				Some(serde_json::to_value(&composition_rules).unwrap().into())
+			}),
 			created_at: Set(now),
 			updated_at: Set(now),
 			created_by_device: Set(Some(created_by_device)),
@@ -8,10 +8,12 @@ pub mod create;
 pub mod facade;
 pub mod manager;
 pub mod search;
+pub mod smart;
 pub mod validation;
 
 pub use facade::TaggingFacade;
 pub use manager::TagManager;
+pub use smart::SmartTagEvaluator;
 pub use validation::TagValidator;
 
 // Re-export commonly used types
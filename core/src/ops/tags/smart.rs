@@ -0,0 +1,879 @@
+//! Smart tags
+//!
+//! A tag with composition rules is a smart tag: its members are the entries its
+//! rules match rather than the entries it was applied to. Each rule operand is a
+//! predicate on an entry:
+//!
+//! - `tag:<uuid>` carries another tag, applied or smart
+//! - `kind:image` content kind
+//! - `ext:jpg` extension, case insensitive
+//! - `size>10MB`, `size<=500KB` size in B, KB, MB, GB or TB (powers of 1024)
+//! - `modified>2024-01-01`, `created<30d` date before or after a day, or age
+//!   below or above a duration in h, d, w or y
+//! - `location:<uuid>` inside an indexed location
+//!
+//! `And` and `With` rules need all of their operands to hold, `Or` rules any of
+//! them and `Without` rules none. An entry is a member when every rule holds.
+//!
+//! Membership is derived from synced data, so each device stores its own in
+//! `smart_tag_members`. It is refreshed for the affected entries whenever tags are
+//! applied, and for the whole library when a smart tag is created. The library's
+//! statistics listener feeds [`SmartTagRefresh`] the files that indexing, change
+//! detection and sync report as changed, and re-evaluates tags with age predicates
+//! every [`AGE_REFRESH_INTERVAL`].
+
+use crate::domain::{
+	tag::{CompositionOperator, CompositionRule, TagError},
+	ContentKind,
+};
+use crate::infra::{
+	db::entities::{entry, smart_tag_member, tag},
+	event::Event,
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use sea_orm::{
+	ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+	QuerySelect, Set, Statement,
+};
+use std::{
+	cmp::Ordering,
+	collections::{HashMap, HashSet, VecDeque},
+	str::FromStr,
+	sync::Arc,
+	time::Instant,
+};
+use tracing::warn;
+use uuid::Uuid;
+
+/// Entries evaluated per database round trip
+const BATCH_SIZE: usize = 500;
+
+/// How often tags with age predicates are re-evaluated for the whole library
+pub const AGE_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Comparison in a size or date predicate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+	Less,
+	LessOrEqual,
+	Equal,
+	GreaterOrEqual,
+	Greater,
+}
+
+impl Comparison {
+	fn holds(self, ordering: Ordering) -> bool {
+		match self {
+			Self::Less => ordering == Ordering::Less,
+			Self::LessOrEqual => ordering != Ordering::Greater,
+			Self::Equal => ordering == Ordering::Equal,
+			Self::GreaterOrEqual => ordering != Ordering::Less,
+			Self::Greater => ordering == Ordering::Greater,
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateField {
+	Created,
+	Modified,
+}
+
+/// Right hand side of a date predicate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateBound {
+	/// Compare the date itself
+	At(DateTime<Utc>),
+	/// Compare how long ago the date was
+	Age(Duration),
+}
+
+/// A single rule operand
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Predicate {
+	Tag(Uuid),
+	Kind(ContentKind),
+	Extension(String),
+	Size(Comparison, u64),
+	Date(DateField, Comparison, DateBound),
+	Location(Uuid),
+}
+
+impl FromStr for Predicate {
+	type Err = TagError;
+
+	fn from_str(operand: &str) -> Result<Self, Self::Err> {
+		let operand = operand.trim();
+		let invalid =
+			|reason: &str| TagError::InvalidCompositionRule(format!("'{}': {}", operand, reason));
+		let uuid = |value: &str| Uuid::parse_str(value.trim()).map_err(|_| invalid("invalid id"));
+
+		if let Some((key, value)) = operand.split_once(':') {
+			return match key.trim() {
+				"tag" => Ok(Self::Tag(uuid(value)?)),
+				"location" => Ok(Self::Location(uuid(value)?)),
+				"ext" => Ok(Self::Extension(
+					value.trim().trim_start_matches('.').to_lowercase(),
+				)),
+				"kind" => match ContentKind::from(value.trim()) {
+					ContentKind::Unknown if value.trim() != "unknown" => {
+						Err(invalid("unknown content kind"))
+					}
+					kind => Ok(Self::Kind(kind)),
+				},
+				_ => Err(invalid("unknown predicate")),
+			};
+		}
+
+		let index = operand
+			.find(['<', '>', '='])
+			.ok_or_else(|| invalid("expected a predicate such as tag:, kind: or size>"))?;
+		let (field, rest) = operand.split_at(index);
+		let (comparison, value) = if let Some(value) = rest.strip_prefix("<=") {
+			(Comparison::LessOrEqual, value)
+		} else if let Some(value) = rest.strip_prefix(">=") {
+			(Comparison::GreaterOrEqual, value)
+		} else if let Some(value) = rest.strip_prefix('<') {
+			(Comparison::Less, value)
+		} else if let Some(value) = rest.strip_prefix('>') {
+			(Comparison::Greater, value)
+		} else {
+			(Comparison::Equal, &rest[1..])
+		};
+		let value = value.trim();
+
+		match field.trim() {
+			"size" => parse_size(value)
+				.map(|bytes| Self::Size(comparison, bytes))
+				.ok_or_else(|| invalid("invalid size")),
+			"created" | "modified" => {
+				let field = if field.trim() == "created" {
+					DateField::Created
+				} else {
+					DateField::Modified
+				};
+				parse_date_bound(value)
+					.map(|bound| Self::Date(field, comparison, bound))
+					.ok_or_else(|| invalid("expected a date (2024-01-31) or an age (30d)"))
+			}
+			_ => Err(invalid("unknown field")),
+		}
+	}
+}
+
+impl Predicate {
+	fn matches(&self, entry: &EntryFacts, now: DateTime<Utc>) -> bool {
+		match self {
+			Self::Tag(tag_id) => entry.tags.contains(tag_id),
+			Self::Kind(kind) => entry.kind == *kind,
+			Self::Extension(extension) => entry
+				.extension
+				.as_deref()
+				.is_some_and(|ext| ext.eq_ignore_ascii_case(extension)),
+			Self::Size(comparison, bytes) => comparison.holds(entry.size.cmp(bytes)),
+			Self::Date(field, comparison, bound) => {
+				let date = match field {
+					DateField::Created => entry.created_at,
+					DateField::Modified => entry.modified_at,
+				};
+				match bound {
+					DateBound::At(at) => comparison.holds(date.cmp(at)),
+					DateBound::Age(age) => comparison.holds((now - date).cmp(age)),
+				}
+			}
+			Self::Location(location_id) => entry.locations.contains(location_id),
+		}
+	}
+}
+
+pub(crate) fn parse_size(value: &str) -> Option<u64> {
+	let index = value
+		.find(|c: char| !c.is_ascii_digit() && c != '.')
+		.unwrap_or(value.len());
+	let (number, unit) = value.split_at(index);
+	let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+		"" | "B" => 1,
+		"KB" => 1 << 10,
+		"MB" => 1 << 20,
+		"GB" => 1 << 30,
+		"TB" => 1 << 40,
+		_ => return None,
+	};
+	let number: f64 = number.parse().ok()?;
+	(number >= 0.0).then_some((number * multiplier as f64) as u64)
+}
+
+fn parse_date_bound(value: &str) -> Option<DateBound> {
+	if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+		return Some(DateBound::At(date.and_hms_opt(0, 0, 0)?.and_utc()));
+	}
+
+	let unit_start = value.len().checked_sub(1)?;
+	if !value.is_char_boundary(unit_start) {
+		return None;
+	}
+	let (number, unit) = value.split_at(unit_start);
+	let number: i64 = number.parse().ok()?;
+	let age = match unit {
+		"h" => Duration::try_hours(number),
+		"d" => Duration::try_days(number),
+		"w" => Duration::try_weeks(number),
+		"y" => Duration::try_days(number.checked_mul(365)?),
+		_ => return None,
+	};
+	age.map(DateBound::Age)
+}
+
+/// Parsed composition rules of one tag
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmartRules {
+	rules: Vec<(CompositionOperator, Vec<Predicate>)>,
+}
+
+impl SmartRules {
+	pub fn parse(rules: &[CompositionRule]) -> Result<Self, TagError> {
+		let rules = rules
+			.iter()
+			.map(|rule| {
+				if rule.operands.is_empty() {
+					return Err(TagError::InvalidCompositionRule(
+						"rule has no operands".to_string(),
+					));
+				}
+				let predicates = rule
+					.operands
+					.iter()
+					.map(|operand| operand.parse())
+					.collect::<Result<Vec<Predicate>, _>>()?;
+				Ok((rule.operator.clone(), predicates))
+			})
+			.collect::<Result<Vec<_>, _>>()?;
+
+		Ok(Self { rules })
+	}
+
+	pub fn matches(&self, entry: &EntryFacts, now: DateTime<Utc>) -> bool {
+		!self.rules.is_empty()
+			&& self.rules.iter().all(|(operator, predicates)| {
+				let mut results = predicates.iter().map(|p| p.matches(entry, now));
+				match operator {
+					CompositionOperator::And | CompositionOperator::With => {
+						results.all(|matched| matched)
+					}
+					CompositionOperator::Or => results.any(|matched| matched),
+					CompositionOperator::Without => !results.any(|matched| matched),
+				}
+			})
+	}
+
+	/// Whether membership changes with time alone, through an age predicate
+	pub fn uses_age(&self) -> bool {
+		self.rules
+			.iter()
+			.flat_map(|(_, predicates)| predicates)
+			.any(|predicate| matches!(predicate, Predicate::Date(_, _, DateBound::Age(_))))
+	}
+
+	/// Tags this tag's membership depends on
+	pub fn referenced_tags(&self) -> impl Iterator<Item = Uuid> + '_ {
+		self.rules
+			.iter()
+			.flat_map(|(_, predicates)| predicates)
+			.filter_map(|predicate| match predicate {
+				Predicate::Tag(tag_id) => Some(*tag_id),
+				_ => None,
+			})
+	}
+}
+
+/// What rules can see about an entry
+#[derive(Debug, Clone)]
+pub struct EntryFacts {
+	pub tags: HashSet<Uuid>,
+	pub kind: ContentKind,
+	pub extension: Option<String>,
+	pub size: u64,
+	pub created_at: DateTime<Utc>,
+	pub modified_at: DateTime<Utc>,
+	pub locations: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+struct SmartTag {
+	id: i32,
+	uuid: Uuid,
+	rules: SmartRules,
+}
+
+/// Order smart tags so every tag comes after the smart tags it references
+///
+/// Tags that are part of a reference cycle can't be evaluated and are left out.
+fn evaluation_order(tags: Vec<SmartTag>) -> Vec<SmartTag> {
+	let smart: HashSet<Uuid> = tags.iter().map(|tag| tag.uuid).collect();
+	let mut pending: HashMap<Uuid, usize> = HashMap::new();
+	let mut dependents: HashMap<Uuid, Vec<usize>> = HashMap::new();
+
+	for (index, tag) in tags.iter().enumerate() {
+		let references: HashSet<Uuid> = tag
+			.rules
+			.referenced_tags()
+			.filter(|id| smart.contains(id))
+			.collect();
+		pending.insert(tag.uuid, references.len());
+		for reference in references {
+			dependents.entry(reference).or_default().push(index);
+		}
+	}
+
+	let mut ready: VecDeque<usize> = (0..tags.len())
+		.filter(|&index| pending[&tags[index].uuid] == 0)
+		.collect();
+	let mut order = Vec::with_capacity(tags.len());
+	while let Some(index) = ready.pop_front() {
+		order.push(index);
+		for &dependent in dependents.get(&tags[index].uuid).into_iter().flatten() {
+			let count = pending
+				.get_mut(&tags[dependent].uuid)
+				.expect("tag is pending");
+			*count -= 1;
+			if *count == 0 {
+				ready.push_back(dependent);
+			}
+		}
+	}
+
+	if order.len() < tags.len() {
+		let ordered: HashSet<usize> = order.iter().copied().collect();
+		for (index, tag) in tags.iter().enumerate() {
+			if !ordered.contains(&index) {
+				warn!(
+					"Smart tag {} is part of a reference cycle, skipping",
+					tag.uuid
+				);
+			}
+		}
+	}
+
+	let mut tags: Vec<Option<SmartTag>> = tags.into_iter().map(Some).collect();
+	order
+		.into_iter()
+		.filter_map(|index| tags[index].take())
+		.collect()
+}
+
+/// SQL selecting the ids of entries carrying a tag, applied directly, through
+/// their content, or matched as a smart tag
+pub fn tagged_entries_sql(tag_id: Uuid) -> (String, Vec<sea_orm::Value>) {
+	let sql = r#"
+		SELECT e.id FROM entries e
+		JOIN user_metadata um ON um.entry_uuid = e.uuid
+		JOIN user_metadata_tag umt ON umt.user_metadata_id = um.id
+		JOIN tag t ON t.id = umt.tag_id
+		WHERE t.uuid = ?
+		UNION
+		SELECT e.id FROM entries e
+		JOIN content_identities ci ON ci.id = e.content_id
+		JOIN user_metadata um ON um.content_identity_uuid = ci.uuid
+		JOIN user_metadata_tag umt ON umt.user_metadata_id = um.id
+		JOIN tag t ON t.id = umt.tag_id
+		WHERE t.uuid = ?
+		UNION
+		SELECT stm.entry_id FROM smart_tag_members stm
+		JOIN tag t ON t.id = stm.tag_id
+		WHERE t.uuid = ?
+	"#;
+
+	(
+		sql.to_string(),
+		vec![tag_id.into(), tag_id.into(), tag_id.into()],
+	)
+}
+
+/// Keeps `smart_tag_members` in line with tag composition rules
+pub struct SmartTagEvaluator {
+	db: Arc<DatabaseConnection>,
+}
+
+impl SmartTagEvaluator {
+	pub fn new(db: Arc<DatabaseConnection>) -> Self {
+		Self { db }
+	}
+
+	/// Re-evaluate every smart tag for the given entries
+	///
+	/// Returns the entries whose membership changed.
+	pub async fn refresh_entries(&self, entry_ids: &[i32]) -> Result<Vec<i32>, TagError> {
+		let tags = self.load_smart_tags().await?;
+		let mut changed = Vec::new();
+
+		for batch in entry_ids.chunks(BATCH_SIZE) {
+			changed.extend(self.refresh_batch(&tags, batch).await?);
+		}
+
+		Ok(changed)
+	}
+
+	/// Re-evaluate every smart tag for the whole library
+	///
+	/// Returns how many entries changed membership.
+	pub async fn refresh_all(&self) -> Result<usize, TagError> {
+		let tags = self.load_smart_tags().await?;
+		self.refresh_library(&tags).await
+	}
+
+	/// Re-evaluate the whole library if any smart tag has an age predicate
+	///
+	/// Returns how many entries changed membership.
+	pub async fn refresh_aging(&self) -> Result<usize, TagError> {
+		let tags = self.load_smart_tags().await?;
+		if !tags.iter().any(|tag| tag.rules.uses_age()) {
+			return Ok(0);
+		}
+		self.refresh_library(&tags).await
+	}
+
+	/// Re-evaluate the given entries, looked up by uuid
+	///
+	/// Returns how many entries changed membership.
+	pub async fn refresh_entry_uuids(&self, uuids: &[Uuid]) -> Result<usize, TagError> {
+		let mut changed = 0;
+		for batch in uuids.chunks(BATCH_SIZE) {
+			let entry_ids: Vec<i32> = entry::Entity::find()
+				.select_only()
+				.column(entry::Column::Id)
+				.filter(entry::Column::Uuid.is_in(batch.to_vec()))
+				.into_tuple()
+				.all(&*self.db)
+				.await
+				.map_err(db_error)?;
+			changed += self.refresh_entries(&entry_ids).await?.len();
+		}
+		Ok(changed)
+	}
+
+	async fn refresh_library(&self, tags: &[SmartTag]) -> Result<usize, TagError> {
+		// Tags that lost their rules keep no members
+		smart_tag_member::Entity::delete_many()
+			.filter(smart_tag_member::Column::TagId.is_not_in(tags.iter().map(|tag| tag.id)))
+			.exec(&*self.db)
+			.await
+			.map_err(db_error)?;
+
+		let mut changed = 0;
+		let mut last_id = 0;
+		loop {
+			let batch: Vec<i32> = entry::Entity::find()
+				.select_only()
+				.column(entry::Column::Id)
+				.filter(entry::Column::Id.gt(last_id))
+				.order_by_asc(entry::Column::Id)
+				.limit(BATCH_SIZE as u64)
+				.into_tuple()
+				.all(&*self.db)
+				.await
+				.map_err(db_error)?;
+
+			let Some(&last) = batch.last() else {
+				break;
+			};
+			last_id = last;
+
+			changed += self.refresh_batch(tags, &batch).await?.len();
+		}
+
+		Ok(changed)
+	}
+
+	async fn refresh_batch(
+		&self,
+		tags: &[SmartTag],
+		entry_ids: &[i32],
+	) -> Result<Vec<i32>, TagError> {
+		let mut facts = self.load_facts(entry_ids).await?;
+
+		let existing: HashSet<(i32, i32)> = smart_tag_member::Entity::find()
+			.filter(smart_tag_member::Column::EntryId.is_in(entry_ids.to_vec()))
+			.all(&*self.db)
+			.await
+			.map_err(db_error)?
+			.into_iter()
+			.map(|member| (member.tag_id, member.entry_id))
+			.collect();
+
+		// Tags are in dependency order, so a match is visible to the tags after it
+		let now = Utc::now();
+		let mut matched = HashSet::new();
+		for tag in tags {
+			for (&entry_id, entry) in facts.iter_mut() {
+				if tag.rules.matches(entry, now) {
+					matched.insert((tag.id, entry_id));
+					entry.tags.insert(tag.uuid);
+				}
+			}
+		}
+
+		let mut changed = HashSet::new();
+
+		for &(tag_id, entry_id) in existing.difference(&matched) {
+			smart_tag_member::Entity::delete_many()
+				.filter(smart_tag_member::Column::TagId.eq(tag_id))
+				.filter(smart_tag_member::Column::EntryId.eq(entry_id))
+				.exec(&*self.db)
+				.await
+				.map_err(db_error)?;
+			changed.insert(entry_id);
+		}
+
+		let added: Vec<smart_tag_member::ActiveModel> = matched
+			.difference(&existing)
+			.map(|&(tag_id, entry_id)| {
+				changed.insert(entry_id);
+				smart_tag_member::ActiveModel {
+					tag_id: Set(tag_id),
+					entry_id: Set(entry_id),
+					matched_at: Set(now),
+				}
+			})
+			.collect();
+		if !added.is_empty() {
+			smart_tag_member::Entity::insert_many(added)
+				.exec(&*self.db)
+				.await
+				.map_err(db_error)?;
+		}
+
+		Ok(changed.into_iter().collect())
+	}
+
+	async fn load_smart_tags(&self) -> Result<Vec<SmartTag>, TagError> {
+		let models = tag::Entity::find()
+			.filter(tag::Column::CompositionRules.is_not_null())
+			.all(&*self.db)
+			.await
+			.map_err(db_error)?;
+
+		let mut tags = Vec::new();
+		for model in models {
+			let rules: Vec<CompositionRule> = model
+				.composition_rules
+				.and_then(|json| serde_json::from_value(json).ok())
+				.unwrap_or_default();
+			if rules.is_empty() {
+				continue;
+			}
+
+			match SmartRules::parse(&rules) {
+				Ok(rules) => tags.push(SmartTag {
+					id: model.id,
+					uuid: model.uuid,
+					rules,
+				}),
+				Err(e) => warn!("Skipping smart tag {}: {}", model.uuid, e),
+			}
+		}
+
+		Ok(evaluation_order(tags))
+	}
+
+	async fn load_facts(&self, entry_ids: &[i32]) -> Result<HashMap<i32, EntryFacts>, TagError> {
+		let ids = entry_ids
+			.iter()
+			.map(|id| id.to_string())
+			.collect::<Vec<_>>()
+			.join(",");
+
+		let rows = self
+			.query(format!(
+				r#"
+				SELECT e.id, e.extension, e.size, e.created_at, e.modified_at, ci.kind_id
+				FROM entries e
+				LEFT JOIN content_identities ci ON ci.id = e.content_id
+				WHERE e.id IN ({})
+				"#,
+				ids
+			))
+			.await?;
+
+		let mut facts = HashMap::with_capacity(rows.len());
+		for row in rows {
+			let entry_id: i32 = row.try_get("", "id").map_err(db_error)?;
+			let modified_at: DateTime<Utc> = row.try_get("", "modified_at").map_err(db_error)?;
+			facts.insert(
+				entry_id,
+				EntryFacts {
+					tags: HashSet::new(),
+					kind: row
+						.try_get::<Option<i32>>("", "kind_id")
+						.ok()
+						.flatten()
+						.map(ContentKind::from_id)
+						.unwrap_or(ContentKind::Unknown),
+					extension: row.try_get("", "extension").ok().flatten(),
+					size: row.try_get::<i64>("", "size").unwrap_or(0).max(0) as u64,
+					created_at: row.try_get("", "created_at").unwrap_or(modified_at),
+					modified_at,
+					locations: Vec::new(),
+				},
+			);
+		}
+
+		let tag_rows = self
+			.query(format!(
+				r#"
+				SELECT e.id AS entry_id, t.uuid AS tag_uuid FROM entries e
+				JOIN user_metadata um ON um.entry_uuid = e.uuid
+				JOIN user_metadata_tag umt ON umt.user_metadata_id = um.id
+				JOIN tag t ON t.id = umt.tag_id
+				WHERE e.id IN ({ids})
+				UNION
+				SELECT e.id AS entry_id, t.uuid AS tag_uuid FROM entries e
+				JOIN content_identities ci ON ci.id = e.content_id
+				JOIN user_metadata um ON um.content_identity_uuid = ci.uuid
+				JOIN user_metadata_tag umt ON umt.user_metadata_id = um.id
+				JOIN tag t ON t.id = umt.tag_id
+				WHERE e.id IN ({ids})
+				"#,
+				ids = ids
+			))
+			.await?;
+		for row in tag_rows {
+			let entry_id: i32 = row.try_get("", "entry_id").map_err(db_error)?;
+			let tag_uuid: Uuid = row.try_get("", "tag_uuid").map_err(db_error)?;
+			if let Some(entry) = facts.get_mut(&entry_id) {
+				entry.tags.insert(tag_uuid);
+			}
+		}
+
+		let location_rows = self
+			.query(format!(
+				r#"
+				SELECT ec.descendant_id AS entry_id, l.uuid AS location_uuid
+				FROM entry_closure ec
+				JOIN locations l ON l.entry_id = ec.ancestor_id
+				WHERE ec.descendant_id IN ({})
+				"#,
+				ids
+			))
+			.await?;
+		for row in location_rows {
+			let entry_id: i32 = row.try_get("", "entry_id").map_err(db_error)?;
+			let location_uuid: Uuid = row.try_get("", "location_uuid").map_err(db_error)?;
+			if let Some(entry) = facts.get_mut(&entry_id) {
+				entry.locations.push(location_uuid);
+			}
+		}
+
+		Ok(facts)
+	}
+
+	async fn query(&self, sql: String) -> Result<Vec<sea_orm::QueryResult>, TagError> {
+		self.db
+			.query_all(Statement::from_string(self.db.get_database_backend(), sql))
+			.await
+			.map_err(db_error)
+	}
+}
+
+/// Smart tag work queued up by resource events
+///
+/// Files reported as changed are collected by [`observe`](Self::observe) and
+/// re-evaluated together on the next [`refresh`](Self::refresh).
+#[derive(Debug, Default)]
+pub struct SmartTagRefresh {
+	changed_files: HashSet<Uuid>,
+	last_age_refresh: Option<Instant>,
+}
+
+impl SmartTagRefresh {
+	/// Queue the files a resource event reports as changed
+	pub fn observe(&mut self, event: &Event) {
+		let resources = match event {
+			Event::ResourceChanged {
+				resource_type,
+				resource,
+				..
+			} if resource_type == "file" => std::slice::from_ref(resource),
+			Event::ResourceChangedBatch {
+				resource_type,
+				resources,
+				..
+			} if resource_type == "file" => match resources.as_array() {
+				Some(resources) => resources.as_slice(),
+				None => return,
+			},
+			_ => return,
+		};
+
+		self.changed_files
+			.extend(resources.iter().filter_map(|file| {
+				file.get("id")
+					.and_then(|id| id.as_str())
+					.and_then(|id| Uuid::parse_str(id).ok())
+			}));
+	}
+
+	/// Re-evaluate the queued files, and the whole library when age predicates are due
+	///
+	/// Returns how many entries changed membership.
+	pub async fn refresh(&mut self, db: Arc<DatabaseConnection>) -> Result<usize, TagError> {
+		let evaluator = SmartTagEvaluator::new(db);
+		let mut changed = 0;
+
+		if !self.changed_files.is_empty() {
+			let uuids: Vec<Uuid> = self.changed_files.drain().collect();
+			changed += evaluator.refresh_entry_uuids(&uuids).await?;
+		}
+
+		let age_due = self
+			.last_age_refresh
+			.map_or(true, |last| last.elapsed() >= AGE_REFRESH_INTERVAL);
+		if age_due {
+			self.last_age_refresh = Some(Instant::now());
+			changed += evaluator.refresh_aging().await?;
+		}
+
+		Ok(changed)
+	}
+}
+
+fn db_error(e: sea_orm::DbErr) -> TagError {
+	TagError::DatabaseError(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn rule(operator: CompositionOperator, operands: &[&str]) -> CompositionRule {
+		CompositionRule {
+			operator,
+			operands: operands.iter().map(|o| o.to_string()).collect(),
+			result_attribute: String::new(),
+		}
+	}
+
+	fn photo(now: DateTime<Utc>) -> EntryFacts {
+		EntryFacts {
+			tags: HashSet::new(),
+			kind: ContentKind::Image,
+			extension: Some("JPG".to_string()),
+			size: 12 << 20,
+			created_at: now - Duration::days(400),
+			modified_at: now - Duration::days(3),
+			locations: Vec::new(),
+		}
+	}
+
+	#[test]
+	fn parses_predicates() {
+		let tag_id = Uuid::new_v4();
+		assert_eq!(
+			format!("tag:{}", tag_id).parse::<Predicate>().unwrap(),
+			Predicate::Tag(tag_id)
+		);
+		assert_eq!(
+			"ext:.PNG".parse::<Predicate>().unwrap(),
+			Predicate::Extension("png".to_string())
+		);
+		assert_eq!(
+			"size >= 1.5KB".parse::<Predicate>().unwrap(),
+			Predicate::Size(Comparison::GreaterOrEqual, 1536)
+		);
+		assert_eq!(
+			"modified<30d".parse::<Predicate>().unwrap(),
+			Predicate::Date(
+				DateField::Modified,
+				Comparison::Less,
+				DateBound::Age(Duration::days(30))
+			)
+		);
+		assert!(matches!(
+			"created>2024-01-01".parse::<Predicate>().unwrap(),
+			Predicate::Date(DateField::Created, Comparison::Greater, DateBound::At(_))
+		));
+
+		for invalid in [
+			"kind:pictures",
+			"size>lots",
+			"colour:red",
+			"tag:nope",
+			"big",
+		] {
+			assert!(invalid.parse::<Predicate>().is_err(), "{}", invalid);
+		}
+	}
+
+	#[test]
+	fn evaluates_operators() {
+		let now = Utc::now();
+		let entry = photo(now);
+
+		let recent_large_photos = SmartRules::parse(&[
+			rule(CompositionOperator::And, &["kind:image", "size>10MB"]),
+			rule(CompositionOperator::Or, &["modified<7d", "ext:raw"]),
+		])
+		.unwrap();
+		assert!(recent_large_photos.matches(&entry, now));
+
+		let not_jpegs =
+			SmartRules::parse(&[rule(CompositionOperator::Without, &["ext:jpg", "ext:jpeg"])])
+				.unwrap();
+		assert!(!not_jpegs.matches(&entry, now));
+
+		let old =
+			SmartRules::parse(&[rule(CompositionOperator::With, &["created<2024-01-01"])]).unwrap();
+		assert!(!old.matches(&entry, now));
+
+		assert!(recent_large_photos.uses_age());
+		assert!(!old.uses_age());
+	}
+
+	#[test]
+	fn queues_changed_files_from_events() {
+		let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+		let mut refresh = SmartTagRefresh::default();
+
+		refresh.observe(&Event::ResourceChanged {
+			resource_type: "file".to_string(),
+			resource: serde_json::json!({ "id": a }),
+			metadata: None,
+		});
+		refresh.observe(&Event::ResourceChangedBatch {
+			resource_type: "file".to_string(),
+			resources: serde_json::json!([{ "id": b }, { "name": "no id" }]),
+			metadata: None,
+		});
+		refresh.observe(&Event::ResourceChanged {
+			resource_type: "location".to_string(),
+			resource: serde_json::json!({ "id": c }),
+			metadata: None,
+		});
+
+		assert_eq!(refresh.changed_files, HashSet::from([a, b]));
+	}
+
+	#[test]
+	fn orders_tags_by_reference() {
+		let tag = |uuid: Uuid, operands: &[&str]| SmartTag {
+			id: 0,
+			uuid,
+			rules: SmartRules::parse(&[rule(CompositionOperator::And, operands)]).unwrap(),
+		};
+		let (a, b, c, d) = (
+			Uuid::new_v4(),
+			Uuid::new_v4(),
+			Uuid::new_v4(),
+			Uuid::new_v4(),
+		);
+
+		let order = evaluation_order(vec![
+			tag(a, &[&format!("tag:{}", b)]),
+			tag(b, &["kind:image"]),
+			tag(c, &[&format!("tag:{}", d)]),
+			tag(d, &[&format!("tag:{}", c)]),
+		]);
+
+		let uuids: Vec<Uuid> = order.iter().map(|tag| tag.uuid).collect();
+		assert_eq!(uuids, vec![b, a]);
+	}
+}
@@ -8,7 +8,9 @@ use crate::{
 	context::CoreContext,
 	crypto::cloud_credentials::{CloudCredential, CloudCredentialManager},
 	infra::action::{error::ActionError, LibraryAction},
-	volume::{backend::CloudServiceType, CloudBackend, Volume, VolumeFingerprint},
+	volume::{
+		backend::CloudServiceType, CloudBackend, EncryptedBackend, Volume, VolumeFingerprint,
+	},
 };
 use serde::{Deserialize, Serialize};
 use specta::Type;
@@ -20,6 +22,13 @@ pub struct VolumeAddCloudInput {
 	pub service: CloudServiceType,
 	pub display_name: String,
 	pub config: CloudStorageConfig,
+	/// Encrypt file contents on this device before they're uploaded.
+	/// The key stays in this device's key manager and never reaches the service.
+	#[serde(default)]
+	pub encrypt: bool,
+	/// Encrypt file and directory names as well, requires encrypt
+	#[serde(default)]
+	pub encrypt_names: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -71,6 +80,23 @@ pub enum CloudStorageConfig {
 		endpoint: Option<String>,
 		credential: String, // Service account JSON
 	},
+	/// SFTP server, logged into through the system's OpenSSH.
+	/// Uses the SSH agent or default identities when key_path is None.
+	Sftp {
+		host: String,
+		port: Option<u16>,
+		username: String,
+		key_path: Option<String>,
+		root: Option<String>,
+	},
+	/// WebDAV server such as Nextcloud.
+	/// The endpoint is the DAV URL, e.g. https://cloud.example.com/remote.php/dav/files/alice
+	WebDav {
+		endpoint: String,
+		username: String,
+		password: String,
+		root: Option<String>,
+	},
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -84,11 +110,57 @@ impl VolumeAddCloudAction {
 	}
 }
 
+/// Cloud identifier of an SFTP volume, "user@host" with the port when it isn't 22
+pub fn sftp_identifier(username: &str, host: &str, port: Option<u16>) -> String {
+	match port {
+		Some(port) if port != 22 => format!("{}@{}:{}", username, host, port),
+		_ => format!("{}@{}", username, host),
+	}
+}
+
+/// Cloud identifier of a WebDAV volume, "user@host" with the host taken from the endpoint
+pub fn webdav_identifier(username: &str, endpoint: &str) -> Result<String, String> {
+	let rest = endpoint
+		.strip_prefix("https://")
+		.or_else(|| endpoint.strip_prefix("http://"))
+		.ok_or_else(|| "WebDAV endpoint must start with http:// or https://".to_string())?;
+	let host = rest.split('/').next().unwrap_or_default();
+	if host.is_empty() {
+		return Err("WebDAV endpoint has no host".to_string());
+	}
+
+	Ok(format!("{}@{}", username, host))
+}
+
 impl LibraryAction for VolumeAddCloudAction {
 	type Input = VolumeAddCloudInput;
 	type Output = VolumeAddCloudOutput;
 
 	fn from_input(input: VolumeAddCloudInput) -> Result<Self, String> {
+		if input.encrypt_names && !input.encrypt {
+			return Err("Encrypting names requires encrypting contents".to_string());
+		}
+
+		match &input.config {
+			CloudStorageConfig::Sftp { host, username, .. } => {
+				if host.trim().is_empty() {
+					return Err("SFTP requires a host".to_string());
+				}
+				if username.trim().is_empty() {
+					return Err("SFTP requires a username".to_string());
+				}
+			}
+			CloudStorageConfig::WebDav {
+				endpoint, username, ..
+			} => {
+				if username.trim().is_empty() {
+					return Err("WebDAV requires a username".to_string());
+				}
+				webdav_identifier(username, endpoint)?;
+			}
+			_ => {}
+		}
+
 		Ok(VolumeAddCloudAction::new(input))
 	}
 
@@ -103,7 +175,7 @@ impl LibraryAction for VolumeAddCloudAction {
 			.map_err(|e| ActionError::InvalidInput(format!("Failed to get device ID: {}", e)))?;
 		let library_id = library.id();
 
-		let (backend, credential, cloud_identifier, mount_point, cloud_config) = match &self
+		let (backend, credential, cloud_identifier, mount_point, mut cloud_config) = match &self
 			.input
 			.config
 		{
@@ -403,6 +475,77 @@ impl LibraryAction for VolumeAddCloudAction {
 					"endpoint": endpoint,
 				});
 
+				(backend, credential, cloud_identifier, mount_point, config)
+			}
+			CloudStorageConfig::Sftp {
+				host,
+				port,
+				username,
+				key_path,
+				root,
+			} => {
+				let backend =
+					CloudBackend::new_sftp(host, *port, username, key_path.clone(), root.clone())
+						.await
+						.map_err(|e| {
+							ActionError::InvalidInput(format!(
+								"Failed to create SFTP backend: {}",
+								e
+							))
+						})?;
+
+				let credential = CloudCredential::new_ssh_key(
+					CloudServiceType::Sftp,
+					username.clone(),
+					key_path.clone(),
+				);
+
+				let cloud_identifier = sftp_identifier(username, host, *port);
+				let desired_mount_point = format!("sftp://{}", cloud_identifier);
+				let mount_point = context
+					.volume_manager
+					.ensure_unique_mount_point(&desired_mount_point)
+					.await;
+
+				let config = serde_json::json!({
+					"host": host,
+					"port": port,
+					"root": root,
+				});
+
+				(backend, credential, cloud_identifier, mount_point, config)
+			}
+			CloudStorageConfig::WebDav {
+				endpoint,
+				username,
+				password,
+				root,
+			} => {
+				let backend = CloudBackend::new_webdav(endpoint, username, password, root.clone())
+					.await
+					.map_err(|e| {
+						ActionError::InvalidInput(format!("Failed to create WebDAV backend: {}", e))
+					})?;
+
+				let credential = CloudCredential::new_password(
+					CloudServiceType::WebDav,
+					username.clone(),
+					password.clone(),
+				);
+
+				let cloud_identifier =
+					webdav_identifier(username, endpoint).map_err(ActionError::InvalidInput)?;
+				let desired_mount_point = format!("webdav://{}", cloud_identifier);
+				let mount_point = context
+					.volume_manager
+					.ensure_unique_mount_point(&desired_mount_point)
+					.await;
+
+				let config = serde_json::json!({
+					"endpoint": endpoint,
+					"root": root,
+				});
+
 				(backend, credential, cloud_identifier, mount_point, config)
 			}
 		};
@@ -411,7 +554,25 @@ impl LibraryAction for VolumeAddCloudAction {
 		let backend_id = format!("{:?}", self.input.service);
 		let fingerprint = VolumeFingerprint::from_network_volume(&backend_id, &cloud_identifier);
 
-		let backend_arc: Arc<dyn crate::volume::VolumeBackend> = Arc::new(backend);
+		let mut backend_arc: Arc<dyn crate::volume::VolumeBackend> = Arc::new(backend);
+		if self.input.encrypt {
+			let volume_key = context
+				.key_manager
+				.get_or_create_volume_key(&fingerprint.0)
+				.await
+				.map_err(|e| {
+					ActionError::InvalidInput(format!("Failed to get volume key: {}", e))
+				})?;
+			backend_arc = Arc::new(EncryptedBackend::new(
+				backend_arc,
+				&volume_key,
+				self.input.encrypt_names,
+			));
+
+			// Read back by the volume manager to wrap the restored backend the same way
+			cloud_config["encrypted"] = serde_json::Value::Bool(true);
+			cloud_config["encrypt_names"] = serde_json::Value::Bool(self.input.encrypt_names);
+		}
 		let now = chrono::Utc::now();
 
 		let volume = Volume {
@@ -503,3 +664,59 @@ impl LibraryAction for VolumeAddCloudAction {
 }
 
 crate::register_library_action!(VolumeAddCloudAction, "volumes.add_cloud");
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn self_hosted_identifiers() {
+		assert_eq!(
+			sftp_identifier("alice", "nas.local", None),
+			"alice@nas.local"
+		);
+		assert_eq!(
+			sftp_identifier("alice", "nas.local", Some(22)),
+			"alice@nas.local"
+		);
+		assert_eq!(
+			sftp_identifier("alice", "nas.local", Some(2222)),
+			"alice@nas.local:2222"
+		);
+
+		assert_eq!(
+			webdav_identifier(
+				"alice",
+				"https://cloud.example.com/remote.php/dav/files/alice"
+			)
+			.unwrap(),
+			"alice@cloud.example.com"
+		);
+		assert_eq!(
+			webdav_identifier("bob", "http://192.168.1.10:8080").unwrap(),
+			"bob@192.168.1.10:8080"
+		);
+		assert!(webdav_identifier("alice", "cloud.example.com").is_err());
+		assert!(webdav_identifier("alice", "https://").is_err());
+	}
+
+	#[test]
+	fn name_encryption_requires_encryption() {
+		let input = |encrypt, encrypt_names| VolumeAddCloudInput {
+			service: CloudServiceType::WebDav,
+			display_name: "Nextcloud".to_string(),
+			config: CloudStorageConfig::WebDav {
+				endpoint: "https://cloud.example.com/remote.php/dav/files/alice".to_string(),
+				username: "alice".to_string(),
+				password: "secret".to_string(),
+				root: None,
+			},
+			encrypt,
+			encrypt_names,
+		};
+
+		assert!(VolumeAddCloudAction::from_input(input(false, true)).is_err());
+		assert!(VolumeAddCloudAction::from_input(input(true, true)).is_ok());
+		assert!(VolumeAddCloudAction::from_input(input(false, false)).is_ok());
+	}
+}
@@ -1,7 +1,10 @@
-use crate::infra::db::entities::{entry, sync_conduit, sync_generation};
+use super::{conflict::ConflictStrategy, resolver::SyncConflict};
+use crate::infra::db::entities::{entry, sync_conduit, sync_conflict, sync_generation};
 use anyhow::Result;
 use chrono::Utc;
-use sea_orm::{prelude::*, ActiveValue::Set, DatabaseConnection, QueryOrder};
+use sea_orm::{
+	prelude::*, sea_query::Expr, ActiveValue::Set, DatabaseConnection, QueryOrder, QuerySelect,
+};
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -22,6 +25,7 @@ impl ConduitManager {
 		target_entry_id: i32,
 		mode: sync_conduit::SyncMode,
 		schedule: String,
+		conflict_strategy: ConflictStrategy,
 	) -> Result<sync_conduit::Model> {
 		// Validate entries exist and are directories
 		let source = entry::Entity::find_by_id(source_entry_id)
@@ -62,6 +66,7 @@ impl ConduitManager {
 			sync_mode: Set(mode.as_str().to_string()),
 			enabled: Set(true),
 			schedule: Set(schedule),
+			conflict_strategy: Set(conflict_strategy.as_str().to_string()),
 			use_index_rules: Set(true),
 			index_mode_override: Set(None),
 			parallel_transfers: Set(3),
@@ -125,6 +130,38 @@ impl ConduitManager {
 		Ok(())
 	}
 
+	/// Update conduit settings, leaving `None` fields unchanged
+	pub async fn update_conduit(
+		&self,
+		conduit_id: i32,
+		update: ConduitUpdate,
+	) -> Result<sync_conduit::Model> {
+		let conduit = self.get_conduit(conduit_id).await?;
+
+		let mut active: sync_conduit::ActiveModel = conduit.into();
+		if let Some(mode) = update.mode {
+			active.sync_mode = Set(mode.as_str().to_string());
+		}
+		if let Some(schedule) = update.schedule {
+			active.schedule = Set(schedule);
+		}
+		if let Some(conflict_strategy) = update.conflict_strategy {
+			active.conflict_strategy = Set(conflict_strategy.as_str().to_string());
+		}
+		if let Some(use_index_rules) = update.use_index_rules {
+			active.use_index_rules = Set(use_index_rules);
+		}
+		if let Some(parallel_transfers) = update.parallel_transfers {
+			active.parallel_transfers = Set(parallel_transfers);
+		}
+		if let Some(bandwidth_limit_mbps) = update.bandwidth_limit_mbps {
+			active.bandwidth_limit_mbps = Set(bandwidth_limit_mbps);
+		}
+		active.updated_at = Set(Utc::now());
+
+		Ok(active.update(&*self.db).await?)
+	}
+
 	/// Update conduit after successful sync
 	pub async fn update_after_sync(&self, conduit_id: i32) -> Result<()> {
 		let conduit = self.get_conduit(conduit_id).await?;
@@ -225,6 +262,174 @@ impl ConduitManager {
 			.await?)
 	}
 
+	/// List generations of a conduit, newest first
+	pub async fn list_generations(
+		&self,
+		conduit_id: i32,
+		limit: Option<u64>,
+	) -> Result<Vec<sync_generation::Model>> {
+		let mut query = sync_generation::Entity::find()
+			.filter(sync_generation::Column::ConduitId.eq(conduit_id))
+			.order_by_desc(sync_generation::Column::Generation);
+		if let Some(limit) = limit {
+			query = query.limit(limit);
+		}
+
+		Ok(query.all(&*self.db).await?)
+	}
+
+	/// Record conflicts waiting for the user, returns how many were not already pending
+	pub async fn record_conflicts(
+		&self,
+		conduit_id: i32,
+		generation_id: i32,
+		conflicts: &[SyncConflict],
+	) -> Result<usize> {
+		let mut recorded = 0;
+		let now = Utc::now();
+
+		for conflict in conflicts {
+			let relative_path = conflict.relative_path.to_string_lossy().to_string();
+
+			// Still waiting for a decision from an earlier generation
+			let pending = sync_conflict::Entity::find()
+				.filter(sync_conflict::Column::ConduitId.eq(conduit_id))
+				.filter(sync_conflict::Column::RelativePath.eq(&relative_path))
+				.filter(sync_conflict::Column::Resolution.is_null())
+				.one(&*self.db)
+				.await?;
+			if pending.is_some() {
+				continue;
+			}
+
+			sync_conflict::ActiveModel {
+				uuid: Set(Uuid::new_v4()),
+				conduit_id: Set(conduit_id),
+				generation_id: Set(generation_id),
+				relative_path: Set(relative_path),
+				conflict_type: Set(conflict.conflict_type.as_str().to_string()),
+				source_entry_id: Set(Some(conflict.source_entry.id)),
+				source_size: Set(conflict.source_entry.size),
+				source_modified_at: Set(conflict.source_entry.modified_at),
+				target_entry_id: Set(Some(conflict.target_entry.id)),
+				target_size: Set(conflict.target_entry.size),
+				target_modified_at: Set(conflict.target_entry.modified_at),
+				detected_at: Set(now),
+				resolution: Set(None),
+				resolved_at: Set(None),
+				applied_at: Set(None),
+				..Default::default()
+			}
+			.insert(&*self.db)
+			.await?;
+
+			recorded += 1;
+		}
+
+		Ok(recorded)
+	}
+
+	/// Conflicts the user decided that no generation has applied yet
+	pub async fn decided_conflicts(&self, conduit_id: i32) -> Result<Vec<sync_conflict::Model>> {
+		Ok(sync_conflict::Entity::find()
+			.filter(sync_conflict::Column::ConduitId.eq(conduit_id))
+			.filter(sync_conflict::Column::Resolution.is_not_null())
+			.filter(sync_conflict::Column::AppliedAt.is_null())
+			.all(&*self.db)
+			.await?)
+	}
+
+	/// Number of conflicts still waiting for a decision
+	pub async fn count_unresolved_conflicts(&self, conduit_id: i32) -> Result<u64> {
+		Ok(sync_conflict::Entity::find()
+			.filter(sync_conflict::Column::ConduitId.eq(conduit_id))
+			.filter(sync_conflict::Column::Resolution.is_null())
+			.count(&*self.db)
+			.await?)
+	}
+
+	/// List conflicts of a conduit with the generation that detected them, newest first
+	pub async fn list_conflicts(
+		&self,
+		conduit_id: i32,
+		include_resolved: bool,
+	) -> Result<Vec<(sync_conflict::Model, Option<sync_generation::Model>)>> {
+		let mut query = sync_conflict::Entity::find()
+			.filter(sync_conflict::Column::ConduitId.eq(conduit_id))
+			.order_by_desc(sync_conflict::Column::DetectedAt);
+		if !include_resolved {
+			query = query.filter(sync_conflict::Column::Resolution.is_null());
+		}
+
+		Ok(query
+			.find_also_related(sync_generation::Entity)
+			.all(&*self.db)
+			.await?)
+	}
+
+	/// Get conflict by UUID
+	pub async fn get_conflict_by_uuid(&self, uuid: Uuid) -> Result<sync_conflict::Model> {
+		sync_conflict::Entity::find()
+			.filter(sync_conflict::Column::Uuid.eq(uuid))
+			.one(&*self.db)
+			.await?
+			.ok_or_else(|| anyhow::anyhow!("Conflict not found"))
+	}
+
+	/// Record the user's decision, the next generation applies it
+	pub async fn resolve_conflict(
+		&self,
+		conflict_id: i32,
+		resolution: sync_conflict::Resolution,
+	) -> Result<sync_conflict::Model> {
+		let conflict = sync_conflict::Entity::find_by_id(conflict_id)
+			.one(&*self.db)
+			.await?
+			.ok_or_else(|| anyhow::anyhow!("Conflict not found"))?;
+
+		if conflict.applied_at.is_some() {
+			return Err(anyhow::anyhow!("Conflict was already resolved by a sync"));
+		}
+
+		let mut active: sync_conflict::ActiveModel = conflict.into();
+		active.resolution = Set(Some(resolution.as_str().to_string()));
+		active.resolved_at = Set(Some(Utc::now()));
+
+		Ok(active.update(&*self.db).await?)
+	}
+
+	/// Mark decided conflicts as carried out
+	pub async fn mark_conflicts_applied(&self, conflict_ids: Vec<i32>) -> Result<()> {
+		if conflict_ids.is_empty() {
+			return Ok(());
+		}
+
+		sync_conflict::Entity::update_many()
+			.col_expr(
+				sync_conflict::Column::AppliedAt,
+				Expr::value(Some(Utc::now())),
+			)
+			.filter(sync_conflict::Column::Id.is_in(conflict_ids))
+			.exec(&*self.db)
+			.await?;
+
+		Ok(())
+	}
+
+	/// Record how many conflicts a generation resolved
+	pub async fn set_conflicts_resolved(&self, generation_id: i32, count: i32) -> Result<()> {
+		let gen = sync_generation::Entity::find_by_id(generation_id)
+			.one(&*self.db)
+			.await?
+			.ok_or_else(|| anyhow::anyhow!("Generation not found"))?;
+
+		let mut active: sync_generation::ActiveModel = gen.into();
+		active.conflicts_resolved = Set(count);
+		active.update(&*self.db).await?;
+
+		Ok(())
+	}
+
 	/// Get entry by ID (helper)
 	pub async fn get_entry(&self, entry_id: i32) -> Result<entry::Model> {
 		entry::Entity::find_by_id(entry_id)
@@ -242,3 +447,15 @@ impl ConduitManager {
 		Ok(())
 	}
 }
+
+/// Settings changes for [`ConduitManager::update_conduit`]
+#[derive(Debug, Clone, Default)]
+pub struct ConduitUpdate {
+	pub mode: Option<sync_conduit::SyncMode>,
+	pub schedule: Option<String>,
+	pub conflict_strategy: Option<ConflictStrategy>,
+	pub use_index_rules: Option<bool>,
+	pub parallel_transfers: Option<i32>,
+	/// `Some(None)` removes the bandwidth limit
+	pub bandwidth_limit_mbps: Option<Option<i32>>,
+}
@@ -1,11 +1,14 @@
 use super::resolver::{ConflictType, SyncConflict};
-use crate::infra::db::entities::entry;
+use crate::infra::db::entities::{entry, sync_conflict::Resolution};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
 
 pub struct ConflictResolver {
 	strategy: ConflictStrategy,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
 pub enum ConflictStrategy {
 	/// Keep the most recently modified version
 	NewestWins,
@@ -30,6 +33,53 @@ pub enum ConflictResolution {
 	PromptUser(SyncConflict),
 }
 
+impl ConflictStrategy {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::NewestWins => "newest_wins",
+			Self::SourceWins => "source_wins",
+			Self::TargetWins => "target_wins",
+			Self::CreateConflictFile => "create_conflict_file",
+			Self::PromptUser => "prompt_user",
+		}
+	}
+
+	pub fn from_str(s: &str) -> Option<Self> {
+		match s {
+			"newest_wins" => Some(Self::NewestWins),
+			"source_wins" => Some(Self::SourceWins),
+			"target_wins" => Some(Self::TargetWins),
+			"create_conflict_file" => Some(Self::CreateConflictFile),
+			"prompt_user" => Some(Self::PromptUser),
+			_ => None,
+		}
+	}
+}
+
+impl ConflictResolution {
+	/// Carry out a decision the user made for a prompted conflict
+	pub fn from_user(resolution: Resolution, conflict: SyncConflict) -> Self {
+		match resolution {
+			Resolution::KeepSource => Self::UseSource,
+			Resolution::KeepTarget => Self::UseTarget,
+			Resolution::KeepBoth => Self::CreateConflictCopy {
+				original: conflict.target_entry,
+				conflicted: conflict.source_entry,
+			},
+		}
+	}
+}
+
+/// File name for the conflicted version of `name`, e.g. "report (conflict 2026-10-17 1530).pdf"
+pub fn conflict_copy_name(name: &str, extension: Option<&str>, at: DateTime<Utc>) -> String {
+	let stem = format!("{} (conflict {})", name, at.format("%Y-%m-%d %H%M"));
+
+	match extension {
+		Some(extension) if !extension.is_empty() => format!("{}.{}", stem, extension),
+		_ => stem,
+	}
+}
+
 impl ConflictResolver {
 	pub fn new(strategy: ConflictStrategy) -> Self {
 		Self { strategy }
@@ -60,3 +110,40 @@ impl Default for ConflictStrategy {
 		Self::NewestWins
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use chrono::TimeZone;
+
+	#[test]
+	fn strategies_round_trip() {
+		for strategy in [
+			ConflictStrategy::NewestWins,
+			ConflictStrategy::SourceWins,
+			ConflictStrategy::TargetWins,
+			ConflictStrategy::CreateConflictFile,
+			ConflictStrategy::PromptUser,
+		] {
+			assert_eq!(
+				ConflictStrategy::from_str(strategy.as_str()),
+				Some(strategy)
+			);
+		}
+		assert_eq!(ConflictStrategy::from_str("ask"), None);
+	}
+
+	#[test]
+	fn conflict_copy_names() {
+		let at = Utc.with_ymd_and_hms(2026, 10, 17, 15, 30, 12).unwrap();
+
+		assert_eq!(
+			conflict_copy_name("report", Some("pdf"), at),
+			"report (conflict 2026-10-17 1530).pdf"
+		);
+		assert_eq!(
+			conflict_copy_name("Makefile", None, at),
+			"Makefile (conflict 2026-10-17 1530)"
+		);
+	}
+}
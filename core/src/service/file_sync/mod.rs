@@ -2,17 +2,23 @@ use crate::{
 	context::CoreContext,
 	domain::addressing::{SdPath, SdPathBatch},
 	infra::{
-		db::entities::{sync_conduit, sync_generation},
+		db::entities::{entry, sync_conduit, sync_conflict, sync_generation},
+		event::Event,
 		job::types::JobId,
 	},
 	library::Library,
-	ops::files::{
-		copy::{job::CopyOptions, job::FileCopyJob},
-		delete::{job::DeleteJob, job::DeleteMode},
+	ops::{
+		files::{
+			copy::{job::CopyOptions, job::FileCopyJob},
+			delete::{job::DeleteJob, job::DeleteMode},
+		},
+		indexing::PathResolver,
 	},
 };
 use anyhow::Result;
+use sea_orm::EntityTrait;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{debug, error, info, warn};
@@ -23,7 +29,10 @@ pub mod conflict;
 pub mod resolver;
 
 use conduit::ConduitManager;
-use resolver::{DirectionalOps, SyncResolver};
+use conflict::{conflict_copy_name, ConflictResolution, ConflictResolver, ConflictStrategy};
+use resolver::{
+	ConflictType, DirectionalOps, EntryWithPath, SyncConflict, SyncOperations, SyncResolver,
+};
 
 /// File sync orchestration service
 pub struct FileSyncService {
@@ -87,12 +96,16 @@ impl FileSyncService {
 
 		// Calculate sync operations
 		info!("Calculating sync operations for conduit {}", conduit_id);
-		let operations = self.resolver.calculate_operations(&conduit).await?;
+		let mut operations = self.resolver.calculate_operations(&conduit).await?;
 
 		let mode = sync_conduit::SyncMode::from_str(&conduit.sync_mode)
 			.ok_or_else(|| anyhow::anyhow!("Invalid sync mode"))?;
 
-		let copy_count = operations.source_to_target.to_copy.len()
+		// Fold conflicts into the plan, everything else syncs regardless
+		let conflicts = self.plan_conflicts(&conduit, &mut operations).await?;
+
+		let copy_count = conflicts.copies.len()
+			+ operations.source_to_target.to_copy.len()
 			+ operations
 				.target_to_source
 				.as_ref()
@@ -106,12 +119,19 @@ impl FileSyncService {
 				.unwrap_or(0);
 
 		info!(
-			"Sync plan for {:?} mode: {} to copy, {} to delete",
-			mode, copy_count, delete_count
+			"Sync plan for {:?} mode: {} to copy, {} to delete, {} conflicts for the user",
+			mode,
+			copy_count,
+			delete_count,
+			conflicts.prompts.len()
 		);
 
 		// If there's nothing to sync, mark as complete immediately
-		if copy_count == 0 && delete_count == 0 {
+		if copy_count == 0
+			&& delete_count == 0
+			&& conflicts.prompts.is_empty()
+			&& conflicts.applied.is_empty()
+		{
 			info!("No changes to sync for conduit {}", conduit_id);
 			self.conduit_manager.update_after_sync(conduit_id).await?;
 			return Ok(SyncHandle {
@@ -122,6 +142,7 @@ impl FileSyncService {
 					delete_job_id: None,
 				},
 				target_to_source: None,
+				conflict_copy_job_ids: Vec::new(),
 			});
 		}
 
@@ -146,6 +167,11 @@ impl FileSyncService {
 			None
 		};
 
+		let conflict_copy_job_ids = self.dispatch_conflict_copies(&conflicts.copies).await?;
+
+		self.record_conflict_outcome(&conduit, &generation, conflicts)
+			.await?;
+
 		// Track active sync
 		let sync_op = SyncOperation {
 			conduit_id,
@@ -171,9 +197,187 @@ impl FileSyncService {
 			generation: generation.generation,
 			source_to_target,
 			target_to_source,
+			conflict_copy_job_ids,
 		})
 	}
 
+	/// Resolve detected conflicts with the conduit's strategy and apply decisions the
+	/// user made since the last generation
+	///
+	/// Decisions take precedence over a newly detected conflict on the same path.
+	async fn plan_conflicts(
+		&self,
+		conduit: &sync_conduit::Model,
+		operations: &mut SyncOperations,
+	) -> Result<ConflictPlan> {
+		let mut plan = ConflictPlan::default();
+		let mut resolutions = Vec::new();
+
+		for decision in self.conduit_manager.decided_conflicts(conduit.id).await? {
+			plan.applied.push(decision.id);
+
+			let resolution = decision
+				.resolution
+				.as_deref()
+				.and_then(sync_conflict::Resolution::from_str);
+			let (Some(resolution), Some(source_entry), Some(target_entry)) = (
+				resolution,
+				self.find_entry(decision.source_entry_id).await?,
+				self.find_entry(decision.target_entry_id).await?,
+			) else {
+				warn!(
+					"Dropping decision for conflict on {}, a side is no longer indexed",
+					decision.relative_path
+				);
+				continue;
+			};
+
+			let relative_path = PathBuf::from(&decision.relative_path);
+			operations
+				.conflicts
+				.retain(|conflict| conflict.relative_path != relative_path);
+
+			let conflict = SyncConflict {
+				relative_path,
+				source_entry,
+				target_entry,
+				conflict_type: ConflictType::from_str(&decision.conflict_type)
+					.unwrap_or(ConflictType::BothModified),
+			};
+			resolutions.push((
+				conflict.clone(),
+				ConflictResolution::from_user(resolution, conflict),
+			));
+		}
+
+		let strategy = ConflictStrategy::from_str(&conduit.conflict_strategy).unwrap_or_default();
+		let resolver = ConflictResolver::new(strategy);
+		for conflict in std::mem::take(&mut operations.conflicts) {
+			resolutions.push((conflict.clone(), resolver.resolve(conflict)));
+		}
+
+		for (conflict, resolution) in resolutions {
+			match resolution {
+				ConflictResolution::UseSource => {
+					operations.source_to_target.to_copy.push(EntryWithPath {
+						relative_path: conflict.relative_path.clone(),
+						full_path: conflict.relative_path,
+						entry: conflict.source_entry,
+					});
+				}
+				ConflictResolution::UseTarget => {
+					operations
+						.target_to_source
+						.get_or_insert_with(DirectionalOps::default)
+						.to_copy
+						.push(EntryWithPath {
+							relative_path: conflict.relative_path.clone(),
+							full_path: conflict.relative_path,
+							entry: conflict.target_entry,
+						});
+				}
+				ConflictResolution::CreateConflictCopy {
+					original,
+					conflicted,
+				} => plan.copies.push((original, conflicted)),
+				ConflictResolution::PromptUser(conflict) => {
+					plan.prompts.push(conflict);
+					continue;
+				}
+			}
+
+			plan.resolved += 1;
+		}
+
+		Ok(plan)
+	}
+
+	async fn find_entry(&self, entry_id: Option<i32>) -> Result<Option<entry::Model>> {
+		let Some(entry_id) = entry_id else {
+			return Ok(None);
+		};
+
+		Ok(entry::Entity::find_by_id(entry_id)
+			.one(self.library.db().conn())
+			.await?)
+	}
+
+	/// Copy each conflicted version next to the version it lost against
+	async fn dispatch_conflict_copies(
+		&self,
+		copies: &[(entry::Model, entry::Model)],
+	) -> Result<Vec<JobId>> {
+		let db = self.library.db().conn();
+		let device_slug = crate::device::get_current_device_slug();
+		let now = chrono::Utc::now();
+		let mut job_ids = Vec::with_capacity(copies.len());
+
+		for (original, conflicted) in copies {
+			let source = PathResolver::get_full_path(db, conflicted.id).await?;
+			let original_path = PathResolver::get_full_path(db, original.id).await?;
+			let destination = original_path.with_file_name(conflict_copy_name(
+				&original.name,
+				original.extension.as_deref(),
+				now,
+			));
+
+			info!(
+				"Saving conflicted version of {} as {}",
+				original_path.display(),
+				destination.display()
+			);
+
+			let job = FileCopyJob::new(
+				SdPathBatch::new(vec![SdPath::physical(device_slug.clone(), source)]),
+				SdPath::physical(device_slug.clone(), destination),
+			);
+			let handle = self.library.jobs().dispatch(job).await?;
+			job_ids.push(handle.id());
+		}
+
+		Ok(job_ids)
+	}
+
+	/// Persist conflicts left for the user and mark applied decisions
+	async fn record_conflict_outcome(
+		&self,
+		conduit: &sync_conduit::Model,
+		generation: &sync_generation::Model,
+		plan: ConflictPlan,
+	) -> Result<()> {
+		let manager = &self.conduit_manager;
+
+		manager.mark_conflicts_applied(plan.applied).await?;
+		manager
+			.set_conflicts_resolved(generation.id, plan.resolved)
+			.await?;
+
+		let detected = manager
+			.record_conflicts(conduit.id, generation.id, &plan.prompts)
+			.await?;
+		if detected == 0 {
+			return Ok(());
+		}
+
+		let pending = manager.count_unresolved_conflicts(conduit.id).await?;
+		info!(
+			"{} new conflicts on conduit {} wait for a decision ({} pending)",
+			detected, conduit.id, pending
+		);
+
+		self.library
+			.event_bus()
+			.emit(Event::FileSyncConflictsDetected {
+				library_id: self.library.id(),
+				conduit_id: conduit.uuid,
+				generation: generation.generation,
+				detected,
+				pending: pending as usize,
+			});
+
+		Ok(())
+	}
+
 	/// Dispatch copy and delete jobs for a single direction
 	async fn dispatch_job_batch(
 		&self,
@@ -340,4 +544,19 @@ pub struct SyncHandle {
 	pub generation: i64,
 	pub source_to_target: JobBatch,
 	pub target_to_source: Option<JobBatch>,
+	/// Jobs saving conflicted versions next to the winning ones
+	pub conflict_copy_job_ids: Vec<JobId>,
+}
+
+/// How the conflicts of one generation are handled
+#[derive(Default)]
+struct ConflictPlan {
+	/// (original, conflicted) pairs where both versions are kept
+	copies: Vec<(entry::Model, entry::Model)>,
+	/// Conflicts left for the user to decide
+	prompts: Vec<SyncConflict>,
+	/// Recorded decisions this generation carries out
+	applied: Vec<i32>,
+	/// Conflicts resolved by the strategy or a decision
+	resolved: i32,
 }
@@ -4,6 +4,8 @@ use crate::{
 };
 use anyhow::Result;
 use sea_orm::{prelude::*, DatabaseConnection, QueryOrder, QuerySelect};
+use serde::{Deserialize, Serialize};
+use specta::Type;
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -48,7 +50,7 @@ pub struct SyncOperations {
 	pub conflicts: Vec<SyncConflict>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct SyncConflict {
 	pub relative_path: PathBuf,
 	pub source_entry: entry::Model,
@@ -56,13 +58,32 @@ pub struct SyncConflict {
 	pub conflict_type: ConflictType,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
 pub enum ConflictType {
 	BothModified,
 	DeletedVsModified,
 	TypeMismatch,
 }
 
+impl ConflictType {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			Self::BothModified => "both_modified",
+			Self::DeletedVsModified => "deleted_vs_modified",
+			Self::TypeMismatch => "type_mismatch",
+		}
+	}
+
+	pub fn from_str(s: &str) -> Option<Self> {
+		match s {
+			"both_modified" => Some(Self::BothModified),
+			"deleted_vs_modified" => Some(Self::DeletedVsModified),
+			"type_mismatch" => Some(Self::TypeMismatch),
+			_ => None,
+		}
+	}
+}
+
 impl SyncResolver {
 	pub fn new(db: Arc<DatabaseConnection>) -> Self {
 		Self { db }
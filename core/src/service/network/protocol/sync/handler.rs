@@ -566,6 +566,78 @@ impl SyncProtocolHandler {
 				Ok(None)
 			}
 
+			SyncMessage::DigestRequest {
+				library_id,
+				model_type,
+				level,
+			} => {
+				debug!(
+					from_device = %from_device,
+					model_type = %model_type,
+					level = ?level,
+					"Processing digest request"
+				);
+
+				let device_id = peer_sync.device_id();
+				let digests = crate::service::sync::anti_entropy::compute_digests(
+					peer_sync,
+					&model_type,
+					device_id,
+					&level,
+				)
+				.await
+				.map_err(|e| {
+					NetworkingError::Protocol(format!("Failed to compute digests: {}", e))
+				})?;
+
+				Ok(Some(SyncMessage::DigestResponse {
+					library_id,
+					model_type,
+					device_id,
+					digests,
+				}))
+			}
+
+			SyncMessage::DigestResponse { .. } => {
+				// Delivered to the verifier waiting for it, like EventLogResponse
+				Ok(None)
+			}
+
+			SyncMessage::RecordRepairRequest {
+				library_id,
+				model_type,
+				uuids,
+			} => {
+				debug!(
+					from_device = %from_device,
+					model_type = %model_type,
+					count = uuids.len(),
+					"Processing record repair request"
+				);
+
+				let device_id = peer_sync.device_id();
+				let records = crate::service::sync::anti_entropy::repair_records(
+					peer_sync,
+					&model_type,
+					device_id,
+					&uuids,
+				)
+				.await
+				.map_err(|e| {
+					NetworkingError::Protocol(format!("Failed to query repair records: {}", e))
+				})?;
+
+				Ok(Some(SyncMessage::StateResponse {
+					library_id,
+					model_type,
+					device_id,
+					records,
+					deleted_uuids: vec![],
+					checkpoint: None,
+					has_more: false,
+				}))
+			}
+
 			SyncMessage::Error {
 				library_id,
 				message,
@@ -4,7 +4,10 @@
 //! - State-based messages for device-owned data
 //! - Log-based messages with HLC for shared resources
 
-use crate::infra::sync::{SharedChangeEntry, HLC};
+use crate::{
+	infra::sync::{SharedChangeEntry, HLC},
+	service::sync::anti_entropy::{Digest, DigestLevel},
+};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -134,6 +137,30 @@ pub enum SyncMessage {
 		events: Vec<serde_json::Value>, // Serialized SyncEventLog
 	},
 
+	// === VERIFICATION ===
+	/// Request digests of the records of a model the receiver owns, or of every record
+	/// of a shared model
+	DigestRequest {
+		library_id: Uuid,
+		model_type: String,
+		level: DigestLevel,
+	},
+
+	/// Digests of the responding device's records
+	DigestResponse {
+		library_id: Uuid,
+		model_type: String,
+		device_id: Uuid, // Responding device, the records' owner for device-owned models
+		digests: Vec<Digest>,
+	},
+
+	/// Request records by UUID, as for digests, answered with a StateResponse
+	RecordRepairRequest {
+		library_id: Uuid,
+		model_type: String,
+		uuids: Vec<Uuid>,
+	},
+
 	/// Error response
 	Error { library_id: Uuid, message: String },
 }
@@ -144,6 +171,9 @@ pub struct StateRecord {
 	pub uuid: Uuid,
 	pub data: serde_json::Value,
 	pub timestamp: DateTime<Utc>,
+	/// Latest logged change of a shared record, sent with verification repairs
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub hlc: Option<HLC>,
 }
 
 impl SyncMessage {
@@ -164,6 +194,9 @@ impl SyncMessage {
 			| SyncMessage::WatermarkExchangeResponse { library_id, .. }
 			| SyncMessage::EventLogRequest { library_id, .. }
 			| SyncMessage::EventLogResponse { library_id, .. }
+			| SyncMessage::DigestRequest { library_id, .. }
+			| SyncMessage::DigestResponse { library_id, .. }
+			| SyncMessage::RecordRepairRequest { library_id, .. }
 			| SyncMessage::Error { library_id, .. } => *library_id,
 		}
 	}
@@ -176,6 +209,8 @@ impl SyncMessage {
 				| SyncMessage::SharedChangeRequest { .. }
 				| SyncMessage::Heartbeat { .. }
 				| SyncMessage::WatermarkExchangeRequest { .. }
+				| SyncMessage::DigestRequest { .. }
+				| SyncMessage::RecordRepairRequest { .. }
 		)
 	}
 
@@ -684,6 +684,52 @@ impl SidecarManager {
 		Ok(())
 	}
 
+	/// Delete a sidecar's file and its record, returns the bytes freed
+	///
+	/// Reference sidecars point at the user's own files, only their record is removed.
+	pub async fn delete_sidecar(&self, library: &Library, sidecar: &sidecar::Model) -> Result<u64> {
+		let kind: SidecarKind = sidecar
+			.kind
+			.as_str()
+			.try_into()
+			.map_err(|e: String| anyhow::anyhow!(e))?;
+
+		let mut freed = 0;
+		if sidecar.source_entry_id.is_none() && !sidecar.rel_path.is_empty() {
+			let sidecars_dir = self.get_path_builder(&library.id()).await?.sidecars_dir();
+			let path = sidecars_dir.join(&sidecar.rel_path);
+
+			match tokio::fs::metadata(&path).await {
+				Ok(metadata) => {
+					tokio::fs::remove_file(&path).await?;
+					freed = metadata.len();
+				}
+				Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+				Err(e) => return Err(e.into()),
+			}
+
+			// Drop the kind and content directories once they're empty
+			let content_dir = sidecars_dir.join("content");
+			let mut dir = path.parent();
+			while let Some(current) = dir {
+				if current == content_dir || tokio::fs::remove_dir(current).await.is_err() {
+					break;
+				}
+				dir = current.parent();
+			}
+		}
+
+		self.remove_sidecar(
+			library,
+			&sidecar.content_uuid,
+			&kind,
+			&SidecarVariant::new(&sidecar.variant),
+		)
+		.await?;
+
+		Ok(freed)
+	}
+
 	/// Bootstrap scan sidecars directory and sync with database
 	pub async fn bootstrap_scan(&self, library: &Library) -> Result<()> {
 		info!("Starting bootstrap scan for library {}", library.id());
@@ -1,8 +1,16 @@
 //! Per-library background listener that recalculates statistics while ResourceEvents flow
+//!
+//! Saved search result counts and smart tag membership are refreshed on the same
+//! schedule. Smart tags with age predicates are also re-evaluated while the library
+//! is idle, since their membership changes with time alone.
 
 use crate::{
 	infra::event::{Event, EventBus, EventSubscriber},
 	library::Library,
+	ops::{
+		search::saved::SavedSearchCounts,
+		tags::smart::{SmartTagRefresh, AGE_REFRESH_INTERVAL},
+	},
 };
 use std::sync::Arc;
 use std::time::Duration;
@@ -42,55 +50,34 @@ pub fn spawn_statistics_listener(
 		);
 
 		let mut subscriber = event_bus.subscribe();
+		let mut saved_search_counts = SavedSearchCounts::default();
+		let mut smart_tags = SmartTagRefresh::default();
 
 		// Wait for first ResourceChanged event to start
-		loop {
-			match subscriber.recv().await {
-				Ok(event) => {
-					// Check if this library was closed
-					if is_library_closed_event(&event, library_id) {
-						info!(
-							library_id = %library_id,
-							library_name = %library_name,
-							"Library closed, statistics listener shutting down"
-						);
-						return;
-					}
-
-					if is_resource_changed_event(&event) {
-						debug!(
-							library_id = %library_id,
-							library_name = %library_name,
-							"First ResourceChanged event detected, starting active recalculation mode"
-						);
-						break;
-					}
-				}
-				Err(RecvError::Lagged(skipped)) => {
-					warn!(
-						library_id = %library_id,
-						library_name = %library_name,
-						skipped = skipped,
-						"Event subscriber lagged, some events were skipped"
-					);
-					// Continue listening
-				}
-				Err(RecvError::Closed) => {
-					info!(
-						library_id = %library_id,
-						library_name = %library_name,
-						"Event bus closed, statistics listener shutting down"
-					);
-					return;
-				}
-			}
+		if !wait_for_resource_change(
+			&library,
+			&mut subscriber,
+			&mut saved_search_counts,
+			&mut smart_tags,
+			library_id,
+			&library_name,
+		)
+		.await
+		{
+			return;
 		}
 
 		// Main loop: active recalculation while events are flowing
 		loop {
-			if let Err(e) =
-				run_active_recalculation_cycle(&library, &mut subscriber, library_id, &library_name)
-					.await
+			if let Err(e) = run_active_recalculation_cycle(
+				&library,
+				&mut subscriber,
+				&mut saved_search_counts,
+				&mut smart_tags,
+				library_id,
+				&library_name,
+			)
+			.await
 			{
 				error!(
 					library_id = %library_id,
@@ -107,45 +94,17 @@ pub fn spawn_statistics_listener(
 				"Active recalculation cycle ended, waiting for next ResourceChanged event"
 			);
 
-			loop {
-				match subscriber.recv().await {
-					Ok(event) => {
-						// Check if this library was closed
-						if is_library_closed_event(&event, library_id) {
-							info!(
-								library_id = %library_id,
-								library_name = %library_name,
-								"Library closed, statistics listener shutting down"
-							);
-							return;
-						}
-
-						if is_resource_changed_event(&event) {
-							debug!(
-								library_id = %library_id,
-								library_name = %library_name,
-								"New ResourceChanged event detected, restarting active recalculation"
-							);
-							break; // Restart active recalculation
-						}
-					}
-					Err(RecvError::Lagged(skipped)) => {
-						warn!(
-							library_id = %library_id,
-							library_name = %library_name,
-							skipped = skipped,
-							"Event subscriber lagged during idle wait"
-						);
-					}
-					Err(RecvError::Closed) => {
-						info!(
-							library_id = %library_id,
-							library_name = %library_name,
-							"Event bus closed, statistics listener shutting down"
-						);
-						return;
-					}
-				}
+			if !wait_for_resource_change(
+				&library,
+				&mut subscriber,
+				&mut saved_search_counts,
+				&mut smart_tags,
+				library_id,
+				&library_name,
+			)
+			.await
+			{
+				return;
 			}
 		}
 	})
@@ -158,6 +117,8 @@ pub fn spawn_statistics_listener(
 async fn run_active_recalculation_cycle(
 	library: &Arc<Library>,
 	subscriber: &mut EventSubscriber,
+	saved_search_counts: &mut SavedSearchCounts,
+	smart_tags: &mut SmartTagRefresh,
 	library_id: uuid::Uuid,
 	library_name: &str,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
@@ -179,6 +140,8 @@ async fn run_active_recalculation_cycle(
 			"Triggered statistics recalculation at start of active cycle"
 		);
 	}
+	refresh_saved_search_counts(library, saved_search_counts, library_id, library_name).await;
+	refresh_smart_tags(library, smart_tags, library_id, library_name).await;
 
 	let mut last_event_time = tokio::time::Instant::now();
 	let mut event_count = 0u64;
@@ -217,6 +180,8 @@ async fn run_active_recalculation_cycle(
 						"Triggered periodic statistics recalculation"
 					);
 				}
+				refresh_saved_search_counts(library, saved_search_counts, library_id, library_name).await;
+				refresh_smart_tags(library, smart_tags, library_id, library_name).await;
 			}
 
 			// Listen for events
@@ -234,6 +199,8 @@ async fn run_active_recalculation_cycle(
 						}
 
 						if is_resource_changed_event(&event) {
+							saved_search_counts.observe(&event);
+							smart_tags.observe(&event);
 							last_event_time = tokio::time::Instant::now();
 							event_count += 1;
 
@@ -274,11 +241,123 @@ async fn run_active_recalculation_cycle(
 	Ok(())
 }
 
-/// Check if an event is a ResourceChanged event
+/// Wait for the next ResourceChanged event, keeping age based smart tags current
+///
+/// Returns false when the library closed or the event bus shut down.
+async fn wait_for_resource_change(
+	library: &Library,
+	subscriber: &mut EventSubscriber,
+	saved_search_counts: &mut SavedSearchCounts,
+	smart_tags: &mut SmartTagRefresh,
+	library_id: uuid::Uuid,
+	library_name: &str,
+) -> bool {
+	let mut age_interval = interval(AGE_REFRESH_INTERVAL);
+	age_interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+	loop {
+		tokio::select! {
+			_ = age_interval.tick() => {
+				refresh_smart_tags(library, smart_tags, library_id, library_name).await;
+			}
+
+			result = subscriber.recv() => {
+				match result {
+					Ok(event) => {
+						// Check if this library was closed
+						if is_library_closed_event(&event, library_id) {
+							info!(
+								library_id = %library_id,
+								library_name = %library_name,
+								"Library closed, statistics listener shutting down"
+							);
+							return false;
+						}
+
+						if is_resource_changed_event(&event) {
+							saved_search_counts.observe(&event);
+							smart_tags.observe(&event);
+							debug!(
+								library_id = %library_id,
+								library_name = %library_name,
+								"ResourceChanged event detected, starting active recalculation"
+							);
+							return true;
+						}
+					}
+					Err(RecvError::Lagged(skipped)) => {
+						warn!(
+							library_id = %library_id,
+							library_name = %library_name,
+							skipped = skipped,
+							"Event subscriber lagged while idle"
+						);
+					}
+					Err(RecvError::Closed) => {
+						info!(
+							library_id = %library_id,
+							library_name = %library_name,
+							"Event bus closed, statistics listener shutting down"
+						);
+						return false;
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Re-evaluate smart tags for the files changed since the last refresh
+async fn refresh_smart_tags(
+	library: &Library,
+	smart_tags: &mut SmartTagRefresh,
+	library_id: uuid::Uuid,
+	library_name: &str,
+) {
+	match smart_tags
+		.refresh(Arc::new(library.db().conn().clone()))
+		.await
+	{
+		Ok(changed) if changed > 0 => debug!(
+			library_id = %library_id,
+			library_name = %library_name,
+			changed = changed,
+			"Updated smart tag membership"
+		),
+		Ok(_) => {}
+		Err(e) => warn!(
+			library_id = %library_id,
+			library_name = %library_name,
+			error = %e,
+			"Failed to refresh smart tags"
+		),
+	}
+}
+
+/// Recount saved searches if files changed since the last recount
+async fn refresh_saved_search_counts(
+	library: &Library,
+	saved_search_counts: &mut SavedSearchCounts,
+	library_id: uuid::Uuid,
+	library_name: &str,
+) {
+	if let Err(e) = saved_search_counts.refresh_if_stale(library).await {
+		warn!(
+			library_id = %library_id,
+			library_name = %library_name,
+			error = %e,
+			"Failed to refresh saved search counts"
+		);
+	}
+}
+
+/// Check if an event is a ResourceChanged or ResourceDeleted event
 fn is_resource_changed_event(event: &Event) -> bool {
 	matches!(
 		event,
-		Event::ResourceChanged { .. } | Event::ResourceChangedBatch { .. }
+		Event::ResourceChanged { .. }
+			| Event::ResourceChangedBatch { .. }
+			| Event::ResourceDeleted { .. }
 	)
 }
 
@@ -0,0 +1,883 @@
+//! Anti-entropy verification of synced data
+//!
+//! Watermarks and backfill assume every change that was sent got applied. When a copy
+//! drifts anyway, a crash mid-apply or a bug in an apply function for example, nothing
+//! notices and the devices stay apart. Verification compares digests of the records a
+//! peer owns with that peer and repairs what differs with targeted requests.
+//!
+//! Digests form a three level tree per model: a root, 256 buckets and the records in
+//! them. Only the level below differing digests is requested, so verifying a model that
+//! matches costs a single digest each way. Records are bucketed by the last byte of their
+//! UUID rather than by time, as `updated_at` is set locally when a change is applied and
+//! differs between devices. Bucket and root digests XOR the record digests together,
+//! which makes them independent of the order records are read in.
+//!
+//! The owner of a record is authoritative: records it has that differ or are missing
+//! here are fetched and applied, records it doesn't have are deleted.
+//!
+//! Shared resources have no owner. Their digests cover every record and leave out
+//! `updated_at`, which applying a change sets locally. Differing records are fetched with
+//! the HLC of their latest logged change and applied when it is newer than the one logged
+//! here, the peer repairs the others when it verifies against this device. Shared records
+//! the peer doesn't have are kept, a deletion can't be told apart from a missed insert.
+
+use super::{state::StateChangeMessage, PeerSync, SyncMetricsCollector};
+use crate::{
+	infra::sync::{
+		compute_registry_sync_order, is_device_owned,
+		registry::{apply_deletion, query_device_state, query_shared_state},
+		ChangeType, EventSeverity, SharedChangeEntry, SyncConfig, SyncEventLog, SyncEventType,
+	},
+	service::network::protocol::sync::messages::{StateRecord, SyncMessage},
+};
+use anyhow::{anyhow, bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use specta::Type;
+use std::{
+	collections::{HashMap, HashSet},
+	sync::{
+		atomic::{AtomicBool, Ordering},
+		Arc,
+	},
+	time::Instant,
+};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Buckets the records of a model are spread over
+const BUCKET_COUNT: usize = 256;
+
+/// Records read per query when computing digests
+const SCAN_BATCH_SIZE: usize = 1_000;
+
+/// Records fetched per repair request
+const REPAIR_BATCH_SIZE: usize = 500;
+
+/// Records read from a shared model, in one query as not every model pages by cursor
+const SHARED_SCAN_LIMIT: usize = 100_000;
+
+/// Fields of shared records left out of digests, applying a change sets them locally
+const LOCAL_SHARED_FIELDS: &[&str] = &["updated_at"];
+
+/// Digests requested from a peer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DigestLevel {
+	/// One digest covering every record
+	Root,
+	/// A digest per non-empty bucket
+	Buckets,
+	/// A digest per record in the given buckets
+	Records { buckets: Vec<u8> },
+}
+
+/// What a digest covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum DigestKey {
+	Root,
+	Bucket(u8),
+	Record(Uuid),
+}
+
+/// Digest of a set of records
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Digest {
+	pub key: DigestKey,
+	/// Records covered
+	pub count: u64,
+	/// Hex encoded XOR of the record hashes
+	pub hash: String,
+}
+
+/// Accumulated digest, before encoding
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct SetDigest {
+	count: u64,
+	hash: [u8; 32],
+}
+
+impl SetDigest {
+	fn add(&mut self, other: &SetDigest) {
+		self.count += other.count;
+		for (byte, other) in self.hash.iter_mut().zip(other.hash) {
+			*byte ^= other;
+		}
+	}
+
+	fn into_digest(self, key: DigestKey) -> Digest {
+		Digest {
+			key,
+			count: self.count,
+			hash: hex::encode(self.hash),
+		}
+	}
+}
+
+/// Bucket a record falls in
+fn bucket_of(uuid: Uuid) -> u8 {
+	uuid.as_bytes()[15]
+}
+
+/// Hash of a record's UUID and synced data
+fn record_digest(uuid: Uuid, data: &Value) -> SetDigest {
+	let mut hasher = blake3::Hasher::new();
+	hasher.update(uuid.as_bytes());
+	hash_value(&mut hasher, data);
+
+	SetDigest {
+		count: 1,
+		hash: *hasher.finalize().as_bytes(),
+	}
+}
+
+/// Record data without the fields that differ between devices by design
+fn digest_data(mut data: Value, local_fields: &[&str]) -> Value {
+	if let Value::Object(map) = &mut data {
+		for field in local_fields {
+			map.remove(*field);
+		}
+	}
+	data
+}
+
+/// Hash JSON with object keys sorted, so field order doesn't change the hash
+fn hash_value(hasher: &mut blake3::Hasher, value: &Value) {
+	match value {
+		Value::Object(map) => {
+			let mut keys = map.keys().collect::<Vec<_>>();
+			keys.sort();
+
+			hasher.update(b"{");
+			for key in keys {
+				hasher.update(Value::String(key.clone()).to_string().as_bytes());
+				hasher.update(b":");
+				hash_value(hasher, &map[key]);
+			}
+			hasher.update(b"}");
+		}
+		Value::Array(items) => {
+			hasher.update(b"[");
+			for item in items {
+				hash_value(hasher, item);
+			}
+			hasher.update(b"]");
+		}
+		scalar => {
+			hasher.update(scalar.to_string().as_bytes());
+			hasher.update(b",");
+		}
+	}
+}
+
+/// Read every record of a model owned by a device, or of a shared model
+///
+/// Errors are returned rather than skipped: a partial scan would look like missing
+/// records and get them deleted.
+async fn scan_records(
+	peer_sync: &PeerSync,
+	model_type: &str,
+	owner: Uuid,
+	mut f: impl FnMut(Uuid, Value, DateTime<Utc>),
+) -> Result<()> {
+	if !is_device_owned(model_type).await {
+		let records = query_shared_state(model_type, SHARED_SCAN_LIMIT, peer_sync.db().clone())
+			.await
+			.map_err(|e| anyhow!("Failed to read {} records: {}", model_type, e))?;
+		for (uuid, data, timestamp) in records {
+			f(uuid, data, timestamp);
+		}
+		return Ok(());
+	}
+
+	let mut cursor = None;
+	loop {
+		let records = query_device_state(
+			model_type,
+			Some(owner),
+			None,
+			cursor,
+			SCAN_BATCH_SIZE,
+			peer_sync.db().clone(),
+		)
+		.await
+		.map_err(|e| anyhow!("Failed to read {} records: {}", model_type, e))?;
+
+		let has_more = records.len() >= SCAN_BATCH_SIZE;
+		if let Some((uuid, _, timestamp)) = records.last() {
+			cursor = Some((*timestamp, *uuid));
+		}
+		for (uuid, data, timestamp) in records {
+			f(uuid, data, timestamp);
+		}
+
+		if !has_more {
+			return Ok(());
+		}
+	}
+}
+
+/// Digests of the records of a model owned by a device, or of a shared model
+pub async fn compute_digests(
+	peer_sync: &PeerSync,
+	model_type: &str,
+	owner: Uuid,
+	level: &DigestLevel,
+) -> Result<Vec<Digest>> {
+	let local_fields = if is_device_owned(model_type).await {
+		&[][..]
+	} else {
+		LOCAL_SHARED_FIELDS
+	};
+
+	match level {
+		DigestLevel::Root | DigestLevel::Buckets => {
+			let mut buckets = [SetDigest::default(); BUCKET_COUNT];
+			scan_records(peer_sync, model_type, owner, |uuid, data, _| {
+				buckets[bucket_of(uuid) as usize]
+					.add(&record_digest(uuid, &digest_data(data, local_fields)));
+			})
+			.await?;
+
+			if *level == DigestLevel::Root {
+				let mut root = SetDigest::default();
+				for bucket in &buckets {
+					root.add(bucket);
+				}
+				return Ok(vec![root.into_digest(DigestKey::Root)]);
+			}
+
+			Ok(buckets
+				.into_iter()
+				.enumerate()
+				.filter(|(_, bucket)| bucket.count > 0)
+				.map(|(index, bucket)| bucket.into_digest(DigestKey::Bucket(index as u8)))
+				.collect())
+		}
+		DigestLevel::Records { buckets } => {
+			let buckets = buckets.iter().copied().collect::<HashSet<_>>();
+			let mut digests = Vec::new();
+			scan_records(peer_sync, model_type, owner, |uuid, data, _| {
+				if buckets.contains(&bucket_of(uuid)) {
+					digests.push(
+						record_digest(uuid, &digest_data(data, local_fields))
+							.into_digest(DigestKey::Record(uuid)),
+					);
+				}
+			})
+			.await?;
+
+			Ok(digests)
+		}
+	}
+}
+
+/// Records of a model owned by a device, or of a shared model, for a peer repairing
+/// its copy
+pub async fn repair_records(
+	peer_sync: &PeerSync,
+	model_type: &str,
+	owner: Uuid,
+	uuids: &[Uuid],
+) -> Result<Vec<StateRecord>> {
+	let wanted = uuids.iter().copied().collect::<HashSet<_>>();
+	let mut records = Vec::new();
+	scan_records(peer_sync, model_type, owner, |uuid, data, timestamp| {
+		if wanted.contains(&uuid) {
+			records.push(StateRecord {
+				uuid,
+				data,
+				timestamp,
+				hlc: None,
+			});
+		}
+	})
+	.await?;
+
+	if !is_device_owned(model_type).await {
+		for record in &mut records {
+			record.hlc = peer_sync
+				.peer_log()
+				.get_latest_hlc_for_record(record.uuid)
+				.await
+				.map_err(|e| anyhow!("Failed to read the shared change log: {}", e))?;
+		}
+	}
+
+	Ok(records)
+}
+
+/// How two sets of digests differ
+#[derive(Debug, Default, PartialEq, Eq)]
+struct DigestDiff {
+	/// In both, with different digests
+	changed: Vec<DigestKey>,
+	/// Only in the remote set
+	missing: Vec<DigestKey>,
+	/// Only in the local set
+	extra: Vec<DigestKey>,
+}
+
+fn diff_digests(local: &[Digest], remote: &[Digest]) -> DigestDiff {
+	let local = local
+		.iter()
+		.map(|digest| (digest.key, digest))
+		.collect::<HashMap<_, _>>();
+	let remote = remote
+		.iter()
+		.map(|digest| (digest.key, digest))
+		.collect::<HashMap<_, _>>();
+
+	let mut diff = DigestDiff::default();
+	for (key, digest) in &remote {
+		match local.get(key) {
+			Some(local) if local == digest => {}
+			Some(_) => diff.changed.push(*key),
+			None => diff.missing.push(*key),
+		}
+	}
+	diff.extra = local
+		.keys()
+		.filter(|key| !remote.contains_key(key))
+		.copied()
+		.collect();
+
+	diff.changed.sort();
+	diff.missing.sort();
+	diff.extra.sort();
+	diff
+}
+
+/// Verification of one model
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ModelVerification {
+	pub model_type: String,
+	/// Records held by this device before repair, those the peer owns for device-owned
+	/// models
+	pub local_records: u64,
+	/// Records the peer holds
+	pub remote_records: u64,
+	/// Records that differ or are missing here
+	pub mismatched: u64,
+	/// Records held here the peer doesn't have, only deleted for device-owned models
+	pub extra: u64,
+	/// Mismatched records fetched and applied
+	pub repaired: u64,
+	/// Extra records deleted
+	pub deleted: u64,
+}
+
+/// Verification of the records a peer owns
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct VerificationReport {
+	pub peer: Uuid,
+	pub started_at: DateTime<Utc>,
+	pub duration_ms: u64,
+	pub models: Vec<ModelVerification>,
+}
+
+impl VerificationReport {
+	/// Records found differing, missing or extra
+	pub fn mismatches(&self) -> u64 {
+		self.models
+			.iter()
+			.map(|model| model.mismatched + model.extra)
+			.sum()
+	}
+
+	/// Records repaired or deleted
+	pub fn repairs(&self) -> u64 {
+		self.models
+			.iter()
+			.map(|model| model.repaired + model.deleted)
+			.sum()
+	}
+}
+
+/// Verifies this device's copy of its peers' data
+pub struct SyncVerifier {
+	peer_sync: Arc<PeerSync>,
+	metrics: Arc<SyncMetricsCollector>,
+	config: Arc<SyncConfig>,
+	/// Held while verifying, so on-demand and periodic runs don't overlap
+	running: Mutex<()>,
+}
+
+impl SyncVerifier {
+	pub fn new(
+		peer_sync: Arc<PeerSync>,
+		metrics: Arc<SyncMetricsCollector>,
+		config: Arc<SyncConfig>,
+	) -> Self {
+		Self {
+			peer_sync,
+			metrics,
+			config,
+			running: Mutex::new(()),
+		}
+	}
+
+	/// Verify against a peer, or every connected sync partner
+	///
+	/// With a single peer, failing to verify is an error. Otherwise peers that fail are
+	/// logged and left out of the reports.
+	pub async fn verify(&self, peer: Option<Uuid>) -> Result<Vec<VerificationReport>> {
+		let _running = self
+			.running
+			.try_lock()
+			.map_err(|_| anyhow!("Verification is already running"))?;
+
+		if !self.peer_sync.state().await.is_ready() {
+			bail!("Sync isn't ready, verification runs once backfill and catch-up are done");
+		}
+
+		if let Some(peer) = peer {
+			return Ok(vec![self.verify_peer(peer).await?]);
+		}
+
+		let partners = self
+			.peer_sync
+			.network()
+			.get_connected_sync_partners(self.peer_sync.library_id(), self.peer_sync.db())
+			.await?;
+
+		let mut reports = Vec::new();
+		for peer in partners {
+			match self.verify_peer(peer).await {
+				Ok(report) => reports.push(report),
+				Err(e) => warn!(peer = %peer, error = %e, "Failed to verify against peer"),
+			}
+		}
+
+		Ok(reports)
+	}
+
+	/// Verify against connected partners at the configured interval
+	pub async fn run_periodic(self: Arc<Self>, is_running: Arc<AtomicBool>) {
+		let interval_secs = self.config.monitoring.verification_interval_secs;
+		if interval_secs == 0 {
+			info!("Periodic sync verification disabled");
+			return;
+		}
+
+		let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(interval_secs));
+		// The first tick is immediate, the service just started and is still catching up
+		interval.tick().await;
+
+		info!(
+			"Starting sync verification task (interval: {}s)",
+			interval_secs
+		);
+
+		loop {
+			interval.tick().await;
+			if !is_running.load(Ordering::SeqCst) {
+				return;
+			}
+
+			if let Err(e) = self.verify(None).await {
+				info!(error = %e, "Skipped periodic sync verification");
+			}
+		}
+	}
+
+	async fn verify_peer(&self, peer: Uuid) -> Result<VerificationReport> {
+		if peer == self.peer_sync.device_id() {
+			bail!("Verification compares this device's copy with another device");
+		}
+
+		let started_at = Utc::now();
+		let start = Instant::now();
+		let mut report = VerificationReport {
+			peer,
+			started_at,
+			duration_ms: 0,
+			models: Vec::new(),
+		};
+
+		let sync_order = compute_registry_sync_order()
+			.await
+			.map_err(|e| anyhow!("Failed to compute sync order: {}", e))?;
+
+		for model_type in sync_order {
+			let model = match self.verify_model(peer, &model_type).await {
+				Ok(model) => model,
+				Err(e) => {
+					self.log_event(
+						SyncEventLog::new(
+							self.peer_sync.device_id(),
+							SyncEventType::SyncError,
+							format!("Verification of {} against peer failed", model_type),
+						)
+						.with_peer(peer)
+						.with_model_types(vec![model_type.clone()])
+						.with_details(json!({ "error": e.to_string() })),
+					)
+					.await;
+					return Err(e);
+				}
+			};
+
+			if model.mismatched + model.extra > 0 {
+				warn!(
+					peer = %peer,
+					model_type = %model_type,
+					mismatched = model.mismatched,
+					extra = model.extra,
+					repaired = model.repaired,
+					deleted = model.deleted,
+					"Verification found records differing from the peer"
+				);
+				self.log_event(
+					SyncEventLog::new(
+						self.peer_sync.device_id(),
+						SyncEventType::VerificationMismatch,
+						format!(
+							"{} {} records differed from the peer",
+							model.mismatched + model.extra,
+							model_type
+						),
+					)
+					.with_peer(peer)
+					.with_model_types(vec![model_type.clone()])
+					.with_record_count(model.mismatched + model.extra)
+					.with_details(json!({
+						"mismatched": model.mismatched,
+						"extra": model.extra,
+						"repaired": model.repaired,
+						"deleted": model.deleted,
+					})),
+				)
+				.await;
+			}
+
+			report.models.push(model);
+		}
+
+		report.duration_ms = start.elapsed().as_millis() as u64;
+		let mismatches = report.mismatches();
+		let repairs = report.repairs();
+		self.metrics.record_verification(mismatches, repairs);
+
+		let mut event = SyncEventLog::new(
+			self.peer_sync.device_id(),
+			SyncEventType::VerificationCompleted,
+			format!(
+				"Verified {} models against peer, {} records differed",
+				report.models.len(),
+				mismatches
+			),
+		)
+		.with_peer(peer)
+		.with_record_count(mismatches)
+		.with_duration_ms(report.duration_ms)
+		.with_details(json!({ "repairs": repairs }));
+		if mismatches > 0 {
+			event = event.with_severity(EventSeverity::Warning);
+		}
+		self.log_event(event).await;
+
+		info!(
+			peer = %peer,
+			models = report.models.len(),
+			mismatches = mismatches,
+			repairs = repairs,
+			duration_ms = report.duration_ms,
+			"Verified sync against peer"
+		);
+
+		Ok(report)
+	}
+
+	async fn verify_model(&self, peer: Uuid, model_type: &str) -> Result<ModelVerification> {
+		// Local digests first: records held here at that point were received from the
+		// peer, so records it creates meanwhile can't be mistaken for ones it deleted
+		let local = compute_digests(&self.peer_sync, model_type, peer, &DigestLevel::Root).await?;
+		let remote = self
+			.request_digests(peer, model_type, DigestLevel::Root)
+			.await?;
+
+		let count = |digests: &[Digest]| digests.first().map_or(0, |digest| digest.count);
+		let mut result = ModelVerification {
+			model_type: model_type.to_string(),
+			local_records: count(&local),
+			remote_records: count(&remote),
+			mismatched: 0,
+			extra: 0,
+			repaired: 0,
+			deleted: 0,
+		};
+		if local == remote {
+			return Ok(result);
+		}
+
+		let local =
+			compute_digests(&self.peer_sync, model_type, peer, &DigestLevel::Buckets).await?;
+		let remote = self
+			.request_digests(peer, model_type, DigestLevel::Buckets)
+			.await?;
+		let diff = diff_digests(&local, &remote);
+		let buckets = [diff.changed, diff.missing, diff.extra]
+			.concat()
+			.into_iter()
+			.filter_map(|key| match key {
+				DigestKey::Bucket(bucket) => Some(bucket),
+				_ => None,
+			})
+			.collect::<Vec<_>>();
+		if buckets.is_empty() {
+			return Ok(result);
+		}
+
+		let level = DigestLevel::Records { buckets };
+		let local = compute_digests(&self.peer_sync, model_type, peer, &level).await?;
+		let remote = self.request_digests(peer, model_type, level).await?;
+		let diff = diff_digests(&local, &remote);
+		let record_uuids = |keys: Vec<DigestKey>| {
+			keys.into_iter()
+				.filter_map(|key| match key {
+					DigestKey::Record(uuid) => Some(uuid),
+					_ => None,
+				})
+				.collect::<Vec<_>>()
+		};
+		let mismatched = record_uuids([diff.changed, diff.missing].concat());
+		let extra = record_uuids(diff.extra);
+		result.mismatched = mismatched.len() as u64;
+		result.extra = extra.len() as u64;
+
+		if !is_device_owned(model_type).await {
+			result.repaired = self.repair_shared(peer, model_type, &mismatched).await?;
+			return Ok(result);
+		}
+
+		for uuids in mismatched.chunks(REPAIR_BATCH_SIZE) {
+			for record in self.request_records(peer, model_type, uuids).await? {
+				let uuid = record.uuid;
+				let applied = self
+					.peer_sync
+					.on_state_change_received(StateChangeMessage {
+						model_type: model_type.to_string(),
+						record_uuid: record.uuid,
+						device_id: peer,
+						data: record.data,
+						timestamp: record.timestamp,
+					})
+					.await;
+				match applied {
+					Ok(()) => result.repaired += 1,
+					Err(e) => {
+						warn!(model_type = %model_type, uuid = %uuid, error = %e, "Failed to repair record")
+					}
+				}
+			}
+		}
+
+		for uuid in extra {
+			match apply_deletion(model_type, uuid, self.peer_sync.db().clone()).await {
+				Ok(()) => result.deleted += 1,
+				Err(e) => {
+					warn!(model_type = %model_type, uuid = %uuid, error = %e, "Failed to delete record")
+				}
+			}
+		}
+
+		Ok(result)
+	}
+
+	/// Apply the peer's copies of shared records whose latest change there is newer
+	///
+	/// Records changed more recently here are left for the peer to repair. Records with
+	/// no logged change on the peer can't be ordered and are left as they are.
+	async fn repair_shared(&self, peer: Uuid, model_type: &str, uuids: &[Uuid]) -> Result<u64> {
+		let mut repaired = 0;
+		for uuids in uuids.chunks(REPAIR_BATCH_SIZE) {
+			for record in self.request_records(peer, model_type, uuids).await? {
+				let Some(hlc) = record.hlc else {
+					continue;
+				};
+				let local_hlc = self
+					.peer_sync
+					.peer_log()
+					.get_latest_hlc_for_record(record.uuid)
+					.await
+					.map_err(|e| anyhow!("Failed to read the shared change log: {}", e))?;
+				if local_hlc.is_some_and(|local_hlc| local_hlc >= hlc) {
+					continue;
+				}
+
+				let uuid = record.uuid;
+				let applied = self
+					.peer_sync
+					.on_shared_change_received(SharedChangeEntry {
+						hlc,
+						model_type: model_type.to_string(),
+						record_uuid: record.uuid,
+						change_type: ChangeType::Update,
+						data: record.data,
+					})
+					.await;
+				match applied {
+					Ok(()) => repaired += 1,
+					Err(e) => {
+						warn!(model_type = %model_type, uuid = %uuid, error = %e, "Failed to repair record")
+					}
+				}
+			}
+		}
+
+		Ok(repaired)
+	}
+
+	async fn request_digests(
+		&self,
+		peer: Uuid,
+		model_type: &str,
+		level: DigestLevel,
+	) -> Result<Vec<Digest>> {
+		let response = self
+			.peer_sync
+			.network()
+			.send_sync_request(
+				peer,
+				SyncMessage::DigestRequest {
+					library_id: self.peer_sync.library_id(),
+					model_type: model_type.to_string(),
+					level,
+				},
+			)
+			.await?;
+
+		match response {
+			SyncMessage::DigestResponse {
+				device_id, digests, ..
+			} if device_id == peer => Ok(digests),
+			SyncMessage::Error { message, .. } => {
+				bail!("Peer failed to compute digests: {}", message)
+			}
+			_ => bail!("Unexpected response to a digest request"),
+		}
+	}
+
+	async fn request_records(
+		&self,
+		peer: Uuid,
+		model_type: &str,
+		uuids: &[Uuid],
+	) -> Result<Vec<StateRecord>> {
+		let response = self
+			.peer_sync
+			.network()
+			.send_sync_request(
+				peer,
+				SyncMessage::RecordRepairRequest {
+					library_id: self.peer_sync.library_id(),
+					model_type: model_type.to_string(),
+					uuids: uuids.to_vec(),
+				},
+			)
+			.await?;
+
+		match response {
+			SyncMessage::StateResponse {
+				device_id, records, ..
+			} if device_id == peer => Ok(records),
+			SyncMessage::Error { message, .. } => bail!("Peer failed to send records: {}", message),
+			_ => bail!("Unexpected response to a record repair request"),
+		}
+	}
+
+	async fn log_event(&self, event: SyncEventLog) {
+		if let Some(event_logger) = self.metrics.event_logger().read().await.as_ref() {
+			let _ = event_logger.log(event).await;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn digest(key: DigestKey, hash: &str) -> Digest {
+		Digest {
+			key,
+			count: 1,
+			hash: hash.to_string(),
+		}
+	}
+
+	#[test]
+	fn record_hash_ignores_field_order() {
+		let uuid = Uuid::new_v4();
+		let a: Value =
+			serde_json::from_str(r#"{"name":"a","meta":{"x":1,"y":[1,2]},"size":3}"#).unwrap();
+		let b: Value =
+			serde_json::from_str(r#"{"size":3,"meta":{"y":[1,2],"x":1},"name":"a"}"#).unwrap();
+		assert_eq!(record_digest(uuid, &a), record_digest(uuid, &b));
+
+		let c = json!({ "name": "a", "meta": { "x": 1, "y": [2, 1] }, "size": 3 });
+		assert_ne!(record_digest(uuid, &a), record_digest(uuid, &c));
+		assert_ne!(record_digest(uuid, &a), record_digest(Uuid::new_v4(), &a));
+	}
+
+	#[test]
+	fn shared_digests_ignore_local_fields() {
+		let uuid = Uuid::new_v4();
+		let here = json!({ "name": "a", "updated_at": "2026-01-01T00:00:00Z" });
+		let there = json!({ "name": "a", "updated_at": "2026-02-01T00:00:00Z" });
+		assert_eq!(
+			record_digest(uuid, &digest_data(here.clone(), LOCAL_SHARED_FIELDS)),
+			record_digest(uuid, &digest_data(there.clone(), LOCAL_SHARED_FIELDS))
+		);
+		assert_ne!(
+			record_digest(uuid, &digest_data(here, &[])),
+			record_digest(uuid, &digest_data(there, &[]))
+		);
+	}
+
+	#[test]
+	fn set_digest_is_order_independent() {
+		let records = (0..3)
+			.map(|i| record_digest(Uuid::new_v4(), &json!({ "i": i })))
+			.collect::<Vec<_>>();
+
+		let mut forward = SetDigest::default();
+		records.iter().for_each(|record| forward.add(record));
+		let mut backward = SetDigest::default();
+		records.iter().rev().for_each(|record| backward.add(record));
+
+		assert_eq!(forward, backward);
+		assert_eq!(forward.count, 3);
+	}
+
+	#[test]
+	fn diffs_digests() {
+		let (a, b, c, d) = (
+			Uuid::new_v4(),
+			Uuid::new_v4(),
+			Uuid::new_v4(),
+			Uuid::new_v4(),
+		);
+		let local = vec![
+			digest(DigestKey::Record(a), "00"),
+			digest(DigestKey::Record(b), "01"),
+			digest(DigestKey::Record(c), "02"),
+		];
+		let remote = vec![
+			digest(DigestKey::Record(a), "00"),
+			digest(DigestKey::Record(b), "ff"),
+			digest(DigestKey::Record(d), "03"),
+		];
+
+		assert_eq!(
+			diff_digests(&local, &remote),
+			DigestDiff {
+				changed: vec![DigestKey::Record(b)],
+				missing: vec![DigestKey::Record(d)],
+				extra: vec![DigestKey::Record(c)],
+			}
+		);
+		assert_eq!(diff_digests(&local, &local), DigestDiff::default());
+	}
+}
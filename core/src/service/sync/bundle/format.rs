@@ -0,0 +1,386 @@
+//! Sync bundle file format
+//!
+//! ```text
+//! magic "SDSYNCB\0" | version (u8) | header length (u32 LE) | header (JSON)
+//! | payload (zstd compressed stream of JSON sync messages) | signature (64 bytes)
+//! ```
+//!
+//! The Ed25519 signature covers the magic, version, header length and header, followed by
+//! the BLAKE3 hash of the payload, so bundles are verified in one streaming pass before any
+//! message is decoded. The header names the public key that signed it, whether that key
+//! belongs to the source device is for the importer to check.
+
+use crate::{infra::sync::HLC, service::network::protocol::sync::messages::SyncMessage};
+use anyhow::{anyhow, bail, Context, Result};
+use chrono::{DateTime, Utc};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::{
+	collections::BTreeMap,
+	fs::{self, File},
+	io::{self, BufReader, BufWriter, Read, Seek, SeekFrom, Take, Write},
+	path::{Path, PathBuf},
+};
+use uuid::Uuid;
+
+/// Extension given to bundle files
+pub const BUNDLE_EXTENSION: &str = "sdsync";
+
+const BUNDLE_MAGIC: &[u8; 8] = b"SDSYNCB\0";
+
+/// Current bundle format version
+const BUNDLE_VERSION: u8 = 1;
+
+/// Largest header accepted, headers only hold IDs and watermarks
+const MAX_HEADER_LEN: u32 = 1024 * 1024;
+
+const SIGNATURE_LEN: u64 = 64;
+
+/// What a bundle carries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum BundleKind {
+	/// Changes the target device hasn't received yet
+	Changes,
+	/// Only watermarks, answering a changes bundle
+	Ack,
+}
+
+/// What a device has received from another, per resource type for device-owned data
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReceivedWatermarks {
+	pub state: BTreeMap<String, DateTime<Utc>>,
+	pub shared: Option<HLC>,
+}
+
+/// Unencrypted, signed header of a bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleHeader {
+	pub bundle_id: Uuid,
+	pub kind: BundleKind,
+	pub library_id: Uuid,
+	pub source_device: Uuid,
+	pub target_device: Uuid,
+	pub created_at: DateTime<Utc>,
+
+	/// Hex encoded Ed25519 public key the bundle is signed with, set when writing
+	#[serde(default)]
+	pub signer: String,
+
+	/// What the source device had received from the target when writing the bundle
+	pub received: ReceivedWatermarks,
+
+	/// The changes bundle an ack answers
+	pub acknowledges: Option<Uuid>,
+}
+
+impl BundleHeader {
+	pub fn new(
+		kind: BundleKind,
+		library_id: Uuid,
+		source_device: Uuid,
+		target_device: Uuid,
+		received: ReceivedWatermarks,
+	) -> Self {
+		Self {
+			bundle_id: Uuid::new_v4(),
+			kind,
+			library_id,
+			source_device,
+			target_device,
+			created_at: Utc::now(),
+			signer: String::new(),
+			received,
+			acknowledges: None,
+		}
+	}
+
+	/// Public key the bundle is signed with
+	pub fn signer_key(&self) -> Result<VerifyingKey> {
+		let bytes: [u8; 32] = hex::decode(&self.signer)
+			.ok()
+			.and_then(|bytes| bytes.try_into().ok())
+			.ok_or_else(|| anyhow!("Sync bundle has an invalid signer key"))?;
+		VerifyingKey::from_bytes(&bytes)
+			.map_err(|e| anyhow!("Sync bundle has an invalid signer key: {}", e))
+	}
+}
+
+/// Writes messages to a bundle, signing it once finished
+///
+/// The bundle is written next to its destination and moved in place by `finish`, an
+/// interrupted export never leaves a truncated bundle behind.
+pub struct BundleWriter {
+	encoder: zstd::Encoder<'static, HashingWriter<BufWriter<File>>>,
+	signed_prefix: Vec<u8>,
+	signing_key: SigningKey,
+	tmp_path: PathBuf,
+	path: PathBuf,
+}
+
+impl BundleWriter {
+	pub fn create(path: &Path, mut header: BundleHeader, signing_key: SigningKey) -> Result<Self> {
+		header.signer = hex::encode(signing_key.verifying_key().as_bytes());
+		let header_bytes = serde_json::to_vec(&header).context("Failed to serialize header")?;
+
+		let mut signed_prefix = Vec::with_capacity(BUNDLE_MAGIC.len() + 5 + header_bytes.len());
+		signed_prefix.extend_from_slice(BUNDLE_MAGIC);
+		signed_prefix.push(BUNDLE_VERSION);
+		signed_prefix.extend_from_slice(&(header_bytes.len() as u32).to_le_bytes());
+		signed_prefix.extend_from_slice(&header_bytes);
+
+		let tmp_path = path.with_extension("tmp");
+		let mut file = BufWriter::new(
+			File::create(&tmp_path)
+				.with_context(|| format!("Failed to create {}", tmp_path.display()))?,
+		);
+		file.write_all(&signed_prefix)?;
+
+		let encoder = zstd::Encoder::new(
+			HashingWriter {
+				inner: file,
+				hasher: blake3::Hasher::new(),
+			},
+			6,
+		)
+		.context("Failed to create zstd encoder")?;
+
+		Ok(Self {
+			encoder,
+			signed_prefix,
+			signing_key,
+			tmp_path,
+			path: path.to_path_buf(),
+		})
+	}
+
+	pub fn write_message(&mut self, message: &SyncMessage) -> Result<()> {
+		serde_json::to_writer(&mut self.encoder, message)
+			.context("Failed to serialize sync message")?;
+		self.encoder.write_all(b"\n")?;
+		Ok(())
+	}
+
+	/// Sign the bundle and move it to its destination, returning its size in bytes
+	pub fn finish(self) -> Result<u64> {
+		let HashingWriter { mut inner, hasher } = self
+			.encoder
+			.finish()
+			.context("Failed to finish compression")?;
+
+		let mut signed = self.signed_prefix;
+		signed.extend_from_slice(hasher.finalize().as_bytes());
+		inner.write_all(&self.signing_key.sign(&signed).to_bytes())?;
+
+		inner.into_inner().map_err(|e| e.into_error())?.sync_all()?;
+		fs::rename(&self.tmp_path, &self.path)
+			.with_context(|| format!("Failed to write {}", self.path.display()))?;
+
+		Ok(fs::metadata(&self.path)?.len())
+	}
+}
+
+/// Reads the messages of a bundle whose signature checked out
+pub struct BundleReader {
+	header: BundleHeader,
+	messages: serde_json::StreamDeserializer<
+		'static,
+		serde_json::de::IoRead<zstd::Decoder<'static, BufReader<Take<File>>>>,
+		SyncMessage,
+	>,
+}
+
+impl BundleReader {
+	/// Open a bundle, failing if it isn't one or if its signature doesn't match
+	pub fn open(path: &Path) -> Result<Self> {
+		let mut file =
+			File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+		let file_len = file.metadata()?.len();
+
+		let mut magic = [0u8; 8];
+		file.read_exact(&mut magic)
+			.map_err(|_| anyhow!("Not a sync bundle"))?;
+		if &magic != BUNDLE_MAGIC {
+			bail!("Not a sync bundle");
+		}
+
+		let mut fixed = [0u8; 5];
+		file.read_exact(&mut fixed)
+			.map_err(|_| anyhow!("Sync bundle is truncated"))?;
+		if fixed[0] != BUNDLE_VERSION {
+			bail!("Unsupported sync bundle version {}", fixed[0]);
+		}
+		let header_len = u32::from_le_bytes([fixed[1], fixed[2], fixed[3], fixed[4]]);
+		if header_len > MAX_HEADER_LEN {
+			bail!("Sync bundle header is too large");
+		}
+
+		let mut header_bytes = vec![0u8; header_len as usize];
+		file.read_exact(&mut header_bytes)
+			.map_err(|_| anyhow!("Sync bundle is truncated"))?;
+		let header: BundleHeader =
+			serde_json::from_slice(&header_bytes).context("Invalid sync bundle header")?;
+
+		let payload_start = BUNDLE_MAGIC.len() as u64 + 5 + u64::from(header_len);
+		let payload_len = file_len
+			.checked_sub(payload_start + SIGNATURE_LEN)
+			.ok_or_else(|| anyhow!("Sync bundle is truncated"))?;
+
+		let mut hasher = blake3::Hasher::new();
+		io::copy(&mut (&mut file).take(payload_len), &mut hasher)?;
+		let mut signature = [0u8; SIGNATURE_LEN as usize];
+		file.read_exact(&mut signature)?;
+
+		let mut signed = Vec::with_capacity(payload_start as usize + 32);
+		signed.extend_from_slice(BUNDLE_MAGIC);
+		signed.extend_from_slice(&fixed);
+		signed.extend_from_slice(&header_bytes);
+		signed.extend_from_slice(hasher.finalize().as_bytes());
+		header
+			.signer_key()?
+			.verify(&signed, &Signature::from_bytes(&signature))
+			.map_err(|_| anyhow!("Sync bundle signature is invalid, it was modified or damaged"))?;
+
+		file.seek(SeekFrom::Start(payload_start))?;
+		let decoder =
+			zstd::Decoder::new(file.take(payload_len)).context("Failed to create zstd decoder")?;
+
+		Ok(Self {
+			header,
+			messages: serde_json::Deserializer::from_reader(decoder).into_iter(),
+		})
+	}
+
+	pub fn header(&self) -> &BundleHeader {
+		&self.header
+	}
+}
+
+impl Iterator for BundleReader {
+	type Item = Result<SyncMessage>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		self.messages
+			.next()
+			.map(|message| message.context("Invalid message in sync bundle"))
+	}
+}
+
+/// Hashes what goes through it, the compressed payload for the signature
+struct HashingWriter<W> {
+	inner: W,
+	hasher: blake3::Hasher,
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		let written = self.inner.write(buf)?;
+		self.hasher.update(&buf[..written]);
+		Ok(written)
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		self.inner.flush()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::infra::sync::{ChangeType, SharedChangeEntry};
+
+	fn header() -> BundleHeader {
+		BundleHeader::new(
+			BundleKind::Changes,
+			Uuid::new_v4(),
+			Uuid::new_v4(),
+			Uuid::new_v4(),
+			ReceivedWatermarks::default(),
+		)
+	}
+
+	fn shared_batch(library_id: Uuid, count: u64) -> SyncMessage {
+		let device_id = Uuid::new_v4();
+		SyncMessage::SharedChangeBatch {
+			library_id,
+			entries: (0..count)
+				.map(|counter| SharedChangeEntry {
+					hlc: HLC {
+						timestamp: 1_700_000_000_000,
+						counter,
+						device_id,
+					},
+					model_type: "tag".to_string(),
+					record_uuid: Uuid::new_v4(),
+					change_type: ChangeType::Insert,
+					data: serde_json::json!({ "name": format!("tag {}", counter) }),
+				})
+				.collect(),
+		}
+	}
+
+	fn write_bundle(path: &Path, header: BundleHeader, messages: &[SyncMessage]) {
+		let mut writer =
+			BundleWriter::create(path, header, SigningKey::from_bytes(&[7u8; 32])).unwrap();
+		for message in messages {
+			writer.write_message(message).unwrap();
+		}
+		writer.finish().unwrap();
+	}
+
+	#[test]
+	fn round_trips_signed_bundles() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("bundle.sdsync");
+		let header = header();
+		let messages = vec![
+			shared_batch(header.library_id, 3),
+			shared_batch(header.library_id, 2),
+		];
+		write_bundle(&path, header.clone(), &messages);
+		assert!(!path.with_extension("tmp").exists());
+
+		let reader = BundleReader::open(&path).unwrap();
+		assert_eq!(reader.header().bundle_id, header.bundle_id);
+		assert_eq!(
+			reader.header().signer_key().unwrap(),
+			SigningKey::from_bytes(&[7u8; 32]).verifying_key()
+		);
+
+		let read = reader.collect::<Result<Vec<_>>>().unwrap();
+		assert_eq!(read.len(), 2);
+		let SyncMessage::SharedChangeBatch { entries, .. } = &read[0] else {
+			panic!("expected a shared change batch");
+		};
+		assert_eq!(entries.len(), 3);
+		assert_eq!(entries[2].data["name"], "tag 2");
+	}
+
+	#[test]
+	fn rejects_modified_bundles() {
+		let dir = tempfile::tempdir().unwrap();
+		let path = dir.path().join("bundle.sdsync");
+		let header = header();
+		write_bundle(&path, header.clone(), &[shared_batch(header.library_id, 4)]);
+
+		let original = fs::read(&path).unwrap();
+		let payload_byte = original.len() - SIGNATURE_LEN as usize - 8;
+		for index in [12, payload_byte, original.len() - 1] {
+			let mut modified = original.clone();
+			modified[index] ^= 0x01;
+			fs::write(&path, &modified).unwrap();
+			assert!(
+				BundleReader::open(&path).is_err(),
+				"byte {} modified",
+				index
+			);
+		}
+
+		fs::write(&path, &original[..original.len() - 10]).unwrap();
+		assert!(BundleReader::open(&path).is_err());
+
+		fs::write(&path, b"PK\x03\x04 not a bundle").unwrap();
+		assert!(BundleReader::open(&path).is_err());
+	}
+}
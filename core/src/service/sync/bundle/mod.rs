@@ -0,0 +1,394 @@
+//! Offline sync through bundle files ("sneakernet")
+//!
+//! Devices that can't reach each other, air-gapped machines in particular, exchange
+//! signed bundles instead: a changes bundle holds this device's state changes and
+//! shared peer log entries the target hasn't received, and the target answers with an
+//! ack bundle once they're applied. Every bundle also carries the watermarks of what its
+//! author received from the other device, which is what the next export starts from.
+//!
+//! Like over the network, a device only sends the data it owns and the shared changes
+//! it made. The target's receipts are kept in this device's sync.db: its per-resource
+//! watermarks for us under its device ID, its shared watermark as a peer ack.
+//!
+//! Shared changes come from the peer log, which is pruned once every peer acked them. A
+//! device that never synced with this one should backfill over the network first.
+
+pub mod format;
+pub mod transport;
+
+pub use format::{
+	BundleHeader, BundleKind, BundleReader, BundleWriter, ReceivedWatermarks, BUNDLE_EXTENSION,
+};
+pub use transport::FileTransport;
+
+use super::{state::StateChangeMessage, PeerSync, SyncService};
+use crate::{
+	device::DeviceManager,
+	infra::sync::{
+		compute_registry_sync_order, is_device_owned, registry::apply_deletion, NetworkTransport,
+		PeerWatermarkStore, ResourceWatermarkStore,
+	},
+	service::network::{protocol::sync::messages::SyncMessage, NetworkIdentity},
+};
+use anyhow::{anyhow, bail, Result};
+use ed25519_dalek::SigningKey;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::Path;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// What a bundle held
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct BundleSummary {
+	pub bundle_id: Uuid,
+	pub kind: BundleKind,
+	pub source_device: Uuid,
+	pub target_device: Uuid,
+	/// Device-owned records created or updated
+	pub state_records: u64,
+	/// Device-owned records deleted
+	pub deletions: u64,
+	/// Shared peer log entries
+	pub shared_changes: u64,
+}
+
+impl BundleSummary {
+	fn new(header: &BundleHeader) -> Self {
+		Self {
+			bundle_id: header.bundle_id,
+			kind: header.kind,
+			source_device: header.source_device,
+			target_device: header.target_device,
+			state_records: 0,
+			deletions: 0,
+			shared_changes: 0,
+		}
+	}
+}
+
+/// Key bundles are signed with, that of this device's network identity
+///
+/// It's derived from the device key like the network identity is, so bundles can be
+/// signed on devices where networking never started.
+pub async fn signing_key(device_manager: &DeviceManager) -> Result<SigningKey> {
+	let device_key = device_manager
+		.master_key()
+		.await
+		.map_err(|e| anyhow!("Failed to get device key: {}", e))?;
+	let identity = NetworkIdentity::from_device_key(&device_key)
+		.await
+		.map_err(|e| anyhow!("Failed to derive network identity: {}", e))?;
+	Ok(SigningKey::from_bytes(identity.keypair_bytes()))
+}
+
+/// What this device received from a peer, for the headers of bundles sent to it
+async fn received_from(peer_sync: &PeerSync, peer: Uuid) -> Result<ReceivedWatermarks> {
+	let conn = peer_sync.peer_log().conn();
+	let device_id = peer_sync.device_id();
+
+	Ok(ReceivedWatermarks {
+		state: ResourceWatermarkStore::new(device_id)
+			.get_all_for_peer(conn, peer)
+			.await?
+			.into_iter()
+			.collect(),
+		shared: PeerWatermarkStore::new(device_id).get(conn, peer).await?,
+	})
+}
+
+/// Write the changes a device hasn't received from this one to a bundle
+pub async fn export_changes(
+	sync: &SyncService,
+	signing_key: SigningKey,
+	target: Uuid,
+	path: &Path,
+) -> Result<(BundleSummary, u64)> {
+	let peer_sync = sync.peer_sync();
+	let device_id = peer_sync.device_id();
+	let library_id = peer_sync.library_id();
+	let conn = peer_sync.peer_log().conn();
+	let batch_size = sync.config().batching.backfill_batch_size;
+
+	if target == device_id {
+		bail!("Bundles are for other devices");
+	}
+
+	let header = BundleHeader::new(
+		BundleKind::Changes,
+		library_id,
+		device_id,
+		target,
+		received_from(peer_sync, target).await?,
+	);
+	let mut summary = BundleSummary::new(&header);
+	let transport = FileTransport::create(path, header, signing_key)?;
+
+	// Device-owned state in dependency order, so foreign keys resolve on import
+	let sync_order = compute_registry_sync_order()
+		.await
+		.map_err(|e| anyhow!("Failed to compute sync order: {}", e))?;
+	let target_watermarks = ResourceWatermarkStore::new(target);
+
+	for model_type in sync_order {
+		if !is_device_owned(&model_type).await {
+			continue;
+		}
+
+		let since = target_watermarks.get(conn, device_id, &model_type).await?;
+		let mut cursor = None;
+		loop {
+			let records = peer_sync
+				.get_device_state(
+					vec![model_type.clone()],
+					Some(device_id),
+					since,
+					cursor,
+					batch_size,
+				)
+				.await?;
+			let Some(last) = records.last() else {
+				break;
+			};
+			cursor = Some((last.timestamp, last.uuid));
+			let has_more = records.len() >= batch_size;
+			summary.state_records += records.len() as u64;
+
+			transport
+				.send_sync_message(
+					target,
+					SyncMessage::StateResponse {
+						library_id,
+						model_type: model_type.clone(),
+						device_id,
+						records,
+						deleted_uuids: vec![],
+						checkpoint: None,
+						has_more,
+					},
+				)
+				.await?;
+
+			if !has_more {
+				break;
+			}
+		}
+
+		// Without a watermark the target gets everything, there is nothing to delete
+		if let Some(since) = since {
+			let deleted_uuids = peer_sync
+				.get_deletion_tombstones(&model_type, Some(device_id), since)
+				.await?;
+			if !deleted_uuids.is_empty() {
+				summary.deletions += deleted_uuids.len() as u64;
+				transport
+					.send_sync_message(
+						target,
+						SyncMessage::StateResponse {
+							library_id,
+							model_type: model_type.clone(),
+							device_id,
+							records: vec![],
+							deleted_uuids,
+							checkpoint: None,
+							has_more: false,
+						},
+					)
+					.await?;
+			}
+		}
+	}
+
+	let acked = peer_sync
+		.peer_log()
+		.get_ack(target)
+		.await
+		.map_err(|e| anyhow!("Failed to get peer ack: {}", e))?;
+	let entries = peer_sync
+		.peer_log()
+		.get_since(acked)
+		.await
+		.map_err(|e| anyhow!("Failed to query peer log: {}", e))?;
+	summary.shared_changes = entries.len() as u64;
+
+	for batch in entries.chunks(batch_size.max(1)) {
+		transport
+			.send_sync_message(
+				target,
+				SyncMessage::SharedChangeBatch {
+					library_id,
+					entries: batch.to_vec(),
+				},
+			)
+			.await?;
+	}
+
+	let size = transport.finish()?;
+
+	info!(
+		target = %target,
+		bundle_id = %summary.bundle_id,
+		state_records = summary.state_records,
+		deletions = summary.deletions,
+		shared_changes = summary.shared_changes,
+		size = size,
+		"Exported sync bundle"
+	);
+
+	Ok((summary, size))
+}
+
+/// Write an ack bundle telling a device what this one received from it
+pub async fn export_ack(
+	sync: &SyncService,
+	signing_key: SigningKey,
+	target: Uuid,
+	acknowledges: Option<Uuid>,
+	path: &Path,
+) -> Result<(BundleSummary, u64)> {
+	let peer_sync = sync.peer_sync();
+
+	let mut header = BundleHeader::new(
+		BundleKind::Ack,
+		peer_sync.library_id(),
+		peer_sync.device_id(),
+		target,
+		received_from(peer_sync, target).await?,
+	);
+	header.acknowledges = acknowledges;
+
+	let summary = BundleSummary::new(&header);
+	let size = FileTransport::create(path, header, signing_key)?.finish()?;
+
+	Ok((summary, size))
+}
+
+/// Apply a bundle written for this device
+///
+/// Its signature was checked when opening it, whether the signer is the source device
+/// is the caller's to check. Importing a bundle again is harmless: state changes are
+/// upserts and older shared changes lose against newer HLCs.
+pub async fn import(sync: &SyncService, reader: BundleReader) -> Result<BundleSummary> {
+	let peer_sync = sync.peer_sync();
+	let device_id = peer_sync.device_id();
+	let conn = peer_sync.peer_log().conn();
+	let header = reader.header().clone();
+	let source = header.source_device;
+
+	if header.library_id != peer_sync.library_id() {
+		bail!(
+			"Bundle belongs to library {}, not this one",
+			header.library_id
+		);
+	}
+	if header.target_device != device_id {
+		bail!(
+			"Bundle was written for device {}, not this one",
+			header.target_device
+		);
+	}
+	if source == device_id {
+		bail!("Bundle was written by this device");
+	}
+
+	// What the source received from us moves the next export to it forward
+	let source_watermarks = ResourceWatermarkStore::new(source);
+	for (resource_type, watermark) in &header.received.state {
+		source_watermarks
+			.upsert(conn, device_id, resource_type, *watermark)
+			.await?;
+	}
+	if let Some(hlc) = header.received.shared {
+		let acked = peer_sync
+			.peer_log()
+			.get_ack(source)
+			.await
+			.map_err(|e| anyhow!("Failed to get peer ack: {}", e))?;
+		if acked.map_or(true, |acked| hlc > acked) {
+			peer_sync.on_ack_received(source, hlc).await?;
+		}
+	}
+
+	let mut summary = BundleSummary::new(&header);
+	let mut max_hlc = None;
+
+	for message in reader {
+		let message = message?;
+		if message.library_id() != header.library_id {
+			bail!("Bundle holds a message for another library");
+		}
+
+		match message {
+			SyncMessage::StateResponse {
+				model_type,
+				device_id: owner,
+				records,
+				deleted_uuids,
+				..
+			} => {
+				// Devices only send the data they own
+				if owner != source {
+					bail!(
+						"Bundle from {} holds {} records owned by {}",
+						source,
+						model_type,
+						owner
+					);
+				}
+
+				summary.state_records += records.len() as u64;
+				for record in records {
+					peer_sync
+						.on_state_change_received(StateChangeMessage {
+							model_type: model_type.clone(),
+							record_uuid: record.uuid,
+							device_id: owner,
+							data: record.data,
+							timestamp: record.timestamp,
+						})
+						.await?;
+				}
+
+				summary.deletions += deleted_uuids.len() as u64;
+				for uuid in deleted_uuids {
+					apply_deletion(&model_type, uuid, peer_sync.db().clone())
+						.await
+						.map_err(|e| anyhow!("{}", e))?;
+				}
+			}
+
+			SyncMessage::SharedChangeBatch { entries, .. } => {
+				summary.shared_changes += entries.len() as u64;
+				for entry in entries {
+					max_hlc = max_hlc.max(Some(entry.hlc));
+					peer_sync.on_shared_change_received(entry).await?;
+				}
+			}
+
+			_ => {
+				warn!(
+					source = %source,
+					"Ignoring sync bundle message that isn't a state or shared change"
+				);
+			}
+		}
+	}
+
+	if let Some(hlc) = max_hlc {
+		PeerWatermarkStore::new(device_id)
+			.upsert(conn, source, hlc)
+			.await?;
+	}
+
+	info!(
+		source = %source,
+		bundle_id = %summary.bundle_id,
+		kind = ?summary.kind,
+		state_records = summary.state_records,
+		deletions = summary.deletions,
+		shared_changes = summary.shared_changes,
+		"Imported sync bundle"
+	);
+
+	Ok(summary)
+}
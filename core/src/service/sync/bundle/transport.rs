@@ -0,0 +1,97 @@
+//! File-based NetworkTransport
+//!
+//! Writes the sync messages sent to a device into a bundle instead of a connection. The
+//! bundle is the only link to its target, so there is no way to wait for a response:
+//! requests fail, and answers arrive later as an ack bundle.
+
+use super::format::{BundleHeader, BundleWriter};
+use crate::{
+	infra::sync::NetworkTransport, service::network::protocol::sync::messages::SyncMessage,
+};
+use anyhow::{anyhow, bail, Result};
+use ed25519_dalek::SigningKey;
+use std::{path::Path, sync::Mutex};
+use uuid::Uuid;
+
+/// Transport sending sync messages to a bundle file
+pub struct FileTransport {
+	library_id: Uuid,
+	target_device: Uuid,
+	writer: Mutex<Option<BundleWriter>>,
+}
+
+impl FileTransport {
+	/// Start a bundle for the header's target device
+	pub fn create(path: &Path, header: BundleHeader, signing_key: SigningKey) -> Result<Self> {
+		Ok(Self {
+			library_id: header.library_id,
+			target_device: header.target_device,
+			writer: Mutex::new(Some(BundleWriter::create(path, header, signing_key)?)),
+		})
+	}
+
+	/// Sign the bundle, returning its size in bytes. Nothing can be sent afterwards.
+	pub fn finish(&self) -> Result<u64> {
+		self.writer
+			.lock()
+			.map_err(|_| anyhow!("Bundle writer lock poisoned"))?
+			.take()
+			.ok_or_else(|| anyhow!("Bundle already finished"))?
+			.finish()
+	}
+}
+
+#[async_trait::async_trait]
+impl NetworkTransport for FileTransport {
+	async fn send_sync_message(&self, target_device: Uuid, message: SyncMessage) -> Result<()> {
+		if target_device != self.target_device {
+			bail!(
+				"Bundle is for device {}, not {}",
+				self.target_device,
+				target_device
+			);
+		}
+		if message.library_id() != self.library_id {
+			bail!(
+				"Bundle is for library {}, not {}",
+				self.library_id,
+				message.library_id()
+			);
+		}
+		if message.is_request() {
+			bail!("Sync requests need a connection, bundles only carry one-way messages");
+		}
+
+		self.writer
+			.lock()
+			.map_err(|_| anyhow!("Bundle writer lock poisoned"))?
+			.as_mut()
+			.ok_or_else(|| anyhow!("Bundle already finished"))?
+			.write_message(&message)
+	}
+
+	async fn send_sync_request(
+		&self,
+		_target_device: Uuid,
+		_request: SyncMessage,
+	) -> Result<SyncMessage> {
+		bail!("Sync requests need a connection, bundles only carry one-way messages")
+	}
+
+	async fn get_connected_sync_partners(
+		&self,
+		_library_id: Uuid,
+		_db: &sea_orm::DatabaseConnection,
+	) -> Result<Vec<Uuid>> {
+		// The bundle's target is the only device it reaches
+		Ok(vec![self.target_device])
+	}
+
+	async fn is_device_reachable(&self, device_uuid: Uuid) -> bool {
+		device_uuid == self.target_device
+	}
+
+	fn transport_name(&self) -> &'static str {
+		"FileTransport"
+	}
+}
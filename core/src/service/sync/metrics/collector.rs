@@ -255,6 +255,22 @@ impl SyncMetricsCollector {
 			.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
 	}
 
+	/// Record a completed verification against a peer
+	pub fn record_verification(&self, mismatches: u64, repairs: u64) {
+		self.metrics
+			.operations
+			.verification_runs
+			.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+		self.metrics
+			.operations
+			.verification_mismatches
+			.fetch_add(mismatches, std::sync::atomic::Ordering::Relaxed);
+		self.metrics
+			.operations
+			.verification_repairs
+			.fetch_add(repairs, std::sync::atomic::Ordering::Relaxed);
+	}
+
 	/// Record data volume by model type
 	pub async fn record_entries_synced(&self, model_type: &str, count: u64) {
 		let mut entries_synced = self.metrics.data_volume.entries_synced.write().await;
@@ -67,6 +67,11 @@ pub struct OperationSnapshot {
 	pub retry_queue_depth: u64,
 	pub retry_attempts: u64,
 	pub retry_successes: u64,
+
+	// Verification
+	pub verification_runs: u64,
+	pub verification_mismatches: u64,
+	pub verification_repairs: u64,
 }
 
 /// Data volume metrics snapshot
@@ -229,6 +234,18 @@ impl SyncMetricsSnapshot {
 				.operations
 				.retry_successes
 				.load(std::sync::atomic::Ordering::Relaxed),
+			verification_runs: metrics
+				.operations
+				.verification_runs
+				.load(std::sync::atomic::Ordering::Relaxed),
+			verification_mismatches: metrics
+				.operations
+				.verification_mismatches
+				.load(std::sync::atomic::Ordering::Relaxed),
+			verification_repairs: metrics
+				.operations
+				.verification_repairs
+				.load(std::sync::atomic::Ordering::Relaxed),
 		};
 
 		// Data volume snapshot
@@ -107,6 +107,11 @@ pub struct OperationMetrics {
 	pub retry_queue_depth: AtomicU64,
 	pub retry_attempts: AtomicU64,
 	pub retry_successes: AtomicU64,
+
+	// Verification
+	pub verification_runs: AtomicU64,
+	pub verification_mismatches: AtomicU64,
+	pub verification_repairs: AtomicU64,
 }
 
 impl Default for OperationMetrics {
@@ -127,6 +132,9 @@ impl Default for OperationMetrics {
 			retry_queue_depth: AtomicU64::new(0),
 			retry_attempts: AtomicU64::new(0),
 			retry_successes: AtomicU64::new(0),
+			verification_runs: AtomicU64::new(0),
+			verification_mismatches: AtomicU64::new(0),
+			verification_repairs: AtomicU64::new(0),
 		}
 	}
 }
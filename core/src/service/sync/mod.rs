@@ -5,7 +5,9 @@
 //! - Log-based sync with HLC for shared resources
 
 pub mod activity;
+pub mod anti_entropy;
 pub mod backfill;
+pub mod bundle;
 pub mod dependency;
 pub mod metrics;
 pub mod peer;
@@ -32,6 +34,7 @@ use crate::library::Library;
 use crate::service::network::protocol::SyncProtocolHandler;
 
 pub use activity::SyncActivityAggregator;
+pub use anti_entropy::SyncVerifier;
 pub use peer::PeerSync;
 pub use state::{
 	select_backfill_peer, BackfillCheckpoint, BufferQueue, BufferedUpdate, DeviceSyncState,
@@ -125,6 +128,9 @@ pub struct SyncService {
 	/// Batch aggregator for reducing event write volume
 	batch_aggregator: Arc<crate::infra::sync::BatchAggregator>,
 
+	/// Verifier comparing device-owned data with peers
+	verifier: Arc<SyncVerifier>,
+
 	/// Whether the service is running
 	is_running: Arc<AtomicBool>,
 
@@ -229,6 +235,13 @@ impl SyncService {
 			library.event_bus().clone(),
 		));
 
+		// Create verifier for anti-entropy checks
+		let verifier = Arc::new(SyncVerifier::new(
+			peer_sync.clone(),
+			metrics.clone(),
+			config.clone(),
+		));
+
 		info!(
 			library_id = %library_id,
 			device_id = %device_id,
@@ -245,6 +258,7 @@ impl SyncService {
 			activity_aggregator,
 			event_logger,
 			batch_aggregator,
+			verifier,
 			is_running: Arc::new(AtomicBool::new(false)),
 			shutdown_tx: Arc::new(Mutex::new(None)),
 		})
@@ -280,6 +294,11 @@ impl SyncService {
 		&self.batch_aggregator
 	}
 
+	/// Get the verifier
+	pub fn verifier(&self) -> &Arc<SyncVerifier> {
+		&self.verifier
+	}
+
 	/// Emit metrics update event
 	pub async fn emit_metrics_event(&self, library_id: Uuid) {
 		// Create a snapshot of current metrics
@@ -748,6 +767,13 @@ impl crate::service::Service for SyncService {
 			run_metrics_persistence_task(metrics, library_id, db).await;
 		});
 
+		// Spawn verification task (runs daily by default)
+		let verifier = self.verifier.clone();
+		let is_running = self.is_running.clone();
+		tokio::spawn(async move {
+			verifier.run_periodic(is_running).await;
+		});
+
 		// Spawn activity aggregator task (runs every second for real-time events)
 		let activity_aggregator = self.activity_aggregator.clone();
 		tokio::spawn(async move {
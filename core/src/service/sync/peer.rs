@@ -1276,6 +1276,7 @@ impl PeerSync {
 					uuid: record_uuid,
 					data,
 					timestamp,
+					hlc: None,
 				});
 		}
 
@@ -2646,6 +2647,7 @@ impl PeerSync {
 							uuid,
 							data,
 							timestamp,
+							hlc: None,
 						}
 					}));
 
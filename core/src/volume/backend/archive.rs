@@ -0,0 +1,870 @@
+//! Read-only backend for the contents of archive files
+//!
+//! Zip, tar (plain, gzip or zstd compressed) and 7z archives are presented as a directory
+//! tree rooted at the archive's own path, so `/photos/trip.zip/day1/a.jpg` addresses the
+//! member `day1/a.jpg` of `/photos/trip.zip`. Members are decompressed on the fly, nothing
+//! is extracted to disk. Compressed tars and solid 7z archives can only be read front to
+//! back, so reaching a member means decompressing everything before it.
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Write};
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::{mpsc, OnceCell};
+use tracing::debug;
+
+use super::{
+	BackendCapabilities, BackendType, RawDirEntry, RawMetadata, VolumeBackend, VolumeReader,
+	VolumeWriter,
+};
+use crate::ops::indexing::state::EntryKind;
+use crate::volume::error::VolumeError;
+
+/// Size of the chunks returned by [`ArchiveBackend::open_reader`]
+const READ_CHUNK_SIZE: usize = 1024 * 1024;
+
+/// Chunks decompressed ahead of the reader
+const READ_AHEAD_CHUNKS: usize = 4;
+
+/// Archive formats that can be browsed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ArchiveFormat {
+	Zip,
+	Tar,
+	TarGz,
+	TarZst,
+	SevenZip,
+}
+
+impl ArchiveFormat {
+	/// Detect the format from a file name, None if it isn't a supported archive
+	pub fn from_path(path: &Path) -> Option<Self> {
+		let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+
+		if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+			Some(Self::TarGz)
+		} else if name.ends_with(".tar.zst") || name.ends_with(".tzst") {
+			Some(Self::TarZst)
+		} else if name.ends_with(".tar") {
+			Some(Self::Tar)
+		} else if name.ends_with(".zip") {
+			Some(Self::Zip)
+		} else if name.ends_with(".7z") {
+			Some(Self::SevenZip)
+		} else {
+			None
+		}
+	}
+}
+
+/// A file or directory inside an archive
+#[derive(Debug, Clone)]
+pub struct ArchiveMember {
+	/// Path relative to the archive root
+	pub path: PathBuf,
+	pub kind: EntryKind,
+	/// Uncompressed size
+	pub size: u64,
+	pub modified: Option<SystemTime>,
+}
+
+/// Members by path, including the directories archives often leave implicit
+type MemberTable = BTreeMap<PathBuf, ArchiveMember>;
+
+/// Backend serving the members of one archive file
+///
+/// The member list is read once per backend and kept, contents are read on every call.
+#[derive(Debug, Clone)]
+pub struct ArchiveBackend {
+	archive: PathBuf,
+	format: ArchiveFormat,
+	members: Arc<OnceCell<Arc<MemberTable>>>,
+}
+
+impl ArchiveBackend {
+	/// Backend for an archive file, None if its name isn't that of a supported format
+	pub fn open(archive: impl Into<PathBuf>) -> Option<Self> {
+		let archive = archive.into();
+		let format = ArchiveFormat::from_path(&archive)?;
+
+		Some(Self {
+			archive,
+			format,
+			members: Arc::new(OnceCell::new()),
+		})
+	}
+
+	/// Split a local path into the archive it goes through and the member path inside it
+	///
+	/// The path itself counts: for an archive file the member path is empty. Returns None
+	/// for ordinary paths, and for paths that exist on disk as directories.
+	pub fn locate(path: &Path) -> Option<(PathBuf, PathBuf)> {
+		for ancestor in path.ancestors() {
+			match std::fs::metadata(ancestor) {
+				Ok(metadata) if metadata.is_file() && ArchiveFormat::from_path(ancestor).is_some() => {
+					let member = path.strip_prefix(ancestor).ok()?.to_path_buf();
+					return Some((ancestor.to_path_buf(), member));
+				}
+				// The first ancestor that exists decides, archives aren't nested
+				Ok(_) => return None,
+				Err(_) => continue,
+			}
+		}
+
+		None
+	}
+
+	/// Whether a local path addresses something inside an archive rather than the archive
+	pub fn is_member_path(path: &Path) -> bool {
+		Self::locate(path).is_some_and(|(_, member)| !member.as_os_str().is_empty())
+	}
+
+	/// Backend for the archive a local path goes through
+	pub fn for_path(path: &Path) -> Option<Self> {
+		let (archive, _) = Self::locate(path)?;
+		Self::open(archive)
+	}
+
+	/// Path of the archive file
+	pub fn archive_path(&self) -> &Path {
+		&self.archive
+	}
+
+	pub fn format(&self) -> ArchiveFormat {
+		self.format
+	}
+
+	/// Member path for a path given to the backend
+	///
+	/// Absolute paths go through the archive, relative ones are taken from its root.
+	fn member_path(&self, path: &Path) -> Result<PathBuf, VolumeError> {
+		let relative = if path.is_absolute() {
+			path.strip_prefix(&self.archive).map_err(|_| {
+				VolumeError::InvalidData(format!(
+					"{} is not inside {}",
+					path.display(),
+					self.archive.display()
+				))
+			})?
+		} else {
+			path
+		};
+
+		Ok(relative
+			.components()
+			.filter(|c| matches!(c, Component::Normal(_)))
+			.collect())
+	}
+
+	/// Member list, read from the archive on first use
+	async fn members(&self) -> Result<Arc<MemberTable>, VolumeError> {
+		self.members
+			.get_or_try_init(|| async {
+				let backend = self.clone();
+				let members = tokio::task::spawn_blocking(move || backend.list_members())
+					.await
+					.map_err(|e| VolumeError::Platform(format!("Archive listing failed: {}", e)))??;
+
+				Ok(Arc::new(
+					members
+						.into_iter()
+						.map(|member| (member.path.clone(), member))
+						.collect(),
+				))
+			})
+			.await
+			.cloned()
+	}
+
+	async fn member(&self, path: &Path) -> Result<ArchiveMember, VolumeError> {
+		let member_path = self.member_path(path)?;
+		if member_path.as_os_str().is_empty() {
+			return Ok(ArchiveMember {
+				path: member_path,
+				kind: EntryKind::Directory,
+				size: 0,
+				modified: None,
+			});
+		}
+
+		self.members()
+			.await?
+			.get(&member_path)
+			.cloned()
+			.ok_or_else(|| not_found(&self.archive, &member_path))
+	}
+
+	/// Read every member of the archive, blocking
+	///
+	/// Directories that only appear as part of member paths are included.
+	pub fn list_members(&self) -> Result<Vec<ArchiveMember>, VolumeError> {
+		debug!("ArchiveBackend::list_members: {}", self.archive.display());
+
+		let mut members = MemberTable::new();
+		match self.format {
+			ArchiveFormat::Zip => {
+				let mut archive = self.open_zip()?;
+				for index in 0..archive.len() {
+					let file = archive.by_index_raw(index).map_err(zip_error)?;
+					if let Some(member) = zip_member(&file) {
+						members.insert(member.path.clone(), member);
+					}
+				}
+			}
+			ArchiveFormat::SevenZip => {
+				let reader = self.open_seven_zip()?;
+				for entry in &reader.archive().files {
+					if let Some(member) = seven_zip_member(entry) {
+						members.insert(member.path.clone(), member);
+					}
+				}
+			}
+			ArchiveFormat::Tar | ArchiveFormat::TarGz | ArchiveFormat::TarZst => {
+				let mut archive = tar::Archive::new(self.open_tar_stream()?);
+				for entry in archive.entries()? {
+					if let Some(member) = tar_member(&entry?) {
+						members.insert(member.path.clone(), member);
+					}
+				}
+			}
+		}
+
+		// Fill in parent directories missing from the archive
+		let paths: Vec<PathBuf> = members.keys().cloned().collect();
+		for path in paths {
+			for ancestor in path.ancestors().skip(1) {
+				if ancestor.as_os_str().is_empty() || members.contains_key(ancestor) {
+					continue;
+				}
+				members.insert(
+					ancestor.to_path_buf(),
+					ArchiveMember {
+						path: ancestor.to_path_buf(),
+						kind: EntryKind::Directory,
+						size: 0,
+						modified: None,
+					},
+				);
+			}
+		}
+
+		Ok(members.into_values().collect())
+	}
+
+	/// Stream the contents of files in archive order, blocking
+	///
+	/// `wanted` picks the files to visit, the others are skipped as cheaply as the format
+	/// allows. `visit` gets each file with a reader over its contents and returns false to
+	/// stop early.
+	pub fn for_each_file<W, V>(&self, wanted: W, mut visit: V) -> Result<(), VolumeError>
+	where
+		W: Fn(&Path) -> bool,
+		V: FnMut(&ArchiveMember, &mut dyn Read) -> io::Result<bool>,
+	{
+		match self.format {
+			ArchiveFormat::Zip => {
+				let mut archive = self.open_zip()?;
+				for index in 0..archive.len() {
+					let member = {
+						let file = archive.by_index_raw(index).map_err(zip_error)?;
+						match zip_member(&file) {
+							Some(member) if member.kind == EntryKind::File => member,
+							_ => continue,
+						}
+					};
+					if !wanted(&member.path) {
+						continue;
+					}
+
+					let mut file = archive.by_index(index).map_err(zip_error)?;
+					if !visit(&member, &mut file)? {
+						break;
+					}
+				}
+			}
+			ArchiveFormat::SevenZip => {
+				let mut reader = self.open_seven_zip()?;
+				reader
+					.for_each_entries(|entry, data| {
+						let mut keep_going = true;
+						if let Some(member) = seven_zip_member(entry) {
+							if member.kind == EntryKind::File && wanted(&member.path) {
+								keep_going = visit(&member, data)?;
+							}
+						}
+						// Entries of a solid block share one stream, what isn't read now
+						// would be read as the start of the next entry
+						if keep_going {
+							io::copy(data, &mut io::sink())?;
+						}
+						Ok(keep_going)
+					})
+					.map_err(seven_zip_error)?;
+			}
+			ArchiveFormat::Tar | ArchiveFormat::TarGz | ArchiveFormat::TarZst => {
+				let mut archive = tar::Archive::new(self.open_tar_stream()?);
+				for entry in archive.entries()? {
+					let mut entry = entry?;
+					let member = match tar_member(&entry) {
+						Some(member) if member.kind == EntryKind::File => member,
+						_ => continue,
+					};
+					if wanted(&member.path) && !visit(&member, &mut entry)? {
+						break;
+					}
+				}
+			}
+		}
+
+		Ok(())
+	}
+
+	/// Write a file of the archive, or a byte range of it, blocking
+	///
+	/// Returns the bytes written.
+	pub fn extract(
+		&self,
+		path: &Path,
+		range: Option<Range<u64>>,
+		out: &mut dyn Write,
+	) -> Result<u64, VolumeError> {
+		let member_path = self.member_path(path)?;
+		let mut written = None;
+
+		self.for_each_file(
+			|candidate| candidate == member_path,
+			|_, data| {
+				written = Some(match &range {
+					Some(range) => {
+						io::copy(&mut (&mut *data).take(range.start), &mut io::sink())?;
+						let len = range.end.saturating_sub(range.start);
+						io::copy(&mut (&mut *data).take(len), out)?
+					}
+					None => io::copy(data, out)?,
+				});
+				Ok(false)
+			},
+		)?;
+
+		written.ok_or_else(|| not_found(&self.archive, &member_path))
+	}
+
+	fn open_zip(&self) -> Result<zip::ZipArchive<BufReader<File>>, VolumeError> {
+		let file = File::open(&self.archive)?;
+		zip::ZipArchive::new(BufReader::new(file)).map_err(zip_error)
+	}
+
+	fn open_seven_zip(&self) -> Result<sevenz_rust::SevenZReader<File>, VolumeError> {
+		sevenz_rust::SevenZReader::open(&self.archive, sevenz_rust::Password::empty())
+			.map_err(seven_zip_error)
+	}
+
+	/// Decompressed tar stream
+	fn open_tar_stream(&self) -> Result<Box<dyn Read>, VolumeError> {
+		let file = BufReader::new(File::open(&self.archive)?);
+		Ok(match self.format {
+			ArchiveFormat::TarGz => Box::new(flate2::read::MultiGzDecoder::new(file)),
+			ArchiveFormat::TarZst => Box::new(zstd::stream::read::Decoder::with_buffer(file)?),
+			_ => Box::new(file),
+		})
+	}
+
+	fn read_only(&self) -> VolumeError {
+		VolumeError::ReadOnly(format!(
+			"{} is an archive, its contents can't be modified",
+			self.archive.display()
+		))
+	}
+}
+
+#[async_trait]
+impl VolumeBackend for ArchiveBackend {
+	async fn read(&self, path: &Path) -> Result<Bytes, VolumeError> {
+		let mut reader = self.open_reader(path, None).await?;
+		let mut data = Vec::new();
+		while let Some(chunk) = reader.next_chunk().await? {
+			data.extend_from_slice(&chunk);
+		}
+
+		Ok(Bytes::from(data))
+	}
+
+	async fn read_range(&self, path: &Path, range: Range<u64>) -> Result<Bytes, VolumeError> {
+		let mut reader = self.open_reader(path, Some(range)).await?;
+		let mut data = Vec::new();
+		while let Some(chunk) = reader.next_chunk().await? {
+			data.extend_from_slice(&chunk);
+		}
+
+		Ok(Bytes::from(data))
+	}
+
+	async fn write(&self, _path: &Path, _data: Bytes) -> Result<(), VolumeError> {
+		Err(self.read_only())
+	}
+
+	async fn read_dir(&self, path: &Path) -> Result<Vec<RawDirEntry>, VolumeError> {
+		debug!(
+			"ArchiveBackend::read_dir: {} in {}",
+			path.display(),
+			self.archive.display()
+		);
+
+		let directory = self.member(path).await?;
+		if directory.kind != EntryKind::Directory {
+			return Err(VolumeError::InvalidData(format!(
+				"{} is not a directory",
+				path.display()
+			)));
+		}
+
+		Ok(self
+			.members()
+			.await?
+			.values()
+			.filter(|member| member.path.parent() == Some(directory.path.as_path()))
+			.map(|member| RawDirEntry {
+				name: member
+					.path
+					.file_name()
+					.map(|name| name.to_string_lossy().to_string())
+					.unwrap_or_default(),
+				kind: member.kind,
+				size: member.size,
+				modified: member.modified,
+				inode: None,
+			})
+			.collect())
+	}
+
+	async fn metadata(&self, path: &Path) -> Result<RawMetadata, VolumeError> {
+		let member = self.member(path).await?;
+
+		Ok(RawMetadata {
+			kind: member.kind,
+			size: member.size,
+			modified: member.modified,
+			created: None,
+			accessed: None,
+			inode: None,
+			permissions: None,
+		})
+	}
+
+	async fn exists(&self, path: &Path) -> Result<bool, VolumeError> {
+		match self.member(path).await {
+			Ok(_) => Ok(true),
+			Err(VolumeError::Io(e)) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+			Err(e) => Err(e),
+		}
+	}
+
+	async fn delete(&self, _path: &Path) -> Result<(), VolumeError> {
+		Err(self.read_only())
+	}
+
+	async fn create_directory(&self, _path: &Path, _recursive: bool) -> Result<(), VolumeError> {
+		Err(self.read_only())
+	}
+
+	async fn open_reader(
+		&self,
+		path: &Path,
+		range: Option<Range<u64>>,
+	) -> Result<Box<dyn VolumeReader>, VolumeError> {
+		debug!(
+			"ArchiveBackend::open_reader: {} in {} ({:?})",
+			path.display(),
+			self.archive.display(),
+			range
+		);
+
+		let member = self.member(path).await?;
+		if member.kind != EntryKind::File {
+			return Err(VolumeError::InvalidData(format!(
+				"{} is not a file",
+				path.display()
+			)));
+		}
+
+		// Decompress on a blocking thread, handing chunks over as they're produced
+		let (sender, receiver) = mpsc::channel(READ_AHEAD_CHUNKS);
+		let backend = self.clone();
+		tokio::task::spawn_blocking(move || {
+			let mut out = ChannelWriter {
+				sender: sender.clone(),
+				buffer: Vec::with_capacity(READ_CHUNK_SIZE),
+			};
+			let result = backend
+				.extract(&member.path, range, &mut out)
+				.and_then(|_| out.flush().map_err(VolumeError::Io));
+			if let Err(e) = result {
+				// Fails only once the reader is gone, when nobody wants the error
+				let _ = sender.blocking_send(Err(e));
+			}
+		});
+
+		Ok(Box::new(ArchiveReader { receiver }))
+	}
+
+	async fn open_writer(
+		&self,
+		_path: &Path,
+		_append: bool,
+	) -> Result<Box<dyn VolumeWriter>, VolumeError> {
+		Err(self.read_only())
+	}
+
+	async fn copy(&self, _from: &Path, _to: &Path) -> Result<(), VolumeError> {
+		Err(self.read_only())
+	}
+
+	async fn rename(&self, _from: &Path, _to: &Path) -> Result<(), VolumeError> {
+		Err(self.read_only())
+	}
+
+	fn capabilities(&self) -> BackendCapabilities {
+		BackendCapabilities::default()
+	}
+
+	fn is_local(&self) -> bool {
+		true
+	}
+
+	fn backend_type(&self) -> BackendType {
+		BackendType::Archive(self.format)
+	}
+}
+
+/// Reader for [`ArchiveBackend::open_reader`]
+struct ArchiveReader {
+	receiver: mpsc::Receiver<Result<Bytes, VolumeError>>,
+}
+
+#[async_trait]
+impl VolumeReader for ArchiveReader {
+	async fn next_chunk(&mut self) -> Result<Option<Bytes>, VolumeError> {
+		self.receiver.recv().await.transpose()
+	}
+}
+
+/// Collects decompressed data into chunks for an [`ArchiveReader`]
+struct ChannelWriter {
+	sender: mpsc::Sender<Result<Bytes, VolumeError>>,
+	buffer: Vec<u8>,
+}
+
+impl Write for ChannelWriter {
+	fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+		let len = data.len().min(READ_CHUNK_SIZE - self.buffer.len());
+		self.buffer.extend_from_slice(&data[..len]);
+		if self.buffer.len() == READ_CHUNK_SIZE {
+			self.flush()?;
+		}
+
+		Ok(len)
+	}
+
+	fn flush(&mut self) -> io::Result<()> {
+		if self.buffer.is_empty() {
+			return Ok(());
+		}
+
+		let chunk = std::mem::replace(&mut self.buffer, Vec::with_capacity(READ_CHUNK_SIZE));
+		self.sender
+			.blocking_send(Ok(Bytes::from(chunk)))
+			.map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "Archive reader was dropped"))
+	}
+}
+
+/// Member path made only of normal components, None for paths escaping the archive root
+fn safe_member_path(path: &Path) -> Option<PathBuf> {
+	let mut safe = PathBuf::new();
+	for component in path.components() {
+		match component {
+			Component::Normal(name) => safe.push(name),
+			Component::CurDir => {}
+			_ => return None,
+		}
+	}
+
+	(!safe.as_os_str().is_empty()).then_some(safe)
+}
+
+fn zip_member<R: Read>(file: &zip::read::ZipFile<'_, R>) -> Option<ArchiveMember> {
+	let path = safe_member_path(&file.enclosed_name()?)?;
+	let kind = if file.is_dir() {
+		EntryKind::Directory
+	} else if file.is_symlink() {
+		EntryKind::Symlink
+	} else {
+		EntryKind::File
+	};
+
+	// Zip times are local time without a zone, taken as UTC
+	let modified = file.last_modified().and_then(|time| {
+		let date = chrono::NaiveDate::from_ymd_opt(
+			time.year().into(),
+			time.month().into(),
+			time.day().into(),
+		)?;
+		let date_time =
+			date.and_hms_opt(time.hour().into(), time.minute().into(), time.second().into())?;
+		Some(SystemTime::from(date_time.and_utc()))
+	});
+
+	Some(ArchiveMember {
+		path,
+		kind,
+		size: if kind == EntryKind::File { file.size() } else { 0 },
+		modified,
+	})
+}
+
+fn tar_member<R: Read>(entry: &tar::Entry<'_, R>) -> Option<ArchiveMember> {
+	let path = safe_member_path(&entry.path().ok()?)?;
+	let entry_type = entry.header().entry_type();
+	let kind = if entry_type.is_dir() {
+		EntryKind::Directory
+	} else if entry_type.is_symlink() || entry_type.is_hard_link() {
+		EntryKind::Symlink
+	} else if entry_type.is_file() {
+		EntryKind::File
+	} else {
+		// Devices, fifos and extension headers
+		return None;
+	};
+
+	Some(ArchiveMember {
+		path,
+		kind,
+		size: if kind == EntryKind::File { entry.size() } else { 0 },
+		modified: entry
+			.header()
+			.mtime()
+			.ok()
+			.map(|secs| UNIX_EPOCH + Duration::from_secs(secs)),
+	})
+}
+
+fn seven_zip_member(entry: &sevenz_rust::SevenZArchiveEntry) -> Option<ArchiveMember> {
+	if entry.is_anti_item {
+		return None;
+	}
+
+	let path = safe_member_path(Path::new(&entry.name.replace('\\', "/")))?;
+	let kind = if entry.is_directory {
+		EntryKind::Directory
+	} else {
+		EntryKind::File
+	};
+
+	Some(ArchiveMember {
+		path,
+		kind,
+		size: if kind == EntryKind::File { entry.size } else { 0 },
+		modified: entry
+			.has_last_modified_date
+			.then(|| SystemTime::from(entry.last_modified_date)),
+	})
+}
+
+fn not_found(archive: &Path, member: &Path) -> VolumeError {
+	VolumeError::Io(io::Error::new(
+		io::ErrorKind::NotFound,
+		format!("{} not found in {}", member.display(), archive.display()),
+	))
+}
+
+fn zip_error(e: zip::result::ZipError) -> VolumeError {
+	match e {
+		zip::result::ZipError::Io(e) => VolumeError::Io(e),
+		e => VolumeError::InvalidData(format!("Invalid zip archive: {}", e)),
+	}
+}
+
+fn seven_zip_error(e: sevenz_rust::Error) -> VolumeError {
+	match e {
+		sevenz_rust::Error::Io(e, _) => VolumeError::Io(e),
+		e => VolumeError::InvalidData(format!("Invalid 7z archive: {}", e)),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use tempfile::TempDir;
+
+	/// Small archive of each format with the same contents
+	fn write_archives(dir: &Path) -> Vec<PathBuf> {
+		let files: [(&str, &[u8]); 3] = [
+			("readme.txt", b"hello archive"),
+			("docs/a.txt", b"0123456789"),
+			("docs/nested/b.bin", &[7u8; 3000]),
+		];
+
+		let zip_path = dir.join("sample.zip");
+		let mut zip = zip::ZipWriter::new(File::create(&zip_path).unwrap());
+		for (name, data) in files {
+			zip.start_file(name, zip::write::SimpleFileOptions::default())
+				.unwrap();
+			zip.write_all(data).unwrap();
+		}
+		zip.finish().unwrap();
+
+		let tar_path = dir.join("sample.tar.gz");
+		let encoder = flate2::write::GzEncoder::new(
+			File::create(&tar_path).unwrap(),
+			flate2::Compression::default(),
+		);
+		let mut tar = tar::Builder::new(encoder);
+		for (name, data) in files {
+			let mut header = tar::Header::new_gnu();
+			header.set_size(data.len() as u64);
+			header.set_mode(0o644);
+			header.set_mtime(1_700_000_000);
+			header.set_cksum();
+			tar.append_data(&mut header, name, data).unwrap();
+		}
+		tar.into_inner().unwrap().finish().unwrap();
+
+		let zst_path = dir.join("sample.tar.zst");
+		let encoder = zstd::stream::write::Encoder::new(File::create(&zst_path).unwrap(), 3)
+			.unwrap()
+			.auto_finish();
+		let mut tar = tar::Builder::new(encoder);
+		for (name, data) in files {
+			let mut header = tar::Header::new_gnu();
+			header.set_size(data.len() as u64);
+			header.set_mode(0o644);
+			header.set_mtime(1_700_000_000);
+			header.set_cksum();
+			tar.append_data(&mut header, name, data).unwrap();
+		}
+		tar.finish().unwrap();
+
+		let seven_zip_path = dir.join("sample.7z");
+		let mut seven_zip = sevenz_rust::SevenZWriter::create(&seven_zip_path).unwrap();
+		for (name, data) in files {
+			let mut entry = sevenz_rust::SevenZArchiveEntry::new();
+			entry.name = name.to_string();
+			seven_zip.push_archive_entry(entry, Some(data)).unwrap();
+		}
+		seven_zip.finish().unwrap();
+
+		vec![zip_path, tar_path, zst_path, seven_zip_path]
+	}
+
+	#[test]
+	fn detects_formats() {
+		let format = |name: &str| ArchiveFormat::from_path(Path::new(name));
+		assert_eq!(format("a.ZIP"), Some(ArchiveFormat::Zip));
+		assert_eq!(format("a.tar"), Some(ArchiveFormat::Tar));
+		assert_eq!(format("a.tar.gz"), Some(ArchiveFormat::TarGz));
+		assert_eq!(format("a.tgz"), Some(ArchiveFormat::TarGz));
+		assert_eq!(format("a.tar.zst"), Some(ArchiveFormat::TarZst));
+		assert_eq!(format("a.7z"), Some(ArchiveFormat::SevenZip));
+		assert_eq!(format("a.gz"), None);
+		assert_eq!(format("zip"), None);
+	}
+
+	#[test]
+	fn locates_members() {
+		let temp = TempDir::new().unwrap();
+		let archives = write_archives(temp.path());
+		let zip = &archives[0];
+
+		assert_eq!(
+			ArchiveBackend::locate(&zip.join("docs/a.txt")),
+			Some((zip.clone(), PathBuf::from("docs/a.txt")))
+		);
+		assert_eq!(
+			ArchiveBackend::locate(zip),
+			Some((zip.clone(), PathBuf::new()))
+		);
+		assert!(ArchiveBackend::is_member_path(&zip.join("readme.txt")));
+		assert!(!ArchiveBackend::is_member_path(zip));
+		assert_eq!(ArchiveBackend::locate(temp.path()), None);
+		assert_eq!(ArchiveBackend::locate(&temp.path().join("missing.txt")), None);
+	}
+
+	#[tokio::test]
+	async fn browses_and_reads_members() {
+		let temp = TempDir::new().unwrap();
+
+		for archive in write_archives(temp.path()) {
+			let backend = ArchiveBackend::open(&archive).unwrap();
+
+			let mut root: Vec<_> = backend
+				.read_dir(&archive)
+				.await
+				.unwrap()
+				.into_iter()
+				.map(|entry| (entry.name, entry.kind))
+				.collect();
+			root.sort_by(|a, b| a.0.cmp(&b.0));
+			assert_eq!(
+				root,
+				vec![
+					("docs".to_string(), EntryKind::Directory),
+					("readme.txt".to_string(), EntryKind::File),
+				]
+			);
+
+			// Directories the archive doesn't list are still there
+			let nested = backend.read_dir(Path::new("docs/nested")).await.unwrap();
+			assert_eq!(nested.len(), 1);
+			assert_eq!(nested[0].size, 3000);
+
+			let readme = backend.read(&archive.join("readme.txt")).await.unwrap();
+			assert_eq!(&readme[..], b"hello archive");
+
+			let range = backend
+				.read_range(Path::new("docs/a.txt"), 2..5)
+				.await
+				.unwrap();
+			assert_eq!(&range[..], b"234");
+
+			let metadata = backend.metadata(Path::new("docs/nested/b.bin")).await.unwrap();
+			assert_eq!(metadata.kind, EntryKind::File);
+			assert_eq!(metadata.size, 3000);
+
+			assert!(!backend.exists(Path::new("docs/missing.txt")).await.unwrap());
+			assert!(matches!(
+				backend.read(Path::new("docs/missing.txt")).await,
+				Err(VolumeError::Io(e)) if e.kind() == io::ErrorKind::NotFound
+			));
+		}
+	}
+
+	#[tokio::test]
+	async fn rejects_writes() {
+		let temp = TempDir::new().unwrap();
+		let archive = &write_archives(temp.path())[0];
+		let backend = ArchiveBackend::open(archive).unwrap();
+
+		assert!(matches!(
+			backend.write(Path::new("new.txt"), Bytes::from("x")).await,
+			Err(VolumeError::ReadOnly(_))
+		));
+		assert!(matches!(
+			backend.delete(Path::new("readme.txt")).await,
+			Err(VolumeError::ReadOnly(_))
+		));
+	}
+
+	#[test]
+	fn skips_paths_escaping_the_archive() {
+		assert_eq!(safe_member_path(Path::new("../etc/passwd")), None);
+		assert_eq!(safe_member_path(Path::new("/etc/passwd")), None);
+		assert_eq!(
+			safe_member_path(Path::new("./a/b.txt")),
+			Some(PathBuf::from("a/b.txt"))
+		);
+	}
+}
@@ -0,0 +1,724 @@
+//! Client-side encryption for any volume backend
+//!
+//! [`EncryptedBackend`] wraps another backend so only ciphertext reaches the storage.
+//! Files are stored in the `SDCRYPT` stream format of `sd-crypto` (see
+//! [`sd_crypto::cloud::header`]): a 64 byte header followed by chunks sealed separately
+//! with XChaCha20-Poly1305, so a ranged read only fetches and decrypts the chunks it
+//! covers, and a truncated file fails to decrypt instead of reading as a shorter one.
+//! The volume's key is used without key derivation, and files are written with
+//! [`CHUNK_SIZE`] chunks, which listings assume when reporting sizes.
+//!
+//! Names can be encrypted too; that's deterministic, so a path always maps to the same
+//! stored path and lookups don't need a listing.
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use bytes::{Bytes, BytesMut};
+use chacha20poly1305::{
+	aead::{Aead, KeyInit},
+	XChaCha20Poly1305, XNonce,
+};
+use futures::TryStreamExt;
+use sd_crypto::{
+	cloud::{Algorithm, Header, Kdf, SecretKey, StreamDecryption, StreamEncryption},
+	CryptoRng,
+};
+use std::ffi::OsString;
+use std::fmt;
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tracing::debug;
+
+use super::{
+	BackendCapabilities, BackendType, RawDirEntry, RawMetadata, VolumeBackend, VolumeReader,
+	VolumeWriter,
+};
+use crate::ops::indexing::state::EntryKind;
+use crate::volume::error::VolumeError;
+
+/// Bytes of content per chunk
+///
+/// Small enough that the samples of content hashing don't pull in much more than they
+/// need, large enough that the 16 byte tags stay negligible.
+pub const CHUNK_SIZE: u32 = 64 * 1024;
+
+const HEADER_SIZE: u64 = Header::SIZE as u64;
+const TAG_SIZE: u64 = 16;
+const NONCE_SIZE: usize = 24;
+
+/// Backend that encrypts everything written to the backend it wraps
+pub struct EncryptedBackend {
+	inner: Arc<dyn VolumeBackend>,
+	contents: Arc<SecretKey>,
+	names: Option<NameCipher>,
+}
+
+impl EncryptedBackend {
+	/// Wrap `inner`, encrypting with the volume's key from the key manager
+	///
+	/// Contents and names use separate keys derived from it. With `encrypt_names` every
+	/// file and directory name is stored encrypted too, which limits names to about 150
+	/// bytes on storage that allows 255.
+	pub fn new(inner: Arc<dyn VolumeBackend>, volume_key: &[u8; 32], encrypt_names: bool) -> Self {
+		let contents_key =
+			blake3::derive_key("spacedrive encrypted volume v1 contents", volume_key);
+		let names = encrypt_names.then(|| NameCipher {
+			cipher: XChaCha20Poly1305::new(
+				&blake3::derive_key("spacedrive encrypted volume v1 names", volume_key).into(),
+			),
+			nonce_key: blake3::derive_key("spacedrive encrypted volume v1 name nonces", volume_key),
+		});
+
+		Self {
+			inner,
+			contents: Arc::new(SecretKey::new(contents_key.into())),
+			names,
+		}
+	}
+
+	/// Whether names are stored encrypted
+	pub fn encrypts_names(&self) -> bool {
+		self.names.is_some()
+	}
+
+	/// Path of `path` on the wrapped backend
+	fn stored_path(&self, path: &Path) -> Result<PathBuf, VolumeError> {
+		let Some(names) = &self.names else {
+			return Ok(path.to_path_buf());
+		};
+
+		path.components()
+			.map(|component| match component {
+				Component::Normal(name) => {
+					let name = name.to_str().ok_or_else(|| {
+						VolumeError::InvalidData(format!("Name isn't valid UTF-8: {:?}", name))
+					})?;
+					names.encrypt(name).map(OsString::from)
+				}
+				other => Ok(other.as_os_str().to_owned()),
+			})
+			.collect()
+	}
+
+	/// Header of a stored file
+	async fn read_header(&self, stored: &Path) -> Result<Header, VolumeError> {
+		let header = self.inner.read_range(stored, 0..HEADER_SIZE).await?;
+		Header::from_bytes(&header).map_err(|e| crypto_error(stored, e))
+	}
+
+	/// Encrypt a whole file
+	async fn seal(&self, content: &[u8]) -> Result<Vec<u8>, VolumeError> {
+		let header = new_header()?;
+
+		StreamEncryption::encrypt(&*self.contents, header, content)
+			.try_concat()
+			.await
+			.map_err(|e| VolumeError::Platform(format!("Failed to encrypt: {}", e)))
+	}
+
+	/// Decrypt a whole file
+	async fn open(&self, stored_path: &Path, stored: &[u8]) -> Result<Vec<u8>, VolumeError> {
+		let header = Header::from_bytes(stored).map_err(|e| crypto_error(stored_path, e))?;
+		let size = header
+			.plain_text_size(stored.len() as u64)
+			.map_err(|e| crypto_error(stored_path, e))?;
+
+		let mut content = Vec::with_capacity(size as usize);
+		StreamDecryption::decrypt(
+			&*self.contents,
+			&header,
+			&stored[HEADER_SIZE as usize..],
+			&mut content,
+		)
+		.await
+		.map_err(|e| crypto_error(stored_path, e))?;
+
+		Ok(content)
+	}
+}
+
+impl fmt::Debug for EncryptedBackend {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("EncryptedBackend")
+			.field("inner", &self.inner)
+			.field("encrypt_names", &self.names.is_some())
+			.finish_non_exhaustive()
+	}
+}
+
+#[async_trait]
+impl VolumeBackend for EncryptedBackend {
+	async fn read(&self, path: &Path) -> Result<Bytes, VolumeError> {
+		let stored = self.stored_path(path)?;
+		let data = self.inner.read(&stored).await?;
+
+		self.open(&stored, &data).await.map(Bytes::from)
+	}
+
+	async fn read_range(&self, path: &Path, range: Range<u64>) -> Result<Bytes, VolumeError> {
+		let mut reader = self.open_reader(path, Some(range)).await?;
+
+		let mut data = BytesMut::new();
+		while let Some(chunk) = reader.next_chunk().await? {
+			data.extend_from_slice(&chunk);
+		}
+
+		Ok(data.freeze())
+	}
+
+	async fn write(&self, path: &Path, data: Bytes) -> Result<(), VolumeError> {
+		let stored = self.stored_path(path)?;
+		let sealed = self.seal(&data).await?;
+
+		self.inner.write(&stored, Bytes::from(sealed)).await
+	}
+
+	async fn read_dir(&self, path: &Path) -> Result<Vec<RawDirEntry>, VolumeError> {
+		let stored = self.stored_path(path)?;
+		let entries = self.inner.read_dir(&stored).await?;
+
+		Ok(entries
+			.into_iter()
+			.filter_map(|mut entry| {
+				if let Some(names) = &self.names {
+					match names.decrypt(&entry.name) {
+						Some(name) => entry.name = name,
+						None => {
+							// Something put there without this volume's key
+							debug!("Skipping undecryptable entry {:?}", entry.name);
+							return None;
+						}
+					}
+				}
+				if entry.kind == EntryKind::File {
+					entry.size = plaintext_size(entry.size).unwrap_or(0);
+				}
+				Some(entry)
+			})
+			.collect())
+	}
+
+	async fn metadata(&self, path: &Path) -> Result<RawMetadata, VolumeError> {
+		let stored = self.stored_path(path)?;
+		let mut metadata = self.inner.metadata(&stored).await?;
+
+		if metadata.kind == EntryKind::File {
+			metadata.size = plaintext_size(metadata.size).ok_or_else(|| not_encrypted(&stored))?;
+		}
+
+		Ok(metadata)
+	}
+
+	async fn exists(&self, path: &Path) -> Result<bool, VolumeError> {
+		self.inner.exists(&self.stored_path(path)?).await
+	}
+
+	async fn delete(&self, path: &Path) -> Result<(), VolumeError> {
+		self.inner.delete(&self.stored_path(path)?).await
+	}
+
+	async fn create_directory(&self, path: &Path, recursive: bool) -> Result<(), VolumeError> {
+		self.inner
+			.create_directory(&self.stored_path(path)?, recursive)
+			.await
+	}
+
+	async fn open_reader(
+		&self,
+		path: &Path,
+		range: Option<Range<u64>>,
+	) -> Result<Box<dyn VolumeReader>, VolumeError> {
+		let stored = self.stored_path(path)?;
+		let header = self.read_header(&stored).await?;
+		let stored_size = self.inner.metadata(&stored).await?.size;
+		let size = header
+			.plain_text_size(stored_size)
+			.map_err(|e| crypto_error(&stored, e))?;
+
+		let range = match range {
+			Some(range) => range.start.min(size)..range.end.min(size),
+			None => 0..size,
+		};
+		if range.start >= range.end {
+			return Ok(Box::new(EncryptedReader::empty(
+				self.contents.clone(),
+				header,
+			)));
+		}
+
+		let chunk_size = u64::from(header.chunk_size);
+		let first = range.start / chunk_size;
+		let last = (range.end - 1) / chunk_size;
+		let sealed_range =
+			header.chunk_offset(first)..header.chunk_offset(last + 1).min(stored_size);
+		let inner = self.inner.open_reader(&stored, Some(sealed_range)).await?;
+
+		Ok(Box::new(EncryptedReader {
+			inner: Some(inner),
+			key: self.contents.clone(),
+			header,
+			size,
+			index: first,
+			skip: (range.start - first * chunk_size) as usize,
+			remaining: range.end - range.start,
+			buffer: BytesMut::new(),
+		}))
+	}
+
+	async fn open_writer(
+		&self,
+		path: &Path,
+		append: bool,
+	) -> Result<Box<dyn VolumeWriter>, VolumeError> {
+		// The last chunk is sealed as the last one, there's nothing to append to
+		if append {
+			return Err(VolumeError::Platform(
+				"Encrypted volumes can't append to files".to_string(),
+			));
+		}
+
+		let stored = self.stored_path(path)?;
+		let header = new_header()?;
+
+		let mut inner = self.inner.open_writer(&stored, false).await?;
+		if let Err(e) = inner.write(Bytes::from(header.to_bytes().to_vec())).await {
+			let _ = inner.abort().await;
+			return Err(e);
+		}
+
+		Ok(Box::new(EncryptedWriter {
+			inner,
+			key: self.contents.clone(),
+			header,
+			index: 0,
+			buffer: BytesMut::new(),
+		}))
+	}
+
+	async fn copy(&self, from: &Path, to: &Path) -> Result<(), VolumeError> {
+		// Nothing in a stored file depends on its path, so it's copied as it is
+		self.inner
+			.copy(&self.stored_path(from)?, &self.stored_path(to)?)
+			.await
+	}
+
+	async fn rename(&self, from: &Path, to: &Path) -> Result<(), VolumeError> {
+		self.inner
+			.rename(&self.stored_path(from)?, &self.stored_path(to)?)
+			.await
+	}
+
+	fn capabilities(&self) -> BackendCapabilities {
+		BackendCapabilities {
+			append: false,
+			..self.inner.capabilities()
+		}
+	}
+
+	fn is_local(&self) -> bool {
+		self.inner.is_local()
+	}
+
+	fn backend_type(&self) -> BackendType {
+		self.inner.backend_type()
+	}
+}
+
+/// Reader for [`EncryptedBackend::open_reader`], decrypting chunk by chunk
+struct EncryptedReader {
+	inner: Option<Box<dyn VolumeReader>>,
+	key: Arc<SecretKey>,
+	header: Header,
+	/// Content size of the whole file
+	size: u64,
+	/// Index of the next chunk to decrypt
+	index: u64,
+	/// Bytes of the first chunk before the requested range
+	skip: usize,
+	/// Bytes of the requested range not returned yet
+	remaining: u64,
+	buffer: BytesMut,
+}
+
+impl EncryptedReader {
+	fn empty(key: Arc<SecretKey>, header: Header) -> Self {
+		Self {
+			inner: None,
+			key,
+			header,
+			size: 0,
+			index: 0,
+			skip: 0,
+			remaining: 0,
+			buffer: BytesMut::new(),
+		}
+	}
+}
+
+#[async_trait]
+impl VolumeReader for EncryptedReader {
+	async fn next_chunk(&mut self) -> Result<Option<Bytes>, VolumeError> {
+		let Some(inner) = self.inner.as_mut() else {
+			return Ok(None);
+		};
+		if self.remaining == 0 {
+			return Ok(None);
+		}
+
+		let chunk_size = u64::from(self.header.chunk_size);
+		let content_len = (self.size - self.index * chunk_size).min(chunk_size);
+		let sealed_len = (content_len + TAG_SIZE) as usize;
+		while self.buffer.len() < sealed_len {
+			match inner.next_chunk().await? {
+				Some(data) => self.buffer.extend_from_slice(&data),
+				None => {
+					return Err(VolumeError::InvalidData(
+						"Encrypted file ended early".to_string(),
+					))
+				}
+			}
+		}
+
+		let sealed = self.buffer.split_to(sealed_len);
+		let last = self.index + 1 == self.header.chunk_count(self.size);
+		let content = self
+			.key
+			.decrypt_chunk(&self.header, self.index, last, &sealed)
+			.map_err(|_| {
+				VolumeError::InvalidData(format!(
+					"Failed to decrypt chunk {}, wrong key or corrupted file",
+					self.index
+				))
+			})?;
+		let mut content = Bytes::from(content);
+		self.index += 1;
+
+		content = content.slice(std::mem::take(&mut self.skip)..);
+		content.truncate(self.remaining.min(content.len() as u64) as usize);
+		self.remaining -= content.len() as u64;
+
+		Ok(Some(content))
+	}
+}
+
+/// Writer for [`EncryptedBackend::open_writer`], sealing a chunk whenever one fills up
+struct EncryptedWriter {
+	inner: Box<dyn VolumeWriter>,
+	key: Arc<SecretKey>,
+	header: Header,
+	index: u64,
+	buffer: BytesMut,
+}
+
+impl EncryptedWriter {
+	fn seal(&self, chunk: &[u8], last: bool) -> Result<Bytes, VolumeError> {
+		self.key
+			.encrypt_chunk(&self.header, self.index, last, chunk)
+			.map(Bytes::from)
+			.map_err(|e| {
+				VolumeError::Platform(format!("Failed to encrypt chunk {}: {}", self.index, e))
+			})
+	}
+}
+
+#[async_trait]
+impl VolumeWriter for EncryptedWriter {
+	async fn write(&mut self, data: Bytes) -> Result<(), VolumeError> {
+		self.buffer.extend_from_slice(&data);
+
+		// A full chunk is held back until more follows, it might be the last one
+		let chunk_size = self.header.chunk_size as usize;
+		while self.buffer.len() > chunk_size {
+			let chunk = self.buffer.split_to(chunk_size);
+			let sealed = self.seal(&chunk, false)?;
+			self.inner.write(sealed).await?;
+			self.index += 1;
+		}
+
+		Ok(())
+	}
+
+	async fn close(mut self: Box<Self>) -> Result<(), VolumeError> {
+		let sealed = self.seal(&self.buffer, true)?;
+		self.inner.write(sealed).await?;
+
+		self.inner.close().await
+	}
+
+	async fn abort(self: Box<Self>) -> Result<(), VolumeError> {
+		self.inner.abort().await
+	}
+}
+
+/// Deterministic name encryption
+///
+/// The nonce is a keyed hash of the name, so equal names encrypt equally and nothing else
+/// about them leaks.
+struct NameCipher {
+	cipher: XChaCha20Poly1305,
+	nonce_key: [u8; 32],
+}
+
+impl NameCipher {
+	fn encrypt(&self, name: &str) -> Result<String, VolumeError> {
+		let hash = blake3::keyed_hash(&self.nonce_key, name.as_bytes());
+		let nonce = XNonce::from_slice(&hash.as_bytes()[..NONCE_SIZE]);
+
+		let cipher_text = self
+			.cipher
+			.encrypt(nonce, name.as_bytes())
+			.map_err(|e| VolumeError::Platform(format!("Failed to encrypt name: {}", e)))?;
+
+		let mut stored = nonce.to_vec();
+		stored.extend(cipher_text);
+		Ok(URL_SAFE_NO_PAD.encode(stored))
+	}
+
+	fn decrypt(&self, stored: &str) -> Option<String> {
+		let stored = URL_SAFE_NO_PAD.decode(stored).ok()?;
+		if stored.len() < NONCE_SIZE + TAG_SIZE as usize {
+			return None;
+		}
+
+		let (nonce, cipher_text) = stored.split_at(NONCE_SIZE);
+		let name = self
+			.cipher
+			.decrypt(XNonce::from_slice(nonce), cipher_text)
+			.ok()?;
+
+		String::from_utf8(name).ok()
+	}
+}
+
+/// Header the sizes of stored files are worked out with
+///
+/// Only the chunk size matters for sizes, and it's the same for every file written here.
+fn layout() -> Header {
+	Header {
+		algorithm: Algorithm::XChaCha20Poly1305,
+		kdf: Kdf::None,
+		chunk_size: CHUNK_SIZE,
+		nonce: Default::default(),
+	}
+}
+
+/// Header for a new file, with a random nonce
+fn new_header() -> Result<Header, VolumeError> {
+	let mut rng = CryptoRng::new()
+		.map_err(|e| VolumeError::Platform(format!("Failed to seed the RNG: {}", e)))?;
+
+	Header::new(Kdf::None, &mut rng)
+		.with_chunk_size(CHUNK_SIZE)
+		.map_err(|e| VolumeError::Platform(format!("Invalid chunk size: {}", e)))
+}
+
+/// Stored size of a file with `size` bytes of content
+pub fn encrypted_size(size: u64) -> u64 {
+	layout().cipher_text_size(size)
+}
+
+/// Content size of a stored file, None when the size can't be one of an encrypted file
+pub fn plaintext_size(stored_size: u64) -> Option<u64> {
+	layout().plain_text_size(stored_size).ok()
+}
+
+fn crypto_error(stored: &Path, e: sd_crypto::Error) -> VolumeError {
+	match e {
+		sd_crypto::Error::Decrypt => VolumeError::InvalidData(format!(
+			"Failed to decrypt {}, wrong key or corrupted file",
+			stored.display()
+		)),
+		e => VolumeError::InvalidData(format!(
+			"{} is not a readable encrypted file: {}",
+			stored.display(),
+			e
+		)),
+	}
+}
+
+fn not_encrypted(stored: &Path) -> VolumeError {
+	VolumeError::InvalidData(format!("{} is not an encrypted file", stored.display()))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::volume::backend::{CloudBackend, CloudServiceType};
+
+	const CHUNK: u64 = CHUNK_SIZE as u64;
+
+	fn memory_backend() -> Arc<dyn VolumeBackend> {
+		let operator = opendal::Operator::new(opendal::services::Memory::default())
+			.unwrap()
+			.finish();
+		Arc::new(CloudBackend::from_operator(
+			operator,
+			CloudServiceType::Other,
+		))
+	}
+
+	fn content(len: usize) -> Bytes {
+		(0..len).map(|i| (i % 251) as u8).collect()
+	}
+
+	#[test]
+	fn sizes_round_trip() {
+		for size in [0, 1, CHUNK - 1, CHUNK, CHUNK + 1, 3 * CHUNK] {
+			assert_eq!(plaintext_size(encrypted_size(size)), Some(size));
+		}
+		assert_eq!(plaintext_size(HEADER_SIZE), None);
+		assert_eq!(plaintext_size(HEADER_SIZE + (CHUNK + TAG_SIZE) + 1), None);
+	}
+
+	#[tokio::test]
+	async fn encrypts_contents_and_reads_ranges() {
+		let inner = memory_backend();
+		let backend = EncryptedBackend::new(inner.clone(), &[7; 32], false);
+		let data = content(3 * CHUNK as usize + 100);
+
+		backend
+			.write(Path::new("dir/file.bin"), data.clone())
+			.await
+			.unwrap();
+
+		let stored = inner.read(Path::new("dir/file.bin")).await.unwrap();
+		assert_eq!(stored.len() as u64, encrypted_size(data.len() as u64));
+		assert!(!stored.windows(64).any(|w| w == &data[..64]));
+
+		assert_eq!(backend.read(Path::new("dir/file.bin")).await.unwrap(), data);
+		assert_eq!(
+			backend
+				.metadata(Path::new("dir/file.bin"))
+				.await
+				.unwrap()
+				.size,
+			data.len() as u64
+		);
+
+		for range in [
+			0..10,
+			CHUNK - 5..CHUNK + 5,
+			CHUNK..2 * CHUNK,
+			3 * CHUNK + 50..data.len() as u64 + 1000,
+		] {
+			let expected = data.slice(range.start as usize..(range.end as usize).min(data.len()));
+			assert_eq!(
+				backend
+					.read_range(Path::new("dir/file.bin"), range)
+					.await
+					.unwrap(),
+				expected
+			);
+		}
+
+		// It's a plain SDCRYPT file that sd-crypto reads on its own
+		let header = Header::from_bytes(&stored).unwrap();
+		assert_eq!(header.kdf, Kdf::None);
+		assert_eq!(header.chunk_size, CHUNK_SIZE);
+		let mut decrypted = Vec::new();
+		StreamDecryption::decrypt(
+			&*backend.contents,
+			&header,
+			&stored[HEADER_SIZE as usize..],
+			&mut decrypted,
+		)
+		.await
+		.unwrap();
+		assert_eq!(decrypted, data);
+
+		// Someone else's key doesn't get anywhere
+		let other = EncryptedBackend::new(inner, &[8; 32], false);
+		assert!(other.read(Path::new("dir/file.bin")).await.is_err());
+	}
+
+	#[tokio::test]
+	async fn streams_writes_in_chunks() {
+		let backend = EncryptedBackend::new(memory_backend(), &[7; 32], false);
+		let data = content(2 * CHUNK as usize);
+
+		let mut writer = backend
+			.open_writer(Path::new("streamed.bin"), false)
+			.await
+			.unwrap();
+		for part in data.chunks(10_000) {
+			writer.write(Bytes::copy_from_slice(part)).await.unwrap();
+		}
+		writer.close().await.unwrap();
+
+		assert_eq!(backend.read(Path::new("streamed.bin")).await.unwrap(), data);
+
+		backend
+			.write(Path::new("empty.bin"), Bytes::new())
+			.await
+			.unwrap();
+		assert!(backend
+			.read(Path::new("empty.bin"))
+			.await
+			.unwrap()
+			.is_empty());
+		assert!(backend
+			.open_writer(Path::new("streamed.bin"), true)
+			.await
+			.is_err());
+	}
+
+	#[tokio::test]
+	async fn detects_truncation() {
+		let inner = memory_backend();
+		let backend = EncryptedBackend::new(inner.clone(), &[7; 32], false);
+		let data = content(2 * CHUNK as usize + 10);
+		backend
+			.write(Path::new("file.bin"), data.clone())
+			.await
+			.unwrap();
+
+		// Dropping the last chunk leaves a file made of whole chunks
+		let stored = inner.read(Path::new("file.bin")).await.unwrap();
+		let truncated = stored.slice(..(HEADER_SIZE + 2 * (CHUNK + TAG_SIZE)) as usize);
+		inner.write(Path::new("file.bin"), truncated).await.unwrap();
+
+		assert!(backend.read(Path::new("file.bin")).await.is_err());
+	}
+
+	#[tokio::test]
+	async fn encrypts_names() {
+		let inner = memory_backend();
+		let backend = EncryptedBackend::new(inner.clone(), &[7; 32], true);
+
+		backend
+			.write(Path::new("photos/holiday.jpg"), Bytes::from("jpeg"))
+			.await
+			.unwrap();
+		backend
+			.copy(
+				Path::new("photos/holiday.jpg"),
+				Path::new("photos/copy.jpg"),
+			)
+			.await
+			.unwrap();
+
+		assert!(!inner.exists(Path::new("photos/holiday.jpg")).await.unwrap());
+		assert!(backend
+			.exists(Path::new("photos/holiday.jpg"))
+			.await
+			.unwrap());
+
+		let mut names: Vec<_> = backend
+			.read_dir(Path::new("photos"))
+			.await
+			.unwrap()
+			.into_iter()
+			.map(|entry| (entry.name, entry.size))
+			.collect();
+		names.sort();
+		assert_eq!(
+			names,
+			vec![("copy.jpg".to_string(), 4), ("holiday.jpg".to_string(), 4)]
+		);
+
+		let stored_dir = inner.read_dir(Path::new("/")).await.unwrap();
+		assert_eq!(stored_dir.len(), 1);
+		assert_ne!(stored_dir[0].name, "photos");
+	}
+}
@@ -12,10 +12,14 @@ use std::time::SystemTime;
 use crate::ops::indexing::state::EntryKind;
 use crate::volume::error::VolumeError;
 
+pub mod archive;
 pub mod cloud;
+pub mod encrypted;
 pub mod local;
 
+pub use archive::{ArchiveBackend, ArchiveFormat};
 pub use cloud::CloudBackend;
+pub use encrypted::EncryptedBackend;
 pub use local::LocalBackend;
 
 /// Minimal I/O backend trait for volume operations
@@ -49,6 +53,40 @@ pub trait VolumeBackend: Send + Sync + Debug {
 	/// Create a directory at the specified path
 	async fn create_directory(&self, path: &Path, recursive: bool) -> Result<(), VolumeError>;
 
+	/// Open a reader that yields the file, or a byte range of it, in chunks
+	///
+	/// Unlike [`read`](Self::read) the file never has to fit in memory.
+	async fn open_reader(
+		&self,
+		path: &Path,
+		range: Option<Range<u64>>,
+	) -> Result<Box<dyn VolumeReader>, VolumeError>;
+
+	/// Open a writer that receives the file in chunks
+	///
+	/// Cloud services get these as a multipart upload. With `append` the chunks are
+	/// added to the end of the existing file, which is how interrupted transfers resume,
+	/// so only pass it when [`BackendCapabilities::append`] is set.
+	async fn open_writer(
+		&self,
+		path: &Path,
+		append: bool,
+	) -> Result<Box<dyn VolumeWriter>, VolumeError>;
+
+	/// Copy a file within this backend
+	///
+	/// Done by the storage itself when [`BackendCapabilities::server_side_copy`] is set,
+	/// otherwise the file is streamed through this process.
+	async fn copy(&self, from: &Path, to: &Path) -> Result<(), VolumeError>;
+
+	/// Move a file within this backend
+	///
+	/// Falls back to copy and delete when [`BackendCapabilities::rename`] isn't set.
+	async fn rename(&self, from: &Path, to: &Path) -> Result<(), VolumeError>;
+
+	/// What this backend can do natively
+	fn capabilities(&self) -> BackendCapabilities;
+
 	/// Backend identification (used to optimize operations)
 	fn is_local(&self) -> bool;
 
@@ -56,11 +94,81 @@ pub trait VolumeBackend: Send + Sync + Debug {
 	fn backend_type(&self) -> BackendType;
 }
 
+/// Chunked reader returned by [`VolumeBackend::open_reader`]
+#[async_trait]
+pub trait VolumeReader: Send {
+	/// Next chunk of the file, None once it's fully read
+	async fn next_chunk(&mut self) -> Result<Option<Bytes>, VolumeError>;
+}
+
+/// Chunked writer returned by [`VolumeBackend::open_writer`]
+#[async_trait]
+pub trait VolumeWriter: Send {
+	/// Write the next chunk of the file
+	async fn write(&mut self, data: Bytes) -> Result<(), VolumeError>;
+
+	/// Finish the file
+	///
+	/// On backends that support appending, whatever was written is kept even if the
+	/// file is incomplete, so a later transfer can pick up from there.
+	async fn close(self: Box<Self>) -> Result<(), VolumeError>;
+
+	/// Give up on the file, discarding what was written in this writer
+	async fn abort(self: Box<Self>) -> Result<(), VolumeError>;
+}
+
+/// Operations a backend supports natively, for callers picking the cheapest way to do something
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BackendCapabilities {
+	/// `read_range` fetches only the requested bytes
+	pub ranged_read: bool,
+	/// `open_writer` uploads in parts instead of buffering the file
+	pub multipart_write: bool,
+	/// `open_writer` can append to an existing file
+	pub append: bool,
+	/// `copy` happens on the storage without transferring the data
+	pub server_side_copy: bool,
+	/// `rename` is a single native operation instead of copy and delete
+	pub rename: bool,
+}
+
+/// Stream a file to another path of the same backend, for backends without native copy
+pub(crate) async fn stream_copy(
+	backend: &dyn VolumeBackend,
+	from: &Path,
+	to: &Path,
+) -> Result<u64, VolumeError> {
+	let mut reader = backend.open_reader(from, None).await?;
+	let mut writer = backend.open_writer(to, false).await?;
+	let mut copied = 0;
+
+	loop {
+		let chunk = match reader.next_chunk().await {
+			Ok(Some(chunk)) => chunk,
+			Ok(None) => break,
+			Err(e) => {
+				let _ = writer.abort().await;
+				return Err(e);
+			}
+		};
+		copied += chunk.len() as u64;
+		if let Err(e) = writer.write(chunk).await {
+			let _ = writer.abort().await;
+			return Err(e);
+		}
+	}
+
+	writer.close().await?;
+	Ok(copied)
+}
+
 /// Backend type identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BackendType {
 	Local,
 	Cloud(CloudServiceType),
+	/// Contents of an archive file, read-only
+	Archive(ArchiveFormat),
 }
 
 /// Cloud service type identifier
@@ -86,6 +194,10 @@ pub enum CloudServiceType {
 	Wasabi,
 	#[serde(rename = "spaces")]
 	DigitalOceanSpaces,
+	#[serde(rename = "sftp")]
+	Sftp,
+	#[serde(rename = "webdav")]
+	WebDav,
 	#[serde(rename = "cloud")]
 	Other,
 }
@@ -104,6 +216,8 @@ impl CloudServiceType {
 			Self::BackblazeB2 => "b2",
 			Self::Wasabi => "wasabi",
 			Self::DigitalOceanSpaces => "spaces",
+			Self::Sftp => "sftp",
+			Self::WebDav => "webdav",
 			Self::Other => "cloud",
 		}
 	}
@@ -121,6 +235,8 @@ impl CloudServiceType {
 			"b2" => Some(Self::BackblazeB2),
 			"wasabi" => Some(Self::Wasabi),
 			"spaces" => Some(Self::DigitalOceanSpaces),
+			"sftp" => Some(Self::Sftp),
+			"webdav" => Some(Self::WebDav),
 			_ => None,
 		}
 	}
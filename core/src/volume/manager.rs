@@ -332,6 +332,72 @@ impl VolumeManager {
 									continue;
 								}
 							}
+							crate::volume::CloudServiceType::Sftp => {
+								let config_str = |key: &str| {
+									cloud_config
+										.as_ref()
+										.and_then(|c| c.get(key))
+										.and_then(|v| v.as_str())
+										.map(String::from)
+								};
+								let port = cloud_config
+									.as_ref()
+									.and_then(|c| c.get("port"))
+									.and_then(|p| p.as_u64())
+									.and_then(|p| u16::try_from(p).ok());
+
+								match (&credential.data, config_str("host")) {
+									(
+										crate::crypto::cloud_credentials::CredentialData::SshKey {
+											username,
+											key_path,
+										},
+										Some(host),
+									) => {
+										crate::volume::CloudBackend::new_sftp(
+											host,
+											port,
+											username,
+											key_path.clone(),
+											config_str("root"),
+										).await
+									}
+									_ => {
+										warn!("Invalid credential or config for SFTP volume {}", fingerprint.0);
+										continue;
+									}
+								}
+							}
+							crate::volume::CloudServiceType::WebDav => {
+								let config_str = |key: &str| {
+									cloud_config
+										.as_ref()
+										.and_then(|c| c.get(key))
+										.and_then(|v| v.as_str())
+										.map(String::from)
+								};
+
+								match (&credential.data, config_str("endpoint")) {
+									(
+										crate::crypto::cloud_credentials::CredentialData::Password {
+											username,
+											password,
+										},
+										Some(endpoint),
+									) => {
+										crate::volume::CloudBackend::new_webdav(
+											endpoint,
+											username,
+											password,
+											config_str("root"),
+										).await
+									}
+									_ => {
+										warn!("Invalid credential or config for WebDAV volume {}", fingerprint.0);
+										continue;
+									}
+								}
+							}
 							_ => {
 								warn!("Unsupported cloud service type {:?} for volume {}", credential.service, fingerprint.0);
 								continue;
@@ -340,6 +406,33 @@ impl VolumeManager {
 
 						match backend_result {
 							Ok(backend) => {
+								let mut backend: Arc<dyn crate::volume::VolumeBackend> =
+									Arc::new(backend);
+
+								// Client-side encrypted volumes are wrapped with the key only this device holds
+								let config_flag = |key: &str| {
+									cloud_config
+										.as_ref()
+										.and_then(|c| c.get(key))
+										.and_then(|v| v.as_bool())
+										.unwrap_or(false)
+								};
+								if config_flag("encrypted") {
+									match key_manager.get_volume_key(&fingerprint.0).await {
+										Ok(volume_key) => {
+											backend = Arc::new(crate::volume::EncryptedBackend::new(
+												backend,
+												&volume_key,
+												config_flag("encrypt_names"),
+											));
+										}
+										Err(e) => {
+											warn!("No key for encrypted cloud volume {} on this device: {}", fingerprint.0, e);
+											continue;
+										}
+									}
+								}
+
 								let now = chrono::Utc::now();
 
 								let volume = Volume {
@@ -367,7 +460,7 @@ impl VolumeManager {
 									is_read_only: false,
 									is_mounted: true,
 									hardware_id: None,
-									backend: Some(Arc::new(backend)),
+									backend: Some(backend),
 									cloud_identifier: db_volume.cloud_identifier.clone(),
 									cloud_config,
 									apfs_container: None,
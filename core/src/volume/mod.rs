@@ -16,8 +16,9 @@ pub mod types;
 pub mod utils;
 
 pub use backend::{
-	BackendType, CloudBackend, CloudServiceType, LocalBackend, RawDirEntry, RawMetadata,
-	VolumeBackend,
+	ArchiveBackend, ArchiveFormat, BackendCapabilities, BackendType, CloudBackend,
+	CloudServiceType, EncryptedBackend, LocalBackend, RawDirEntry, RawMetadata, VolumeBackend,
+	VolumeReader, VolumeWriter,
 };
 pub use error::VolumeError;
 pub use manager::VolumeManager;
@@ -0,0 +1,325 @@
+//! Integration tests for collections
+//!
+//! Tests cover:
+//! - Adding files, skipping duplicates and appending positions
+//! - Reordering listed files first while the rest keep their order
+//! - Removing files and deleting a collection with its members
+//! - Applying synced membership changes that move a file
+
+mod helpers;
+
+use helpers::*;
+use sd_core::{
+	domain::CollectionFile,
+	infra::{
+		action::LibraryAction,
+		db::entities::{collection, collection_entry, entry},
+		sync::{ChangeType, SharedChangeEntry, Syncable, SystemTimeSource, HLC},
+	},
+	location::IndexMode,
+	ops::collections::{
+		CollectionAddFilesAction, CollectionAddFilesInput, CollectionCreateAction,
+		CollectionCreateInput, CollectionDeleteAction, CollectionDeleteInput,
+		CollectionRemoveFilesAction, CollectionRemoveFilesInput, CollectionReorderAction,
+		CollectionReorderInput,
+	},
+};
+use sea_orm::{ColumnTrait, EntityTrait, PaginatorTrait, QueryFilter};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Index a location holding `a.txt` to `e.txt` and return their entry UUIDs by name
+async fn index_files(harness: &IndexingHarness) -> anyhow::Result<HashMap<String, Uuid>> {
+	let test_location = harness.create_test_location("test_collection").await?;
+	for name in ["a", "b", "c", "d", "e"] {
+		test_location
+			.write_file(&format!("{}.txt", name), name)
+			.await?;
+	}
+	test_location
+		.index("Test Location", IndexMode::Shallow)
+		.await?;
+
+	Ok(entry::Entity::find()
+		.filter(entry::Column::Extension.eq("txt"))
+		.all(harness.library.db().conn())
+		.await?
+		.into_iter()
+		.filter_map(|entry| Some((entry.name, entry.uuid?)))
+		.collect())
+}
+
+async fn create_collection(harness: &IndexingHarness, name: &str) -> anyhow::Result<Uuid> {
+	Ok(CollectionCreateAction::from_input(CollectionCreateInput {
+		name: name.to_string(),
+		description: None,
+	})
+	.unwrap()
+	.execute(harness.library.clone(), harness.core.context.clone())
+	.await?
+	.collection
+	.id)
+}
+
+async fn collection_db_id(harness: &IndexingHarness, collection_id: Uuid) -> anyhow::Result<i32> {
+	Ok(collection::Entity::find()
+		.filter(collection::Column::Uuid.eq(collection_id))
+		.one(harness.library.db().conn())
+		.await?
+		.expect("collection row missing")
+		.id)
+}
+
+/// Member file UUIDs in collection order, with their positions
+async fn members(
+	harness: &IndexingHarness,
+	collection_id: Uuid,
+) -> anyhow::Result<Vec<(Uuid, i32)>> {
+	let db_id = collection_db_id(harness, collection_id).await?;
+	Ok(
+		CollectionFile::for_collection(harness.library.db().conn(), db_id)
+			.await?
+			.into_iter()
+			.map(|member| (member.file.id, member.position))
+			.collect(),
+	)
+}
+
+async fn add_files(
+	harness: &IndexingHarness,
+	collection_id: Uuid,
+	file_ids: Vec<Uuid>,
+) -> anyhow::Result<u32> {
+	Ok(
+		CollectionAddFilesAction::from_input(CollectionAddFilesInput {
+			collection_id,
+			file_ids,
+		})
+		.unwrap()
+		.execute(harness.library.clone(), harness.core.context.clone())
+		.await?
+		.added,
+	)
+}
+
+async fn reorder(
+	harness: &IndexingHarness,
+	collection_id: Uuid,
+	file_ids: Vec<Uuid>,
+) -> Result<(), sd_core::infra::action::error::ActionError> {
+	CollectionReorderAction::from_input(CollectionReorderInput {
+		collection_id,
+		file_ids,
+	})
+	.unwrap()
+	.execute(harness.library.clone(), harness.core.context.clone())
+	.await
+	.map(|_| ())
+}
+
+#[tokio::test]
+async fn test_collection_add_files_dedupes_and_appends() -> anyhow::Result<()> {
+	let harness = IndexingHarnessBuilder::new("collection_add_files")
+		.disable_watcher()
+		.build()
+		.await?;
+	let files = index_files(&harness).await?;
+	let collection_id = create_collection(&harness, "Favourites").await?;
+
+	// Duplicates within one request are only added once
+	let added = add_files(
+		&harness,
+		collection_id,
+		vec![files["b"], files["a"], files["b"]],
+	)
+	.await?;
+	assert_eq!(added, 2);
+
+	// Files already in the collection keep their place, new ones go after the last
+	let added = add_files(&harness, collection_id, vec![files["a"], files["c"]]).await?;
+	assert_eq!(added, 1);
+
+	assert_eq!(
+		members(&harness, collection_id).await?,
+		vec![(files["b"], 0), (files["a"], 1), (files["c"], 2)]
+	);
+
+	// Unknown files are rejected without adding anything
+	assert!(
+		add_files(&harness, collection_id, vec![files["d"], Uuid::new_v4()])
+			.await
+			.is_err()
+	);
+	assert_eq!(members(&harness, collection_id).await?.len(), 3);
+
+	harness.shutdown().await?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_collection_reorder() -> anyhow::Result<()> {
+	let harness = IndexingHarnessBuilder::new("collection_reorder")
+		.disable_watcher()
+		.build()
+		.await?;
+	let files = index_files(&harness).await?;
+	let collection_id = create_collection(&harness, "Favourites").await?;
+	add_files(
+		&harness,
+		collection_id,
+		vec![files["a"], files["b"], files["c"], files["d"]],
+	)
+	.await?;
+
+	// Listed files come first in the given order, the rest keep theirs
+	reorder(&harness, collection_id, vec![files["d"], files["b"]]).await?;
+	assert_eq!(
+		members(&harness, collection_id).await?,
+		vec![
+			(files["d"], 0),
+			(files["b"], 1),
+			(files["a"], 2),
+			(files["c"], 3)
+		]
+	);
+
+	// Files outside the collection are rejected and the order is left alone
+	assert!(
+		reorder(&harness, collection_id, vec![files["a"], files["e"]])
+			.await
+			.is_err()
+	);
+	assert!(reorder(&harness, collection_id, vec![Uuid::new_v4()])
+		.await
+		.is_err());
+	assert_eq!(
+		members(&harness, collection_id)
+			.await?
+			.into_iter()
+			.map(|(file_id, _)| file_id)
+			.collect::<Vec<_>>(),
+		vec![files["d"], files["b"], files["a"], files["c"]]
+	);
+
+	harness.shutdown().await?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_collection_remove_files_and_delete() -> anyhow::Result<()> {
+	let harness = IndexingHarnessBuilder::new("collection_remove_delete")
+		.disable_watcher()
+		.build()
+		.await?;
+	let files = index_files(&harness).await?;
+	let collection_id = create_collection(&harness, "Favourites").await?;
+	let other_id = create_collection(&harness, "Other").await?;
+	add_files(
+		&harness,
+		collection_id,
+		vec![files["a"], files["b"], files["c"]],
+	)
+	.await?;
+	add_files(&harness, other_id, vec![files["a"]]).await?;
+
+	let removed = CollectionRemoveFilesAction::from_input(CollectionRemoveFilesInput {
+		collection_id,
+		file_ids: vec![files["b"], files["e"]],
+	})
+	.unwrap()
+	.execute(harness.library.clone(), harness.core.context.clone())
+	.await?
+	.removed;
+	assert_eq!(removed, 1);
+	assert_eq!(
+		members(&harness, collection_id)
+			.await?
+			.into_iter()
+			.map(|(file_id, _)| file_id)
+			.collect::<Vec<_>>(),
+		vec![files["a"], files["c"]]
+	);
+
+	// Deleting a collection takes its members with it, and only its members
+	let db_id = collection_db_id(&harness, collection_id).await?;
+	let deleted = CollectionDeleteAction::from_input(CollectionDeleteInput { collection_id })
+		.unwrap()
+		.execute(harness.library.clone(), harness.core.context.clone())
+		.await?;
+	assert!(deleted.success);
+
+	let db = harness.library.db().conn();
+	assert!(collection::Entity::find()
+		.filter(collection::Column::Uuid.eq(collection_id))
+		.one(db)
+		.await?
+		.is_none());
+	assert_eq!(
+		collection_entry::Entity::find()
+			.filter(collection_entry::Column::CollectionId.eq(db_id))
+			.count(db)
+			.await?,
+		0
+	);
+	assert_eq!(members(&harness, other_id).await?, vec![(files["a"], 0)]);
+
+	harness.shutdown().await?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_collection_sync_apply_position() -> anyhow::Result<()> {
+	let harness = IndexingHarnessBuilder::new("collection_sync_position")
+		.disable_watcher()
+		.build()
+		.await?;
+	let files = index_files(&harness).await?;
+	let collection_id = create_collection(&harness, "Favourites").await?;
+	add_files(&harness, collection_id, vec![files["a"], files["b"]]).await?;
+
+	let db = harness.library.db().conn();
+	let db_id = collection_db_id(&harness, collection_id).await?;
+	let member_a = collection_entry::Entity::find()
+		.filter(collection_entry::Column::CollectionId.eq(db_id))
+		.filter(collection_entry::Column::Position.eq(0))
+		.one(db)
+		.await?
+		.expect("first member missing");
+
+	// Serialize the membership the way a peer would send it, with FKs as UUIDs
+	let mut data = collection_entry::Model::query_for_sync(None, None, None, 100, db)
+		.await?
+		.into_iter()
+		.find(|(uuid, _, _)| *uuid == member_a.uuid)
+		.map(|(_, data, _)| data)
+		.expect("member missing from sync query");
+
+	let entry = |change_type: ChangeType, data: serde_json::Value| SharedChangeEntry {
+		hlc: HLC::now(harness.device_id, &SystemTimeSource),
+		model_type: "collection_entry".to_string(),
+		record_uuid: member_a.uuid,
+		change_type,
+		data,
+	};
+
+	// A peer moved the file to the end
+	data["position"] = serde_json::json!(2);
+	data["version"] = serde_json::json!(member_a.version + 1);
+	collection_entry::Model::apply_shared_change(entry(ChangeType::Update, data.clone()), db)
+		.await?;
+	assert_eq!(
+		members(&harness, collection_id).await?,
+		vec![(files["b"], 1), (files["a"], 2)]
+	);
+
+	// Devices that predate ordering send no position
+	data.as_object_mut().unwrap().remove("position");
+	collection_entry::Model::apply_shared_change(entry(ChangeType::Update, data), db).await?;
+	assert_eq!(
+		members(&harness, collection_id).await?,
+		vec![(files["a"], 0), (files["b"], 1)]
+	);
+
+	harness.shutdown().await?;
+	Ok(())
+}
@@ -105,6 +105,7 @@ async fn test_entry_metadata_preservation_on_move() {
 				name: Some("Source".to_string()),
 				mode: IndexMode::Deep,
 				job_policies: None,
+				indexer_limits: None,
 			})
 			.unwrap(),
 		)
@@ -413,6 +414,7 @@ async fn test_child_entry_metadata_preservation_on_parent_move() {
 		name: Some("Source".to_string()),
 		mode: IndexMode::Deep,
 		job_policies: None,
+		indexer_limits: None,
 	};
 	let add_loc_action = LocationAddAction::from_input(add_loc_input).unwrap();
 	let _add_output = action_manager
@@ -15,7 +15,22 @@
 //! ```
 
 use sd_core::{
-	infra::db::entities::{entry, sync_conduit},
+	domain::SdPath,
+	infra::{
+		action::LibraryAction,
+		db::entities::{entry, sync_conduit, sync_conflict},
+	},
+	ops::{
+		file_sync::{
+			FileSyncCreateAction, FileSyncCreateInput, FileSyncNowAction, FileSyncNowInput,
+		},
+		indexing::IndexMode,
+		locations::add::action::{LocationAddAction, LocationAddInput},
+	},
+	service::file_sync::{
+		conflict::ConflictStrategy,
+		resolver::{ConflictType, SyncConflict},
+	},
 	Core,
 };
 use sea_orm::{ActiveModelTrait, Set};
@@ -151,6 +166,7 @@ async fn test_conduit_creation() {
 			target_dir.id,
 			sync_conduit::SyncMode::Mirror,
 			"manual".to_string(),
+			ConflictStrategy::NewestWins,
 		)
 		.await
 		.unwrap();
@@ -194,6 +210,7 @@ async fn test_conduit_list() {
 			dir2.id,
 			sync_conduit::SyncMode::Mirror,
 			"manual".to_string(),
+			ConflictStrategy::NewestWins,
 		)
 		.await
 		.unwrap();
@@ -204,6 +221,7 @@ async fn test_conduit_list() {
 			dir3.id,
 			sync_conduit::SyncMode::Bidirectional,
 			"interval:5m".to_string(),
+			ConflictStrategy::NewestWins,
 		)
 		.await
 		.unwrap();
@@ -241,6 +259,7 @@ async fn test_conduit_enable_disable() {
 			target.id,
 			sync_conduit::SyncMode::Mirror,
 			"manual".to_string(),
+			ConflictStrategy::NewestWins,
 		)
 		.await
 		.unwrap();
@@ -289,6 +308,7 @@ async fn test_mirror_sync_empty_to_empty() {
 			target.id,
 			sync_conduit::SyncMode::Mirror,
 			"manual".to_string(),
+			ConflictStrategy::NewestWins,
 		)
 		.await
 		.unwrap();
@@ -340,6 +360,7 @@ async fn test_mirror_sync_with_files() {
 			target.id,
 			sync_conduit::SyncMode::Mirror,
 			"manual".to_string(),
+			ConflictStrategy::NewestWins,
 		)
 		.await
 		.unwrap();
@@ -402,6 +423,7 @@ async fn test_sync_resolver_calculates_operations() {
 			target.id,
 			sync_conduit::SyncMode::Mirror,
 			"manual".to_string(),
+			ConflictStrategy::NewestWins,
 		)
 		.await
 		.unwrap();
@@ -443,6 +465,7 @@ async fn test_cannot_sync_disabled_conduit() {
 			target.id,
 			sync_conduit::SyncMode::Mirror,
 			"manual".to_string(),
+			ConflictStrategy::NewestWins,
 		)
 		.await
 		.unwrap();
@@ -481,6 +504,7 @@ async fn test_cannot_sync_same_conduit_twice() {
 			target.id,
 			sync_conduit::SyncMode::Mirror,
 			"manual".to_string(),
+			ConflictStrategy::NewestWins,
 		)
 		.await
 		.unwrap();
@@ -512,6 +536,7 @@ async fn test_generation_tracking() {
 			target.id,
 			sync_conduit::SyncMode::Mirror,
 			"manual".to_string(),
+			ConflictStrategy::NewestWins,
 		)
 		.await
 		.unwrap();
@@ -535,3 +560,206 @@ async fn test_generation_tracking() {
 	println!("✓ Generation tracking working correctly");
 	println!("  Generation increments on each sync: 0 -> 1 -> 2");
 }
+
+#[tokio::test]
+async fn test_conflicts_persist_until_applied() {
+	let setup = FileSyncTestSetup::new().await.unwrap();
+
+	let source = setup.create_entry("source", 1, None, 0).await.unwrap();
+	let target = setup.create_entry("target", 1, None, 0).await.unwrap();
+	let source_file = setup
+		.create_entry("notes.txt", 0, Some(source.id), 100)
+		.await
+		.unwrap();
+	let target_file = setup
+		.create_entry("notes.txt", 0, Some(target.id), 120)
+		.await
+		.unwrap();
+
+	let file_sync = setup.library.file_sync_service().unwrap();
+	let conduit_manager = file_sync.conduit_manager();
+
+	let conduit = conduit_manager
+		.create_conduit(
+			source.id,
+			target.id,
+			sync_conduit::SyncMode::Bidirectional,
+			"manual".to_string(),
+			ConflictStrategy::PromptUser,
+		)
+		.await
+		.unwrap();
+	assert_eq!(conduit.conflict_strategy, "prompt_user");
+
+	let generation = conduit_manager
+		.create_generation(conduit.id, 1)
+		.await
+		.unwrap();
+	let conflict = SyncConflict {
+		relative_path: "notes.txt".into(),
+		source_entry: source_file,
+		target_entry: target_file,
+		conflict_type: ConflictType::BothModified,
+	};
+
+	// A conflict still waiting for a decision is only recorded once
+	let recorded = conduit_manager
+		.record_conflicts(conduit.id, generation.id, &[conflict.clone()])
+		.await
+		.unwrap();
+	assert_eq!(recorded, 1);
+	let recorded = conduit_manager
+		.record_conflicts(conduit.id, generation.id, &[conflict])
+		.await
+		.unwrap();
+	assert_eq!(recorded, 0);
+	assert_eq!(
+		conduit_manager
+			.count_unresolved_conflicts(conduit.id)
+			.await
+			.unwrap(),
+		1
+	);
+
+	let pending = conduit_manager
+		.list_conflicts(conduit.id, false)
+		.await
+		.unwrap();
+	assert_eq!(pending.len(), 1);
+	let (record, detected_by) = &pending[0];
+	assert_eq!(record.relative_path, "notes.txt");
+	assert_eq!(record.source_size, 100);
+	assert_eq!(record.target_size, 120);
+	assert_eq!(detected_by.as_ref().map(|g| g.generation), Some(1));
+
+	let resolved = conduit_manager
+		.resolve_conflict(record.id, sync_conflict::Resolution::KeepBoth)
+		.await
+		.unwrap();
+	assert_eq!(resolved.resolution.as_deref(), Some("keep_both"));
+	assert!(conduit_manager
+		.list_conflicts(conduit.id, false)
+		.await
+		.unwrap()
+		.is_empty());
+	assert_eq!(
+		conduit_manager
+			.decided_conflicts(conduit.id)
+			.await
+			.unwrap()
+			.len(),
+		1
+	);
+
+	// Once a generation applied it, the decision is final
+	conduit_manager
+		.mark_conflicts_applied(vec![record.id])
+		.await
+		.unwrap();
+	assert!(conduit_manager
+		.decided_conflicts(conduit.id)
+		.await
+		.unwrap()
+		.is_empty());
+	assert!(conduit_manager
+		.resolve_conflict(record.id, sync_conflict::Resolution::KeepSource)
+		.await
+		.is_err());
+	assert_eq!(
+		conduit_manager
+			.list_conflicts(conduit.id, true)
+			.await
+			.unwrap()
+			.len(),
+		1
+	);
+}
+
+/// Wait for a job to finish, jobs that already finished are no longer tracked
+async fn wait_for_job(library: &sd_core::library::Library, job_id: Uuid) -> anyhow::Result<()> {
+	if let Some(handle) = library
+		.jobs()
+		.get_job(sd_core::infra::job::types::JobId(job_id))
+		.await
+	{
+		tokio::time::timeout(std::time::Duration::from_secs(60), handle.wait()).await??;
+	}
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_create_and_sync_now_actions() {
+	let setup = FileSyncTestSetup::new().await.unwrap();
+	let library = setup.library.clone();
+	let context = setup.core.context.clone();
+
+	// Index a real location holding the source and target directories
+	let location_dir = TempDir::new().unwrap();
+	let source_path = location_dir.path().join("source");
+	let target_path = location_dir.path().join("target");
+	create_test_file(&source_path.join("notes.txt"), "hello")
+		.await
+		.unwrap();
+	create_test_file(&source_path.join("nested/photo.raw"), "pixels")
+		.await
+		.unwrap();
+	fs::create_dir_all(&target_path).await.unwrap();
+
+	let location = LocationAddAction::from_input(LocationAddInput {
+		path: SdPath::local(location_dir.path()),
+		name: Some("File Sync Location".to_string()),
+		mode: IndexMode::Deep,
+		job_policies: None,
+		indexer_limits: None,
+	})
+	.unwrap()
+	.execute(library.clone(), context.clone())
+	.await
+	.unwrap();
+	wait_for_job(&library, location.job_id.expect("indexing job"))
+		.await
+		.unwrap();
+
+	// Create the conduit through the action, which resolves both paths to entries
+	let conduit = FileSyncCreateAction::from_input(FileSyncCreateInput {
+		source: SdPath::local(&source_path),
+		target: SdPath::local(&target_path),
+		mode: sync_conduit::SyncMode::Mirror,
+		schedule: "manual".to_string(),
+		conflict_strategy: ConflictStrategy::NewestWins,
+		parallel_transfers: None,
+		bandwidth_limit_mbps: None,
+	})
+	.unwrap()
+	.execute(library.clone(), context.clone())
+	.await
+	.unwrap();
+	assert_eq!(conduit.schedule, "manual");
+
+	let output = FileSyncNowAction::from_input(FileSyncNowInput {
+		conduit_id: conduit.id,
+	})
+	.unwrap()
+	.execute(library.clone(), context.clone())
+	.await
+	.unwrap();
+	assert_eq!(output.generation, 1);
+	assert!(!output.job_ids.is_empty());
+
+	for job_id in output.job_ids {
+		wait_for_job(&library, job_id).await.unwrap();
+	}
+
+	assert_eq!(
+		fs::read_to_string(target_path.join("notes.txt"))
+			.await
+			.unwrap(),
+		"hello"
+	);
+	assert_eq!(
+		fs::read_to_string(target_path.join("nested/photo.raw"))
+			.await
+			.unwrap(),
+		"pixels"
+	);
+}
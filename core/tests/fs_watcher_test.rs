@@ -510,6 +510,7 @@ impl TestHarness {
 			name: Some("SD_FS_WATCHER_TEST_DIR".to_string()),
 			mode: IndexMode::Deep,
 			job_policies: None,
+			indexer_limits: None,
 		};
 
 		let action = LocationAddAction::new(input);
@@ -213,6 +213,7 @@ impl IndexingHarness {
 				mode,
 				None, // No action context for tests
 				None, // Use default job policies
+				None, // No indexer limits
 				&self.core.context.volume_manager,
 			)
 			.await?;
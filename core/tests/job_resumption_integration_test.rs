@@ -277,6 +277,7 @@ async fn start_and_interrupt_job(
 		name: Some("Test Location".to_string()),
 		mode: IndexMode::Content,
 		job_policies: None,
+		indexer_limits: None,
 	};
 
 	let location_action = LocationAddAction::from_input(location_input)
@@ -147,6 +147,7 @@ async fn test_location_export_import() -> Result<(), Box<dyn std::error::Error +
 		name: Some("Test Location".to_string()),
 		mode: IndexMode::Content,
 		job_policies: None,
+		indexer_limits: None,
 	};
 
 	let location_action = LocationAddAction::from_input(location_input)
@@ -513,6 +514,7 @@ async fn test_import_links_existing_content_identities(
 		name: Some("Source Location".to_string()),
 		mode: IndexMode::Content,
 		job_policies: None,
+		indexer_limits: None,
 	};
 
 	let location_action = LocationAddAction::from_input(location_input)
@@ -588,6 +590,7 @@ async fn test_import_links_existing_content_identities(
 		name: Some("Existing Location".to_string()),
 		mode: IndexMode::Content,
 		job_policies: None,
+		indexer_limits: None,
 	};
 
 	let location_action2 = LocationAddAction::from_input(location_input2)
@@ -244,6 +244,7 @@ async fn capture_event_fixtures_for_typescript() -> Result<(), Box<dyn std::erro
 		name: Some("Test Location".to_string()),
 		mode: sd_core::ops::indexing::IndexMode::Deep,
 		job_policies: None,
+		indexer_limits: None,
 	};
 
 	let action = LocationAddAction::from_input(location_input)
@@ -0,0 +1,322 @@
+//! Integration tests for saved searches
+//!
+//! Tests cover:
+//! - Create, update and delete actions persisting the definition
+//! - Running a saved search against the persistent index
+//! - Applying synced inserts, updates and deletes from another device
+//! - Result count refreshes only emitting counts that changed
+
+mod helpers;
+
+use helpers::*;
+use sd_core::{
+	domain::SearchDefinition,
+	infra::{
+		action::LibraryAction,
+		api::SessionContext,
+		db::entities::saved_search,
+		event::{Event, EventSubscriber},
+		query::LibraryQuery,
+		sync::{ChangeType, SharedChangeEntry, Syncable, SystemTimeSource, HLC},
+	},
+	location::IndexMode,
+	ops::search::{
+		input::{SearchFilters, SearchMode, SearchScope, SortOptions},
+		saved::{
+			SavedSearchCounts, SavedSearchCreateAction, SavedSearchCreateInput,
+			SavedSearchDeleteAction, SavedSearchDeleteInput, SavedSearchRunQuery,
+			SavedSearchRunQueryInput, SavedSearchUpdateAction, SavedSearchUpdateInput,
+		},
+	},
+};
+use sea_orm::{ActiveModelTrait, ColumnTrait, EntityTrait, QueryFilter, Set};
+use tokio::time::Duration;
+use uuid::Uuid;
+
+fn definition(query: &str) -> SearchDefinition {
+	SearchDefinition {
+		query: query.to_string(),
+		scope: SearchScope::Library,
+		mode: SearchMode::Normal,
+		filters: SearchFilters::default(),
+		sort: SortOptions::default(),
+	}
+}
+
+async fn find_model(
+	harness: &IndexingHarness,
+	saved_search_id: Uuid,
+) -> anyhow::Result<Option<saved_search::Model>> {
+	Ok(saved_search::Entity::find()
+		.filter(saved_search::Column::Uuid.eq(saved_search_id))
+		.one(harness.library.db().conn())
+		.await?)
+}
+
+/// Collect the saved search events already waiting on the subscriber
+fn drain_saved_search_events(subscriber: &mut EventSubscriber) -> Vec<Event> {
+	let mut events = Vec::new();
+	while let Ok(event) = subscriber.try_recv() {
+		if event.resource_type() == Some("saved_search") {
+			events.push(event);
+		}
+	}
+	events
+}
+
+#[tokio::test]
+async fn test_saved_search_create_update_delete() -> anyhow::Result<()> {
+	let harness = IndexingHarnessBuilder::new("saved_search_crud")
+		.disable_watcher()
+		.build()
+		.await?;
+	let library = harness.library.clone();
+	let context = harness.core.context.clone();
+
+	assert!(SavedSearchCreateAction::from_input(SavedSearchCreateInput {
+		name: "   ".to_string(),
+		definition: definition("report"),
+	})
+	.is_err());
+
+	let created = SavedSearchCreateAction::from_input(SavedSearchCreateInput {
+		name: " Reports ".to_string(),
+		definition: definition("report"),
+	})
+	.unwrap()
+	.execute(library.clone(), context.clone())
+	.await?
+	.saved_search;
+	assert_eq!(created.name, "Reports");
+	assert_eq!(created.result_count, Some(0));
+
+	let model = find_model(&harness, created.id)
+		.await?
+		.expect("saved search row missing");
+	assert_eq!(model.name, "Reports");
+
+	let updated = SavedSearchUpdateAction::from_input(SavedSearchUpdateInput {
+		saved_search_id: created.id,
+		name: None,
+		definition: Some(definition("invoice")),
+	})
+	.unwrap()
+	.execute(library.clone(), context.clone())
+	.await?
+	.saved_search;
+	assert_eq!(updated.name, "Reports", "Name should be left unchanged");
+	assert_eq!(updated.definition.query, "invoice");
+
+	let model = find_model(&harness, created.id)
+		.await?
+		.expect("saved search row missing");
+	let stored: SearchDefinition = serde_json::from_value(model.definition)?;
+	assert_eq!(stored.query, "invoice");
+
+	let deleted = SavedSearchDeleteAction::from_input(SavedSearchDeleteInput {
+		saved_search_id: created.id,
+	})
+	.unwrap()
+	.execute(library.clone(), context.clone())
+	.await?;
+	assert!(deleted.success);
+	assert!(find_model(&harness, created.id).await?.is_none());
+
+	harness.shutdown().await?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_saved_search_run() -> anyhow::Result<()> {
+	let harness = IndexingHarnessBuilder::new("saved_search_run")
+		.disable_watcher()
+		.build()
+		.await?;
+
+	let test_location = harness.create_test_location("test_saved_search").await?;
+	test_location
+		.write_file("report.txt", "Annual report content")
+		.await?;
+	test_location
+		.write_file("notes.md", "Meeting notes")
+		.await?;
+	test_location
+		.index("Test Location", IndexMode::Shallow)
+		.await?;
+
+	tokio::time::sleep(Duration::from_millis(500)).await;
+
+	let created = SavedSearchCreateAction::from_input(SavedSearchCreateInput {
+		name: "Reports".to_string(),
+		definition: definition("report"),
+	})
+	.unwrap()
+	.execute(harness.library.clone(), harness.core.context.clone())
+	.await?
+	.saved_search;
+	assert_eq!(created.result_count, Some(1));
+
+	let device_id = sd_core::device::get_current_device_id();
+	let device_name = sd_core::device::get_current_device_slug();
+	let mut session = SessionContext::device_session(device_id, device_name);
+	session.current_library_id = Some(harness.library.id());
+
+	let output = SavedSearchRunQuery::from_input(SavedSearchRunQueryInput {
+		saved_search_id: created.id,
+		pagination: None,
+	})?
+	.execute(harness.core.context.clone(), session)
+	.await?;
+
+	assert_eq!(output.saved_search.id, created.id);
+	assert_eq!(output.saved_search.result_count, Some(1));
+	assert_eq!(output.results.results.len(), 1);
+	assert_eq!(output.results.results[0].file.name, "report");
+
+	harness.shutdown().await?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_saved_search_sync_apply() -> anyhow::Result<()> {
+	let harness = IndexingHarnessBuilder::new("saved_search_sync_apply")
+		.disable_watcher()
+		.build()
+		.await?;
+	let db = harness.library.db().conn();
+
+	// Serialize a saved search the way a peer would send it
+	let created = SavedSearchCreateAction::from_input(SavedSearchCreateInput {
+		name: "Reports".to_string(),
+		definition: definition("report"),
+	})
+	.unwrap()
+	.execute(harness.library.clone(), harness.core.context.clone())
+	.await?
+	.saved_search;
+	let mut data = find_model(&harness, created.id)
+		.await?
+		.expect("saved search row missing")
+		.to_sync_json()?;
+
+	let remote_id = Uuid::new_v4();
+	let entry = |change_type: ChangeType, data: serde_json::Value| SharedChangeEntry {
+		hlc: HLC::now(harness.device_id, &SystemTimeSource),
+		model_type: "saved_search".to_string(),
+		record_uuid: remote_id,
+		change_type,
+		data,
+	};
+
+	saved_search::Model::apply_shared_change(entry(ChangeType::Insert, data.clone()), db).await?;
+	let remote = find_model(&harness, remote_id)
+		.await?
+		.expect("synced saved search missing");
+	assert_eq!(remote.name, "Reports");
+
+	data["name"] = serde_json::json!("Quarterly reports");
+	data["definition"] = serde_json::to_value(definition("quarterly"))?;
+	saved_search::Model::apply_shared_change(entry(ChangeType::Update, data.clone()), db).await?;
+	let remote = find_model(&harness, remote_id)
+		.await?
+		.expect("synced saved search missing");
+	assert_eq!(remote.name, "Quarterly reports");
+	let stored: SearchDefinition = serde_json::from_value(remote.definition)?;
+	assert_eq!(stored.query, "quarterly");
+
+	saved_search::Model::apply_shared_change(
+		entry(ChangeType::Delete, serde_json::Value::Null),
+		db,
+	)
+	.await?;
+	assert!(find_model(&harness, remote_id).await?.is_none());
+	assert!(
+		find_model(&harness, created.id).await?.is_some(),
+		"Local saved search should be untouched"
+	);
+
+	harness.shutdown().await?;
+	Ok(())
+}
+
+#[tokio::test]
+async fn test_saved_search_counts_emit_only_on_change() -> anyhow::Result<()> {
+	let harness = IndexingHarnessBuilder::new("saved_search_counts")
+		.disable_watcher()
+		.build()
+		.await?;
+
+	let test_location = harness.create_test_location("test_saved_search").await?;
+	test_location
+		.write_file("report.txt", "Annual report content")
+		.await?;
+	test_location
+		.index("Test Location", IndexMode::Shallow)
+		.await?;
+
+	// Let the library's statistics listener recount after indexing, so it stays quiet
+	tokio::time::sleep(Duration::from_secs(6)).await;
+
+	let created = SavedSearchCreateAction::from_input(SavedSearchCreateInput {
+		name: "Reports".to_string(),
+		definition: definition("report"),
+	})
+	.unwrap()
+	.execute(harness.library.clone(), harness.core.context.clone())
+	.await?
+	.saved_search;
+
+	let mut subscriber = harness.library.event_bus().subscribe();
+	let mut counts = SavedSearchCounts::default();
+
+	// The first refresh has nothing to compare against, so every count goes out
+	counts.refresh(&harness.library).await?;
+	let events = drain_saved_search_events(&mut subscriber);
+	assert_eq!(events.len(), 1);
+	match &events[0] {
+		Event::ResourceChanged { resource, .. } => {
+			assert_eq!(resource["id"], serde_json::json!(created.id));
+			assert_eq!(resource["result_count"], serde_json::json!(1));
+		}
+		other => panic!("Unexpected event {:?}", other),
+	}
+
+	// Nothing changed, nothing is emitted
+	counts.refresh(&harness.library).await?;
+	assert!(drain_saved_search_events(&mut subscriber).is_empty());
+
+	// Change what the search matches without touching any files
+	let mut model: saved_search::ActiveModel = find_model(&harness, created.id)
+		.await?
+		.expect("saved search row missing")
+		.into();
+	model.definition = Set(serde_json::to_value(definition("invoice"))?);
+	model.update(harness.library.db().conn()).await?;
+
+	// Counts only go stale after file events, not the saved search events refreshes emit
+	counts.observe(&events[0]);
+	counts.refresh_if_stale(&harness.library).await?;
+	assert!(drain_saved_search_events(&mut subscriber).is_empty());
+
+	counts.observe(&Event::ResourceChanged {
+		resource_type: "file".to_string(),
+		resource: serde_json::json!({}),
+		metadata: None,
+	});
+	counts.refresh_if_stale(&harness.library).await?;
+	let events = drain_saved_search_events(&mut subscriber);
+	assert_eq!(events.len(), 1);
+	match &events[0] {
+		Event::ResourceChanged { resource, .. } => {
+			assert_eq!(resource["result_count"], serde_json::json!(0));
+		}
+		other => panic!("Unexpected event {:?}", other),
+	}
+
+	// The recount cleared the stale flag
+	counts.refresh_if_stale(&harness.library).await?;
+	assert!(drain_saved_search_events(&mut subscriber).is_empty());
+
+	harness.shutdown().await?;
+	Ok(())
+}
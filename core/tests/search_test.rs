@@ -25,6 +25,7 @@ use sd_core::{
 				SearchMode, SearchScope, SizeRangeFilter, SortDirection, SortField, SortOptions,
 			},
 			query::FileSearchQuery,
+			syntax::SearchSyntax,
 			IndexType,
 		},
 	},
@@ -360,6 +361,67 @@ async fn test_persistent_search_by_path() -> anyhow::Result<()> {
 	Ok(())
 }
 
+#[tokio::test]
+async fn test_persistent_search_exact_phrase() -> anyhow::Result<()> {
+	// Tests that a quoted phrase from the query syntax only matches as written
+	let harness = IndexingHarnessBuilder::new("persistent_search_phrase")
+		.disable_watcher()
+		.build()
+		.await?;
+
+	let test_location = harness.create_test_location("test_search").await?;
+
+	test_location.write_file("annual report.txt", "A").await?;
+	test_location.write_file("report annual.txt", "B").await?;
+	test_location
+		.write_file("annual sales report.txt", "C")
+		.await?;
+	test_location.write_file("annual reports.txt", "D").await?;
+
+	let location = test_location
+		.index("Test Location", IndexMode::Shallow)
+		.await?;
+
+	tokio::time::sleep(Duration::from_millis(500)).await;
+
+	let syntax = SearchSyntax::parse(r#""Annual Report""#)?;
+
+	for mode in [SearchMode::Fast, SearchMode::Normal, SearchMode::Full] {
+		let phrase_search = FileSearchInput {
+			query: syntax.query(),
+			scope: SearchScope::Location {
+				location_id: location.uuid,
+			},
+			mode: mode.clone(),
+			filters: syntax.filters(),
+			sort: SortOptions {
+				field: SortField::Relevance,
+				direction: SortDirection::Desc,
+			},
+			pagination: PaginationOptions {
+				limit: 50,
+				offset: 0,
+			},
+		};
+
+		let results = execute_search(&harness, phrase_search).await?;
+		let names: Vec<&str> = results
+			.results
+			.iter()
+			.map(|r| r.file.name.as_str())
+			.collect();
+		assert_eq!(
+			names,
+			vec!["annual report"],
+			"{:?} search should only match the exact phrase",
+			mode
+		);
+	}
+
+	harness.shutdown().await?;
+	Ok(())
+}
+
 // ============================================================================
 // EPHEMERAL SEARCH TESTS (Non-Indexed Directories)
 // ============================================================================
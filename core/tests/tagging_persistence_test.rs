@@ -73,6 +73,7 @@ async fn test_tagging_persists_to_database() {
 		name: Some("Source".to_string()),
 		mode: IndexMode::Deep,
 		job_policies: None,
+		indexer_limits: None,
 	})
 	.unwrap();
 	let _ = action_manager
@@ -1,32 +1,61 @@
 use crate::{
-	primitives::{EncryptedBlock, EncryptedBlockRef, StreamNonce},
+	primitives::{EncryptedBlock, EncryptedBlockRef},
 	Error,
 };
 
-use std::future::Future;
+use std::{future::Future, io::SeekFrom, ops::Range};
 
-// Stream functionality temporarily disabled
-// use aead::{stream::DecryptorLE31, Aead, KeyInit};
-use aead::{Aead, KeyInit};
+use aead::{Aead, KeyInit, Payload};
 use chacha20poly1305::XChaCha20Poly1305;
-use tokio::io::{AsyncRead, AsyncWrite};
-// Unused imports commented out due to stream functionality being disabled
-// use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, BufWriter};
+use tokio::io::{
+	AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, AsyncWrite, AsyncWriteExt, BufReader,
+	BufWriter,
+};
 
-use super::secret_key::SecretKey;
+use super::{
+	header::{read_chunk, Header},
+	secret_key::SecretKey,
+};
 
 pub trait OneShotDecryption {
 	fn decrypt(&self, cipher_text: EncryptedBlockRef<'_>) -> Result<Vec<u8>, Error>;
 	fn decrypt_owned(&self, cipher_text: &EncryptedBlock) -> Result<Vec<u8>, Error>;
 }
 
+/// Decrypt streams written by [`StreamEncryption`](super::StreamEncryption)
+///
+/// The header has to be read first with [`Header::read`], as it may be needed to derive the key.
 pub trait StreamDecryption {
+	/// Decrypt everything after the header, returning the size of the plain text
 	fn decrypt(
 		&self,
-		nonce: &StreamNonce,
+		header: &Header,
 		reader: impl AsyncRead + Unpin + Send,
 		writer: impl AsyncWrite + Unpin + Send,
-	) -> impl Future<Output = Result<(), Error>> + Send;
+	) -> impl Future<Output = Result<u64, Error>> + Send;
+
+	/// Decrypt a byte range of the plain text, only reading the chunks that cover it
+	///
+	/// The reader must be the whole encrypted file, header included. The range is clamped to
+	/// the size of the plain text, and the number of bytes written is returned.
+	fn decrypt_range(
+		&self,
+		header: &Header,
+		reader: impl AsyncRead + AsyncSeek + Unpin + Send,
+		range: Range<u64>,
+		writer: impl AsyncWrite + Unpin + Send,
+	) -> impl Future<Output = Result<u64, Error>> + Send;
+
+	/// Decrypt the chunk at `index` on its own, for readers that fetch the chunks themselves
+	///
+	/// `last` must be set for the final chunk of the file, or decryption fails.
+	fn decrypt_chunk(
+		&self,
+		header: &Header,
+		index: u64,
+		last: bool,
+		cipher_text: &[u8],
+	) -> Result<Vec<u8>, Error>;
 }
 
 impl OneShotDecryption for SecretKey {
@@ -49,66 +78,134 @@ impl OneShotDecryption for SecretKey {
 	}
 }
 
-// Stream decryption temporarily disabled due to aead::stream removal
-/*
 impl StreamDecryption for SecretKey {
 	async fn decrypt(
 		&self,
-		nonce: &StreamNonce,
+		header: &Header,
 		reader: impl AsyncRead + Unpin + Send,
 		writer: impl AsyncWrite + Unpin + Send,
-	) -> Result<(), Error> {
-		let mut reader = BufReader::with_capacity(EncryptedBlock::CIPHER_TEXT_SIZE, reader);
-		let mut writer = BufWriter::with_capacity(EncryptedBlock::PLAIN_TEXT_SIZE * 5, writer);
-
-		let mut buf = Vec::with_capacity(EncryptedBlock::CIPHER_TEXT_SIZE);
-
-		let mut decryptor = DecryptorLE31::from_aead(XChaCha20Poly1305::new(&self.0), nonce);
-
-		loop {
-			match reader.fill_buf().await {
-				Ok([]) => {
-					// Jobs done
-					break;
-				}
-
-				Ok(bytes) => {
-					let total_bytes = bytes.len();
-
-					buf.clear();
-					buf.extend_from_slice(bytes);
-
-					reader.consume(total_bytes);
-
-					if total_bytes == EncryptedBlock::CIPHER_TEXT_SIZE {
-						decryptor
-							.decrypt_next_in_place(b"", &mut buf)
-							.map_err(|aead::Error| Error::Decrypt)?;
-
-						writer.write_all(&buf).await.map_err(|e| Error::DecryptIo {
-							context: "Writing decrypted block to writer",
-							source: e,
-						})?;
-					} else {
-						decryptor
-							.decrypt_last_in_place(b"", &mut buf)
-							.map_err(|aead::Error| Error::Decrypt)?;
-
-						writer.write_all(&buf).await.map_err(|e| Error::DecryptIo {
-							context: "Writing last decrypted block to writer",
-							source: e,
-						})?;
-						break;
-					}
-				}
-
-				Err(e) => {
-					return Err(Error::DecryptIo {
-						context: "Reading a block from the reader",
-						source: e,
-					});
-				}
+	) -> Result<u64, Error> {
+		let cipher = XChaCha20Poly1305::new(&self.0);
+		let aad = header.to_bytes();
+
+		let sealed_len = header.sealed_chunk_len();
+		let mut reader = BufReader::with_capacity(sealed_len, reader);
+		let mut writer = BufWriter::with_capacity(header.chunk_len(), writer);
+
+		let mut current = Vec::with_capacity(sealed_len);
+		let mut next = Vec::with_capacity(sealed_len);
+		let mut written = 0;
+
+		read_chunk(&mut reader, &mut current, sealed_len)
+			.await
+			.map_err(read_error)?;
+
+		for index in 0.. {
+			let last = if current.len() < sealed_len {
+				true
+			} else {
+				read_chunk(&mut reader, &mut next, sealed_len)
+					.await
+					.map_err(read_error)?;
+				next.is_empty()
+			};
+
+			let plain_text = open_chunk(&cipher, header, &aad, index, last, &current)?;
+
+			writer
+				.write_all(&plain_text)
+				.await
+				.map_err(|e| Error::DecryptIo {
+					context: "Writing a decrypted chunk to the writer",
+					source: e,
+				})?;
+			written += u64::try_from(plain_text.len()).expect("chunks are capped at 16 MiB");
+
+			if last {
+				break;
 			}
+
+			std::mem::swap(&mut current, &mut next);
+		}
+
+		writer.flush().await.map_err(|e| Error::DecryptIo {
+			context: "Flushing writer",
+			source: e,
+		})?;
+
+		Ok(written)
+	}
+
+	async fn decrypt_range(
+		&self,
+		header: &Header,
+		mut reader: impl AsyncRead + AsyncSeek + Unpin + Send,
+		range: Range<u64>,
+		writer: impl AsyncWrite + Unpin + Send,
+	) -> Result<u64, Error> {
+		let cipher = XChaCha20Poly1305::new(&self.0);
+		let aad = header.to_bytes();
+
+		let cipher_text_size =
+			reader
+				.seek(SeekFrom::End(0))
+				.await
+				.map_err(|e| Error::DecryptIo {
+					context: "Seeking to the end of the reader",
+					source: e,
+				})?;
+		let plain_text_size = header.plain_text_size(cipher_text_size)?;
+
+		let end = range.end.min(plain_text_size);
+		if range.start >= end {
+			return Ok(0);
+		}
+
+		let chunk_size = u64::from(header.chunk_size);
+		let first = range.start / chunk_size;
+		let last = (end - 1) / chunk_size;
+		let last_index = header.chunk_count(plain_text_size) - 1;
+
+		reader
+			.seek(SeekFrom::Start(header.chunk_offset(first)))
+			.await
+			.map_err(|e| Error::DecryptIo {
+				context: "Seeking to the first chunk of the range",
+				source: e,
+			})?;
+
+		let mut writer = BufWriter::with_capacity(header.chunk_len(), writer);
+		let mut buf = Vec::with_capacity(header.sealed_chunk_len());
+
+		for index in first..=last {
+			buf.resize(header.sealed_len(index, plain_text_size), 0);
+			reader
+				.read_exact(&mut buf)
+				.await
+				.map_err(|e| Error::DecryptIo {
+					context: "Reading a chunk of the range",
+					source: e,
+				})?;
+
+			let plain_text = open_chunk(&cipher, header, &aad, index, index == last_index, &buf)?;
+
+			// Only the first and last chunks may need trimming
+			let chunk_start = index * chunk_size;
+			let from = range.start.saturating_sub(chunk_start);
+			let to = (end - chunk_start).min(chunk_size);
+			let slice = usize::try_from(from)
+				.ok()
+				.zip(usize::try_from(to).ok())
+				.and_then(|(from, to)| plain_text.get(from..to))
+				.ok_or(Error::InvalidSize(cipher_text_size))?;
+
+			writer
+				.write_all(slice)
+				.await
+				.map_err(|e| Error::DecryptIo {
+					context: "Writing a decrypted range to the writer",
+					source: e,
+				})?;
 		}
 
 		writer.flush().await.map_err(|e| Error::DecryptIo {
@@ -116,7 +213,222 @@ impl StreamDecryption for SecretKey {
 			source: e,
 		})?;
 
-		Ok(())
+		Ok(end - range.start)
+	}
+
+	fn decrypt_chunk(
+		&self,
+		header: &Header,
+		index: u64,
+		last: bool,
+		cipher_text: &[u8],
+	) -> Result<Vec<u8>, Error> {
+		open_chunk(
+			&XChaCha20Poly1305::new(&self.0),
+			header,
+			&header.to_bytes(),
+			index,
+			last,
+			cipher_text,
+		)
+	}
+}
+
+fn open_chunk(
+	cipher: &XChaCha20Poly1305,
+	header: &Header,
+	aad: &[u8],
+	index: u64,
+	last: bool,
+	cipher_text: &[u8],
+) -> Result<Vec<u8>, Error> {
+	cipher
+		.decrypt(
+			&header.chunk_nonce(index, last)?,
+			Payload {
+				msg: cipher_text,
+				aad,
+			},
+		)
+		.map_err(|aead::Error| Error::Decrypt)
+}
+
+const fn read_error(e: std::io::Error) -> Error {
+	Error::DecryptIo {
+		context: "Reading a chunk from the reader",
+		source: e,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::cloud::{header::Kdf, StreamEncryption};
+
+	use std::io::Cursor;
+
+	use aead::array::Array;
+	use futures::TryStreamExt;
+
+	const HEADER: &str = "534443525950540101001000000000000000000000000000000000000000000000000000000000000000404142434445464748494a4b4c4d4e4f505152530000";
+	const MESSAGE: &[u8] = b"Spacedrive stream format test vector";
+	const MESSAGE_CHUNKS: &str = "54b5ce062c9eae881449750b6a60459eb40ebcdf4def6a654a013609ca49b71d1d158435b32006feaa3a7804c7f57ba22d422f5d5f0a173eee59b0f49e4922a35a8ae6e10c358c7be1fa5d6f855127e0eb1f6e11";
+	const EMPTY_CHUNK: &str = "c25c840a20679989df457cd168597b83";
+
+	fn key() -> SecretKey {
+		SecretKey::new(Array::from(std::array::from_fn::<u8, 32, _>(|i| {
+			u8::try_from(i).unwrap()
+		})))
+	}
+
+	fn header() -> Header {
+		Header {
+			algorithm: crate::cloud::Algorithm::XChaCha20Poly1305,
+			kdf: Kdf::None,
+			chunk_size: 16,
+			nonce: std::array::from_fn(|i| 0x40 + u8::try_from(i).unwrap()),
+		}
+	}
+
+	async fn encrypt(key: &SecretKey, header: Header, plain_text: &[u8]) -> Vec<u8> {
+		StreamEncryption::encrypt(key, header, plain_text)
+			.try_concat()
+			.await
+			.unwrap()
+	}
+
+	async fn decrypt(key: &SecretKey, cipher_text: &[u8]) -> Result<Vec<u8>, Error> {
+		let mut reader = cipher_text;
+		let header = Header::read(&mut reader).await?;
+		let mut plain_text = vec![];
+		StreamDecryption::decrypt(key, &header, reader, &mut plain_text).await?;
+		Ok(plain_text)
+	}
+
+	#[tokio::test]
+	async fn matches_test_vectors() {
+		let header = header();
+		assert_eq!(hex::encode(header.to_bytes()), HEADER);
+
+		let cipher_text = encrypt(&key(), header, MESSAGE).await;
+		assert_eq!(
+			hex::encode(&cipher_text),
+			format!("{HEADER}{MESSAGE_CHUNKS}")
+		);
+		assert_eq!(decrypt(&key(), &cipher_text).await.unwrap(), MESSAGE);
+
+		let cipher_text = encrypt(&key(), header, b"").await;
+		assert_eq!(hex::encode(&cipher_text), format!("{HEADER}{EMPTY_CHUNK}"));
+		assert!(decrypt(&key(), &cipher_text).await.unwrap().is_empty());
+	}
+
+	#[tokio::test]
+	async fn round_trips_chunk_boundaries() {
+		let header = header();
+		for size in [1, 15, 16, 17, 32, 33, 100] {
+			let plain_text = (0..size).map(|i: u8| i.wrapping_mul(7)).collect::<Vec<_>>();
+			let cipher_text = encrypt(&key(), header, &plain_text).await;
+
+			assert_eq!(
+				u64::try_from(cipher_text.len()).unwrap(),
+				header.cipher_text_size(u64::from(size))
+			);
+			assert_eq!(decrypt(&key(), &cipher_text).await.unwrap(), plain_text);
+		}
+	}
+
+	#[tokio::test]
+	async fn decrypts_ranges() {
+		let header = header();
+		let plain_text = (0..100).collect::<Vec<u8>>();
+		let cipher_text = encrypt(&key(), header, &plain_text).await;
+
+		for (start, end) in [
+			(0, 100),
+			(0, 1),
+			(15, 17),
+			(16, 32),
+			(40, 95),
+			(99, 100),
+			(90, 200),
+		] {
+			let mut out = vec![];
+			let written = key()
+				.decrypt_range(&header, Cursor::new(&cipher_text), start..end, &mut out)
+				.await
+				.unwrap();
+
+			let expected = &plain_text
+				[usize::try_from(start).unwrap()..usize::try_from(end.min(100)).unwrap()];
+			assert_eq!(out, expected);
+			assert_eq!(written, u64::try_from(expected.len()).unwrap());
+		}
+
+		let mut out = vec![];
+		let written = key()
+			.decrypt_range(&header, Cursor::new(&cipher_text), 100..120, &mut out)
+			.await
+			.unwrap();
+		assert_eq!(written, 0);
+	}
+
+	#[tokio::test]
+	async fn chunks_match_the_stream() {
+		let header = header();
+		let cipher_text = encrypt(&key(), header, MESSAGE).await;
+		let sealed = &cipher_text[Header::SIZE..];
+
+		let chunks = MESSAGE.chunks(16).collect::<Vec<_>>();
+		for (index, chunk) in chunks.iter().enumerate() {
+			let last = index + 1 == chunks.len();
+			let index = u64::try_from(index).unwrap();
+			let offset = usize::try_from(index).unwrap() * 32;
+			let stored = &sealed[offset..(offset + 32).min(sealed.len())];
+
+			assert_eq!(
+				key().encrypt_chunk(&header, index, last, chunk).unwrap(),
+				stored
+			);
+			assert_eq!(
+				key().decrypt_chunk(&header, index, last, stored).unwrap(),
+				*chunk
+			);
+			assert!(matches!(
+				key().decrypt_chunk(&header, index, !last, stored),
+				Err(Error::Decrypt)
+			));
+		}
+	}
+
+	#[tokio::test]
+	async fn rejects_tampering_and_truncation() {
+		let cipher_text = encrypt(&key(), header(), MESSAGE).await;
+
+		let mut flipped = cipher_text.clone();
+		flipped[Header::SIZE + 3] ^= 1;
+		assert!(matches!(
+			decrypt(&key(), &flipped).await,
+			Err(Error::Decrypt)
+		));
+
+		// A larger chunk size in the header must not go unnoticed
+		let mut other_header = cipher_text.clone();
+		other_header[10] = 17;
+		assert!(matches!(
+			decrypt(&key(), &other_header).await,
+			Err(Error::Decrypt)
+		));
+
+		// Dropping the last chunk leaves a full chunk that wasn't sealed as the last one
+		let truncated = &cipher_text[..Header::SIZE + 2 * 32];
+		assert!(matches!(
+			decrypt(&key(), truncated).await,
+			Err(Error::Decrypt)
+		));
+
+		assert!(matches!(
+			decrypt(&key(), &cipher_text[..Header::SIZE]).await,
+			Err(Error::Decrypt)
+		));
 	}
 }
-*/
@@ -1,20 +1,19 @@
 use crate::{
-	primitives::{EncryptedBlock, OneShotNonce, StreamNonce},
+	primitives::{EncryptedBlock, OneShotNonce},
 	Error,
 };
 
-// Stream functionality temporarily disabled
-// use aead::{stream::EncryptorLE31, Aead, KeyInit};
-use aead::{Aead, KeyInit};
-// Unused imports commented out due to stream functionality being disabled
-// use async_stream::stream;
+use aead::{Aead, KeyInit, Payload};
+use async_stream::stream;
 use chacha20poly1305::{Tag, XChaCha20Poly1305, XNonce};
 use futures::Stream;
 use rand::CryptoRng;
 use tokio::io::AsyncRead;
-// use tokio::io::{AsyncBufReadExt, BufReader};
 
-use super::secret_key::SecretKey;
+use super::{
+	header::{read_chunk, Header},
+	secret_key::SecretKey,
+};
 
 pub trait OneShotEncryption {
 	fn encrypt(&self, plaintext: &[u8], rng: &mut impl CryptoRng) -> Result<EncryptedBlock, Error>;
@@ -24,22 +23,26 @@ pub trait OneShotEncryption {
 	}
 }
 
+/// Encrypt a stream in the chunked format described in [`header`](super::header)
 pub trait StreamEncryption {
+	/// The first item is the serialized header, every following one an encrypted chunk
 	fn encrypt(
 		&self,
+		header: Header,
 		reader: impl AsyncRead + Unpin + Send,
-		rng: &mut (impl CryptoRng + Send),
-	) -> (
-		StreamNonce,
-		impl Stream<Item = Result<Vec<u8>, Error>> + Send,
-	);
+	) -> impl Stream<Item = Result<Vec<u8>, Error>> + Send;
 
-	fn cipher_text_size(&self, plain_text_size: usize) -> usize {
-		size_of::<StreamNonce>()
-			+ (plain_text_size / EncryptedBlock::PLAIN_TEXT_SIZE * EncryptedBlock::CIPHER_TEXT_SIZE)
-			+ plain_text_size % EncryptedBlock::PLAIN_TEXT_SIZE
-			+ size_of::<Tag>()
-	}
+	/// Encrypt the chunk at `index` on its own, for writers that split the plain text themselves
+	///
+	/// Every chunk but the last must hold exactly the header's chunk size, and the last one
+	/// must have `last` set, even when it's empty.
+	fn encrypt_chunk(
+		&self,
+		header: &Header,
+		index: u64,
+		last: bool,
+		plain_text: &[u8],
+	) -> Result<Vec<u8>, Error>;
 }
 
 impl OneShotEncryption for SecretKey {
@@ -61,57 +64,93 @@ impl OneShotEncryption for SecretKey {
 	}
 }
 
-// Stream encryption temporarily disabled due to aead::stream removal
-/*
 impl StreamEncryption for SecretKey {
 	fn encrypt(
 		&self,
-		reader: impl AsyncRead + Unpin + Send,
-		rng: &mut (impl CryptoRng + Send),
-	) -> (
-		StreamNonce,
-		impl Stream<Item = Result<Vec<u8>, Error>> + Send,
-	) {
-		let mut nonce = StreamNonce::default();
-		rng.fill_bytes(&mut nonce);
+		header: Header,
+		mut reader: impl AsyncRead + Unpin + Send,
+	) -> impl Stream<Item = Result<Vec<u8>, Error>> + Send {
+		let cipher = XChaCha20Poly1305::new(&self.0);
 
-		(
-			nonce,
-			stream! {
-				let mut reader = BufReader::with_capacity(EncryptedBlock::PLAIN_TEXT_SIZE, reader);
-				let mut encryptor = EncryptorLE31::from_aead(XChaCha20Poly1305::new(&self.0), &nonce);
-
-				loop {
-					match reader.fill_buf().await {
-						Ok([]) => {
-							// Jobs done
-							break;
-						}
-
-						Ok(bytes) => {
-							let total_bytes = bytes.len();
-							if bytes.len() == EncryptedBlock::PLAIN_TEXT_SIZE {
-								let cipher_text = encryptor.encrypt_next(bytes).map_err(|aead::Error| Error::Encrypt)?;
-								assert_eq!(cipher_text.len(), EncryptedBlock::CIPHER_TEXT_SIZE);
-								yield Ok(cipher_text);
-								reader.consume(total_bytes);
-							} else {
-								yield encryptor.encrypt_last(bytes).map_err(|aead::Error| Error::Encrypt);
-								break;
-							}
-						}
-
-						Err(e) => {
-							yield Err(Error::EncryptIo {
-								context: "Reading a block from the reader",
-								source: e,
-							});
-							break;
-						}
+		stream! {
+			let aad = header.to_bytes();
+			yield Ok(aad.to_vec());
+
+			let chunk_len = header.chunk_len();
+			let mut current = Vec::with_capacity(chunk_len);
+			let mut next = Vec::with_capacity(chunk_len);
+
+			if let Err(e) = read_chunk(&mut reader, &mut current, chunk_len).await.map_err(read_error) {
+				yield Err(e);
+				return;
+			}
+
+			for index in 0.. {
+				// A short chunk means we hit the end, a full one needs a peek at what follows
+				let last = if current.len() < chunk_len {
+					true
+				} else {
+					if let Err(e) = read_chunk(&mut reader, &mut next, chunk_len).await.map_err(read_error) {
+						yield Err(e);
+						return;
 					}
+					next.is_empty()
+				};
+
+				let sealed = seal_chunk(&cipher, &header, &aad, index, last, &current);
+
+				let failed = sealed.is_err();
+				yield sealed;
+
+				if last || failed {
+					break;
 				}
+
+				std::mem::swap(&mut current, &mut next);
+			}
+		}
+	}
+
+	fn encrypt_chunk(
+		&self,
+		header: &Header,
+		index: u64,
+		last: bool,
+		plain_text: &[u8],
+	) -> Result<Vec<u8>, Error> {
+		seal_chunk(
+			&XChaCha20Poly1305::new(&self.0),
+			header,
+			&header.to_bytes(),
+			index,
+			last,
+			plain_text,
+		)
+	}
+}
+
+fn seal_chunk(
+	cipher: &XChaCha20Poly1305,
+	header: &Header,
+	aad: &[u8],
+	index: u64,
+	last: bool,
+	plain_text: &[u8],
+) -> Result<Vec<u8>, Error> {
+	cipher
+		.encrypt(
+			&header.chunk_nonce(index, last)?,
+			Payload {
+				msg: plain_text,
+				aad,
 			},
 		)
+		.map_err(|aead::Error| Error::Encrypt)
+}
+
+const fn read_error(e: std::io::Error) -> Error {
+	Error::EncryptIo {
+		context: "Reading a chunk from the reader",
+		source: e,
 	}
 }
-*/
@@ -0,0 +1,487 @@
+//! The header of the streaming encryption format, and the layout math that goes with it.
+//!
+//! An encrypted file is a 64 byte header followed by the encrypted chunks:
+//!
+//! | Offset | Size | Field                                                       |
+//! | ------ | ---- | ----------------------------------------------------------- |
+//! | 0      | 7    | Magic bytes, `SDCRYPT`                                      |
+//! | 7      | 1    | Format version, currently 1                                 |
+//! | 8      | 1    | Algorithm, 1 = `XChaCha20-Poly1305`                         |
+//! | 9      | 1    | Key derivation, 0 = none (raw key), 1 = `Argon2id`          |
+//! | 10     | 4    | Chunk size in bytes of plain text, little endian            |
+//! | 14     | 4    | `Argon2id` memory cost in KiB, little endian, 0 without KDF |
+//! | 18     | 4    | `Argon2id` iterations, little endian, 0 without KDF         |
+//! | 22     | 4    | `Argon2id` parallelism, little endian, 0 without KDF        |
+//! | 26     | 16   | `Argon2id` salt, zeroes without KDF                         |
+//! | 42     | 20   | Stream nonce                                                |
+//! | 62     | 2    | Reserved, zeroes                                            |
+//!
+//! Every chunk holds `chunk size` bytes of plain text, the last one possibly fewer, and is
+//! followed by its 16 byte tag. An empty file still has one empty chunk, so it can't be
+//! confused with a truncated one.
+//!
+//! Chunk nonces follow the STREAM construction: the 20 byte stream nonce, then a 4 byte
+//! little endian counter holding the chunk index, with the high bit set on the last chunk.
+//! This limits a file to 2^31 chunks. The whole header is the associated data of every
+//! chunk, so it can't be altered without decryption failing.
+//!
+//! Since each chunk can be decrypted on its own, any byte range of the plain text can be
+//! read by only fetching the chunks that cover it.
+
+use crate::{primitives::StreamNonce, Error};
+
+use aead::array::Array;
+use chacha20poly1305::{Tag, XNonce};
+use rand::CryptoRng;
+use tokio::io::{self, AsyncRead, AsyncReadExt};
+use zeroize::Zeroizing;
+
+use super::secret_key::SecretKey;
+
+/// The cipher used for the chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+	XChaCha20Poly1305,
+}
+
+impl Algorithm {
+	const fn id(self) -> u8 {
+		match self {
+			Self::XChaCha20Poly1305 => 1,
+		}
+	}
+
+	const fn from_id(id: u8) -> Result<Self, Error> {
+		match id {
+			1 => Ok(Self::XChaCha20Poly1305),
+			_ => Err(Error::UnsupportedAlgorithm(id)),
+		}
+	}
+}
+
+/// How the key of a file was obtained
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+	/// The key was used as is
+	None,
+	/// The key was derived from a password
+	Argon2id(Argon2Params),
+}
+
+/// Parameters to derive a key from a password with `Argon2id`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argon2Params {
+	pub salt: [u8; 16],
+	pub memory_kib: u32,
+	pub iterations: u32,
+	pub parallelism: u32,
+}
+
+impl Argon2Params {
+	/// Costs above what [`Self::generate`] writes are refused, since a crafted header
+	/// could otherwise make decryption allocate gigabytes or spin for hours
+	pub const MAX_MEMORY_KIB: u32 = 64 * 1024;
+	pub const MAX_ITERATIONS: u32 = 3;
+	pub const MAX_PARALLELISM: u32 = 4;
+
+	/// The second recommended option of RFC 9106: 64 MiB of memory, 3 passes, 4 lanes
+	pub fn generate(rng: &mut impl CryptoRng) -> Self {
+		let mut salt = [0u8; 16];
+		rng.fill_bytes(&mut salt);
+
+		Self {
+			salt,
+			memory_kib: Self::MAX_MEMORY_KIB,
+			iterations: Self::MAX_ITERATIONS,
+			parallelism: Self::MAX_PARALLELISM,
+		}
+	}
+
+	/// Check the costs are within the maxima
+	pub const fn validate(&self) -> Result<(), Error> {
+		if self.memory_kib > Self::MAX_MEMORY_KIB {
+			return Err(Error::InvalidHeader("Argon2id memory cost is too high"));
+		}
+		if self.iterations > Self::MAX_ITERATIONS {
+			return Err(Error::InvalidHeader("Argon2id iterations are too high"));
+		}
+		if self.parallelism > Self::MAX_PARALLELISM {
+			return Err(Error::InvalidHeader("Argon2id parallelism is too high"));
+		}
+
+		Ok(())
+	}
+
+	/// Derive the key of a file from its password
+	pub fn derive(&self, password: &[u8]) -> Result<SecretKey, Error> {
+		self.validate()?;
+
+		let params =
+			argon2::Params::new(self.memory_kib, self.iterations, self.parallelism, Some(32))
+				.map_err(Error::KeyDerivation)?;
+
+		let mut key = Zeroizing::new([0u8; 32]);
+		argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+			.hash_password_into(password, &self.salt, &mut *key)
+			.map_err(Error::KeyDerivation)?;
+
+		Ok(SecretKey::new(Array::from(*key)))
+	}
+}
+
+/// Header of a file encrypted with [`StreamEncryption`](super::StreamEncryption)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Header {
+	pub algorithm: Algorithm,
+	pub kdf: Kdf,
+	/// Bytes of plain text per chunk
+	pub chunk_size: u32,
+	pub nonce: StreamNonce,
+}
+
+impl Header {
+	pub const SIZE: usize = 64;
+	pub const VERSION: u8 = 1;
+	pub const DEFAULT_CHUNK_SIZE: u32 = 1_048_576;
+	pub const MAX_CHUNK_SIZE: u32 = 16 * 1_048_576;
+
+	const MAGIC: &'static [u8; 7] = b"SDCRYPT";
+	const MAX_CHUNKS: u64 = 1 << 31;
+
+	/// A header for a new file, with a random nonce and the default chunk size
+	pub fn new(kdf: Kdf, rng: &mut impl CryptoRng) -> Self {
+		let mut nonce = StreamNonce::default();
+		rng.fill_bytes(&mut nonce);
+
+		Self {
+			algorithm: Algorithm::XChaCha20Poly1305,
+			kdf,
+			chunk_size: Self::DEFAULT_CHUNK_SIZE,
+			nonce,
+		}
+	}
+
+	/// Use another chunk size, between 1 byte and [`Self::MAX_CHUNK_SIZE`]
+	pub const fn with_chunk_size(mut self, chunk_size: u32) -> Result<Self, Error> {
+		if chunk_size == 0 || chunk_size > Self::MAX_CHUNK_SIZE {
+			return Err(Error::InvalidChunkSize(chunk_size));
+		}
+
+		self.chunk_size = chunk_size;
+		Ok(self)
+	}
+
+	#[must_use]
+	pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+		let mut bytes = [0u8; Self::SIZE];
+		bytes[..7].copy_from_slice(Self::MAGIC);
+		bytes[7] = Self::VERSION;
+		bytes[8] = self.algorithm.id();
+		bytes[10..14].copy_from_slice(&self.chunk_size.to_le_bytes());
+		bytes[42..62].copy_from_slice(&self.nonce);
+
+		if let Kdf::Argon2id(params) = &self.kdf {
+			bytes[9] = 1;
+			bytes[14..18].copy_from_slice(&params.memory_kib.to_le_bytes());
+			bytes[18..22].copy_from_slice(&params.iterations.to_le_bytes());
+			bytes[22..26].copy_from_slice(&params.parallelism.to_le_bytes());
+			bytes[26..42].copy_from_slice(&params.salt);
+		}
+
+		bytes
+	}
+
+	pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+		let bytes: &[u8; Self::SIZE] = bytes
+			.get(..Self::SIZE)
+			.and_then(|bytes| bytes.try_into().ok())
+			.ok_or(Error::InvalidHeader("too short"))?;
+
+		if &bytes[..7] != Self::MAGIC {
+			return Err(Error::InvalidHeader("not an encrypted file"));
+		}
+		if bytes[7] != Self::VERSION {
+			return Err(Error::UnsupportedVersion(bytes[7]));
+		}
+		if bytes[62..] != [0, 0] {
+			return Err(Error::InvalidHeader("reserved bytes are set"));
+		}
+
+		let u32_at = |offset: usize| {
+			let mut le = [0u8; 4];
+			le.copy_from_slice(&bytes[offset..offset + 4]);
+			u32::from_le_bytes(le)
+		};
+
+		let kdf = match bytes[9] {
+			0 => Kdf::None,
+			1 => {
+				let mut salt = [0u8; 16];
+				salt.copy_from_slice(&bytes[26..42]);
+				let params = Argon2Params {
+					salt,
+					memory_kib: u32_at(14),
+					iterations: u32_at(18),
+					parallelism: u32_at(22),
+				};
+				params.validate()?;
+				Kdf::Argon2id(params)
+			}
+			_ => return Err(Error::InvalidHeader("unknown key derivation")),
+		};
+
+		let mut nonce = StreamNonce::default();
+		nonce.copy_from_slice(&bytes[42..62]);
+
+		Self {
+			algorithm: Algorithm::from_id(bytes[8])?,
+			kdf,
+			chunk_size: Self::DEFAULT_CHUNK_SIZE,
+			nonce,
+		}
+		.with_chunk_size(u32_at(10))
+	}
+
+	/// Read the header at the start of an encrypted file
+	pub async fn read(reader: &mut (impl AsyncRead + Unpin + Send)) -> Result<Self, Error> {
+		let mut bytes = [0u8; Self::SIZE];
+		reader
+			.read_exact(&mut bytes)
+			.await
+			.map_err(|e| Error::DecryptIo {
+				context: "Reading the header",
+				source: e,
+			})?;
+
+		Self::from_bytes(&bytes)
+	}
+
+	/// Size of the encrypted file, header included
+	#[must_use]
+	pub fn cipher_text_size(&self, plain_text_size: u64) -> u64 {
+		Self::size() + plain_text_size + self.chunk_count(plain_text_size) * Self::tag_size()
+	}
+
+	/// Size of the plain text of an encrypted file of `cipher_text_size` bytes
+	pub fn plain_text_size(&self, cipher_text_size: u64) -> Result<u64, Error> {
+		let body = cipher_text_size
+			.checked_sub(Self::size())
+			.ok_or(Error::InvalidSize(cipher_text_size))?;
+		let sealed_chunk_size = self.sealed_chunk_size();
+		let last_chunk = body % sealed_chunk_size;
+
+		if body < Self::tag_size() || (last_chunk > 0 && last_chunk < Self::tag_size()) {
+			return Err(Error::InvalidSize(cipher_text_size));
+		}
+
+		Ok(body - body.div_ceil(sealed_chunk_size) * Self::tag_size())
+	}
+
+	/// Number of chunks of a file with `plain_text_size` bytes
+	#[must_use]
+	pub fn chunk_count(&self, plain_text_size: u64) -> u64 {
+		plain_text_size.div_ceil(u64::from(self.chunk_size)).max(1)
+	}
+
+	/// Offset of a chunk in the encrypted file
+	#[must_use]
+	pub fn chunk_offset(&self, index: u64) -> u64 {
+		Self::size() + index * self.sealed_chunk_size()
+	}
+
+	/// Size of an encrypted chunk, tag included, in a file with `plain_text_size` bytes
+	pub(super) fn sealed_len(&self, index: u64, plain_text_size: u64) -> usize {
+		let chunk_size = u64::from(self.chunk_size);
+		let len = plain_text_size
+			.saturating_sub(index * chunk_size)
+			.min(chunk_size);
+
+		usize::try_from(len).expect("chunks are capped at 16 MiB") + size_of::<Tag>()
+	}
+
+	/// Chunk size as a buffer length
+	pub(super) fn chunk_len(&self) -> usize {
+		usize::try_from(self.chunk_size).expect("chunks are capped at 16 MiB")
+	}
+
+	/// Encrypted chunk size as a buffer length
+	pub(super) fn sealed_chunk_len(&self) -> usize {
+		self.chunk_len() + size_of::<Tag>()
+	}
+
+	/// Nonce of a chunk, following STREAM's LE31 counter
+	pub(super) fn chunk_nonce(&self, index: u64, last: bool) -> Result<XNonce, Error> {
+		if index >= Self::MAX_CHUNKS {
+			return Err(Error::TooManyChunks);
+		}
+
+		let mut position = u32::try_from(index).map_err(|_| Error::TooManyChunks)?;
+		if last {
+			position |= 1 << 31;
+		}
+
+		let mut nonce = XNonce::default();
+		nonce[..20].copy_from_slice(&self.nonce);
+		nonce[20..].copy_from_slice(&position.to_le_bytes());
+		Ok(nonce)
+	}
+
+	fn sealed_chunk_size(&self) -> u64 {
+		u64::from(self.chunk_size) + Self::tag_size()
+	}
+
+	const fn size() -> u64 {
+		64
+	}
+
+	const fn tag_size() -> u64 {
+		16
+	}
+}
+
+/// Fill `buf` with up to `len` bytes, stopping early only at the end of the reader
+pub(super) async fn read_chunk(
+	reader: &mut (impl AsyncRead + Unpin + Send),
+	buf: &mut Vec<u8>,
+	len: usize,
+) -> io::Result<()> {
+	buf.clear();
+
+	while buf.len() < len {
+		let wanted = u64::try_from(len - buf.len()).unwrap_or(u64::MAX);
+		if (&mut *reader).take(wanted).read_buf(buf).await? == 0 {
+			break;
+		}
+	}
+
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn header() -> Header {
+		Header {
+			algorithm: Algorithm::XChaCha20Poly1305,
+			kdf: Kdf::None,
+			chunk_size: 16,
+			nonce: [0x40; 20],
+		}
+	}
+
+	#[test]
+	fn header_round_trip() {
+		let header = Header {
+			kdf: Kdf::Argon2id(Argon2Params {
+				salt: [1; 16],
+				memory_kib: 256,
+				iterations: 1,
+				parallelism: 1,
+			}),
+			..header()
+		};
+
+		assert_eq!(Header::from_bytes(&header.to_bytes()).unwrap(), header);
+	}
+
+	#[test]
+	fn rejects_bad_headers() {
+		let bytes = header().to_bytes();
+
+		let mut wrong_magic = bytes;
+		wrong_magic[0] = b'X';
+		assert!(matches!(
+			Header::from_bytes(&wrong_magic),
+			Err(Error::InvalidHeader(_))
+		));
+
+		let mut future_version = bytes;
+		future_version[7] = 2;
+		assert!(matches!(
+			Header::from_bytes(&future_version),
+			Err(Error::UnsupportedVersion(2))
+		));
+
+		let mut no_chunk_size = bytes;
+		no_chunk_size[10..14].copy_from_slice(&[0; 4]);
+		assert!(matches!(
+			Header::from_bytes(&no_chunk_size),
+			Err(Error::InvalidChunkSize(0))
+		));
+
+		assert!(Header::from_bytes(&bytes[..63]).is_err());
+	}
+
+	#[test]
+	fn rejects_excessive_argon2id_costs() {
+		let params = Argon2Params {
+			salt: [1; 16],
+			memory_kib: Argon2Params::MAX_MEMORY_KIB,
+			iterations: Argon2Params::MAX_ITERATIONS,
+			parallelism: Argon2Params::MAX_PARALLELISM,
+		};
+		let with = |params| {
+			Header {
+				kdf: Kdf::Argon2id(params),
+				..header()
+			}
+			.to_bytes()
+		};
+
+		assert!(Header::from_bytes(&with(params)).is_ok());
+		for params in [
+			Argon2Params {
+				memory_kib: 4 * 1024 * 1024,
+				..params
+			},
+			Argon2Params {
+				iterations: u32::MAX,
+				..params
+			},
+			Argon2Params {
+				parallelism: 64,
+				..params
+			},
+		] {
+			assert!(matches!(
+				Header::from_bytes(&with(params)),
+				Err(Error::InvalidHeader(_))
+			));
+			assert!(params.derive(b"password").is_err());
+		}
+	}
+
+	#[test]
+	fn sizes_round_trip() {
+		let header = header();
+		for size in [0, 1, 15, 16, 17, 48, 1000] {
+			assert_eq!(
+				header
+					.plain_text_size(header.cipher_text_size(size))
+					.unwrap(),
+				size
+			);
+		}
+
+		assert!(header.plain_text_size(63).is_err());
+		assert!(header.plain_text_size(64).is_err());
+		assert!(header.plain_text_size(64 + 32 + 5).is_err());
+	}
+
+	#[test]
+	fn derives_argon2id_keys() {
+		let params = Argon2Params {
+			salt: [1; 16],
+			memory_kib: 256,
+			iterations: 1,
+			parallelism: 1,
+		};
+
+		let key = params.derive(b"correct horse battery staple").unwrap();
+		assert_eq!(
+			hex::encode(key.0),
+			"38b74a587b3b07498c3d16cd5ae3c63973bc392889a403360266634f3555f40f"
+		);
+	}
+}
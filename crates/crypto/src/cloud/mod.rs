@@ -1,10 +1,9 @@
 pub mod decrypt;
 pub mod encrypt;
+pub mod header;
 pub mod secret_key;
 
-pub use decrypt::OneShotDecryption;
-pub use encrypt::OneShotEncryption;
-// Stream functionality temporarily disabled
-// pub use decrypt::StreamDecryption;
-// pub use encrypt::StreamEncryption;
+pub use decrypt::{OneShotDecryption, StreamDecryption};
+pub use encrypt::{OneShotEncryption, StreamEncryption};
+pub use header::{Algorithm, Argon2Params, Header, Kdf};
 pub use secret_key::SecretKey;
@@ -39,6 +39,22 @@ pub enum Error {
 		source: io::Error,
 	},
 
+	/// Streaming format errors
+	#[error("Invalid header: {0}")]
+	InvalidHeader(&'static str),
+	#[error("Unsupported format version: {0}")]
+	UnsupportedVersion(u8),
+	#[error("Unsupported algorithm: {0}")]
+	UnsupportedAlgorithm(u8),
+	#[error("Invalid chunk size: {0}")]
+	InvalidChunkSize(u32),
+	#[error("Invalid encrypted file size: {0}")]
+	InvalidSize(u64),
+	#[error("Too many chunks for a single stream")]
+	TooManyChunks,
+	#[error("Key derivation error: {0}")]
+	KeyDerivation(argon2::Error),
+
 	#[error("hex error: {0}")]
 	Hex(#[from] hex::FromHexError),
 	// EntropySource error temporarily removed due to rand_core version conflicts
@@ -2,14 +2,10 @@
 
 // DO NOT EDIT THIS FILE. IF THESE CONSTANTS CHANGE, THINGS CAN (AND PROBABLY WILL) BREAK
 
-// Stream functionality temporarily disabled due to aead::stream removal in 0.6.0-rc.2
-// use aead::stream::{Nonce, StreamLE31};
 use chacha20poly1305::XNonce;
-// XChaCha20Poly1305 temporarily unused due to stream functionality being disabled
-// use chacha20poly1305::XChaCha20Poly1305;
 
 pub type OneShotNonce = XNonce;
-// Temporary placeholder for StreamNonce until stream functionality is reimplemented
+/// The per-file nonce of the streaming format, the chunk counter makes up the remaining 4 bytes
 pub type StreamNonce = [u8; 20];
 pub use chacha20poly1305::Tag;
 
@@ -77,6 +77,7 @@ impl Default for EventFilters {
 			skip_patterns: Vec::new(),
 			important_dotfiles: vec![
 				".gitignore".to_string(),
+				".spacedriveignore".to_string(),
 				".gitkeep".to_string(),
 				".gitattributes".to_string(),
 				".editorconfig".to_string(),
@@ -231,6 +232,7 @@ mod tests {
 
 		// Should NOT skip important dotfiles
 		assert!(!filters.should_skip(&PathBuf::from("/test/.gitignore")));
+		assert!(!filters.should_skip(&PathBuf::from("/test/.spacedriveignore")));
 		assert!(!filters.should_skip(&PathBuf::from("/test/.env")));
 
 		// Should NOT skip normal files
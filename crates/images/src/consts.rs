@@ -17,6 +17,11 @@ pub const GENERIC_EXTENSIONS: [&str; 17] = [
 ];
 pub const SVG_EXTENSIONS: [&str; 2] = ["svg", "svgz"];
 pub const PDF_EXTENSIONS: [&str; 1] = ["pdf"];
+/// RAW camera images, handled through their embedded preview or a basic demosaic
+pub const RAW_EXTENSIONS: [&str; 23] = [
+	"3fr", "arw", "cr2", "cr3", "crw", "dcr", "dng", "erf", "iiq", "kdc", "mef", "mos", "mrw",
+	"nef", "nrw", "orf", "pef", "raf", "rw2", "rwl", "sr2", "srf", "srw",
+];
 #[cfg(feature = "heif")]
 pub const HEIF_EXTENSIONS: [&str; 8] = [
 	"hif", "heif", "heifs", "heic", "heics", "avif", "avci", "avcs",
@@ -32,6 +37,11 @@ pub const HEIF_EXTENSIONS: [&str; 8] = [
 /// It is 512x512, but if the SVG has a non-1:1 aspect ratio we need to account for that.
 pub const SVG_TARGET_PX: f32 = 262_144f32;
 
+/// The smallest embedded preview, by its longest side, used for a RAW image.
+///
+/// Smaller previews are only used when the sensor data can't be decoded.
+pub const RAW_MIN_PREVIEW_SIDE: u32 = 512;
+
 /// The size that PDF pages are rendered at.
 ///
 /// This is 96DPI at standard A4 printer paper size - the target aspect
@@ -193,6 +203,7 @@ pub fn all_compatible_extensions() -> Vec<String> {
 		.chain(HEIF_EXTENSIONS)
 		.chain(SVG_EXTENSIONS)
 		.chain(PDF_EXTENSIONS)
+		.chain(RAW_EXTENSIONS)
 		.map(String::from)
 		.collect();
 
@@ -201,6 +212,7 @@ pub fn all_compatible_extensions() -> Vec<String> {
 		.into_iter()
 		.chain(SVG_EXTENSIONS)
 		.chain(PDF_EXTENSIONS)
+		.chain(RAW_EXTENSIONS)
 		.map(String::from)
 		.collect();
 
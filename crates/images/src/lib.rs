@@ -36,15 +36,18 @@ mod handler;
 #[cfg(feature = "heif")]
 mod heif;
 mod pdf;
+mod perceptual;
+mod raw;
 mod svg;
 
 use consts::MAXIMUM_FILE_SIZE;
 
 // Re-exports
-pub use consts::{all_compatible_extensions, ConvertibleExtension};
+pub use consts::{all_compatible_extensions, ConvertibleExtension, RAW_EXTENSIONS};
 pub use error::{Error, Result};
 pub use handler::{convert_image, format_image};
 pub use image::DynamicImage;
+pub use perceptual::{dhash, hamming_distance, phash, similarity, HASH_BITS};
 
 pub trait ImageHandler {
 	#[inline]
@@ -0,0 +1,164 @@
+//! Perceptual image hashes for near-duplicate detection
+//!
+//! Unlike content hashes, perceptual hashes of the same picture stay within a few
+//! bits of each other after resizing, recompression or small color changes, so
+//! similarity is measured as the Hamming distance between two hashes.
+
+use image::{imageops::FilterType, DynamicImage, GrayImage};
+use std::f32::consts::PI;
+
+/// Number of bits in every hash produced by this module
+pub const HASH_BITS: u32 = 64;
+
+/// Side of the grayscale image the DCT is computed on
+const PHASH_SIZE: u32 = 32;
+
+/// Side of the low frequency block of DCT coefficients kept in the hash
+const PHASH_BLOCK: usize = 8;
+
+/// Difference hash (dHash)
+///
+/// Compares each pixel with its right neighbour on a 9x8 grayscale thumbnail.
+/// Cheap and robust to scaling and compression, less so to global edits.
+#[must_use]
+pub fn dhash(img: &DynamicImage) -> u64 {
+	let small = img.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+
+	let mut hash = 0u64;
+	for y in 0..8 {
+		for x in 0..8 {
+			hash <<= 1;
+			if small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0] {
+				hash |= 1;
+			}
+		}
+	}
+
+	hash
+}
+
+/// DCT based perceptual hash (pHash)
+///
+/// Keeps the signs of the 8x8 lowest frequencies of a 32x32 grayscale
+/// thumbnail relative to their median, which captures the overall structure
+/// of the picture and survives gamma and contrast changes.
+#[must_use]
+pub fn phash(img: &DynamicImage) -> u64 {
+	let small = img
+		.resize_exact(PHASH_SIZE, PHASH_SIZE, FilterType::Triangle)
+		.to_luma8();
+	let coefficients = low_frequency_dct(&small);
+
+	// The DC coefficient is the average brightness, it would dominate the median
+	let mut ac = coefficients[1..].to_vec();
+	ac.sort_by(f32::total_cmp);
+	let median = (ac[ac.len() / 2 - 1] + ac[ac.len() / 2]) / 2.0;
+
+	coefficients.iter().fold(0u64, |hash, coefficient| {
+		(hash << 1) | u64::from(*coefficient > median)
+	})
+}
+
+/// Number of differing bits between two hashes
+#[must_use]
+pub const fn hamming_distance(a: u64, b: u64) -> u32 {
+	(a ^ b).count_ones()
+}
+
+/// Similarity between two hashes, from 0.0 (opposite) to 1.0 (identical)
+#[must_use]
+#[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+pub fn similarity(a: u64, b: u64) -> f32 {
+	1.0 - hamming_distance(a, b) as f32 / HASH_BITS as f32
+}
+
+/// Top-left `PHASH_BLOCK`² coefficients of the 2D DCT-II, row major
+#[allow(clippy::as_conversions, clippy::cast_precision_loss)]
+fn low_frequency_dct(img: &GrayImage) -> Vec<f32> {
+	let n = PHASH_SIZE as usize;
+
+	// cos((2x + 1) * u * PI / 2n) for every kept frequency u and sample x
+	let cosines: Vec<Vec<f32>> = (0..PHASH_BLOCK)
+		.map(|u| {
+			(0..n)
+				.map(|x| ((2 * x + 1) as f32 * u as f32 * PI / (2 * n) as f32).cos())
+				.collect()
+		})
+		.collect();
+
+	let pixels: Vec<f32> = img.pixels().map(|p| f32::from(p[0])).collect();
+
+	// Separable transform: rows first, then columns
+	let mut rows = vec![0.0f32; n * PHASH_BLOCK];
+	for y in 0..n {
+		for (u, cos_u) in cosines.iter().enumerate() {
+			rows[y * PHASH_BLOCK + u] = (0..n).map(|x| pixels[y * n + x] * cos_u[x]).sum();
+		}
+	}
+
+	let mut coefficients = Vec::with_capacity(PHASH_BLOCK * PHASH_BLOCK);
+	for cos_v in &cosines {
+		for u in 0..PHASH_BLOCK {
+			coefficients.push((0..n).map(|y| rows[y * PHASH_BLOCK + u] * cos_v[y]).sum());
+		}
+	}
+
+	coefficients
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use image::{ImageBuffer, Rgb};
+
+	/// Smooth blobs of light at fixed relative positions, at any resolution
+	#[allow(
+		clippy::cast_precision_loss,
+		clippy::cast_possible_truncation,
+		clippy::cast_sign_loss
+	)]
+	fn picture(size: u32) -> DynamicImage {
+		DynamicImage::ImageRgb8(ImageBuffer::from_fn(size, size, |x, y| {
+			let (u, v) = (x as f32 / size as f32, y as f32 / size as f32);
+			let value = (u * 7.0)
+				.sin()
+				.mul_add((v * 3.0).cos(), (u * v * 11.0).sin());
+			let shade = ((value + 2.0) * 63.0) as u8;
+			Rgb([shade, shade / 2, 255 - shade])
+		}))
+	}
+
+	fn other_picture(size: u32) -> DynamicImage {
+		DynamicImage::ImageRgb8(ImageBuffer::from_fn(size, size, |x, _| {
+			if (x / (size / 8)) % 2 == 0 {
+				Rgb([0, 0, 0])
+			} else {
+				Rgb([255, 255, 255])
+			}
+		}))
+	}
+
+	#[test]
+	fn resized_copies_hash_close() {
+		let original = picture(512);
+		let smaller = picture(128);
+
+		assert!(hamming_distance(phash(&original), phash(&smaller)) <= 6);
+		assert!(hamming_distance(dhash(&original), dhash(&smaller)) <= 6);
+	}
+
+	#[test]
+	fn different_pictures_hash_far_apart() {
+		let a = picture(256);
+		let b = other_picture(256);
+
+		assert!(hamming_distance(phash(&a), phash(&b)) > 16);
+		assert!(hamming_distance(dhash(&a), dhash(&b)) > 16);
+	}
+
+	#[test]
+	fn identical_hashes_are_fully_similar() {
+		assert_eq!(hamming_distance(0b1011, 0b0010), 2);
+		assert!((similarity(42, 42) - 1.0).abs() < f32::EPSILON);
+	}
+}
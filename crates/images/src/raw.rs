@@ -0,0 +1,242 @@
+use crate::{
+	consts::RAW_MIN_PREVIEW_SIDE,
+	error::{Error, Result},
+	ImageHandler,
+};
+use image::{DynamicImage, ImageFormat, RgbImage};
+use std::{cmp::Reverse, path::Path};
+
+/// How many embedded JPEGs are tried before giving up on the previews
+const MAX_PREVIEW_ATTEMPTS: usize = 4;
+
+/// Handler for RAW camera images
+///
+/// Cameras embed a JPEG preview, often full size, in almost every RAW format, which is
+/// what's used when there is one. Otherwise the sensor data is decoded with `rawloader`
+/// and given a basic demosaic. EXIF orientation is left to the caller, like for JPEGs.
+pub struct RawHandler {}
+
+impl ImageHandler for RawHandler {
+	fn handle_image(&self, path: &Path) -> Result<DynamicImage> {
+		let data = self.get_data(path)?;
+
+		let preview = embedded_jpegs(&data)
+			.into_iter()
+			.take(MAX_PREVIEW_ATTEMPTS)
+			.find_map(|jpeg| image::load_from_memory_with_format(jpeg, ImageFormat::Jpeg).ok());
+
+		match preview {
+			Some(preview) if preview.width().max(preview.height()) >= RAW_MIN_PREVIEW_SIDE => {
+				Ok(preview)
+			}
+			// A thumbnail-sized preview is still better than nothing
+			Some(preview) => Ok(demosaic(path).unwrap_or(preview)),
+			None => demosaic(path),
+		}
+	}
+}
+
+/// Baseline and progressive JPEGs embedded in a file, largest first
+fn embedded_jpegs(data: &[u8]) -> Vec<&[u8]> {
+	let mut jpegs = Vec::new();
+	let mut pos = 0;
+
+	while let Some(start) = data[pos..]
+		.windows(3)
+		.position(|window| window == [0xFF, 0xD8, 0xFF])
+		.map(|offset| pos + offset)
+	{
+		pos = jpeg_length(&data[start..]).map_or(start + 3, |length| {
+			jpegs.push(&data[start..start + length]);
+			start + length
+		});
+	}
+
+	jpegs.sort_by_key(|jpeg| Reverse(jpeg.len()));
+	jpegs
+}
+
+/// Length of the JPEG at the start of `data`, found by walking its segments
+///
+/// Lossless JPEGs are rejected: RAW formats like CR2 and DNG store the sensor data as
+/// one, which isn't a preview and which the `image` crate can't decode.
+fn jpeg_length(data: &[u8]) -> Option<usize> {
+	// Skip the start of image marker
+	let mut pos = 2;
+	let mut has_frame = false;
+
+	loop {
+		if *data.get(pos)? != 0xFF {
+			return None;
+		}
+		while *data.get(pos)? == 0xFF {
+			pos += 1;
+		}
+		let marker = *data.get(pos)?;
+		pos += 1;
+
+		match marker {
+			// End of image
+			0xD9 => return has_frame.then_some(pos),
+			// Markers without a length
+			0x01 | 0xD0..=0xD7 => continue,
+			// Baseline, extended and progressive frames
+			0xC0..=0xC2 => has_frame = true,
+			// Lossless, hierarchical and arithmetic coded frames, and reserved markers
+			0x00 | 0x02..=0xBF | 0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF => return None,
+			_ => {}
+		}
+
+		let length = usize::from(u16::from_be_bytes([*data.get(pos)?, *data.get(pos + 1)?]));
+		if length < 2 {
+			return None;
+		}
+		pos += length;
+
+		// Start of scan, entropy coded data runs until the next marker
+		if marker == 0xDA {
+			loop {
+				let ff = pos + data.get(pos..)?.iter().position(|byte| *byte == 0xFF)?;
+				match *data.get(ff + 1)? {
+					// Stuffed byte or restart marker, still in the scan
+					0x00 | 0xD0..=0xD7 => pos = ff + 2,
+					_ => {
+						pos = ff;
+						break;
+					}
+				}
+			}
+		}
+	}
+}
+
+/// Decode the sensor data and demosaic it at half resolution
+///
+/// Each 2x2 block of the color filter array becomes one pixel, white balanced with the
+/// camera's coefficients and gamma corrected. Good enough for thumbnails, not for editing.
+#[allow(
+	clippy::cast_possible_truncation,
+	clippy::cast_sign_loss,
+	clippy::as_conversions
+)]
+fn demosaic(path: &Path) -> Result<DynamicImage> {
+	let raw = rawloader::decode_file(path).map_err(|e| Error::RawConversion(e.to_string()))?;
+	let rawloader::RawImageData::Integer(data) = &raw.data else {
+		return Err(Error::RawConversion(
+			"floating point sensor data is unsupported".to_string(),
+		));
+	};
+
+	let [top, right, bottom, left] = raw.crops;
+	let width = raw.width.saturating_sub(left + right);
+	let height = raw.height.saturating_sub(top + bottom);
+
+	let level = |value: u16, color: usize| {
+		let black = f32::from(raw.blacklevels[color]);
+		let white = f32::from(raw.whitelevels[color]).max(black + 1.0);
+		((f32::from(value) - black) / (white - black)).max(0.0)
+	};
+
+	// Coefficients relative to green, cameras that don't report them get none
+	let green = raw.wb_coeffs[1];
+	let white_balance: [f32; 3] = std::array::from_fn(|color| {
+		let coefficient = raw.wb_coeffs[color] / green;
+		if coefficient.is_finite() && coefficient > 0.0 {
+			coefficient
+		} else {
+			1.0
+		}
+	});
+
+	let to_byte = |value: f32| (value.clamp(0.0, 1.0).powf(1.0 / 2.2) * 255.0).round() as u8;
+
+	// Linear DNGs and the like are already demosaiced
+	let is_mosaic = raw.cpp != 3;
+	let (out_width, out_height) = if is_mosaic {
+		(width / 2, height / 2)
+	} else {
+		(width, height)
+	};
+
+	let mut image = RgbImage::new(u32::try_from(out_width)?, u32::try_from(out_height)?);
+
+	for (x, y, out) in image.enumerate_pixels_mut() {
+		let (x, y) = (x as usize, y as usize);
+		let mut rgb = [0f32; 3];
+
+		if is_mosaic {
+			let mut counts = [0u8; 3];
+			for (dy, dx) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+				let (row, col) = (top + y * 2 + dy, left + x * 2 + dx);
+				let color = raw.cfa.color_at(row, col);
+				let value = level(*data.get(row * raw.width + col).unwrap_or(&0), color);
+				// Some sensors have a fourth, emerald, filter counted as green
+				let channel = if color > 2 { 1 } else { color };
+				rgb[channel] += value;
+				counts[channel] += 1;
+			}
+			for (value, count) in rgb.iter_mut().zip(counts) {
+				if count > 0 {
+					*value /= f32::from(count);
+				}
+			}
+		} else {
+			let index = ((top + y) * raw.width + left + x) * 3;
+			for (color, value) in rgb.iter_mut().enumerate() {
+				*value = level(*data.get(index + color).unwrap_or(&0), color);
+			}
+		}
+
+		*out = image::Rgb(std::array::from_fn(|color| {
+			to_byte(rgb[color] * white_balance[color])
+		}));
+	}
+
+	Ok(DynamicImage::ImageRgb8(image))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::io::Cursor;
+
+	fn jpeg(width: u32, height: u32) -> Vec<u8> {
+		let mut bytes = Cursor::new(Vec::new());
+		DynamicImage::ImageRgb8(RgbImage::from_pixel(
+			width,
+			height,
+			image::Rgb([200, 90, 40]),
+		))
+		.write_to(&mut bytes, ImageFormat::Jpeg)
+		.expect("encoding a JPEG");
+		bytes.into_inner()
+	}
+
+	#[test]
+	fn finds_embedded_jpegs_largest_first() {
+		let small = jpeg(16, 16);
+		let large = jpeg(640, 480);
+
+		// A TIFF-like header, sensor noise and a fake lossless JPEG around the previews
+		let mut raw = b"II*\x00\x08\x00\x00\x00".to_vec();
+		raw.extend_from_slice(&small);
+		raw.extend_from_slice(&[0x12, 0xFF, 0xD8, 0xFF, 0x00, 0x34]);
+		raw.extend_from_slice(&[0xFF, 0xD8, 0xFF, 0xC3, 0x00, 0x02, 0xFF, 0xD9]);
+		raw.extend_from_slice(&large);
+		raw.extend_from_slice(&[0x56; 32]);
+
+		let jpegs = embedded_jpegs(&raw);
+		assert_eq!(jpegs, vec![large.as_slice(), small.as_slice()]);
+
+		let preview = image::load_from_memory_with_format(jpegs[0], ImageFormat::Jpeg)
+			.expect("decoding the preview");
+		assert_eq!((preview.width(), preview.height()), (640, 480));
+	}
+
+	#[test]
+	fn rejects_truncated_jpegs() {
+		let full = jpeg(32, 32);
+		assert_eq!(jpeg_length(&full), Some(full.len()));
+		assert_eq!(jpeg_length(&full[..full.len() - 2]), None);
+	}
+}
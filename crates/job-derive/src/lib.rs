@@ -43,6 +43,7 @@ pub fn derive_job(input: TokenStream) -> TokenStream {
 			crate::infra::job::types::JobRegistration {
 				name: <#name as crate::infra::job::traits::Job>::NAME,
 				schema_fn: <#name as crate::infra::job::traits::Job>::schema,
+				requirements_fn: <#name as crate::infra::job::traits::Job>::requirements,
 				create_fn: |data| {
 					let job: #name = serde_json::from_value(data)?;
 					Ok(Box::new(job))
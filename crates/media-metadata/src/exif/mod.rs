@@ -11,6 +11,7 @@ mod flash;
 mod geographic;
 mod orientation;
 mod profile;
+mod raw;
 mod reader;
 mod resolution;
 
@@ -89,6 +90,9 @@ impl ExifMetadata {
 				color_profile: ColorProfile::from_reader(reader),
 				focal_length: reader.get_tag(Tag::FocalLength),
 				shutter_speed: reader.get_tag(Tag::ShutterSpeedValue),
+				exposure_time: reader.get_tag_rational(Tag::ExposureTime),
+				aperture: reader.get_tag_rational(Tag::FNumber),
+				exposure_bias: reader.get_tag_rational(Tag::ExposureBiasValue),
 				flash: Flash::from_reader(reader),
 				orientation: Orientation::from_reader(reader).unwrap_or_default(),
 				lens_make: reader.get_tag(Tag::LensMake),
@@ -132,6 +136,12 @@ pub struct CameraData {
 	pub color_profile: Option<ColorProfile>,
 	pub focal_length: Option<f64>,
 	pub shutter_speed: Option<f64>,
+	/// Exposure time in seconds
+	pub exposure_time: Option<f64>,
+	/// F-number
+	pub aperture: Option<f64>,
+	/// Exposure compensation in EV
+	pub exposure_bias: Option<f64>,
 	pub flash: Option<Flash>,
 	pub orientation: Orientation,
 	pub lens_make: Option<String>,
@@ -0,0 +1,176 @@
+//! EXIF of RAW camera images `kamadak-exif` can't read as they are
+//!
+//! Most RAW formats (DNG, CR2, NEF, ARW, PEF...) are TIFF files and are read directly.
+//! The others need help: ORF and RW2 are TIFF with their own header magic, RAF keeps
+//! its EXIF in the embedded JPEG preview, and CR3 is an ISO media file with TIFF blocks
+//! in its `CMT1` (image) and `CMT2` (EXIF) boxes.
+
+use std::{
+	fs::File,
+	io::{Cursor, Read, Seek, SeekFrom},
+	path::Path,
+};
+
+use exif::{Exif, Reader};
+
+const RAF_MAGIC: &[u8] = b"FUJIFILMCCD-RAW ";
+
+/// RAF header bytes up to the offset and length of the JPEG preview
+const RAF_HEADER_LEN: u64 = 92;
+
+/// CR3 files have a `crx ` brand
+const CR3_FTYP: &[u8] = b"ftypcrx ";
+
+/// The `moov` box holding the CR3 metadata comes first, within this many bytes
+const CR3_METADATA_LEN: u64 = 1024 * 1024;
+
+/// Largest file or preview read looking for EXIF
+const MAX_READ_LEN: u64 = 128 * 1024 * 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RawFormat {
+	/// ORF and RW2, TIFF files with another magic number
+	TiffVariant,
+	Raf,
+	Cr3,
+}
+
+impl RawFormat {
+	fn detect(header: &[u8]) -> Option<Self> {
+		match header {
+			[b'I', b'I', b'R', b'O' | b'S', ..]
+			| [b'M', b'M', b'O', b'R', ..]
+			| [b'I', b'I', b'U', 0, ..] => Some(Self::TiffVariant),
+			_ if header.starts_with(RAF_MAGIC) => Some(Self::Raf),
+			_ if header.get(4..12) == Some(CR3_FTYP) => Some(Self::Cr3),
+			_ => None,
+		}
+	}
+}
+
+/// EXIF blocks of a RAW file, `None` if it isn't one of the formats handled here
+pub(super) fn read_path(path: &Path) -> Option<Vec<Exif>> {
+	let mut file = File::open(path).ok()?;
+	let mut header = Vec::new();
+	(&mut file)
+		.take(RAF_HEADER_LEN)
+		.read_to_end(&mut header)
+		.ok()?;
+
+	let format = RawFormat::detect(&header)?;
+	let (start, len) = match format {
+		RawFormat::TiffVariant => (0, MAX_READ_LEN),
+		RawFormat::Cr3 => (0, CR3_METADATA_LEN),
+		RawFormat::Raf => {
+			let (offset, len) = raf_preview(&header)?;
+			(offset, len.min(MAX_READ_LEN))
+		}
+	};
+
+	file.seek(SeekFrom::Start(start)).ok()?;
+	let mut data = Vec::new();
+	file.take(len).read_to_end(&mut data).ok()?;
+
+	match format {
+		RawFormat::TiffVariant => read_tiff_variant(data),
+		RawFormat::Cr3 => read_cr3(&data),
+		RawFormat::Raf => read_jpeg(&data),
+	}
+}
+
+/// EXIF blocks of a RAW file in memory, `None` if it isn't one of the formats handled here
+pub(super) fn read_slice(data: &[u8]) -> Option<Vec<Exif>> {
+	match RawFormat::detect(data)? {
+		RawFormat::TiffVariant => read_tiff_variant(data.to_vec()),
+		RawFormat::Cr3 => read_cr3(data),
+		RawFormat::Raf => {
+			let (offset, len) = raf_preview(data)?;
+			let start = usize::try_from(offset).ok()?;
+			let end = start.checked_add(usize::try_from(len).ok()?)?;
+			read_jpeg(data.get(start..end)?)
+		}
+	}
+}
+
+fn read_tiff_variant(mut data: Vec<u8>) -> Option<Vec<Exif>> {
+	let magic: &[u8] = if data.starts_with(b"II") {
+		&[0x2A, 0x00]
+	} else {
+		&[0x00, 0x2A]
+	};
+	data.get_mut(2..4)?.copy_from_slice(magic);
+
+	Reader::new().read_raw(data).ok().map(|exif| vec![exif])
+}
+
+fn read_jpeg(data: &[u8]) -> Option<Vec<Exif>> {
+	Reader::new()
+		.read_from_container(&mut Cursor::new(data))
+		.ok()
+		.map(|exif| vec![exif])
+}
+
+/// Offset and length of the JPEG preview, big endian after the RAF header
+fn raf_preview(header: &[u8]) -> Option<(u64, u64)> {
+	let offset = u32::from_be_bytes(header.get(84..88)?.try_into().ok()?);
+	let len = u32::from_be_bytes(header.get(88..92)?.try_into().ok()?);
+	Some((u64::from(offset), u64::from(len)))
+}
+
+/// The `CMT1` and `CMT2` blocks, IFD0 first as it's the primary one
+fn read_cr3(data: &[u8]) -> Option<Vec<Exif>> {
+	let blocks = [b"CMT1", b"CMT2"]
+		.into_iter()
+		.filter_map(|name| {
+			let name_at = data.windows(4).position(|window| window == name)?;
+			let size_at = name_at.checked_sub(4)?;
+			let size = u32::from_be_bytes(data.get(size_at..name_at)?.try_into().ok()?);
+			let end = size_at.checked_add(usize::try_from(size).ok()?)?;
+			let tiff = data.get(name_at + 4..end)?;
+			Reader::new().read_raw(tiff.to_vec()).ok()
+		})
+		.collect::<Vec<_>>();
+
+	(!blocks.is_empty()).then_some(blocks)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn detects_raw_formats() {
+		assert_eq!(
+			RawFormat::detect(b"IIRO\x08\x00\x00\x00"),
+			Some(RawFormat::TiffVariant)
+		);
+		assert_eq!(
+			RawFormat::detect(b"IIU\x00\x08\x00\x00\x00"),
+			Some(RawFormat::TiffVariant)
+		);
+		assert_eq!(
+			RawFormat::detect(b"FUJIFILMCCD-RAW 0201"),
+			Some(RawFormat::Raf)
+		);
+		assert_eq!(
+			RawFormat::detect(b"\x00\x00\x00\x18ftypcrx \x00\x00\x00\x01"),
+			Some(RawFormat::Cr3)
+		);
+		// Plain TIFF based RAWs are read directly
+		assert_eq!(RawFormat::detect(b"II*\x00\x08\x00\x00\x00"), None);
+	}
+
+	#[test]
+	fn reads_tiff_with_another_magic() {
+		// Little endian TIFF, with an IFD0 holding a single Orientation (0x0112) of 6
+		let mut orf = b"IIRO\x08\x00\x00\x00\x01\x00".to_vec();
+		orf.extend_from_slice(&[0x12, 0x01, 0x03, 0x00, 0x01, 0x00, 0x00, 0x00]);
+		orf.extend_from_slice(&[0x06, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]);
+
+		let blocks = read_slice(&orf).expect("ORF should be read");
+		let orientation = blocks[0]
+			.get_field(exif::Tag::Orientation, exif::In::PRIMARY)
+			.and_then(|field| field.value.get_uint(0));
+		assert_eq!(orientation, Some(6));
+	}
+}
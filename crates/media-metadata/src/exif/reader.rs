@@ -7,11 +7,18 @@ use std::{
 	str::FromStr,
 };
 
-use exif::{Exif, In, Tag};
+use exif::{Exif, Field, In, Tag, Value};
 use sd_utils::error::FileIOError;
 
+use super::raw;
+
 /// An [`ExifReader`]. This can get exif tags from images (either files or slices).
-pub struct ExifReader(Exif);
+pub struct ExifReader {
+	exif: Exif,
+	/// Further blocks of RAW formats splitting their EXIF up (CR3). They're parsed without
+	/// knowing which IFD they belong to, so their tags are matched by number.
+	extra: Vec<Exif>,
+}
 
 impl ExifReader {
 	pub fn from_path(path: impl AsRef<Path>) -> Result<Self> {
@@ -19,15 +26,51 @@ impl ExifReader {
 			.read_from_container(&mut BufReader::new(
 				File::open(&path).map_err(|e| FileIOError::from_std_io_err(&path, e))?,
 			))
-			.map(Self)
-			.map_err(Into::into)
+			.map(Self::new)
+			.or_else(|e| {
+				raw::read_path(path.as_ref())
+					.and_then(Self::from_blocks)
+					.ok_or_else(|| e.into())
+			})
 	}
 
 	pub fn from_slice(slice: &[u8]) -> Result<Self> {
 		exif::Reader::new()
 			.read_from_container(&mut Cursor::new(slice))
-			.map(Self)
-			.map_err(Into::into)
+			.map(Self::new)
+			.or_else(|e| {
+				raw::read_slice(slice)
+					.and_then(Self::from_blocks)
+					.ok_or_else(|| e.into())
+			})
+	}
+
+	const fn new(exif: Exif) -> Self {
+		Self {
+			exif,
+			extra: Vec::new(),
+		}
+	}
+
+	fn from_blocks(mut blocks: Vec<Exif>) -> Option<Self> {
+		if blocks.is_empty() {
+			return None;
+		}
+		let exif = blocks.remove(0);
+		Some(Self {
+			exif,
+			extra: blocks,
+		})
+	}
+
+	fn get_field(&self, tag: Tag) -> Option<&Field> {
+		self.exif.get_field(tag, In::PRIMARY).or_else(|| {
+			self.extra.iter().find_map(|exif| {
+				exif.fields().find(|field| {
+					field.ifd_num == In::PRIMARY && field.tag.number() == tag.number()
+				})
+			})
+		})
 	}
 
 	/// A helper function which gets the target `Tag` as `T`, provided `T` impls `FromStr`.
@@ -38,7 +81,7 @@ impl ExifReader {
 	where
 		T: FromStr,
 	{
-		self.0.get_field(tag, In::PRIMARY).map(|x| {
+		self.get_field(tag).map(|x| {
 			x.display_value()
 				.to_string()
 				.replace(['\\', '\"'], "")
@@ -48,9 +91,18 @@ impl ExifReader {
 	}
 
 	pub(crate) fn get_tag_int(&self, tag: Tag) -> Option<u32> {
-		self.0
-			.get_field(tag, In::PRIMARY)
+		self.get_field(tag)
 			.map(|x| x.value.get_uint(0))
 			.unwrap_or_default()
 	}
+
+	/// Gets a rational tag as a number, rather than as it's displayed (e.g. `1/250`)
+	pub(crate) fn get_tag_rational(&self, tag: Tag) -> Option<f64> {
+		match &self.get_field(tag)?.value {
+			Value::Rational(values) => values.first().map(exif::Rational::to_f64),
+			Value::SRational(values) => values.first().map(exif::SRational::to_f64),
+			_ => None,
+		}
+		.filter(|value| value.is_finite())
+	}
 }
@@ -6,6 +6,8 @@ import {
 	HardDrive,
 	Tag as TagIcon,
 	Folders,
+	Stack,
+	MagnifyingGlass,
 } from "@phosphor-icons/react";
 import { Location } from "@sd/assets/icons";
 import type {
@@ -61,6 +63,24 @@ export function isTagItem(t: ItemType): t is { Tag: { tag_id: string } } {
 	return typeof t === "object" && "Tag" in t;
 }
 
+export function isSmartTagItem(
+	t: ItemType,
+): t is { SmartTag: { tag_id: string } } {
+	return typeof t === "object" && "SmartTag" in t;
+}
+
+export function isCollectionItem(
+	t: ItemType,
+): t is { Collection: { collection_id: string } } {
+	return typeof t === "object" && "Collection" in t;
+}
+
+export function isSavedSearchItem(
+	t: ItemType,
+): t is { SavedSearch: { saved_search_id: string } } {
+	return typeof t === "object" && "SavedSearch" in t;
+}
+
 export function isPathItem(t: ItemType): t is { Path: { sd_path: SdPath } } {
 	return typeof t === "object" && "Path" in t;
 }
@@ -81,6 +101,10 @@ function getItemIcon(itemType: ItemType): IconData {
 	if (isLocationItem(itemType)) return { type: "image", icon: Location };
 	if (isVolumeItem(itemType)) return { type: "component", icon: HardDrive };
 	if (isTagItem(itemType)) return { type: "component", icon: TagIcon };
+	if (isSmartTagItem(itemType)) return { type: "component", icon: TagIcon };
+	if (isCollectionItem(itemType)) return { type: "component", icon: Stack };
+	if (isSavedSearchItem(itemType))
+		return { type: "component", icon: MagnifyingGlass };
 	if (isPathItem(itemType)) return { type: "image", icon: Location };
 	return { type: "image", icon: Location };
 }
@@ -94,6 +118,9 @@ function getItemLabel(itemType: ItemType, resolvedFile?: File | null): string {
 	if (isLocationItem(itemType)) return itemType.Location.name || "Unnamed Location";
 	if (isVolumeItem(itemType)) return itemType.Volume.name || "Unnamed Volume";
 	if (isTagItem(itemType)) return itemType.Tag.name || "Unnamed Tag";
+	if (isSmartTagItem(itemType)) return "Smart Tag";
+	if (isCollectionItem(itemType)) return "Collection";
+	if (isSavedSearchItem(itemType)) return "Saved Search";
 	if (isPathItem(itemType)) {
 		// Use resolved file name if available, otherwise extract from path
 		if (resolvedFile?.name) return resolvedFile.name;
@@ -146,6 +173,10 @@ function getItemPath(
 		return `/tag/${itemType.Tag.tag_id}`;
 	}
 
+	if (isSmartTagItem(itemType)) {
+		return `/tag/${itemType.SmartTag.tag_id}`;
+	}
+
 	if (isPathItem(itemType)) {
 		// Navigate to explorer with the SD path
 		return `/explorer?path=${encodeURIComponent(JSON.stringify(itemType.Path.sd_path))}`;
@@ -8,7 +8,7 @@ export type Empty = Record<string, never>;
 
 export type ActionContextInfo = { action_type: string; initiated_at: string; initiated_by: string | null; action_input: JsonValue; context: JsonValue };
 
-export type ActiveJobItem = { id: string; name: string; status: JobStatus; progress: number; action_type: string | null; action_context: ActionContextInfo | null };
+export type ActiveJobItem = { id: string; name: string; status: JobStatus; progress: number; action_type: string | null; action_context: ActionContextInfo | null; waiting_reason: WaitReason | null };
 
 export type ActiveJobsInput = Record<string, never>;
 
@@ -170,10 +170,40 @@ export type ApplyToTargets =
  */
 export type AudioMediaData = { uuid: string; duration_seconds: number | null; bit_rate: number | null; sample_rate: number | null; channels: string | null; codec: string | null; title: string | null; artist: string | null; album: string | null; album_artist: string | null; genre: string | null; year: number | null; track_number: number | null; disc_number: number | null; composer: string | null; publisher: string | null; copyright: string | null };
 
+/**
+ * What a bundle carries
+ */
+export type BundleKind = 
+/**
+ * Changes the target device hasn't received yet
+ */
+"changes" | 
+/**
+ * Only watermarks, answering a changes bundle
+ */
+"ack";
+
+/**
+ * What a bundle held
+ */
+export type BundleSummary = { bundle_id: string; kind: BundleKind; source_device: string; target_device: string; 
+/**
+ * Device-owned records created or updated
+ */
+state_records: number; 
+/**
+ * Device-owned records deleted
+ */
+deletions: number; 
+/**
+ * Shared peer log entries
+ */
+shared_changes: number };
+
 /**
  * Cloud service type identifier
  */
-export type CloudServiceType = "s3" | "gdrive" | "dropbox" | "onedrive" | "gcs" | "azblob" | "b2" | "wasabi" | "spaces" | "cloud";
+export type CloudServiceType = "s3" | "gdrive" | "dropbox" | "onedrive" | "gcs" | "azblob" | "b2" | "wasabi" | "spaces" | "sftp" | "webdav" | "cloud";
 
 export type CloudStorageConfig = { type: "S3"; bucket: string; region: string; access_key_id: string; secret_access_key: string; endpoint: string | null } | 
 /**
@@ -191,7 +221,95 @@ export type CloudStorageConfig = { type: "S3"; bucket: string; region: string; a
  * OpenDAL automatically obtains and refreshes access tokens as needed.
  * Only refresh_token is required (not access_token).
  */
-{ type: "Dropbox"; root: string | null; refresh_token: string; client_id: string; client_secret: string } | { type: "AzureBlob"; container: string; endpoint: string | null; account_name: string; account_key: string } | { type: "GoogleCloudStorage"; bucket: string; root: string | null; endpoint: string | null; credential: string };
+{ type: "Dropbox"; root: string | null; refresh_token: string; client_id: string; client_secret: string } | { type: "AzureBlob"; container: string; endpoint: string | null; account_name: string; account_key: string } | { type: "GoogleCloudStorage"; bucket: string; root: string | null; endpoint: string | null; credential: string } | 
+/**
+ * SFTP server, logged into through the system's OpenSSH.
+ * Uses the SSH agent or default identities when key_path is None.
+ */
+{ type: "Sftp"; host: string; port: number | null; username: string; key_path: string | null; root: string | null } | 
+/**
+ * WebDAV server such as Nextcloud.
+ * The endpoint is the DAV URL, e.g. https://cloud.example.com/remote.php/dav/files/alice
+ */
+{ type: "WebDav"; endpoint: string; username: string; password: string; root: string | null };
+
+/**
+ * A hand-curated collection of files
+ */
+export type Collection = { 
+/**
+ * Unique identifier
+ */
+id: string; name: string; description: string | null; 
+/**
+ * Number of files in the collection
+ */
+file_count: number; 
+/**
+ * Timestamps
+ */
+created_at: string; updated_at: string };
+
+export type CollectionAddFilesInput = { collection_id: string; 
+/**
+ * Files to append, in order. Files already in the collection keep their place.
+ */
+file_ids: string[] };
+
+export type CollectionAddFilesOutput = { collection: Collection; 
+/**
+ * Files that weren't in the collection yet
+ */
+added: number };
+
+export type CollectionCreateInput = { name: string; description: string | null };
+
+export type CollectionCreateOutput = { collection: Collection };
+
+export type CollectionDeleteInput = { collection_id: string };
+
+export type CollectionDeleteOutput = { success: boolean };
+
+/**
+ * A file in a collection
+ */
+export type CollectionFile = { 
+/**
+ * Position in the collection, files are listed in ascending order
+ */
+position: number; added_at: string; file: File };
+
+export type CollectionGetOutput = { collection: Collection; 
+/**
+ * Files in collection order
+ */
+files: CollectionFile[] };
+
+export type CollectionGetQueryInput = { collection_id: string };
+
+export type CollectionRemoveFilesInput = { collection_id: string; file_ids: string[] };
+
+export type CollectionRemoveFilesOutput = { collection: Collection; 
+/**
+ * Files that were in the collection
+ */
+removed: number };
+
+export type CollectionRenameInput = { collection_id: string; name: string };
+
+export type CollectionRenameOutput = { collection: Collection };
+
+export type CollectionReorderInput = { collection_id: string; 
+/**
+ * Files in their new order. Files left out keep their relative order after these.
+ */
+file_ids: string[] };
+
+export type CollectionReorderOutput = { collection: Collection };
+
+export type CollectionsListOutput = { collections: Collection[] };
+
+export type CollectionsListQueryInput = null;
 
 /**
  * Operators for combining tag attributes
@@ -219,6 +337,30 @@ export type CompositionOperator =
  */
 export type CompositionRule = { operator: CompositionOperator; operands: string[]; result_attribute: string };
 
+export type ConflictStrategy = 
+/**
+ * Keep the most recently modified version
+ */
+"NewestWins" | 
+/**
+ * Source always wins
+ */
+"SourceWins" | 
+/**
+ * Target always wins
+ */
+"TargetWins" | 
+/**
+ * Create a conflict copy of the source file
+ */
+"CreateConflictFile" | 
+/**
+ * Prompt user for decision
+ */
+"PromptUser";
+
+export type ConflictType = "BothModified" | "DeletedVsModified" | "TypeMismatch";
+
 /**
  * Network connection method for a device
  */
@@ -501,6 +643,10 @@ is_organizational_anchor: boolean | null; privacy_level: PrivacyLevel | null; se
  * Initial attributes
  */
 attributes: { [key in string]: JsonValue } | null; 
+/**
+ * Composition rules, making this a smart tag whose members are computed
+ */
+composition_rules: CompositionRule[] | null; 
 /**
  * Optional: Targets to immediately apply this tag to after creation
  */
@@ -524,6 +670,71 @@ namespace: string | null;
  */
 message: string };
 
+/**
+ * A file type defined by the user
+ */
+export type CustomFileType = { 
+/**
+ * Unique identifier in MIME form, e.g. "application/x-acme-scan"
+ */
+id: string; 
+/**
+ * Human-readable name
+ */
+name: string; 
+/**
+ * File extensions (without dots), may be empty if magic bytes identify the type
+ */
+extensions?: string[]; 
+/**
+ * MIME types, the id is used when none are given
+ */
+mime_types?: string[]; 
+/**
+ * Category for grouping
+ */
+category: ContentKind; 
+/**
+ * Priority against other types sharing an extension (higher = preferred)
+ */
+priority?: number; 
+/**
+ * Magic byte patterns, any of which identifies the type
+ */
+magic_bytes?: CustomMagicBytes[] };
+
+export type CustomFileTypeInfo = { file_type: CustomFileType; source: CustomFileTypeSource };
+
+/**
+ * Where a custom file type is defined
+ */
+export type CustomFileTypeSource = 
+/**
+ * A TOML file in the app data directory, shared by every library
+ */
+"app_data" | 
+/**
+ * The library's settings
+ */
+"library";
+
+/**
+ * Magic bytes of a custom file type
+ */
+export type CustomMagicBytes = { 
+/**
+ * Hex bytes separated by spaces, `??` matches any byte and `00-1F` a range
+ */
+pattern: string; 
+/**
+ * Offset from start of file
+ */
+offset?: number; 
+/**
+ * Priority against other patterns matching the same file (higher = more specific)
+ */
+priority?: number };
+
 /**
  * Data volume metrics snapshot
  */
@@ -1038,7 +1249,15 @@ error_type: string } } | { LibraryStatisticsUpdated: { library_id: string; stati
  * Refresh event - signals that all frontend caches should be invalidated
  * Emitted after major data recalculations (e.g., volume unique_bytes refresh)
  */
-"Refresh" | { ProxyPairingConfirmationRequired: { session_id: string; vouchee_device_name: string; vouchee_device_os: string; voucher_device_name: string; voucher_device_id: string; expires_at: string } } | { ProxyPairingVouchingReady: { session_id: string; vouchee_device_id: string } } | { EntryCreated: { library_id: string; entry_id: string } } | { EntryModified: { library_id: string; entry_id: string } } | { EntryDeleted: { library_id: string; entry_id: string } } | { EntryMoved: { library_id: string; entry_id: string; old_path: string; new_path: string } } | { FsRawChange: { library_id: string; kind: FsRawEventKind } } | { VolumeAdded: Volume } | { VolumeRemoved: { fingerprint: VolumeFingerprint } } | { VolumeUpdated: { fingerprint: VolumeFingerprint; old_info: VolumeInfo; new_info: VolumeInfo } } | { VolumeSpeedTested: { fingerprint: VolumeFingerprint; read_speed_mbps: number; write_speed_mbps: number } } | { VolumeMountChanged: { fingerprint: VolumeFingerprint; is_mounted: boolean } } | { VolumeError: { fingerprint: VolumeFingerprint; error: string } } | { JobQueued: { job_id: string; job_type: string; device_id: string } } | { JobStarted: { job_id: string; job_type: string; device_id: string } } | { JobProgress: { job_id: string; job_type: string; device_id: string; progress: number; message: string | null; generic_progress: GenericProgress | null } } | { JobCompleted: { job_id: string; job_type: string; device_id: string; output: JobOutput } } | { JobFailed: { job_id: string; job_type: string; device_id: string; error: string } } | { JobCancelled: { job_id: string; job_type: string; device_id: string } } | { JobPaused: { job_id: string; device_id: string } } | { JobResumed: { job_id: string; device_id: string } } | { IndexingStarted: { location_id: string } } | { IndexingProgress: { location_id: string; processed: number; total: number | null } } | { IndexingCompleted: { location_id: string; total_files: number; total_dirs: number } } | { IndexingFailed: { location_id: string; error: string } } | { DeviceConnected: { device_id: string; device_name: string } } | { DeviceDisconnected: { device_id: string } } | { SyncStateChanged: { library_id: string; previous_state: string; new_state: string; timestamp: string } } | { SyncActivity: { library_id: string; peer_device_id: string; activity_type: SyncActivityType; model_type: string | null; count: number; timestamp: string } } | { SyncConnectionChanged: { library_id: string; peer_device_id: string; peer_name: string; connected: boolean; timestamp: string } } | { SyncError: { library_id: string; peer_device_id: string | null; error_type: string; message: string; timestamp: string } } | { ResourceChanged: { 
+"Refresh" | { ProxyPairingConfirmationRequired: { session_id: string; vouchee_device_name: string; vouchee_device_os: string; voucher_device_name: string; voucher_device_id: string; expires_at: string } } | { ProxyPairingVouchingReady: { session_id: string; vouchee_device_id: string } } | { EntryCreated: { library_id: string; entry_id: string } } | { EntryModified: { library_id: string; entry_id: string } } | { EntryDeleted: { library_id: string; entry_id: string } } | { EntryMoved: { library_id: string; entry_id: string; old_path: string; new_path: string } } | { FsRawChange: { library_id: string; kind: FsRawEventKind } } | { VolumeAdded: Volume } | { VolumeRemoved: { fingerprint: VolumeFingerprint } } | { VolumeUpdated: { fingerprint: VolumeFingerprint; old_info: VolumeInfo; new_info: VolumeInfo } } | { VolumeSpeedTested: { fingerprint: VolumeFingerprint; read_speed_mbps: number; write_speed_mbps: number } } | { VolumeMountChanged: { fingerprint: VolumeFingerprint; is_mounted: boolean } } | { VolumeError: { fingerprint: VolumeFingerprint; error: string } } | { JobQueued: { job_id: string; job_type: string; device_id: string } } | { JobStarted: { job_id: string; job_type: string; device_id: string } } | { JobProgress: { job_id: string; job_type: string; device_id: string; progress: number; message: string | null; generic_progress: GenericProgress | null } } | { JobCompleted: { job_id: string; job_type: string; device_id: string; output: JobOutput } } | { JobFailed: { job_id: string; job_type: string; device_id: string; error: string } } | { JobCancelled: { job_id: string; job_type: string; device_id: string } } | { JobPaused: { job_id: string; device_id: string } } | { JobResumed: { job_id: string; device_id: string } } | { IndexingStarted: { location_id: string } } | { IndexingProgress: { location_id: string; processed: number; total: number | null } } | { IndexingCompleted: { location_id: string; total_files: number; total_dirs: number } } | { IndexingFailed: { location_id: string; error: string } } | { DeviceConnected: { device_id: string; device_name: string } } | { DeviceDisconnected: { device_id: string } } | { SyncStateChanged: { library_id: string; previous_state: string; new_state: string; timestamp: string } } | { SyncActivity: { library_id: string; peer_device_id: string; activity_type: SyncActivityType; model_type: string | null; count: number; timestamp: string } } | { SyncConnectionChanged: { library_id: string; peer_device_id: string; peer_name: string; connected: boolean; timestamp: string } } | { SyncError: { library_id: string; peer_device_id: string | null; error_type: string; message: string; timestamp: string } } | { FileSyncConflictsDetected: { library_id: string; conduit_id: string; generation: number; 
+/**
+ * Conflicts first seen in this generation
+ */
+detected: number; 
+/**
+ * All conflicts of the conduit still waiting for a decision
+ */
+pending: number } } | { ResourceChanged: { 
 /**
  * Resource type identifier (e.g., "location", "tag", "album")
  */
@@ -1289,6 +1508,36 @@ permanent: boolean;
  */
 recursive: boolean };
 
+/**
+ * Input for decrypting files encrypted with `files.encrypt`
+ */
+export type FileDecryptInput = { 
+/**
+ * `.sdenc` files to decrypt, each one is replaced by the original file
+ */
+targets: SdPathBatch; 
+/**
+ * Password the files were encrypted with
+ */
+password: string };
+
+/**
+ * Input for encrypting files in place
+ */
+export type FileEncryptInput = { 
+/**
+ * Files to encrypt, each one is replaced by `<name>.sdenc`
+ */
+targets: SdPathBatch; 
+/**
+ * Password the encryption key is derived from
+ */
+password: string; 
+/**
+ * Overwrite the original file with random data before removing it
+ */
+secure_erase?: boolean };
+
 /**
  * Types of file operations
  */
@@ -1362,6 +1611,138 @@ available_filters: FilterKind[] };
  */
 export type FileSearchResult = { file: File; score: number; score_breakdown: ScoreBreakdown; highlights: TextHighlight[]; matched_content: string | null };
 
+/**
+ * A sync relationship between two indexed directories
+ */
+export type FileSyncConduit = { id: string; source_path: string; target_path: string; mode: SyncMode; enabled: boolean; 
+/**
+ * Always "manual" for now
+ */
+schedule: string; conflict_strategy: ConflictStrategy; use_index_rules: boolean; parallel_transfers: number; bandwidth_limit_mbps: number | null; 
+/**
+ * Whether a sync of this conduit is running right now
+ */
+syncing: boolean; 
+/**
+ * Number of the last generation started
+ */
+generation: number; last_sync_completed_at: string | null; last_sync_error: string | null; total_syncs: number; files_synced: number; bytes_transferred: number; created_at: string; updated_at: string };
+
+/**
+ * A file changed on both sides of a conduit since its last sync
+ */
+export type FileSyncConflict = { id: string; 
+/**
+ * Generation that detected the conflict
+ */
+generation: number | null; 
+/**
+ * Path relative to the conduit's source and target directories
+ */
+path: string; conflict_type: ConflictType; source: FileSyncConflictSide; target: FileSyncConflictSide; 
+/**
+ * None while waiting for a decision
+ */
+resolution: Resolution | null; detected_at: string; resolved_at: string | null; 
+/**
+ * When a sync carried out the resolution
+ */
+applied_at: string | null };
+
+/**
+ * One side of a conflict as it was when the conflict was detected
+ */
+export type FileSyncConflictSide = { 
+/**
+ * None once the file is no longer indexed
+ */
+entry_id: string | null; path: string | null; size: number; modified_at: string };
+
+export type FileSyncConflictsInput = { conduit_id: string; 
+/**
+ * Also list conflicts that already have a decision
+ */
+include_resolved?: boolean };
+
+export type FileSyncConflictsOutput = { conflicts: FileSyncConflict[] };
+
+export type FileSyncCreateInput = { 
+/**
+ * Indexed directory to sync from
+ */
+source: SdPath; 
+/**
+ * Indexed directory to sync to
+ */
+target: SdPath; mode?: SyncMode; 
+/**
+ * Only "manual" is supported, syncs are started with `file_sync.sync_now`
+ */
+schedule?: string; 
+/**
+ * How conflicts of bidirectional conduits are settled
+ */
+conflict_strategy?: ConflictStrategy; parallel_transfers?: number | null; bandwidth_limit_mbps?: number | null };
+
+export type FileSyncDeleteInput = { conduit_id: string };
+
+export type FileSyncDeleteOutput = { conduit_id: string };
+
+/**
+ * One sync run of a conduit
+ */
+export type FileSyncGeneration = { generation: number; started_at: string; 
+/**
+ * None while the sync is still running
+ */
+completed_at: string | null; files_copied: number; files_deleted: number; conflicts_resolved: number; bytes_transferred: number; errors_encountered: number; verified_at: string | null; 
+/**
+ * "unverified", "waiting_watcher", "waiting_library_sync", "verified" or "failed:<reason>"
+ */
+verification_status: string };
+
+export type FileSyncGenerationsInput = { conduit_id: string; 
+/**
+ * Return at most this many generations, newest first
+ */
+limit?: number | null };
+
+export type FileSyncGenerationsOutput = { generations: FileSyncGeneration[] };
+
+export type FileSyncListInput = Record<string, never>;
+
+export type FileSyncListOutput = { conduits: FileSyncConduit[] };
+
+export type FileSyncNowInput = { conduit_id: string };
+
+export type FileSyncNowOutput = { conduit_id: string; generation: number; 
+/**
+ * Copy, delete and conflict copy jobs dispatched for this sync, empty when nothing changed
+ */
+job_ids: string[] };
+
+export type FileSyncResolveConflictInput = { conflict_id: string; resolution: Resolution };
+
+export type FileSyncSetEnabledInput = { conduit_id: string; 
+/**
+ * Disabled conduits keep their history but refuse to sync
+ */
+enabled: boolean };
+
+export type FileSyncStatusInput = { conduit_id: string };
+
+export type FileSyncStatusOutput = { conduit: FileSyncConduit; 
+/**
+ * Most recent generation, running or finished
+ */
+latest_generation: FileSyncGeneration | null };
+
+export type FileSyncUpdateInput = { conduit_id: string; mode?: SyncMode | null; schedule?: string | null; conflict_strategy?: ConflictStrategy | null; use_index_rules?: boolean | null; parallel_transfers?: number | null; 
+/**
+ * New bandwidth limit, 0 removes the limit
+ */
+bandwidth_limit_mbps?: number | null };
+
 /**
  * Filesystem type
  */
@@ -1415,6 +1796,42 @@ export type FileSystem =
  */
 { Other: string };
 
+export type FileTypeAddInput = { file_type: CustomFileType; 
+/**
+ * Identify files the new type may match again
+ */
+reidentify?: boolean };
+
+export type FileTypeAddOutput = { 
+/**
+ * The definition as stored, with normalized extensions
+ */
+file_type: CustomFileType; 
+/**
+ * Re-identification job, if one was started
+ */
+job: JobReceipt | null };
+
+export type FileTypeListInput = Record<string, never>;
+
+export type FileTypeListOutput = { 
+/**
+ * Custom types from the app data directory and the library, sorted by id
+ */
+file_types: CustomFileTypeInfo[] };
+
+export type FileTypeRemoveInput = { id: string; 
+/**
+ * Identify the files the type matched again
+ */
+reidentify?: boolean };
+
+export type FileTypeRemoveOutput = { id: string; 
+/**
+ * Re-identification job, if one was started
+ */
+job: JobReceipt | null };
+
 /**
  * Indicates which filters are available for a given search type
  */
@@ -1668,7 +2085,15 @@ export type GroupType =
 /**
  * Image metadata extracted from EXIF
  */
-export type ImageMediaData = { uuid: string; width: number; height: number; blurhash: string | null; date_taken: string | null; latitude: number | null; longitude: number | null; camera_make: string | null; camera_model: string | null; lens_model: string | null; focal_length: string | null; aperture: string | null; shutter_speed: string | null; iso: number | null; orientation: number | null; color_space: string | null; color_profile: string | null; bit_depth: string | null; artist: string | null; copyright: string | null; description: string | null };
+export type ImageMediaData = { uuid: string; width: number; height: number; blurhash: string | null; date_taken: string | null; latitude: number | null; longitude: number | null; camera_make: string | null; camera_model: string | null; lens_model: string | null; focal_length: string | null; aperture: string | null; shutter_speed: string | null; 
+/**
+ * Exposure time in seconds
+ */
+exposure_time: string | null; 
+/**
+ * Exposure compensation in EV
+ */
+exposure_bias: string | null; iso: number | null; orientation: number | null; color_space: string | null; color_profile: string | null; bit_depth: string | null; artist: string | null; copyright: string | null; description: string | null };
 
 /**
  * Statistics about what was imported
@@ -1870,6 +2295,30 @@ path: string;
  */
 child_count: number };
 
+/**
+ * Limits a location applies on top of the rule toggles
+ * 
+ * Set through the location add and update actions and stored as a single
+ * indexer rule linked to the location.
+ */
+export type IndexerLimits = { 
+/**
+ * Skip files larger than this many bytes
+ */
+max_file_size: number | null; 
+/**
+ * Skip files not modified within this many days
+ */
+modified_within_days: number | null; 
+/**
+ * Skip files of these content kinds, identified by extension
+ */
+excluded_kinds?: ContentKind[]; 
+/**
+ * Skip entries more than this many levels below the location root
+ */
+max_depth: number | null };
+
 /**
  * Complete snapshot of indexer performance after job completion.
  */
@@ -2022,6 +2471,18 @@ export type ItemType =
  * Tag filter
  */
 { Tag: { tag_id: string } } | 
+/**
+ * Smart tag, listing the entries its composition rules match
+ */
+{ SmartTag: { tag_id: string } } | 
+/**
+ * Hand-curated collection of files
+ */
+{ Collection: { collection_id: string } } | 
+/**
+ * Saved search, listing the files it currently matches
+ */
+{ SavedSearch: { saved_search_id: string } } | 
 /**
  * Any arbitrary path (dragged from explorer)
  */
@@ -2036,13 +2497,13 @@ export type JobCancelOutput = { job_id: string; success: boolean };
  */
 export type JobId = string;
 
-export type JobInfoOutput = { id: string; name: string; status: JobStatus; progress: number; created_at: string; started_at: string | null; completed_at: string | null; error_message: string | null };
+export type JobInfoOutput = { id: string; name: string; status: JobStatus; progress: number; created_at: string; started_at: string | null; completed_at: string | null; error_message: string | null; waiting_reason: WaitReason | null };
 
 export type JobInfoQueryInput = { job_id: string };
 
 export type JobListInput = { status: JobStatus | null };
 
-export type JobListItem = { id: string; name: string; device_id: string; status: JobStatus; progress: number; action_type: string | null; action_context: ActionContextInfo | null; created_at: string; started_at: string | null; completed_at: string | null };
+export type JobListItem = { id: string; name: string; device_id: string; status: JobStatus; progress: number; action_type: string | null; action_context: ActionContextInfo | null; created_at: string; started_at: string | null; completed_at: string | null; waiting_reason: WaitReason | null };
 
 export type JobListOutput = { jobs: JobListItem[] };
 
@@ -2083,6 +2544,10 @@ export type JobOutput =
  * File delete operation output
  */
 { type: "FileDelete"; data: { deleted_count: number; failed_count: number; total_bytes: number } } | 
+/**
+ * File encryption or decryption output
+ */
+{ type: "FileEncryption"; data: { processed_count: number; failed_count: number; total_bytes: number } } | 
 /**
  * Duplicate detection output
  */
@@ -2099,10 +2564,22 @@ export type JobOutput =
  * Speech-to-text transcription output
  */
 { type: "SpeechToText"; data: { total_processed: number; success_count: number; error_count: number } } | 
+/**
+ * Embedding generation output
+ */
+{ type: "EmbeddingGeneration"; data: { total_processed: number; success_count: number; error_count: number } } | 
 /**
  * Gaussian splat generation output
  */
-{ type: "GaussianSplat"; data: { total_processed: number; success_count: number; error_count: number } };
+{ type: "GaussianSplat"; data: { total_processed: number; success_count: number; error_count: number } } | 
+/**
+ * Sidecar garbage collection output
+ */
+{ type: "SidecarGc"; data: { dry_run: boolean; orphaned_count: number; stale_count: number; evicted_count: number; freed_bytes: number; failed_count: number } } | 
+/**
+ * File type re-identification output
+ */
+{ type: "FileTypeReidentify"; data: { checked_count: number; updated_count: number; skipped_count: number } };
 
 export type JobPauseInput = { job_id: string };
 
@@ -2146,6 +2623,41 @@ export type JobResumeInput = { job_id: string };
 
 export type JobResumeOutput = { job_id: string; success: boolean };
 
+/**
+ * A recurring job schedule
+ */
+export type JobSchedule = { id: string; name: string; job_name: string; job_args: JsonValue; trigger: ScheduleTrigger; paused: boolean; catch_up: boolean; created_at: string; next_run_at: string | null; last_run_at: string | null; last_job_id: string | null; last_error: string | null };
+
+export type JobScheduleCreateInput = { 
+/**
+ * Display name of the schedule
+ */
+name: string; 
+/**
+ * Registered job type to dispatch, e.g. "indexer"
+ */
+job_name: string; 
+/**
+ * Job arguments, deserialized into the job like `dispatch_by_name` does
+ */
+job_args?: JsonValue; trigger: ScheduleTrigger; 
+/**
+ * Run once after a restart if runs were missed while the daemon was down
+ */
+catch_up?: boolean };
+
+export type JobScheduleDeleteInput = { schedule_id: string };
+
+export type JobScheduleDeleteOutput = { schedule_id: string };
+
+export type JobScheduleListInput = Record<string, never>;
+
+export type JobScheduleListOutput = { schedules: JobSchedule[] };
+
+export type JobSchedulePauseInput = { schedule_id: string };
+
+export type JobScheduleResumeInput = { schedule_id: string };
+
 /**
  * Statistics from the indexer job
  */
@@ -2405,7 +2917,15 @@ auto_track_external_volumes: boolean;
 /**
  * Indexer settings (rule toggles and related)
  */
-indexer?: IndexerSettings };
+indexer?: IndexerSettings; 
+/**
+ * Disk space limits for regenerable sidecars on this device, none by default
+ */
+sidecar_quotas?: SidecarQuota[]; 
+/**
+ * File types defined by the user for this library
+ */
+custom_file_types?: CustomFileType[] };
 
 /**
  * Library settings output
@@ -2442,7 +2962,11 @@ auto_track_external_volumes: boolean;
 /**
  * Indexer settings
  */
-indexer: IndexerSettingsOutput };
+indexer: IndexerSettingsOutput; 
+/**
+ * Disk space limits for regenerable sidecars
+ */
+sidecar_quotas: SidecarQuota[] };
 
 /**
  * Library statistics
@@ -2698,7 +3222,11 @@ ignore_patterns: string[];
  */
 job_policies?: JobPolicies };
 
-export type LocationAddInput = { path: SdPath; name: string | null; mode: IndexMode; job_policies: JsonValue | null };
+export type LocationAddInput = { path: SdPath; name: string | null; mode: IndexMode; job_policies: JsonValue | null; 
+/**
+ * Size, age, content kind and depth limits applied when indexing
+ */
+indexer_limits?: IndexerLimits | null };
 
 /**
  * Output from location add action dispatch
@@ -2812,7 +3340,11 @@ name: string | null;
 /**
  * Optional job policies to update
  */
-job_policies: JobPolicies | null };
+job_policies: JobPolicies | null; 
+/**
+ * Optional indexer limits, replacing the current ones. Empty limits remove them.
+ */
+indexer_limits?: IndexerLimits | null };
 
 export type LocationUpdateOutput = { 
 /**
@@ -2971,6 +3503,35 @@ export type ModelType =
  */
 "Tesseract";
 
+/**
+ * Verification of one model
+ */
+export type ModelVerification = { model_type: string; 
+/**
+ * Records of the peer held by this device, before repair
+ */
+local_records: number; 
+/**
+ * Records the peer holds
+ */
+remote_records: number; 
+/**
+ * Records that differ or are missing here
+ */
+mismatched: number; 
+/**
+ * Records held here the peer no longer has
+ */
+extra: number; 
+/**
+ * Mismatched records fetched and applied
+ */
+repaired: number; 
+/**
+ * Extra records deleted
+ */
+deleted: number };
+
 /**
  * Mount type classification
  */
@@ -3054,7 +3615,7 @@ export type OperatingSystem = "MacOS" | "Windows" | "Linux" | "IOs" | "Android"
 /**
  * Operation metrics snapshot
  */
-export type OperationSnapshot = { broadcasts_sent: number; state_changes_broadcast: number; shared_changes_broadcast: number; broadcast_batches_sent: number; failed_broadcasts: number; changes_received: number; changes_applied: number; changes_rejected: number; buffer_queue_depth: number; active_backfill_sessions: number; backfill_sessions_completed: number; backfill_pagination_rounds: number; retry_queue_depth: number; retry_attempts: number; retry_successes: number };
+export type OperationSnapshot = { broadcasts_sent: number; state_changes_broadcast: number; shared_changes_broadcast: number; broadcast_batches_sent: number; failed_broadcasts: number; changes_received: number; changes_applied: number; changes_rejected: number; buffer_queue_depth: number; active_backfill_sessions: number; backfill_sessions_completed: number; backfill_pagination_rounds: number; retry_queue_depth: number; retry_attempts: number; retry_successes: number; verification_runs: number; verification_mismatches: number; verification_repairs: number };
 
 /**
  * Pagination information
@@ -3332,6 +3893,23 @@ success: boolean;
  */
 message: string };
 
+/**
+ * How the user decided a conflict
+ */
+export type Resolution = 
+/**
+ * Overwrite the target with the source version
+ */
+"KeepSource" | 
+/**
+ * Overwrite the source with the target version
+ */
+"KeepTarget" | 
+/**
+ * Keep the target version and save the source version next to it as a conflict copy
+ */
+"KeepBoth";
+
 /**
  * Metadata for resource cache updates
  */
@@ -3366,6 +3944,59 @@ export type RiskLevel =
  */
 "high";
 
+/**
+ * A named file search, run on demand or pinned to a space
+ */
+export type SavedSearch = { 
+/**
+ * Unique identifier
+ */
+id: string; name: string; 
+/**
+ * What the search matches
+ */
+definition: SearchDefinition; 
+/**
+ * Number of files matching on this device, absent until counted
+ */
+result_count?: number | null; 
+/**
+ * Timestamps
+ */
+created_at: string; updated_at: string };
+
+export type SavedSearchCreateInput = { name: string; definition: SearchDefinition };
+
+export type SavedSearchCreateOutput = { saved_search: SavedSearch };
+
+export type SavedSearchDeleteInput = { saved_search_id: string };
+
+export type SavedSearchDeleteOutput = { success: boolean };
+
+export type SavedSearchRunOutput = { saved_search: SavedSearch; results: FileSearchOutput };
+
+export type SavedSearchRunQueryInput = { saved_search_id: string; 
+/**
+ * Page of results to return, the first 50 if not provided
+ */
+pagination: PaginationOptions | null };
+
+export type SavedSearchUpdateInput = { saved_search_id: string; 
+/**
+ * New name, unchanged if not provided
+ */
+name: string | null; 
+/**
+ * New definition, unchanged if not provided
+ */
+definition: SearchDefinition | null };
+
+export type SavedSearchUpdateOutput = { saved_search: SavedSearch };
+
+export type SavedSearchesListOutput = { saved_searches: SavedSearch[] };
+
+export type SavedSearchesListQueryInput = null;
+
 /**
  * Current scanning state of a location
  */
@@ -3398,7 +4029,41 @@ progress: number } } |
 /**
  * Detailed breakdown of how the score was calculated
  */
-export type ScoreBreakdown = { temporal_score: number; semantic_score: number | null; metadata_score: number; recency_boost: number; user_preference_boost: number; final_score: number };
+/**
+ * What makes a schedule run
+ */
+export type ScheduleTrigger = 
+/**
+ * Five field cron expression, evaluated in the device's local time
+ */
+{ type: "Cron"; data: { expression: string } } | 
+/**
+ * Fixed delay between runs
+ */
+{ type: "Interval"; data: { seconds: number } } | 
+/**
+ * Run once no jobs are running and the CPU has been quiet for `idle_seconds`,
+ * at most once every `min_interval_seconds`
+ */
+{ type: "OnIdle"; data: { idle_seconds: number; min_interval_seconds: number } };
+
+export type ScoreBreakdown = { temporal_score: number; semantic_score: number | null; metadata_score: number; recency_boost: number; user_preference_boost: number; 
+/**
+ * Weighted BM25 over the file name and extension
+ */
+name_score: number; 
+/**
+ * Weighted BM25 over the containing directory path
+ */
+path_score: number; 
+/**
+ * Weighted BM25 over extracted text (OCR, transcripts, documents)
+ */
+content_score: number; 
+/**
+ * Boost from how recently the file was accessed
+ */
+access_boost: number; final_score: number };
 
 /**
  * A path within the Spacedrive Virtual Distributed File System
@@ -3476,6 +4141,11 @@ format: SidecarFormat } };
  */
 export type SdPathBatch = { paths: SdPath[] };
 
+/**
+ * A `FileSearchInput` without pagination
+ */
+export type SearchDefinition = { query: string; scope?: SearchScope; mode?: SearchMode; filters?: SearchFilters; sort?: SortOptions };
+
 /**
  * Search facets for filtering UI
  */
@@ -3495,13 +4165,41 @@ export type SearchMode =
  */
 "Fast" | 
 /**
- * Normal search with semantic ranking (<100ms)
+ * Normal search with BM25 ranking over names, paths and extracted text (<100ms)
  */
 "Normal" | 
 /**
- * Full search with content analysis (<500ms)
+ * Normal ranking plus text read from local documents (<500ms)
  */
-"Full";
+"Full" | 
+/**
+ * Normal ranking blended with embedding similarity, matching files by meaning
+ */
+"Semantic";
+
+export type SearchParseOutput = { 
+/**
+ * Text to match, with the filters taken out
+ */
+query: string; 
+/**
+ * Filters written in the query, tags resolved to ids
+ */
+filters: SearchFilters; 
+/**
+ * The query in canonical form
+ */
+canonical: string; 
+/**
+ * Why the query can't be used, the other fields are empty when set
+ */
+error: SyntaxError | null };
+
+export type SearchParseQueryInput = { 
+/**
+ * Query in search syntax, as in `kind:image size:>20MB beach`
+ */
+query: string };
 
 /**
  * Defines the scope of the filesystem to search within
@@ -3607,10 +4305,44 @@ export type Sidecar = { id: number; content_uuid: string; kind: string; variant:
  */
 export type SidecarFormat = "webp" | "mp_4" | "json" | "message_pack" | "text" | "ply";
 
+export type SidecarGcInput = { 
+/**
+ * Only report what would be removed
+ */
+dry_run?: boolean };
+
 export type SidecarKind = "thumb" | "thumbstrip" | "proxy" | "embeddings" | "ocr" | "transcript" | "gaussian_splat";
 
+export type SidecarKindUsage = { kind: SidecarKind; count: number; bytes: number; 
+/**
+ * Quota configured for the kind, if any
+ */
+quota: number | null; 
+/**
+ * Largest variants first
+ */
+variants: SidecarVariantUsage[] };
+
+/**
+ * Disk space one kind of sidecar may take up on this device
+ * 
+ * Only regenerable kinds can have a quota. Above it, the least recently used
+ * sidecars of the kind are evicted by the sidecar garbage collector.
+ */
+export type SidecarQuota = { kind: SidecarKind; max_bytes: number };
+
+export type SidecarUsageInput = Record<string, never>;
+
+export type SidecarUsageOutput = { 
+/**
+ * Largest kinds first
+ */
+kinds: SidecarKindUsage[]; total_count: number; total_bytes: number };
+
 export type SidecarVariant = string;
 
+export type SidecarVariantUsage = { variant: string; count: number; bytes: number };
+
 /**
  * Filter for file size in bytes
  */
@@ -3786,6 +4518,11 @@ export type SpacesListOutput = { spaces: Space[] };
 
 export type SpacesListQueryInput = null;
 
+/**
+ * Byte offsets of part of a query, `end` exclusive
+ */
+export type Span = { start: number; end: number };
+
 /**
  * Speech-to-text transcription policy
  */
@@ -3823,6 +4560,50 @@ export type SuggestedLocationsQueryInput = null;
  */
 export type SyncActivityType = { type: "BroadcastSent"; data: { changes: number } } | { type: "ChangesReceived"; data: { changes: number } } | { type: "ChangesApplied"; data: { changes: number } } | { type: "BackfillStarted" } | { type: "BackfillCompleted"; data: { records: number } } | { type: "CatchUpStarted" } | { type: "CatchUpCompleted" };
 
+/**
+ * Input for exporting a sync bundle
+ */
+export type SyncBundleExportInput = { 
+/**
+ * Device the bundle is written for
+ */
+target_device: string; 
+/**
+ * Path where the bundle will be written
+ */
+export_path: string };
+
+/**
+ * Output from sync bundle export action
+ */
+export type SyncBundleExportOutput = { export_path: string; size_bytes: number; summary: BundleSummary };
+
+/**
+ * Input for importing a sync bundle
+ */
+export type SyncBundleImportInput = { 
+/**
+ * Path to the bundle
+ */
+import_path: string; 
+/**
+ * Where to write the ack bundle, next to the imported one by default
+ */
+ack_path: string | null; 
+/**
+ * Import bundles signed by a key the device registry doesn't know for the source device
+ */
+allow_unknown_signer?: boolean };
+
+/**
+ * Output from sync bundle import action
+ */
+export type SyncBundleImportOutput = { summary: BundleSummary; 
+/**
+ * Ack bundle to take back to the source device, none for imported acks
+ */
+ack_path: string | null; ack_size_bytes: number | null };
+
 /**
  * A logged sync event
  */
@@ -3880,6 +4661,14 @@ export type SyncEventType =
  * Peer device disconnected
  */
 "peer_disconnected" | 
+/**
+ * Verification against a peer finished
+ */
+"verification_completed" | 
+/**
+ * Verification found records that differ from the peer's
+ */
+"verification_mismatch" | 
 /**
  * Sync error occurred
  */
@@ -3914,6 +4703,23 @@ performance: PerformanceSnapshot;
  */
 errors: ErrorSnapshot };
 
+/**
+ * Sync mode variants
+ */
+export type SyncMode = 
+/**
+ * One-way sync from source to target with automatic cleanup
+ */
+"Mirror" | 
+/**
+ * Two-way sync with conflict detection
+ */
+"Bidirectional" | 
+/**
+ * Intelligent local storage management (future)
+ */
+"Selective";
+
 export type SyncPartnerInfo = { device_uuid: string; device_name: string; is_paired: boolean };
 
 export type SyncPartnersDebugInfo = { total_devices: number; sync_enabled_devices: number; paired_devices: number; final_sync_partners: number; device_details: DeviceDebugInfo[] };
@@ -3923,6 +4729,25 @@ export type SyncPartnersDebugInfo = { total_devices: number; sync_enabled_device
  */
 export type SyncStateSnapshot = { current_state: DeviceSyncState; state_entered_at: string; uptime_seconds: number; state_history: StateTransition[]; total_time_in_state: ([DeviceSyncState, number])[]; transition_count: ([[DeviceSyncState, DeviceSyncState], number])[] };
 
+/**
+ * Input for verifying synced data against peers
+ */
+export type SyncVerifyInput = { 
+/**
+ * Peer whose records to verify, every connected sync partner if unset
+ */
+peer_id?: string | null };
+
+/**
+ * Output from sync verify action
+ */
+export type SyncVerifyOutput = { reports: VerificationReport[] };
+
+/**
+ * A query that can't be parsed, pointing at the part at fault
+ */
+export type SyntaxError = { message: string; span: Span };
+
 export type SystemInfo = { uptime: number | null; data_directory: string; instance_name: string | null; current_library: string | null };
 
 /**
@@ -4349,7 +5174,11 @@ gitignore?: boolean | null;
 /**
  * Only index images
  */
-only_images?: boolean | null };
+only_images?: boolean | null; 
+/**
+ * Replace the sidecar quotas, an empty list removes them all
+ */
+sidecar_quotas?: SidecarQuota[] | null };
 
 /**
  * Output for update library configuration action
@@ -4403,6 +5232,11 @@ is_on_primary_volume: boolean };
  */
 export type ValidationWarning = { message: string; suggestion: string | null };
 
+/**
+ * Verification of the records a peer owns
+ */
+export type VerificationReport = { peer: string; started_at: string; duration_ms: number; models: ModelVerification[] };
+
 /**
  * Video metadata extracted from FFmpeg
  */
@@ -4532,7 +5366,16 @@ display_name: string | null; is_favorite: boolean; color: string | null; icon: s
  */
 error_message: string | null };
 
-export type VolumeAddCloudInput = { service: CloudServiceType; display_name: string; config: CloudStorageConfig };
+export type VolumeAddCloudInput = { service: CloudServiceType; display_name: string; config: CloudStorageConfig; 
+/**
+ * Encrypt file contents on this device before they're uploaded.
+ * The key stays in this device's key manager and never reaches the service.
+ */
+encrypt?: boolean; 
+/**
+ * Encrypt file and directory names as well, requires encrypt
+ */
+encrypt_names?: boolean };
 
 export type VolumeAddCloudOutput = { fingerprint: VolumeFingerprint; volume_name: string; service: CloudServiceType };
 
@@ -4734,6 +5577,8 @@ export type VouchingSessionInput = { session_id: string };
 export type VouchingSessionOutput = { session: VouchingSession | null };
 
 export type VouchingSessionState = "Pending" | "InProgress" | "Completed";
+
+export type WaitReason = { type: "Dependency"; data: { job_name: string } } | { type: "ConcurrencyLimit"; data: { max_concurrent: number } } | { type: "Resource"; data: { name: string } } | { type: "Memory"; data: { required: number; available: number } } | { type: "DiskSpace"; data: { required: number; available: number } };
 // ===== API Type Unions =====
 
 export type CoreAction =
@@ -4759,16 +5604,36 @@ export type CoreAction =
 ;
 
 export type LibraryAction =
-     { type: 'config.library.update'; input: UpdateLibraryConfigInput; output: UpdateLibraryConfigOutput }
+     { type: 'collections.add_files'; input: CollectionAddFilesInput; output: CollectionAddFilesOutput }
+  |  { type: 'collections.create'; input: CollectionCreateInput; output: CollectionCreateOutput }
+  |  { type: 'collections.delete'; input: CollectionDeleteInput; output: CollectionDeleteOutput }
+  |  { type: 'collections.remove_files'; input: CollectionRemoveFilesInput; output: CollectionRemoveFilesOutput }
+  |  { type: 'collections.rename'; input: CollectionRenameInput; output: CollectionRenameOutput }
+  |  { type: 'collections.reorder'; input: CollectionReorderInput; output: CollectionReorderOutput }
+  |  { type: 'config.library.update'; input: UpdateLibraryConfigInput; output: UpdateLibraryConfigOutput }
   |  { type: 'files.copy'; input: FileCopyInput; output: JobReceipt }
   |  { type: 'files.createFolder'; input: CreateFolderInput; output: CreateFolderOutput }
+  |  { type: 'files.decrypt'; input: FileDecryptInput; output: JobReceipt }
   |  { type: 'files.delete'; input: FileDeleteInput; output: JobReceipt }
+  |  { type: 'files.encrypt'; input: FileEncryptInput; output: JobReceipt }
   |  { type: 'files.rename'; input: FileRenameInput; output: JobReceipt }
+  |  { type: 'file_sync.create'; input: FileSyncCreateInput; output: FileSyncConduit }
+  |  { type: 'file_sync.delete'; input: FileSyncDeleteInput; output: FileSyncDeleteOutput }
+  |  { type: 'file_sync.resolve_conflict'; input: FileSyncResolveConflictInput; output: FileSyncConflict }
+  |  { type: 'file_sync.set_enabled'; input: FileSyncSetEnabledInput; output: FileSyncConduit }
+  |  { type: 'file_sync.sync_now'; input: FileSyncNowInput; output: FileSyncNowOutput }
+  |  { type: 'file_sync.update'; input: FileSyncUpdateInput; output: FileSyncConduit }
+  |  { type: 'filetypes.add'; input: FileTypeAddInput; output: FileTypeAddOutput }
+  |  { type: 'filetypes.remove'; input: FileTypeRemoveInput; output: FileTypeRemoveOutput }
   |  { type: 'indexing.start'; input: IndexInput; output: JobReceipt }
   |  { type: 'indexing.verify'; input: IndexVerifyInput; output: IndexVerifyOutput }
   |  { type: 'jobs.cancel'; input: JobCancelInput; output: JobCancelOutput }
   |  { type: 'jobs.pause'; input: JobPauseInput; output: JobPauseOutput }
   |  { type: 'jobs.resume'; input: JobResumeInput; output: JobResumeOutput }
+  |  { type: 'jobs.schedule.create'; input: JobScheduleCreateInput; output: JobSchedule }
+  |  { type: 'jobs.schedule.delete'; input: JobScheduleDeleteInput; output: JobScheduleDeleteOutput }
+  |  { type: 'jobs.schedule.pause'; input: JobSchedulePauseInput; output: JobSchedule }
+  |  { type: 'jobs.schedule.resume'; input: JobScheduleResumeInput; output: JobSchedule }
   |  { type: 'libraries.export'; input: LibraryExportInput; output: LibraryExportOutput }
   |  { type: 'libraries.rename'; input: LibraryRenameInput; output: LibraryRenameOutput }
   |  { type: 'locations.add'; input: LocationAddInput; output: LocationAddOutput }
@@ -4786,6 +5651,10 @@ export type LibraryAction =
   |  { type: 'media.thumbnail'; input: ThumbnailInput; output: JobReceipt }
   |  { type: 'media.thumbnail.regenerate'; input: RegenerateThumbnailInput; output: RegenerateThumbnailOutput }
   |  { type: 'media.thumbstrip.generate'; input: GenerateThumbstripInput; output: GenerateThumbstripOutput }
+  |  { type: 'search.saved.create'; input: SavedSearchCreateInput; output: SavedSearchCreateOutput }
+  |  { type: 'search.saved.delete'; input: SavedSearchDeleteInput; output: SavedSearchDeleteOutput }
+  |  { type: 'search.saved.update'; input: SavedSearchUpdateInput; output: SavedSearchUpdateOutput }
+  |  { type: 'sidecars.gc'; input: SidecarGcInput; output: JobReceipt }
   |  { type: 'spaces.add_group'; input: AddGroupInput; output: AddGroupOutput }
   |  { type: 'spaces.add_item'; input: AddItemInput; output: AddItemOutput }
   |  { type: 'spaces.create'; input: SpaceCreateInput; output: SpaceCreateOutput }
@@ -4796,6 +5665,9 @@ export type LibraryAction =
   |  { type: 'spaces.reorder_items'; input: ReorderItemsInput; output: ReorderOutput }
   |  { type: 'spaces.update'; input: SpaceUpdateInput; output: SpaceUpdateOutput }
   |  { type: 'spaces.update_group'; input: UpdateGroupInput; output: UpdateGroupOutput }
+  |  { type: 'sync.bundle.export'; input: SyncBundleExportInput; output: SyncBundleExportOutput }
+  |  { type: 'sync.bundle.import'; input: SyncBundleImportInput; output: SyncBundleImportOutput }
+  |  { type: 'sync.verify'; input: SyncVerifyInput; output: SyncVerifyOutput }
   |  { type: 'tags.apply'; input: ApplyTagsInput; output: ApplyTagsOutput }
   |  { type: 'tags.create'; input: CreateTagInput; output: CreateTagOutput }
   |  { type: 'volumes.add_cloud'; input: VolumeAddCloudInput; output: VolumeAddCloudOutput }
@@ -4825,7 +5697,9 @@ export type CoreQuery =
 ;
 
 export type LibraryQuery =
-     { type: 'config.library.get'; input: GetLibraryConfigQueryInput; output: LibrarySettingsOutput }
+     { type: 'collections.get'; input: CollectionGetQueryInput; output: CollectionGetOutput }
+  |  { type: 'collections.list'; input: CollectionsListQueryInput; output: CollectionsListOutput }
+  |  { type: 'config.library.get'; input: GetLibraryConfigQueryInput; output: LibrarySettingsOutput }
   |  { type: 'devices.list'; input: ListLibraryDevicesInput; output: [Device] }
   |  { type: 'files.alternate_instances'; input: AlternateInstancesInput; output: AlternateInstancesOutput }
   |  { type: 'files.by_id'; input: FileByIdQuery; output: File }
@@ -4834,15 +5708,25 @@ export type LibraryQuery =
   |  { type: 'files.directory_listing'; input: DirectoryListingInput; output: DirectoryListingOutput }
   |  { type: 'files.media_listing'; input: MediaListingInput; output: MediaListingOutput }
   |  { type: 'files.unique_to_location'; input: UniqueToLocationInput; output: UniqueToLocationOutput }
+  |  { type: 'file_sync.conflicts'; input: FileSyncConflictsInput; output: FileSyncConflictsOutput }
+  |  { type: 'file_sync.generations'; input: FileSyncGenerationsInput; output: FileSyncGenerationsOutput }
+  |  { type: 'file_sync.list'; input: FileSyncListInput; output: FileSyncListOutput }
+  |  { type: 'file_sync.status'; input: FileSyncStatusInput; output: FileSyncStatusOutput }
+  |  { type: 'filetypes.list'; input: FileTypeListInput; output: FileTypeListOutput }
   |  { type: 'jobs.active'; input: ActiveJobsInput; output: ActiveJobsOutput }
   |  { type: 'jobs.get_copy_metadata'; input: CopyMetadataQueryInput; output: CopyMetadataOutput }
   |  { type: 'jobs.info'; input: JobInfoQueryInput; output: JobInfoOutput }
   |  { type: 'jobs.list'; input: JobListInput; output: JobListOutput }
+  |  { type: 'jobs.schedule.list'; input: JobScheduleListInput; output: JobScheduleListOutput }
   |  { type: 'libraries.info'; input: LibraryInfoQueryInput; output: Library }
   |  { type: 'locations.list'; input: LocationsListQueryInput; output: LocationsListOutput }
   |  { type: 'locations.suggested'; input: SuggestedLocationsQueryInput; output: SuggestedLocationsOutput }
   |  { type: 'locations.validate_path'; input: ValidateLocationPathInput; output: ValidateLocationPathOutput }
   |  { type: 'search.files'; input: FileSearchInput; output: FileSearchOutput }
+  |  { type: 'search.parse'; input: SearchParseQueryInput; output: SearchParseOutput }
+  |  { type: 'search.saved.list'; input: SavedSearchesListQueryInput; output: SavedSearchesListOutput }
+  |  { type: 'search.saved.run'; input: SavedSearchRunQueryInput; output: SavedSearchRunOutput }
+  |  { type: 'sidecars.usage'; input: SidecarUsageInput; output: SidecarUsageOutput }
   |  { type: 'spaces.get'; input: SpaceGetQueryInput; output: SpaceGetOutput }
   |  { type: 'spaces.get_layout'; input: SpaceLayoutQueryInput; output: SpaceLayout }
   |  { type: 'spaces.list'; input: SpacesListQueryInput; output: SpacesListOutput }
@@ -4881,16 +5765,36 @@ export const WIRE_METHODS = {
   },
 
   libraryActions: {
+    'collections.add_files': 'action:collections.add_files.input',
+    'collections.create': 'action:collections.create.input',
+    'collections.delete': 'action:collections.delete.input',
+    'collections.remove_files': 'action:collections.remove_files.input',
+    'collections.rename': 'action:collections.rename.input',
+    'collections.reorder': 'action:collections.reorder.input',
     'config.library.update': 'action:config.library.update.input',
     'files.copy': 'action:files.copy.input',
     'files.createFolder': 'action:files.createFolder.input',
+    'files.decrypt': 'action:files.decrypt.input',
     'files.delete': 'action:files.delete.input',
+    'files.encrypt': 'action:files.encrypt.input',
     'files.rename': 'action:files.rename.input',
+    'file_sync.create': 'action:file_sync.create.input',
+    'file_sync.delete': 'action:file_sync.delete.input',
+    'file_sync.resolve_conflict': 'action:file_sync.resolve_conflict.input',
+    'file_sync.set_enabled': 'action:file_sync.set_enabled.input',
+    'file_sync.sync_now': 'action:file_sync.sync_now.input',
+    'file_sync.update': 'action:file_sync.update.input',
+    'filetypes.add': 'action:filetypes.add.input',
+    'filetypes.remove': 'action:filetypes.remove.input',
     'indexing.start': 'action:indexing.start.input',
     'indexing.verify': 'action:indexing.verify.input',
     'jobs.cancel': 'action:jobs.cancel.input',
     'jobs.pause': 'action:jobs.pause.input',
     'jobs.resume': 'action:jobs.resume.input',
+    'jobs.schedule.create': 'action:jobs.schedule.create.input',
+    'jobs.schedule.delete': 'action:jobs.schedule.delete.input',
+    'jobs.schedule.pause': 'action:jobs.schedule.pause.input',
+    'jobs.schedule.resume': 'action:jobs.schedule.resume.input',
     'libraries.export': 'action:libraries.export.input',
     'libraries.rename': 'action:libraries.rename.input',
     'locations.add': 'action:locations.add.input',
@@ -4908,6 +5812,10 @@ export const WIRE_METHODS = {
     'media.thumbnail': 'action:media.thumbnail.input',
     'media.thumbnail.regenerate': 'action:media.thumbnail.regenerate.input',
     'media.thumbstrip.generate': 'action:media.thumbstrip.generate.input',
+    'search.saved.create': 'action:search.saved.create.input',
+    'search.saved.delete': 'action:search.saved.delete.input',
+    'search.saved.update': 'action:search.saved.update.input',
+    'sidecars.gc': 'action:sidecars.gc.input',
     'spaces.add_group': 'action:spaces.add_group.input',
     'spaces.add_item': 'action:spaces.add_item.input',
     'spaces.create': 'action:spaces.create.input',
@@ -4918,6 +5826,9 @@ export const WIRE_METHODS = {
     'spaces.reorder_items': 'action:spaces.reorder_items.input',
     'spaces.update': 'action:spaces.update.input',
     'spaces.update_group': 'action:spaces.update_group.input',
+    'sync.bundle.export': 'action:sync.bundle.export.input',
+    'sync.bundle.import': 'action:sync.bundle.import.input',
+    'sync.verify': 'action:sync.verify.input',
     'tags.apply': 'action:tags.apply.input',
     'tags.create': 'action:tags.create.input',
     'volumes.add_cloud': 'action:volumes.add_cloud.input',
@@ -4947,6 +5858,8 @@ export const WIRE_METHODS = {
   },
 
   libraryQueries: {
+    'collections.get': 'query:collections.get',
+    'collections.list': 'query:collections.list',
     'config.library.get': 'query:config.library.get',
     'devices.list': 'query:devices.list',
     'files.alternate_instances': 'query:files.alternate_instances',
@@ -4956,15 +5869,25 @@ export const WIRE_METHODS = {
     'files.directory_listing': 'query:files.directory_listing',
     'files.media_listing': 'query:files.media_listing',
     'files.unique_to_location': 'query:files.unique_to_location',
+    'file_sync.conflicts': 'query:file_sync.conflicts',
+    'file_sync.generations': 'query:file_sync.generations',
+    'file_sync.list': 'query:file_sync.list',
+    'file_sync.status': 'query:file_sync.status',
+    'filetypes.list': 'query:filetypes.list',
     'jobs.active': 'query:jobs.active',
     'jobs.get_copy_metadata': 'query:jobs.get_copy_metadata',
     'jobs.info': 'query:jobs.info',
     'jobs.list': 'query:jobs.list',
+    'jobs.schedule.list': 'query:jobs.schedule.list',
     'libraries.info': 'query:libraries.info',
     'locations.list': 'query:locations.list',
     'locations.suggested': 'query:locations.suggested',
     'locations.validate_path': 'query:locations.validate_path',
     'search.files': 'query:search.files',
+    'search.parse': 'query:search.parse',
+    'search.saved.list': 'query:search.saved.list',
+    'search.saved.run': 'query:search.saved.run',
+    'sidecars.usage': 'query:sidecars.usage',
     'spaces.get': 'query:spaces.get',
     'spaces.get_layout': 'query:spaces.get_layout',
     'spaces.list': 'query:spaces.list',
@@ -23,6 +23,8 @@ const cloudProviderIcons: Record<CloudServiceType, string> = {
 	b2: DriveBackBlaze,
 	wasabi: DriveAmazonS3,
 	spaces: DriveAmazonS3,
+	sftp: HDDIcon,
+	webdav: DrivePCloud,
 	cloud: DrivePCloud,
 };
 
@@ -50,6 +52,8 @@ function parseCloudService(mountPoint: string | null): CloudServiceType | null {
 		"b2",
 		"wasabi",
 		"spaces",
+		"sftp",
+		"webdav",
 		"cloud",
 	];
 